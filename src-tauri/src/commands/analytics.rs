@@ -1,20 +1,43 @@
 //! Analytics and export commands
 //!
 //! Implements 07_revenue.md specification
-//! Note: SuperChat amounts are NOT calculated numerically due to different currencies.
-//! Instead, we use tier-based aggregation based on YouTube's color scheme.
-
-use crate::core::{ChatMessage, MessageType};
+//! Note: SuperChat amounts are aggregated by YouTube's color-scheme tiers.
+//! Numeric totals are only computed after converting to the configured base currency.
+
+use crate::commands::config::ConfigState;
+use crate::core::api::{
+    extract_milestone_months_from_badge, parse_purchase_amount, usec_to_datetime,
+};
+use crate::core::currency::CurrencyConverter;
+use crate::core::revenue_goal::{GoalProgress, RevenueGoal};
+use crate::core::trend::TrendAnalyzer;
+use crate::core::{ChatMessage, MessageMetadata, MessageType};
 use crate::errors::CommandError;
 use crate::state::AppState;
-use chrono::Utc;
+use chrono::{DateTime, Local, Offset, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::File;
-use std::io::Write;
-use tauri::State;
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
 use ts_rs::TS;
 
+mod import;
+mod ndjson_export;
+#[cfg(feature = "parquet")]
+mod parquet_export;
+mod recompute;
+
+pub use import::ImportWarning;
+pub use recompute::{
+    AnalyticsRecomputeState, RecomputeProgress, RecomputedAnalytics, submit_full_recompute,
+};
+
+/// 再計算の進捗を通知するイベント名（ペイロードは [`RecomputeProgress`]）
+pub const RECOMPUTE_PROGRESS_EVENT: &str = "analytics:recompute_progress";
+
 /// SuperChat tier based on YouTube color scheme
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, TS)]
 #[serde(rename_all = "lowercase")]
@@ -75,7 +98,42 @@ pub struct RevenueAnalytics {
     pub super_sticker_count: usize,
     pub membership_gains: usize,
     pub hourly_stats: Vec<HourlyStats>,
+    /// SuperChat + SuperSticker が最も多かった時間帯（`HourlyStats::hour`）
+    pub peak_hour: Option<String>,
     pub top_contributors: Vec<ContributorInfo>,
+    /// 基準通貨に換算した金額の合計（換算器を渡した場合のみ）
+    pub total: Option<RevenueTotal>,
+    /// 収益目標の進捗（現在のセッションで目標を設定している場合のみ）
+    pub goal: Option<GoalProgress>,
+}
+
+impl RevenueAnalytics {
+    /// 金額の合計を目標の通貨に換算した進捗
+    ///
+    /// 合計がない・目標が設定されていない・目標の通貨への換算レートがなければ None。
+    pub fn goal_progress(
+        &self,
+        goal: &RevenueGoal,
+        converter: &dyn CurrencyConverter,
+    ) -> Option<GoalProgress> {
+        let total = self.total.as_ref()?;
+        GoalProgress::compute(total.amount, &total.currency, goal, converter)
+    }
+}
+
+/// 基準通貨に換算した SuperChat + SuperSticker の合計 (07_revenue.md)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct RevenueTotal {
+    /// 合計の通貨（ISO 4217 コード）
+    pub currency: String,
+    pub amount: f64,
+    /// 合計に含めた件数
+    pub converted_count: usize,
+    /// レートがなく合計に含めなかった通貨（昇順）
+    pub unconverted_currencies: Vec<String>,
+    /// 金額をパースできず合計に含めなかった件数
+    pub unparsed_count: usize,
 }
 
 /// Contributor information (07_revenue.md)
@@ -103,11 +161,72 @@ pub struct HourlyStats {
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../src/lib/types/generated/")]
 pub struct ExportConfig {
-    pub format: String, // "csv", "json"
+    pub format: String, // "csv", "json", "ndjson", "parquet"（parquet feature 有効時のみ）
     pub include_metadata: bool,
     pub include_system_messages: bool,
     pub max_records: Option<usize>,
     pub sort_order: Option<String>,
+    /// ロール列（is_owner, membership_months）を出力に含めるか
+    #[serde(default)]
+    pub include_role_columns: bool,
+    /// 出力対象の投稿者（チャンネルID）。None は絞り込みなし
+    #[serde(default)]
+    pub author_filter: Option<Vec<String>>,
+    /// 出力対象のメッセージ種別（"text", "superchat" など）。None は絞り込みなし
+    #[serde(default)]
+    pub message_type_filter: Option<Vec<String>>,
+    /// 配信中に削除されたメッセージも出力するか（出力には `is_deleted` 列が加わる）
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+/// 貢献者ランキングの並び順 (07_revenue.md)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub enum LeaderboardSortOrder {
+    /// 通貨ごとに合計金額の降順（順位は通貨ごとに1から振る）
+    #[default]
+    ByAmount,
+    /// 件数の降順（全体で1から順位を振る）
+    ByMessageCount,
+}
+
+/// 貢献者ランキングのエクスポート設定
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct LeaderboardExportConfig {
+    pub format: String, // "csv", "json"
+    #[serde(default)]
+    pub sort_order: LeaderboardSortOrder,
+}
+
+/// 貢献者ランキングの1行（チャンネル×通貨ごとに集計）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub author: String,
+    pub channel_id: String,
+    pub total_amount: f64,
+    /// ISO 4217 通貨コード。金額を解釈できなかった場合は空文字
+    pub currency: String,
+    pub message_count: usize,
+    pub first_contribution: String,
+    pub last_contribution: String,
+}
+
+/// エクスポートファイルから読み込んだセッションのアナリティクス
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ImportedSessionAnalytics {
+    pub session_id: String,
+    pub stream_title: Option<String>,
+    pub message_count: usize,
+    pub unique_viewers: usize,
+    pub analytics: RevenueAnalytics,
+    /// 読み込めずにスキップしたレコード
+    pub warnings: Vec<ImportWarning>,
 }
 
 /// Session statistics for export
@@ -132,7 +251,7 @@ pub struct SessionMetadata {
 }
 
 /// Export message format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExportMessage {
     pub id: String,
     pub timestamp: String,
@@ -145,7 +264,13 @@ pub struct ExportMessage {
     pub is_moderator: bool,
     pub is_member: bool,
     pub is_verified: bool,
+    pub is_owner: bool,
+    /// メンバー継続月数（マイルストーンまたはメンバーバッジから取得）
+    pub membership_months: Option<u32>,
     pub badges: Vec<String>,
+    /// 配信中に削除されたメッセージか（統計の集計からは除く）
+    #[serde(default)]
+    pub is_deleted: bool,
 }
 
 /// Session statistics
@@ -221,108 +346,322 @@ fn determine_tier_from_amount(amount: &str) -> SuperChatTier {
 }
 
 fn parse_amount_value(amount_str: &str) -> Option<f64> {
-    if amount_str.is_empty() {
-        return None;
+    parse_purchase_amount(amount_str).map(|parsed| parsed.amount)
+}
+
+/// メッセージリストからRevenueAnalyticsを計算する純粋関数
+///
+/// 時間別統計はシステムのローカルタイムゾーンで区切る（[`compute_revenue_analytics_in`]）
+pub(crate) fn compute_revenue_analytics(messages: &[ChatMessage]) -> RevenueAnalytics {
+    compute_revenue_analytics_in(messages, &Local)
+}
+
+/// メッセージリストからRevenueAnalyticsを計算する純粋関数（タイムゾーン指定）
+///
+/// SuperChat/SuperSticker/Membershipの集計、貢献者トラッキング、上位10人truncate、
+/// `tz` のローカル時刻での時間別集計を行う
+pub(crate) fn compute_revenue_analytics_in<Tz: TimeZone>(
+    messages: &[ChatMessage],
+    tz: &Tz,
+) -> RevenueAnalytics {
+    let mut tracker = RevenueTracker::new(tz.clone());
+    tracker.rebuild_from(messages);
+    tracker.snapshot()
+}
+
+/// メッセージリストからRevenueAnalyticsを計算し、金額の合計を `base_currency` 建てで添える純粋関数
+pub(crate) fn compute_revenue_analytics_with<Tz: TimeZone>(
+    messages: &[ChatMessage],
+    tz: &Tz,
+    converter: &dyn CurrencyConverter,
+    base_currency: &str,
+) -> RevenueAnalytics {
+    let mut tracker = RevenueTracker::new(tz.clone());
+    tracker.rebuild_from(messages);
+    RevenueAnalytics {
+        total: Some(tracker.total_in(converter, base_currency)),
+        ..tracker.snapshot()
     }
+}
 
-    let clean_amount: String = amount_str
-        .chars()
-        .filter(|c| c.is_ascii_digit() || *c == '.')
-        .collect();
+/// `RevenueAnalytics::top_contributors` に含める人数
+const TOP_CONTRIBUTORS_LIMIT: usize = 10;
 
-    clean_amount.parse::<f64>().ok()
+/// 貢献者ランキングの並び順キー（件数降順 → tier降順 → channel_id昇順）
+type LeaderboardKey = (Reverse<usize>, Reverse<Option<SuperChatTier>>, String);
+
+fn leaderboard_key(info: &ContributorInfo) -> LeaderboardKey {
+    (
+        Reverse(info.super_chat_count),
+        Reverse(info.highest_tier),
+        info.channel_id.clone(),
+    )
 }
 
-/// メッセージリストからRevenueAnalyticsを計算する純粋関数
+/// 収益アナリティクスをメッセージ1件ごとに更新する集計器 (07_revenue.md)
 ///
-/// SuperChat/SuperSticker/Membershipの集計、貢献者トラッキング、上位10人truncateを行う
-pub(crate) fn compute_revenue_analytics(messages: &[ChatMessage]) -> RevenueAnalytics {
-    let mut analytics = RevenueAnalytics::default();
+/// 貢献者は channel_id ごとの累計と、並び順を保った索引（`BTreeSet`）で管理する。
+/// ライブ中のダッシュボード更新でセッション全体を再走査・再ソートせずに上位を取り出せる。
+pub struct RevenueTracker<Tz: TimeZone> {
+    tz: Tz,
+    super_chat_count: usize,
+    super_chat_by_tier: SuperChatTierStats,
+    super_sticker_count: usize,
+    membership_gains: usize,
+    contributors: HashMap<String, ContributorInfo>,
+    leaderboard: BTreeSet<LeaderboardKey>,
+    /// 時間帯の開始時刻（UTC秒）→ 時間別統計
+    hourly: BTreeMap<i64, HourlyStats>,
+    /// 通貨ごとの金額の合計（換算は読み出し時に行う）
+    amounts_by_currency: BTreeMap<String, (f64, usize)>,
+    /// 金額をパースできなかった SuperChat + SuperSticker の件数
+    unparsed_amount_count: usize,
+}
 
-    // 貢献者トラッキング: channel_id -> (display_name, count, highest_tier)
-    let mut contributors: HashMap<String, (String, usize, Option<SuperChatTier>)> = HashMap::new();
+impl<Tz: TimeZone> RevenueTracker<Tz> {
+    /// 時間別統計を `tz` のローカル時刻で区切る空の集計器を作る
+    pub fn new(tz: Tz) -> Self {
+        Self {
+            tz,
+            super_chat_count: 0,
+            super_chat_by_tier: SuperChatTierStats::default(),
+            super_sticker_count: 0,
+            membership_gains: 0,
+            contributors: HashMap::new(),
+            leaderboard: BTreeSet::new(),
+            hourly: BTreeMap::new(),
+            amounts_by_currency: BTreeMap::new(),
+            unparsed_amount_count: 0,
+        }
+    }
 
-    for message in messages {
+    /// メッセージ1件分の集計を反映する
+    pub fn add_message(&mut self, message: &ChatMessage) {
         match &message.message_type {
             MessageType::SuperChat { amount } => {
-                analytics.super_chat_count += 1;
+                self.super_chat_count += 1;
 
                 // 色情報があればそこからtierを判定、なければ金額からフォールバック
-                let tier = if let Some(ref metadata) = message.metadata {
-                    if let Some(ref colors) = metadata.superchat_colors {
-                        determine_tier_from_color(&colors.header_background)
-                    } else {
-                        determine_tier_from_amount(amount)
-                    }
-                } else {
-                    determine_tier_from_amount(amount)
+                let tier = match message
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.superchat_colors.as_ref())
+                {
+                    Some(colors) => determine_tier_from_color(&colors.header_background),
+                    None => determine_tier_from_amount(amount),
                 };
 
-                analytics.super_chat_by_tier.increment(tier);
-
-                // 貢献者情報を更新
-                let entry = contributors.entry(message.channel_id.clone()).or_insert((
-                    message.author.clone(),
-                    0,
-                    None,
-                ));
-                entry.1 += 1;
-                // より高いtierがあれば更新
-                if entry.2.is_none_or(|existing| tier > existing) {
-                    entry.2 = Some(tier);
-                }
+                self.super_chat_by_tier.increment(tier);
+                self.update_contributor(message, Some(tier));
+                self.add_amount(amount);
             }
-            MessageType::SuperSticker { amount: _ } => {
-                analytics.super_sticker_count += 1;
+            MessageType::SuperSticker { amount } => {
+                self.super_sticker_count += 1;
+                self.add_amount(amount);
 
                 // SuperStickerは件数カウントのみ（tier統計には影響しない）
-                let entry = contributors.entry(message.channel_id.clone()).or_insert((
-                    message.author.clone(),
-                    0,
-                    None,
-                ));
-                entry.1 += 1;
+                self.update_contributor(message, None);
             }
             MessageType::Membership { .. } | MessageType::MembershipGift { .. } => {
-                analytics.membership_gains += 1;
+                self.membership_gains += 1;
             }
             _ => {}
         }
+
+        self.add_to_hourly(message);
     }
 
-    // 貢献者リストを件数降順→tier降順でソートし上位10人に絞る
-    let mut contributors_vec: Vec<ContributorInfo> = contributors
-        .into_iter()
-        .map(
-            |(channel_id, (display_name, super_chat_count, highest_tier))| ContributorInfo {
-                channel_id,
-                display_name,
-                super_chat_count,
-                highest_tier,
-            },
-        )
-        .collect();
+    /// 集計をリセットし、`messages` から作り直す（アーカイブ読み込み用）
+    pub fn rebuild_from(&mut self, messages: &[ChatMessage]) {
+        *self = Self::new(self.tz.clone());
+        for message in messages {
+            self.add_message(message);
+        }
+    }
 
-    contributors_vec.sort_by(|a, b| match b.super_chat_count.cmp(&a.super_chat_count) {
-        std::cmp::Ordering::Equal => b.highest_tier.cmp(&a.highest_tier),
-        other => other,
-    });
+    /// 上位 `n` 人の貢献者（件数降順 → tier降順、同順位は channel_id 昇順）
+    pub fn top_contributors(&self, n: usize) -> Vec<ContributorInfo> {
+        self.leaderboard
+            .iter()
+            .take(n)
+            .filter_map(|(_, _, channel_id)| self.contributors.get(channel_id).cloned())
+            .collect()
+    }
+
+    /// 現時点の集計結果（上位10人・時間別統計を含む）
+    pub fn snapshot(&self) -> RevenueAnalytics {
+        let hourly_stats: Vec<HourlyStats> = self.hourly.values().cloned().collect();
+        RevenueAnalytics {
+            super_chat_count: self.super_chat_count,
+            super_chat_by_tier: self.super_chat_by_tier.clone(),
+            super_sticker_count: self.super_sticker_count,
+            membership_gains: self.membership_gains,
+            peak_hour: peak_revenue_hour(&hourly_stats),
+            hourly_stats,
+            top_contributors: self.top_contributors(TOP_CONTRIBUTORS_LIMIT),
+            total: None,
+            goal: None,
+        }
+    }
+
+    /// 金額の合計を `base_currency` 建てで返す
+    ///
+    /// レートがない通貨は合計に含めず `unconverted_currencies` に挙げる。
+    pub fn total_in(&self, converter: &dyn CurrencyConverter, base_currency: &str) -> RevenueTotal {
+        let mut total = RevenueTotal {
+            currency: base_currency.to_string(),
+            amount: 0.0,
+            converted_count: 0,
+            unconverted_currencies: Vec::new(),
+            unparsed_count: self.unparsed_amount_count,
+        };
+        for (currency, (amount, count)) in &self.amounts_by_currency {
+            match converter.convert(*amount, currency, base_currency) {
+                Some(converted) => {
+                    total.amount += converted;
+                    total.converted_count += count;
+                }
+                None => total.unconverted_currencies.push(currency.clone()),
+            }
+        }
+        total
+    }
+
+    /// 金額テキストをパースして通貨ごとの合計に加える
+    fn add_amount(&mut self, amount: &str) {
+        match parse_purchase_amount(amount) {
+            Some(parsed) => {
+                let entry = self
+                    .amounts_by_currency
+                    .entry(parsed.currency)
+                    .or_insert((0.0, 0));
+                entry.0 += parsed.amount;
+                entry.1 += 1;
+            }
+            None => self.unparsed_amount_count += 1,
+        }
+    }
 
-    contributors_vec.truncate(10);
-    analytics.top_contributors = contributors_vec;
+    /// 貢献者の件数・最高tierを更新し、ランキング索引の位置を付け替える
+    fn update_contributor(&mut self, message: &ChatMessage, tier: Option<SuperChatTier>) {
+        let entry = self
+            .contributors
+            .entry(message.channel_id.clone())
+            .or_insert_with(|| ContributorInfo {
+                channel_id: message.channel_id.clone(),
+                display_name: message.author.clone(),
+                super_chat_count: 0,
+                highest_tier: None,
+            });
+        self.leaderboard.remove(&leaderboard_key(entry));
+
+        entry.super_chat_count += 1;
+        // より高いtierがあれば更新
+        if tier > entry.highest_tier {
+            entry.highest_tier = tier;
+        }
+        self.leaderboard.insert(leaderboard_key(entry));
+    }
 
-    analytics
+    /// `tz` のローカル時刻で1時間ごとに集計する
+    ///
+    /// `hour` は時間帯の開始時刻をオフセット付きRFC 3339で表す（例: `"2025-01-14T23:00:00+09:00"`）。
+    /// 夏時間の終了で同じローカル時刻が2回現れる場合は、オフセットの異なる別の時間帯になる。
+    /// `timestamp_usec` を解釈できないメッセージは集計しない。
+    fn add_to_hourly(&mut self, message: &ChatMessage) {
+        let Some(utc) = usec_to_datetime(&message.timestamp_usec) else {
+            return;
+        };
+        // ローカル時刻で時間を切り捨てる（30分単位のオフセットにも対応するため秒で計算）
+        let offset = utc.with_timezone(&self.tz).offset().fix();
+        let offset_secs = i64::from(offset.local_minus_utc());
+        let local_secs = utc.timestamp() + offset_secs;
+        let start_utc = local_secs.div_euclid(3600) * 3600 - offset_secs;
+
+        let stats = self.hourly.entry(start_utc).or_insert_with(|| HourlyStats {
+            hour: DateTime::from_timestamp(start_utc, 0)
+                .unwrap_or(utc)
+                .with_timezone(&offset)
+                .to_rfc3339(),
+            super_chat_count: 0,
+            super_sticker_count: 0,
+            membership_count: 0,
+            message_count: 0,
+        });
+        stats.message_count += 1;
+        match message.message_type {
+            MessageType::SuperChat { .. } => stats.super_chat_count += 1,
+            MessageType::SuperSticker { .. } => stats.super_sticker_count += 1,
+            MessageType::Membership { .. } | MessageType::MembershipGift { .. } => {
+                stats.membership_count += 1
+            }
+            _ => {}
+        }
+    }
+}
+
+/// SuperChat + SuperSticker の件数が最も多い時間帯を返す（同数なら早い方、0件のみならNone）
+fn peak_revenue_hour(hourly_stats: &[HourlyStats]) -> Option<String> {
+    let revenue = |stats: &HourlyStats| stats.super_chat_count + stats.super_sticker_count;
+    let mut peak: Option<&HourlyStats> = None;
+    for stats in hourly_stats {
+        if revenue(stats) > peak.map_or(0, revenue) {
+            peak = Some(stats);
+        }
+    }
+    peak.map(|stats| stats.hour.clone())
 }
 
 /// Get revenue analytics for current session
+///
+/// `timezone` はIANAタイムゾーン名（例: `"Asia/Tokyo"`）。省略時はシステムのローカルタイムゾーン。
+/// 金額の合計は設定（`currency` セクション）の基準通貨・レートで換算し、
+/// 収益目標（`revenue_goal` セクション）があればその進捗を添える
 #[tauri::command]
 pub async fn get_revenue_analytics(
     state: State<'_, AppState>,
+    config_state: State<'_, ConfigState>,
+    timezone: Option<String>,
 ) -> Result<RevenueAnalytics, CommandError> {
+    let tz = timezone
+        .map(|name| {
+            name.parse::<chrono_tz::Tz>()
+                .map_err(|e| CommandError::InvalidInput(format!("Invalid timezone: {}", e)))
+        })
+        .transpose()?;
+
     let messages = state.messages.read().await;
     // VecDequeをVecに変換して純粋関数に渡す
     let messages_vec: Vec<ChatMessage> = messages.iter().cloned().collect();
-    Ok(compute_revenue_analytics(&messages_vec))
+    let config = config_state.get();
+    let rates = config.currency.rate_table();
+    let mut analytics = match tz {
+        Some(tz) => compute_revenue_analytics_with(&messages_vec, &tz, &rates, rates.base()),
+        None => compute_revenue_analytics_with(&messages_vec, &Local, &rates, rates.base()),
+    };
+    analytics.goal = analytics.goal_progress(&config.revenue_goal, &rates);
+    Ok(analytics)
+}
+
+/// Get trending terms in recent messages of the current session
+///
+/// `window_secs` 秒以内（最新メッセージの投稿時刻基準）の語を出現メッセージ数の多い順に `top_n` 件返す。
+/// `extra_stopwords` は既定のストップワードに追加で除外する語
+#[tauri::command]
+pub async fn get_trending_terms(
+    state: State<'_, AppState>,
+    window_secs: u64,
+    top_n: usize,
+    extra_stopwords: Option<Vec<String>>,
+) -> Result<Vec<(String, usize)>, CommandError> {
+    let mut analyzer = TrendAnalyzer::default();
+    analyzer.add_stopwords(extra_stopwords.unwrap_or_default());
+
+    let messages = state.messages.read().await;
+    for message in messages.iter() {
+        analyzer.add_message(message);
+    }
+    Ok(analyzer.trending_terms(Duration::from_secs(window_secs), top_n))
 }
 
 /// DB行データからRevenueAnalyticsを計算する純粋関数
@@ -376,7 +715,10 @@ pub async fn get_session_analytics(
 
     // セッションのメッセージをカラー情報と一緒に取得
     let mut stmt = conn
-        .prepare("SELECT message_type, amount, header_color FROM messages WHERE session_id = ?")
+        .prepare(
+            "SELECT message_type, amount, header_color FROM messages
+             WHERE session_id = ? AND is_deleted = 0",
+        )
         .map_err(|e| CommandError::DatabaseError(e.to_string()))?;
 
     let rows: Vec<(String, Option<String>, Option<String>)> = stmt
@@ -394,6 +736,102 @@ pub async fn get_session_analytics(
     Ok(compute_session_analytics_from_rows(&rows))
 }
 
+/// Recompute full revenue and engagement analytics for a stored session
+///
+/// 計算はブロッキング用スレッドで行い、進捗を `analytics:recompute_progress` イベントで通知する。
+/// 実行中の再計算があればキャンセルしてから始める。キャンセルされた場合は None を返す。
+/// `timezone` は [`get_revenue_analytics`] と同じ
+#[tauri::command]
+pub async fn recompute_session_analytics(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config_state: State<'_, ConfigState>,
+    recompute_state: State<'_, AnalyticsRecomputeState>,
+    session_id: String,
+    timezone: Option<String>,
+) -> Result<Option<RecomputedAnalytics>, CommandError> {
+    let tz = timezone
+        .map(|name| {
+            name.parse::<chrono_tz::Tz>()
+                .map_err(|e| CommandError::InvalidInput(format!("Invalid timezone: {}", e)))
+        })
+        .transpose()?;
+
+    let messages: Vec<ChatMessage> = {
+        let db_guard = state.database.read().await;
+        let db = db_guard
+            .as_ref()
+            .ok_or_else(|| CommandError::DatabaseError("Database not initialized".to_string()))?;
+        let conn = db.connection().await;
+        crate::database::get_all_session_messages(&conn, &session_id)
+            .map_err(|e| CommandError::DatabaseError(format!("Failed to get messages: {}", e)))?
+            .into_iter()
+            .map(|m| m.into_chat_message())
+            .collect()
+    };
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    {
+        let mut current = recompute_state
+            .0
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?;
+        if let Some(previous) = current.replace(cancel.clone()) {
+            previous.cancel();
+        }
+    }
+
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = progress_rx.recv().await {
+            let _ = app.emit(RECOMPUTE_PROGRESS_EVENT, progress);
+        }
+    });
+
+    let config = config_state.get();
+    let rates = config.currency.rate_table();
+    let base = rates.base().to_string();
+    let handle = match tz {
+        Some(tz) => submit_full_recompute(
+            messages,
+            tz,
+            rates,
+            base,
+            config.engagement,
+            cancel,
+            progress_tx,
+        ),
+        None => submit_full_recompute(
+            messages,
+            Local,
+            rates,
+            base,
+            config.engagement,
+            cancel,
+            progress_tx,
+        ),
+    };
+    handle
+        .await
+        .map_err(|e| CommandError::Internal(format!("Recompute task failed: {}", e)))
+}
+
+/// Cancel the running analytics recompute, if any
+#[tauri::command]
+pub fn cancel_analytics_recompute(
+    recompute_state: State<'_, AnalyticsRecomputeState>,
+) -> Result<(), CommandError> {
+    let token = recompute_state
+        .0
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?
+        .take();
+    if let Some(token) = token {
+        token.cancel();
+    }
+    Ok(())
+}
+
 /// Export session data to file
 #[tauri::command]
 pub async fn export_session_data(
@@ -402,6 +840,8 @@ pub async fn export_session_data(
     file_path: String,
     config: ExportConfig,
 ) -> Result<(), CommandError> {
+    validate_export_config(&config)?;
+
     let db_guard = state.database.read().await;
     let db = db_guard
         .as_ref()
@@ -431,28 +871,29 @@ pub async fn export_session_data(
         )
         .map_err(|e| CommandError::NotFound(format!("Session not found: {}", e)))?;
 
-    // メッセージを取得
-    let limit_clause = config
-        .max_records
-        .map(|n| format!(" LIMIT {}", n))
-        .unwrap_or_default();
-    let query = format!(
-        "SELECT id, timestamp, author, channel_id, content, message_type, amount, is_member,
-                is_moderator, is_verified, badges, header_color
-         FROM messages WHERE session_id = ? ORDER BY timestamp{}",
-        limit_clause
-    );
-
+    // メッセージを取得（max_records は絞り込み後に適用するため SQL では制限しない）
     let mut stmt = conn
-        .prepare(&query)
+        .prepare(
+            "SELECT message_id, timestamp, author, channel_id, content, message_type, amount, is_member,
+                    metadata, is_deleted
+             FROM messages WHERE session_id = ?
+             ORDER BY CAST(timestamp_usec AS INTEGER), message_id",
+        )
         .map_err(|e| CommandError::DatabaseError(e.to_string()))?;
 
     let messages: Vec<ExportMessage> = stmt
         .query_map([&session_id], |row| {
             let message_type: String = row.get(5)?;
             let amount: Option<String> = row.get(6)?;
-            let header_color: Option<String> = row.get(11)?;
-            let badges_json: Option<String> = row.get(10)?;
+            let metadata_json: Option<String> = row.get(8)?;
+
+            // ロール・バッジ・色情報は metadata 列（MessageMetadata の JSON）から復元する
+            let metadata: Option<MessageMetadata> =
+                metadata_json.and_then(|j| serde_json::from_str(&j).ok());
+            let header_color = metadata
+                .as_ref()
+                .and_then(|m| m.superchat_colors.as_ref())
+                .map(|c| c.header_background.clone());
 
             let tier = if message_type == "superchat" {
                 if let Some(ref color) = header_color {
@@ -464,8 +905,9 @@ pub async fn export_session_data(
                 None
             };
 
-            let badges: Vec<String> = badges_json
-                .and_then(|j| serde_json::from_str(&j).ok())
+            let (is_moderator, is_verified, is_owner, badges) = metadata
+                .as_ref()
+                .map(|m| (m.is_moderator, m.is_verified, m.is_owner, m.badges.clone()))
                 .unwrap_or_default();
 
             Ok(ExportMessage {
@@ -478,14 +920,18 @@ pub async fn export_session_data(
                 amount_display: amount,
                 tier,
                 is_member: row.get(7)?,
-                is_moderator: row.get(8).unwrap_or(false),
-                is_verified: row.get(9).unwrap_or(false),
+                is_moderator,
+                is_verified,
+                is_owner,
+                membership_months: membership_months_from_metadata(metadata.as_ref()),
                 badges,
+                is_deleted: row.get(9)?,
             })
         })
         .map_err(|e| CommandError::DatabaseError(e.to_string()))?
         .filter_map(|r| r.ok())
         .collect();
+    let messages = apply_export_filters(messages, &config);
 
     let statistics = calculate_session_statistics(&messages);
 
@@ -495,26 +941,8 @@ pub async fn export_session_data(
         statistics,
     };
 
-    // フォーマットに応じてエクスポート
-    let content = match config.format.as_str() {
-        "json" => export_to_json(&export_data, &config)?,
-        "csv" => export_to_csv(&export_data, &config)?,
-        _ => {
-            return Err(CommandError::InvalidInput(format!(
-                "Unsupported format: {}",
-                config.format
-            )));
-        }
-    };
-
-    // ファイルに書き出し
-    let mut file = File::create(&file_path)
-        .map_err(|e| CommandError::IoError(format!("Failed to create file: {}", e)))?;
-
-    file.write_all(content.as_bytes())
-        .map_err(|e| CommandError::IoError(format!("Failed to write file: {}", e)))?;
-
-    Ok(())
+    // フォーマットに応じてファイルに書き出し
+    write_export_file(&file_path, &export_data, &config)
 }
 
 /// ChatMessageリストからExportMessageリストへの変換
@@ -550,14 +978,24 @@ pub(crate) fn convert_messages_to_export(
                 MessageType::System => ("system".to_string(), None, None),
             };
 
-            let (is_moderator, is_verified, badges) = if let Some(ref metadata) = msg.metadata {
-                (
-                    metadata.is_moderator,
-                    metadata.is_verified,
-                    metadata.badges.clone(),
-                )
-            } else {
-                (false, false, vec![])
+            let (is_moderator, is_verified, is_owner, badges) =
+                if let Some(ref metadata) = msg.metadata {
+                    (
+                        metadata.is_moderator,
+                        metadata.is_verified,
+                        metadata.is_owner,
+                        metadata.badges.clone(),
+                    )
+                } else {
+                    (false, false, false, vec![])
+                };
+
+            // マイルストーンメッセージの月数を優先し、なければメンバーバッジから推定
+            let membership_months = match &msg.message_type {
+                MessageType::Membership {
+                    milestone_months: Some(months),
+                } => Some(*months),
+                _ => membership_months_from_metadata(msg.metadata.as_ref()),
             };
 
             ExportMessage {
@@ -572,7 +1010,10 @@ pub(crate) fn convert_messages_to_export(
                 is_moderator,
                 is_member: msg.is_member,
                 is_verified,
+                is_owner,
+                membership_months,
                 badges,
+                is_deleted: false,
             }
         })
         .collect()
@@ -585,6 +1026,8 @@ pub async fn export_current_messages(
     file_path: String,
     config: ExportConfig,
 ) -> Result<(), CommandError> {
+    validate_export_config(&config)?;
+
     let messages = state.messages.read().await;
 
     // 多接続モデル: 最初の接続からセッションID・配信者IDを取得（エクスポートヘッダ用）
@@ -604,12 +1047,11 @@ pub async fn export_current_messages(
     };
 
     // VecDequeをVecに変換して純粋関数に渡す
-    let messages_vec: Vec<ChatMessage> = messages
-        .iter()
-        .take(config.max_records.unwrap_or(usize::MAX))
-        .cloned()
-        .collect();
-    let export_messages = convert_messages_to_export(&messages_vec, &session_id, &broadcaster_id);
+    let messages_vec: Vec<ChatMessage> = messages.iter().cloned().collect();
+    let export_messages = apply_export_filters(
+        convert_messages_to_export(&messages_vec, &session_id, &broadcaster_id),
+        &config,
+    );
 
     let statistics = calculate_session_statistics(&export_messages);
 
@@ -628,36 +1070,300 @@ pub async fn export_current_messages(
         messages: export_messages,
     };
 
-    let content = match config.format.as_str() {
-        "json" => export_to_json(&export_data, &config)?,
-        "csv" => export_to_csv(&export_data, &config)?,
-        _ => {
-            return Err(CommandError::InvalidInput(format!(
-                "Unsupported format: {}",
-                config.format
-            )));
+    write_export_file(&file_path, &export_data, &config)
+}
+
+/// 現在メッセージのエクスポートで出力される件数を見積もる
+///
+/// `export_current_messages` と同じ絞り込み・`max_records` を適用した件数を返す。
+#[tauri::command]
+pub async fn estimate_current_export(
+    state: State<'_, AppState>,
+    config: ExportConfig,
+) -> Result<usize, CommandError> {
+    validate_export_config(&config)?;
+
+    let messages = state.messages.read().await;
+    let messages_vec: Vec<ChatMessage> = messages.iter().cloned().collect();
+    let export_messages = convert_messages_to_export(&messages_vec, "current", "");
+    Ok(estimate_export_size(&export_messages, &config))
+}
+
+/// Rebuild analytics from a previously exported CSV or JSON file
+///
+/// 形式は拡張子（`.csv` / `.json`）で判定する。時間別統計はシステムのローカルタイムゾーンで区切り、
+/// 金額の合計は設定（`currency` セクション）の基準通貨・レートで換算する。
+#[tauri::command]
+pub async fn import_session_file(
+    config_state: State<'_, ConfigState>,
+    file_path: String,
+) -> Result<ImportedSessionAnalytics, CommandError> {
+    let path = std::path::Path::new(&file_path);
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    let imported = match extension.as_str() {
+        "csv" => import::import_csv(path),
+        "json" => import::import_json(path),
+        _ => return Err(unsupported_format(&extension)),
+    }
+    .map_err(|e| match e {
+        import::ImportError::Io(_) => CommandError::IoError(e.to_string()),
+        _ => CommandError::InvalidInput(e.to_string()),
+    })?;
+
+    let messages = import::to_chat_messages(&imported.data.messages);
+    let rates = config_state.get().currency.rate_table();
+    Ok(ImportedSessionAnalytics {
+        session_id: imported.data.metadata.session_id,
+        stream_title: imported.data.metadata.stream_title,
+        message_count: imported.data.statistics.total_messages,
+        unique_viewers: imported.data.statistics.unique_viewers,
+        analytics: compute_revenue_analytics_with(&messages, &Local, &rates, rates.base()),
+        warnings: imported.warnings,
+    })
+}
+
+/// Export the contributor leaderboard of the current session
+#[tauri::command]
+pub async fn export_contributor_leaderboard(
+    state: State<'_, AppState>,
+    file_path: String,
+    config: LeaderboardExportConfig,
+) -> Result<(), CommandError> {
+    let messages = state.messages.read().await;
+    let messages_vec: Vec<ChatMessage> = messages.iter().cloned().collect();
+    let entries = compute_contributor_leaderboard(&messages_vec, config.sort_order);
+
+    // ファイル作成前に変換し、不正な形式で空ファイルが残らないようにする
+    let bytes = export_leaderboard(&entries, &config)?;
+    std::fs::write(&file_path, bytes).map_err(write_error)
+}
+
+/// SuperChat/SuperSticker の送信者ごとの貢献ランキングを作る純粋関数
+///
+/// 通貨の異なる金額は合算できないため、チャンネル×通貨ごとに1行とする。
+/// 投稿時刻は `timestamp_usec` をRFC 3339（UTC）に変換し、解釈できなければ表示用の `timestamp` を使う。
+pub(crate) fn compute_contributor_leaderboard(
+    messages: &[ChatMessage],
+    sort_order: LeaderboardSortOrder,
+) -> Vec<LeaderboardEntry> {
+    let mut entries: Vec<LeaderboardEntry> = Vec::new();
+    // (channel_id, currency) -> entries のインデックス
+    let mut index: HashMap<(String, String), usize> = HashMap::new();
+
+    for message in messages {
+        let amount = match &message.message_type {
+            MessageType::SuperChat { amount } | MessageType::SuperSticker { amount } => amount,
+            _ => continue,
+        };
+        let (currency, value) = parse_purchase_amount(amount)
+            .map(|parsed| (parsed.currency, parsed.amount))
+            .unwrap_or_default();
+        let time = usec_to_datetime(&message.timestamp_usec)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_else(|| message.timestamp.clone());
+
+        let i = *index
+            .entry((message.channel_id.clone(), currency.clone()))
+            .or_insert_with(|| {
+                entries.push(LeaderboardEntry {
+                    rank: 0,
+                    author: message.author.clone(),
+                    channel_id: message.channel_id.clone(),
+                    total_amount: 0.0,
+                    currency,
+                    message_count: 0,
+                    first_contribution: time.clone(),
+                    last_contribution: String::new(),
+                });
+                entries.len() - 1
+            });
+        let entry = &mut entries[i];
+        entry.total_amount += value;
+        entry.message_count += 1;
+        entry.last_contribution = time;
+    }
+
+    match sort_order {
+        LeaderboardSortOrder::ByAmount => {
+            entries.sort_by(|a, b| {
+                a.currency
+                    .cmp(&b.currency)
+                    .then(b.total_amount.total_cmp(&a.total_amount))
+                    .then(b.message_count.cmp(&a.message_count))
+                    .then(a.channel_id.cmp(&b.channel_id))
+            });
+            let mut rank = 0;
+            for i in 0..entries.len() {
+                rank = if i > 0 && entries[i - 1].currency == entries[i].currency {
+                    rank + 1
+                } else {
+                    1
+                };
+                entries[i].rank = rank;
+            }
         }
-    };
+        LeaderboardSortOrder::ByMessageCount => {
+            entries.sort_by(|a, b| {
+                b.message_count
+                    .cmp(&a.message_count)
+                    .then(a.channel_id.cmp(&b.channel_id))
+                    .then(a.currency.cmp(&b.currency))
+            });
+            for (i, entry) in entries.iter_mut().enumerate() {
+                entry.rank = i + 1;
+            }
+        }
+    }
 
-    let mut file = File::create(&file_path)
-        .map_err(|e| CommandError::IoError(format!("Failed to create file: {}", e)))?;
+    entries
+}
 
-    file.write_all(content.as_bytes())
-        .map_err(|e| CommandError::IoError(format!("Failed to write file: {}", e)))?;
+/// 貢献者ランキングを指定形式（csv / json）のバイト列に変換する
+pub(crate) fn export_leaderboard(
+    entries: &[LeaderboardEntry],
+    config: &LeaderboardExportConfig,
+) -> Result<Vec<u8>, CommandError> {
+    match config.format.as_str() {
+        "json" => serde_json::to_vec_pretty(entries)
+            .map_err(|e| CommandError::Internal(format!("JSON serialization error: {}", e))),
+        "csv" => {
+            let mut buf = Vec::new();
+            write_leaderboard_csv(entries, &mut buf).map_err(write_error)?;
+            Ok(buf)
+        }
+        _ => Err(unsupported_format(&config.format)),
+    }
+}
 
+fn write_leaderboard_csv(entries: &[LeaderboardEntry], w: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "rank,author,channel_id,total_amount,currency,message_count,first_contribution,last_contribution"
+    )?;
+    for entry in entries {
+        writeln!(
+            w,
+            "{},\"{}\",\"{}\",{},\"{}\",{},\"{}\",\"{}\"",
+            entry.rank,
+            entry.author.replace('"', "\"\""),
+            entry.channel_id,
+            entry.total_amount,
+            entry.currency,
+            entry.message_count,
+            entry.first_contribution,
+            entry.last_contribution
+        )?;
+    }
     Ok(())
 }
 
 // Helper functions
 
+/// 対応しているエクスポート形式
+#[cfg(feature = "parquet")]
+const EXPORT_FORMATS: &[&str] = &["json", "csv", "ndjson", "parquet"];
+#[cfg(not(feature = "parquet"))]
+const EXPORT_FORMATS: &[&str] = &["json", "csv", "ndjson"];
+
+/// エクスポートの種別絞り込みで指定できるメッセージ種別
+const EXPORT_MESSAGE_TYPES: &[&str] = &[
+    "text",
+    "superchat",
+    "supersticker",
+    "membership",
+    "membership_gift",
+    "system",
+];
+
+/// エクスポート設定（形式・絞り込み条件）を検証する
+///
+/// ファイル作成前に呼び出し、不正な設定で空ファイルが残らないようにする。
+/// 絞り込みの空リストは「絞り込みなし」（None）と区別がつかないため拒否する。
+fn validate_export_config(config: &ExportConfig) -> Result<(), CommandError> {
+    if !EXPORT_FORMATS.contains(&config.format.as_str()) {
+        return Err(unsupported_format(&config.format));
+    }
+    if config.author_filter.as_ref().is_some_and(Vec::is_empty) {
+        return Err(CommandError::InvalidInput(
+            "author_filter must not be empty (use null to export all authors)".to_string(),
+        ));
+    }
+    if let Some(types) = &config.message_type_filter {
+        if types.is_empty() {
+            return Err(CommandError::InvalidInput(
+                "message_type_filter must not be empty (use null to export all types)".to_string(),
+            ));
+        }
+        if let Some(unknown) = types
+            .iter()
+            .find(|t| !EXPORT_MESSAGE_TYPES.contains(&t.as_str()))
+        {
+            return Err(CommandError::InvalidInput(format!(
+                "Unknown message type in message_type_filter: {}",
+                unknown
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// メッセージが投稿者・種別の絞り込み条件（AND 結合）に一致するか
+fn message_matches_filter(message: &ExportMessage, config: &ExportConfig) -> bool {
+    let author_matches = config
+        .author_filter
+        .as_ref()
+        .is_none_or(|ids| ids.contains(&message.author_id));
+    let type_matches = config
+        .message_type_filter
+        .as_ref()
+        .is_none_or(|types| types.contains(&message.message_type));
+    // 削除されたメッセージは include_deleted の場合だけ出力する
+    let deleted_allowed = config.include_deleted || !message.is_deleted;
+    author_matches && type_matches && deleted_allowed
+}
+
+/// 絞り込み条件に一致するメッセージを max_records 件まで残す
+fn apply_export_filters(messages: Vec<ExportMessage>, config: &ExportConfig) -> Vec<ExportMessage> {
+    messages
+        .into_iter()
+        .filter(|m| message_matches_filter(m, config))
+        .take(config.max_records.unwrap_or(usize::MAX))
+        .collect()
+}
+
+/// 絞り込み条件と max_records を適用した場合の件数（`apply_export_filters` の結果の件数と一致する）
+fn estimate_export_size(messages: &[ExportMessage], config: &ExportConfig) -> usize {
+    messages
+        .iter()
+        .filter(|m| message_matches_filter(m, config))
+        .count()
+        .min(config.max_records.unwrap_or(usize::MAX))
+}
+
+/// メンバーバッジの tooltip からメンバー継続月数を取得する
+fn membership_months_from_metadata(metadata: Option<&MessageMetadata>) -> Option<u32> {
+    metadata?
+        .badge_info
+        .iter()
+        .filter_map(|b| b.tooltip.as_deref())
+        .find_map(extract_milestone_months_from_badge)
+}
+
 /// Calculate session statistics from export messages (DRY: used by both export functions)
 fn calculate_session_statistics(messages: &[ExportMessage]) -> SessionStatistics {
     let mut unique_viewers: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut super_chat_count = 0;
     let mut super_chat_by_tier = SuperChatTierStats::default();
     let mut membership_count = 0;
+    let mut total_messages = 0;
 
-    for msg in messages {
+    // 削除されたメッセージは出力に含めても集計しない
+    for msg in messages.iter().filter(|m| !m.is_deleted) {
+        total_messages += 1;
         unique_viewers.insert(msg.author_id.clone());
 
         match msg.message_type.as_str() {
@@ -675,7 +1381,7 @@ fn calculate_session_statistics(messages: &[ExportMessage]) -> SessionStatistics
     }
 
     SessionStatistics {
-        total_messages: messages.len(),
+        total_messages,
         unique_viewers: unique_viewers.len(),
         super_chat_count,
         super_chat_by_tier,
@@ -683,54 +1389,138 @@ fn calculate_session_statistics(messages: &[ExportMessage]) -> SessionStatistics
     }
 }
 
+/// ロール列が無効な場合に JSON 出力から除外するフィールド
+const ROLE_COLUMN_FIELDS: [&str; 2] = ["is_owner", "membership_months"];
+
 fn export_to_json(data: &SessionExportData, config: &ExportConfig) -> Result<String, CommandError> {
-    if config.include_metadata {
-        serde_json::to_string_pretty(data)
-            .map_err(|e| CommandError::Internal(format!("JSON serialization error: {}", e)))
+    let mut value = if config.include_metadata {
+        serde_json::to_value(data)
     } else {
-        serde_json::to_string_pretty(&data.messages)
-            .map_err(|e| CommandError::Internal(format!("JSON serialization error: {}", e)))
+        serde_json::to_value(&data.messages)
     }
-}
+    .map_err(|e| CommandError::Internal(format!("JSON serialization error: {}", e)))?;
 
-fn export_to_csv(data: &SessionExportData, config: &ExportConfig) -> Result<String, CommandError> {
-    let mut csv = String::new();
+    let mut removed_fields: Vec<&str> = Vec::new();
+    if !config.include_role_columns {
+        removed_fields.extend(ROLE_COLUMN_FIELDS);
+    }
+    if !config.include_deleted {
+        removed_fields.push("is_deleted");
+    }
+    if !removed_fields.is_empty() {
+        let messages = if config.include_metadata {
+            value.get_mut("messages")
+        } else {
+            Some(&mut value)
+        };
+        if let Some(serde_json::Value::Array(messages)) = messages {
+            for msg in messages.iter_mut().filter_map(|m| m.as_object_mut()) {
+                for field in &removed_fields {
+                    msg.remove(*field);
+                }
+            }
+        }
+    }
 
-    // Metadata header (per spec)
+    serde_json::to_string_pretty(&value)
+        .map_err(|e| CommandError::Internal(format!("JSON serialization error: {}", e)))
+}
+
+/// 形式に応じてエクスポートデータを書き出す
+///
+/// CSV は 1 行ずつ `writer` に書き込み、出力全体をメモリ上に構築しない。
+/// JSON はロール列の除外に全体の Value が必要なため、文字列を構築してから書き込む。
+/// NDJSON（再生モードで読み込める保存ファイル形式）は 1 メッセージずつ書き込む。
+/// Parquet は一定行数ごとの RecordBatch 単位で書き込む。
+fn write_export(
+    data: &SessionExportData,
+    config: &ExportConfig,
+    writer: &mut (dyn Write + Send),
+) -> Result<(), CommandError> {
+    match config.format.as_str() {
+        "json" => writer
+            .write_all(export_to_json(data, config)?.as_bytes())
+            .map_err(write_error),
+        "csv" => write_csv(data, config, writer).map_err(write_error),
+        "ndjson" => ndjson_export::write_ndjson(data, writer).map_err(write_error),
+        #[cfg(feature = "parquet")]
+        "parquet" => parquet_export::write_parquet(data, config, writer),
+        _ => Err(unsupported_format(&config.format)),
+    }
+}
+
+/// エクスポートデータをファイルに書き出す
+fn write_export_file(
+    file_path: &str,
+    data: &SessionExportData,
+    config: &ExportConfig,
+) -> Result<(), CommandError> {
+    let file = File::create(file_path)
+        .map_err(|e| CommandError::IoError(format!("Failed to create file: {}", e)))?;
+    let mut writer = BufWriter::new(file);
+
+    write_export(data, config, &mut writer)?;
+    writer.flush().map_err(write_error)
+}
+
+fn write_error(e: std::io::Error) -> CommandError {
+    CommandError::IoError(format!("Failed to write file: {}", e))
+}
+
+fn unsupported_format(format: &str) -> CommandError {
+    CommandError::InvalidInput(format!("Unsupported format: {}", format))
+}
+
+/// テスト用: CSV 出力を文字列として取得する
+#[cfg(test)]
+fn export_to_csv(data: &SessionExportData, config: &ExportConfig) -> Result<String, CommandError> {
+    let mut buf = Vec::new();
+    write_csv(data, config, &mut buf).map_err(write_error)?;
+    String::from_utf8(buf).map_err(|e| CommandError::Internal(e.to_string()))
+}
+
+/// CSV 形式で書き出す（メタデータ・ヘッダー・データ行の順に逐次書き込む）
+fn write_csv(
+    data: &SessionExportData,
+    config: &ExportConfig,
+    w: &mut dyn Write,
+) -> std::io::Result<()> {
+    // Metadata header (per spec)
     if config.include_metadata {
-        csv.push_str("# Metadata\n");
-        csv.push_str(&format!("# Session ID,{}\n", data.metadata.session_id));
+        writeln!(w, "# Metadata")?;
+        writeln!(w, "# Session ID,{}", data.metadata.session_id)?;
         if let Some(ref title) = data.metadata.stream_title {
-            csv.push_str(&format!("# Stream Title,{}\n", title));
+            writeln!(w, "# Stream Title,{}", title)?;
         }
         if let Some(ref name) = data.metadata.broadcaster_name {
-            csv.push_str(&format!("# Channel,{}\n", name));
+            writeln!(w, "# Channel,{}", name)?;
         }
         if let Some(ref url) = data.metadata.stream_url {
-            csv.push_str(&format!("# Stream URL,{}\n", url));
+            writeln!(w, "# Stream URL,{}", url)?;
         }
-        csv.push_str(&format!("# Start Time,{}\n", data.metadata.start_time));
+        writeln!(w, "# Start Time,{}", data.metadata.start_time)?;
         if let Some(ref end) = data.metadata.end_time {
-            csv.push_str(&format!("# End Time,{}\n", end));
+            writeln!(w, "# End Time,{}", end)?;
         }
-        csv.push_str(&format!(
-            "# Total Messages,{}\n",
-            data.statistics.total_messages
-        ));
-        csv.push_str(&format!(
-            "# Unique Viewers,{}\n",
-            data.statistics.unique_viewers
-        ));
-        csv.push_str(&format!(
-            "# SuperChat Count,{}\n",
-            data.statistics.super_chat_count
-        ));
-        csv.push_str(&format!("# Export Time,{}\n", data.metadata.export_time));
-        csv.push('\n');
+        writeln!(w, "# Total Messages,{}", data.statistics.total_messages)?;
+        writeln!(w, "# Unique Viewers,{}", data.statistics.unique_viewers)?;
+        writeln!(w, "# SuperChat Count,{}", data.statistics.super_chat_count)?;
+        writeln!(w, "# Export Time,{}", data.metadata.export_time)?;
+        writeln!(w)?;
     }
 
     // Header (per spec)
-    csv.push_str("id,timestamp,author,author_id,content,message_type,amount_display,tier,is_moderator,is_member,is_verified,badges\n");
+    write!(
+        w,
+        "id,timestamp,author,author_id,content,message_type,amount_display,tier,is_moderator,is_member,is_verified,badges"
+    )?;
+    if config.include_role_columns {
+        write!(w, ",is_owner,membership_months")?;
+    }
+    if config.include_deleted {
+        write!(w, ",is_deleted")?;
+    }
+    writeln!(w)?;
 
     // Data rows
     for msg in &data.messages {
@@ -742,8 +1532,9 @@ fn export_to_csv(data: &SessionExportData, config: &ExportConfig) -> Result<Stri
         let content_escaped = msg.content.replace('"', "\"\"");
         let badges_str = msg.badges.join(";");
 
-        csv.push_str(&format!(
-            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{},{},\"{}\"\n",
+        write!(
+            w,
+            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{},{},\"{}\"",
             msg.id,
             msg.timestamp,
             msg.author.replace('"', "\"\""),
@@ -756,15 +1547,27 @@ fn export_to_csv(data: &SessionExportData, config: &ExportConfig) -> Result<Stri
             msg.is_member,
             msg.is_verified,
             badges_str
-        ));
+        )?;
+        if config.include_role_columns {
+            let months_str = msg
+                .membership_months
+                .map(|m| m.to_string())
+                .unwrap_or_default();
+            write!(w, ",{},{}", msg.is_owner, months_str)?;
+        }
+        if config.include_deleted {
+            write!(w, ",{}", msg.is_deleted)?;
+        }
+        writeln!(w)?;
     }
 
-    Ok(csv)
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::currency::StaticRateTable;
     use crate::core::{MessageMetadata, SuperChatColors};
 
     // ========================================================================
@@ -893,8 +1696,10 @@ mod tests {
 
     #[test]
     fn parse_amount_value_euro() {
-        // ',' is filtered out by parse_amount_value since it only keeps digits and '.'
         assert_eq!(parse_amount_value("€5.50"), Some(5.5));
+        // カンマを小数点とする通貨表記
+        assert_eq!(parse_amount_value("5,50 €"), Some(5.5));
+        assert_eq!(parse_amount_value("R$ 1.234,56"), Some(1234.56));
     }
 
     #[test]
@@ -969,7 +1774,10 @@ mod tests {
                     is_moderator: false,
                     is_member: false,
                     is_verified: false,
+                    is_owner: false,
+                    membership_months: None,
                     badges: vec![],
+                    is_deleted: false,
                 },
                 ExportMessage {
                     id: "msg2".to_string(),
@@ -983,7 +1791,10 @@ mod tests {
                     is_moderator: false,
                     is_member: true,
                     is_verified: false,
+                    is_owner: false,
+                    membership_months: None,
                     badges: vec!["member".to_string()],
+                    is_deleted: false,
                 },
             ],
             statistics: SessionStatistics {
@@ -1005,6 +1816,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let csv = export_to_csv(&data, &config).unwrap();
@@ -1030,6 +1845,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let csv = export_to_csv(&data, &config).unwrap();
@@ -1047,6 +1866,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let csv = export_to_csv(&data, &config).unwrap();
@@ -1066,6 +1889,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let csv = export_to_csv(&data, &config).unwrap();
@@ -1087,6 +1914,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let json = export_to_json(&data, &config).unwrap();
@@ -1107,6 +1938,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let json = export_to_json(&data, &config).unwrap();
@@ -1217,7 +2052,10 @@ mod tests {
             is_moderator: false,
             is_member: false,
             is_verified: false,
+            is_owner: false,
+            membership_months: None,
             badges: vec![],
+            is_deleted: false,
         }
     }
 
@@ -1371,6 +2209,8 @@ mod tests {
                     color: None,
                     is_moderator: false,
                     is_verified: false,
+                    is_owner: false,
+                    sticker: None,
                 }),
             ),
             make_chat_message(
@@ -1392,6 +2232,8 @@ mod tests {
                     color: None,
                     is_moderator: false,
                     is_verified: false,
+                    is_owner: false,
+                    sticker: None,
                 }),
             ),
             make_chat_message(
@@ -1471,6 +2313,8 @@ mod tests {
                     color: None,
                     is_moderator: false,
                     is_verified: false,
+                    is_owner: false,
+                    sticker: None,
                 }),
             ),
             make_chat_message(
@@ -1571,6 +2415,8 @@ mod tests {
                     color: None,
                     is_moderator: false,
                     is_verified: false,
+                    is_owner: false,
+                    sticker: None,
                 }),
             ),
         ];
@@ -1600,6 +2446,455 @@ mod tests {
         assert_eq!(analytics.top_contributors[0].super_chat_count, 1);
     }
 
+    // ========================================================================
+    // hourly_stats / peak_hour (07_revenue.md: ローカル時刻での時間別集計)
+    // ========================================================================
+
+    /// `rfc3339` の時刻に投稿されたテスト用ChatMessage
+    fn make_timed_message(rfc3339: &str, message_type: MessageType) -> ChatMessage {
+        let usec = DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .timestamp_micros();
+        ChatMessage {
+            timestamp_usec: usec.to_string(),
+            ..make_chat_message("UC_a", "UserA", message_type, None)
+        }
+    }
+
+    fn superchat() -> MessageType {
+        MessageType::SuperChat {
+            amount: "¥500".to_string(),
+        }
+    }
+
+    #[test]
+    fn hourly_stats_bucketed_in_configured_timezone() {
+        // 07_revenue.md: UTC 14:30 は JST 23時台として集計される
+        let messages = vec![
+            make_timed_message("2025-01-14T14:30:00Z", superchat()),
+            make_timed_message("2025-01-14T14:59:59Z", MessageType::Text),
+            make_timed_message("2025-01-14T15:00:00Z", MessageType::Text),
+        ];
+
+        let analytics = compute_revenue_analytics_in(&messages, &chrono_tz::Asia::Tokyo);
+
+        assert_eq!(analytics.hourly_stats.len(), 2);
+        assert_eq!(analytics.hourly_stats[0].hour, "2025-01-14T23:00:00+09:00");
+        assert_eq!(analytics.hourly_stats[0].message_count, 2);
+        assert_eq!(analytics.hourly_stats[0].super_chat_count, 1);
+        assert_eq!(analytics.hourly_stats[1].hour, "2025-01-15T00:00:00+09:00");
+        assert_eq!(
+            analytics.peak_hour.as_deref(),
+            Some("2025-01-14T23:00:00+09:00")
+        );
+    }
+
+    #[test]
+    fn hourly_stats_split_repeated_hour_at_dst_end() {
+        // 07_revenue.md: 夏時間終了（America/New_York 2024-11-03）で2回現れる1時台は別集計
+        let messages = vec![
+            make_timed_message("2024-11-03T05:30:00Z", MessageType::Text), // 01:30 EDT
+            make_timed_message("2024-11-03T06:10:00Z", superchat()),       // 01:10 EST
+            make_timed_message("2024-11-03T06:50:00Z", superchat()),       // 01:50 EST
+        ];
+
+        let analytics = compute_revenue_analytics_in(&messages, &chrono_tz::America::New_York);
+
+        let hours: Vec<&str> = analytics
+            .hourly_stats
+            .iter()
+            .map(|stats| stats.hour.as_str())
+            .collect();
+        assert_eq!(
+            hours,
+            vec!["2024-11-03T01:00:00-04:00", "2024-11-03T01:00:00-05:00"]
+        );
+        assert_eq!(analytics.hourly_stats[1].super_chat_count, 2);
+        assert_eq!(
+            analytics.peak_hour.as_deref(),
+            Some("2024-11-03T01:00:00-05:00")
+        );
+    }
+
+    #[test]
+    fn hourly_stats_skips_messages_without_timestamp() {
+        let messages = vec![make_chat_message("UC_a", "UserA", superchat(), None)];
+
+        let analytics = compute_revenue_analytics_in(&messages, &Utc);
+
+        assert!(analytics.hourly_stats.is_empty());
+        assert_eq!(analytics.peak_hour, None);
+        assert_eq!(analytics.super_chat_count, 1);
+    }
+
+    #[test]
+    fn peak_hour_none_without_revenue() {
+        let messages = vec![make_timed_message(
+            "2025-01-14T14:30:00Z",
+            MessageType::Text,
+        )];
+
+        let analytics = compute_revenue_analytics_in(&messages, &Utc);
+
+        assert_eq!(analytics.hourly_stats[0].hour, "2025-01-14T14:00:00+00:00");
+        assert_eq!(analytics.peak_hour, None);
+    }
+
+    // ========================================================================
+    // RevenueTracker (07_revenue.md: 逐次集計)
+    // ========================================================================
+
+    /// 送信者・種別が混在するメッセージ列（SuperChat金額でtierが変わる）
+    fn make_mixed_session() -> Vec<ChatMessage> {
+        let amounts = ["$1.00", "$200.00", "$5.00", "$20.00", "$2.00", "$50.00"];
+        (0..60)
+            .map(|i| {
+                let channel = format!("UC_{}", i % 7);
+                let message_type = match i % 4 {
+                    0 => MessageType::SuperChat {
+                        amount: amounts[i % amounts.len()].to_string(),
+                    },
+                    1 => MessageType::SuperSticker {
+                        amount: "$3.00".to_string(),
+                    },
+                    2 => MessageType::Membership {
+                        milestone_months: None,
+                    },
+                    _ => MessageType::Text,
+                };
+                let mut message = make_chat_message(&channel, &channel, message_type, None);
+                message.timestamp_usec =
+                    (1_736_860_000_000_000i64 + i as i64 * 300_000_000).to_string();
+                message
+            })
+            .collect()
+    }
+
+    #[test]
+    fn revenue_tracker_incremental_matches_batch() {
+        let messages = make_mixed_session();
+        let mut tracker = RevenueTracker::new(Utc);
+
+        for (i, message) in messages.iter().enumerate() {
+            tracker.add_message(message);
+
+            let incremental = tracker.snapshot();
+            let batch = compute_revenue_analytics_in(&messages[..=i], &Utc);
+            assert_eq!(
+                serde_json::to_value(&incremental).unwrap(),
+                serde_json::to_value(&batch).unwrap(),
+                "{}件目で逐次集計と一括集計が一致しない",
+                i + 1
+            );
+        }
+    }
+
+    #[test]
+    fn revenue_tracker_leaderboard_matches_full_sort() {
+        // 全件ソートによる順位付け（従来の一括計算）と一致すること
+        let messages = make_mixed_session();
+        let mut tracker = RevenueTracker::new(Utc);
+        let mut expected: HashMap<String, ContributorInfo> = HashMap::new();
+
+        for message in &messages {
+            tracker.add_message(message);
+
+            let tier = match &message.message_type {
+                MessageType::SuperChat { amount } => Some(determine_tier_from_amount(amount)),
+                MessageType::SuperSticker { .. } => None,
+                _ => continue,
+            };
+            let entry = expected
+                .entry(message.channel_id.clone())
+                .or_insert_with(|| ContributorInfo {
+                    channel_id: message.channel_id.clone(),
+                    display_name: message.author.clone(),
+                    super_chat_count: 0,
+                    highest_tier: None,
+                });
+            entry.super_chat_count += 1;
+            entry.highest_tier = entry.highest_tier.max(tier);
+        }
+
+        let mut sorted: Vec<ContributorInfo> = expected.into_values().collect();
+        sorted.sort_by(|a, b| {
+            b.super_chat_count
+                .cmp(&a.super_chat_count)
+                .then(b.highest_tier.cmp(&a.highest_tier))
+                .then(a.channel_id.cmp(&b.channel_id))
+        });
+        let ids = |list: &[ContributorInfo]| -> Vec<(String, usize, Option<SuperChatTier>)> {
+            list.iter()
+                .map(|c| (c.channel_id.clone(), c.super_chat_count, c.highest_tier))
+                .collect()
+        };
+
+        assert_eq!(ids(&tracker.top_contributors(3)), ids(&sorted[..3]));
+        assert_eq!(ids(&tracker.top_contributors(100)), ids(&sorted));
+    }
+
+    #[test]
+    fn revenue_tracker_rebuild_from_resets_previous_state() {
+        let mut tracker = RevenueTracker::new(Utc);
+        tracker.add_message(&make_chat_message(
+            "UC_old",
+            "Old",
+            MessageType::SuperChat {
+                amount: "$100.00".to_string(),
+            },
+            None,
+        ));
+
+        let messages = make_mixed_session();
+        tracker.rebuild_from(&messages);
+
+        assert!(
+            tracker
+                .top_contributors(100)
+                .iter()
+                .all(|c| c.channel_id != "UC_old")
+        );
+        assert_eq!(
+            tracker.snapshot().super_chat_count,
+            compute_revenue_analytics_in(&messages, &Utc).super_chat_count
+        );
+    }
+
+    // ========================================================================
+    // 貢献者ランキングのエクスポート (07_revenue.md)
+    // ========================================================================
+
+    fn make_paid_message(channel_id: &str, amount: &str, usec: i64, sticker: bool) -> ChatMessage {
+        let amount = amount.to_string();
+        let message_type = if sticker {
+            MessageType::SuperSticker { amount }
+        } else {
+            MessageType::SuperChat { amount }
+        };
+        ChatMessage {
+            timestamp_usec: usec.to_string(),
+            ..make_chat_message(
+                channel_id,
+                &format!("{}_name", channel_id),
+                message_type,
+                None,
+            )
+        }
+    }
+
+    fn make_leaderboard_messages() -> Vec<ChatMessage> {
+        vec![
+            make_paid_message("UC_a", "¥500", 1_736_863_200_000_000, false),
+            make_paid_message("UC_b", "¥10,000", 1_736_863_260_000_000, false),
+            make_paid_message("UC_a", "¥300", 1_736_863_320_000_000, true),
+            make_paid_message("UC_a", "¥1,000", 1_736_863_380_000_000, false),
+            make_paid_message("UC_c", "$5.00", 1_736_863_440_000_000, false),
+            make_chat_message("UC_d", "Text", MessageType::Text, None),
+        ]
+    }
+
+    // ========================================================================
+    // 通貨換算した合計 (07_revenue.md)
+    // ========================================================================
+
+    #[test]
+    fn revenue_total_converts_to_base_currency() {
+        let rates = StaticRateTable::new("JPY", [("USD".to_string(), 150.0)]);
+        let analytics = compute_revenue_analytics_with(
+            &make_leaderboard_messages(),
+            &Utc,
+            &rates,
+            rates.base(),
+        );
+
+        assert_eq!(
+            analytics.total,
+            Some(RevenueTotal {
+                currency: "JPY".to_string(),
+                amount: 11800.0 + 5.0 * 150.0,
+                converted_count: 5,
+                unconverted_currencies: vec![],
+                unparsed_count: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn goal_progress_uses_the_converted_total() {
+        let rates = StaticRateTable::new("JPY", [("USD".to_string(), 150.0)]);
+        let analytics = compute_revenue_analytics_with(
+            &make_leaderboard_messages(),
+            &Utc,
+            &rates,
+            rates.base(),
+        );
+        // 合計 12,550 円 = 83.67 USD（円建ての金額と USD 建ての金額を合わせた値）
+        let goal = RevenueGoal {
+            target: 100.0,
+            currency: "USD".to_string(),
+        };
+        let progress = analytics.goal_progress(&goal, &rates).unwrap();
+        assert!((progress.raised - 12550.0 / 150.0).abs() < 1e-9);
+        assert!((progress.remaining - (100.0 - 12550.0 / 150.0)).abs() < 1e-9);
+        assert!(!progress.is_reached());
+
+        assert!(
+            analytics
+                .goal_progress(&RevenueGoal::default(), &rates)
+                .is_none()
+        );
+        assert!(
+            compute_revenue_analytics(&make_leaderboard_messages())
+                .goal_progress(&goal, &rates)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn revenue_total_reports_missing_rates_and_unparsed_amounts() {
+        let mut messages = make_leaderboard_messages();
+        messages.push(make_paid_message(
+            "UC_e",
+            "€2,00",
+            1_736_863_500_000_000,
+            false,
+        ));
+        messages.push(make_paid_message(
+            "UC_f",
+            "???",
+            1_736_863_560_000_000,
+            true,
+        ));
+        let rates = StaticRateTable::new("JPY", []);
+
+        let total = compute_revenue_analytics_with(&messages, &Utc, &rates, rates.base())
+            .total
+            .unwrap();
+
+        assert_eq!(total.amount, 11800.0);
+        assert_eq!(total.converted_count, 4);
+        assert_eq!(total.unconverted_currencies, vec!["EUR", "USD"]);
+        assert_eq!(total.unparsed_count, 1);
+    }
+
+    #[test]
+    fn revenue_total_absent_without_converter() {
+        assert!(
+            compute_revenue_analytics(&make_leaderboard_messages())
+                .total
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn leaderboard_by_amount_ranks_within_currency() {
+        let entries = compute_contributor_leaderboard(
+            &make_leaderboard_messages(),
+            LeaderboardSortOrder::ByAmount,
+        );
+
+        let rows: Vec<(usize, &str, &str, f64, usize)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.rank,
+                    e.channel_id.as_str(),
+                    e.currency.as_str(),
+                    e.total_amount,
+                    e.message_count,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (1, "UC_b", "JPY", 10000.0, 1),
+                (2, "UC_a", "JPY", 1800.0, 3),
+                (1, "UC_c", "USD", 5.0, 1),
+            ]
+        );
+        assert_eq!(entries[1].first_contribution, "2025-01-14T14:00:00+00:00");
+        assert_eq!(entries[1].last_contribution, "2025-01-14T14:03:00+00:00");
+    }
+
+    #[test]
+    fn leaderboard_by_message_count() {
+        let entries = compute_contributor_leaderboard(
+            &make_leaderboard_messages(),
+            LeaderboardSortOrder::ByMessageCount,
+        );
+
+        let ranked: Vec<(usize, &str)> = entries
+            .iter()
+            .map(|e| (e.rank, e.channel_id.as_str()))
+            .collect();
+        assert_eq!(ranked, vec![(1, "UC_a"), (2, "UC_b"), (3, "UC_c")]);
+    }
+
+    #[test]
+    fn leaderboard_csv_has_header_and_rows() {
+        let entries = compute_contributor_leaderboard(
+            &make_leaderboard_messages(),
+            LeaderboardSortOrder::ByAmount,
+        );
+        let config = LeaderboardExportConfig {
+            format: "csv".to_string(),
+            sort_order: LeaderboardSortOrder::ByAmount,
+        };
+
+        let csv = String::from_utf8(export_leaderboard(&entries, &config).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "rank,author,channel_id,total_amount,currency,message_count,first_contribution,last_contribution"
+        );
+        assert_eq!(
+            lines[1],
+            "1,\"UC_b_name\",\"UC_b\",10000,\"JPY\",1,\"2025-01-14T14:01:00+00:00\",\"2025-01-14T14:01:00+00:00\""
+        );
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn leaderboard_json_roundtrip() {
+        let entries = compute_contributor_leaderboard(
+            &make_leaderboard_messages(),
+            LeaderboardSortOrder::ByAmount,
+        );
+        let config = LeaderboardExportConfig {
+            format: "json".to_string(),
+            sort_order: LeaderboardSortOrder::ByAmount,
+        };
+
+        let bytes = export_leaderboard(&entries, &config).unwrap();
+        let parsed: Vec<LeaderboardEntry> = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(parsed, entries);
+    }
+
+    #[test]
+    fn leaderboard_rejects_unsupported_format() {
+        let config = LeaderboardExportConfig {
+            format: "parquet".to_string(),
+            sort_order: LeaderboardSortOrder::ByAmount,
+        };
+
+        let err = export_leaderboard(&[], &config).unwrap_err();
+
+        assert!(matches!(err, CommandError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn leaderboard_sort_order_defaults_to_by_amount() {
+        let config: LeaderboardExportConfig = serde_json::from_str(r#"{"format":"csv"}"#).unwrap();
+        assert_eq!(config.sort_order, LeaderboardSortOrder::ByAmount);
+
+        let config: LeaderboardExportConfig =
+            serde_json::from_str(r#"{"format":"csv","sort_order":"by_message_count"}"#).unwrap();
+        assert_eq!(config.sort_order, LeaderboardSortOrder::ByMessageCount);
+    }
+
     // ========================================================================
     // compute_session_analytics_from_rows (07_revenue.md: DB行データから集計)
     // ========================================================================
@@ -1729,6 +3024,8 @@ mod tests {
                 color: None,
                 is_moderator: true,
                 is_verified: false,
+                is_owner: false,
+                sticker: None,
             }),
             is_member: true,
             ..Default::default()
@@ -1920,6 +3217,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let csv = export_to_csv(&data, &config).unwrap();
@@ -1949,6 +3250,10 @@ mod tests {
             include_system_messages: false,
             max_records: None,
             sort_order: None,
+            include_role_columns: false,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
         };
 
         let json = export_to_json(&data, &config).unwrap();
@@ -1973,4 +3278,410 @@ mod tests {
         assert_eq!(second["message_type"], "superchat");
         assert_eq!(second["amount_display"], "$10.00");
     }
+
+    // ========================================================================
+    // ロール列エクスポート (07_revenue.md: include_role_columns)
+    // ========================================================================
+
+    fn make_role_test_messages() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage {
+                id: "mod1".to_string(),
+                author: "Moderator".to_string(),
+                channel_id: "UC_mod".to_string(),
+                metadata: Some(MessageMetadata {
+                    is_moderator: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ChatMessage {
+                id: "mem1".to_string(),
+                author: "Member".to_string(),
+                channel_id: "UC_mem".to_string(),
+                is_member: true,
+                metadata: Some(MessageMetadata {
+                    badge_info: vec![crate::core::BadgeInfo {
+                        badge_type: "member".to_string(),
+                        label: "Member (6 months)".to_string(),
+                        tooltip: Some("Member (6 months)".to_string()),
+                        icon_url: None,
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn make_role_test_config(format: &str, include_role_columns: bool) -> ExportConfig {
+        ExportConfig {
+            format: format.to_string(),
+            include_metadata: false,
+            include_system_messages: false,
+            max_records: None,
+            sort_order: None,
+            include_role_columns,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
+        }
+    }
+
+    #[test]
+    fn convert_messages_to_export_membership_months_from_badge() {
+        // 07_revenue.md: membership_months はメンバーバッジの tooltip から取得する
+        let exports = convert_messages_to_export(&make_role_test_messages(), "s1", "UC_bc");
+
+        assert_eq!(exports[0].membership_months, None);
+        assert_eq!(exports[1].membership_months, Some(6));
+    }
+
+    #[test]
+    fn convert_messages_to_export_membership_months_prefers_milestone() {
+        // 07_revenue.md: マイルストーンメッセージは milestone_months を優先する
+        let messages = vec![ChatMessage {
+            message_type: MessageType::Membership {
+                milestone_months: Some(12),
+            },
+            ..Default::default()
+        }];
+
+        let exports = convert_messages_to_export(&messages, "s1", "UC_bc");
+
+        assert_eq!(exports[0].membership_months, Some(12));
+    }
+
+    #[test]
+    fn export_to_csv_with_role_columns() {
+        // 07_revenue.md: include_role_columns=true でロール列が追加される
+        let mut data = make_test_export_data();
+        data.messages = convert_messages_to_export(&make_role_test_messages(), "s1", "UC_bc");
+
+        let csv = export_to_csv(&data, &make_role_test_config("csv", true)).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert!(lines[0].ends_with(",badges,is_owner,membership_months"));
+        // モデレーター行: is_moderator=true、月数は空
+        assert!(lines[1].starts_with("\"mod1\""));
+        assert!(lines[1].ends_with(",true,false,false,\"\",false,"));
+        // メンバー行: is_member=true、月数=6
+        assert!(lines[2].starts_with("\"mem1\""));
+        assert!(lines[2].ends_with(",false,true,false,\"\",false,6"));
+    }
+
+    #[test]
+    fn export_to_csv_without_role_columns_keeps_spec_header() {
+        // 07_revenue.md: include_role_columns=false では既存のカラム構成を維持する
+        let csv = export_to_csv(
+            &make_test_export_data(),
+            &make_role_test_config("csv", false),
+        )
+        .unwrap();
+
+        assert!(csv.starts_with(
+            "id,timestamp,author,author_id,content,message_type,amount_display,tier,is_moderator,is_member,is_verified,badges\n"
+        ));
+        assert!(!csv.contains("membership_months"));
+    }
+
+    #[test]
+    fn export_to_json_role_fields_follow_config() {
+        // 07_revenue.md: JSON のロールフィールドは include_role_columns に従う
+        let mut data = make_test_export_data();
+        data.messages = convert_messages_to_export(&make_role_test_messages(), "s1", "UC_bc");
+
+        let with_roles = export_to_json(&data, &make_role_test_config("json", true)).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&with_roles).unwrap();
+        assert_eq!(parsed[0]["is_moderator"], true);
+        assert_eq!(parsed[0]["is_owner"], false);
+        assert_eq!(parsed[1]["membership_months"], 6);
+
+        let without_roles = export_to_json(&data, &make_role_test_config("json", false)).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&without_roles).unwrap();
+        assert!(parsed[1].get("membership_months").is_none());
+        assert!(parsed[1].get("is_owner").is_none());
+        // 既存のロール列は常に出力される
+        assert_eq!(parsed[1]["is_member"], true);
+    }
+
+    // ========================================================================
+    // 削除されたメッセージ (07_revenue.md: include_deleted)
+    // ========================================================================
+
+    #[test]
+    fn deleted_messages_are_excluded_unless_requested() {
+        let mut messages = convert_messages_to_export(&make_role_test_messages(), "s1", "UC_bc");
+        messages[0].is_deleted = true;
+
+        let mut config = make_role_test_config("csv", false);
+        let kept = apply_export_filters(messages.clone(), &config);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].id, "mem1");
+
+        config.include_deleted = true;
+        assert_eq!(estimate_export_size(&messages, &config), 2);
+        let kept = apply_export_filters(messages, &config);
+        assert_eq!(kept.len(), 2);
+
+        // 出力に含めても統計では削除済みとして扱う
+        let statistics = calculate_session_statistics(&kept);
+        assert_eq!(statistics.total_messages, 1);
+        assert_eq!(statistics.unique_viewers, 1);
+    }
+
+    #[test]
+    fn is_deleted_column_follows_config() {
+        let mut data = make_test_export_data();
+        data.messages = convert_messages_to_export(&make_role_test_messages(), "s1", "UC_bc");
+        data.messages[0].is_deleted = true;
+
+        let mut config = make_role_test_config("csv", false);
+        let csv = export_to_csv(&data, &config).unwrap();
+        assert!(!csv.contains("is_deleted"));
+        let json = export_to_json(&data, &config).unwrap();
+        assert!(!json.contains("is_deleted"));
+
+        config.include_deleted = true;
+        let csv = export_to_csv(&data, &config).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].ends_with(",badges,is_deleted"));
+        assert!(lines[1].ends_with(",true"));
+        assert!(lines[2].ends_with(",false"));
+        let json = export_to_json(&data, &config).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["is_deleted"], true);
+        assert_eq!(parsed[1]["is_deleted"], false);
+    }
+
+    // ========================================================================
+    // 投稿者・種別の絞り込み (07_revenue.md: author_filter / message_type_filter)
+    // ========================================================================
+
+    fn make_filter_test_config(
+        author_filter: Option<Vec<&str>>,
+        message_type_filter: Option<Vec<&str>>,
+    ) -> ExportConfig {
+        let to_strings = |v: Vec<&str>| v.into_iter().map(String::from).collect();
+        ExportConfig {
+            format: "csv".to_string(),
+            include_metadata: false,
+            include_system_messages: false,
+            max_records: None,
+            sort_order: None,
+            include_role_columns: false,
+            author_filter: author_filter.map(to_strings),
+            message_type_filter: message_type_filter.map(to_strings),
+            include_deleted: false,
+        }
+    }
+
+    fn make_filter_test_messages() -> Vec<ExportMessage> {
+        vec![
+            make_export_message("m1", "UC_a", "text", None),
+            make_export_message("m2", "UC_a", "superchat", Some(SuperChatTier::Blue)),
+            make_export_message("m3", "UC_b", "superchat", Some(SuperChatTier::Red)),
+            make_export_message("m4", "UC_c", "membership", None),
+        ]
+    }
+
+    fn filtered_ids(config: &ExportConfig) -> Vec<String> {
+        apply_export_filters(make_filter_test_messages(), config)
+            .into_iter()
+            .map(|m| m.id)
+            .collect()
+    }
+
+    #[test]
+    fn export_filter_none_keeps_all_messages() {
+        let config = make_filter_test_config(None, None);
+        assert_eq!(filtered_ids(&config), vec!["m1", "m2", "m3", "m4"]);
+    }
+
+    #[test]
+    fn export_filter_by_author() {
+        let config = make_filter_test_config(Some(vec!["UC_a", "UC_c"]), None);
+        assert_eq!(filtered_ids(&config), vec!["m1", "m2", "m4"]);
+    }
+
+    #[test]
+    fn export_filter_by_message_type() {
+        let config = make_filter_test_config(None, Some(vec!["superchat"]));
+        assert_eq!(filtered_ids(&config), vec!["m2", "m3"]);
+    }
+
+    #[test]
+    fn export_filter_author_and_type_are_and_combined() {
+        let config = make_filter_test_config(Some(vec!["UC_a"]), Some(vec!["superchat"]));
+        assert_eq!(filtered_ids(&config), vec!["m2"]);
+    }
+
+    #[test]
+    fn export_filter_applies_max_records_after_filtering() {
+        let mut config = make_filter_test_config(None, Some(vec!["superchat", "membership"]));
+        config.max_records = Some(2);
+        assert_eq!(filtered_ids(&config), vec!["m2", "m3"]);
+    }
+
+    #[test]
+    fn estimate_export_size_matches_filtered_export() {
+        let mut configs = vec![
+            make_filter_test_config(None, None),
+            make_filter_test_config(Some(vec!["UC_a"]), None),
+            make_filter_test_config(Some(vec!["UC_a"]), Some(vec!["superchat"])),
+            make_filter_test_config(Some(vec!["UC_z"]), None),
+        ];
+        let mut limited = make_filter_test_config(None, Some(vec!["superchat", "membership"]));
+        limited.max_records = Some(2);
+        configs.push(limited);
+
+        let messages = make_filter_test_messages();
+        for config in &configs {
+            assert_eq!(
+                estimate_export_size(&messages, config),
+                filtered_ids(config).len()
+            );
+        }
+        assert_eq!(estimate_export_size(&messages, &configs[3]), 0);
+    }
+
+    #[test]
+    fn validate_export_config_accepts_none_and_non_empty_filters() {
+        assert!(validate_export_config(&make_filter_test_config(None, None)).is_ok());
+        assert!(
+            validate_export_config(&make_filter_test_config(
+                Some(vec!["UC_a"]),
+                Some(vec!["superchat", "membership_gift"])
+            ))
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_export_config_rejects_empty_filters() {
+        let err = validate_export_config(&make_filter_test_config(Some(vec![]), None)).unwrap_err();
+        assert!(matches!(err, CommandError::InvalidInput(_)));
+
+        let err = validate_export_config(&make_filter_test_config(None, Some(vec![]))).unwrap_err();
+        assert!(matches!(err, CommandError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn validate_export_config_rejects_unknown_message_type() {
+        let err = validate_export_config(&make_filter_test_config(None, Some(vec!["sticker"])))
+            .unwrap_err();
+        assert!(matches!(err, CommandError::InvalidInput(msg) if msg.contains("sticker")));
+    }
+
+    #[test]
+    fn export_config_filters_default_to_none_when_omitted() {
+        let config: ExportConfig = serde_json::from_str(
+            r#"{"format":"csv","include_metadata":true,"include_system_messages":false,"max_records":null,"sort_order":null}"#,
+        )
+        .unwrap();
+        assert!(config.author_filter.is_none());
+        assert!(config.message_type_filter.is_none());
+    }
+
+    // ========================================================================
+    // ストリーミング書き出し (07_revenue.md: 書き出し方式)
+    // ========================================================================
+
+    /// write 呼び出し回数を数えるライター
+    struct CountingWriter {
+        buf: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.buf.extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// 常に失敗するライター
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _data: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_export_csv_streams_rows_incrementally() {
+        let data = make_test_export_data();
+        let config = make_filter_test_config(None, None);
+        let mut writer = CountingWriter {
+            buf: Vec::new(),
+            writes: 0,
+        };
+
+        write_export(&data, &config, &mut writer).unwrap();
+
+        // 全体を一度に書き込まず、ヘッダーと各行を逐次書き込む
+        assert!(writer.writes > data.messages.len());
+        assert_eq!(
+            String::from_utf8(writer.buf).unwrap(),
+            export_to_csv(&data, &config).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_export_json_matches_buffered_output() {
+        let data = make_test_export_data();
+        let mut config = make_filter_test_config(None, None);
+        config.format = "json".to_string();
+        let mut buf = Vec::new();
+
+        write_export(&data, &config, &mut buf).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            export_to_json(&data, &config).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_export_reports_io_error() {
+        let data = make_test_export_data();
+        let config = make_filter_test_config(None, None);
+
+        let err = write_export(&data, &config, &mut FailingWriter).unwrap_err();
+
+        assert!(matches!(err, CommandError::IoError(msg) if msg.contains("disk full")));
+    }
+
+    #[test]
+    fn write_export_file_writes_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+        let data = make_test_export_data();
+        let config = make_filter_test_config(None, None);
+
+        write_export_file(path.to_str().unwrap(), &data, &config).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, export_to_csv(&data, &config).unwrap());
+    }
+
+    #[test]
+    fn validate_export_config_rejects_unsupported_format() {
+        let mut config = make_filter_test_config(None, None);
+        config.format = "xml".to_string();
+
+        let err = validate_export_config(&config).unwrap_err();
+
+        assert!(matches!(err, CommandError::InvalidInput(msg) if msg.contains("xml")));
+    }
 }
@@ -0,0 +1,676 @@
+//! エクスポートファイル（CSV / JSON）の読み込み
+//!
+//! DB を削除した後でも、以前エクスポートしたファイルからアナリティクスを作り直せるよう、
+//! `write_csv` / `export_to_json` の出力を `SessionExportData` に戻す。
+//! 解釈できない行（レコード）はスキップして警告として返し、読み込みは中断しない。
+//! エラーになるのはファイル自体を読めない・ヘッダーが不正な場合のみ。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+use super::{
+    EXPORT_MESSAGE_TYPES, ExportMessage, SessionExportData, SessionMetadata, SuperChatTier,
+    calculate_session_statistics,
+};
+use crate::core::{ChatMessage, MessageMetadata, MessageType, SuperChatColors};
+
+/// `ImportWarning::message` に残すレコードの最大文字数
+const WARNING_RECORD_MAX_CHARS: usize = 200;
+
+/// CSV のデータ行に必須の列（`write_csv` のヘッダー順）
+const CSV_REQUIRED_COLUMNS: [&str; 12] = [
+    "id",
+    "timestamp",
+    "author",
+    "author_id",
+    "content",
+    "message_type",
+    "amount_display",
+    "tier",
+    "is_moderator",
+    "is_member",
+    "is_verified",
+    "badges",
+];
+
+/// エクスポートファイルの読み込みエラー
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Failed to read export file: {0}")]
+    Io(#[from] std::io::Error),
+    /// CSV のヘッダー行がない
+    #[error("CSV header row not found")]
+    MissingHeader,
+    /// CSV のヘッダーに必須の列がない
+    #[error("CSV header is missing column: {0}")]
+    MissingColumn(&'static str),
+    /// JSON として読めない、またはメッセージの配列がない
+    #[error("Invalid export JSON: {0}")]
+    InvalidJson(String),
+}
+
+/// 読み込み時にスキップしたレコード
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ImportWarning {
+    /// CSV はレコードの開始行（1始まり）、JSON はメッセージの番号（1始まり）
+    pub record: usize,
+    /// スキップした理由
+    pub message: String,
+}
+
+/// 読み込んだセッション
+#[derive(Debug, Clone)]
+pub struct ImportedSession {
+    /// メッセージから集計し直した統計を含むセッションデータ
+    pub data: SessionExportData,
+    pub warnings: Vec<ImportWarning>,
+}
+
+/// CSV エクスポートを読み込む
+///
+/// `# Metadata` ブロックがあればセッションのメタデータに戻す（なければファイル名をセッション ID にする）。
+/// 列は名前で対応付けるため、ロール列（`is_owner`, `membership_months`）はなくてもよい。
+pub fn import_csv(path: impl AsRef<Path>) -> Result<ImportedSession, ImportError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+    let mut records = CsvRecords::new(text.trim_start_matches('\u{FEFF}'));
+
+    // メタデータ行（"# Key,Value"）と空行を読み飛ばしてヘッダー行を探す
+    let mut meta = HashMap::new();
+    let header = loop {
+        let Some((_, fields)) = records.next() else {
+            return Err(ImportError::MissingHeader);
+        };
+        match fields.first().map(String::as_str) {
+            Some(first) if first.starts_with('#') => {
+                if let Some(key) = first.strip_prefix("# ") {
+                    meta.insert(key.to_string(), fields[1..].join(","));
+                }
+            }
+            Some("") | None if fields.len() <= 1 => {}
+            _ => break fields,
+        }
+    };
+    let columns: HashMap<&str, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.trim(), i))
+        .collect();
+    if let Some(missing) = CSV_REQUIRED_COLUMNS
+        .iter()
+        .find(|name| !columns.contains_key(*name))
+    {
+        return Err(ImportError::MissingColumn(missing));
+    }
+
+    let mut messages = Vec::new();
+    let mut warnings = Vec::new();
+    for (line, fields) in records {
+        if fields.len() == 1 && fields[0].is_empty() {
+            continue;
+        }
+        match csv_message(&columns, &fields) {
+            Ok(msg) => messages.push(msg),
+            Err(message) => warnings.push(ImportWarning {
+                record: line,
+                message: format!("{} ({})", message, truncate(&fields.join(","))),
+            }),
+        }
+    }
+
+    let declared_total = meta.get("Total Messages").and_then(|v| v.parse().ok());
+    let metadata = SessionMetadata {
+        session_id: meta.remove("Session ID").unwrap_or_else(|| file_stem(path)),
+        stream_title: meta.remove("Stream Title"),
+        stream_url: meta.remove("Stream URL"),
+        broadcaster_name: meta.remove("Channel"),
+        broadcaster_channel_id: None,
+        start_time: meta
+            .remove("Start Time")
+            .unwrap_or_else(|| first_timestamp(&messages)),
+        end_time: meta.remove("End Time"),
+        export_time: meta
+            .remove("Export Time")
+            .unwrap_or_else(|| Utc::now().to_rfc3339()),
+    };
+    Ok(finish(metadata, messages, warnings, declared_total))
+}
+
+/// JSON エクスポートを読み込む
+///
+/// メタデータ付き（`{ metadata, messages, statistics }`）とメッセージの配列のみの両方に対応する。
+/// ロール列を除いて出力したメッセージは `is_owner = false`・`membership_months = None` として読む。
+pub fn import_json(path: impl AsRef<Path>) -> Result<ImportedSession, ImportError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+    let value: Value =
+        serde_json::from_str(&text).map_err(|e| ImportError::InvalidJson(e.to_string()))?;
+
+    let (metadata, items, declared_total) = match value {
+        Value::Array(items) => (None, items, None),
+        Value::Object(mut object) => {
+            let Some(Value::Array(items)) = object.remove("messages") else {
+                return Err(ImportError::InvalidJson(
+                    "\"messages\" array not found".to_string(),
+                ));
+            };
+            let metadata = object
+                .remove("metadata")
+                .and_then(|m| serde_json::from_value::<SessionMetadata>(m).ok());
+            let declared_total = object
+                .get("statistics")
+                .and_then(|s| s.get("total_messages"))
+                .and_then(Value::as_u64)
+                .and_then(|n| usize::try_from(n).ok());
+            (metadata, items, declared_total)
+        }
+        _ => {
+            return Err(ImportError::InvalidJson(
+                "expected an object or an array".to_string(),
+            ));
+        }
+    };
+
+    let mut messages = Vec::new();
+    let mut warnings = Vec::new();
+    for (i, mut item) in items.into_iter().enumerate() {
+        if let Value::Object(fields) = &mut item {
+            fields.entry("is_owner").or_insert(Value::Bool(false));
+            fields.entry("membership_months").or_insert(Value::Null);
+        }
+        let parsed = serde_json::from_value::<ExportMessage>(item.clone())
+            .map_err(|e| e.to_string())
+            .and_then(|msg| {
+                check_message_type(&msg.message_type)?;
+                Ok(msg)
+            });
+        match parsed {
+            Ok(msg) => messages.push(msg),
+            Err(message) => warnings.push(ImportWarning {
+                record: i + 1,
+                message: format!("{} ({})", message, truncate(&item.to_string())),
+            }),
+        }
+    }
+
+    let metadata = metadata.unwrap_or_else(|| SessionMetadata {
+        session_id: file_stem(path),
+        stream_title: None,
+        stream_url: None,
+        broadcaster_name: None,
+        broadcaster_channel_id: None,
+        start_time: first_timestamp(&messages),
+        end_time: None,
+        export_time: Utc::now().to_rfc3339(),
+    });
+    Ok(finish(metadata, messages, warnings, declared_total))
+}
+
+/// 統計を集計し直し、エクスポート時の件数と合わなければ警告を加える
+fn finish(
+    metadata: SessionMetadata,
+    messages: Vec<ExportMessage>,
+    mut warnings: Vec<ImportWarning>,
+    declared_total: Option<usize>,
+) -> ImportedSession {
+    let statistics = calculate_session_statistics(&messages);
+    if let Some(declared) = declared_total.filter(|&n| n != statistics.total_messages) {
+        warnings.push(ImportWarning {
+            record: 0,
+            message: format!(
+                "Export declared {} messages but {} were imported",
+                declared, statistics.total_messages
+            ),
+        });
+    }
+    ImportedSession {
+        data: SessionExportData {
+            metadata,
+            messages,
+            statistics,
+        },
+        warnings,
+    }
+}
+
+/// CSV のデータ行を `ExportMessage` に戻す
+fn csv_message(columns: &HashMap<&str, usize>, fields: &[String]) -> Result<ExportMessage, String> {
+    let field = |name: &str| -> Result<&str, String> {
+        columns
+            .get(name)
+            .and_then(|&i| fields.get(i))
+            .map(String::as_str)
+            .ok_or_else(|| format!("Missing field '{}'", name))
+    };
+    let optional = |name: &str| -> Option<&str> {
+        columns
+            .get(name)
+            .and_then(|&i| fields.get(i))
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    };
+    let flag = |name: &str| -> Result<bool, String> {
+        let value = field(name)?;
+        value
+            .parse()
+            .map_err(|_| format!("Invalid boolean in '{}': {}", name, value))
+    };
+
+    let message_type = field("message_type")?;
+    check_message_type(message_type)?;
+    let tier = optional("tier")
+        .map(|t| {
+            serde_json::from_value::<SuperChatTier>(Value::String(t.to_string()))
+                .map_err(|_| format!("Invalid tier: {}", t))
+        })
+        .transpose()?;
+    let membership_months = optional("membership_months")
+        .map(|m| {
+            m.parse()
+                .map_err(|_| format!("Invalid membership_months: {}", m))
+        })
+        .transpose()?;
+    let is_owner = match optional("is_owner") {
+        Some(_) => flag("is_owner")?,
+        None => false,
+    };
+    let is_deleted = match optional("is_deleted") {
+        Some(_) => flag("is_deleted")?,
+        None => false,
+    };
+
+    Ok(ExportMessage {
+        id: field("id")?.to_string(),
+        timestamp: field("timestamp")?.to_string(),
+        author: field("author")?.to_string(),
+        author_id: field("author_id")?.to_string(),
+        content: field("content")?.to_string(),
+        message_type: message_type.to_string(),
+        amount_display: optional("amount_display").map(str::to_string),
+        tier,
+        is_moderator: flag("is_moderator")?,
+        is_member: flag("is_member")?,
+        is_verified: flag("is_verified")?,
+        is_owner,
+        membership_months,
+        badges: optional("badges")
+            .map(|b| b.split(';').map(str::to_string).collect())
+            .unwrap_or_default(),
+        is_deleted,
+    })
+}
+
+fn check_message_type(message_type: &str) -> Result<(), String> {
+    if EXPORT_MESSAGE_TYPES.contains(&message_type) {
+        Ok(())
+    } else {
+        Err(format!("Unknown message_type: {}", message_type))
+    }
+}
+
+/// 読み込んだメッセージをアナリティクスの集計に渡せる `ChatMessage` に戻す
+///
+/// SuperChat の tier はヘッダー色として持たせ、集計時の色判定で同じ tier に戻るようにする。
+/// メンバーシップギフトの件数はエクスポートに含まれないため 0 とする。
+pub(crate) fn to_chat_messages(messages: &[ExportMessage]) -> Vec<ChatMessage> {
+    messages
+        .iter()
+        .map(|msg| {
+            let amount = msg.amount_display.clone().unwrap_or_default();
+            let message_type = match msg.message_type.as_str() {
+                "superchat" => MessageType::SuperChat { amount },
+                "supersticker" => MessageType::SuperSticker { amount },
+                "membership" => MessageType::Membership {
+                    milestone_months: msg.membership_months,
+                },
+                "membership_gift" => MessageType::MembershipGift { gift_count: 0 },
+                "system" => MessageType::System,
+                _ => MessageType::Text,
+            };
+            let superchat_colors = msg.tier.map(|tier| SuperChatColors {
+                header_background: format!(
+                    "#{:06x}",
+                    super::ndjson_export::tier_header_color(tier) & 0xFF_FFFF
+                ),
+                header_text: String::new(),
+                body_background: String::new(),
+                body_text: String::new(),
+            });
+            let timestamp_usec = DateTime::parse_from_rfc3339(&msg.timestamp)
+                .map(|t| t.timestamp_micros().to_string())
+                .unwrap_or_default();
+            ChatMessage {
+                id: msg.id.clone(),
+                timestamp: msg.timestamp.clone(),
+                timestamp_usec,
+                message_type,
+                author: msg.author.clone(),
+                channel_id: msg.author_id.clone(),
+                content: msg.content.clone(),
+                metadata: Some(MessageMetadata {
+                    amount: msg.amount_display.clone(),
+                    badges: msg.badges.clone(),
+                    is_moderator: msg.is_moderator,
+                    is_verified: msg.is_verified,
+                    is_owner: msg.is_owner,
+                    superchat_colors,
+                    ..Default::default()
+                }),
+                is_member: msg.is_member,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn first_timestamp(messages: &[ExportMessage]) -> String {
+    messages
+        .first()
+        .map(|m| m.timestamp.clone())
+        .unwrap_or_default()
+}
+
+fn truncate(record: &str) -> String {
+    record.chars().take(WARNING_RECORD_MAX_CHARS).collect()
+}
+
+/// CSV のレコードを順に返す（引用符内の `""` と改行に対応する）
+///
+/// 各要素はレコードの開始行（1始まり）とフィールド。
+struct CsvRecords<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> CsvRecords<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+        }
+    }
+}
+
+impl Iterator for CsvRecords<'_> {
+    type Item = (usize, Vec<String>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chars.peek()?;
+        let start_line = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        while let Some(c) = self.chars.next() {
+            match c {
+                '"' if in_quotes => {
+                    if self.chars.peek() == Some(&'"') {
+                        self.chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                }
+                '"' if field.is_empty() => in_quotes = true,
+                ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+                '\n' => {
+                    self.line += 1;
+                    if in_quotes {
+                        field.push(c);
+                    } else {
+                        break;
+                    }
+                }
+                '\r' if !in_quotes && self.chars.peek() == Some(&'\n') => {}
+                _ => field.push(c),
+            }
+        }
+        fields.push(field);
+        Some((start_line, fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ExportConfig, SessionStatistics, write_export_file};
+    use super::*;
+
+    fn make_message(id: &str, second: u32, message_type: &str) -> ExportMessage {
+        ExportMessage {
+            id: id.to_string(),
+            timestamp: format!("2025-01-14T14:00:{:02}+00:00", second),
+            author: format!("User \"{}\"", id),
+            author_id: format!("UC_{}", id),
+            content: format!("line1, \"quoted\"\nline2 {}", id),
+            message_type: message_type.to_string(),
+            amount_display: None,
+            tier: None,
+            is_moderator: false,
+            is_member: false,
+            is_verified: false,
+            is_owner: false,
+            membership_months: None,
+            badges: vec![],
+            is_deleted: false,
+        }
+    }
+
+    fn sample_data() -> SessionExportData {
+        let mut superchat = make_message("sc", 10, "superchat");
+        superchat.amount_display = Some("¥1,000".to_string());
+        superchat.tier = Some(SuperChatTier::Yellow);
+        superchat.is_member = true;
+        superchat.membership_months = Some(6);
+        superchat.badges = vec!["Member (6 months)".to_string(), "Moderator".to_string()];
+        superchat.is_moderator = true;
+        let mut sticker = make_message("st", 20, "supersticker");
+        sticker.amount_display = Some("$5.00".to_string());
+        let mut owner = make_message("owner", 30, "text");
+        owner.is_owner = true;
+        owner.is_verified = true;
+
+        let messages = vec![
+            make_message("a", 0, "text"),
+            superchat,
+            sticker,
+            make_message("m", 25, "membership"),
+            owner,
+        ];
+        SessionExportData {
+            metadata: SessionMetadata {
+                session_id: "session-1".to_string(),
+                stream_title: Some("Title".to_string()),
+                stream_url: Some("https://www.youtube.com/watch?v=abc".to_string()),
+                broadcaster_name: Some("Channel".to_string()),
+                broadcaster_channel_id: None,
+                start_time: "2025-01-14T14:00:00+00:00".to_string(),
+                end_time: None,
+                export_time: "2025-01-14T15:00:00+00:00".to_string(),
+            },
+            statistics: calculate_session_statistics(&messages),
+            messages,
+        }
+    }
+
+    fn config(format: &str) -> ExportConfig {
+        ExportConfig {
+            format: format.to_string(),
+            include_metadata: true,
+            include_system_messages: false,
+            max_records: None,
+            sort_order: None,
+            include_role_columns: true,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
+        }
+    }
+
+    fn assert_same_totals(a: &SessionStatistics, b: &SessionStatistics) {
+        assert_eq!(a.total_messages, b.total_messages);
+        assert_eq!(a.unique_viewers, b.unique_viewers);
+        assert_eq!(a.super_chat_count, b.super_chat_count);
+        assert_eq!(a.membership_count, b.membership_count);
+        assert_eq!(a.super_chat_by_tier.total(), b.super_chat_by_tier.total());
+        assert_eq!(
+            a.super_chat_by_tier.tier_yellow,
+            b.super_chat_by_tier.tier_yellow
+        );
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+        let data = sample_data();
+        write_export_file(path.to_str().unwrap(), &data, &config("csv")).unwrap();
+
+        let imported = import_csv(&path).unwrap();
+        assert!(imported.warnings.is_empty(), "{:?}", imported.warnings);
+        assert_eq!(imported.data.messages, data.messages);
+        assert_same_totals(&imported.data.statistics, &data.statistics);
+        assert_eq!(imported.data.metadata.session_id, "session-1");
+        assert_eq!(
+            imported.data.metadata.stream_title.as_deref(),
+            Some("Title")
+        );
+        assert_eq!(
+            imported.data.metadata.broadcaster_name.as_deref(),
+            Some("Channel")
+        );
+    }
+
+    #[test]
+    fn csv_without_metadata_or_role_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.csv");
+        let data = sample_data();
+        let config = ExportConfig {
+            include_metadata: false,
+            include_role_columns: false,
+            ..config("csv")
+        };
+        write_export_file(path.to_str().unwrap(), &data, &config).unwrap();
+
+        let imported = import_csv(&path).unwrap();
+        assert!(imported.warnings.is_empty());
+        assert_eq!(imported.data.metadata.session_id, "plain");
+        assert_eq!(
+            imported.data.metadata.start_time,
+            "2025-01-14T14:00:00+00:00"
+        );
+        assert_same_totals(&imported.data.statistics, &data.statistics);
+        assert!(imported.data.messages.iter().all(|m| !m.is_owner));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = sample_data();
+
+        let path = dir.path().join("export.json");
+        write_export_file(path.to_str().unwrap(), &data, &config("json")).unwrap();
+        let imported = import_json(&path).unwrap();
+        assert!(imported.warnings.is_empty());
+        assert_eq!(imported.data.messages, data.messages);
+        assert_eq!(imported.data.metadata.session_id, "session-1");
+        assert_same_totals(&imported.data.statistics, &data.statistics);
+
+        // メッセージの配列のみ・ロール列なし
+        let path = dir.path().join("messages.json");
+        let config = ExportConfig {
+            include_metadata: false,
+            include_role_columns: false,
+            ..config("json")
+        };
+        write_export_file(path.to_str().unwrap(), &data, &config).unwrap();
+        let imported = import_json(&path).unwrap();
+        assert!(imported.warnings.is_empty());
+        assert_eq!(imported.data.metadata.session_id, "messages");
+        assert_same_totals(&imported.data.statistics, &data.statistics);
+    }
+
+    #[test]
+    fn malformed_csv_rows_become_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.csv");
+        std::fs::write(
+            &path,
+            "# Metadata\n# Session ID,s\n# Total Messages,3\n\n\
+             id,timestamp,author,author_id,content,message_type,amount_display,tier,is_moderator,is_member,is_verified,badges\n\
+             \"1\",\"t\",\"a\",\"UC_a\",\"ok\",\"text\",\"\",\"\",false,false,false,\"\"\n\
+             \"2\",\"t\",\"a\",\"UC_a\",\"bad\",\"text\",\"\",\"\",maybe,false,false,\"\"\n\
+             \"3\",\"t\",\"a\",\"UC_a\",\"bad\",\"superchat\",\"\",\"purple\",false,false,false,\"\"\n\
+             \"4\",\"t\",\"a\",\"UC_a\",\"bad\",\"poll\",\"\",\"\",false,false,false,\"\"\n\
+             \"5\",\"t\"\n",
+        )
+        .unwrap();
+
+        let imported = import_csv(&path).unwrap();
+        assert_eq!(imported.data.messages.len(), 1);
+        let records: Vec<usize> = imported.warnings.iter().map(|w| w.record).collect();
+        // 件数の不一致はレコード 0 の警告として最後に加わる
+        assert_eq!(records, [7, 8, 9, 10, 0]);
+        assert!(imported.warnings[0].message.contains("is_moderator"));
+        assert!(imported.warnings[1].message.contains("Invalid tier"));
+        assert!(
+            imported.warnings[2]
+                .message
+                .contains("Unknown message_type")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_headers_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.csv");
+        std::fs::write(&path, "id,timestamp,author\n\"1\",\"t\",\"a\"\n").unwrap();
+        assert!(matches!(
+            import_csv(&path),
+            Err(ImportError::MissingColumn("author_id"))
+        ));
+        std::fs::write(&path, "# Metadata\n\n").unwrap();
+        assert!(matches!(import_csv(&path), Err(ImportError::MissingHeader)));
+
+        let path = dir.path().join("bad.json");
+        std::fs::write(&path, r#"{"metadata": {}}"#).unwrap();
+        assert!(matches!(
+            import_json(&path),
+            Err(ImportError::InvalidJson(_))
+        ));
+        std::fs::write(&path, "[{\"id\": 1}, ").unwrap();
+        assert!(matches!(
+            import_json(&path),
+            Err(ImportError::InvalidJson(_))
+        ));
+
+        assert!(matches!(
+            import_csv(dir.path().join("missing.csv")),
+            Err(ImportError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn chat_messages_keep_tier_and_roles() {
+        let data = sample_data();
+        let chat = to_chat_messages(&data.messages);
+        let analytics = super::super::compute_revenue_analytics_in(&chat, &Utc);
+        assert_eq!(analytics.super_chat_count, 1);
+        assert_eq!(analytics.super_chat_by_tier.tier_yellow, 1);
+        assert_eq!(analytics.super_sticker_count, 1);
+        assert_eq!(analytics.membership_gains, 1);
+        assert_eq!(analytics.hourly_stats[0].message_count, 5);
+        assert!(chat[4].author_role().is_owner);
+    }
+}
@@ -0,0 +1,266 @@
+//! 保存ファイル形式（NDJSON）のエクスポート
+//!
+//! エクスポートしたメッセージを編集して再生モードに読み込み直せるよう、
+//! 生レスポンスの保存ファイルと同じ `ResponseEntry` を 1 行 1 件で書き出す。
+//! 各行の `response` は 1 件の `addChatItemAction` を持つ continuation レスポンスで、
+//! チャットパーサーが ExportMessage と同じ内容（投稿者・ロール・金額・tier など）に戻せる形にする。
+//! システムメッセージは対応する renderer がないため出力しない。
+
+use std::io::Write;
+
+use chrono::DateTime;
+use serde_json::{Value, json};
+
+use super::{ExportMessage, SessionExportData, SuperChatTier};
+use crate::core::raw_response::ResponseEntry;
+
+/// NDJSON 形式で書き出す（1 メッセージごとに 1 行ずつ書き込む）
+pub(super) fn write_ndjson(data: &SessionExportData, w: &mut dyn Write) -> std::io::Result<()> {
+    // RFC3339 として読めない投稿時刻は直前のメッセージ（最初はセッション開始）の時刻を使う
+    let mut prev_usec = timestamp_usec(&data.metadata.start_time).unwrap_or(0);
+    for msg in &data.messages {
+        let usec = timestamp_usec(&msg.timestamp).unwrap_or(prev_usec);
+        prev_usec = usec;
+        let Some(item) = chat_item(msg, usec) else {
+            continue;
+        };
+        let entry = ResponseEntry {
+            timestamp: usec.div_euclid(1_000_000),
+            response: json!({
+                "continuationContents": {
+                    "liveChatContinuation": {
+                        "actions": [{ "addChatItemAction": { "item": item } }]
+                    }
+                }
+            }),
+        };
+        serde_json::to_writer(&mut *w, &entry)?;
+        w.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn timestamp_usec(timestamp: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.timestamp_micros())
+}
+
+/// tier ごとの SuperChat ヘッダー色（ARGB。読み込み時の色判定で同じ tier に戻る）
+pub(super) fn tier_header_color(tier: SuperChatTier) -> i64 {
+    let rgb = match tier {
+        SuperChatTier::Blue => 0x1565C0,
+        SuperChatTier::Cyan => 0x00B8D4,
+        SuperChatTier::Green => 0x00E676,
+        SuperChatTier::Yellow => 0xFFB300,
+        SuperChatTier::Orange => 0xE65100,
+        SuperChatTier::Magenta => 0xC2185B,
+        SuperChatTier::Red => 0xE62117,
+    };
+    0xFF00_0000 | rgb
+}
+
+/// ロール・メンバーシップを表す投稿者バッジ
+fn author_badges(msg: &ExportMessage) -> Vec<Value> {
+    let mut badges = Vec::new();
+    for (enabled, icon_type, tooltip) in [
+        (msg.is_owner, "OWNER", "Owner"),
+        (msg.is_moderator, "MODERATOR", "Moderator"),
+        (msg.is_verified, "VERIFIED", "Verified"),
+    ] {
+        if enabled {
+            badges.push(json!({
+                "liveChatAuthorBadgeRenderer": {
+                    "icon": { "iconType": icon_type },
+                    "tooltip": tooltip
+                }
+            }));
+        }
+    }
+    if msg.is_member {
+        let tooltip = match msg.membership_months {
+            Some(months) => format!("Member ({} months)", months),
+            None => "Member".to_string(),
+        };
+        badges.push(json!({
+            "liveChatAuthorBadgeRenderer": {
+                "customThumbnail": { "thumbnails": [] },
+                "tooltip": tooltip
+            }
+        }));
+    }
+    badges
+}
+
+/// メッセージの種別に応じたチャットアイテム（出力しない種別は None）
+fn chat_item(msg: &ExportMessage, usec: i64) -> Option<Value> {
+    let mut renderer = json!({
+        "id": msg.id,
+        "timestampUsec": usec.to_string(),
+        "authorName": { "simpleText": msg.author },
+        "authorExternalChannelId": msg.author_id,
+        "authorBadges": author_badges(msg),
+    });
+    let amount = msg.amount_display.as_deref().unwrap_or_default();
+    let message = json!({ "runs": [{ "text": msg.content }] });
+
+    let key = match msg.message_type.as_str() {
+        "text" => {
+            renderer["message"] = message;
+            "liveChatTextMessageRenderer"
+        }
+        "superchat" => {
+            renderer["purchaseAmountText"] = json!({ "simpleText": amount });
+            if !msg.content.is_empty() {
+                renderer["message"] = message;
+            }
+            if let Some(tier) = msg.tier {
+                renderer["headerBackgroundColor"] = json!(tier_header_color(tier));
+            }
+            "liveChatPaidMessageRenderer"
+        }
+        "supersticker" => {
+            renderer["purchaseAmountText"] = json!({ "simpleText": amount });
+            "liveChatPaidStickerRenderer"
+        }
+        "membership" => {
+            renderer["headerSubtext"] = json!({ "simpleText": msg.content });
+            if let Some(months) = msg.membership_months {
+                renderer["headerPrimaryText"] =
+                    json!({ "simpleText": format!("Member for {} months", months) });
+            }
+            "liveChatMembershipItemRenderer"
+        }
+        "membership_gift" => {
+            // ギフトの投稿者情報は header 側に置く
+            renderer = json!({
+                "id": msg.id,
+                "timestampUsec": usec.to_string(),
+                "authorExternalChannelId": msg.author_id,
+                "header": {
+                    "liveChatSponsorshipsHeaderRenderer": {
+                        "authorName": { "simpleText": msg.author },
+                        "primaryText": { "simpleText": msg.content }
+                    }
+                }
+            });
+            "liveChatSponsorshipsGiftPurchaseAnnouncementRenderer"
+        }
+        _ => return None,
+    };
+    Some(json!({ key: renderer }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{SessionMetadata, SessionStatistics, convert_messages_to_export};
+    use super::*;
+    use crate::core::raw_response::parse_ndjson_file_lenient;
+    use crate::core::replay::replay_items;
+
+    fn make_message(id: &str, second: u32, message_type: &str) -> ExportMessage {
+        ExportMessage {
+            id: id.to_string(),
+            timestamp: format!("2025-01-14T14:00:{:02}+00:00", second),
+            author: format!("User {}", id),
+            author_id: format!("UC_{}", id),
+            content: format!("content {}", id),
+            message_type: message_type.to_string(),
+            amount_display: None,
+            tier: None,
+            is_moderator: false,
+            is_member: false,
+            is_verified: false,
+            is_owner: false,
+            membership_months: None,
+            badges: vec![],
+            is_deleted: false,
+        }
+    }
+
+    fn make_data(messages: Vec<ExportMessage>) -> SessionExportData {
+        SessionExportData {
+            metadata: SessionMetadata {
+                session_id: "session-1".to_string(),
+                stream_title: None,
+                stream_url: None,
+                broadcaster_name: None,
+                broadcaster_channel_id: None,
+                start_time: "2025-01-14T14:00:00+00:00".to_string(),
+                end_time: None,
+                export_time: "2025-01-14T15:00:00+00:00".to_string(),
+            },
+            messages,
+            statistics: SessionStatistics {
+                total_messages: 0,
+                unique_viewers: 0,
+                super_chat_count: 0,
+                super_chat_by_tier: Default::default(),
+                membership_count: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn ndjson_export_round_trips_through_replay_reader() {
+        let mut moderator = make_message("text1", 1, "text");
+        moderator.is_moderator = true;
+        moderator.is_member = true;
+        let mut owner = make_message("text2", 2, "text");
+        owner.is_owner = true;
+        owner.is_verified = true;
+        let mut superchat = make_message("sc1", 3, "superchat");
+        superchat.amount_display = Some("$10.00".to_string());
+        superchat.tier = Some(SuperChatTier::Magenta);
+        let mut sticker = make_message("st1", 4, "supersticker");
+        sticker.amount_display = Some("¥500".to_string());
+        sticker.content = "[Sticker]".to_string();
+        let mut milestone = make_message("mem1", 5, "membership");
+        milestone.is_member = true;
+        milestone.membership_months = Some(6);
+        let mut gift = make_message("gift1", 6, "membership_gift");
+        gift.is_member = true;
+        gift.content = "Sent 5 gift memberships".to_string();
+        let messages = vec![moderator, owner, superchat, sticker, milestone, gift];
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.ndjson");
+        let mut buf = Vec::new();
+        write_ndjson(&make_data(messages.clone()), &mut buf).unwrap();
+        std::fs::write(&path, &buf).unwrap();
+
+        let (entries, warnings) = parse_ndjson_file_lenient(&path).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(entries.len(), messages.len());
+        assert_eq!(entries[0].timestamp, 1_736_863_201);
+
+        let parsed: Vec<_> = replay_items(&entries)
+            .into_iter()
+            .map(|item| item.message)
+            .collect();
+        assert_eq!(parsed[0].timestamp_usec, "1736863201000000");
+        assert_eq!(convert_messages_to_export(&parsed, "", ""), messages);
+    }
+
+    #[test]
+    fn ndjson_export_skips_system_messages_and_keeps_order_for_unparsable_time() {
+        let mut late = make_message("text2", 0, "text");
+        late.timestamp = "14:00:09".to_string();
+        let messages = vec![
+            make_message("text1", 5, "text"),
+            make_message("sys1", 6, "system"),
+            late,
+        ];
+        let mut buf = Vec::new();
+        write_ndjson(&make_data(messages), &mut buf).unwrap();
+
+        let lines: Vec<ResponseEntry> = String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        // 読めない時刻は直前のメッセージ（出力しないシステムメッセージを含む）の時刻になる
+        assert_eq!(lines[1].timestamp, lines[0].timestamp + 1);
+    }
+}
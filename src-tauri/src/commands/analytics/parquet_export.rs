@@ -0,0 +1,383 @@
+//! Parquet 形式のエクスポート（`parquet` feature で有効）
+//!
+//! pandas / Polars でのオフライン分析向けに、ExportMessage の各フィールドを型付きの列として書き出す。
+//! タイムスタンプは UTC のマイクロ秒、金額は float64、メッセージ種別・通貨・tier は辞書エンコードの文字列とする。
+
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::types::Int32Type;
+use arrow_array::{
+    ArrayRef, BooleanArray, DictionaryArray, Float64Array, RecordBatch, StringArray,
+    TimestampMicrosecondArray, UInt32Array,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+
+use super::{ExportConfig, ExportMessage, SessionExportData};
+use crate::core::api::parse_purchase_amount;
+use crate::errors::CommandError;
+
+/// 1 回の書き込み（RecordBatch）に含める行数
+const BATCH_ROWS: usize = 8192;
+
+fn dictionary_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+/// エクスポートのスキーマ（`include_role_columns` が false のときは is_owner・membership_months を、
+/// `include_deleted` が false のときは is_deleted を除く）
+fn export_schema(config: &ExportConfig) -> SchemaRef {
+    let mut fields = vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+        Field::new("author", DataType::Utf8, false),
+        Field::new("author_id", DataType::Utf8, false),
+        Field::new("content", DataType::Utf8, false),
+        Field::new("message_type", dictionary_type(), false),
+        Field::new("amount_display", DataType::Utf8, true),
+        Field::new("amount", DataType::Float64, true),
+        Field::new("currency", dictionary_type(), true),
+        Field::new("tier", dictionary_type(), true),
+        Field::new("is_moderator", DataType::Boolean, false),
+        Field::new("is_member", DataType::Boolean, false),
+        Field::new("is_verified", DataType::Boolean, false),
+        Field::new_list("badges", Field::new_list_field(DataType::Utf8, true), false),
+    ];
+    if config.include_role_columns {
+        fields.push(Field::new("is_owner", DataType::Boolean, false));
+        fields.push(Field::new("membership_months", DataType::UInt32, true));
+    }
+    if config.include_deleted {
+        fields.push(Field::new("is_deleted", DataType::Boolean, false));
+    }
+    Arc::new(Schema::new(fields))
+}
+
+/// メッセージ列を RecordBatch に変換する
+fn record_batch(
+    messages: &[ExportMessage],
+    schema: &SchemaRef,
+    config: &ExportConfig,
+) -> Result<RecordBatch, ArrowError> {
+    let amounts: Vec<_> = messages
+        .iter()
+        .map(|m| m.amount_display.as_deref().and_then(parse_purchase_amount))
+        .collect();
+    let tiers: Vec<Option<String>> = messages
+        .iter()
+        .map(|m| m.tier.map(|t| format!("{:?}", t).to_lowercase()))
+        .collect();
+
+    let mut badges = ListBuilder::new(StringBuilder::new());
+    for m in messages {
+        for badge in &m.badges {
+            badges.values().append_value(badge);
+        }
+        badges.append(true);
+    }
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            messages.iter().map(|m| &m.id),
+        )),
+        Arc::new(
+            messages
+                .iter()
+                .map(|m| {
+                    chrono::DateTime::parse_from_rfc3339(&m.timestamp)
+                        .ok()
+                        .map(|dt| dt.timestamp_micros())
+                })
+                .collect::<TimestampMicrosecondArray>()
+                .with_timezone("UTC"),
+        ),
+        Arc::new(StringArray::from_iter_values(
+            messages.iter().map(|m| &m.author),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            messages.iter().map(|m| &m.author_id),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            messages.iter().map(|m| &m.content),
+        )),
+        Arc::new(
+            messages
+                .iter()
+                .map(|m| m.message_type.as_str())
+                .collect::<DictionaryArray<Int32Type>>(),
+        ),
+        Arc::new(
+            messages
+                .iter()
+                .map(|m| m.amount_display.as_deref())
+                .collect::<StringArray>(),
+        ),
+        Arc::new(
+            amounts
+                .iter()
+                .map(|a| a.as_ref().map(|a| a.amount))
+                .collect::<Float64Array>(),
+        ),
+        Arc::new(
+            amounts
+                .iter()
+                .map(|a| a.as_ref().map(|a| a.currency.as_str()))
+                .collect::<DictionaryArray<Int32Type>>(),
+        ),
+        Arc::new(
+            tiers
+                .iter()
+                .map(Option::as_deref)
+                .collect::<DictionaryArray<Int32Type>>(),
+        ),
+        Arc::new(BooleanArray::from_iter(
+            messages.iter().map(|m| Some(m.is_moderator)),
+        )),
+        Arc::new(BooleanArray::from_iter(
+            messages.iter().map(|m| Some(m.is_member)),
+        )),
+        Arc::new(BooleanArray::from_iter(
+            messages.iter().map(|m| Some(m.is_verified)),
+        )),
+        Arc::new(badges.finish()),
+    ];
+    if config.include_role_columns {
+        columns.push(Arc::new(BooleanArray::from_iter(
+            messages.iter().map(|m| Some(m.is_owner)),
+        )));
+        columns.push(Arc::new(
+            messages
+                .iter()
+                .map(|m| m.membership_months)
+                .collect::<UInt32Array>(),
+        ));
+    }
+    if config.include_deleted {
+        columns.push(Arc::new(BooleanArray::from_iter(
+            messages.iter().map(|m| Some(m.is_deleted)),
+        )));
+    }
+
+    RecordBatch::try_new(Arc::clone(schema), columns)
+}
+
+/// セッション情報を Parquet のキー・バリューメタデータに変換する
+fn session_metadata(data: &SessionExportData) -> Vec<KeyValue> {
+    let meta = &data.metadata;
+    [
+        ("liscov.session_id", Some(meta.session_id.clone())),
+        ("liscov.stream_title", meta.stream_title.clone()),
+        ("liscov.broadcaster_name", meta.broadcaster_name.clone()),
+        ("liscov.stream_url", meta.stream_url.clone()),
+        ("liscov.start_time", Some(meta.start_time.clone())),
+        ("liscov.end_time", meta.end_time.clone()),
+        ("liscov.export_time", Some(meta.export_time.clone())),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.map(|v| KeyValue::new(key.to_string(), v)))
+    .collect()
+}
+
+fn parquet_error(e: impl std::fmt::Display) -> CommandError {
+    CommandError::Internal(format!("Parquet export error: {}", e))
+}
+
+/// Parquet 形式で書き出す
+///
+/// `BATCH_ROWS` 行ずつ RecordBatch に変換して書き込む。`include_metadata` が true の場合は
+/// セッション情報をファイルのキー・バリューメタデータ（`liscov.*`）に格納する。
+pub(super) fn write_parquet(
+    data: &SessionExportData,
+    config: &ExportConfig,
+    writer: &mut (dyn Write + Send),
+) -> Result<(), CommandError> {
+    let schema = export_schema(config);
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_key_value_metadata(config.include_metadata.then(|| session_metadata(data)))
+        .build();
+
+    let mut parquet_writer =
+        ArrowWriter::try_new(writer, Arc::clone(&schema), Some(props)).map_err(parquet_error)?;
+    for chunk in data.messages.chunks(BATCH_ROWS) {
+        let batch = record_batch(chunk, &schema, config).map_err(parquet_error)?;
+        parquet_writer.write(&batch).map_err(parquet_error)?;
+    }
+    parquet_writer.close().map_err(parquet_error)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{SessionMetadata, SuperChatTier, calculate_session_statistics};
+    use super::*;
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn make_message(id: &str, message_type: &str, amount: Option<&str>) -> ExportMessage {
+        ExportMessage {
+            id: id.to_string(),
+            timestamp: "2025-01-14T14:00:00+00:00".to_string(),
+            author: "TestUser".to_string(),
+            author_id: "UC_test".to_string(),
+            content: "hello".to_string(),
+            message_type: message_type.to_string(),
+            amount_display: amount.map(String::from),
+            tier: amount.map(|_| SuperChatTier::Yellow),
+            is_moderator: false,
+            is_member: true,
+            is_verified: false,
+            is_owner: false,
+            membership_months: Some(6),
+            badges: vec!["member".to_string()],
+            is_deleted: false,
+        }
+    }
+
+    fn make_data(messages: Vec<ExportMessage>) -> SessionExportData {
+        SessionExportData {
+            metadata: SessionMetadata {
+                session_id: "session-1".to_string(),
+                start_time: "2025-01-14T13:00:00+00:00".to_string(),
+                end_time: None,
+                stream_url: None,
+                stream_title: Some("Test Stream".to_string()),
+                broadcaster_channel_id: None,
+                broadcaster_name: None,
+                export_time: "2025-01-14T15:00:00+00:00".to_string(),
+            },
+            statistics: calculate_session_statistics(&messages),
+            messages,
+        }
+    }
+
+    fn make_config(include_metadata: bool, include_role_columns: bool) -> ExportConfig {
+        ExportConfig {
+            format: "parquet".to_string(),
+            include_metadata,
+            include_system_messages: false,
+            max_records: None,
+            sort_order: None,
+            include_role_columns,
+            author_filter: None,
+            message_type_filter: None,
+            include_deleted: false,
+        }
+    }
+
+    /// 書き出した Parquet を読み戻す
+    fn write_and_read(
+        data: &SessionExportData,
+        config: &ExportConfig,
+    ) -> (
+        ParquetRecordBatchReaderBuilder<std::fs::File>,
+        Vec<RecordBatch>,
+    ) {
+        let mut file = tempfile::tempfile().unwrap();
+        write_parquet(data, config, &mut file).unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file.try_clone().unwrap()).unwrap();
+        let batches = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (builder, batches)
+    }
+
+    #[test]
+    fn parquet_columns_are_typed() {
+        let data = make_data(vec![
+            make_message("m1", "text", None),
+            make_message("m2", "superchat", Some("¥1,000")),
+        ]);
+        let (builder, batches) = write_and_read(&data, &make_config(false, false));
+
+        let schema = builder.schema();
+        assert_eq!(
+            schema.field_with_name("timestamp").unwrap().data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert_eq!(
+            schema.field_with_name("amount").unwrap().data_type(),
+            &DataType::Float64
+        );
+        assert_eq!(
+            schema.field_with_name("message_type").unwrap().data_type(),
+            &dictionary_type()
+        );
+
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        let timestamps = batch
+            .column_by_name("timestamp")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(timestamps.value(0), 1_736_863_200_000_000);
+        let amounts = batch
+            .column_by_name("amount")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert!(amounts.is_null(0));
+        assert_eq!(amounts.value(1), 1000.0);
+    }
+
+    #[test]
+    fn parquet_role_columns_follow_config() {
+        let data = make_data(vec![make_message("m1", "text", None)]);
+
+        let (without_roles, _) = write_and_read(&data, &make_config(false, false));
+        assert!(without_roles.schema().field_with_name("is_owner").is_err());
+
+        let (with_roles, _) = write_and_read(&data, &make_config(false, true));
+        assert!(with_roles.schema().field_with_name("is_owner").is_ok());
+        assert!(
+            with_roles
+                .schema()
+                .field_with_name("membership_months")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn parquet_session_metadata_is_stored_when_requested() {
+        let data = make_data(vec![make_message("m1", "text", None)]);
+        let (builder, _) = write_and_read(&data, &make_config(true, false));
+
+        let kv = builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap();
+        assert!(
+            kv.iter().any(|e| {
+                e.key == "liscov.session_id" && e.value.as_deref() == Some("session-1")
+            })
+        );
+        assert!(!kv.iter().any(|e| e.key == "liscov.end_time"));
+    }
+
+    #[test]
+    fn parquet_export_with_no_messages_writes_empty_file() {
+        let data = make_data(vec![]);
+        let (builder, batches) = write_and_read(&data, &make_config(false, false));
+
+        assert_eq!(builder.metadata().file_metadata().num_rows(), 0);
+        assert!(batches.is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+//! 保存したセッションの集計の再計算（spec: 07_revenue.md 集計の再計算）
+//!
+//! 数万件のメッセージから収益アナリティクスとエンゲージメントの推移を作り直すと
+//! 数秒かかるため、`spawn_blocking` のスレッドで計算し、進捗をチャネルで送る。
+//! 画面を離れたときなどは `CancellationToken` で途中から打ち切る。
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use ts_rs::TS;
+
+use super::{RevenueAnalytics, RevenueTracker};
+use crate::core::ChatMessage;
+use crate::core::api::usec_to_datetime;
+use crate::core::currency::CurrencyConverter;
+use crate::core::engagement::{EngagementConfig, EngagementMetrics, EngagementSnapshot};
+
+/// 進捗の通知・キャンセルの確認を行う間隔（メッセージ数）
+pub const RECOMPUTE_CHUNK_SIZE: usize = 1000;
+
+/// 再計算の進捗
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct RecomputeProgress {
+    /// 集計を終えたメッセージ数
+    pub processed: usize,
+    /// メッセージの総数
+    pub total: usize,
+}
+
+/// 再計算の結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct RecomputedAnalytics {
+    pub revenue: RevenueAnalytics,
+    /// メッセージの投稿時刻で区切ったエンゲージメントの推移（古い順）
+    pub engagement: Vec<EngagementSnapshot>,
+    pub message_count: usize,
+}
+
+/// 実行中の再計算（新しい再計算を始めると前のものはキャンセルする）
+#[derive(Default)]
+pub struct AnalyticsRecomputeState(pub Mutex<Option<CancellationToken>>);
+
+/// `messages` から収益アナリティクスとエンゲージメントの推移を計算する純粋関数
+///
+/// [`RECOMPUTE_CHUNK_SIZE`] 件ごとに `on_progress` を呼び、`cancel` を確認する。
+/// キャンセルされた場合は `None` を返す。
+/// エンゲージメントのスナップショットは受信時刻ではなく投稿時刻（`timestamp_usec`）で区切る。
+pub(crate) fn recompute_analytics<Tz: TimeZone>(
+    messages: &[ChatMessage],
+    tz: &Tz,
+    converter: &dyn CurrencyConverter,
+    base_currency: &str,
+    engagement_config: &EngagementConfig,
+    cancel: &CancellationToken,
+    mut on_progress: impl FnMut(RecomputeProgress),
+) -> Option<RecomputedAnalytics> {
+    let total = messages.len();
+    let mut revenue = RevenueTracker::new(tz.clone());
+    let mut engagement = EngagementMetrics::new();
+    // 最後に集計したメッセージの投稿時刻
+    let mut last_at: Option<DateTime<Utc>> = None;
+    let mut processed = 0;
+
+    for chunk in messages.chunks(RECOMPUTE_CHUNK_SIZE) {
+        if cancel.is_cancelled() {
+            return None;
+        }
+        for message in chunk {
+            revenue.add_message(message);
+            let Some(at) = usec_to_datetime(&message.timestamp_usec) else {
+                continue;
+            };
+            engagement.maybe_sample(engagement_config, at);
+            engagement.record(message, at);
+            last_at = Some(at);
+        }
+        processed += chunk.len();
+        on_progress(RecomputeProgress { processed, total });
+    }
+
+    // 最後の間隔に残ったメッセージを最後の投稿時刻で締める
+    if let Some(last) = last_at {
+        engagement.take_snapshot(last, engagement_config.max_snapshots);
+    }
+
+    Some(RecomputedAnalytics {
+        revenue: RevenueAnalytics {
+            total: Some(revenue.total_in(converter, base_currency)),
+            ..revenue.snapshot()
+        },
+        engagement: engagement.snapshots().to_vec(),
+        message_count: total,
+    })
+}
+
+/// [`recompute_analytics`] をブロッキング用スレッドで実行する
+///
+/// 進捗は `progress_tx` に送る（受信側が閉じていても計算は続ける）。
+pub fn submit_full_recompute<Tz, C>(
+    messages: Vec<ChatMessage>,
+    tz: Tz,
+    converter: C,
+    base_currency: String,
+    engagement_config: EngagementConfig,
+    cancel: CancellationToken,
+    progress_tx: mpsc::UnboundedSender<RecomputeProgress>,
+) -> JoinHandle<Option<RecomputedAnalytics>>
+where
+    Tz: TimeZone + Send + 'static,
+    C: CurrencyConverter + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        recompute_analytics(
+            &messages,
+            &tz,
+            &converter,
+            &base_currency,
+            &engagement_config,
+            &cancel,
+            |progress| {
+                let _ = progress_tx.send(progress);
+            },
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MessageType;
+    use crate::core::currency::StaticRateTable;
+    use std::collections::HashMap;
+
+    /// 1 秒ごとに投稿されたメッセージ（10 件に 1 件は ¥500 の SuperChat）
+    fn archive(count: usize) -> Vec<ChatMessage> {
+        (0..count)
+            .map(|i| {
+                let message_type = if i % 10 == 0 {
+                    MessageType::SuperChat {
+                        amount: "¥500".to_string(),
+                    }
+                } else {
+                    MessageType::Text
+                };
+                ChatMessage {
+                    id: format!("m{}", i),
+                    timestamp_usec: ((1_700_000_000 + i as i64) * 1_000_000).to_string(),
+                    message_type,
+                    author: format!("user{}", i % 100),
+                    channel_id: format!("UC_{}", i % 100),
+                    content: "hello".to_string(),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn yen_rates() -> StaticRateTable {
+        StaticRateTable::new("JPY", HashMap::new())
+    }
+
+    #[tokio::test]
+    async fn full_recompute_of_10k_messages_reports_progress() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let handle = submit_full_recompute(
+            archive(10_000),
+            Utc,
+            yen_rates(),
+            "JPY".to_string(),
+            EngagementConfig::default(),
+            CancellationToken::new(),
+            tx,
+        );
+
+        let result = handle.await.unwrap().expect("not cancelled");
+        assert_eq!(result.message_count, 10_000);
+        assert_eq!(result.revenue.super_chat_count, 1_000);
+        let total = result.revenue.total.unwrap();
+        assert_eq!(total.amount, 500_000.0);
+        assert_eq!(total.converted_count, 1_000);
+
+        // 10,000 秒分 → 60 秒間隔で 166 回 + 残りの 40 秒分
+        assert_eq!(result.engagement.len(), 167);
+        let messages: u32 = result.engagement.iter().map(|s| s.messages).sum();
+        assert_eq!(messages, 10_000);
+        assert_eq!(result.engagement.last().unwrap().unique_viewers, 100);
+
+        let mut progress = Vec::new();
+        while let Ok(p) = rx.try_recv() {
+            progress.push(p);
+        }
+        assert_eq!(progress.len(), 10);
+        assert_eq!(
+            progress.last(),
+            Some(&RecomputeProgress {
+                processed: 10_000,
+                total: 10_000
+            })
+        );
+        assert!(progress.windows(2).all(|w| w[0].processed < w[1].processed));
+    }
+
+    #[test]
+    fn cancelled_recompute_stops_and_returns_none() {
+        let messages = archive(5_000);
+        let cancel = CancellationToken::new();
+        let mut progress = Vec::new();
+
+        let result = recompute_analytics(
+            &messages,
+            &Utc,
+            &yen_rates(),
+            "JPY",
+            &EngagementConfig::default(),
+            &cancel,
+            |p| {
+                progress.push(p);
+                // 2 チャンク目の後で画面を離れた
+                if p.processed == 2 * RECOMPUTE_CHUNK_SIZE {
+                    cancel.cancel();
+                }
+            },
+        );
+
+        assert!(result.is_none());
+        assert_eq!(progress.len(), 2);
+    }
+
+    #[test]
+    fn empty_archive_has_no_snapshots() {
+        let result = recompute_analytics(
+            &[],
+            &Utc,
+            &yen_rates(),
+            "JPY",
+            &EngagementConfig::default(),
+            &CancellationToken::new(),
+            |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(result.message_count, 0);
+        assert!(result.engagement.is_empty());
+        assert_eq!(result.revenue.super_chat_count, 0);
+    }
+}
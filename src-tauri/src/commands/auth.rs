@@ -3,8 +3,12 @@
 //! Implements 01_auth.md specification
 
 use crate::commands::auth_window;
-use crate::commands::config::{ConfigState, StorageMode};
-use crate::core::models::YouTubeCookies;
+use crate::commands::config::{ConfigState, StorageConfig, StorageMode};
+use crate::core::api::oauth::{
+    self, DeviceCode, OAuthClientConfig, PollOutcome, TOKEN_EXPIRY_MARGIN_SECS,
+};
+use crate::core::api::{ProxyConfig, build_http_client_with_proxy};
+use crate::core::models::{ApiCredentials, OAuthTokens, YouTubeCookies};
 use crate::errors::CommandError;
 use crate::state::AppState;
 use chrono::Utc;
@@ -17,6 +21,7 @@ use ts_rs::TS;
 
 // keyring_service のデフォルト値は paths モジュールで管理
 const KEYRING_USER: &str = "youtube_credentials";
+const KEYRING_OAUTH_USER: &str = "youtube_oauth_tokens";
 
 // =============================================================================
 // CredentialStorage トレイト
@@ -32,12 +37,26 @@ pub(crate) trait CredentialStorage: Send + Sync {
 }
 
 /// keyring を使用するセキュアストレージ実装（本番用）
-struct KeyringStorage;
+struct KeyringStorage {
+    /// keyring のユーザー名（プロファイルごとに分ける）
+    user: String,
+}
+
+impl KeyringStorage {
+    /// プロファイルの keyring エントリ（None はデフォルトプロファイル）
+    fn for_profile(profile: Option<&str>) -> Self {
+        let user = match profile {
+            None => KEYRING_USER.to_string(),
+            Some(name) => format!("{}:{}", KEYRING_USER, name),
+        };
+        Self { user }
+    }
+}
 
 impl CredentialStorage for KeyringStorage {
     fn load(&self) -> Result<YouTubeCookies, String> {
         log::info!("📂 Loading from secure storage...");
-        let entry = keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_USER)
+        let entry = keyring::Entry::new(&crate::paths::keyring_service(), &self.user)
             .map_err(|e| format!("Failed to access secure storage: {}", e))?;
 
         let secret = entry.get_password().map_err(|e| {
@@ -64,7 +83,7 @@ impl CredentialStorage for KeyringStorage {
 
     fn save(&self, cookies: &YouTubeCookies) -> Result<(), String> {
         log::info!("📝 Saving to secure storage...");
-        let entry = keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_USER)
+        let entry = keyring::Entry::new(&crate::paths::keyring_service(), &self.user)
             .map_err(|e| format!("Failed to access secure storage: {}", e))?;
 
         let json: CredentialsJson = cookies.into();
@@ -98,7 +117,7 @@ impl CredentialStorage for KeyringStorage {
     }
 
     fn delete(&self) -> Result<(), String> {
-        let entry = keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_USER)
+        let entry = keyring::Entry::new(&crate::paths::keyring_service(), &self.user)
             .map_err(|e| format!("Failed to access secure storage: {}", e))?;
 
         match entry.delete_credential() {
@@ -112,7 +131,7 @@ impl CredentialStorage for KeyringStorage {
     }
 
     fn is_available(&self) -> bool {
-        let Ok(entry) = keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_USER) else {
+        let Ok(entry) = keyring::Entry::new(&crate::paths::keyring_service(), &self.user) else {
             return false;
         };
         // エントリへのアクセスを試行（読み込みテスト）
@@ -127,10 +146,18 @@ impl CredentialStorage for KeyringStorage {
 /// the same Entry instance. This cache provides a reliable fallback.
 static CREDENTIALS_CACHE: RwLock<Option<YouTubeCookies>> = RwLock::new(None);
 
+/// OAuth トークンのインメモリキャッシュ（CREDENTIALS_CACHE と同じ理由）
+static OAUTH_TOKENS_CACHE: RwLock<Option<OAuthTokens>> = RwLock::new(None);
+
 /// テスト用: CREDENTIALS_CACHE をクリアする
 /// テスト間のキャッシュ汚染を防ぐためにのみ使用する
 #[doc(hidden)]
 pub fn clear_credentials_cache_for_test() {
+    clear_credentials_cache();
+}
+
+/// CREDENTIALS_CACHE をクリアする（キャッシュは使用中のプロファイルの Cookie を持つため、切り替え時に呼ぶ）
+fn clear_credentials_cache() {
     if let Ok(mut guard) = CREDENTIALS_CACHE.write() {
         *guard = None;
     }
@@ -145,6 +172,37 @@ pub enum StorageType {
     Fallback,
 }
 
+/// Source of the credentials used for API requests
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub enum AuthSource {
+    Cookies,
+    OAuth,
+}
+
+/// 認証の有効期限の状態
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS)]
+#[serde(tag = "state", rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub enum AuthExpiry {
+    /// 有効期限が分からない（未ログイン・セッション Cookie・自動更新される OAuth トークン）
+    Unknown,
+    Valid,
+    /// まもなく期限切れになる（`in_secs` 秒後）
+    ExpiringSoon {
+        in_secs: u32,
+    },
+    Expired,
+}
+
+impl AuthExpiry {
+    /// 再ログインを促すべき状態か
+    pub fn needs_relogin(&self) -> bool {
+        matches!(self, AuthExpiry::ExpiringSoon { .. } | AuthExpiry::Expired)
+    }
+}
+
 /// Authentication status
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../src/lib/types/generated/")]
@@ -153,6 +211,32 @@ pub struct AuthStatus {
     pub has_saved_credentials: bool,
     pub storage_type: StorageType,
     pub storage_error: Option<String>,
+    /// API リクエストに使う認証情報の種類（cookie を優先する）
+    pub auth_source: Option<AuthSource>,
+    /// 使用中の認証情報の有効期限
+    pub expiry: AuthExpiry,
+}
+
+/// 保存済みアカウントプロファイルの一覧
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct AuthProfiles {
+    /// 使用中のプロファイル（null はデフォルトプロファイル）
+    pub active_profile: Option<String>,
+    /// 名前付きプロファイル（デフォルトプロファイルは含まない）
+    pub profiles: Vec<String>,
+}
+
+/// OAuth ログインのポーリング結果
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub enum OAuthPollStatus {
+    Pending,
+    SlowDown,
+    Denied,
+    Expired,
+    Authorized,
 }
 
 /// Session validity result
@@ -174,6 +258,8 @@ struct CredentialsJson {
     sapisid: String,
     #[serde(default)]
     raw_cookie_string: Option<String>,
+    #[serde(default)]
+    expires_at: Option<i64>,
 }
 
 impl From<&YouTubeCookies> for CredentialsJson {
@@ -185,6 +271,7 @@ impl From<&YouTubeCookies> for CredentialsJson {
             apisid: cookies.apisid.clone(),
             sapisid: cookies.sapisid.clone(),
             raw_cookie_string: cookies.raw_cookie_string.clone(),
+            expires_at: cookies.expires_at,
         }
     }
 }
@@ -198,6 +285,7 @@ impl From<CredentialsJson> for YouTubeCookies {
             apisid: json.apisid,
             sapisid: json.sapisid,
             raw_cookie_string: json.raw_cookie_string,
+            expires_at: json.expires_at,
         }
     }
 }
@@ -222,6 +310,8 @@ struct YouTubeCookiesConfig {
     sapisid: String,
     #[serde(default)]
     raw_cookies: Option<String>,
+    #[serde(default)]
+    expires_at: Option<i64>,
 }
 
 impl From<YouTubeCookiesConfig> for YouTubeCookies {
@@ -233,6 +323,7 @@ impl From<YouTubeCookiesConfig> for YouTubeCookies {
             apisid: config.apisid,
             sapisid: config.sapisid,
             raw_cookie_string: config.raw_cookies,
+            expires_at: config.expires_at,
         }
     }
 }
@@ -246,18 +337,24 @@ impl From<&YouTubeCookies> for YouTubeCookiesConfig {
             apisid: cookies.apisid.clone(),
             sapisid: cookies.sapisid.clone(),
             raw_cookies: cookies.raw_cookie_string.clone(),
+            expires_at: cookies.expires_at,
         }
     }
 }
 
-/// 認証情報ファイルのパスを返す（フォールバックモード用）
-fn get_credentials_path() -> Result<PathBuf, String> {
-    crate::paths::credentials_path()
+/// 認証情報ファイルのパスを返す（フォールバックモード用、None はデフォルトプロファイル）
+fn get_credentials_path(profile: Option<&str>) -> Result<PathBuf, String> {
+    match profile {
+        None => crate::paths::credentials_path(),
+        Some(name) => crate::paths::profile_credentials_path(name),
+    }
 }
 
 /// Check if credentials file exists
-fn credentials_file_exists() -> bool {
-    get_credentials_path().map(|p| p.exists()).unwrap_or(false)
+fn credentials_file_exists(profile: Option<&str>) -> bool {
+    get_credentials_path(profile)
+        .map(|p| p.exists())
+        .unwrap_or(false)
 }
 
 // =============================================================================
@@ -265,13 +362,13 @@ fn credentials_file_exists() -> bool {
 // =============================================================================
 
 /// Load cookies from secure storage (keyring)
-fn load_cookies_from_secure_storage() -> Result<YouTubeCookies, String> {
-    KeyringStorage.load()
+fn load_cookies_from_secure_storage(profile: Option<&str>) -> Result<YouTubeCookies, String> {
+    KeyringStorage::for_profile(profile).load()
 }
 
 /// Check if secure storage is available
 fn is_secure_storage_available() -> bool {
-    KeyringStorage.is_available()
+    KeyringStorage::for_profile(None).is_available()
 }
 
 // =============================================================================
@@ -279,8 +376,8 @@ fn is_secure_storage_available() -> bool {
 // =============================================================================
 
 /// Load cookies from file (fallback mode)
-fn load_cookies_from_file() -> Result<YouTubeCookies, String> {
-    let path = get_credentials_path()?;
+fn load_cookies_from_file(profile: Option<&str>) -> Result<YouTubeCookies, String> {
+    let path = get_credentials_path(profile)?;
 
     if !path.exists() {
         return Err("Credentials file not found".to_string());
@@ -295,7 +392,10 @@ fn load_cookies_from_file() -> Result<YouTubeCookies, String> {
     // Handle raw_cookies if present
     if let Some(ref raw) = config.youtube.raw_cookies {
         if !raw.is_empty() {
-            return Ok(parse_raw_cookies(raw));
+            return Ok(YouTubeCookies {
+                expires_at: config.youtube.expires_at,
+                ..parse_raw_cookies(raw)
+            });
         }
     }
 
@@ -330,12 +430,13 @@ fn parse_raw_cookies(raw: &str) -> YouTubeCookies {
         apisid: extract("APISID"),
         sapisid: extract("SAPISID"),
         raw_cookie_string: Some(raw.to_string()),
+        expires_at: None,
     }
 }
 
 /// Save cookies to file (fallback mode)
-fn save_cookies_to_file(cookies: &YouTubeCookies) -> Result<(), String> {
-    let path = get_credentials_path()?;
+fn save_cookies_to_file(cookies: &YouTubeCookies, profile: Option<&str>) -> Result<(), String> {
+    let path = get_credentials_path(profile)?;
 
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
@@ -357,8 +458,8 @@ fn save_cookies_to_file(cookies: &YouTubeCookies) -> Result<(), String> {
 }
 
 /// Delete credentials file
-fn delete_credentials_file() -> Result<(), String> {
-    let path = get_credentials_path()?;
+fn delete_credentials_file(profile: Option<&str>) -> Result<(), String> {
+    let path = get_credentials_path(profile)?;
 
     if path.exists() {
         fs::remove_file(&path).map_err(|e| format!("Failed to delete credentials file: {}", e))?;
@@ -383,6 +484,7 @@ fn delete_credentials_file() -> Result<(), String> {
 ///    - その他のエラー → そのまま返却
 pub(crate) fn load_cookies_with_storage(
     storage_mode: &StorageMode,
+    profile: Option<&str>,
     secure_storage: &dyn CredentialStorage,
     cache: &RwLock<Option<YouTubeCookies>>,
 ) -> Result<YouTubeCookies, String> {
@@ -396,7 +498,7 @@ pub(crate) fn load_cookies_with_storage(
 
     match storage_mode {
         StorageMode::Fallback => {
-            let cookies = load_cookies_from_file()?;
+            let cookies = load_cookies_from_file(profile)?;
             // キャッシュ更新
             if let Ok(mut guard) = cache.write() {
                 *guard = Some(cookies.clone());
@@ -417,13 +519,13 @@ pub(crate) fn load_cookies_with_storage(
                     // "no entry" エラーとストレージ障害を区別
                     if e.contains("No credentials found") {
                         // マイグレーション: ファイルにデータがあればセキュアストレージへ移行
-                        if credentials_file_exists() {
+                        if credentials_file_exists(profile) {
                             log::info!("Migrating credentials from file to secure storage");
-                            let cookies = load_cookies_from_file()?;
+                            let cookies = load_cookies_from_file(profile)?;
                             // 移行を試行
                             if secure_storage.save(&cookies).is_ok() {
                                 // 移行成功 → ファイル削除
-                                let _ = delete_credentials_file();
+                                let _ = delete_credentials_file(profile);
                             }
                             // キャッシュ更新
                             if let Ok(mut guard) = cache.write() {
@@ -447,6 +549,7 @@ pub(crate) fn load_cookies_with_storage(
 pub(crate) fn save_cookies_with_storage(
     cookies: &YouTubeCookies,
     storage_mode: &StorageMode,
+    profile: Option<&str>,
     secure_storage: &dyn CredentialStorage,
     cache: &RwLock<Option<YouTubeCookies>>,
 ) -> Result<(), String> {
@@ -457,7 +560,7 @@ pub(crate) fn save_cookies_with_storage(
     }
 
     match storage_mode {
-        StorageMode::Fallback => save_cookies_to_file(cookies),
+        StorageMode::Fallback => save_cookies_to_file(cookies, profile),
         StorageMode::Secure => match secure_storage.save(cookies) {
             Ok(()) => Ok(()),
             Err(e) if e.contains("platform limit") || e.contains("2560") => {
@@ -465,7 +568,7 @@ pub(crate) fn save_cookies_with_storage(
                     "⚠️ Secure storage size limit exceeded, falling back to file storage: {}",
                     e
                 );
-                save_cookies_to_file(cookies)
+                save_cookies_to_file(cookies, profile)
             }
             Err(e) => Err(e),
         },
@@ -475,6 +578,7 @@ pub(crate) fn save_cookies_with_storage(
 /// 認証情報を削除する（CredentialStorage差し替え可能版）
 pub(crate) fn delete_credentials_with_storage(
     storage_mode: &StorageMode,
+    profile: Option<&str>,
     secure_storage: &dyn CredentialStorage,
     cache: &RwLock<Option<YouTubeCookies>>,
 ) -> Result<(), String> {
@@ -485,29 +589,297 @@ pub(crate) fn delete_credentials_with_storage(
     }
 
     match storage_mode {
-        StorageMode::Fallback => delete_credentials_file(),
+        StorageMode::Fallback => delete_credentials_file(profile),
         StorageMode::Secure => {
             // 安全のため両方から削除
             let _ = secure_storage.delete();
-            let _ = delete_credentials_file();
+            let _ = delete_credentials_file(profile);
             Ok(())
         }
     }
 }
 
-/// Load cookies based on storage mode（本番用ラッパー）
-pub(crate) fn load_cookies(storage_mode: &StorageMode) -> Result<YouTubeCookies, String> {
-    load_cookies_with_storage(storage_mode, &KeyringStorage, &CREDENTIALS_CACHE)
+/// 使用中のプロファイルの Cookie をロードする（本番用ラッパー）
+pub(crate) fn load_cookies(storage: &StorageConfig) -> Result<YouTubeCookies, String> {
+    let profile = active_profile(storage);
+    load_cookies_with_storage(
+        &storage.mode,
+        profile,
+        &KeyringStorage::for_profile(profile),
+        &CREDENTIALS_CACHE,
+    )
+}
+
+/// 使用中のプロファイルに Cookie を保存する（本番用ラッパー）
+fn save_cookies(cookies: &YouTubeCookies, storage: &StorageConfig) -> Result<(), String> {
+    let profile = active_profile(storage);
+    save_cookies_with_storage(
+        cookies,
+        &storage.mode,
+        profile,
+        &KeyringStorage::for_profile(profile),
+        &CREDENTIALS_CACHE,
+    )
+}
+
+/// 使用中のプロファイルの認証情報を削除する（本番用ラッパー）
+fn delete_credentials(storage: &StorageConfig) -> Result<(), String> {
+    let profile = active_profile(storage);
+    delete_credentials_with_storage(
+        &storage.mode,
+        profile,
+        &KeyringStorage::for_profile(profile),
+        &CREDENTIALS_CACHE,
+    )
+}
+
+// =============================================================================
+// Account profiles
+// =============================================================================
+
+/// プロファイル名の最大長
+const MAX_PROFILE_NAME_LEN: usize = 32;
+
+/// 使用中のプロファイル名（None はデフォルトプロファイル）
+pub(crate) fn active_profile(storage: &StorageConfig) -> Option<&str> {
+    storage.active_profile.as_deref()
+}
+
+/// 保存済みの名前付きプロファイル一覧
+pub(crate) fn list_profiles(storage: &StorageConfig) -> Vec<String> {
+    storage.profiles.clone()
+}
+
+/// プロファイル名のバリデーション（ファイル名と keyring のユーザー名に使うため英数字・`-`・`_` のみ）
+pub(crate) fn validate_profile_name(name: &str) -> Result<(), CommandError> {
+    if name.is_empty() || name.len() > MAX_PROFILE_NAME_LEN {
+        return Err(CommandError::InvalidInput(format!(
+            "Profile name must be 1-{} characters",
+            MAX_PROFILE_NAME_LEN
+        )));
+    }
+    if !name
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(CommandError::InvalidInput(
+            "Profile name may only contain letters, digits, '-' and '_'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 名前付きプロファイルに Cookie を保存する
+///
+/// 使用中のプロファイルは切り替えない（一覧への登録は呼び出し側で行う）。
+pub(crate) fn save_profile(
+    name: &str,
+    cookies: &YouTubeCookies,
+    storage_mode: &StorageMode,
+) -> Result<(), CommandError> {
+    validate_profile_name(name)?;
+    save_cookies_with_storage(
+        cookies,
+        storage_mode,
+        Some(name),
+        &KeyringStorage::for_profile(Some(name)),
+        &RwLock::new(None),
+    )
+    .map_err(CommandError::StorageError)
+}
+
+/// 名前付きプロファイルの Cookie をロードする（未登録なら ProfileNotFound）
+pub(crate) fn load_profile(
+    name: &str,
+    storage: &StorageConfig,
+) -> Result<YouTubeCookies, CommandError> {
+    if !storage.profiles.iter().any(|profile| profile == name) {
+        return Err(CommandError::ProfileNotFound(name.to_string()));
+    }
+    load_cookies_with_storage(
+        &storage.mode,
+        Some(name),
+        &KeyringStorage::for_profile(Some(name)),
+        &RwLock::new(None),
+    )
+    .map_err(CommandError::AuthRequired)
+}
+
+/// 使用中のプロファイルを切り替えた設定を作る（None はデフォルトプロファイル）
+pub(crate) fn switch_profile(
+    storage: &StorageConfig,
+    name: Option<&str>,
+) -> Result<StorageConfig, CommandError> {
+    if let Some(name) = name {
+        if !storage.profiles.iter().any(|profile| profile == name) {
+            return Err(CommandError::ProfileNotFound(name.to_string()));
+        }
+    }
+    Ok(StorageConfig {
+        active_profile: name.map(str::to_string),
+        ..storage.clone()
+    })
+}
+
+// =============================================================================
+// OAuth token storage
+// =============================================================================
+
+/// OAuth トークンを読み込む（Secure → keyring、Fallback → JSON ファイル）
+pub(crate) fn load_oauth_tokens(storage_mode: &StorageMode) -> Result<OAuthTokens, String> {
+    if let Ok(guard) = OAUTH_TOKENS_CACHE.read() {
+        if let Some(ref tokens) = *guard {
+            return Ok(tokens.clone());
+        }
+    }
+
+    let json = match storage_mode {
+        StorageMode::Fallback => {
+            let path = crate::paths::oauth_tokens_path()?;
+            fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read OAuth tokens file: {}", e))?
+        }
+        StorageMode::Secure => {
+            keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_OAUTH_USER)
+                .map_err(|e| format!("Failed to access secure storage: {}", e))?
+                .get_password()
+                .map_err(|e| format!("Failed to read OAuth tokens: {}", e))?
+        }
+    };
+    let tokens: OAuthTokens =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse OAuth tokens: {}", e))?;
+
+    if let Ok(mut guard) = OAUTH_TOKENS_CACHE.write() {
+        *guard = Some(tokens.clone());
+    }
+    Ok(tokens)
 }
 
-/// Save cookies based on storage mode（本番用ラッパー）
-fn save_cookies(cookies: &YouTubeCookies, storage_mode: &StorageMode) -> Result<(), String> {
-    save_cookies_with_storage(cookies, storage_mode, &KeyringStorage, &CREDENTIALS_CACHE)
+/// OAuth トークンを保存する
+pub(crate) fn save_oauth_tokens(
+    tokens: &OAuthTokens,
+    storage_mode: &StorageMode,
+) -> Result<(), String> {
+    if let Ok(mut guard) = OAUTH_TOKENS_CACHE.write() {
+        *guard = Some(tokens.clone());
+    }
+
+    let json = serde_json::to_string(tokens)
+        .map_err(|e| format!("Failed to serialize OAuth tokens: {}", e))?;
+    match storage_mode {
+        StorageMode::Fallback => {
+            let path = crate::paths::oauth_tokens_path()?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create config directory: {}", e))?;
+            }
+            fs::write(&path, json).map_err(|e| format!("Failed to write OAuth tokens file: {}", e))
+        }
+        StorageMode::Secure => {
+            keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_OAUTH_USER)
+                .map_err(|e| format!("Failed to access secure storage: {}", e))?
+                .set_password(&json)
+                .map_err(|e| format!("Failed to save OAuth tokens: {}", e))
+        }
+    }
 }
 
-/// Delete credentials based on storage mode（本番用ラッパー）
-fn delete_credentials(storage_mode: &StorageMode) -> Result<(), String> {
-    delete_credentials_with_storage(storage_mode, &KeyringStorage, &CREDENTIALS_CACHE)
+/// OAuth トークンを削除する（安全のため両方のストレージから削除）
+pub(crate) fn delete_oauth_tokens() -> Result<(), String> {
+    if let Ok(mut guard) = OAUTH_TOKENS_CACHE.write() {
+        *guard = None;
+    }
+
+    if let Ok(entry) = keyring::Entry::new(&crate::paths::keyring_service(), KEYRING_OAUTH_USER) {
+        let _ = entry.delete_credential();
+    }
+    let path = crate::paths::oauth_tokens_path()?;
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete OAuth tokens file: {}", e))?;
+    }
+    Ok(())
+}
+
+/// API リクエストに使う認証情報を読み込む
+///
+/// cookie を優先し、なければ OAuth トークンを使う。
+/// アクセストークンが期限切れ間近なら `proxy` 経由でリフレッシュして保存し直す（失敗したら未認証扱い）。
+pub(crate) async fn load_api_credentials(
+    storage: &StorageConfig,
+    proxy: &ProxyConfig,
+) -> Option<ApiCredentials> {
+    if let Ok(cookies) = load_cookies(storage) {
+        return Some(ApiCredentials::Cookies(cookies));
+    }
+
+    let storage_mode = &storage.mode;
+    let tokens = load_oauth_tokens(storage_mode).ok()?;
+    if !tokens.is_expired(Utc::now().timestamp(), TOKEN_EXPIRY_MARGIN_SECS) {
+        return Some(ApiCredentials::OAuth(tokens));
+    }
+
+    let Some(config) = OAuthClientConfig::from_env() else {
+        log::warn!("OAuth access token expired but no OAuth client is configured");
+        return None;
+    };
+    let client = match build_http_client_with_proxy(proxy) {
+        Ok(client) => client,
+        Err(e) => {
+            log::warn!("Cannot refresh OAuth access token through the proxy: {}", e);
+            return None;
+        }
+    };
+    match oauth::refresh_tokens(&client, &config, &tokens).await {
+        Ok(refreshed) => {
+            if let Err(e) = save_oauth_tokens(&refreshed, storage_mode) {
+                log::warn!("Failed to save refreshed OAuth tokens: {}", e);
+            }
+            log::info!("🔑 OAuth access token refreshed");
+            Some(ApiCredentials::OAuth(refreshed))
+        }
+        Err(e) => {
+            log::warn!("Failed to refresh OAuth access token: {}", e);
+            None
+        }
+    }
+}
+
+// =============================================================================
+// Expiry check
+// =============================================================================
+
+/// 期限切れが近いと判断する残り時間（秒）
+pub(crate) const AUTH_EXPIRY_WARNING_SECS: i64 = 3 * 24 * 60 * 60;
+
+/// 再ログインが必要になる時刻から有効期限の状態を求める純粋関数
+pub(crate) fn auth_expiry(lapses_at: Option<i64>, now: i64, warn_within_secs: i64) -> AuthExpiry {
+    let Some(lapses_at) = lapses_at else {
+        return AuthExpiry::Unknown;
+    };
+    let remaining = lapses_at.saturating_sub(now);
+    if remaining <= 0 {
+        AuthExpiry::Expired
+    } else if remaining <= warn_within_secs {
+        AuthExpiry::ExpiringSoon {
+            in_secs: u32::try_from(remaining).unwrap_or(u32::MAX),
+        }
+    } else {
+        AuthExpiry::Valid
+    }
+}
+
+/// 使用中の認証情報（cookie を優先）の有効期限の状態
+///
+/// OAuth トークンの更新は行わない（バックグラウンドの定期確認からも呼ぶため）。
+pub(crate) fn current_auth_expiry(storage: &StorageConfig, now: i64) -> AuthExpiry {
+    let lapses_at = match load_cookies(storage) {
+        Ok(cookies) => cookies.expires_at,
+        Err(_) => match load_oauth_tokens(&storage.mode) {
+            Ok(tokens) => tokens.lapses_at(),
+            Err(_) => return AuthExpiry::Unknown,
+        },
+    };
+    auth_expiry(lapses_at, now, AUTH_EXPIRY_WARNING_SECS)
 }
 
 // =============================================================================
@@ -515,8 +887,13 @@ fn delete_credentials(storage_mode: &StorageMode) -> Result<(), String> {
 // =============================================================================
 
 /// Check session validity by making a test request to YouTube API
-async fn check_session_validity_internal(cookies: &YouTubeCookies) -> SessionValidity {
-    use crate::core::api::build_auth_headers;
+///
+/// `client` should be built from the configured `[proxy]` so the check takes the same route as chat.
+async fn check_session_validity_internal(
+    client: &reqwest::Client,
+    credentials: &ApiCredentials,
+) -> SessionValidity {
+    use crate::core::api::build_credential_headers;
     use std::time::Duration;
 
     let checked_at = Utc::now().to_rfc3339();
@@ -530,17 +907,13 @@ async fn check_session_validity_internal(cookies: &YouTubeCookies) -> SessionVal
         session_check_url
     );
 
-    // G4: API接続と同じCookie・認証ヘッダーを使用（build_auth_headersで統一）
-    let auth_headers = build_auth_headers(cookies);
+    // G4: API接続と同じ認証ヘッダーを使用（build_credential_headersで統一）
+    let auth_headers = build_credential_headers(credentials);
 
     // Make request to YouTube InnerTube API with timeout
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .build()
-        .unwrap_or_else(|_| reqwest::Client::new());
-
     let mut request = client
         .post(&session_check_url)
+        .timeout(Duration::from_secs(10))
         .header("Content-Type", "application/json")
         .body(r#"{"context":{"client":{"clientName":"WEB","clientVersion":"2.20231219.04.00"}}}"#);
 
@@ -611,6 +984,8 @@ pub(crate) fn build_auth_status(
     storage_mode: &StorageMode,
     secure_storage_available: bool,
     has_credentials: bool,
+    has_oauth_tokens: bool,
+    expiry: AuthExpiry,
 ) -> AuthStatus {
     let storage_type = match storage_mode {
         StorageMode::Secure => StorageType::Secure,
@@ -620,11 +995,21 @@ pub(crate) fn build_auth_status(
     let storage_error = (*storage_mode == StorageMode::Secure && !secure_storage_available)
         .then(|| "Secure storage is not available".to_string());
 
+    let auth_source = if has_credentials {
+        Some(AuthSource::Cookies)
+    } else if has_oauth_tokens {
+        Some(AuthSource::OAuth)
+    } else {
+        None
+    };
+
     AuthStatus {
-        is_authenticated: has_credentials,
+        is_authenticated: auth_source.is_some(),
         has_saved_credentials: has_credentials,
         storage_type,
         storage_error,
+        auth_source,
+        expiry,
     }
 }
 
@@ -644,9 +1029,17 @@ pub async fn auth_get_status(
     log::info!("📦 Storage mode: {:?}", storage_mode);
 
     let secure_storage_available = is_secure_storage_available();
-    let has_credentials = load_cookies(storage_mode).is_ok();
-
-    let status = build_auth_status(storage_mode, secure_storage_available, has_credentials);
+    let has_credentials = load_cookies(&config.storage).is_ok();
+    let has_oauth_tokens = load_oauth_tokens(storage_mode).is_ok();
+    let expiry = current_auth_expiry(&config.storage, Utc::now().timestamp());
+
+    let status = build_auth_status(
+        storage_mode,
+        secure_storage_available,
+        has_credentials,
+        has_oauth_tokens,
+        expiry,
+    );
 
     log::info!(
         "🔐 Auth status: is_authenticated={}, has_saved={}, storage_error={:?}",
@@ -664,9 +1057,8 @@ pub async fn auth_load_credentials(
     config_state: State<'_, ConfigState>,
 ) -> Result<bool, CommandError> {
     let config = config_state.get();
-    let storage_mode = &config.storage.mode;
 
-    match load_cookies(storage_mode) {
+    match load_cookies(&config.storage) {
         Ok(_) => {
             log::info!("Credentials loaded successfully");
             Ok(true)
@@ -688,7 +1080,7 @@ pub async fn auth_save_raw_cookies(
 
     let cookies = parse_raw_cookies(&raw_cookies);
     let config = config_state.get();
-    save_cookies(&cookies, &config.storage.mode).map_err(CommandError::StorageError)?;
+    save_cookies(&cookies, &config.storage).map_err(CommandError::StorageError)?;
 
     log::info!("Credentials saved from raw cookies");
     Ok(())
@@ -717,10 +1109,11 @@ pub async fn auth_save_credentials(
         apisid,
         sapisid,
         raw_cookie_string: None,
+        expires_at: None,
     };
 
     let config = config_state.get();
-    save_cookies(&cookies, &config.storage.mode).map_err(CommandError::StorageError)?;
+    save_cookies(&cookies, &config.storage).map_err(CommandError::StorageError)?;
 
     log::info!("Credentials saved");
     Ok(())
@@ -732,11 +1125,61 @@ pub async fn auth_delete_credentials(
     config_state: State<'_, ConfigState>,
 ) -> Result<(), CommandError> {
     let config = config_state.get();
-    delete_credentials(&config.storage.mode).map_err(CommandError::StorageError)?;
+    delete_credentials(&config.storage).map_err(CommandError::StorageError)?;
     log::info!("Credentials deleted");
     Ok(())
 }
 
+/// Start OAuth device-flow login and return the code to show to the user
+#[tauri::command]
+pub async fn auth_oauth_start(
+    config_state: State<'_, ConfigState>,
+) -> Result<DeviceCode, CommandError> {
+    let config = OAuthClientConfig::from_env()
+        .ok_or_else(|| CommandError::InvalidInput("OAuth client is not configured".to_string()))?;
+    let client = build_http_client_with_proxy(&config_state.get().proxy)?;
+    let device_code = oauth::start_device_flow(&client, &config)
+        .await
+        .map_err(|e| CommandError::AuthFailed(e.to_string()))?;
+    log::info!("🔑 OAuth device flow started");
+    Ok(device_code)
+}
+
+/// Poll OAuth device-flow login (saves tokens once authorized)
+#[tauri::command]
+pub async fn auth_oauth_poll(
+    device_code: String,
+    config_state: State<'_, ConfigState>,
+) -> Result<OAuthPollStatus, CommandError> {
+    let config = OAuthClientConfig::from_env()
+        .ok_or_else(|| CommandError::InvalidInput("OAuth client is not configured".to_string()))?;
+    let client = build_http_client_with_proxy(&config_state.get().proxy)?;
+    let outcome = oauth::poll_token(&client, &config, &device_code)
+        .await
+        .map_err(|e| CommandError::AuthFailed(e.to_string()))?;
+
+    Ok(match outcome {
+        PollOutcome::Pending => OAuthPollStatus::Pending,
+        PollOutcome::SlowDown => OAuthPollStatus::SlowDown,
+        PollOutcome::Denied => OAuthPollStatus::Denied,
+        PollOutcome::Expired => OAuthPollStatus::Expired,
+        PollOutcome::Authorized(tokens) => {
+            save_oauth_tokens(&tokens, &config_state.get().storage.mode)
+                .map_err(CommandError::StorageError)?;
+            log::info!("🔑 OAuth login completed");
+            OAuthPollStatus::Authorized
+        }
+    })
+}
+
+/// Delete saved OAuth tokens
+#[tauri::command]
+pub async fn auth_oauth_logout() -> Result<(), CommandError> {
+    delete_oauth_tokens().map_err(CommandError::StorageError)?;
+    log::info!("OAuth tokens deleted");
+    Ok(())
+}
+
 /// Clear WebView cookies (logout from YouTube)
 #[tauri::command]
 pub async fn auth_clear_webview_cookies(app: tauri::AppHandle) -> Result<(), CommandError> {
@@ -764,10 +1207,10 @@ pub async fn auth_validate_credentials(
     config_state: State<'_, ConfigState>,
 ) -> Result<bool, CommandError> {
     let config = config_state.get();
-    let cookies = load_cookies(&config.storage.mode).map_err(CommandError::AuthRequired)?;
+    let cookies = load_cookies(&config.storage).map_err(CommandError::AuthRequired)?;
 
-    // SAPISIDが存在し空でないことをチェック
-    Ok(!cookies.sapisid.is_empty())
+    // 5つの必須Cookieが存在し空でないことをチェック
+    Ok(cookies.has_required_cookies())
 }
 
 /// Check session validity by testing with YouTube API
@@ -777,10 +1220,13 @@ pub async fn auth_check_session_validity(
 ) -> Result<SessionValidity, CommandError> {
     log::info!("🔍 auth_check_session_validity called");
     let config = config_state.get();
-    let cookies = load_cookies(&config.storage.mode).map_err(CommandError::AuthRequired)?;
+    let credentials = load_api_credentials(&config.storage, &config.proxy)
+        .await
+        .ok_or_else(|| CommandError::AuthRequired("No credentials found".to_string()))?;
     log::info!("🔍 Checking session validity...");
 
-    let result = check_session_validity_internal(&cookies).await;
+    let client = build_http_client_with_proxy(&config.proxy)?;
+    let result = check_session_validity_internal(&client, &credentials).await;
     log::info!(
         "🔍 Session validity result: is_valid={}, error={:?}",
         result.is_valid,
@@ -796,12 +1242,20 @@ pub async fn auth_use_fallback_storage(
 ) -> Result<bool, CommandError> {
     let mut config = config_state.get();
 
-    // セキュアストレージからの移行対象クレデンシャルを確認
-    let credentials_to_migrate = if config.storage.mode == StorageMode::Secure {
-        load_cookies_from_secure_storage().ok()
-    } else {
-        None
-    };
+    // セキュアストレージからの移行対象クレデンシャルを確認（デフォルトと名前付きプロファイルすべて）
+    let credentials_to_migrate: Vec<(Option<String>, YouTubeCookies)> =
+        if config.storage.mode == StorageMode::Secure {
+            std::iter::once(None)
+                .chain(config.storage.profiles.iter().cloned().map(Some))
+                .filter_map(|profile| {
+                    load_cookies_from_secure_storage(profile.as_deref())
+                        .ok()
+                        .map(|cookies| (profile, cookies))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
 
     // フォールバックモードに切り替え
     config.storage.mode = StorageMode::Fallback;
@@ -814,8 +1268,8 @@ pub async fn auth_use_fallback_storage(
     }
 
     // クレデンシャルが存在する場合は移行
-    if let Some(cookies) = credentials_to_migrate {
-        if let Err(e) = save_cookies_to_file(&cookies) {
+    for (profile, cookies) in credentials_to_migrate {
+        if let Err(e) = save_cookies_to_file(&cookies, profile.as_deref()) {
             log::error!("Failed to migrate credentials to file: {}", e);
             return Err(CommandError::StorageError(format!(
                 "Failed to migrate credentials: {}",
@@ -829,6 +1283,100 @@ pub async fn auth_use_fallback_storage(
     Ok(true)
 }
 
+/// List saved account profiles
+#[tauri::command]
+pub async fn auth_list_profiles(
+    config_state: State<'_, ConfigState>,
+) -> Result<AuthProfiles, CommandError> {
+    let config = config_state.get();
+    Ok(AuthProfiles {
+        active_profile: active_profile(&config.storage).map(str::to_string),
+        profiles: list_profiles(&config.storage),
+    })
+}
+
+/// Save the current credentials as a named profile
+#[tauri::command]
+pub async fn auth_save_profile(
+    name: String,
+    config_state: State<'_, ConfigState>,
+) -> Result<(), CommandError> {
+    let mut config = config_state.get();
+    let cookies = load_cookies(&config.storage).map_err(CommandError::AuthRequired)?;
+    save_profile(&name, &cookies, &config.storage.mode)?;
+    // 使用中のプロファイルに上書き保存した場合に備えてキャッシュを捨てる
+    clear_credentials_cache();
+
+    if !config.storage.profiles.contains(&name) {
+        config.storage.profiles.push(name.clone());
+        config_state.set(config.clone());
+        use crate::commands::config::save_config_to_file;
+        save_config_to_file(&config).map_err(CommandError::IoError)?;
+    }
+    log::info!("Credentials saved as profile '{}'", name);
+    Ok(())
+}
+
+/// Switch the active profile (None switches back to the default profile)
+#[tauri::command]
+pub async fn auth_switch_profile(
+    name: Option<String>,
+    config_state: State<'_, ConfigState>,
+) -> Result<(), CommandError> {
+    let mut config = config_state.get();
+    config.storage = switch_profile(&config.storage, name.as_deref())?;
+    clear_credentials_cache();
+    config_state.set(config.clone());
+
+    // 切り替え先の認証情報をキャッシュに読み込んでおく（未保存ならログのみ）
+    if let Some(name) = name.as_deref() {
+        match load_profile(name, &config.storage) {
+            Ok(cookies) => {
+                if let Ok(mut guard) = CREDENTIALS_CACHE.write() {
+                    *guard = Some(cookies);
+                }
+            }
+            Err(e) => log::info!("Profile '{}' has no saved credentials: {}", name, e),
+        }
+    }
+
+    use crate::commands::config::save_config_to_file;
+    save_config_to_file(&config).map_err(CommandError::IoError)?;
+    log::info!("Switched to profile {:?}", name);
+    Ok(())
+}
+
+/// Delete a named profile (switches back to the default profile if it was active)
+#[tauri::command]
+pub async fn auth_delete_profile(
+    name: String,
+    config_state: State<'_, ConfigState>,
+) -> Result<(), CommandError> {
+    let mut config = config_state.get();
+    if !config.storage.profiles.contains(&name) {
+        return Err(CommandError::ProfileNotFound(name));
+    }
+    delete_credentials_with_storage(
+        &config.storage.mode,
+        Some(name.as_str()),
+        &KeyringStorage::for_profile(Some(name.as_str())),
+        &RwLock::new(None),
+    )
+    .map_err(CommandError::StorageError)?;
+
+    config.storage.profiles.retain(|profile| *profile != name);
+    if config.storage.active_profile.as_deref() == Some(name.as_str()) {
+        config.storage.active_profile = None;
+        clear_credentials_cache();
+    }
+    config_state.set(config.clone());
+
+    use crate::commands::config::save_config_to_file;
+    save_config_to_file(&config).map_err(CommandError::IoError)?;
+    log::info!("Profile '{}' deleted", name);
+    Ok(())
+}
+
 /// Open authentication window (WebView-based login)
 #[tauri::command]
 pub async fn auth_open_window(
@@ -841,7 +1389,7 @@ pub async fn auth_open_window(
 
             // 現在のストレージモードでCookieを保存
             let config = config_state.get();
-            save_cookies(&cookies, &config.storage.mode).map_err(CommandError::StorageError)?;
+            save_cookies(&cookies, &config.storage).map_err(CommandError::StorageError)?;
 
             // Windows資格情報マネージャーへの永続化を待機
             // 参照: https://docs.rs/keyring/latest/x86_64-pc-windows-msvc/keyring/windows/index.html
@@ -902,6 +1450,7 @@ mod tests {
             apisid: "a".to_string(),
             sapisid: "sa".to_string(),
             raw_cookie_string: Some("SID=s; __Secure-1PSID=sec1".to_string()),
+            expires_at: Some(1_800_000_000),
         };
         let json: CredentialsJson = (&cookies).into();
         let serialized = serde_json::to_string(&json).unwrap();
@@ -909,6 +1458,7 @@ mod tests {
         let restored: YouTubeCookies = deserialized.into();
         assert_eq!(restored.raw_cookie_string, cookies.raw_cookie_string);
         assert_eq!(restored.sapisid, cookies.sapisid);
+        assert_eq!(restored.expires_at, cookies.expires_at);
     }
 
     #[test]
@@ -930,6 +1480,7 @@ mod tests {
             apisid: "a".to_string(),
             sapisid: "sa".to_string(),
             raw_cookie_string: Some("SID=s; YSC=ysc; __Secure-1PSID=sec1".to_string()),
+            expires_at: None,
         };
         let config: YouTubeCookiesConfig = (&cookies).into();
         assert_eq!(config.raw_cookies, cookies.raw_cookie_string);
@@ -948,6 +1499,7 @@ mod tests {
             apisid: "a".to_string(),
             sapisid: "sa".to_string(),
             raw_cookie_string: Some("SID=s; SAPISID=sa; __Secure-1PSID=sec1".to_string()),
+            expires_at: Some(1_800_000_000),
         };
         let config = CredentialsConfig {
             youtube: (&cookies).into(),
@@ -959,6 +1511,7 @@ mod tests {
             restored_cookies.raw_cookie_string,
             cookies.raw_cookie_string
         );
+        assert_eq!(restored_cookies.expires_at, cookies.expires_at);
     }
 
     // =========================================================================
@@ -989,7 +1542,8 @@ mod tests {
 
     #[test]
     fn build_auth_status_secure_available_with_credentials() {
-        let status = build_auth_status(&StorageMode::Secure, true, true);
+        let status =
+            build_auth_status(&StorageMode::Secure, true, true, false, AuthExpiry::Unknown);
         assert!(status.is_authenticated);
         assert!(status.has_saved_credentials);
         assert_eq!(status.storage_type, StorageType::Secure);
@@ -998,7 +1552,13 @@ mod tests {
 
     #[test]
     fn build_auth_status_secure_unavailable_without_credentials() {
-        let status = build_auth_status(&StorageMode::Secure, false, false);
+        let status = build_auth_status(
+            &StorageMode::Secure,
+            false,
+            false,
+            false,
+            AuthExpiry::Unknown,
+        );
         assert!(!status.is_authenticated);
         assert!(!status.has_saved_credentials);
         assert_eq!(status.storage_type, StorageType::Secure);
@@ -1007,7 +1567,13 @@ mod tests {
 
     #[test]
     fn build_auth_status_fallback_with_credentials() {
-        let status = build_auth_status(&StorageMode::Fallback, false, true);
+        let status = build_auth_status(
+            &StorageMode::Fallback,
+            false,
+            true,
+            false,
+            AuthExpiry::Unknown,
+        );
         assert!(status.is_authenticated);
         assert!(status.has_saved_credentials);
         assert_eq!(status.storage_type, StorageType::Fallback);
@@ -1016,11 +1582,99 @@ mod tests {
 
     #[test]
     fn build_auth_status_fallback_without_credentials() {
-        let status = build_auth_status(&StorageMode::Fallback, false, false);
+        let status = build_auth_status(
+            &StorageMode::Fallback,
+            false,
+            false,
+            false,
+            AuthExpiry::Unknown,
+        );
         assert!(!status.is_authenticated);
         assert!(!status.has_saved_credentials);
         assert_eq!(status.storage_type, StorageType::Fallback);
         assert!(status.storage_error.is_none());
+        assert!(status.auth_source.is_none());
+    }
+
+    #[test]
+    fn build_auth_status_prefers_cookies_over_oauth() {
+        let status =
+            build_auth_status(&StorageMode::Secure, true, false, true, AuthExpiry::Unknown);
+        assert!(status.is_authenticated);
+        assert!(!status.has_saved_credentials);
+        assert_eq!(status.auth_source, Some(AuthSource::OAuth));
+
+        let status = build_auth_status(&StorageMode::Secure, true, true, true, AuthExpiry::Unknown);
+        assert_eq!(status.auth_source, Some(AuthSource::Cookies));
+    }
+
+    #[test]
+    fn build_auth_status_carries_expiry() {
+        let expiry = AuthExpiry::ExpiringSoon { in_secs: 60 };
+        let status = build_auth_status(&StorageMode::Secure, true, true, false, expiry);
+        assert_eq!(status.expiry, expiry);
+    }
+
+    // =========================================================================
+    // 有効期限 テスト
+    // =========================================================================
+
+    #[test]
+    fn auth_expiry_classifies_remaining_time() {
+        let warn = AUTH_EXPIRY_WARNING_SECS;
+        assert_eq!(auth_expiry(None, 0, warn), AuthExpiry::Unknown);
+        assert_eq!(auth_expiry(Some(warn + 1), 0, warn), AuthExpiry::Valid);
+        assert_eq!(
+            auth_expiry(Some(warn), 0, warn),
+            AuthExpiry::ExpiringSoon {
+                in_secs: warn as u32
+            }
+        );
+        assert_eq!(auth_expiry(Some(100), 100, warn), AuthExpiry::Expired);
+        assert_eq!(auth_expiry(Some(100), 200, warn), AuthExpiry::Expired);
+    }
+
+    #[test]
+    fn auth_expiry_serializes_with_state_tag() {
+        let json = serde_json::to_value(AuthExpiry::ExpiringSoon { in_secs: 90 }).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "state": "expiring_soon", "in_secs": 90 })
+        );
+        let json = serde_json::to_value(AuthExpiry::Unknown).unwrap();
+        assert_eq!(json, serde_json::json!({ "state": "unknown" }));
+    }
+
+    // =========================================================================
+    // アカウントプロファイル テスト
+    // =========================================================================
+
+    #[test]
+    fn validate_profile_name_rejects_path_like_names() {
+        assert!(validate_profile_name("main_2").is_ok());
+        assert!(validate_profile_name("sub-channel").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../config").is_err());
+        assert!(validate_profile_name("a b").is_err());
+        assert!(validate_profile_name(&"a".repeat(MAX_PROFILE_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn switch_profile_requires_registered_profile() {
+        let storage = StorageConfig {
+            profiles: vec!["main".to_string(), "sub".to_string()],
+            ..Default::default()
+        };
+
+        let switched = switch_profile(&storage, Some("sub")).unwrap();
+        assert_eq!(active_profile(&switched), Some("sub"));
+        assert_eq!(list_profiles(&switched), storage.profiles);
+
+        let back = switch_profile(&switched, None).unwrap();
+        assert_eq!(active_profile(&back), None);
+
+        let err = switch_profile(&storage, Some("other")).unwrap_err();
+        assert!(matches!(err, CommandError::ProfileNotFound(name) if name == "other"));
     }
 
     // =========================================================================
@@ -1132,6 +1786,7 @@ mod tests {
             apisid: "test_apisid".to_string(),
             sapisid: "test_sapisid".to_string(),
             raw_cookie_string: None,
+            expires_at: None,
         }
     }
 
@@ -1146,7 +1801,7 @@ mod tests {
         let storage = InMemoryStorage::with_data(cookies.clone());
         let cache = RwLock::new(None);
 
-        let result = load_cookies_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = load_cookies_with_storage(&StorageMode::Secure, None, &storage, &cache);
         assert!(result.is_ok());
         let loaded = result.unwrap();
         assert_eq!(loaded.sapisid, cookies.sapisid);
@@ -1160,7 +1815,7 @@ mod tests {
         let storage = InMemoryStorage::empty();
         let cache = RwLock::new(None);
 
-        let result = load_cookies_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = load_cookies_with_storage(&StorageMode::Secure, None, &storage, &cache);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("No credentials found"));
     }
@@ -1175,7 +1830,7 @@ mod tests {
         let storage = CountingStorage::new(Some(sample_cookies()));
         let cache = RwLock::new(None);
 
-        let _result = load_cookies_with_storage(&StorageMode::Fallback, &storage, &cache);
+        let _result = load_cookies_with_storage(&StorageMode::Fallback, None, &storage, &cache);
 
         // Fallbackモードではsecure_storage.load()は呼ばれない
         assert_eq!(
@@ -1196,7 +1851,8 @@ mod tests {
         let storage = InMemoryStorage::empty();
         let cache = RwLock::new(None);
 
-        let result = save_cookies_with_storage(&cookies, &StorageMode::Secure, &storage, &cache);
+        let result =
+            save_cookies_with_storage(&cookies, &StorageMode::Secure, None, &storage, &cache);
         assert!(result.is_ok());
 
         // ストレージに保存されていることを確認
@@ -1210,7 +1866,7 @@ mod tests {
     struct CredentialsFileGuard;
     impl Drop for CredentialsFileGuard {
         fn drop(&mut self) {
-            let _ = delete_credentials_file();
+            let _ = delete_credentials_file(None);
         }
     }
 
@@ -1226,7 +1882,8 @@ mod tests {
 
         // Fallbackモードではsecure_storageは使用されない
         // ファイルパスへの保存が試みられる（パスが存在しない環境ではエラーになりうる）
-        let _result = save_cookies_with_storage(&cookies, &StorageMode::Fallback, &storage, &cache);
+        let _result =
+            save_cookies_with_storage(&cookies, &StorageMode::Fallback, None, &storage, &cache);
 
         // InMemoryStorageには保存されないことを確認（Fallbackモードはファイルに委譲）
         assert!(storage.get_stored().is_none());
@@ -1247,7 +1904,7 @@ mod tests {
         // 削除前にデータがあることを確認
         assert!(storage.get_stored().is_some());
 
-        let result = delete_credentials_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = delete_credentials_with_storage(&StorageMode::Secure, None, &storage, &cache);
         assert!(result.is_ok());
 
         // ストレージからデータが削除されていることを確認
@@ -1263,7 +1920,7 @@ mod tests {
         let cache = RwLock::new(Some(cookies.clone()));
         let storage = InMemoryStorage::with_data(cookies);
 
-        let result = delete_credentials_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = delete_credentials_with_storage(&StorageMode::Secure, None, &storage, &cache);
         assert!(result.is_ok());
 
         // キャッシュがクリアされていることを確認
@@ -1282,7 +1939,7 @@ mod tests {
         // CountingStorage: load() が呼ばれたかを追跡
         let storage = CountingStorage::new(None);
 
-        let result = load_cookies_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = load_cookies_with_storage(&StorageMode::Secure, None, &storage, &cache);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap().sapisid, cookies.sapisid);
@@ -1301,7 +1958,7 @@ mod tests {
         let cache = RwLock::new(None);
         let storage = InMemoryStorage::with_data(cookies.clone());
 
-        let result = load_cookies_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = load_cookies_with_storage(&StorageMode::Secure, None, &storage, &cache);
 
         assert!(result.is_ok());
         // ロード後にキャッシュが更新されていることを確認
@@ -1317,7 +1974,8 @@ mod tests {
         let cache = RwLock::new(None);
         let storage = InMemoryStorage::empty();
 
-        let result = save_cookies_with_storage(&cookies, &StorageMode::Secure, &storage, &cache);
+        let result =
+            save_cookies_with_storage(&cookies, &StorageMode::Secure, None, &storage, &cache);
 
         assert!(result.is_ok());
         // save後にキャッシュが更新されていることを確認
@@ -1336,7 +1994,7 @@ mod tests {
         // delete前はキャッシュにデータがある
         assert!(cache.read().unwrap().is_some());
 
-        let result = delete_credentials_with_storage(&StorageMode::Secure, &storage, &cache);
+        let result = delete_credentials_with_storage(&StorageMode::Secure, None, &storage, &cache);
 
         assert!(result.is_ok());
         // delete後にキャッシュがNoneになっていることを確認
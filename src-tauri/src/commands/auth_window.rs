@@ -3,9 +3,11 @@
 //! Tauriの別ウィンドウでYouTubeログインページを表示し、
 //! ログイン完了後にCookieを取得する機能を提供します。
 
+use crate::commands::auth::{AuthExpiry, current_auth_expiry};
+use crate::commands::config::ConfigState;
 use crate::core::models::YouTubeCookies;
 use std::sync::Arc;
-use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 use thiserror::Error;
 use tokio::sync::Mutex;
 
@@ -63,6 +65,15 @@ const AUTH_TIMEOUT_SECS: u64 = 300; // 5分
 /// Cookieポーリング間隔（ミリ秒）
 const POLL_INTERVAL_MS: u64 = 1000; // 1秒
 
+/// 認証の有効期限を確認する間隔
+const EXPIRY_CHECK_INTERVAL_SECS: u64 = 30 * 60; // 30分
+
+/// 期限切れが近づいた・切れたときに送るイベント名
+pub const AUTH_EXPIRY_WARNING_EVENT: &str = "auth-expiry-warning";
+
+/// 必須Cookie名
+const REQUIRED_COOKIE_NAMES: [&str; 5] = ["SID", "HSID", "SSID", "APISID", "SAPISID"];
+
 /// 認証ウィンドウの状態
 struct AuthState {
     completed: bool,
@@ -189,14 +200,20 @@ pub async fn open_auth_window(app: AppHandle) -> AuthResult {
                     if all_cookies.iter().any(|c| c.name() == "SAPISID") {
                         tracing::info!("🔓 SAPISID detected in cookies");
 
+                        let expires_at = earliest_required_expiry(all_cookies.iter().map(|c| {
+                            (c.name(), c.expires_datetime().map(|dt| dt.unix_timestamp()))
+                        }));
+
                         let mut cookies_map = std::collections::HashMap::new();
                         for cookie in all_cookies {
                             cookies_map
                                 .insert(cookie.name().to_string(), cookie.value().to_string());
                         }
 
-                        if let Some(yt_cookies) = extract_youtube_cookies_from_map(&cookies_map) {
+                        if let Some(mut yt_cookies) = extract_youtube_cookies_from_map(&cookies_map)
+                        {
                             tracing::info!("✅ Successfully extracted YouTube cookies");
+                            yt_cookies.expires_at = expires_at;
 
                             let _ = auth_window.close();
                             return Ok(yt_cookies);
@@ -211,6 +228,49 @@ pub async fn open_auth_window(app: AppHandle) -> AuthResult {
     }
 }
 
+/// 必須Cookieのうち最も早い有効期限（UNIX秒）を返す
+/// セッションCookie（期限なし）は無視し、期限付きの必須Cookieがなければ None
+fn earliest_required_expiry<'a>(
+    cookies: impl IntoIterator<Item = (&'a str, Option<i64>)>,
+) -> Option<i64> {
+    cookies
+        .into_iter()
+        .filter(|(name, _)| REQUIRED_COOKIE_NAMES.contains(name))
+        .filter_map(|(_, expires_at)| expires_at)
+        .min()
+}
+
+/// 前回の状態から、警告イベントを送るべき状態を返す
+/// 同じ種類の警告を繰り返し送らないよう、状態の種類が変わったときだけ返す
+fn expiry_warning_to_emit(previous: Option<AuthExpiry>, current: AuthExpiry) -> Option<AuthExpiry> {
+    if !current.needs_relogin() {
+        return None;
+    }
+    let unchanged = previous
+        .is_some_and(|prev| std::mem::discriminant(&prev) == std::mem::discriminant(&current));
+    if unchanged { None } else { Some(current) }
+}
+
+/// 認証の有効期限をバックグラウンドで定期確認し、
+/// 期限切れが近づいた・切れたときに `auth-expiry-warning` イベントを送る
+pub fn spawn_expiry_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(EXPIRY_CHECK_INTERVAL_SECS));
+        let mut previous: Option<AuthExpiry> = None;
+        loop {
+            interval.tick().await;
+            let storage = app.state::<ConfigState>().get().storage;
+            let current = current_auth_expiry(&storage, chrono::Utc::now().timestamp());
+            if let Some(expiry) = expiry_warning_to_emit(previous, current) {
+                tracing::warn!("⏰ Authentication expiry warning: {:?}", expiry);
+                let _ = app.emit(AUTH_EXPIRY_WARNING_EVENT, expiry);
+            }
+            previous = Some(current);
+        }
+    });
+}
+
 /// Cookie文字列をパース（テストで使用）
 #[cfg(test)]
 fn parse_cookie_string(cookie_str: &str) -> std::collections::HashMap<String, String> {
@@ -252,6 +312,7 @@ fn extract_youtube_cookies_from_map(
         apisid: apisid.clone(),
         sapisid: sapisid.clone(),
         raw_cookie_string: Some(raw_cookie_string),
+        expires_at: None,
     })
 }
 
@@ -513,4 +574,42 @@ mod tests {
             raw
         );
     }
+
+    #[test]
+    fn earliest_required_expiry_ignores_optional_and_session_cookies() {
+        let cookies = [
+            ("SID", Some(2_000)),
+            ("SAPISID", Some(1_500)),
+            ("HSID", None),
+            ("PREF", Some(100)),
+        ];
+        assert_eq!(earliest_required_expiry(cookies), Some(1_500));
+        assert_eq!(
+            earliest_required_expiry([("SID", None), ("PREF", Some(100))]),
+            None
+        );
+    }
+
+    #[test]
+    fn expiry_warning_is_emitted_only_on_transition() {
+        let soon = AuthExpiry::ExpiringSoon { in_secs: 600 };
+        let sooner = AuthExpiry::ExpiringSoon { in_secs: 60 };
+
+        assert_eq!(expiry_warning_to_emit(None, AuthExpiry::Valid), None);
+        assert_eq!(expiry_warning_to_emit(None, AuthExpiry::Unknown), None);
+        assert_eq!(expiry_warning_to_emit(None, soon), Some(soon));
+        assert_eq!(
+            expiry_warning_to_emit(Some(AuthExpiry::Valid), soon),
+            Some(soon)
+        );
+        assert_eq!(expiry_warning_to_emit(Some(soon), sooner), None);
+        assert_eq!(
+            expiry_warning_to_emit(Some(sooner), AuthExpiry::Expired),
+            Some(AuthExpiry::Expired)
+        );
+        assert_eq!(
+            expiry_warning_to_emit(Some(AuthExpiry::Expired), AuthExpiry::Expired),
+            None
+        );
+    }
 }
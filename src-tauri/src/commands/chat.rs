@@ -3,17 +3,36 @@
 use crate::AppState;
 use crate::commands::SaveConfigState;
 use crate::commands::auth;
-use crate::commands::config::ConfigState;
+use crate::commands::config::{Config, ConfigState};
+use crate::commands::moderation::ModerationState;
+use crate::commands::notifications::{DiscordState, NotificationState};
+use crate::commands::replay::REPLAY_CONNECTION_ID;
 use crate::connection::{ConnectionInfo, MAX_CONNECTIONS, StreamConnection};
-use crate::core::api::InnerTubeClient;
-use crate::core::chat_runtime::{MonitoringDeps, run_monitoring_loop};
-use crate::core::models::{ChatMessage, ChatMode, ConnectionStatus, Platform, extract_video_id};
+use crate::core::api::{
+    InnerTubeClient, InnerTubeContext, ParsedAmount, ProxyConfig, VideoMetadata,
+    fetch_video_metadata, is_members_only, is_proxy_error, usec_to_rfc3339,
+};
+use crate::core::capture_watchdog::{CaptureWatchdog, MIN_STALL_THRESHOLD, run_supervised};
+use crate::core::channel_monitor::{YouTubeLiveResolver, wait_for_next_live};
+use crate::core::chat_runtime::{
+    MonitoringDeps, MonitoringOutcome, ProcessorSlot, run_monitoring_loop,
+};
+use crate::core::connection_health::{ConnectionHealthEvent, ConnectionHealthHandle};
+use crate::core::continuation_store::ContinuationStore;
+use crate::core::discord::DiscordEvent;
+use crate::core::disk_archive::{self, DiskArchive};
+use crate::core::first_chatter::is_first_in_stream;
+use crate::core::models::{
+    ChatMessage, ChatMode, ConnectionStatus, MessageType, Platform, extract_video_id,
+};
+use crate::core::moderation::{LocalModerator, ModerationAction};
+use crate::core::notifications::ChatNotificationEvent;
 use crate::database;
 use crate::errors::CommandError;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::{RwLock, watch};
 use tokio_util::sync::CancellationToken;
 use ts_rs::TS;
@@ -49,6 +68,26 @@ impl From<ConnectionStatus> for ConnectionResult {
     }
 }
 
+/// ポーリング停止検出イベント（`chat:capture_stalled`）のペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct CaptureStalled {
+    pub connection_id: u64,
+    /// 最後のポーリング成功からの経過秒数
+    pub stalled_secs: u64,
+}
+
+/// 高額スーパーチャット検出イベント（`chat:big_donation`）のペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct BigDonationEvent {
+    pub connection_id: u64,
+    pub message_id: String,
+    pub author: String,
+    pub channel_id: String,
+    pub amount: ParsedAmount,
+}
+
 /// Message run (text or emoji)
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(tag = "type")]
@@ -84,6 +123,14 @@ pub struct SuperChatColors {
     pub body_text: String,
 }
 
+/// SuperSticker image (spec: 02_chat.md)
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct StickerImage {
+    pub url: String,
+    pub alt_text: String,
+}
+
 /// Message metadata
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../src/lib/types/generated/")]
@@ -96,6 +143,30 @@ pub struct GuiMessageMetadata {
     pub is_moderator: bool,
     pub is_verified: bool,
     pub superchat_colors: Option<SuperChatColors>,
+    /// SuperSticker image, if any
+    #[serde(default)]
+    pub sticker: Option<StickerImage>,
+}
+
+/// Author role derived from author badges (spec: 02_chat.md 投稿者ロール)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct AuthorRole {
+    pub is_owner: bool,
+    pub is_moderator: bool,
+    pub is_verified: bool,
+    pub is_member: bool,
+}
+
+impl From<crate::core::models::AuthorRole> for AuthorRole {
+    fn from(role: crate::core::models::AuthorRole) -> Self {
+        Self {
+            is_owner: role.is_owner,
+            is_moderator: role.is_moderator,
+            is_verified: role.is_verified,
+            is_member: role.is_member,
+        }
+    }
 }
 
 /// GUI-friendly chat message
@@ -112,9 +183,15 @@ pub struct GuiChatMessage {
     pub runs: Vec<MessageRun>,
     pub message_type: String,
     pub amount: Option<String>,
+    /// `author_role.is_member` と同じ（既存の表示・フィルタ用）
     pub is_member: bool,
+    /// 投稿者のロール（オーナー・モデレーター・認証済み・メンバー）
+    pub author_role: AuthorRole,
     pub is_first_time_viewer: bool,
     pub in_stream_comment_count: Option<u32>,
+    /// 配信内で最初のコメントか（DB には保存しない。spec: 02_chat.md 配信内の初コメント）
+    #[serde(default)]
+    pub is_first_in_stream: bool,
     pub metadata: Option<GuiMessageMetadata>,
     /// この接続に割り当てられた接続ID
     pub connection_id: u64,
@@ -122,6 +199,9 @@ pub struct GuiChatMessage {
     pub platform: String,
     /// 配信者名
     pub broadcaster_name: String,
+    /// ローカルモデレーションで一致したルールのアクション（spec: 02_chat.md）
+    #[serde(default)]
+    pub moderation: Vec<ModerationAction>,
 }
 
 impl From<ChatMessage> for GuiChatMessage {
@@ -143,6 +223,9 @@ impl From<ChatMessage> for GuiChatMessage {
             crate::core::models::MessageType::System => ("system".to_string(), None, None, None),
         };
 
+        let author_role = AuthorRole::from(msg.author_role());
+        let is_first_in_stream = is_first_in_stream(&msg);
+
         // runs を core models から GUI models に変換
         let runs: Vec<MessageRun> = msg
             .runs
@@ -153,6 +236,7 @@ impl From<ChatMessage> for GuiChatMessage {
                     emoji_id,
                     image_url,
                     alt_text,
+                    ..
                 } => MessageRun::Emoji {
                     emoji_id,
                     image_url,
@@ -185,11 +269,18 @@ impl From<ChatMessage> for GuiChatMessage {
                 body_background: c.body_background,
                 body_text: c.body_text,
             }),
+            sticker: m.sticker.map(|s| StickerImage {
+                url: s.url,
+                alt_text: s.alt_text,
+            }),
         });
 
+        // 表示用の時刻は timestamp_usec から作り、分析の時間帯とずれないようにする
+        let timestamp = usec_to_rfc3339(&msg.timestamp_usec).unwrap_or(msg.timestamp);
+
         Self {
             id: msg.id,
-            timestamp: msg.timestamp,
+            timestamp,
             timestamp_usec: msg.timestamp_usec,
             author: msg.author,
             author_icon_url: msg.author_icon_url,
@@ -198,14 +289,17 @@ impl From<ChatMessage> for GuiChatMessage {
             runs,
             message_type,
             amount,
-            is_member: msg.is_member,
+            is_member: author_role.is_member,
+            author_role,
             is_first_time_viewer: msg.is_first_time_viewer,
             in_stream_comment_count: msg.in_stream_comment_count,
+            is_first_in_stream,
             metadata,
             // デフォルト値（呼び出し元で from_with_connection を使うべき）
             connection_id: 0,
             platform: "youtube".to_string(),
             broadcaster_name: String::new(),
+            moderation: Vec::new(),
         }
     }
 }
@@ -233,6 +327,7 @@ pub async fn connect_to_stream(
     state: State<'_, AppState>,
     save_config_state: State<'_, SaveConfigState>,
     config_state: State<'_, ConfigState>,
+    moderation_state: State<'_, ModerationState>,
     url: String,
     chat_mode: Option<String>,
 ) -> Result<ConnectionResult, CommandError> {
@@ -267,26 +362,36 @@ pub async fn connect_to_stream(
     };
 
     // InnerTube クライアントを作成・初期化
-    let mut client = InnerTubeClient::new(&video_id);
-
-    // 認証クッキーをストレージから読み込んでクライアントに設定（メンバー限定配信用）
     let config = config_state.get();
-    if let Ok(cookies) = auth::load_cookies(&config.storage.mode) {
-        tracing::info!("Auth cookies loaded, setting on InnerTube client");
-        client.set_auth(cookies);
+    let mut client = InnerTubeClient::new(&video_id)
+        .with_context(innertube_context(&config))
+        .with_proxy(&config.proxy)?;
+
+    // 認証情報（cookie または OAuth トークン）をストレージから読み込んでクライアントに設定（メンバー限定配信用）
+    if let Some(credentials) = auth::load_api_credentials(&config.storage, &config.proxy).await {
+        tracing::info!("Auth credentials loaded, setting on InnerTube client");
+        client.set_credentials(credentials);
     } else {
-        tracing::debug!("No auth cookies available, connecting without authentication");
+        tracing::debug!("No auth credentials available, connecting without authentication");
     }
 
-    let status = client
-        .initialize()
-        .await
-        .map_err(|e| CommandError::ConnectionFailed(format!("Failed to connect: {}", e)))?;
+    let status = client.initialize().await.map_err(|e| {
+        if is_members_only(&e) {
+            CommandError::MembersOnly(e.to_string())
+        } else if is_proxy_error(&e) {
+            CommandError::ProxyError(e.to_string())
+        } else {
+            CommandError::ConnectionFailed(format!("Failed to connect: {}", e))
+        }
+    })?;
 
     // 初期化後にチャットモードを設定（continuation token が必要）
     if status.is_connected && !client.set_chat_mode(mode) {
         tracing::warn!("Failed to set chat mode to {:?}, using default", mode);
     }
+    if status.is_connected {
+        resume_saved_continuation(&mut client, &state.continuation_store, mode).await;
+    }
 
     tracing::info!(
         "Connection status: is_connected={}, stream_title={:?}, broadcaster_channel_id={:?}, broadcaster_name={:?}",
@@ -300,31 +405,11 @@ pub async fn connect_to_stream(
     result.connection_id = connection_id;
 
     if result.success {
+        // ウォッチページから取れなかったタイトル・配信者をメタデータで補う
+        fill_missing_stream_info(&video_id, &config.proxy, &mut result).await;
+
         // データベースセッションを作成
-        let session_id = {
-            let db_guard = state.database.read().await;
-            if let Some(db) = db_guard.as_ref() {
-                let conn = db.connection().await;
-                match database::create_session(
-                    &conn,
-                    Some(&url),
-                    result.stream_title.as_deref(),
-                    result.broadcaster_channel_id.as_deref(),
-                    result.broadcaster_name.as_deref(),
-                ) {
-                    Ok(id) => {
-                        tracing::info!("Created session: {}", id);
-                        Some(id)
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create session: {}", e);
-                        None
-                    }
-                }
-            } else {
-                None
-            }
-        };
+        let session_id = create_stream_session(&state, &url, &result).await;
 
         result.session_id = session_id.clone();
 
@@ -350,14 +435,27 @@ pub async fn connect_to_stream(
 
         // emit コールバック用に接続情報をキャプチャ
         let conn_id = connection_id;
-        let platform_str = Platform::YouTube.as_str().to_string();
-        let broadcaster = result.broadcaster_name.clone().unwrap_or_default();
+        let emit_gui_message = gui_message_emitter(
+            conn_id,
+            Platform::YouTube.as_str().to_string(),
+            result.broadcaster_name.clone().unwrap_or_default(),
+            Arc::clone(&moderation_state.0),
+        );
 
         let app_handle = app.clone();
         let innertube_for_task = Arc::clone(&innertube_client);
         let token_for_task = cancellation_token.clone();
         let broadcaster_id = result.broadcaster_channel_id.clone();
 
+        // ポーリング停止を検出するウォッチドッグ（下限より短い古い設定は下限に揃える）
+        let watchdog = CaptureWatchdog::new(
+            std::time::Duration::from_secs(config.monitoring.stall_threshold_secs)
+                .max(MIN_STALL_THRESHOLD),
+        );
+        let health = ConnectionHealthHandle::default();
+        // 監視ループを再起動しても処理タスクは接続ごとに 1 つに保つ
+        let processor_slot = ProcessorSlot::default();
+
         // StreamConnection を生成して connections マップに追加
         let stream_conn = StreamConnection {
             id: connection_id,
@@ -371,6 +469,7 @@ pub async fn connect_to_stream(
             cancellation_token: cancellation_token.clone(),
             task_handle: None, // spawn後に設定
             chat_mode_tx,
+            health: health.clone(),
         };
 
         {
@@ -384,29 +483,122 @@ pub async fn connect_to_stream(
 
         // 監視タスクをスポーン
         let handle = tokio::spawn(async move {
-            run_monitoring_loop(
-                deps,
-                innertube_for_task,
-                app_handle,
-                video_id,
-                conn_id,
-                session_id,
-                broadcaster_id,
-                token_for_task,
-                save_config,
-                chat_mode_rx,
-                move |app, msg| {
-                    // ChatMessage を接続情報付き GUI メッセージに変換してフロントエンドへ emit
-                    let gui_msg = GuiChatMessage::from_with_connection(
-                        msg.clone(),
-                        conn_id,
-                        &platform_str,
-                        &broadcaster,
-                    );
-                    let _ = app.emit("chat:message", &gui_msg);
-                },
-            )
-            .await;
+            let mut video_id = video_id;
+            let mut session_id = session_id;
+            let outcome = loop {
+                // 停止を検出した場合は実行中のループを破棄して再起動する
+                let outcome = run_supervised(
+                    &watchdog,
+                    || {
+                        let deps = deps.clone();
+                        let innertube = Arc::clone(&innertube_for_task);
+                        let app = app_handle.clone();
+                        let video_id = video_id.clone();
+                        let session_id = session_id.clone();
+                        let broadcaster_id = broadcaster_id.clone();
+                        let token = token_for_task.clone();
+                        let save_config = save_config.clone();
+                        let chat_mode_rx = chat_mode_rx.clone();
+                        let watchdog = watchdog.clone();
+                        let health = health.clone();
+                        let processor_slot = processor_slot.clone();
+                        let emit_gui_message = emit_gui_message.clone();
+                        async move {
+                            // フェッチ中に破棄されたクライアントは再初期化する
+                            if innertube.read().await.is_none() {
+                                let chat_mode = *chat_mode_rx.borrow();
+                                match init_innertube_client(&app, &video_id, chat_mode).await {
+                                    Ok((client, status)) if status.is_connected => {
+                                        *innertube.write().await = Some(client);
+                                    }
+                                    other => {
+                                        tracing::warn!(
+                                            "再起動時のクライアント再初期化に失敗 video_id: {}: {:?}",
+                                            video_id,
+                                            other.err()
+                                        );
+                                        return MonitoringOutcome::ClientUnavailable;
+                                    }
+                                }
+                            }
+                            run_monitoring_loop(
+                                deps,
+                                innertube,
+                                app,
+                                video_id,
+                                conn_id,
+                                session_id,
+                                broadcaster_id,
+                                token,
+                                save_config,
+                                chat_mode_rx,
+                                watchdog,
+                                health,
+                                processor_slot,
+                                emit_gui_message,
+                            )
+                            .await
+                        }
+                    },
+                    |stall| {
+                        if stall.restarts() {
+                            tracing::warn!(
+                                "ポーリング停止を検出 — 監視ループを再起動 connection_id: {} elapsed: {:?}",
+                                conn_id,
+                                stall.elapsed()
+                            );
+                        } else {
+                            // 処理が止まってキューへの追加で待たされ続けている（再起動しても解消しない）
+                            tracing::warn!(
+                                "処理の待ちが長すぎる — 監視ループは再起動しない connection_id: {} elapsed: {:?}",
+                                conn_id,
+                                stall.elapsed()
+                            );
+                        }
+                        let _ = app_handle.emit(
+                            "chat:capture_stalled",
+                            CaptureStalled {
+                                connection_id: conn_id,
+                                stalled_secs: stall.elapsed().as_secs(),
+                            },
+                        );
+                        if let Some(info) = health.check() {
+                            let _ = app_handle.emit(
+                                "chat:connection_health",
+                                ConnectionHealthEvent {
+                                    connection_id: conn_id,
+                                    health: info,
+                                },
+                            );
+                        }
+                    },
+                )
+                .await;
+
+                if outcome != MonitoringOutcome::StreamEnded {
+                    break outcome;
+                }
+
+                // 配信終了: 設定が有効ならチャンネルを監視して次の配信へ接続する
+                let chat_mode = *chat_mode_rx.borrow();
+                match follow_next_live(
+                    &app_handle,
+                    conn_id,
+                    broadcaster_id.as_deref(),
+                    &video_id,
+                    chat_mode,
+                    &innertube_for_task,
+                    &token_for_task,
+                )
+                .await
+                {
+                    Some(next) => {
+                        video_id = next.video_id;
+                        session_id = next.session_id;
+                    }
+                    None => break outcome,
+                }
+            };
 
             // 監視タスク終了後: connections マップに残っている場合はクリーンアップ
             // （disconnect_stream 経由で既に削除済みの場合はスキップ）
@@ -427,7 +619,17 @@ pub async fn connect_to_stream(
                         broadcaster_channel_id: None,
                         broadcaster_name: None,
                         is_replay: false,
-                        error: Some("監視タスクが予期せず終了しました".to_string()),
+                        error: Some(match outcome {
+                            MonitoringOutcome::StreamEnded => "配信が終了しました".to_string(),
+                            // 接続時と同じログインの案内を表示できるよう CommandError の形で渡す
+                            MonitoringOutcome::MembersOnly => {
+                                serde_json::to_string(&CommandError::MembersOnly(
+                                    "メンバー限定の配信のためチャットを取得できません".to_string(),
+                                ))
+                                .unwrap_or_default()
+                            }
+                            _ => "監視タスクが予期せず終了しました".to_string(),
+                        }),
                         session_id: None,
                         connection_id: conn_id,
                     },
@@ -450,6 +652,321 @@ pub async fn connect_to_stream(
     Ok(result)
 }
 
+/// 接続結果に欠けているタイトル・配信者情報を配信のメタデータで補う
+///
+/// メンバー限定配信などでウォッチページの `ytInitialData` から取れなかった場合に限り取得する。
+async fn fill_missing_stream_info(
+    video_id: &str,
+    proxy: &ProxyConfig,
+    result: &mut ConnectionResult,
+) {
+    if result.stream_title.is_some()
+        && result.broadcaster_name.is_some()
+        && result.broadcaster_channel_id.is_some()
+    {
+        return;
+    }
+    match fetch_video_metadata(video_id, proxy).await {
+        Ok(metadata) => {
+            let non_empty = |s: String| (!s.is_empty()).then_some(s);
+            if result.stream_title.is_none() {
+                result.stream_title = non_empty(metadata.title);
+            }
+            if result.broadcaster_name.is_none() {
+                result.broadcaster_name = non_empty(metadata.channel_name);
+            }
+            if result.broadcaster_channel_id.is_none() {
+                result.broadcaster_channel_id = non_empty(metadata.channel_id);
+            }
+        }
+        Err(e) => {
+            tracing::warn!("配信メタデータの取得失敗 video_id={}: {}", video_id, e);
+        }
+    }
+}
+
+/// 接続結果に対応するデータベースセッションを作成する
+async fn create_stream_session(
+    state: &AppState,
+    url: &str,
+    result: &ConnectionResult,
+) -> Option<String> {
+    let db_guard = state.database.read().await;
+    let db = db_guard.as_ref()?;
+    let conn = db.connection().await;
+    match database::create_session(
+        &conn,
+        Some(url),
+        result.stream_title.as_deref(),
+        result.broadcaster_channel_id.as_deref(),
+        result.broadcaster_name.as_deref(),
+    ) {
+        Ok(id) => {
+            tracing::info!("Created session: {}", id);
+            Some(id)
+        }
+        Err(e) => {
+            tracing::error!("Failed to create session: {}", e);
+            None
+        }
+    }
+}
+
+/// ChatMessage を接続情報付き GUI メッセージに変換して emit するコールバックを作る
+///
+/// ライブの監視ループと保存済みレスポンスの再生で共通に使う。
+pub(crate) fn gui_message_emitter(
+    conn_id: u64,
+    platform_str: String,
+    broadcaster: String,
+    moderator: Arc<std::sync::RwLock<LocalModerator>>,
+) -> impl Fn(&AppHandle, &ChatMessage) + Clone + Send + Sync + 'static {
+    move |app: &AppHandle, msg: &ChatMessage| {
+        // ChatMessage を接続情報付き GUI メッセージに変換してフロントエンドへ emit
+        let mut gui_msg =
+            GuiChatMessage::from_with_connection(msg.clone(), conn_id, &platform_str, &broadcaster);
+        if let Ok(moderator) = moderator.read() {
+            gui_msg.moderation = moderator.evaluate(msg);
+        }
+        let _ = app.emit("chat:message", &gui_msg);
+
+        // 設定変更を接続中にも反映するため都度参照する
+        let config = app.state::<ConfigState>().get();
+        // ローカルフィード（`local_server` feature）にも同じメッセージを流す
+        #[cfg(feature = "local_server")]
+        if let Some(feed) = app.try_state::<crate::commands::websocket::LocalFeedState>() {
+            let filtered_out = gui_msg.moderation.contains(&ModerationAction::Hide)
+                || !config.chat_display.shows_channel(msg);
+            feed.publish(&gui_msg, filtered_out);
+        }
+
+        // 保存済みレスポンスの再生では外部（Discord）へ投稿しない
+        let discord = (conn_id != REPLAY_CONNECTION_ID).then(|| app.state::<DiscordState>());
+
+        // キーワード通知（非表示にしたメッセージは通知しない）
+        if config.notifications.enabled && !gui_msg.moderation.contains(&ModerationAction::Hide) {
+            let notification =
+                app.state::<NotificationState>()
+                    .0
+                    .lock()
+                    .ok()
+                    .and_then(|mut notifier| {
+                        notifier.evaluate(&config.notifications, msg, std::time::Instant::now())
+                    });
+            if let Some(notification) = notification {
+                let _ = app.emit(
+                    "chat:notification",
+                    ChatNotificationEvent {
+                        connection_id: conn_id,
+                        message_id: msg.id.clone(),
+                        author: msg.author.clone(),
+                        content: msg.content.clone(),
+                        reason: notification.reason,
+                        suppressed: notification.suppressed,
+                    },
+                );
+            }
+        }
+
+        // 高額スーパーチャットの通知
+        if matches!(
+            msg.message_type,
+            MessageType::SuperChat { .. } | MessageType::SuperSticker { .. }
+        ) {
+            let rates = config.currency.rate_table();
+            if let Some(amount) = config
+                .donation_alert
+                .big_donation_amount(&msg.message_type, &rates)
+            {
+                if let Some(discord) = &discord {
+                    discord.0.notify(
+                        &config.discord,
+                        &DiscordEvent::BigDonation {
+                            author: msg.author.clone(),
+                            amount: amount.clone(),
+                            message: msg.content.clone(),
+                        },
+                    );
+                }
+                let _ = app.emit(
+                    "chat:big_donation",
+                    BigDonationEvent {
+                        connection_id: conn_id,
+                        message_id: msg.id.clone(),
+                        author: msg.author.clone(),
+                        channel_id: msg.channel_id.clone(),
+                        amount,
+                    },
+                );
+            }
+        }
+
+        // メンバーシップの Discord 通知
+        if let (Some(discord), Some(event)) = (&discord, DiscordEvent::membership(msg)) {
+            discord.0.notify(&config.discord, &event);
+        }
+    }
+}
+
+/// 設定の InnerTube クライアント情報（不正なら既定値に戻して警告する）
+///
+/// config.toml を直接編集した場合は `config_set_value` の検証を通らないため、ここでも検証する。
+fn innertube_context(config: &Config) -> InnerTubeContext {
+    match config.innertube.validate() {
+        Ok(()) => config.innertube.clone(),
+        Err(e) => {
+            tracing::warn!("innertube の設定が不正なため既定値を使用: {}", e);
+            InnerTubeContext::default()
+        }
+    }
+}
+
+/// 保存済みの認証情報を設定した InnerTube クライアントを作成・初期化する
+///
+/// 接続できた場合はチャットモードも設定する。
+async fn init_innertube_client(
+    app: &AppHandle,
+    video_id: &str,
+    chat_mode: ChatMode,
+) -> anyhow::Result<(InnerTubeClient, ConnectionStatus)> {
+    let config = app.state::<ConfigState>().get();
+    let mut client = InnerTubeClient::new(video_id)
+        .with_context(innertube_context(&config))
+        .with_proxy(&config.proxy)?;
+    if let Some(credentials) = auth::load_api_credentials(&config.storage, &config.proxy).await {
+        client.set_credentials(credentials);
+    }
+
+    let status = client.initialize().await?;
+    if status.is_connected && !client.set_chat_mode(chat_mode) {
+        tracing::warn!("Failed to set chat mode to {:?}, using default", chat_mode);
+    }
+    if status.is_connected {
+        let store = Arc::clone(&app.state::<AppState>().continuation_store);
+        resume_saved_continuation(&mut client, &store, chat_mode).await;
+    }
+    Ok((client, status))
+}
+
+/// 保存済みの取得位置があれば、そこから取得を再開する（spec: 02_chat.md 取得位置の再開）
+///
+/// 保存済みの continuation token にもチャットモードを適用する。
+/// token が無効だった場合はクライアントがライブエッジに切り替える。
+async fn resume_saved_continuation(
+    client: &mut InnerTubeClient,
+    store: &ContinuationStore,
+    chat_mode: ChatMode,
+) {
+    let Some(saved) = store.load(client.video_id()).await else {
+        return;
+    };
+    tracing::info!("保存済みの取得位置から再開 video_id: {}", client.video_id());
+    client.resume_from(saved);
+    if !client.set_chat_mode(chat_mode) {
+        tracing::warn!(
+            "Failed to set chat mode to {:?} on saved continuation",
+            chat_mode
+        );
+    }
+}
+
+/// 次の配信への自動接続結果
+struct NextLiveConnection {
+    video_id: String,
+    session_id: Option<String>,
+}
+
+/// 配信終了後、設定に応じてチャンネルを監視し次の配信へ接続する
+///
+/// 接続 ID・キャンセレーショントークン・チャットモードは引き継ぎ、
+/// 新しいデータベースセッションを開始する。設定が無効、チャンネル ID が不明、
+/// またはキャンセルされた場合は None を返す。
+async fn follow_next_live(
+    app: &AppHandle,
+    connection_id: u64,
+    channel_id: Option<&str>,
+    ended_video_id: &str,
+    chat_mode: ChatMode,
+    innertube_client: &Arc<RwLock<Option<InnerTubeClient>>>,
+    cancellation_token: &CancellationToken,
+) -> Option<NextLiveConnection> {
+    let config = app.state::<ConfigState>().get();
+    if !config.monitoring.follow_next_live {
+        return None;
+    }
+    let channel_id = channel_id.filter(|id| !id.is_empty())?;
+    let poll_interval =
+        std::time::Duration::from_secs(config.monitoring.next_live_poll_interval_secs);
+    let resolver = match YouTubeLiveResolver::with_proxy(&config.proxy) {
+        Ok(resolver) => resolver,
+        Err(e) => {
+            tracing::warn!("次の配信の監視を開始できません: {}", e);
+            return None;
+        }
+    };
+    let state = app.state::<AppState>();
+
+    loop {
+        let video_id = wait_for_next_live(
+            &resolver,
+            channel_id,
+            ended_video_id,
+            poll_interval,
+            cancellation_token,
+        )
+        .await?;
+
+        let (client, status) = match init_innertube_client(app, &video_id, chat_mode).await {
+            Ok((client, status)) if status.is_connected => (client, status),
+            other => {
+                tracing::warn!(
+                    "次の配信への接続に失敗 video_id: {}: {:?}",
+                    video_id,
+                    other.err()
+                );
+                // 接続できるまで同じ間隔で再試行する
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => return None,
+                    _ = tokio::time::sleep(poll_interval) => continue,
+                }
+            }
+        };
+
+        // 待機中に切断された接続は再開しない
+        if !state.connections.read().await.contains_key(&connection_id) {
+            return None;
+        }
+
+        let url = format!("https://www.youtube.com/watch?v={}", video_id);
+        let mut result = ConnectionResult::from(status);
+        result.connection_id = connection_id;
+        result.session_id = create_stream_session(&state, &url, &result).await;
+
+        {
+            let mut connections = state.connections.write().await;
+            if let Some(conn) = connections.get_mut(&connection_id) {
+                conn.stream_url = url;
+                conn.stream_title = result.stream_title.clone().unwrap_or_default();
+                conn.session_id = result.session_id.clone();
+            }
+        }
+
+        *innertube_client.write().await = Some(client);
+
+        tracing::info!(
+            "次の配信へ自動接続 connection_id: {} video_id: {}",
+            connection_id,
+            video_id
+        );
+        let _ = app.emit("chat:connection", &result);
+
+        return Some(NextLiveConnection {
+            video_id,
+            session_id: result.session_id,
+        });
+    }
+}
+
 /// 特定の配信への接続を切断する
 #[tauri::command]
 pub async fn disconnect_stream(
@@ -599,3 +1116,148 @@ pub async fn set_chat_mode(
 
     Ok(true)
 }
+
+/// 配信のメタデータ（タイトル・チャンネル・同時視聴者数・配信状態）を取得する
+///
+/// `url` は配信 URL または動画 ID。同じ動画の結果は短時間キャッシュされる。
+#[tauri::command]
+pub async fn get_video_metadata(
+    config_state: State<'_, ConfigState>,
+    url: String,
+) -> Result<VideoMetadata, CommandError> {
+    let video_id = extract_video_id(&url)
+        .ok_or_else(|| CommandError::InvalidInput("Invalid YouTube URL".to_string()))?;
+    fetch_video_metadata(&video_id, &config_state.get().proxy)
+        .await
+        .map_err(|e| {
+            if is_proxy_error(&e) {
+                CommandError::ProxyError(e.to_string())
+            } else {
+                CommandError::ConnectionFailed(format!("Failed to fetch metadata: {}", e))
+            }
+        })
+}
+
+/// One page of archive search results
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ArchiveSearchPage {
+    /// 新しい順の `offset` 件目から最大 `limit` 件
+    pub messages: Vec<GuiChatMessage>,
+    /// 一致した件数の合計（メッセージバッファとディスクアーカイブの合計）
+    pub total: usize,
+}
+
+/// 本文でメッセージを検索する（メモリ上の直近のメッセージ → ディスクアーカイブの順、新しい順）
+///
+/// spec: 02_chat.md メッセージのディスクアーカイブ
+#[tauri::command]
+pub async fn search_archive_by_content(
+    state: State<'_, AppState>,
+    config_state: State<'_, ConfigState>,
+    query: String,
+    offset: usize,
+    limit: usize,
+) -> Result<ArchiveSearchPage, CommandError> {
+    let archive_to_disk = config_state.get().archive.archive_to_disk;
+    search_archive(
+        &state,
+        archive_to_disk,
+        &query,
+        offset,
+        limit,
+        ArchiveField::Content,
+    )
+    .await
+}
+
+/// 投稿者名でメッセージを検索する（メモリ上の直近のメッセージ → ディスクアーカイブの順、新しい順）
+///
+/// spec: 02_chat.md メッセージのディスクアーカイブ
+#[tauri::command]
+pub async fn search_archive_by_author(
+    state: State<'_, AppState>,
+    config_state: State<'_, ConfigState>,
+    query: String,
+    offset: usize,
+    limit: usize,
+) -> Result<ArchiveSearchPage, CommandError> {
+    let archive_to_disk = config_state.get().archive.archive_to_disk;
+    search_archive(
+        &state,
+        archive_to_disk,
+        &query,
+        offset,
+        limit,
+        ArchiveField::Author,
+    )
+    .await
+}
+
+/// アーカイブ検索の対象
+#[derive(Debug, Clone, Copy)]
+enum ArchiveField {
+    Content,
+    Author,
+}
+
+/// メッセージバッファ（新しい順）に続けてディスクアーカイブ（新しい順）を探し、`offset` 件目から最大 `limit` 件返す
+///
+/// アーカイブのメッセージは接続情報を持たないため `connection_id` は 0 になる。
+async fn search_archive(
+    state: &AppState,
+    archive_to_disk: Option<std::path::PathBuf>,
+    query: &str,
+    offset: usize,
+    limit: usize,
+    field: ArchiveField,
+) -> Result<ArchiveSearchPage, CommandError> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(ArchiveSearchPage {
+            messages: Vec::new(),
+            total: 0,
+        });
+    }
+    let matches = match field {
+        ArchiveField::Content => disk_archive::content_matches,
+        ArchiveField::Author => disk_archive::author_matches,
+    };
+    let (mut found, buffer_total): (Vec<ChatMessage>, usize) = {
+        let messages = state.messages.read().await;
+        let matched: Vec<&ChatMessage> = messages
+            .iter()
+            .rev()
+            .filter(|msg| matches(msg, &query))
+            .collect();
+        let page = matched
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|msg| (*msg).clone())
+            .collect();
+        (page, matched.len())
+    };
+
+    // バッファの分を読み飛ばした残りをアーカイブのオフセットにする
+    let mut total = buffer_total;
+    if let Some(path) = archive_to_disk {
+        let archive = DiskArchive::new(path);
+        let archive_offset = offset.saturating_sub(buffer_total);
+        let remaining = limit - found.len();
+        let older = tokio::task::spawn_blocking(move || match field {
+            ArchiveField::Content => archive.search_by_content(&query, archive_offset, remaining),
+            ArchiveField::Author => archive.search_by_author(&query, archive_offset, remaining),
+        })
+        .await
+        .map_err(|e| CommandError::Internal(e.to_string()))?
+        .map_err(|e| CommandError::IoError(format!("Failed to read archive: {}", e)))?;
+        total += older.total;
+        found.extend(older.messages);
+    }
+
+    Ok(ArchiveSearchPage {
+        messages: found.into_iter().map(GuiChatMessage::from).collect(),
+        total,
+    })
+}
@@ -2,9 +2,25 @@
 //!
 //! Implements 09_config.md specification
 
+use crate::core::api::{
+    InnerTubeContext, MAX_POLL_INTERVAL_OVERRIDE, MIN_POLL_INTERVAL_OVERRIDE, ParsedAmount,
+    ProxyAuth, ProxyConfig, parse_purchase_amount,
+};
+use crate::core::capture_watchdog::MIN_STALL_THRESHOLD;
+use crate::core::currency::{CurrencyConverter, StaticRateTable};
+use crate::core::discord::{DiscordConfig, is_valid_webhook_url};
+use crate::core::engagement::EngagementConfig;
+use crate::core::milestones::MilestoneConfig;
+use crate::core::models::{ChatMessage, MessageType};
+use crate::core::notifications::NotificationConfig;
+use crate::core::processing_queue::{
+    DEFAULT_PROCESSING_QUEUE_CAPACITY, PROCESSING_QUEUE_CAPACITY_RANGE, QueueFullPolicy,
+};
+use crate::core::revenue_goal::RevenueGoal;
 use crate::errors::CommandError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::RwLock;
@@ -24,12 +40,20 @@ pub enum StorageMode {
 pub struct StorageConfig {
     #[serde(default)]
     pub mode: StorageMode,
+    /// 使用中のアカウントプロファイル（None はデフォルトプロファイル）
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// 保存済みの名前付きプロファイル
+    #[serde(default)]
+    pub profiles: Vec<String>,
 }
 
 impl Default for StorageConfig {
     fn default() -> Self {
         Self {
             mode: StorageMode::Secure,
+            active_profile: None,
+            profiles: Vec::new(),
         }
     }
 }
@@ -63,6 +87,26 @@ pub struct ChatDisplayConfig {
     pub message_font_size: u32,
     pub show_timestamps: bool,
     pub auto_scroll_enabled: bool,
+    /// 常に非表示にする投稿者のチャンネルID
+    pub blocked_channel_ids: Vec<String>,
+    /// 指定時はこのチャンネルIDの投稿者のみ表示する（`blocked_channel_ids` も併せて適用）
+    pub allowed_channel_ids: Option<Vec<String>>,
+    /// 常に強調表示するキーワード・投稿者
+    pub highlight: HighlightRules,
+    /// 「自分宛て」フィルタで本文と照合する配信者のハンドル・呼び名
+    pub mention_keywords: Vec<String>,
+}
+
+/// 常に強調表示するメッセージの条件（spec: 02_chat.md 注目ワードの強調表示）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightRules {
+    /// 本文にいずれかを含むメッセージを強調する（全角/半角・大文字小文字を区別しない）
+    pub keywords: Vec<String>,
+    /// このチャンネルIDの投稿者のメッセージを強調する
+    pub channel_ids: Vec<String>,
+    /// 強調色（`#rrggbb`。空ならテーマの色）
+    pub color: String,
 }
 
 impl Default for ChatDisplayConfig {
@@ -71,10 +115,248 @@ impl Default for ChatDisplayConfig {
             message_font_size: 13,
             show_timestamps: true,
             auto_scroll_enabled: true,
+            blocked_channel_ids: Vec::new(),
+            allowed_channel_ids: None,
+            highlight: HighlightRules::default(),
+            mention_keywords: Vec::new(),
+        }
+    }
+}
+
+impl ChatDisplayConfig {
+    /// 投稿者のチャンネルIDがブロック・許可リストの条件を満たすか（システムメッセージは常に満たす）
+    pub fn shows_channel(&self, msg: &ChatMessage) -> bool {
+        if matches!(msg.message_type, MessageType::System) {
+            return true;
+        }
+        !self.blocked_channel_ids.contains(&msg.channel_id)
+            && self
+                .allowed_channel_ids
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&msg.channel_id))
+    }
+}
+
+/// チャンネルID・キーワードのリストの前後空白を除き、空文字と重複を取り除く（順序は維持）
+fn normalize_channel_ids(ids: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    ids.into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && seen.insert(id.clone()))
+        .collect()
+}
+
+/// `#rrggbb` 形式の色か
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 次の配信待機時のポーリング間隔の有効範囲（秒）
+pub const NEXT_LIVE_POLL_INTERVAL_RANGE: std::ops::RangeInclusive<u64> = 10..=3600;
+
+/// チャットのポーリング間隔の指定の有効範囲（ミリ秒）
+pub const POLL_INTERVAL_OVERRIDE_RANGE_MS: std::ops::RangeInclusive<u64> =
+    (MIN_POLL_INTERVAL_OVERRIDE.as_millis() as u64)
+        ..=(MAX_POLL_INTERVAL_OVERRIDE.as_millis() as u64);
+
+/// ポーリング停止判定しきい値の有効範囲（秒）。下限はリトライ中の試行間隔から決まる
+pub const STALL_THRESHOLD_RANGE: std::ops::RangeInclusive<u64> =
+    MIN_STALL_THRESHOLD.as_secs()..=600;
+
+/// エンゲージメントのスナップショット間隔の有効範囲（秒）
+pub const ENGAGEMENT_INTERVAL_RANGE: std::ops::RangeInclusive<u64> = 10..=3600;
+
+/// 保持するエンゲージメントのスナップショット件数の有効範囲
+pub const ENGAGEMENT_MAX_SNAPSHOTS_RANGE: std::ops::RangeInclusive<usize> = 1..=10_000;
+
+/// ローカルフィードのポートの有効範囲（特権ポートは使わない）
+pub const LOCAL_SERVER_PORT_RANGE: std::ops::RangeInclusive<u16> = 1024..=65535;
+
+/// Local feed server configuration section (`local_server` feature)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalServerConfig {
+    /// 起動時にローカルフィード（`http://127.0.0.1:{port}/events`）を開始するか
+    pub enabled: bool,
+    /// 待ち受けるポート（127.0.0.1 のみ）
+    pub port: u16,
+}
+
+impl Default for LocalServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8780,
+        }
+    }
+}
+
+/// メモリに保持するメッセージ数（ホットアーカイブ）の有効範囲
+pub const ARCHIVE_HOT_SIZE_RANGE: std::ops::RangeInclusive<usize> = 100..=100_000;
+
+/// Message archive configuration section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// 指定すると、メモリから外れたメッセージをこのファイル（NDJSON）に追記する（絶対パス）
+    pub archive_to_disk: Option<PathBuf>,
+    /// メモリに保持する直近のメッセージ数
+    pub hot_size: usize,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            archive_to_disk: None,
+            hot_size: crate::state::MAX_MESSAGES,
+        }
+    }
+}
+
+/// Monitoring configuration section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitoringConfig {
+    /// 配信終了後にチャンネルを監視し、次の配信へ自動接続するか
+    pub follow_next_live: bool,
+    /// 次の配信を待機する際のポーリング間隔（秒）
+    pub next_live_poll_interval_secs: u64,
+    /// 最後のポーリング成功からこの秒数を超えたらポーリングタスクを再起動する
+    pub stall_threshold_secs: u64,
+    /// チャットのポーリング間隔（ミリ秒）。指定するとサーバーの `timeoutMs` の代わりに使う
+    pub poll_interval_override_ms: Option<u64>,
+    /// 未対応のアクションの生の JSON を追記する NDJSON ファイル（絶対パス、未指定なら保存しない）
+    pub unknown_actions_file: Option<PathBuf>,
+    /// 取得と処理の間のキューの容量（メッセージ数）
+    pub processing_queue_capacity: usize,
+    /// キューが満杯のときの動作
+    pub processing_queue_policy: QueueFullPolicy,
+}
+
+impl MonitoringConfig {
+    /// サーバーの `timeoutMs` の代わりに使うポーリング間隔
+    pub fn poll_interval_override(&self) -> Option<std::time::Duration> {
+        self.poll_interval_override_ms
+            .map(std::time::Duration::from_millis)
+    }
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            follow_next_live: false,
+            next_live_poll_interval_secs: 60,
+            stall_threshold_secs: 60,
+            poll_interval_override_ms: None,
+            unknown_actions_file: None,
+            processing_queue_capacity: DEFAULT_PROCESSING_QUEUE_CAPACITY,
+            processing_queue_policy: QueueFullPolicy::default(),
+        }
+    }
+}
+
+/// Donation alert configuration section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DonationAlertConfig {
+    /// 高額スーパーチャットの通知（`chat:big_donation`）を有効にするか
+    pub enabled: bool,
+    /// 通知する金額のしきい値（`base_currency` 建て、この金額以上で通知）
+    pub threshold: f64,
+    /// しきい値の通貨（ISO 4217 コード）
+    pub base_currency: String,
+}
+
+impl Default for DonationAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 10000.0,
+            base_currency: "JPY".to_string(),
+        }
+    }
+}
+
+impl DonationAlertConfig {
+    /// 通知対象の高額スーパーチャットなら金額を返す
+    ///
+    /// SuperChat / SuperSticker のうち、`base_currency` に換算して `threshold` 以上のもの。
+    /// 換算レートがない通貨の金額は対象外。
+    pub fn big_donation_amount(
+        &self,
+        message_type: &MessageType,
+        converter: &dyn CurrencyConverter,
+    ) -> Option<ParsedAmount> {
+        if !self.enabled {
+            return None;
+        }
+        let amount = match message_type {
+            MessageType::SuperChat { amount } | MessageType::SuperSticker { amount } => amount,
+            _ => return None,
+        };
+        let parsed = parse_purchase_amount(amount)?;
+        let converted = converter.convert(parsed.amount, &parsed.currency, &self.base_currency)?;
+        (converted >= self.threshold).then_some(parsed)
+    }
+}
+
+/// Currency configuration section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CurrencyConfig {
+    /// 金額の合計などを換算する基準通貨（ISO 4217 コード）
+    pub base_currency: String,
+    /// 通貨 1 単位あたりの `base_currency` 建ての値（例: `USD = 150.0`）
+    pub rates: BTreeMap<String, f64>,
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            base_currency: "JPY".to_string(),
+            rates: BTreeMap::new(),
         }
     }
 }
 
+impl CurrencyConfig {
+    /// 設定のレートから換算表を作る
+    pub fn rate_table(&self) -> StaticRateTable {
+        StaticRateTable::new(
+            &self.base_currency,
+            self.rates.iter().map(|(k, v)| (k.clone(), *v)),
+        )
+    }
+}
+
+/// ISO 4217 通貨コードを検証し、大文字に揃える
+fn normalize_currency_code(code: &str) -> Result<String, CommandError> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(CommandError::InvalidInput(format!(
+            "Currency must be an ISO 4217 code, got {}",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+/// マイルストーンのしきい値を検証し、昇順に並べて重複を除く
+fn normalize_thresholds<T: Copy + Ord + Default + std::fmt::Display>(
+    thresholds: Vec<T>,
+) -> Result<Vec<T>, CommandError> {
+    if let Some(invalid) = thresholds.iter().find(|t| **t == T::default()) {
+        return Err(CommandError::InvalidInput(format!(
+            "Milestone thresholds must be positive, got {}",
+            invalid
+        )));
+    }
+    let mut thresholds = thresholds;
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    Ok(thresholds)
+}
+
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -84,6 +366,30 @@ pub struct Config {
     pub chat_display: ChatDisplayConfig,
     #[serde(default)]
     pub ui: UiConfig,
+    #[serde(default)]
+    pub monitoring: MonitoringConfig,
+    #[serde(default)]
+    pub donation_alert: DonationAlertConfig,
+    #[serde(default)]
+    pub currency: CurrencyConfig,
+    #[serde(default)]
+    pub milestones: MilestoneConfig,
+    #[serde(default)]
+    pub revenue_goal: RevenueGoal,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub engagement: EngagementConfig,
+    #[serde(default)]
+    pub discord: DiscordConfig,
+    #[serde(default)]
+    pub local_server: LocalServerConfig,
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+    #[serde(default)]
+    pub innertube: InnerTubeContext,
+    #[serde(default)]
+    pub proxy: ProxyConfig,
 }
 
 /// Configuration state for managing in-memory config
@@ -164,7 +470,7 @@ fn save_config_to_path(path: &std::path::Path, config: &Config) -> Result<(), St
 }
 
 /// Load config from file
-fn load_config_from_file() -> Config {
+pub(crate) fn load_config_from_file() -> Config {
     match get_config_path() {
         Ok(p) => load_config_from_path(&p),
         Err(e) => {
@@ -215,12 +521,144 @@ pub(crate) fn config_lookup(config: &Config, section: &str, key: &str) -> Option
             "auto_scroll_enabled" => {
                 Some(serde_json::to_value(config.chat_display.auto_scroll_enabled).unwrap())
             }
+            "blocked_channel_ids" => {
+                Some(serde_json::to_value(&config.chat_display.blocked_channel_ids).unwrap())
+            }
+            "allowed_channel_ids" => {
+                Some(serde_json::to_value(&config.chat_display.allowed_channel_ids).unwrap())
+            }
+            "highlight" => Some(serde_json::to_value(&config.chat_display.highlight).unwrap()),
+            "mention_keywords" => {
+                Some(serde_json::to_value(&config.chat_display.mention_keywords).unwrap())
+            }
             _ => None,
         },
         "ui" => match key {
             "theme" => Some(serde_json::to_value(&config.ui.theme).unwrap()),
             _ => None,
         },
+        "monitoring" => match key {
+            "follow_next_live" => {
+                Some(serde_json::to_value(config.monitoring.follow_next_live).unwrap())
+            }
+            "next_live_poll_interval_secs" => {
+                Some(serde_json::to_value(config.monitoring.next_live_poll_interval_secs).unwrap())
+            }
+            "stall_threshold_secs" => {
+                Some(serde_json::to_value(config.monitoring.stall_threshold_secs).unwrap())
+            }
+            "poll_interval_override_ms" => {
+                Some(serde_json::to_value(config.monitoring.poll_interval_override_ms).unwrap())
+            }
+            "unknown_actions_file" => {
+                Some(serde_json::to_value(&config.monitoring.unknown_actions_file).unwrap())
+            }
+            "processing_queue_capacity" => {
+                Some(serde_json::to_value(config.monitoring.processing_queue_capacity).unwrap())
+            }
+            "processing_queue_policy" => {
+                Some(serde_json::to_value(config.monitoring.processing_queue_policy).unwrap())
+            }
+            _ => None,
+        },
+        "currency" => match key {
+            "base_currency" => Some(serde_json::to_value(&config.currency.base_currency).unwrap()),
+            "rates" => Some(serde_json::to_value(&config.currency.rates).unwrap()),
+            _ => None,
+        },
+        "donation_alert" => match key {
+            "enabled" => Some(serde_json::to_value(config.donation_alert.enabled).unwrap()),
+            "threshold" => Some(serde_json::to_value(config.donation_alert.threshold).unwrap()),
+            "base_currency" => {
+                Some(serde_json::to_value(&config.donation_alert.base_currency).unwrap())
+            }
+            _ => None,
+        },
+        "milestones" => match key {
+            "enabled" => Some(serde_json::to_value(config.milestones.enabled).unwrap()),
+            "unique_chatters" => {
+                Some(serde_json::to_value(&config.milestones.unique_chatters).unwrap())
+            }
+            "super_chat_total" => {
+                Some(serde_json::to_value(&config.milestones.super_chat_total).unwrap())
+            }
+            "stream_duration_minutes" => {
+                Some(serde_json::to_value(&config.milestones.stream_duration_minutes).unwrap())
+            }
+            "tts" => Some(serde_json::to_value(config.milestones.tts).unwrap()),
+            _ => None,
+        },
+        "revenue_goal" => match key {
+            "target" => Some(serde_json::to_value(config.revenue_goal.target).unwrap()),
+            "currency" => Some(serde_json::to_value(&config.revenue_goal.currency).unwrap()),
+            _ => None,
+        },
+        "notifications" => match key {
+            "enabled" => Some(serde_json::to_value(config.notifications.enabled).unwrap()),
+            "keywords" => Some(serde_json::to_value(&config.notifications.keywords).unwrap()),
+            "on_superchat" => {
+                Some(serde_json::to_value(config.notifications.on_superchat).unwrap())
+            }
+            "on_membership" => {
+                Some(serde_json::to_value(config.notifications.on_membership).unwrap())
+            }
+            "sound" => Some(serde_json::to_value(&config.notifications.sound).unwrap()),
+            "cooldown_secs" => {
+                Some(serde_json::to_value(config.notifications.cooldown_secs).unwrap())
+            }
+            _ => None,
+        },
+        "engagement" => match key {
+            "snapshot_interval_secs" => {
+                Some(serde_json::to_value(config.engagement.snapshot_interval_secs).unwrap())
+            }
+            "max_snapshots" => Some(serde_json::to_value(config.engagement.max_snapshots).unwrap()),
+            _ => None,
+        },
+        "discord" => match key {
+            "enabled" => Some(serde_json::to_value(config.discord.enabled).unwrap()),
+            "webhook_url" => Some(serde_json::to_value(&config.discord.webhook_url).unwrap()),
+            "big_donations" => Some(serde_json::to_value(config.discord.big_donations).unwrap()),
+            "memberships" => Some(serde_json::to_value(config.discord.memberships).unwrap()),
+            "milestones" => Some(serde_json::to_value(config.discord.milestones).unwrap()),
+            "donation_template" => {
+                Some(serde_json::to_value(&config.discord.donation_template).unwrap())
+            }
+            "membership_template" => {
+                Some(serde_json::to_value(&config.discord.membership_template).unwrap())
+            }
+            "milestone_template" => {
+                Some(serde_json::to_value(&config.discord.milestone_template).unwrap())
+            }
+            _ => None,
+        },
+        "local_server" => match key {
+            "enabled" => Some(serde_json::to_value(config.local_server.enabled).unwrap()),
+            "port" => Some(serde_json::to_value(config.local_server.port).unwrap()),
+            _ => None,
+        },
+        "archive" => match key {
+            "archive_to_disk" => {
+                Some(serde_json::to_value(&config.archive.archive_to_disk).unwrap())
+            }
+            "hot_size" => Some(serde_json::to_value(config.archive.hot_size).unwrap()),
+            _ => None,
+        },
+        "innertube" => match key {
+            "client_name" => Some(serde_json::to_value(&config.innertube.client_name).unwrap()),
+            "client_version" => {
+                Some(serde_json::to_value(&config.innertube.client_version).unwrap())
+            }
+            "user_agent" => Some(serde_json::to_value(&config.innertube.user_agent).unwrap()),
+            "gl" => Some(serde_json::to_value(&config.innertube.gl).unwrap()),
+            "hl" => Some(serde_json::to_value(&config.innertube.hl).unwrap()),
+            _ => None,
+        },
+        "proxy" => match key {
+            "url" => Some(serde_json::to_value(&config.proxy.url).unwrap()),
+            "auth" => Some(serde_json::to_value(&config.proxy.auth).unwrap()),
+            _ => None,
+        },
         _ => None,
     }
 }
@@ -289,6 +727,41 @@ pub(crate) fn config_apply_value(
                         ))
                     })?;
             }
+            "blocked_channel_ids" => {
+                let ids: Vec<String> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid blocked_channel_ids value: {}", e))
+                })?;
+                new_config.chat_display.blocked_channel_ids = normalize_channel_ids(ids);
+            }
+            "allowed_channel_ids" => {
+                let ids: Option<Vec<String>> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid allowed_channel_ids value: {}", e))
+                })?;
+                new_config.chat_display.allowed_channel_ids = ids.map(normalize_channel_ids);
+            }
+            "highlight" => {
+                let rules: HighlightRules = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid highlight value: {}", e))
+                })?;
+                let color = rules.color.trim().to_string();
+                if !color.is_empty() && !is_hex_color(&color) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Highlight color must be #rrggbb, got {}",
+                        color
+                    )));
+                }
+                new_config.chat_display.highlight = HighlightRules {
+                    keywords: normalize_channel_ids(rules.keywords),
+                    channel_ids: normalize_channel_ids(rules.channel_ids),
+                    color,
+                };
+            }
+            "mention_keywords" => {
+                let keywords: Vec<String> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid mention_keywords value: {}", e))
+                })?;
+                new_config.chat_display.mention_keywords = normalize_channel_ids(keywords);
+            }
             _ => {
                 return Err(CommandError::InvalidInput(format!(
                     "Unknown key in chat_display section: {}",
@@ -309,323 +782,1709 @@ pub(crate) fn config_apply_value(
                 )));
             }
         },
-        _ => {
-            return Err(CommandError::InvalidInput(format!(
-                "Unknown section: {}",
-                section
-            )));
-        }
-    }
-
-    Ok(new_config)
-}
-
-/// Set a specific configuration value and save
-#[tauri::command]
-pub async fn config_set_value(
-    section: String,
-    key: String,
-    value: Value,
-    state: State<'_, ConfigState>,
-) -> Result<(), CommandError> {
-    let config = state.get();
-    let new_config = config_apply_value(&config, &section, &key, value)?;
-
-    state.set(new_config.clone());
-
-    // ファイル保存を試行。失敗してもメモリ上の変更は維持
-    if let Err(e) = save_config_to_file(&new_config) {
-        log::error!("Failed to save config: {}", e);
-    }
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // ========================================================================
-    // Config defaults (09_config.md: デフォルト値)
-    // ========================================================================
-
-    #[test]
-    fn config_default_values() {
-        let config = Config::default();
-        assert_eq!(config.storage.mode, StorageMode::Secure);
-        assert_eq!(config.chat_display.message_font_size, 13);
-        assert!(config.chat_display.show_timestamps);
-        assert!(config.chat_display.auto_scroll_enabled);
-        assert_eq!(config.ui.theme, Theme::Dark);
-    }
-
-    #[test]
-    fn storage_mode_default() {
-        assert_eq!(StorageMode::default(), StorageMode::Secure);
-    }
-
-    #[test]
-    fn theme_default() {
-        assert_eq!(Theme::default(), Theme::Dark);
-    }
-
-    // ========================================================================
-    // ConfigState get/set (09_config.md: メモリ上の設定操作)
-    // ========================================================================
-
-    #[test]
-    fn config_state_get_returns_default() {
-        let state = ConfigState::new();
-        let config = state.get();
-        assert_eq!(config.chat_display.message_font_size, 13);
-    }
-
-    #[test]
-    fn config_state_set_updates_value() {
-        let state = ConfigState::new();
-        let mut config = state.get();
-        config.chat_display.message_font_size = 20;
-        state.set(config);
-
-        let updated = state.get();
-        assert_eq!(updated.chat_display.message_font_size, 20);
-    }
-
-    // ========================================================================
-    // TOML serialization/deserialization (09_config.md: シリアライズ往復)
-    // ========================================================================
-
-    #[test]
-    fn config_toml_roundtrip() {
-        let config = Config::default();
-        let toml_str = toml::to_string_pretty(&config).unwrap();
-        let parsed: Config = toml::from_str(&toml_str).unwrap();
-
-        assert_eq!(parsed.storage.mode, config.storage.mode);
-        assert_eq!(
-            parsed.chat_display.message_font_size,
-            config.chat_display.message_font_size
-        );
-        assert_eq!(
-            parsed.chat_display.show_timestamps,
-            config.chat_display.show_timestamps
-        );
-        assert_eq!(
-            parsed.chat_display.auto_scroll_enabled,
-            config.chat_display.auto_scroll_enabled
-        );
-        assert_eq!(parsed.ui.theme, config.ui.theme);
+        "monitoring" => match key {
+            "follow_next_live" => {
+                new_config.monitoring.follow_next_live =
+                    serde_json::from_value(value).map_err(|e| {
+                        CommandError::InvalidInput(format!("Invalid follow_next_live value: {}", e))
+                    })?;
+            }
+            "next_live_poll_interval_secs" => {
+                let secs: u64 = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid poll interval value: {}", e))
+                })?;
+                if !NEXT_LIVE_POLL_INTERVAL_RANGE.contains(&secs) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Poll interval must be between {} and {} seconds, got {}",
+                        NEXT_LIVE_POLL_INTERVAL_RANGE.start(),
+                        NEXT_LIVE_POLL_INTERVAL_RANGE.end(),
+                        secs
+                    )));
+                }
+                new_config.monitoring.next_live_poll_interval_secs = secs;
+            }
+            "stall_threshold_secs" => {
+                let secs: u64 = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid stall threshold value: {}", e))
+                })?;
+                if !STALL_THRESHOLD_RANGE.contains(&secs) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Stall threshold must be between {} and {} seconds, got {}",
+                        STALL_THRESHOLD_RANGE.start(),
+                        STALL_THRESHOLD_RANGE.end(),
+                        secs
+                    )));
+                }
+                new_config.monitoring.stall_threshold_secs = secs;
+            }
+            "poll_interval_override_ms" => {
+                let ms: Option<u64> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid poll interval override: {}", e))
+                })?;
+                if let Some(ms) = ms.filter(|ms| !POLL_INTERVAL_OVERRIDE_RANGE_MS.contains(ms)) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Poll interval override must be between {} and {} milliseconds, got {}",
+                        POLL_INTERVAL_OVERRIDE_RANGE_MS.start(),
+                        POLL_INTERVAL_OVERRIDE_RANGE_MS.end(),
+                        ms
+                    )));
+                }
+                new_config.monitoring.poll_interval_override_ms = ms;
+            }
+            "unknown_actions_file" => {
+                let path: Option<String> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid unknown_actions_file value: {}", e))
+                })?;
+                // 空文字列は無効化として扱う
+                let path = path
+                    .map(|p| PathBuf::from(p.trim()))
+                    .filter(|p| !p.as_os_str().is_empty());
+                if let Some(path) = path.as_ref().filter(|p| !p.is_absolute()) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "unknown_actions_file must be an absolute path, got {}",
+                        path.display()
+                    )));
+                }
+                new_config.monitoring.unknown_actions_file = path;
+            }
+            "processing_queue_capacity" => {
+                let capacity: usize = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!(
+                        "Invalid processing_queue_capacity value: {}",
+                        e
+                    ))
+                })?;
+                if !PROCESSING_QUEUE_CAPACITY_RANGE.contains(&capacity) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "processing_queue_capacity must be between {} and {}, got {}",
+                        PROCESSING_QUEUE_CAPACITY_RANGE.start(),
+                        PROCESSING_QUEUE_CAPACITY_RANGE.end(),
+                        capacity
+                    )));
+                }
+                new_config.monitoring.processing_queue_capacity = capacity;
+            }
+            "processing_queue_policy" => {
+                new_config.monitoring.processing_queue_policy = serde_json::from_value(value)
+                    .map_err(|e| {
+                        CommandError::InvalidInput(format!(
+                            "Invalid processing_queue_policy value: {}",
+                            e
+                        ))
+                    })?;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in monitoring section: {}",
+                    key
+                )));
+            }
+        },
+        "donation_alert" => match key {
+            "enabled" => {
+                new_config.donation_alert.enabled = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid enabled value: {}", e))
+                })?;
+            }
+            "threshold" => {
+                let threshold: f64 = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid threshold value: {}", e))
+                })?;
+                if !threshold.is_finite() || threshold <= 0.0 {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Threshold must be a positive number, got {}",
+                        threshold
+                    )));
+                }
+                new_config.donation_alert.threshold = threshold;
+            }
+            "base_currency" => {
+                let currency: String = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid base_currency value: {}", e))
+                })?;
+                new_config.donation_alert.base_currency = normalize_currency_code(&currency)?;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in donation_alert section: {}",
+                    key
+                )));
+            }
+        },
+        "currency" => match key {
+            "base_currency" => {
+                let currency: String = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid base_currency value: {}", e))
+                })?;
+                new_config.currency.base_currency = normalize_currency_code(&currency)?;
+            }
+            "rates" => {
+                let rates: BTreeMap<String, f64> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid rates value: {}", e))
+                })?;
+                let mut normalized = BTreeMap::new();
+                for (currency, rate) in rates {
+                    if !rate.is_finite() || rate <= 0.0 {
+                        return Err(CommandError::InvalidInput(format!(
+                            "Rate for {} must be a positive number, got {}",
+                            currency, rate
+                        )));
+                    }
+                    normalized.insert(normalize_currency_code(&currency)?, rate);
+                }
+                new_config.currency.rates = normalized;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in currency section: {}",
+                    key
+                )));
+            }
+        },
+        "milestones" => match key {
+            "enabled" => {
+                new_config.milestones.enabled = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid enabled value: {}", e))
+                })?;
+            }
+            "unique_chatters" => {
+                let thresholds: Vec<u32> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid unique_chatters value: {}", e))
+                })?;
+                new_config.milestones.unique_chatters = normalize_thresholds(thresholds)?;
+            }
+            "super_chat_total" => {
+                let thresholds: Vec<u64> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid super_chat_total value: {}", e))
+                })?;
+                new_config.milestones.super_chat_total = normalize_thresholds(thresholds)?;
+            }
+            "stream_duration_minutes" => {
+                let thresholds: Vec<u32> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!(
+                        "Invalid stream_duration_minutes value: {}",
+                        e
+                    ))
+                })?;
+                new_config.milestones.stream_duration_minutes = normalize_thresholds(thresholds)?;
+            }
+            "tts" => {
+                new_config.milestones.tts = serde_json::from_value(value)
+                    .map_err(|e| CommandError::InvalidInput(format!("Invalid tts value: {}", e)))?;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in milestones section: {}",
+                    key
+                )));
+            }
+        },
+        "revenue_goal" => match key {
+            "target" => {
+                let target: f64 = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid target value: {}", e))
+                })?;
+                // 0 は目標なし
+                if !target.is_finite() || target < 0.0 {
+                    return Err(CommandError::InvalidInput(format!(
+                        "target must be zero or a positive number, got {}",
+                        target
+                    )));
+                }
+                new_config.revenue_goal.target = target;
+            }
+            "currency" => {
+                let currency: String = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid currency value: {}", e))
+                })?;
+                new_config.revenue_goal.currency = normalize_currency_code(&currency)?;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in revenue_goal section: {}",
+                    key
+                )));
+            }
+        },
+        "notifications" => match key {
+            "enabled" => {
+                new_config.notifications.enabled = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid enabled value: {}", e))
+                })?;
+            }
+            "keywords" => {
+                let keywords: Vec<String> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid keywords value: {}", e))
+                })?;
+                // 空のキーワードは全メッセージに一致してしまうため保存しない
+                new_config.notifications.keywords = keywords
+                    .into_iter()
+                    .map(|k| k.trim().to_string())
+                    .filter(|k| !k.is_empty())
+                    .collect();
+            }
+            "on_superchat" => {
+                new_config.notifications.on_superchat =
+                    serde_json::from_value(value).map_err(|e| {
+                        CommandError::InvalidInput(format!("Invalid on_superchat value: {}", e))
+                    })?;
+            }
+            "on_membership" => {
+                new_config.notifications.on_membership =
+                    serde_json::from_value(value).map_err(|e| {
+                        CommandError::InvalidInput(format!("Invalid on_membership value: {}", e))
+                    })?;
+            }
+            "sound" => {
+                let sound: Option<PathBuf> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid sound value: {}", e))
+                })?;
+                new_config.notifications.sound = sound.filter(|p| !p.as_os_str().is_empty());
+            }
+            "cooldown_secs" => {
+                new_config.notifications.cooldown_secs =
+                    serde_json::from_value(value).map_err(|e| {
+                        CommandError::InvalidInput(format!("Invalid cooldown_secs value: {}", e))
+                    })?;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in notifications section: {}",
+                    key
+                )));
+            }
+        },
+        "engagement" => match key {
+            "snapshot_interval_secs" => {
+                let secs: u64 = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid snapshot interval value: {}", e))
+                })?;
+                if !ENGAGEMENT_INTERVAL_RANGE.contains(&secs) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Snapshot interval must be between {} and {} seconds, got {}",
+                        ENGAGEMENT_INTERVAL_RANGE.start(),
+                        ENGAGEMENT_INTERVAL_RANGE.end(),
+                        secs
+                    )));
+                }
+                new_config.engagement.snapshot_interval_secs = secs;
+            }
+            "max_snapshots" => {
+                let count: usize = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid max_snapshots value: {}", e))
+                })?;
+                if !ENGAGEMENT_MAX_SNAPSHOTS_RANGE.contains(&count) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Max snapshots must be between {} and {}, got {}",
+                        ENGAGEMENT_MAX_SNAPSHOTS_RANGE.start(),
+                        ENGAGEMENT_MAX_SNAPSHOTS_RANGE.end(),
+                        count
+                    )));
+                }
+                new_config.engagement.max_snapshots = count;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in engagement section: {}",
+                    key
+                )));
+            }
+        },
+        "discord" => match key {
+            "enabled" | "big_donations" | "memberships" | "milestones" => {
+                let flag: bool = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid {} value: {}", key, e))
+                })?;
+                match key {
+                    "enabled" => new_config.discord.enabled = flag,
+                    "big_donations" => new_config.discord.big_donations = flag,
+                    "memberships" => new_config.discord.memberships = flag,
+                    _ => new_config.discord.milestones = flag,
+                }
+            }
+            "webhook_url" => {
+                let url: String = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid webhook_url value: {}", e))
+                })?;
+                let url = url.trim().to_string();
+                // 空文字は未設定として受け付ける
+                if !url.is_empty() && !is_valid_webhook_url(&url) {
+                    return Err(CommandError::InvalidInput(
+                        "Webhook URL must start with https://discord.com/api/webhooks/".to_string(),
+                    ));
+                }
+                new_config.discord.webhook_url = url;
+            }
+            "donation_template" | "membership_template" | "milestone_template" => {
+                let template: String = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid {} value: {}", key, e))
+                })?;
+                match key {
+                    "donation_template" => new_config.discord.donation_template = template,
+                    "membership_template" => new_config.discord.membership_template = template,
+                    _ => new_config.discord.milestone_template = template,
+                }
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in discord section: {}",
+                    key
+                )));
+            }
+        },
+        "local_server" => match key {
+            "enabled" => {
+                new_config.local_server.enabled = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid enabled value: {}", e))
+                })?;
+            }
+            "port" => {
+                let port: u16 = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid port value: {}", e))
+                })?;
+                if !LOCAL_SERVER_PORT_RANGE.contains(&port) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Port must be between {} and {}, got {}",
+                        LOCAL_SERVER_PORT_RANGE.start(),
+                        LOCAL_SERVER_PORT_RANGE.end(),
+                        port
+                    )));
+                }
+                new_config.local_server.port = port;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in local_server section: {}",
+                    key
+                )));
+            }
+        },
+        "archive" => match key {
+            "archive_to_disk" => {
+                let path: Option<String> = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid archive_to_disk value: {}", e))
+                })?;
+                // 空文字列は無効化として扱う
+                let path = path
+                    .map(|p| PathBuf::from(p.trim()))
+                    .filter(|p| !p.as_os_str().is_empty());
+                if let Some(path) = path.as_ref().filter(|p| !p.is_absolute()) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "archive_to_disk must be an absolute path, got {}",
+                        path.display()
+                    )));
+                }
+                new_config.archive.archive_to_disk = path;
+            }
+            "hot_size" => {
+                let size: usize = serde_json::from_value(value).map_err(|e| {
+                    CommandError::InvalidInput(format!("Invalid hot_size value: {}", e))
+                })?;
+                if !ARCHIVE_HOT_SIZE_RANGE.contains(&size) {
+                    return Err(CommandError::InvalidInput(format!(
+                        "hot_size must be between {} and {}, got {}",
+                        ARCHIVE_HOT_SIZE_RANGE.start(),
+                        ARCHIVE_HOT_SIZE_RANGE.end(),
+                        size
+                    )));
+                }
+                new_config.archive.hot_size = size;
+            }
+            _ => {
+                return Err(CommandError::InvalidInput(format!(
+                    "Unknown key in archive section: {}",
+                    key
+                )));
+            }
+        },
+        "innertube" => {
+            let mut context = new_config.innertube.clone();
+            let field = match key {
+                "client_name" => &mut context.client_name,
+                "client_version" => &mut context.client_version,
+                "user_agent" => &mut context.user_agent,
+                "gl" => &mut context.gl,
+                "hl" => &mut context.hl,
+                _ => {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Unknown key in innertube section: {}",
+                        key
+                    )));
+                }
+            };
+            let text: String = serde_json::from_value(value)
+                .map_err(|e| CommandError::InvalidInput(format!("Invalid {} value: {}", key, e)))?;
+            *field = text.trim().to_string();
+            context.validate().map_err(CommandError::InvalidInput)?;
+            new_config.innertube = context;
+        }
+        "proxy" => {
+            let mut proxy = new_config.proxy.clone();
+            match key {
+                "url" => {
+                    let url: Option<String> = serde_json::from_value(value).map_err(|e| {
+                        CommandError::InvalidInput(format!("Invalid url value: {}", e))
+                    })?;
+                    // 空文字列はプロキシなし（環境変数に従う）として扱う
+                    proxy.url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+                }
+                "auth" => {
+                    proxy.auth =
+                        serde_json::from_value::<Option<ProxyAuth>>(value).map_err(|e| {
+                            CommandError::InvalidInput(format!("Invalid auth value: {}", e))
+                        })?;
+                }
+                _ => {
+                    return Err(CommandError::InvalidInput(format!(
+                        "Unknown key in proxy section: {}",
+                        key
+                    )));
+                }
+            }
+            proxy.validate().map_err(CommandError::InvalidInput)?;
+            new_config.proxy = proxy;
+        }
+        _ => {
+            return Err(CommandError::InvalidInput(format!(
+                "Unknown section: {}",
+                section
+            )));
+        }
+    }
+
+    Ok(new_config)
+}
+
+/// Set a specific configuration value and save
+#[tauri::command]
+pub async fn config_set_value(
+    section: String,
+    key: String,
+    value: Value,
+    state: State<'_, ConfigState>,
+) -> Result<(), CommandError> {
+    let config = state.get();
+    let new_config = config_apply_value(&config, &section, &key, value)?;
+
+    state.set(new_config.clone());
+
+    // ファイル保存を試行。失敗してもメモリ上の変更は維持
+    if let Err(e) = save_config_to_file(&new_config) {
+        log::error!("Failed to save config: {}", e);
     }
 
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========================================================================
+    // Config defaults (09_config.md: デフォルト値)
+    // ========================================================================
+
     #[test]
-    fn config_toml_migration_partial_keys() {
-        // 09_config.md: 一部キーのみのTOML → 不足分はデフォルト値で補完
-        let partial_toml = r#"
-[storage]
-mode = "fallback"
-"#;
-        let config: Config = toml::from_str(partial_toml).unwrap();
-        assert_eq!(config.storage.mode, StorageMode::Fallback);
+    fn config_default_values() {
+        let config = Config::default();
+        assert_eq!(config.storage.mode, StorageMode::Secure);
         assert_eq!(config.chat_display.message_font_size, 13);
         assert!(config.chat_display.show_timestamps);
+        assert!(config.chat_display.auto_scroll_enabled);
         assert_eq!(config.ui.theme, Theme::Dark);
     }
 
     #[test]
-    fn config_toml_unknown_keys_ignored() {
-        // 09_config.md: 未知のキーは無視
-        let toml_with_extra = r#"
-[storage]
-mode = "secure"
-unknown_field = "value"
+    fn storage_mode_default() {
+        assert_eq!(StorageMode::default(), StorageMode::Secure);
+    }
 
-[chat_display]
-message_font_size = 16
-future_setting = true
-"#;
+    #[test]
+    fn theme_default() {
+        assert_eq!(Theme::default(), Theme::Dark);
+    }
+
+    // ========================================================================
+    // ConfigState get/set (09_config.md: メモリ上の設定操作)
+    // ========================================================================
+
+    #[test]
+    fn config_state_get_returns_default() {
+        let state = ConfigState::new();
+        let config = state.get();
+        assert_eq!(config.chat_display.message_font_size, 13);
+    }
+
+    #[test]
+    fn config_state_set_updates_value() {
+        let state = ConfigState::new();
+        let mut config = state.get();
+        config.chat_display.message_font_size = 20;
+        state.set(config);
+
+        let updated = state.get();
+        assert_eq!(updated.chat_display.message_font_size, 20);
+    }
+
+    // ========================================================================
+    // TOML serialization/deserialization (09_config.md: シリアライズ往復)
+    // ========================================================================
+
+    #[test]
+    fn config_toml_roundtrip() {
+        let config = Config::default();
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: Config = toml::from_str(&toml_str).unwrap();
+
+        assert_eq!(parsed.storage.mode, config.storage.mode);
+        assert_eq!(
+            parsed.chat_display.message_font_size,
+            config.chat_display.message_font_size
+        );
+        assert_eq!(
+            parsed.chat_display.show_timestamps,
+            config.chat_display.show_timestamps
+        );
+        assert_eq!(
+            parsed.chat_display.auto_scroll_enabled,
+            config.chat_display.auto_scroll_enabled
+        );
+        assert_eq!(parsed.ui.theme, config.ui.theme);
+    }
+
+    #[test]
+    fn config_toml_migration_partial_keys() {
+        // 09_config.md: 一部キーのみのTOML → 不足分はデフォルト値で補完
+        let partial_toml = r#"
+[storage]
+mode = "fallback"
+"#;
+        let config: Config = toml::from_str(partial_toml).unwrap();
+        assert_eq!(config.storage.mode, StorageMode::Fallback);
+        assert_eq!(config.chat_display.message_font_size, 13);
+        assert!(config.chat_display.show_timestamps);
+        assert_eq!(config.ui.theme, Theme::Dark);
+    }
+
+    #[test]
+    fn config_toml_unknown_keys_ignored() {
+        // 09_config.md: 未知のキーは無視
+        let toml_with_extra = r#"
+[storage]
+mode = "secure"
+unknown_field = "value"
+
+[chat_display]
+message_font_size = 16
+future_setting = true
+"#;
         let config: Config = toml::from_str(toml_with_extra).unwrap();
         assert_eq!(config.storage.mode, StorageMode::Secure);
         assert_eq!(config.chat_display.message_font_size, 16);
     }
 
-    // ========================================================================
-    // Font size validation (09_config.md: フォントサイズ範囲 10-24)
-    // ========================================================================
-
+    // ========================================================================
+    // Font size validation (09_config.md: フォントサイズ範囲 10-24)
+    // ========================================================================
+
+    #[test]
+    fn font_size_valid_range_boundaries() {
+        for size in [10u32, 13, 24] {
+            assert!((10..=24).contains(&size), "Size {} should be valid", size);
+        }
+    }
+
+    #[test]
+    fn font_size_invalid_range() {
+        for size in [9u32, 25, 0, 100] {
+            assert!(
+                !(10..=24).contains(&size),
+                "Size {} should be invalid",
+                size
+            );
+        }
+    }
+
+    // ========================================================================
+    // Serialization format (09_config.md)
+    // ========================================================================
+
+    #[test]
+    fn storage_mode_serializes_lowercase() {
+        assert_eq!(
+            serde_json::to_string(&StorageMode::Secure).unwrap(),
+            "\"secure\""
+        );
+        assert_eq!(
+            serde_json::to_string(&StorageMode::Fallback).unwrap(),
+            "\"fallback\""
+        );
+    }
+
+    #[test]
+    fn theme_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&Theme::Dark).unwrap(), "\"dark\"");
+        assert_eq!(serde_json::to_string(&Theme::Light).unwrap(), "\"light\"");
+    }
+
+    // ========================================================================
+    // config_lookup (09_config.md: 個別値取得)
+    // ========================================================================
+
+    #[test]
+    fn config_lookup_storage_mode_default() {
+        let config = Config::default();
+        let val = config_lookup(&config, "storage", "mode");
+        assert_eq!(val, Some(serde_json::json!("secure")));
+    }
+
+    #[test]
+    fn config_lookup_chat_display_message_font_size_default() {
+        let config = Config::default();
+        let val = config_lookup(&config, "chat_display", "message_font_size");
+        assert_eq!(val, Some(serde_json::json!(13)));
+    }
+
+    #[test]
+    fn config_lookup_chat_display_show_timestamps_default() {
+        let config = Config::default();
+        let val = config_lookup(&config, "chat_display", "show_timestamps");
+        assert_eq!(val, Some(serde_json::json!(true)));
+    }
+
+    #[test]
+    fn config_lookup_chat_display_auto_scroll_enabled_default() {
+        let config = Config::default();
+        let val = config_lookup(&config, "chat_display", "auto_scroll_enabled");
+        assert_eq!(val, Some(serde_json::json!(true)));
+    }
+
+    #[test]
+    fn config_lookup_chat_display_channel_ids_default() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "chat_display", "blocked_channel_ids"),
+            Some(serde_json::json!([]))
+        );
+        assert_eq!(
+            config_lookup(&config, "chat_display", "allowed_channel_ids"),
+            Some(serde_json::Value::Null)
+        );
+    }
+
+    #[test]
+    fn config_lookup_ui_theme_default() {
+        let config = Config::default();
+        let val = config_lookup(&config, "ui", "theme");
+        assert_eq!(val, Some(serde_json::json!("dark")));
+    }
+
+    #[test]
+    fn config_lookup_unknown_section_returns_none() {
+        let config = Config::default();
+        assert_eq!(config_lookup(&config, "nonexistent", "key"), None);
+    }
+
+    #[test]
+    fn config_lookup_unknown_key_returns_none() {
+        let config = Config::default();
+        assert_eq!(config_lookup(&config, "storage", "nonexistent"), None);
+        assert_eq!(config_lookup(&config, "chat_display", "nonexistent"), None);
+        assert_eq!(config_lookup(&config, "ui", "nonexistent"), None);
+        assert_eq!(config_lookup(&config, "monitoring", "nonexistent"), None);
+    }
+
+    #[test]
+    fn config_lookup_monitoring_defaults() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "monitoring", "follow_next_live"),
+            Some(serde_json::json!(false))
+        );
+        assert_eq!(
+            config_lookup(&config, "monitoring", "next_live_poll_interval_secs"),
+            Some(serde_json::json!(60))
+        );
+        assert_eq!(
+            config_lookup(&config, "monitoring", "stall_threshold_secs"),
+            Some(serde_json::json!(60))
+        );
+    }
+
+    // ========================================================================
+    // config_apply_value (09_config.md: 個別値設定・バリデーション)
+    // ========================================================================
+
+    #[test]
+    fn config_apply_value_storage_mode_fallback() {
+        let config = Config::default();
+        let result = config_apply_value(&config, "storage", "mode", serde_json::json!("fallback"));
+        let new_config = result.unwrap();
+        assert_eq!(new_config.storage.mode, StorageMode::Fallback);
+    }
+
+    #[test]
+    fn config_apply_value_font_size_valid() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "chat_display",
+            "message_font_size",
+            serde_json::json!(20),
+        )
+        .unwrap();
+        assert_eq!(new_config.chat_display.message_font_size, 20);
+    }
+
+    #[test]
+    fn config_apply_value_font_size_too_small() {
+        let config = Config::default();
+        let result = config_apply_value(
+            &config,
+            "chat_display",
+            "message_font_size",
+            serde_json::json!(9),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn config_apply_value_font_size_too_large() {
+        let config = Config::default();
+        let result = config_apply_value(
+            &config,
+            "chat_display",
+            "message_font_size",
+            serde_json::json!(25),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn config_apply_value_show_timestamps_false() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "chat_display",
+            "show_timestamps",
+            serde_json::json!(false),
+        )
+        .unwrap();
+        assert!(!new_config.chat_display.show_timestamps);
+    }
+
+    #[test]
+    fn config_apply_value_auto_scroll_enabled_false() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "chat_display",
+            "auto_scroll_enabled",
+            serde_json::json!(false),
+        )
+        .unwrap();
+        assert!(!new_config.chat_display.auto_scroll_enabled);
+    }
+
+    #[test]
+    fn config_apply_value_blocked_channel_ids_normalized() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "chat_display",
+            "blocked_channel_ids",
+            serde_json::json!([" UC_spam ", "UC_bot", "", "UC_spam"]),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.chat_display.blocked_channel_ids,
+            vec!["UC_spam".to_string(), "UC_bot".to_string()]
+        );
+    }
+
+    #[test]
+    fn config_apply_value_allowed_channel_ids_set_and_clear() {
+        let config = Config::default();
+        let with_allowlist = config_apply_value(
+            &config,
+            "chat_display",
+            "allowed_channel_ids",
+            serde_json::json!(["UC_vip"]),
+        )
+        .unwrap();
+        assert_eq!(
+            with_allowlist.chat_display.allowed_channel_ids,
+            Some(vec!["UC_vip".to_string()])
+        );
+
+        let cleared = config_apply_value(
+            &with_allowlist,
+            "chat_display",
+            "allowed_channel_ids",
+            serde_json::Value::Null,
+        )
+        .unwrap();
+        assert_eq!(cleared.chat_display.allowed_channel_ids, None);
+    }
+
+    #[test]
+    fn config_apply_value_highlight() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "chat_display", "highlight"),
+            Some(serde_json::json!({ "keywords": [], "channel_ids": [], "color": "" }))
+        );
+
+        let new_config = config_apply_value(
+            &config,
+            "chat_display",
+            "highlight",
+            serde_json::json!({
+                "keywords": [" 抽選 ", "", "抽選", "mod"],
+                "channel_ids": ["UC_me", " UC_me "],
+                "color": "#FFAA00"
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.chat_display.highlight,
+            HighlightRules {
+                keywords: vec!["抽選".to_string(), "mod".to_string()],
+                channel_ids: vec!["UC_me".to_string()],
+                color: "#FFAA00".to_string(),
+            }
+        );
+
+        for color in ["red", "#ffaa0", "#ffaa00ff"] {
+            let result = config_apply_value(
+                &config,
+                "chat_display",
+                "highlight",
+                serde_json::json!({ "keywords": ["x"], "color": color }),
+            );
+            assert!(
+                matches!(result, Err(CommandError::InvalidInput(_))),
+                "{}",
+                color
+            );
+        }
+    }
+
+    #[test]
+    fn config_apply_value_mention_keywords() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "chat_display", "mention_keywords"),
+            Some(serde_json::json!([]))
+        );
+
+        let new_config = config_apply_value(
+            &config,
+            "chat_display",
+            "mention_keywords",
+            serde_json::json!([" @liscov_ch ", "", "りすこ", "@liscov_ch"]),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.chat_display.mention_keywords,
+            vec!["@liscov_ch".to_string(), "りすこ".to_string()]
+        );
+
+        let result = config_apply_value(
+            &config,
+            "chat_display",
+            "mention_keywords",
+            serde_json::json!("@liscov_ch"),
+        );
+        assert!(matches!(result, Err(CommandError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn config_apply_value_channel_ids_invalid_type() {
+        let config = Config::default();
+        let result = config_apply_value(
+            &config,
+            "chat_display",
+            "blocked_channel_ids",
+            serde_json::json!("UC_spam"),
+        );
+        assert!(matches!(result, Err(CommandError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn config_channel_ids_toml_roundtrip() {
+        let mut config = Config::default();
+        config.chat_display.blocked_channel_ids = vec!["UC_spam".to_string()];
+        config.chat_display.allowed_channel_ids = Some(vec!["UC_vip".to_string()]);
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: Config = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.chat_display.blocked_channel_ids, vec!["UC_spam"]);
+        assert_eq!(
+            parsed.chat_display.allowed_channel_ids,
+            Some(vec!["UC_vip".to_string()])
+        );
+
+        // 未指定なら従来どおり全チャンネルを表示
+        let default_parsed: Config =
+            toml::from_str(&toml::to_string_pretty(&Config::default()).unwrap()).unwrap();
+        assert!(default_parsed.chat_display.blocked_channel_ids.is_empty());
+        assert_eq!(default_parsed.chat_display.allowed_channel_ids, None);
+    }
+
+    #[test]
+    fn config_apply_value_ui_theme_light() {
+        let config = Config::default();
+        let new_config =
+            config_apply_value(&config, "ui", "theme", serde_json::json!("light")).unwrap();
+        assert_eq!(new_config.ui.theme, Theme::Light);
+    }
+
+    #[test]
+    fn config_apply_value_monitoring_follow_next_live() {
+        let config = Config::default();
+        assert!(!config.monitoring.follow_next_live);
+        let new_config = config_apply_value(
+            &config,
+            "monitoring",
+            "follow_next_live",
+            serde_json::json!(true),
+        )
+        .unwrap();
+        assert!(new_config.monitoring.follow_next_live);
+    }
+
+    #[test]
+    fn config_apply_value_next_live_poll_interval_range() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "monitoring",
+            "next_live_poll_interval_secs",
+            serde_json::json!(30),
+        )
+        .unwrap();
+        assert_eq!(new_config.monitoring.next_live_poll_interval_secs, 30);
+
+        for invalid in [9, 3601] {
+            let result = config_apply_value(
+                &config,
+                "monitoring",
+                "next_live_poll_interval_secs",
+                serde_json::json!(invalid),
+            );
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn config_apply_value_stall_threshold_range() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "monitoring",
+            "stall_threshold_secs",
+            serde_json::json!(120),
+        )
+        .unwrap();
+        assert_eq!(new_config.monitoring.stall_threshold_secs, 120);
+
+        for invalid in [44, 601] {
+            let result = config_apply_value(
+                &config,
+                "monitoring",
+                "stall_threshold_secs",
+                serde_json::json!(invalid),
+            );
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn config_apply_value_unknown_actions_file() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "monitoring", "unknown_actions_file"),
+            Some(serde_json::Value::Null)
+        );
+
+        let path = std::env::temp_dir().join("liscov-unknown-actions.ndjson");
+        let new_config = config_apply_value(
+            &config,
+            "monitoring",
+            "unknown_actions_file",
+            serde_json::json!(path.to_string_lossy()),
+        )
+        .unwrap();
+        assert_eq!(new_config.monitoring.unknown_actions_file, Some(path));
+        let new_config = config_apply_value(
+            &new_config,
+            "monitoring",
+            "unknown_actions_file",
+            serde_json::json!(""),
+        )
+        .unwrap();
+        assert_eq!(new_config.monitoring.unknown_actions_file, None);
+        assert!(
+            config_apply_value(
+                &config,
+                "monitoring",
+                "unknown_actions_file",
+                serde_json::json!("unknown.ndjson")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn config_apply_value_processing_queue() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "monitoring", "processing_queue_capacity"),
+            Some(serde_json::json!(1000))
+        );
+        assert_eq!(
+            config_lookup(&config, "monitoring", "processing_queue_policy"),
+            Some(serde_json::json!("block"))
+        );
+
+        let new_config = config_apply_value(
+            &config,
+            "monitoring",
+            "processing_queue_capacity",
+            serde_json::json!(200),
+        )
+        .unwrap();
+        assert_eq!(new_config.monitoring.processing_queue_capacity, 200);
+        let new_config = config_apply_value(
+            &new_config,
+            "monitoring",
+            "processing_queue_policy",
+            serde_json::json!("drop_oldest"),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.monitoring.processing_queue_policy,
+            QueueFullPolicy::DropOldest
+        );
+
+        for (key, invalid) in [
+            ("processing_queue_capacity", serde_json::json!(0)),
+            ("processing_queue_capacity", serde_json::json!(1_000_000)),
+            ("processing_queue_policy", serde_json::json!("drop_newest")),
+        ] {
+            assert!(config_apply_value(&config, "monitoring", key, invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn config_apply_value_poll_interval_override_set_and_clear() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "monitoring", "poll_interval_override_ms"),
+            Some(serde_json::Value::Null)
+        );
+
+        let new_config = config_apply_value(
+            &config,
+            "monitoring",
+            "poll_interval_override_ms",
+            serde_json::json!(3000),
+        )
+        .unwrap();
+        assert_eq!(new_config.monitoring.poll_interval_override_ms, Some(3000));
+        assert_eq!(
+            new_config.monitoring.poll_interval_override(),
+            Some(std::time::Duration::from_secs(3))
+        );
+
+        let cleared = config_apply_value(
+            &new_config,
+            "monitoring",
+            "poll_interval_override_ms",
+            serde_json::Value::Null,
+        )
+        .unwrap();
+        assert_eq!(cleared.monitoring.poll_interval_override_ms, None);
+
+        for invalid in [499, 30_001] {
+            let result = config_apply_value(
+                &config,
+                "monitoring",
+                "poll_interval_override_ms",
+                serde_json::json!(invalid),
+            );
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn config_lookup_donation_alert_defaults() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "donation_alert", "enabled"),
+            Some(serde_json::json!(true))
+        );
+        assert_eq!(
+            config_lookup(&config, "donation_alert", "threshold"),
+            Some(serde_json::json!(10000.0))
+        );
+        assert_eq!(
+            config_lookup(&config, "donation_alert", "base_currency"),
+            Some(serde_json::json!("JPY"))
+        );
+    }
+
+    #[test]
+    fn config_apply_value_donation_alert() {
+        let config = Config::default();
+        let new_config = config_apply_value(
+            &config,
+            "donation_alert",
+            "threshold",
+            serde_json::json!(50),
+        )
+        .unwrap();
+        assert_eq!(new_config.donation_alert.threshold, 50.0);
+
+        let new_config = config_apply_value(
+            &config,
+            "donation_alert",
+            "base_currency",
+            serde_json::json!(" usd "),
+        )
+        .unwrap();
+        assert_eq!(new_config.donation_alert.base_currency, "USD");
+
+        for invalid in [serde_json::json!(0), serde_json::json!(-100)] {
+            assert!(config_apply_value(&config, "donation_alert", "threshold", invalid).is_err());
+        }
+        for invalid in ["", "YEN!", "JPYY"] {
+            assert!(
+                config_apply_value(
+                    &config,
+                    "donation_alert",
+                    "base_currency",
+                    serde_json::json!(invalid),
+                )
+                .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn big_donation_amount_respects_threshold_and_currency() {
+        let config = DonationAlertConfig::default();
+        let rates = StaticRateTable::new("JPY", [("USD".to_string(), 150.0)]);
+        let superchat = |amount: &str| MessageType::SuperChat {
+            amount: amount.to_string(),
+        };
+
+        let parsed = config
+            .big_donation_amount(&superchat("¥10,000"), &rates)
+            .unwrap();
+        assert_eq!(parsed.currency, "JPY");
+        assert_eq!(parsed.amount, 10000.0);
+        assert!(
+            config
+                .big_donation_amount(&superchat("¥9,999"), &rates)
+                .is_none()
+        );
+        // 基準通貨に換算して判定し、元の金額を返す
+        let parsed = config
+            .big_donation_amount(&superchat("$100.00"), &rates)
+            .unwrap();
+        assert_eq!(parsed.currency, "USD");
+        assert!(
+            config
+                .big_donation_amount(&superchat("$50.00"), &rates)
+                .is_none()
+        );
+        // レートのない通貨は対象外
+        assert!(
+            config
+                .big_donation_amount(&superchat("€500,00"), &rates)
+                .is_none()
+        );
+        assert!(
+            config
+                .big_donation_amount(&MessageType::Text, &rates)
+                .is_none()
+        );
+
+        let disabled = DonationAlertConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(
+            disabled
+                .big_donation_amount(&superchat("¥50,000"), &rates)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn big_donation_amount_applies_to_super_stickers() {
+        let config = DonationAlertConfig::default();
+        let rates = CurrencyConfig::default().rate_table();
+        let sticker = |amount: &str| MessageType::SuperSticker {
+            amount: amount.to_string(),
+        };
+        assert!(
+            config
+                .big_donation_amount(&sticker("¥500"), &rates)
+                .is_none()
+        );
+        assert!(
+            config
+                .big_donation_amount(&sticker("¥10,000"), &rates)
+                .is_some()
+        );
+    }
+
     #[test]
-    fn font_size_valid_range_boundaries() {
-        for size in [10u32, 13, 24] {
-            assert!((10..=24).contains(&size), "Size {} should be valid", size);
+    fn config_apply_value_currency() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "currency", "base_currency"),
+            Some(serde_json::json!("JPY"))
+        );
+        assert_eq!(
+            config_lookup(&config, "currency", "rates"),
+            Some(serde_json::json!({}))
+        );
+
+        let new_config = config_apply_value(
+            &config,
+            "currency",
+            "rates",
+            serde_json::json!({ "usd": 150.0, "EUR": 160 }),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.currency.rates,
+            BTreeMap::from([("EUR".to_string(), 160.0), ("USD".to_string(), 150.0)])
+        );
+        assert_eq!(
+            new_config.currency.rate_table().convert(2.0, "USD", "JPY"),
+            Some(300.0)
+        );
+
+        for invalid in [
+            serde_json::json!({ "USD": 0 }),
+            serde_json::json!({ "USD": -1.5 }),
+            serde_json::json!({ "DOLLAR": 150 }),
+        ] {
+            assert!(config_apply_value(&config, "currency", "rates", invalid).is_err());
         }
+        assert!(
+            config_apply_value(
+                &config,
+                "currency",
+                "base_currency",
+                serde_json::json!("yen")
+            )
+            .is_err()
+        );
     }
 
     #[test]
-    fn font_size_invalid_range() {
-        for size in [9u32, 25, 0, 100] {
+    fn config_apply_value_milestones() {
+        let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "milestones", "enabled"),
+            Some(serde_json::json!(false))
+        );
+        assert_eq!(
+            config_lookup(&config, "milestones", "super_chat_total"),
+            Some(serde_json::json!([50000]))
+        );
+
+        let new_config = config_apply_value(
+            &config,
+            "milestones",
+            "unique_chatters",
+            serde_json::json!([500, 100, 500, 1000]),
+        )
+        .unwrap();
+        assert_eq!(new_config.milestones.unique_chatters, vec![100, 500, 1000]);
+
+        let new_config =
+            config_apply_value(&config, "milestones", "tts", serde_json::json!(true)).unwrap();
+        assert!(new_config.milestones.tts);
+
+        for invalid in [
+            serde_json::json!([0]),
+            serde_json::json!([-60]),
+            serde_json::json!(60),
+        ] {
             assert!(
-                !(10..=24).contains(&size),
-                "Size {} should be invalid",
-                size
+                config_apply_value(&config, "milestones", "stream_duration_minutes", invalid)
+                    .is_err()
             );
         }
     }
 
-    // ========================================================================
-    // Serialization format (09_config.md)
-    // ========================================================================
-
     #[test]
-    fn storage_mode_serializes_lowercase() {
+    fn config_apply_value_revenue_goal() {
+        let config = Config::default();
         assert_eq!(
-            serde_json::to_string(&StorageMode::Secure).unwrap(),
-            "\"secure\""
+            config_lookup(&config, "revenue_goal", "target"),
+            Some(serde_json::json!(0.0))
         );
         assert_eq!(
-            serde_json::to_string(&StorageMode::Fallback).unwrap(),
-            "\"fallback\""
+            config_lookup(&config, "revenue_goal", "currency"),
+            Some(serde_json::json!("JPY"))
         );
-    }
-
-    #[test]
-    fn theme_serializes_lowercase() {
-        assert_eq!(serde_json::to_string(&Theme::Dark).unwrap(), "\"dark\"");
-        assert_eq!(serde_json::to_string(&Theme::Light).unwrap(), "\"light\"");
-    }
 
-    // ========================================================================
-    // config_lookup (09_config.md: 個別値取得)
-    // ========================================================================
+        let new_config =
+            config_apply_value(&config, "revenue_goal", "target", serde_json::json!(50000))
+                .unwrap();
+        assert_eq!(new_config.revenue_goal.target, 50000.0);
+        let new_config = config_apply_value(
+            &new_config,
+            "revenue_goal",
+            "currency",
+            serde_json::json!("usd"),
+        )
+        .unwrap();
+        assert_eq!(new_config.revenue_goal.currency, "USD");
+        assert_eq!(new_config.revenue_goal.target, 50000.0);
 
-    #[test]
-    fn config_lookup_storage_mode_default() {
-        let config = Config::default();
-        let val = config_lookup(&config, "storage", "mode");
-        assert_eq!(val, Some(serde_json::json!("secure")));
+        for invalid in [serde_json::json!(-1), serde_json::json!("50000")] {
+            assert!(config_apply_value(&config, "revenue_goal", "target", invalid).is_err());
+        }
+        assert!(
+            config_apply_value(
+                &config,
+                "revenue_goal",
+                "currency",
+                serde_json::json!("yen")
+            )
+            .is_err()
+        );
     }
 
     #[test]
-    fn config_lookup_chat_display_message_font_size_default() {
+    fn config_apply_value_innertube() {
         let config = Config::default();
-        let val = config_lookup(&config, "chat_display", "message_font_size");
-        assert_eq!(val, Some(serde_json::json!(13)));
-    }
+        assert_eq!(
+            config_lookup(&config, "innertube", "client_version"),
+            Some(serde_json::json!("2.20240101.00.00"))
+        );
+        assert_eq!(
+            config_lookup(&config, "innertube", "client_name"),
+            Some(serde_json::json!("WEB"))
+        );
 
-    #[test]
-    fn config_lookup_chat_display_show_timestamps_default() {
-        let config = Config::default();
-        let val = config_lookup(&config, "chat_display", "show_timestamps");
-        assert_eq!(val, Some(serde_json::json!(true)));
+        let new_config = config_apply_value(
+            &config,
+            "innertube",
+            "client_version",
+            serde_json::json!(" 2.20250301.01.00 "),
+        )
+        .unwrap();
+        assert_eq!(new_config.innertube.client_version, "2.20250301.01.00");
+        let new_config =
+            config_apply_value(&new_config, "innertube", "hl", serde_json::json!("ja")).unwrap();
+        assert_eq!(new_config.innertube.hl, "ja");
+        assert_eq!(new_config.innertube.client_version, "2.20250301.01.00");
+
+        for (key, invalid) in [
+            ("client_version", serde_json::json!("latest")),
+            ("client_version", serde_json::json!(2)),
+            ("client_name", serde_json::json!("")),
+            ("user_agent", serde_json::json!("a\nb")),
+        ] {
+            assert!(config_apply_value(&config, "innertube", key, invalid).is_err());
+        }
+        assert!(
+            config_apply_value(&config, "innertube", "api_key", serde_json::json!("x")).is_err()
+        );
     }
 
     #[test]
-    fn config_lookup_chat_display_auto_scroll_enabled_default() {
+    fn config_apply_value_proxy() {
         let config = Config::default();
-        let val = config_lookup(&config, "chat_display", "auto_scroll_enabled");
-        assert_eq!(val, Some(serde_json::json!(true)));
-    }
+        assert_eq!(
+            config_lookup(&config, "proxy", "url"),
+            Some(serde_json::Value::Null)
+        );
 
-    #[test]
-    fn config_lookup_ui_theme_default() {
-        let config = Config::default();
-        let val = config_lookup(&config, "ui", "theme");
-        assert_eq!(val, Some(serde_json::json!("dark")));
+        let new_config = config_apply_value(
+            &config,
+            "proxy",
+            "url",
+            serde_json::json!(" socks5h://127.0.0.1:1080 "),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.proxy.url.as_deref(),
+            Some("socks5h://127.0.0.1:1080")
+        );
+        let new_config = config_apply_value(
+            &new_config,
+            "proxy",
+            "auth",
+            serde_json::json!({ "username": "user", "password": "secret" }),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.proxy.auth,
+            Some(ProxyAuth {
+                username: "user".to_string(),
+                password: "secret".to_string(),
+            })
+        );
+        let cleared =
+            config_apply_value(&new_config, "proxy", "url", serde_json::json!("")).unwrap();
+        assert_eq!(cleared.proxy.url, None);
+        let cleared =
+            config_apply_value(&cleared, "proxy", "auth", serde_json::Value::Null).unwrap();
+        assert_eq!(cleared.proxy.auth, None);
+
+        for (key, invalid) in [
+            ("url", serde_json::json!("ftp://proxy.example")),
+            ("url", serde_json::json!("proxy.example:8080")),
+            ("url", serde_json::json!(8080)),
+            (
+                "auth",
+                serde_json::json!({ "username": "", "password": "x" }),
+            ),
+            ("auth", serde_json::json!("user:secret")),
+        ] {
+            assert!(config_apply_value(&config, "proxy", key, invalid).is_err());
+        }
+        assert!(config_apply_value(&config, "proxy", "no_proxy", serde_json::json!("x")).is_err());
     }
 
     #[test]
-    fn config_lookup_unknown_section_returns_none() {
+    fn config_apply_value_notifications() {
         let config = Config::default();
-        assert_eq!(config_lookup(&config, "nonexistent", "key"), None);
-    }
+        assert_eq!(
+            config_lookup(&config, "notifications", "sound"),
+            Some(serde_json::Value::Null)
+        );
 
-    #[test]
-    fn config_lookup_unknown_key_returns_none() {
-        let config = Config::default();
-        assert_eq!(config_lookup(&config, "storage", "nonexistent"), None);
-        assert_eq!(config_lookup(&config, "chat_display", "nonexistent"), None);
-        assert_eq!(config_lookup(&config, "ui", "nonexistent"), None);
-    }
+        let new_config = config_apply_value(
+            &config,
+            "notifications",
+            "keywords",
+            serde_json::json!([" Liscov ", "", "  "]),
+        )
+        .unwrap();
+        assert_eq!(new_config.notifications.keywords, vec!["Liscov"]);
 
-    // ========================================================================
-    // config_apply_value (09_config.md: 個別値設定・バリデーション)
-    // ========================================================================
+        let new_config = config_apply_value(
+            &config,
+            "notifications",
+            "sound",
+            serde_json::json!("C:/sounds/ding.wav"),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.notifications.sound,
+            Some(PathBuf::from("C:/sounds/ding.wav"))
+        );
+        let new_config =
+            config_apply_value(&new_config, "notifications", "sound", serde_json::json!(""))
+                .unwrap();
+        assert_eq!(new_config.notifications.sound, None);
 
-    #[test]
-    fn config_apply_value_storage_mode_fallback() {
-        let config = Config::default();
-        let result = config_apply_value(&config, "storage", "mode", serde_json::json!("fallback"));
-        let new_config = result.unwrap();
-        assert_eq!(new_config.storage.mode, StorageMode::Fallback);
+        assert!(
+            config_apply_value(
+                &config,
+                "notifications",
+                "cooldown_secs",
+                serde_json::json!(-1)
+            )
+            .is_err()
+        );
     }
 
     #[test]
-    fn config_apply_value_font_size_valid() {
+    fn config_apply_value_engagement() {
         let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "engagement", "snapshot_interval_secs"),
+            Some(serde_json::json!(60))
+        );
+
         let new_config = config_apply_value(
             &config,
-            "chat_display",
-            "message_font_size",
-            serde_json::json!(20),
+            "engagement",
+            "max_snapshots",
+            serde_json::json!(120),
         )
         .unwrap();
-        assert_eq!(new_config.chat_display.message_font_size, 20);
+        assert_eq!(new_config.engagement.max_snapshots, 120);
+
+        for (key, value) in [
+            ("snapshot_interval_secs", serde_json::json!(5)),
+            ("snapshot_interval_secs", serde_json::json!(3601)),
+            ("max_snapshots", serde_json::json!(0)),
+            ("max_snapshots", serde_json::json!(10_001)),
+        ] {
+            assert!(
+                config_apply_value(&config, "engagement", key, value).is_err(),
+                "{} should be rejected",
+                key
+            );
+        }
     }
 
     #[test]
-    fn config_apply_value_font_size_too_small() {
+    fn config_apply_value_discord() {
         let config = Config::default();
-        let result = config_apply_value(
-            &config,
-            "chat_display",
-            "message_font_size",
-            serde_json::json!(9),
+        assert_eq!(
+            config_lookup(&config, "discord", "enabled"),
+            Some(serde_json::json!(false))
         );
-        assert!(result.is_err());
-    }
 
-    #[test]
-    fn config_apply_value_font_size_too_large() {
-        let config = Config::default();
-        let result = config_apply_value(
+        let new_config = config_apply_value(
             &config,
-            "chat_display",
-            "message_font_size",
-            serde_json::json!(25),
+            "discord",
+            "webhook_url",
+            serde_json::json!(" https://discord.com/api/webhooks/1/abc "),
+        )
+        .unwrap();
+        assert_eq!(
+            new_config.discord.webhook_url,
+            "https://discord.com/api/webhooks/1/abc"
+        );
+        let new_config = config_apply_value(
+            &new_config,
+            "discord",
+            "memberships",
+            serde_json::json!(false),
+        )
+        .unwrap();
+        assert!(!new_config.discord.memberships);
+        let new_config = config_apply_value(
+            &new_config,
+            "discord",
+            "milestone_template",
+            serde_json::json!("🎉 {message}"),
+        )
+        .unwrap();
+        assert_eq!(new_config.discord.milestone_template, "🎉 {message}");
+
+        // 空文字は未設定として受け付ける
+        assert!(
+            config_apply_value(&config, "discord", "webhook_url", serde_json::json!("")).is_ok()
+        );
+        assert!(
+            config_apply_value(
+                &config,
+                "discord",
+                "webhook_url",
+                serde_json::json!("https://example.com/hook")
+            )
+            .is_err()
         );
-        assert!(result.is_err());
     }
 
     #[test]
-    fn config_apply_value_show_timestamps_false() {
+    fn config_apply_value_local_server() {
         let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "local_server", "port"),
+            Some(serde_json::json!(8780))
+        );
+
+        let new_config =
+            config_apply_value(&config, "local_server", "port", serde_json::json!(9000)).unwrap();
+        assert_eq!(new_config.local_server.port, 9000);
         let new_config = config_apply_value(
-            &config,
-            "chat_display",
-            "show_timestamps",
-            serde_json::json!(false),
+            &new_config,
+            "local_server",
+            "enabled",
+            serde_json::json!(true),
         )
         .unwrap();
-        assert!(!new_config.chat_display.show_timestamps);
+        assert!(new_config.local_server.enabled);
+
+        for port in [serde_json::json!(80), serde_json::json!(70000)] {
+            assert!(config_apply_value(&config, "local_server", "port", port).is_err());
+        }
     }
 
     #[test]
-    fn config_apply_value_auto_scroll_enabled_false() {
+    fn config_apply_value_archive() {
         let config = Config::default();
+        assert_eq!(
+            config_lookup(&config, "archive", "archive_to_disk"),
+            Some(serde_json::Value::Null)
+        );
+
+        let path = std::env::temp_dir().join("liscov-archive.ndjson");
         let new_config = config_apply_value(
             &config,
-            "chat_display",
-            "auto_scroll_enabled",
-            serde_json::json!(false),
+            "archive",
+            "archive_to_disk",
+            serde_json::json!(path.to_string_lossy()),
         )
         .unwrap();
-        assert!(!new_config.chat_display.auto_scroll_enabled);
+        assert_eq!(new_config.archive.archive_to_disk, Some(path));
+        let new_config = config_apply_value(
+            &new_config,
+            "archive",
+            "archive_to_disk",
+            serde_json::json!(""),
+        )
+        .unwrap();
+        assert_eq!(new_config.archive.archive_to_disk, None);
+        assert!(
+            config_apply_value(
+                &config,
+                "archive",
+                "archive_to_disk",
+                serde_json::json!("relative/chat.ndjson")
+            )
+            .is_err()
+        );
+
+        let new_config =
+            config_apply_value(&config, "archive", "hot_size", serde_json::json!(5000)).unwrap();
+        assert_eq!(new_config.archive.hot_size, 5000);
+        for size in [serde_json::json!(10), serde_json::json!(1_000_000)] {
+            assert!(config_apply_value(&config, "archive", "hot_size", size).is_err());
+        }
     }
 
     #[test]
-    fn config_apply_value_ui_theme_light() {
-        let config = Config::default();
-        let new_config =
-            config_apply_value(&config, "ui", "theme", serde_json::json!("light")).unwrap();
-        assert_eq!(new_config.ui.theme, Theme::Light);
+    fn chat_display_shows_channel_applies_block_and_allow_lists() {
+        let message = |channel_id: &str, message_type| ChatMessage {
+            channel_id: channel_id.to_string(),
+            message_type,
+            ..Default::default()
+        };
+        let display = ChatDisplayConfig {
+            blocked_channel_ids: vec!["UC_spam".to_string()],
+            allowed_channel_ids: Some(vec!["UC_vip".to_string(), "UC_spam".to_string()]),
+            ..ChatDisplayConfig::default()
+        };
+
+        assert!(display.shows_channel(&message("UC_vip", MessageType::Text)));
+        assert!(!display.shows_channel(&message("UC_spam", MessageType::Text)));
+        assert!(!display.shows_channel(&message("UC_other", MessageType::Text)));
+        assert!(display.shows_channel(&message("", MessageType::System)));
+        assert!(
+            ChatDisplayConfig::default().shows_channel(&message("UC_other", MessageType::Text))
+        );
+    }
+
+    #[test]
+    fn config_milestones_section_is_optional() {
+        // [milestones] のない既存の config.toml はデフォルト値で読み込む
+        let parsed: Config = toml::from_str("[ui]\ntheme = \"light\"\n").unwrap();
+        assert_eq!(parsed.milestones, MilestoneConfig::default());
+
+        let parsed: Config =
+            toml::from_str("[milestones]\nenabled = true\nunique_chatters = [50]\n").unwrap();
+        assert!(parsed.milestones.enabled);
+        assert_eq!(parsed.milestones.unique_chatters, vec![50]);
+        assert_eq!(parsed.milestones.stream_duration_minutes, vec![60]);
     }
 
     #[test]
@@ -12,6 +12,7 @@ pub struct GuiSession {
     pub id: String,
     pub start_time: String,
     pub end_time: Option<String>,
+    pub video_id: Option<String>,
     pub stream_url: Option<String>,
     pub stream_title: Option<String>,
     pub broadcaster_name: Option<String>,
@@ -25,6 +26,7 @@ impl From<Session> for GuiSession {
             id: s.id,
             start_time: s.start_time,
             end_time: s.end_time,
+            video_id: s.video_id,
             stream_url: s.stream_url,
             stream_title: s.stream_title,
             broadcaster_name: s.broadcaster_name,
@@ -103,6 +105,40 @@ pub async fn get_session_messages(
     Ok(messages.into_iter().map(GuiStoredMessage::from).collect())
 }
 
+/// GUI-friendly full-text search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuiMessageSearchResult {
+    pub session_id: String,
+    pub message: GuiStoredMessage,
+    pub snippet: String,
+}
+
+/// Full-text search over stored messages (content and author)
+#[tauri::command]
+pub async fn search_stored_messages(
+    state: State<'_, AppState>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<GuiMessageSearchResult>, CommandError> {
+    let db_guard = state.database.read().await;
+    let db = db_guard
+        .as_ref()
+        .ok_or_else(|| CommandError::DatabaseError("Database not initialized".to_string()))?;
+
+    let conn = db.connection().await;
+    let results = database::search_messages(&conn, &query, limit.unwrap_or(100))
+        .map_err(|e| CommandError::DatabaseError(format!("Failed to search messages: {}", e)))?;
+
+    Ok(results
+        .into_iter()
+        .map(|r| GuiMessageSearchResult {
+            session_id: r.message.session_id.clone(),
+            message: GuiStoredMessage::from(r.message),
+            snippet: r.snippet,
+        })
+        .collect())
+}
+
 /// Update viewer info (custom info + tags) by viewer_profile_id
 #[tauri::command]
 pub async fn viewer_update_info(
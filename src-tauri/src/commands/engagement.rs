@@ -0,0 +1,56 @@
+//! Engagement timeline commands (spec: 02_chat.md エンゲージメントの推移)
+
+use crate::core::engagement::{EngagementMetrics, EngagementSnapshot};
+use crate::errors::CommandError;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::State;
+
+/// Global engagement metrics state
+///
+/// 全接続のメッセージを集計するため、監視タスクの `deliver_message` から `app.state()` で参照する。
+#[derive(Default)]
+pub struct EngagementState(pub Mutex<EngagementMetrics>);
+
+/// Get the recorded engagement snapshots (oldest first)
+#[tauri::command]
+pub fn get_engagement_snapshots(
+    state: State<'_, EngagementState>,
+) -> Result<Vec<EngagementSnapshot>, CommandError> {
+    let metrics = state
+        .0
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?;
+    Ok(metrics.snapshots().to_vec())
+}
+
+/// Get the message density per time bucket for a heatmap (oldest first)
+///
+/// 投稿時刻を `bucket_secs` 秒ごとに区切り、区間の開始時刻（RFC3339、UTC）と件数を返す。
+/// `weighted` ならスーパーチャットなどを重み付けした件数を返す。
+#[tauri::command]
+pub fn get_message_density(
+    state: State<'_, EngagementState>,
+    bucket_secs: u64,
+    weighted: bool,
+) -> Result<Vec<(String, usize)>, CommandError> {
+    if bucket_secs == 0 {
+        return Err(CommandError::InvalidInput(
+            "bucket_secs must be at least 1".to_string(),
+        ));
+    }
+    let metrics = state
+        .0
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?;
+    let bucket = Duration::from_secs(bucket_secs);
+    let buckets = if weighted {
+        metrics.weighted_density_buckets(bucket)
+    } else {
+        metrics.density_buckets(bucket)
+    };
+    Ok(buckets
+        .into_iter()
+        .map(|(start, count)| (start.to_rfc3339(), count))
+        .collect())
+}
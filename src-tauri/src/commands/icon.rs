@@ -0,0 +1,83 @@
+//! Author icon cache commands (spec: 02_chat.md 投稿者アイコンのキャッシュ)
+
+use crate::commands::config::ConfigState;
+use crate::core::api::{ProxyConfig, build_http_client_with_proxy};
+use crate::core::icon_cache::{IconCache, IconFetcher, is_allowed_icon_url};
+use crate::errors::CommandError;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::State;
+
+/// Global author icon cache state
+#[derive(Default)]
+pub struct IconCacheState {
+    /// HTTP client and the proxy config it was built with (rebuilt when `[proxy]` changes)
+    client: Mutex<Option<(ProxyConfig, reqwest::Client)>>,
+    cache: Mutex<IconCache>,
+    /// Fetches in flight, so concurrent requests for one URL share a download
+    fetcher: IconFetcher,
+}
+
+impl IconCacheState {
+    /// HTTP client for the current proxy config, rebuilding it if the config has changed
+    fn client(&self, proxy: &ProxyConfig) -> Result<reqwest::Client, CommandError> {
+        let mut client = self
+            .client
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?;
+        match client.as_ref() {
+            Some((built_with, http_client)) if built_with == proxy => Ok(http_client.clone()),
+            _ => {
+                let http_client = build_http_client_with_proxy(proxy)?;
+                *client = Some((proxy.clone(), http_client.clone()));
+                Ok(http_client)
+            }
+        }
+    }
+}
+
+/// Get an author icon as a data URL, downloading it only on cache miss
+#[tauri::command]
+pub async fn get_author_icon(
+    state: State<'_, IconCacheState>,
+    config_state: State<'_, ConfigState>,
+    url: String,
+) -> Result<String, CommandError> {
+    if !is_allowed_icon_url(&url) {
+        return Err(CommandError::InvalidInput(format!(
+            "Not an author icon URL: {}",
+            url
+        )));
+    }
+
+    if let Some(data_url) = state
+        .cache
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?
+        .get(&url, Instant::now())
+    {
+        return Ok(data_url);
+    }
+
+    let client = state.client(&config_state.get().proxy)?;
+    let data_url = state.fetcher.fetch(&client, &url).await?;
+
+    state
+        .cache
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?
+        .insert(url, data_url.clone(), Instant::now());
+    Ok(data_url)
+}
+
+/// Drop all cached author icons
+#[tauri::command]
+pub fn clear_icon_cache(state: State<'_, IconCacheState>) -> Result<(), CommandError> {
+    let mut cache = state
+        .cache
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?;
+    tracing::info!("🖼️ Icon cache cleared: {} entries", cache.len());
+    cache.clear();
+    Ok(())
+}
@@ -6,7 +6,12 @@ pub mod auth_window;
 pub mod chat;
 pub mod config;
 pub mod database;
+pub mod engagement;
+pub mod icon;
+pub mod moderation;
+pub mod notifications;
 pub mod raw_response;
+pub mod replay;
 pub mod tts;
 pub mod viewer;
 pub mod websocket;
@@ -17,7 +22,12 @@ pub use auth::*;
 pub use chat::*;
 pub use config::*;
 pub use database::*;
+pub use engagement::*;
+pub use icon::*;
+pub use moderation::*;
+pub use notifications::*;
 pub use raw_response::*;
+pub use replay::*;
 pub use tts::*;
 pub use viewer::*;
 pub use websocket::*;
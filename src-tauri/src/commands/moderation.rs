@@ -0,0 +1,51 @@
+//! Local moderation rule commands (spec: 02_chat.md ローカルモデレーション)
+
+use crate::core::moderation::{LocalModerator, ModerationRule};
+use crate::errors::CommandError;
+use std::sync::{Arc, RwLock};
+use tauri::State;
+
+/// Global local moderator state
+///
+/// 監視タスクの emit コールバックからも参照するため Arc で共有する。
+#[derive(Default)]
+pub struct ModerationState(pub Arc<RwLock<LocalModerator>>);
+
+fn rules_path() -> Result<std::path::PathBuf, CommandError> {
+    crate::paths::moderation_rules_path().map_err(CommandError::IoError)
+}
+
+/// Load moderation rules from file and apply them
+#[tauri::command]
+pub fn moderation_load_rules(
+    state: State<'_, ModerationState>,
+) -> Result<Vec<ModerationRule>, CommandError> {
+    let moderator = LocalModerator::load(&rules_path()?)?;
+    let rules = moderator.rules().to_vec();
+    *state
+        .0
+        .write()
+        .map_err(|e| CommandError::Internal(format!("RwLock write failed: {}", e)))? = moderator;
+    Ok(rules)
+}
+
+/// Validate, save and apply moderation rules
+///
+/// 不正なルールを含む場合は保存も適用もしない。
+#[tauri::command]
+pub fn moderation_save_rules(
+    state: State<'_, ModerationState>,
+    rules: Vec<ModerationRule>,
+) -> Result<(), CommandError> {
+    let moderator = LocalModerator::new(rules)?;
+    moderator.save(&rules_path()?)?;
+    tracing::info!(
+        "🛡️ Moderation rules updated: {} rules",
+        moderator.rules().len()
+    );
+    *state
+        .0
+        .write()
+        .map_err(|e| CommandError::Internal(format!("RwLock write failed: {}", e)))? = moderator;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Keyword notification commands (spec: 02_chat.md キーワード通知)
+
+use crate::commands::config::ConfigState;
+use crate::core::discord::DiscordNotifier;
+use crate::core::notifications::{MentionNotifier, sound_data_url};
+use crate::errors::CommandError;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Global notifier state
+///
+/// クールダウンを全接続で共有するため、監視タスクの emit コールバックから `app.state()` で参照する。
+#[derive(Default)]
+pub struct NotificationState(pub Mutex<MentionNotifier>);
+
+/// Discord webhook queue state (spec: 02_chat.md Discord への通知)
+///
+/// 投稿の間隔を全接続で共有するため、監視タスクから `app.state()` で参照する。
+#[derive(Default)]
+pub struct DiscordState(pub DiscordNotifier);
+
+/// Get the configured notification sound as a data URL (None if not configured)
+#[tauri::command]
+pub fn notification_sound(
+    config_state: State<'_, ConfigState>,
+) -> Result<Option<String>, CommandError> {
+    let Some(path) = config_state.get().notifications.sound else {
+        return Ok(None);
+    };
+    Ok(Some(sound_data_url(&path)?))
+}
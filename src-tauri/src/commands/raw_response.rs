@@ -1,6 +1,8 @@
 //! Raw response save configuration commands
 
-use crate::core::raw_response::SaveConfig;
+use crate::core::raw_response::{
+    RotationPolicy, SaveConfig, SchemaMismatch, pretty_print, validate_response,
+};
 use crate::errors::CommandError;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
@@ -23,6 +25,8 @@ pub struct GuiSaveConfig {
     pub max_file_size_mb: u64,
     pub enable_rotation: bool,
     pub max_backup_files: u32,
+    #[serde(default)]
+    pub rotation: Option<RotationPolicy>,
 }
 
 impl From<SaveConfig> for GuiSaveConfig {
@@ -33,6 +37,7 @@ impl From<SaveConfig> for GuiSaveConfig {
             max_file_size_mb: config.max_file_size_mb,
             enable_rotation: config.enable_rotation,
             max_backup_files: config.max_backup_files,
+            rotation: config.rotation,
         }
     }
 }
@@ -45,6 +50,7 @@ impl From<GuiSaveConfig> for SaveConfig {
             max_file_size_mb: config.max_file_size_mb,
             enable_rotation: config.enable_rotation,
             max_backup_files: config.max_backup_files,
+            rotation: config.rotation,
         }
     }
 }
@@ -133,6 +139,26 @@ pub fn raw_response_resolve_path(file_path: String) -> Result<String, CommandErr
     }
 }
 
+/// Saved response validation result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuiResponseValidation {
+    /// Pretty-printed JSON (None if the input is not valid JSON)
+    pub pretty: Option<String>,
+    /// Differences from the structure the chat parser expects (empty if valid)
+    pub mismatches: Vec<SchemaMismatch>,
+}
+
+/// Validate a saved response against the chat parser's expectations
+/// (spec: 05_raw_response.md 保存レスポンスの検証)
+#[tauri::command]
+pub fn raw_response_validate(response: String) -> Result<GuiResponseValidation, CommandError> {
+    let bytes = response.as_bytes();
+    Ok(GuiResponseValidation {
+        pretty: pretty_print(bytes).ok(),
+        mismatches: validate_response(bytes).err().unwrap_or_default(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,6 +221,7 @@ mod tests {
             max_file_size_mb: 50,
             enable_rotation: false,
             max_backup_files: 10,
+            rotation: Some(RotationPolicy::ByMessageCount(500)),
         };
         let config = SaveConfig::from(gui);
         assert!(config.enabled);
@@ -202,5 +229,21 @@ mod tests {
         assert_eq!(config.max_file_size_mb, 50);
         assert!(!config.enable_rotation);
         assert_eq!(config.max_backup_files, 10);
+        assert_eq!(config.rotation, Some(RotationPolicy::ByMessageCount(500)));
+    }
+
+    // spec: 05_raw_response.md 保存レスポンスの検証
+    #[test]
+    fn validate_command_returns_pretty_json_and_mismatches() {
+        let result = raw_response_validate(r#"{"responseContext":{}}"#.to_string()).unwrap();
+        assert_eq!(
+            result.pretty.as_deref(),
+            Some("{\n  \"responseContext\": {}\n}")
+        );
+        assert_eq!(result.mismatches.len(), 1);
+
+        let broken = raw_response_validate("{".to_string()).unwrap();
+        assert!(broken.pretty.is_none());
+        assert_eq!(broken.mismatches[0].expected, "JSON");
     }
 }
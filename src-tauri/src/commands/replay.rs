@@ -0,0 +1,180 @@
+//! Raw response replay commands (spec: 05_raw_response.md 再生モード)
+
+use crate::AppState;
+use crate::commands::chat::{GuiChatMessage, gui_message_emitter};
+use crate::commands::moderation::ModerationState;
+use crate::core::chat_runtime::{MonitoringDeps, deliver_message, process_message};
+use crate::core::models::Platform;
+use crate::core::replay::{ReplayController, ReplayPlayer, ReplayStatus, load_replay_items};
+use crate::errors::CommandError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, State};
+
+/// 再生メッセージに付ける接続 ID（実際の接続は 1 から採番される）
+pub const REPLAY_CONNECTION_ID: u64 = 0;
+
+/// Global replay state (at most one replay at a time)
+#[derive(Default)]
+pub struct ReplayState(pub Mutex<Option<ReplayController>>);
+
+impl ReplayState {
+    fn controller(&self) -> Result<ReplayController, CommandError> {
+        self.0
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?
+            .clone()
+            .ok_or_else(|| CommandError::NotFound("No replay in progress".to_string()))
+    }
+}
+
+/// Start replaying a saved raw response file through the live message pipeline
+///
+/// `speed` is the playback multiplier (default 1.0, 0 = no waiting).
+/// A replay already in progress is stopped first.
+#[tauri::command]
+pub async fn replay_start(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    replay_state: State<'_, ReplayState>,
+    moderation_state: State<'_, ModerationState>,
+    file_path: String,
+    speed: Option<f64>,
+) -> Result<ReplayStatus, CommandError> {
+    let speed = speed.unwrap_or(1.0);
+    if !speed.is_finite() || speed < 0.0 {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid replay speed: {}",
+            speed
+        )));
+    }
+
+    let path = file_path.clone();
+    let (timeline, warnings) = tokio::task::spawn_blocking(move || load_replay_items(path))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Replay load task failed: {}", e)))??;
+    tracing::info!(
+        "▶️ Replay started: {} messages from {} (speed {}, skipped {} lines)",
+        timeline.len(),
+        file_path,
+        speed,
+        warnings.len()
+    );
+
+    let controller = ReplayController::new(timeline, speed);
+    {
+        let mut current = replay_state
+            .0
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?;
+        if let Some(previous) = current.replace(controller.clone()) {
+            previous.stop();
+        }
+    }
+
+    let deps = MonitoringDeps::from_state(&state);
+    let emit_gui_message = gui_message_emitter(
+        REPLAY_CONNECTION_ID,
+        Platform::YouTube.as_str().to_string(),
+        String::new(),
+        Arc::clone(&moderation_state.0),
+    );
+    let mut player = ReplayPlayer::new(controller.clone());
+    tokio::spawn(async move {
+        // 配信内コメント数はライブと同じく再生中のメッセージから数える（DB には保存しない）
+        let mut in_stream_counts = HashMap::new();
+        while let Some(msg) = player.next_message().await {
+            let Some(mut msg) = deps.transform(msg) else {
+                continue;
+            };
+            process_message(&mut msg, "", &None, &None, &mut in_stream_counts, &deps).await;
+            deliver_message(&deps, &app, &msg, &emit_gui_message).await;
+        }
+        tracing::info!("⏹️ Replay finished");
+    });
+
+    Ok(controller.status())
+}
+
+/// Pause the current replay
+#[tauri::command]
+pub fn replay_pause(replay_state: State<'_, ReplayState>) -> Result<ReplayStatus, CommandError> {
+    let controller = replay_state.controller()?;
+    controller.pause();
+    Ok(controller.status())
+}
+
+/// Resume the current replay
+#[tauri::command]
+pub fn replay_resume(replay_state: State<'_, ReplayState>) -> Result<ReplayStatus, CommandError> {
+    let controller = replay_state.controller()?;
+    controller.resume();
+    Ok(controller.status())
+}
+
+/// Jump to the given message position (0-based) of the current replay
+#[tauri::command]
+pub fn replay_seek(
+    replay_state: State<'_, ReplayState>,
+    position: usize,
+) -> Result<ReplayStatus, CommandError> {
+    let controller = replay_state.controller()?;
+    controller.seek(position);
+    Ok(controller.status())
+}
+
+/// Jump to the first message posted at or after `at_usec` (microseconds since the epoch)
+#[tauri::command]
+pub fn replay_seek_time(
+    replay_state: State<'_, ReplayState>,
+    at_usec: u64,
+) -> Result<ReplayStatus, CommandError> {
+    let controller = replay_state.controller()?;
+    controller.seek_time(at_usec);
+    Ok(controller.status())
+}
+
+/// Get the replayed messages posted in `[start_usec, end_usec)` (for the timeline scrubber)
+#[tauri::command]
+pub fn replay_messages_in_range(
+    replay_state: State<'_, ReplayState>,
+    start_usec: u64,
+    end_usec: u64,
+) -> Result<Vec<GuiChatMessage>, CommandError> {
+    let controller = replay_state.controller()?;
+    Ok(controller
+        .timeline()
+        .messages_in_range(start_usec, end_usec)
+        .iter()
+        .map(|item| {
+            GuiChatMessage::from_with_connection(
+                item.message.clone(),
+                REPLAY_CONNECTION_ID,
+                Platform::YouTube.as_str(),
+                "",
+            )
+        })
+        .collect())
+}
+
+/// Stop the current replay
+#[tauri::command]
+pub fn replay_stop(replay_state: State<'_, ReplayState>) -> Result<(), CommandError> {
+    let controller = replay_state
+        .0
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Mutex lock failed: {}", e)))?
+        .take();
+    if let Some(controller) = controller {
+        controller.stop();
+    }
+    Ok(())
+}
+
+/// Get the current replay status (None when no replay has been started)
+#[tauri::command]
+pub fn replay_get_status(
+    replay_state: State<'_, ReplayState>,
+) -> Result<Option<ReplayStatus>, CommandError> {
+    Ok(replay_state.controller().ok().map(|c| c.status()))
+}
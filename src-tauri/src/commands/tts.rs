@@ -2,6 +2,7 @@
 
 use crate::errors::CommandError;
 use crate::state::AppState;
+use crate::tts::backends::{Speaker, VoicevoxBackend, is_backend_registered};
 use crate::tts::{TtsBackendType, TtsConfig, TtsPriority, TtsProcessManager, TtsQueueItem};
 use serde::{Deserialize, Serialize};
 use tauri::{Emitter, State};
@@ -11,7 +12,7 @@ use tauri_plugin_dialog::DialogExt;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TtsConfigDto {
     pub enabled: bool,
-    pub backend: String, // "none", "bouyomichan", "voicevox"
+    pub backend: String, // "none", "bouyomichan", "voicevox", カスタムバックエンドのID
     pub read_author_name: bool,
     pub add_honorific: bool,
     pub strip_at_prefix: bool,
@@ -22,6 +23,11 @@ pub struct TtsConfigDto {
     pub first_comment_prefix_enabled: bool,
     pub first_comment_prefix: String,
     pub first_comment_only: bool,
+    pub preempt_normal_on_priority: bool,
+    pub drain_queue_on_exit: bool,
+    pub min_alnum_ratio: f32,
+    pub reading_overrides: Vec<(String, String)>,
+    pub apply_reading_overrides_to_author: bool,
     // Bouyomichan settings
     pub bouyomichan_host: String,
     pub bouyomichan_port: u16,
@@ -49,11 +55,7 @@ impl From<TtsConfig> for TtsConfigDto {
     fn from(config: TtsConfig) -> Self {
         Self {
             enabled: config.enabled,
-            backend: match config.backend {
-                TtsBackendType::None => "none".to_string(),
-                TtsBackendType::Bouyomichan => "bouyomichan".to_string(),
-                TtsBackendType::Voicevox => "voicevox".to_string(),
-            },
+            backend: config.backend.id().to_string(),
             read_author_name: config.read_author_name,
             add_honorific: config.add_honorific,
             strip_at_prefix: config.strip_at_prefix,
@@ -64,6 +66,11 @@ impl From<TtsConfig> for TtsConfigDto {
             first_comment_prefix_enabled: config.first_comment_prefix_enabled,
             first_comment_prefix: config.first_comment_prefix,
             first_comment_only: config.first_comment_only,
+            preempt_normal_on_priority: config.preempt_normal_on_priority,
+            drain_queue_on_exit: config.drain_queue_on_exit,
+            min_alnum_ratio: config.min_alnum_ratio,
+            reading_overrides: config.reading_overrides,
+            apply_reading_overrides_to_author: config.apply_reading_overrides_to_author,
             bouyomichan_host: config.bouyomichan.host,
             bouyomichan_port: config.bouyomichan.port,
             bouyomichan_voice: config.bouyomichan.voice,
@@ -93,10 +100,11 @@ impl From<TtsConfigDto> for TtsConfig {
 
         Self {
             enabled: dto.enabled,
-            backend: match dto.backend.as_str() {
-                "bouyomichan" => TtsBackendType::Bouyomichan,
-                "voicevox" => TtsBackendType::Voicevox,
-                _ => TtsBackendType::None,
+            // 未登録のバックエンドIDは None にフォールバックする
+            backend: if is_backend_registered(&dto.backend) {
+                TtsBackendType::from_id(&dto.backend)
+            } else {
+                TtsBackendType::None
             },
             bouyomichan: BouyomichanConfig {
                 host: dto.bouyomichan_host,
@@ -131,6 +139,11 @@ impl From<TtsConfigDto> for TtsConfig {
             first_comment_prefix_enabled: dto.first_comment_prefix_enabled,
             first_comment_prefix: dto.first_comment_prefix,
             first_comment_only: dto.first_comment_only,
+            preempt_normal_on_priority: dto.preempt_normal_on_priority,
+            drain_queue_on_exit: dto.drain_queue_on_exit,
+            min_alnum_ratio: dto.min_alnum_ratio,
+            reading_overrides: dto.reading_overrides,
+            apply_reading_overrides_to_author: dto.apply_reading_overrides_to_author,
         }
     }
 }
@@ -238,6 +251,18 @@ pub async fn tts_test_connection(
     }
 }
 
+/// VOICEVOX の話者一覧を取得する（設定画面の話者選択用）
+#[tauri::command]
+pub async fn tts_get_voicevox_speakers(
+    state: State<'_, AppState>,
+) -> Result<Vec<Speaker>, CommandError> {
+    let config = state.tts_manager.get_config().await;
+    VoicevoxBackend::new(config.voicevox)
+        .list_speakers()
+        .await
+        .map_err(CommandError::from)
+}
+
 /// Start TTS queue processing
 #[tauri::command]
 pub async fn tts_start(state: State<'_, AppState>) -> Result<(), CommandError> {
@@ -501,6 +526,26 @@ mod tests {
         assert_eq!(config.backend, TtsBackendType::None);
     }
 
+    #[test]
+    fn dto_to_config_backend_registered_custom_id() {
+        // spec: 登録済みのカスタムバックエンドID → TtsBackendType::Custom
+        crate::tts::register_backend(
+            "dto-test-custom",
+            Box::new(|config| Box::new(crate::tts::VoicevoxBackend::new(config.voicevox.clone()))),
+        )
+        .unwrap();
+        let dto = TtsConfigDto {
+            backend: "dto-test-custom".to_string(),
+            ..TtsConfigDto::default()
+        };
+        let config = TtsConfig::from(dto);
+        assert_eq!(
+            config.backend,
+            TtsBackendType::Custom("dto-test-custom".to_string())
+        );
+        assert_eq!(TtsConfigDto::from(config).backend, "dto-test-custom");
+    }
+
     #[test]
     fn dto_to_config_backend_invalid_string_falls_back_to_none() {
         // spec: 無効な文字列 → TtsBackendType::None にフォールバック
@@ -1,7 +1,7 @@
 //! Viewer management commands
 
 use crate::AppState;
-use crate::database::{self, ContributorStats, ViewerCustomInfo};
+use crate::database::{self, ContributorStats, ProfileSort, ViewerCustomInfo};
 use crate::errors::CommandError;
 use serde::{Deserialize, Serialize};
 use tauri::State;
@@ -44,6 +44,16 @@ impl From<database::ViewerProfile> for GuiViewerProfile {
     }
 }
 
+/// A page of viewer profiles with the total count for the broadcaster
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct GuiViewerProfilePage {
+    pub profiles: Vec<GuiViewerProfile>,
+    /// 配信者の視聴者の総数（JS number の安全整数範囲内）
+    #[ts(type = "number")]
+    pub total: i64,
+}
+
 /// GUI-friendly viewer with custom info
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../src/lib/types/generated/")]
@@ -168,6 +178,36 @@ pub async fn viewer_get_list(
     Ok(viewers.into_iter().map(GuiViewerWithInfo::from).collect())
 }
 
+/// Get a page of viewer profiles for a broadcaster sorted by contribution, message count or last seen
+#[tauri::command]
+pub async fn viewer_list_profiles(
+    state: State<'_, AppState>,
+    broadcaster_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort: Option<ProfileSort>,
+) -> Result<GuiViewerProfilePage, CommandError> {
+    let db_guard = state.database.read().await;
+    let db = db_guard
+        .as_ref()
+        .ok_or_else(|| CommandError::DatabaseError("Database not initialized".to_string()))?;
+
+    let conn = db.connection().await;
+    let (profiles, total) = database::list_viewer_profiles(
+        &conn,
+        &broadcaster_id,
+        offset.unwrap_or(0),
+        limit.unwrap_or(50),
+        sort.unwrap_or_default(),
+    )
+    .map_err(|e| CommandError::DatabaseError(format!("Failed to list viewer profiles: {}", e)))?;
+
+    Ok(GuiViewerProfilePage {
+        profiles: profiles.into_iter().map(GuiViewerProfile::from).collect(),
+        total,
+    })
+}
+
 /// Search viewers
 #[tauri::command]
 pub async fn viewer_search(
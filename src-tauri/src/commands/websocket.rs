@@ -116,3 +116,55 @@ pub async fn websocket_get_status(
         })
     }
 }
+
+/// Local SSE feed for browser sources (spec: 03_websocket.md ローカル SSE フィード)
+///
+/// 監視タスクの emit コールバックから `app.state()` で参照し、GUI に送ったメッセージを流す。
+#[cfg(feature = "local_server")]
+#[derive(Default)]
+pub struct LocalFeedState(pub std::sync::Mutex<Option<crate::core::api::LocalFeedServer>>);
+
+#[cfg(feature = "local_server")]
+impl LocalFeedState {
+    /// 起動中ならメッセージを流す（`filtered_out` なら `/events/raw` のみ）
+    pub fn publish<T: Serialize>(&self, message: &T, filtered_out: bool) {
+        let Ok(server) = self.0.lock() else {
+            return;
+        };
+        let Some(server) = server.as_ref() else {
+            return;
+        };
+        match serde_json::to_string(message) {
+            Ok(json) => server.publish(json, filtered_out),
+            Err(e) => tracing::warn!("Failed to serialize local feed message: {}", e),
+        }
+    }
+}
+
+/// Start the local feed on app launch when `[local_server] enabled = true`
+///
+/// This function is called from the setup hook. Port changes take effect on the next launch.
+#[cfg(feature = "local_server")]
+pub async fn start_local_feed_auto(app: AppHandle) {
+    use tauri::Manager;
+
+    let config = crate::commands::config::load_config_from_file().local_server;
+    if !config.enabled {
+        return;
+    }
+    match crate::core::api::LocalFeedServer::start(config.port).await {
+        Ok(server) => {
+            if let Ok(mut state) = app.state::<LocalFeedState>().0.lock() {
+                *state = Some(server);
+            }
+        }
+        Err(e) => {
+            // Log error but don't fail app startup
+            tracing::error!(
+                "Failed to start local feed on port {}: {}. App will continue without it.",
+                config.port,
+                e
+            );
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! 配信接続の管理
 
+use crate::core::connection_health::{ConnectionHealthHandle, ConnectionHealthInfo};
 use crate::core::models::{ChatMode, Platform};
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
@@ -29,6 +30,8 @@ pub struct StreamConnection {
     pub task_handle: Option<JoinHandle<()>>,
     /// チャットモード変更要求を監視タスクに伝達する watch チャネル
     pub chat_mode_tx: watch::Sender<ChatMode>,
+    /// 監視タスクが更新する接続の健全性
+    pub health: ConnectionHealthHandle,
 }
 
 /// フロントエンドに公開する接続情報（シリアライズ可能）
@@ -43,6 +46,7 @@ pub struct ConnectionInfo {
     pub broadcaster_channel_id: String,
     pub is_monitoring: bool,
     pub is_cancelling: bool,
+    pub health: ConnectionHealthInfo,
 }
 
 impl From<&StreamConnection> for ConnectionInfo {
@@ -57,6 +61,7 @@ impl From<&StreamConnection> for ConnectionInfo {
             is_monitoring: conn.is_monitoring,
             // キャンセル済みかどうかをCancellationTokenから取得
             is_cancelling: conn.cancellation_token.is_cancelled(),
+            health: conn.health.info(),
         }
     }
 }
@@ -64,6 +69,7 @@ impl From<&StreamConnection> for ConnectionInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::connection_health::HealthLevel;
 
     /// テスト用のStreamConnectionを作成するヘルパー
     fn make_connection(id: u64) -> StreamConnection {
@@ -80,6 +86,7 @@ mod tests {
             cancellation_token: CancellationToken::new(),
             task_handle: None,
             chat_mode_tx,
+            health: ConnectionHealthHandle::default(),
         }
     }
 
@@ -97,6 +104,18 @@ mod tests {
         assert_eq!(info.broadcaster_channel_id, "UCtest123");
         assert!(!info.is_monitoring);
         assert!(!info.is_cancelling);
+        assert_eq!(info.health.level, HealthLevel::Good);
+    }
+
+    #[test]
+    fn connection_info_reflects_health_handle() {
+        // 監視タスクが記録した健全性が接続情報に反映される
+        let conn = make_connection(1);
+        conn.health.record_failure(true);
+
+        let info = ConnectionInfo::from(&conn);
+        assert_eq!(info.health.level, HealthLevel::Degraded);
+        assert!(info.health.rate_limited);
     }
 
     #[test]
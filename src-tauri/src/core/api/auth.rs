@@ -1,6 +1,8 @@
 //! YouTube authentication utilities
 
-use crate::core::models::YouTubeCookies;
+pub mod oauth;
+
+use crate::core::models::{ApiCredentials, YouTubeCookies};
 use sha1::{Digest, Sha1};
 
 /// Generate SAPISIDHASH for YouTube API authentication
@@ -36,6 +38,16 @@ pub fn build_auth_headers(cookies: &YouTubeCookies) -> Vec<(String, String)> {
     ]
 }
 
+/// 認証情報の種類に応じた API リクエストのヘッダー（OAuth は Bearer トークンのみ）
+pub fn build_credential_headers(credentials: &ApiCredentials) -> Vec<(String, String)> {
+    match credentials {
+        ApiCredentials::Cookies(cookies) => build_auth_headers(cookies),
+        ApiCredentials::OAuth(tokens) => {
+            vec![("Authorization".to_string(), tokens.authorization_header())]
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,4 +63,18 @@ mod tests {
         // Hash should be hex
         assert_eq!(parts[1].len(), 40);
     }
+
+    #[test]
+    fn test_build_credential_headers_for_oauth() {
+        let headers =
+            build_credential_headers(&ApiCredentials::OAuth(crate::core::models::OAuthTokens {
+                access_token: "token".to_string(),
+                refresh_token: None,
+                expires_at: 0,
+            }));
+        assert_eq!(
+            headers,
+            vec![("Authorization".to_string(), "Bearer token".to_string())]
+        );
+    }
 }
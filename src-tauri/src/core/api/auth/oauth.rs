@@ -0,0 +1,301 @@
+//! OAuth 2.0 デバイス認可フロー（spec: 01_auth.md OAuth ログイン）
+//!
+//! WebView の cookie を使えない環境（cookie を消した・ヘッドレス）向けに、
+//! 別のブラウザでコードを入力してログインし、Bearer トークンで InnerTube API を呼ぶ。
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::core::models::OAuthTokens;
+
+/// デバイスコード発行のエンドポイント
+pub const DEFAULT_DEVICE_CODE_URL: &str = "https://oauth2.googleapis.com/device/code";
+/// トークン発行・更新のエンドポイント
+pub const DEFAULT_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+/// 要求するスコープ
+pub const YOUTUBE_SCOPE: &str = "https://www.googleapis.com/auth/youtube";
+/// アクセストークンを期限切れとみなす残り時間（秒）
+pub const TOKEN_EXPIRY_MARGIN_SECS: i64 = 60;
+
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// `interval` が返されなかったときのポーリング間隔（秒）
+const DEFAULT_POLL_INTERVAL_SECS: u32 = 5;
+
+/// OAuth クライアントの設定
+#[derive(Debug, Clone)]
+pub struct OAuthClientConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub device_code_url: String,
+    pub token_url: String,
+}
+
+impl OAuthClientConfig {
+    /// 環境変数から読み込む（クライアント ID が設定されていなければ None）
+    ///
+    /// `LISCOV_OAUTH_CLIENT_ID` / `LISCOV_OAUTH_CLIENT_SECRET` がなければビルド時の同名の値を使う。
+    /// エンドポイントは E2E テスト用に `LISCOV_OAUTH_DEVICE_CODE_URL` / `LISCOV_OAUTH_TOKEN_URL` で差し替えられる。
+    pub fn from_env() -> Option<Self> {
+        let client_id = std::env::var("LISCOV_OAUTH_CLIENT_ID")
+            .ok()
+            .or_else(|| option_env!("LISCOV_OAUTH_CLIENT_ID").map(str::to_string))
+            .filter(|id| !id.is_empty())?;
+        let client_secret = std::env::var("LISCOV_OAUTH_CLIENT_SECRET")
+            .ok()
+            .or_else(|| option_env!("LISCOV_OAUTH_CLIENT_SECRET").map(str::to_string))
+            .unwrap_or_default();
+        Some(Self {
+            client_id,
+            client_secret,
+            device_code_url: std::env::var("LISCOV_OAUTH_DEVICE_CODE_URL")
+                .unwrap_or_else(|_| DEFAULT_DEVICE_CODE_URL.to_string()),
+            token_url: std::env::var("LISCOV_OAUTH_TOKEN_URL")
+                .unwrap_or_else(|_| DEFAULT_TOKEN_URL.to_string()),
+        })
+    }
+}
+
+/// 発行されたデバイスコード
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct DeviceCode {
+    /// トークン取得のポーリングに使うコード（ユーザーには表示しない）
+    pub device_code: String,
+    /// ユーザーが入力するコード
+    pub user_code: String,
+    /// コードを入力するページ
+    pub verification_url: String,
+    /// コードの有効期間（秒）
+    pub expires_in: u32,
+    /// ポーリング間隔（秒）
+    #[serde(default = "default_poll_interval")]
+    pub interval: u32,
+}
+
+fn default_poll_interval() -> u32 {
+    DEFAULT_POLL_INTERVAL_SECS
+}
+
+/// トークン取得のポーリング結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum PollOutcome {
+    /// ユーザーがまだ承認していない
+    Pending,
+    /// ポーリング間隔を延ばす必要がある
+    SlowDown,
+    /// ユーザーが拒否した
+    Denied,
+    /// デバイスコードの有効期限が切れた
+    Expired,
+    Authorized(OAuthTokens),
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+impl ErrorResponse {
+    fn into_error(self) -> anyhow::Error {
+        match self.error_description {
+            Some(description) => anyhow!("OAuth error: {} ({})", self.error, description),
+            None => anyhow!("OAuth error: {}", self.error),
+        }
+    }
+}
+
+/// トークンエンドポイントの応答からトークンを作る
+///
+/// リフレッシュトークンが返されなければ `previous_refresh_token` を引き継ぐ。
+fn tokens_from_response(
+    body: &str,
+    now: i64,
+    previous_refresh_token: Option<&str>,
+) -> Result<OAuthTokens> {
+    let response: TokenResponse = serde_json::from_str(body)?;
+    Ok(OAuthTokens {
+        access_token: response.access_token,
+        refresh_token: response
+            .refresh_token
+            .or_else(|| previous_refresh_token.map(str::to_string)),
+        expires_at: now.saturating_add(response.expires_in),
+    })
+}
+
+/// デバイスコードでのトークン要求に対する応答を解釈する
+pub fn parse_poll_response(success: bool, body: &str, now: i64) -> Result<PollOutcome> {
+    if success {
+        return tokens_from_response(body, now, None).map(PollOutcome::Authorized);
+    }
+    let error: ErrorResponse = serde_json::from_str(body)?;
+    match error.error.as_str() {
+        "authorization_pending" => Ok(PollOutcome::Pending),
+        "slow_down" => Ok(PollOutcome::SlowDown),
+        "access_denied" => Ok(PollOutcome::Denied),
+        "expired_token" => Ok(PollOutcome::Expired),
+        _ => Err(error.into_error()),
+    }
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// デバイス認可フローを開始し、ユーザーに表示するコードを発行する
+pub async fn start_device_flow(
+    http_client: &reqwest::Client,
+    config: &OAuthClientConfig,
+) -> Result<DeviceCode> {
+    let response = http_client
+        .post(&config.device_code_url)
+        .form(&[
+            ("client_id", config.client_id.as_str()),
+            ("scope", YOUTUBE_SCOPE),
+        ])
+        .send()
+        .await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(serde_json::from_str::<ErrorResponse>(&body)
+            .map(ErrorResponse::into_error)
+            .unwrap_or_else(|_| anyhow!("Device code request returned {}", status)));
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// ユーザーが承認したかを確認し、承認済みならトークンを返す
+///
+/// `DeviceCode::interval` 秒以上の間隔で呼ぶ（`SlowDown` が返ったら間隔を延ばす）。
+pub async fn poll_token(
+    http_client: &reqwest::Client,
+    config: &OAuthClientConfig,
+    device_code: &str,
+) -> Result<PollOutcome> {
+    let response = http_client
+        .post(&config.token_url)
+        .form(&[
+            ("client_id", config.client_id.as_str()),
+            ("client_secret", config.client_secret.as_str()),
+            ("device_code", device_code),
+            ("grant_type", DEVICE_CODE_GRANT_TYPE),
+        ])
+        .send()
+        .await?;
+    let success = response.status().is_success();
+    let body = response.text().await?;
+    parse_poll_response(success, &body, now_secs())
+}
+
+/// リフレッシュトークンでアクセストークンを更新する
+pub async fn refresh_tokens(
+    http_client: &reqwest::Client,
+    config: &OAuthClientConfig,
+    tokens: &OAuthTokens,
+) -> Result<OAuthTokens> {
+    let refresh_token = tokens
+        .refresh_token
+        .as_deref()
+        .ok_or_else(|| anyhow!("No refresh token"))?;
+    let response = http_client
+        .post(&config.token_url)
+        .form(&[
+            ("client_id", config.client_id.as_str()),
+            ("client_secret", config.client_secret.as_str()),
+            ("refresh_token", refresh_token),
+            ("grant_type", "refresh_token"),
+        ])
+        .send()
+        .await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(serde_json::from_str::<ErrorResponse>(&body)
+            .map(ErrorResponse::into_error)
+            .unwrap_or_else(|_| anyhow!("Token refresh returned {}", status)));
+    }
+    tokens_from_response(&body, now_secs(), Some(refresh_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_code_parses_google_response() {
+        let google: DeviceCode = serde_json::from_str(
+            r#"{"device_code":"dev","user_code":"ABC-DEF","verification_url":"https://www.google.com/device","expires_in":1800,"interval":5}"#,
+        )
+        .unwrap();
+        assert_eq!(google.user_code, "ABC-DEF");
+        assert_eq!(google.verification_url, "https://www.google.com/device");
+
+        let without_interval: DeviceCode = serde_json::from_str(
+            r#"{"device_code":"dev","user_code":"ABC","verification_url":"https://www.google.com/device","expires_in":600}"#,
+        )
+        .unwrap();
+        assert_eq!(without_interval.interval, DEFAULT_POLL_INTERVAL_SECS);
+    }
+
+    #[test]
+    fn poll_response_maps_pending_errors() {
+        let outcome = |error: &str| {
+            parse_poll_response(false, &format!(r#"{{"error":"{}"}}"#, error), 0).unwrap()
+        };
+        assert_eq!(outcome("authorization_pending"), PollOutcome::Pending);
+        assert_eq!(outcome("slow_down"), PollOutcome::SlowDown);
+        assert_eq!(outcome("access_denied"), PollOutcome::Denied);
+        assert_eq!(outcome("expired_token"), PollOutcome::Expired);
+
+        let err = parse_poll_response(
+            false,
+            r#"{"error":"invalid_client","error_description":"The OAuth client was not found."}"#,
+            0,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid_client"));
+    }
+
+    #[test]
+    fn poll_response_returns_tokens_with_expiry() {
+        let outcome = parse_poll_response(
+            true,
+            r#"{"access_token":"at","expires_in":3599,"refresh_token":"rt","token_type":"Bearer"}"#,
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            PollOutcome::Authorized(OAuthTokens {
+                access_token: "at".to_string(),
+                refresh_token: Some("rt".to_string()),
+                expires_at: 4_599,
+            })
+        );
+    }
+
+    #[test]
+    fn refreshed_tokens_keep_previous_refresh_token() {
+        let tokens = tokens_from_response(
+            r#"{"access_token":"at2","expires_in":3600}"#,
+            100,
+            Some("rt"),
+        )
+        .unwrap();
+        assert_eq!(tokens.refresh_token.as_deref(), Some("rt"));
+        assert_eq!(tokens.authorization_header(), "Bearer at2");
+        assert!(!tokens.is_expired(100, TOKEN_EXPIRY_MARGIN_SECS));
+        assert!(tokens.is_expired(3_650, TOKEN_EXPIRY_MARGIN_SECS));
+    }
+}
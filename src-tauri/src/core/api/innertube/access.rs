@@ -0,0 +1,120 @@
+//! メンバー限定配信のアクセス拒否の検出（spec: 02_chat.md メンバー限定配信のアクセス拒否）
+//!
+//! メンバーでない（または未ログインの）アカウントでメンバー限定配信を開くと、ウォッチページは
+//! チャットの代わりに参加案内を返し、get_live_chat は権限エラーを返す。どちらも「動画が
+//! 見つからない」と区別できるよう、専用のエラー [`MembersOnly`] にする。
+
+use serde_json::Value;
+
+/// メンバー限定配信へのアクセスを拒否されたエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MembersOnly {
+    /// YouTube が返した理由（あれば）
+    pub reason: Option<String>,
+}
+
+impl std::fmt::Display for MembersOnly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "This stream is members-only; sign in with a member account"
+        )?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({})", reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MembersOnly {}
+
+/// メンバー限定配信へのアクセス拒否のエラーか
+pub fn is_members_only(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<MembersOnly>().is_some()
+}
+
+/// get_live_chat のレスポンスが権限エラー（403 / `PERMISSION_DENIED`）ならエラーを返す
+pub fn live_chat_denial(data: &Value) -> Option<MembersOnly> {
+    let error = data.get("error")?;
+    let denied = error.get("code").and_then(|v| v.as_u64()) == Some(403)
+        || error.get("status").and_then(|v| v.as_str()) == Some("PERMISSION_DENIED");
+    denied.then(|| MembersOnly {
+        reason: error
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// ウォッチページの `ytInitialPlayerResponse` がメンバー限定の参加案内ならエラーを返す
+///
+/// 再生できない（`playabilityStatus.status` が `OK` 以外）うえで、メンバーシップの参加案内
+/// （`playerLegacyDesktopYpcOfferRenderer`）があるか、理由にメンバー限定と書かれている場合。
+pub fn watch_page_denial(player_response: &Value) -> Option<MembersOnly> {
+    let status = player_response.get("playabilityStatus")?;
+    if status.get("status").and_then(|v| v.as_str()) == Some("OK") {
+        return None;
+    }
+    let reason = status.get("reason").and_then(|v| v.as_str());
+    let has_offer = status
+        .pointer("/errorScreen/playerLegacyDesktopYpcOfferRenderer")
+        .is_some();
+    let mentions_members = reason
+        .is_some_and(|r| r.to_lowercase().contains("members-only") || r.contains("メンバー限定"));
+    (has_offer || mentions_members).then(|| MembersOnly {
+        reason: reason.map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_chat_permission_denied_is_members_only() {
+        let data: Value =
+            serde_json::from_str(include_str!("fixtures/get_live_chat_members_only.json")).unwrap();
+        let denial = live_chat_denial(&data).unwrap();
+        assert_eq!(
+            denial.reason.as_deref(),
+            Some("The caller does not have permission")
+        );
+        assert!(is_members_only(&anyhow::Error::new(denial)));
+    }
+
+    #[test]
+    fn other_live_chat_errors_are_not_members_only() {
+        assert!(live_chat_denial(&serde_json::json!({ "error": { "code": 503 } })).is_none());
+        assert!(live_chat_denial(&serde_json::json!({ "continuationContents": {} })).is_none());
+        assert!(!is_members_only(&anyhow::anyhow!("Failed to connect")));
+    }
+
+    #[test]
+    fn watch_page_members_only_offer_is_detected() {
+        let player: Value =
+            serde_json::from_str(include_str!("fixtures/player_response_members_only.json"))
+                .unwrap();
+        let denial = watch_page_denial(&player).unwrap();
+        assert!(denial.reason.unwrap().contains("members-only"));
+
+        // 参加案内がなくても理由にメンバー限定とあれば検出する
+        let japanese = serde_json::json!({
+            "playabilityStatus": {
+                "status": "LOGIN_REQUIRED",
+                "reason": "このメンバー限定コンテンツを視聴するには、ログインしてください"
+            }
+        });
+        assert!(watch_page_denial(&japanese).is_some());
+    }
+
+    #[test]
+    fn playable_or_other_unplayable_pages_are_not_members_only() {
+        let ok = serde_json::json!({ "playabilityStatus": { "status": "OK" } });
+        assert!(watch_page_denial(&ok).is_none());
+        let removed = serde_json::json!({
+            "playabilityStatus": { "status": "ERROR", "reason": "Video unavailable" }
+        });
+        assert!(watch_page_denial(&removed).is_none());
+        assert!(watch_page_denial(&serde_json::json!({})).is_none());
+    }
+}
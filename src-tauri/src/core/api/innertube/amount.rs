@@ -0,0 +1,173 @@
+//! SuperChat / SuperSticker の金額テキスト（`purchaseAmountText`）のパース
+//!
+//! "¥1,000" / "$5.00" / "5,00 €" / "R$ 1.234,56" / "CHF 10.00" のように
+//! 通貨記号・ISO コードの位置や桁区切りが通貨ごとに異なるため、ここで一元的に解釈する。
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// パース済みの金額
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ParsedAmount {
+    /// ISO 4217 通貨コード（例: "JPY", "USD"）
+    pub currency: String,
+    pub amount: f64,
+    /// 元の金額テキスト
+    pub raw: String,
+}
+
+/// 通貨記号と ISO コードの対応
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("CA$", "CAD"),
+    ("NT$", "TWD"),
+    ("HK$", "HKD"),
+    ("MX$", "MXN"),
+    ("NZ$", "NZD"),
+    ("US$", "USD"),
+    ("R$", "BRL"),
+    ("A$", "AUD"),
+    ("$", "USD"),
+    ("¥", "JPY"),
+    ("￥", "JPY"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("₩", "KRW"),
+    ("₹", "INR"),
+    ("₱", "PHP"),
+    ("₫", "VND"),
+    ("₪", "ILS"),
+];
+
+/// 通貨記号または ISO コードを ISO コードに変換する
+fn resolve_currency(token: &str) -> Option<String> {
+    if let Some((_, code)) = CURRENCY_SYMBOLS.iter().find(|(symbol, _)| *symbol == token) {
+        return Some(code.to_string());
+    }
+    if token.len() == 3 && token.chars().all(|c| c.is_ascii_uppercase()) {
+        return Some(token.to_string());
+    }
+    None
+}
+
+/// 桁区切りを取り除き、小数点を `.` に統一した数値文字列に変換する
+///
+/// `,` と `.` が両方ある場合は後に現れる方を小数点とみなす。一方のみの場合は、
+/// 1 回だけ現れて直後が 3 桁なら桁区切り、それ以外の 1 回は小数点、複数回は桁区切りとみなす。
+fn normalize_number(number: &str) -> Option<String> {
+    let digits: String = number
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{202F}')
+        .collect();
+    if digits.is_empty()
+        || !digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+
+    let normalized = match (digits.rfind(','), digits.rfind('.')) {
+        (Some(comma), Some(dot)) if comma > dot => digits.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => digits.replace(',', ""),
+        (Some(_), None) => normalize_single_separator(&digits, ','),
+        (None, Some(_)) => normalize_single_separator(&digits, '.'),
+        (None, None) => digits,
+    };
+    Some(normalized)
+}
+
+fn normalize_single_separator(digits: &str, separator: char) -> String {
+    let parts: Vec<&str> = digits.split(separator).collect();
+    if parts.len() == 2 && parts[1].len() != 3 {
+        format!("{}.{}", parts[0], parts[1])
+    } else {
+        parts.concat()
+    }
+}
+
+/// 金額テキストを通貨と数値にパースする
+///
+/// 通貨記号・ISO コードは数値の前後どちらにあってもよい。
+/// 通貨を判別できない場合や数値として解釈できない場合は None を返す（0 にはしない）。
+pub fn parse_purchase_amount(text: &str) -> Option<ParsedAmount> {
+    let trimmed = text.trim();
+    let start = trimmed.find(|c: char| c.is_ascii_digit())?;
+    let end = trimmed.rfind(|c: char| c.is_ascii_digit())? + 1;
+
+    let prefix = trimmed[..start].trim();
+    let suffix = trimmed[end..].trim();
+    let currency = match (prefix.is_empty(), suffix.is_empty()) {
+        (false, true) => resolve_currency(prefix)?,
+        (true, false) => resolve_currency(suffix)?,
+        _ => return None,
+    };
+
+    let amount = normalize_number(&trimmed[start..end])?
+        .parse::<f64>()
+        .ok()?;
+
+    Some(ParsedAmount {
+        currency,
+        amount,
+        raw: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(text: &str) -> (String, f64) {
+        let p = parse_purchase_amount(text).expect("パースできること");
+        (p.currency, p.amount)
+    }
+
+    #[test]
+    fn parse_symbol_prefix_amounts() {
+        assert_eq!(parsed("¥1,000"), ("JPY".to_string(), 1000.0));
+        assert_eq!(parsed("￥500"), ("JPY".to_string(), 500.0));
+        assert_eq!(parsed("$5.00"), ("USD".to_string(), 5.0));
+        assert_eq!(parsed("£2.50"), ("GBP".to_string(), 2.5));
+        assert_eq!(parsed("₩10,000"), ("KRW".to_string(), 10000.0));
+        assert_eq!(parsed("€1.234,56"), ("EUR".to_string(), 1234.56));
+    }
+
+    #[test]
+    fn parse_multi_character_symbols() {
+        // "R$" を "$"（USD）と誤認しないこと
+        assert_eq!(parsed("R$ 1.234,56"), ("BRL".to_string(), 1234.56));
+        assert_eq!(parsed("CA$10.00"), ("CAD".to_string(), 10.0));
+        assert_eq!(parsed("NT$150"), ("TWD".to_string(), 150.0));
+    }
+
+    #[test]
+    fn parse_symbol_suffix_amounts() {
+        assert_eq!(parsed("5,00 €"), ("EUR".to_string(), 5.0));
+        assert_eq!(parsed("1 234,56 €"), ("EUR".to_string(), 1234.56));
+    }
+
+    #[test]
+    fn parse_iso_code_amounts() {
+        assert_eq!(parsed("CHF 10.00"), ("CHF".to_string(), 10.0));
+        assert_eq!(parsed("100,00 PLN"), ("PLN".to_string(), 100.0));
+    }
+
+    #[test]
+    fn parse_keeps_raw_text() {
+        let p = parse_purchase_amount("¥1,000").unwrap();
+        assert_eq!(p.raw, "¥1,000");
+    }
+
+    #[test]
+    fn parse_unknown_or_malformed_returns_none() {
+        assert_eq!(parse_purchase_amount(""), None);
+        assert_eq!(parse_purchase_amount("free"), None);
+        assert_eq!(parse_purchase_amount("$"), None);
+        // 通貨が判別できない
+        assert_eq!(parse_purchase_amount("1000"), None);
+        assert_eq!(parse_purchase_amount("?? 1000"), None);
+        // 数値部分に不正な文字を含む
+        assert_eq!(parse_purchase_amount("$10a00"), None);
+    }
+}
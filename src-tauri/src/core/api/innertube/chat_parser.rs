@@ -1,5 +1,7 @@
 //! チャットメッセージのパース・変換ロジック
 
+use super::timestamp::usec_to_rfc3339;
+use crate::core::emoji::count_emojis;
 use crate::core::models::*;
 use serde_json::Value;
 
@@ -55,6 +57,51 @@ fn parse_supersticker_colors(renderer: &Value) -> Option<SuperChatColors> {
     })
 }
 
+/// カスタム絵文字の画像を選ぶときの幅（px）
+const EMOJI_IMAGE_SIZE: u64 = 48;
+
+/// SuperSticker の画像を選ぶときの幅（px）。表示は 40px で、高解像度ディスプレイ向けに 2 倍を求める
+const STICKER_IMAGE_SIZE: u64 = 80;
+
+/// `thumbnails` 配列から幅 `size` 以上で最も小さい画像の URL を返す。
+/// 該当がなければ最も大きい画像を返す。幅のない要素は 0 として扱い、
+/// `//` で始まる URL には `https:` を補う。
+pub fn best_thumbnail_url(thumbnails: &Value, size: u64) -> Option<String> {
+    let candidates: Vec<(u64, &str)> = thumbnails
+        .as_array()?
+        .iter()
+        .filter_map(|thumbnail| {
+            let url = thumbnail
+                .get("url")?
+                .as_str()
+                .filter(|url| !url.is_empty())?;
+            let width = thumbnail.get("width").and_then(|v| v.as_u64()).unwrap_or(0);
+            Some((width, url))
+        })
+        .collect();
+    let (_, url) = candidates
+        .iter()
+        .filter(|(width, _)| *width >= size)
+        .min_by_key(|(width, _)| *width)
+        .or_else(|| candidates.iter().max_by_key(|(width, _)| *width))?;
+    Some(match url.strip_prefix("//") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    })
+}
+
+/// SuperSticker の画像と代替テキストをパースする
+fn parse_sticker_image(renderer: &Value) -> Option<StickerImage> {
+    let sticker = renderer.get("sticker")?;
+    let url = best_thumbnail_url(sticker.get("thumbnails")?, STICKER_IMAGE_SIZE)?;
+    let alt_text = sticker
+        .pointer("/accessibility/accessibilityData/label")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    Some(StickerImage { url, alt_text })
+}
+
 /// バッジ tooltip から milestone の月数を抽出する（例: "Member (6 months)"）。
 /// 新規メンバーバッジは None を返す。
 pub fn extract_milestone_months_from_badge(tooltip: &str) -> Option<u32> {
@@ -88,15 +135,178 @@ pub fn extract_milestone_months_from_badge(tooltip: &str) -> Option<u32> {
     None
 }
 
+/// メンバーバッジの tooltip か（"Member" / "New member" / "Member (6 months)" / "メンバー（6か月）" / "新規メンバー"）
+fn is_member_tooltip(tooltip: &str) -> bool {
+    let tooltip = tooltip.trim();
+    let lower = tooltip.to_lowercase();
+    lower.starts_with("member") || lower.starts_with("new member") || tooltip.contains("メンバー")
+}
+
+/// 投稿者バッジ（`authorBadges` の各要素）の中からメンバーバッジの renderer を探す
+///
+/// モデレーター・認証済み・オーナーのバッジはアイコン（`icon.iconType`）で表され、
+/// メンバーバッジはチャンネル独自の画像（`customThumbnail`）を持つ。
+fn find_member_badge(badges: &[Value]) -> Option<&Value> {
+    badges
+        .iter()
+        .filter_map(|b| b.get("liveChatAuthorBadgeRenderer"))
+        .find(|renderer| {
+            if renderer.pointer("/icon/iconType").is_some() {
+                return false;
+            }
+            renderer.get("customThumbnail").is_some()
+                || renderer
+                    .get("tooltip")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(is_member_tooltip)
+        })
+}
+
+/// 投稿者バッジがメンバーであることを示すか
+pub fn author_badges_indicate_member(badges: &[Value]) -> bool {
+    find_member_badge(badges).is_some()
+}
+
+/// メンバーバッジの tooltip（例: "Member (6 months)"）から継続月数を返す
+pub fn member_months_from_badge(badges: &[Value]) -> Option<u32> {
+    find_member_badge(badges)?
+        .get("tooltip")
+        .and_then(|v| v.as_str())
+        .and_then(extract_milestone_months_from_badge)
+}
+
+/// オーナー・モデレーター・認証済みのバッジ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoleBadge {
+    Owner,
+    Moderator,
+    Verified,
+}
+
+/// バッジの renderer がどのロールを表すか（メンバーバッジ・未知のバッジは None）
+///
+/// 通常は `icon.iconType` で判定し、アイコン種別がないバッジは tooltip で判定する。
+fn role_badge(renderer: &Value) -> Option<RoleBadge> {
+    if let Some(icon_type) = renderer.pointer("/icon/iconType").and_then(|v| v.as_str()) {
+        return match icon_type {
+            "OWNER" => Some(RoleBadge::Owner),
+            "MODERATOR" => Some(RoleBadge::Moderator),
+            "VERIFIED" | "CHECK_CIRCLE_THICK" => Some(RoleBadge::Verified),
+            _ => None,
+        };
+    }
+    let tooltip = renderer.get("tooltip")?.as_str()?.trim().to_lowercase();
+    match tooltip.as_str() {
+        "owner" | "所有者" | "オーナー" => Some(RoleBadge::Owner),
+        "moderator" | "モデレーター" => Some(RoleBadge::Moderator),
+        "verified" | "確認済み" | "認証済み" => Some(RoleBadge::Verified),
+        _ => None,
+    }
+}
+
+/// 投稿者バッジからロールを判定する（`is_member` は `author_badges_indicate_member` と同じ）
+pub fn author_role_from_badges(badges: &[Value]) -> AuthorRole {
+    let mut role = AuthorRole {
+        is_member: author_badges_indicate_member(badges),
+        ..Default::default()
+    };
+    for renderer in badges
+        .iter()
+        .filter_map(|b| b.get("liveChatAuthorBadgeRenderer"))
+    {
+        match role_badge(renderer) {
+            Some(RoleBadge::Owner) => role.is_owner = true,
+            Some(RoleBadge::Moderator) => role.is_moderator = true,
+            Some(RoleBadge::Verified) => role.is_verified = true,
+            None => {}
+        }
+    }
+    role
+}
+
+/// renderer の `authorBadges`（なければ空）
+fn author_badges(renderer: &Value) -> &[Value] {
+    renderer
+        .get("authorBadges")
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// メンバーシップメッセージのヘッダーから判定した種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MembershipHeader {
+    /// 新規メンバー（"Welcome to ..." / "新規メンバー"）
+    NewMember,
+    /// 継続メンバーのマイルストーン（月数）
+    Milestone(u32),
+}
+
+/// simpleText または runs 形式のテキストを結合して返す
+fn text_of(value: Option<&Value>) -> Option<String> {
+    let value = value?;
+    if let Some(text) = value.get("simpleText").and_then(|v| v.as_str()) {
+        return Some(text.to_string());
+    }
+    value.get("runs").and_then(|v| v.as_array()).map(|runs| {
+        runs.iter()
+            .filter_map(|r| r.get("text").and_then(|t| t.as_str()))
+            .collect::<String>()
+    })
+}
+
+/// メンバーシップメッセージの `headerPrimaryText` / `headerSubtext` から種別を判定する。
+/// サポートフォーマット:
+/// - 日本語: "メンバー歴 6 か月" / "新規メンバー"
+/// - 英語: "Member for 6 months" / "Welcome to [channel]!"
+///
+/// どちらとも判定できないレイアウトは None を返す。
+pub fn parse_membership_header(renderer: &Value) -> Option<MembershipHeader> {
+    use regex::Regex;
+
+    let primary = text_of(renderer.get("headerPrimaryText")).unwrap_or_default();
+    let subtext = text_of(renderer.get("headerSubtext")).unwrap_or_default();
+
+    let months_regex = Regex::new(r"(?i)(\d+)\s*(?:months?|か月|ヶ月|カ月)").ok()?;
+    if let Some(months) = months_regex
+        .captures(&primary)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse::<u32>().ok())
+    {
+        return Some(MembershipHeader::Milestone(months));
+    }
+
+    let text = format!("{} {}", primary, subtext).to_lowercase();
+    let new_member_markers = ["new member", "welcome to", "新規メンバー", "へようこそ"];
+    if new_member_markers
+        .iter()
+        .any(|marker| text.contains(marker))
+    {
+        return Some(MembershipHeader::NewMember);
+    }
+
+    None
+}
+
+/// メンバーシップメッセージのヘッダーから milestone の月数を抽出する。
+/// 新規メンバーや判定できないレイアウトは None を返す（区別が必要な場合は
+/// `parse_membership_header` を使う）。
+pub fn membership_milestone_months(renderer: &Value) -> Option<u32> {
+    match parse_membership_header(renderer)? {
+        MembershipHeader::Milestone(months) => Some(months),
+        MembershipHeader::NewMember => None,
+    }
+}
+
 /// メンバーシップギフトメッセージからギフト数を抽出する。
 /// サポートフォーマット:
-/// - 日本語: "5人にメンバーシップをギフトしました"
+/// - 日本語: "5人にメンバーシップをギフトしました" / "5 件のメンバーシップ ギフトを贈呈しました"
 /// - 英語: "Sent 5 [channel] gift memberships"
 pub fn extract_gift_count(content: &str) -> Option<u32> {
     use regex::Regex;
 
-    // 日本語フォーマット: "5人にメンバーシップをギフト"
-    let ja_regex = Regex::new(r"(\d+)\s*人").ok()?;
+    // 日本語フォーマット: "5人にメンバーシップをギフト" / "5 件のメンバーシップ ギフト"
+    let ja_regex = Regex::new(r"(\d+)\s*[人件]").ok()?;
     if let Some(caps) = ja_regex.captures(content) {
         if let Some(m) = caps.get(1) {
             if let Ok(count) = m.as_str().parse::<u32>() {
@@ -128,7 +338,41 @@ pub fn extract_gift_count(content: &str) -> Option<u32> {
     None
 }
 
+/// メンバーシップギフトアナウンスの header からギフト数を抽出する。
+/// 未知のレイアウトでは None を返す。
+pub fn membership_gift_count(renderer: &Value) -> Option<u32> {
+    let header = renderer.pointer("/header/liveChatSponsorshipsHeaderRenderer")?;
+    extract_gift_count(&text_of(header.get("primaryText"))?)
+}
+
+/// テキストを runs に追加する（直前がテキストならつなげる）
+fn push_text_run(runs: &mut Vec<MessageRun>, text: &str) {
+    if let Some(MessageRun::Text { content }) = runs.last_mut() {
+        content.push_str(text);
+    } else {
+        runs.push(MessageRun::Text {
+            content: text.to_string(),
+        });
+    }
+}
+
+/// 標準の Unicode 絵文字なら、その文字列を返す
+///
+/// YouTube は Unicode 絵文字も `emoji` run で送るが、`isCustomEmoji` がなく
+/// `emojiId` が絵文字そのものになっている。
+fn unicode_emoji_text(emoji: &Value) -> Option<&str> {
+    if emoji.get("isCustomEmoji").and_then(|v| v.as_bool()) == Some(true) {
+        return None;
+    }
+    emoji
+        .get("emojiId")
+        .and_then(|v| v.as_str())
+        .filter(|id| count_emojis(id) > 0)
+}
+
 /// メッセージの runs（テキスト・絵文字）をパースして (content文字列, runs配列) を返す
+///
+/// 画像の run になるのはカスタム絵文字だけで、Unicode 絵文字はテキストとして扱う。
 pub fn parse_message_content(message: &Value) -> (String, Vec<MessageRun>) {
     let mut content = String::new();
     let mut runs = Vec::new();
@@ -137,9 +381,10 @@ pub fn parse_message_content(message: &Value) -> (String, Vec<MessageRun>) {
         for run in runs_array {
             if let Some(text) = run.get("text").and_then(|v| v.as_str()) {
                 content.push_str(text);
-                runs.push(MessageRun::Text {
-                    content: text.to_string(),
-                });
+                push_text_run(&mut runs, text);
+            } else if let Some(text) = run.get("emoji").and_then(unicode_emoji_text) {
+                content.push_str(text);
+                push_text_run(&mut runs, text);
             } else if let Some(emoji) = run.get("emoji") {
                 let emoji_id = emoji
                     .get("emojiId")
@@ -147,21 +392,31 @@ pub fn parse_message_content(message: &Value) -> (String, Vec<MessageRun>) {
                     .unwrap_or("")
                     .to_string();
                 let image_url = emoji
-                    .pointer("/image/thumbnails/0/url")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
+                    .pointer("/image/thumbnails")
+                    .and_then(|thumbnails| best_thumbnail_url(thumbnails, EMOJI_IMAGE_SIZE))
+                    .unwrap_or_default();
                 let alt_text = emoji
                     .pointer("/image/accessibility/accessibilityData/label")
                     .and_then(|v| v.as_str())
                     .unwrap_or("")
                     .to_string();
+                let shortcuts = emoji
+                    .get("shortcuts")
+                    .and_then(|v| v.as_array())
+                    .map(|shortcuts| {
+                        shortcuts
+                            .iter()
+                            .filter_map(|s| s.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
 
                 content.push_str(&alt_text);
                 runs.push(MessageRun::Emoji {
                     emoji_id,
                     image_url,
                     alt_text,
+                    shortcuts,
                 });
             }
         }
@@ -169,16 +424,9 @@ pub fn parse_message_content(message: &Value) -> (String, Vec<MessageRun>) {
     (content, runs)
 }
 
-/// タイムスタンプ（マイクロ秒文字列）を RFC3339 文字列に変換する
+/// タイムスタンプ（マイクロ秒文字列）を RFC3339 文字列に変換する（解釈できなければ空文字列）
 pub fn format_timestamp(timestamp_usec: &str) -> String {
-    if let Ok(usec) = timestamp_usec.parse::<i64>() {
-        let secs = usec / 1_000_000;
-        let datetime = chrono::DateTime::from_timestamp(secs, 0).unwrap_or_default();
-        // フロントエンドがローカルタイムゾーンに変換できるよう RFC3339 フォーマットで返す
-        datetime.to_rfc3339()
-    } else {
-        String::new()
-    }
+    usec_to_rfc3339(timestamp_usec).unwrap_or_default()
 }
 
 /// テキストチャットメッセージをパースする
@@ -205,17 +453,14 @@ fn parse_text_message(renderer: &Value) -> Option<ChatMessage> {
 
     let (content, runs) = parse_message_content(renderer.get("message")?);
 
-    // メンバーバッジ（customThumbnail）の有無でメンバー判定
-    let is_member = renderer
-        .get("authorBadges")
-        .and_then(|v| v.as_array())
-        .map(|badges| {
-            badges.iter().any(|b| {
-                b.pointer("/liveChatAuthorBadgeRenderer/customThumbnail")
-                    .is_some()
-            })
-        })
-        .unwrap_or(false);
+    let role = author_role_from_badges(author_badges(renderer));
+    // テキストメッセージはオーナー・モデレーター・認証済みのときだけメタデータを持つ
+    let metadata = role.is_privileged().then(|| MessageMetadata {
+        is_moderator: role.is_moderator,
+        is_verified: role.is_verified,
+        is_owner: role.is_owner,
+        ..Default::default()
+    });
 
     Some(ChatMessage {
         id,
@@ -227,8 +472,8 @@ fn parse_text_message(renderer: &Value) -> Option<ChatMessage> {
         channel_id,
         content,
         runs,
-        metadata: None,
-        is_member,
+        metadata,
+        is_member: role.is_member,
         is_first_time_viewer: false,
         in_stream_comment_count: None,
     })
@@ -269,6 +514,7 @@ fn parse_superchat_message(renderer: &Value) -> Option<ChatMessage> {
 
     // YouTube API から SuperChat の色情報をパース
     let superchat_colors = parse_superchat_colors(renderer);
+    let role = author_role_from_badges(author_badges(renderer));
 
     Some(ChatMessage {
         id,
@@ -287,11 +533,13 @@ fn parse_superchat_message(renderer: &Value) -> Option<ChatMessage> {
             badges: vec![],
             badge_info: vec![],
             color: None,
-            is_moderator: false,
-            is_verified: false,
+            is_moderator: role.is_moderator,
+            is_verified: role.is_verified,
+            is_owner: role.is_owner,
             superchat_colors,
+            sticker: None,
         }),
-        is_member: false,
+        is_member: role.is_member,
         is_first_time_viewer: false,
         in_stream_comment_count: None,
     })
@@ -322,6 +570,8 @@ fn parse_supersticker_message(renderer: &Value) -> Option<ChatMessage> {
 
     // YouTube API から SuperSticker の色情報をパース
     let superchat_colors = parse_supersticker_colors(renderer);
+    let sticker = parse_sticker_image(renderer);
+    let role = author_role_from_badges(author_badges(renderer));
 
     Some(ChatMessage {
         id,
@@ -340,11 +590,13 @@ fn parse_supersticker_message(renderer: &Value) -> Option<ChatMessage> {
             badges: vec![],
             badge_info: vec![],
             color: None,
-            is_moderator: false,
-            is_verified: false,
+            is_moderator: role.is_moderator,
+            is_verified: role.is_verified,
+            is_owner: role.is_owner,
             superchat_colors,
+            sticker,
         }),
-        is_member: false,
+        is_member: role.is_member,
         is_first_time_viewer: false,
         in_stream_comment_count: None,
     })
@@ -373,30 +625,13 @@ fn parse_membership_message(renderer: &Value) -> Option<ChatMessage> {
         .map(|s| s.to_string());
 
     // headerSubtext は simpleText または runs フォーマットの場合がある
-    let content = renderer
-        .pointer("/headerSubtext/simpleText")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .or_else(|| {
-            // runs フォーマットの場合は全テキストを結合する
-            renderer
-                .pointer("/headerSubtext/runs")
-                .and_then(|v| v.as_array())
-                .map(|runs| {
-                    runs.iter()
-                        .filter_map(|r| r.get("text").and_then(|t| t.as_str()))
-                        .collect::<String>()
-                })
-        })
-        .unwrap_or_else(|| "New member".to_string());
-
-    // バッジの tooltip から milestone の月数を抽出する（例: "Member (6 months)"）
-    let badge_tooltip = renderer
-        .pointer("/authorBadges/0/liveChatAuthorBadgeRenderer/tooltip")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
+    let content =
+        text_of(renderer.get("headerSubtext")).unwrap_or_else(|| "New member".to_string());
 
-    let milestone_months = extract_milestone_months_from_badge(badge_tooltip);
+    // ヘッダーから milestone の月数を抽出し、取得できなければ
+    // バッジの tooltip（例: "Member (6 months)"）にフォールバックする
+    let milestone_months = membership_milestone_months(renderer)
+        .or_else(|| member_months_from_badge(author_badges(renderer)));
 
     Some(ChatMessage {
         id,
@@ -442,24 +677,13 @@ fn parse_membership_gift_message(renderer: &Value) -> Option<ChatMessage> {
         .map(|s| s.to_string());
 
     // プライマリテキストを抽出（例: "Sent 5 [channel] gift memberships"）
-    let primary_text = header
-        .pointer("/primaryText/runs")
-        .and_then(|v| v.as_array())
-        .map(|runs| {
-            runs.iter()
-                .filter_map(|r| r.get("text").and_then(|t| t.as_str()))
-                .collect::<String>()
-        })
-        .or_else(|| {
-            header
-                .pointer("/primaryText/simpleText")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-        })
-        .unwrap_or_default();
+    let primary_text = text_of(header.get("primaryText")).unwrap_or_default();
 
-    // プライマリテキストからギフト数を抽出する
-    let gift_count = extract_gift_count(&primary_text).unwrap_or(1);
+    // プライマリテキストからギフト数を抽出する（未知のレイアウトは1件として扱う）
+    let gift_count = membership_gift_count(renderer).unwrap_or_else(|| {
+        tracing::warn!("ギフト数を抽出できないレイアウト: {:?}", primary_text);
+        1
+    });
 
     Some(ChatMessage {
         id,
@@ -480,10 +704,34 @@ fn parse_membership_gift_message(renderer: &Value) -> Option<ChatMessage> {
 
 /// 1件のチャットアクションをパースして `ChatMessage` に変換する
 pub fn parse_chat_action(action: &Value) -> Option<ChatMessage> {
-    let item = action
+    chat_item(action).and_then(parse_chat_item)
+}
+
+/// チャットアクションから追加されたアイテム（`addChatItemAction.item`）を取り出す
+///
+/// アーカイブの `replayChatItemAction` に包まれたものも取り出す。削除などアイテムを追加しない
+/// アクションは `None`。
+pub fn chat_item(action: &Value) -> Option<&Value> {
+    action
         .pointer("/replayChatItemAction/actions/0/addChatItemAction/item")
-        .or_else(|| action.pointer("/addChatItemAction/item"))?;
+        .or_else(|| action.pointer("/addChatItemAction/item"))
+}
 
+/// レスポンスのアクションを順に取り出す
+fn response_actions(data: &Value) -> impl Iterator<Item = &Value> {
+    data.pointer("/continuationContents/liveChatContinuation/actions")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+}
+
+/// レスポンスの全アクションから、追加されたアイテムを順に取り出す
+pub fn chat_items(data: &Value) -> impl Iterator<Item = &Value> {
+    response_actions(data).filter_map(chat_item)
+}
+
+/// アイテムをレンダラーの種類に応じてパースする（メッセージとして扱わないレンダラーは `None`）
+pub fn parse_chat_item(item: &Value) -> Option<ChatMessage> {
     if let Some(renderer) = item.get("liveChatTextMessageRenderer") {
         return parse_text_message(renderer);
     }
@@ -502,28 +750,189 @@ pub fn parse_chat_action(action: &Value) -> Option<ChatMessage> {
     None
 }
 
+/// 配信終了・チャット終了を知らせるエンゲージメントメッセージの文言（小文字で比較）
+const STREAM_END_NOTICES: &[&str] = &[
+    "配信は終了しました",
+    "ライブ配信が終了しました",
+    "チャットは利用できません",
+    "stream has ended",
+    "live chat is no longer available",
+];
+
+/// エンゲージメントメッセージの本文が配信終了の通知か
+pub fn is_stream_end_notice(text: &str) -> bool {
+    let text = text.to_lowercase();
+    STREAM_END_NOTICES
+        .iter()
+        .any(|notice| text.contains(notice))
+}
+
+/// レスポンスに配信終了を知らせるエンゲージメントメッセージ
+/// （`liveChatViewerEngagementMessageRenderer`）が含まれるか
+pub fn has_stream_end_notice(data: &Value) -> bool {
+    chat_items(data)
+        .filter_map(|item| item.get("liveChatViewerEngagementMessageRenderer"))
+        .filter_map(|renderer| text_of(renderer.get("message")))
+        .any(|text| is_stream_end_notice(&text))
+}
+
+/// レスポンスの削除アクション（`markChatItemAsDeletedAction` / `removeChatItemAction`）が
+/// 対象とするメッセージ ID を順に返す
+pub fn deleted_item_ids(data: &Value) -> Vec<String> {
+    response_actions(data)
+        .filter_map(|action| {
+            action
+                .pointer("/markChatItemAsDeletedAction/targetItemId")
+                .or_else(|| action.pointer("/removeChatItemAction/targetItemId"))
+        })
+        .filter_map(|id| id.as_str())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// InnerTube API レスポンスからチャットアクションをパースして `ChatMessage` 配列を返す
 pub fn parse_chat_actions(data: &Value) -> Vec<ChatMessage> {
-    let mut messages = Vec::new();
+    chat_items(data).filter_map(parse_chat_item).collect()
+}
 
-    let actions = data
-        .pointer("/continuationContents/liveChatContinuation/actions")
-        .and_then(|v| v.as_array());
+/// 処理するアクション（`replayChatItemAction` は中のアクションを調べる）
+const HANDLED_ACTIONS: &[&str] = &[
+    "addChatItemAction",
+    "replayChatItemAction",
+    "markChatItemAsDeletedAction",
+    "removeChatItemAction",
+];
+
+/// 届くことが分かっていて、処理しないアクション
+const IGNORED_ACTIONS: &[&str] = &[
+    "addLiveChatTickerItemAction",
+    "addBannerToLiveChatCommand",
+    "removeBannerForLiveChatCommand",
+    "updateLiveChatPollAction",
+    "showLiveChatActionPanelAction",
+    "closeLiveChatActionPanelAction",
+    "replaceChatItemAction",
+    "markChatItemsByAuthorAsDeletedAction",
+    "liveChatReportModerationStateCommand",
+    "showLiveChatTooltipCommand",
+];
+
+/// 追加されるアイテムのうち、[`parse_chat_item`] がパースするか、届くことが分かっていて
+/// メッセージとして扱わないレンダラー
+const KNOWN_RENDERERS: &[&str] = &[
+    "liveChatTextMessageRenderer",
+    "liveChatPaidMessageRenderer",
+    "liveChatPaidStickerRenderer",
+    "liveChatMembershipItemRenderer",
+    "liveChatSponsorshipsGiftPurchaseAnnouncementRenderer",
+    "liveChatSponsorshipsGiftRedemptionAnnouncementRenderer",
+    "liveChatViewerEngagementMessageRenderer",
+    "liveChatPlaceholderItemRenderer",
+    "liveChatModeChangeMessageRenderer",
+    "liveChatAutoModMessageRenderer",
+];
+
+/// 未対応のアクション、または未対応のレンダラーのアイテム
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownAction {
+    /// アクション名（例: `"newFooAction"`）またはレンダラー名（例: `"liveChatFooRenderer"`）
+    pub kind: String,
+    /// アクション（未対応のレンダラーの場合はアイテム）の生の JSON
+    pub raw: Value,
+}
 
-    if let Some(actions) = actions {
-        for action in actions {
-            if let Some(msg) = parse_chat_action(action) {
-                messages.push(msg);
+/// レスポンスに含まれる未対応のアクション・アイテムを順に返す
+///
+/// YouTube が新しい種類のアクションやレンダラーを追加した場合、パーサーは黙って読み飛ばす。
+/// それに気付けるよう、処理も無視もしない種類を取り出す。
+pub fn unknown_actions(data: &Value) -> Vec<UnknownAction> {
+    let mut unknown = Vec::new();
+    for action in response_actions(data) {
+        collect_unknown_actions(action, &mut unknown);
+    }
+    unknown
+}
+
+fn collect_unknown_actions(action: &Value, unknown: &mut Vec<UnknownAction>) {
+    // `clickTrackingParams` はアクション名と並んで入っているだけなので除く
+    let Some(kind) = action
+        .as_object()
+        .and_then(|object| object.keys().find(|key| *key != "clickTrackingParams"))
+    else {
+        return;
+    };
+    match kind.as_str() {
+        "replayChatItemAction" => {
+            let inner = action
+                .pointer("/replayChatItemAction/actions")
+                .and_then(|v| v.as_array());
+            for inner in inner.into_iter().flatten() {
+                collect_unknown_actions(inner, unknown);
+            }
+        }
+        "addChatItemAction" => {
+            let Some(item) = action.pointer("/addChatItemAction/item") else {
+                return;
+            };
+            let renderer = item.as_object().and_then(|object| object.keys().next());
+            if let Some(renderer) = renderer.filter(|r| !KNOWN_RENDERERS.contains(&r.as_str())) {
+                unknown.push(UnknownAction {
+                    kind: renderer.clone(),
+                    raw: item.clone(),
+                });
             }
         }
+        kind if HANDLED_ACTIONS.contains(&kind) || IGNORED_ACTIONS.contains(&kind) => {}
+        kind => unknown.push(UnknownAction {
+            kind: kind.to_string(),
+            raw: action.clone(),
+        }),
     }
-    messages
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn engagement_response(runs: Value) -> Value {
+        serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": {
+                    "actions": [{
+                        "addChatItemAction": {
+                            "item": {
+                                "liveChatViewerEngagementMessageRenderer": {
+                                    "id": "engagement_1",
+                                    "message": { "runs": runs }
+                                }
+                            }
+                        }
+                    }]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_stream_end_notice_detected_from_engagement_message() {
+        // 配信終了のエンゲージメントメッセージを日本語・英語とも検出すること
+        let ja = engagement_response(serde_json::json!([{ "text": "配信は終了しました" }]));
+        assert!(has_stream_end_notice(&ja));
+        let en = engagement_response(serde_json::json!([
+            { "text": "Live chat is no longer " },
+            { "text": "available" }
+        ]));
+        assert!(has_stream_end_notice(&en));
+
+        // 配信開始時の案内は配信終了ではない
+        let welcome = engagement_response(serde_json::json!([
+            { "text": "Welcome to live chat! Remember to guard your privacy." }
+        ]));
+        assert!(!has_stream_end_notice(&welcome));
+        assert!(!has_stream_end_notice(&serde_json::json!({})));
+    }
+
     #[test]
     fn test_parse_member_badge() {
         // メンバーバッジ（customThumbnail）が正しく検出されること
@@ -624,6 +1033,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unknown_actions_and_renderers_are_reported() {
+        let response = serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": [
+                { "clickTrackingParams": "abc", "addChatItemAction": { "item": {
+                    "liveChatTextMessageRenderer": { "id": "text" }
+                }}},
+                { "addChatItemAction": { "item": {
+                    "liveChatViewerEngagementMessageRenderer": { "id": "engagement" }
+                }}},
+                { "addLiveChatTickerItemAction": { "item": {} } },
+                { "markChatItemAsDeletedAction": { "targetItemId": "text" } },
+                { "clickTrackingParams": "def", "newShinyFeatureAction": { "foo": 1 } },
+                { "replayChatItemAction": { "actions": [
+                    { "addChatItemAction": { "item": {
+                        "liveChatNewRenderer": { "id": "new" }
+                    }}}
+                ]}}
+            ]}}
+        });
+
+        let unknown = unknown_actions(&response);
+        let kinds: Vec<&str> = unknown.iter().map(|u| u.kind.as_str()).collect();
+        assert_eq!(kinds, ["newShinyFeatureAction", "liveChatNewRenderer"]);
+        assert_eq!(unknown[0].raw["newShinyFeatureAction"]["foo"], 1);
+        assert_eq!(unknown[1].raw["liveChatNewRenderer"]["id"], "new");
+
+        assert!(unknown_actions(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_chat_items_over_mixed_actions() {
+        fn add(renderer: &str, body: Value) -> Value {
+            serde_json::json!({ "addChatItemAction": { "item": { renderer: body } } })
+        }
+        let base = |id: &str| serde_json::json!({ "id": id, "timestampUsec": "1700000000000000" });
+        let mut text = base("text");
+        text["message"] = serde_json::json!({ "runs": [{ "text": "hello" }] });
+        let mut paid = base("paid");
+        paid["purchaseAmountText"] = serde_json::json!({ "simpleText": "¥500" });
+        let mut sticker = base("sticker");
+        sticker["purchaseAmountText"] = serde_json::json!({ "simpleText": "¥200" });
+        let mut gift = base("gift");
+        gift["header"] = serde_json::json!({ "liveChatSponsorshipsHeaderRenderer": {
+            "primaryText": { "runs": [{ "text": "Sent 5 Test gift memberships" }] }
+        }});
+
+        let response = serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": [
+                add("liveChatTextMessageRenderer", text.clone()),
+                { "markChatItemAsDeletedAction": { "targetItemId": "text" } },
+                add("liveChatPaidMessageRenderer", paid),
+                add("liveChatPaidStickerRenderer", sticker),
+                add("liveChatMembershipItemRenderer", base("member")),
+                add("liveChatSponsorshipsGiftPurchaseAnnouncementRenderer", gift),
+                add("liveChatViewerEngagementMessageRenderer", base("engagement")),
+                { "replayChatItemAction": { "actions": [
+                    add("liveChatTextMessageRenderer", text)
+                ]}}
+            ]}}
+        });
+
+        // 削除アクションはアイテムを追加しないので数えない
+        assert_eq!(chat_items(&response).count(), 7);
+        assert_eq!(deleted_item_ids(&response), ["text"]);
+
+        let messages = parse_chat_actions(&response);
+        let kinds: Vec<_> = messages
+            .iter()
+            .map(|m| match m.message_type {
+                MessageType::Text => "text",
+                MessageType::SuperChat { .. } => "superchat",
+                MessageType::SuperSticker { .. } => "sticker",
+                MessageType::Membership { .. } => "membership",
+                MessageType::MembershipGift { .. } => "gift",
+                _ => "other",
+            })
+            .collect();
+        // エンゲージメントメッセージはメッセージにならない
+        assert_eq!(
+            kinds,
+            ["text", "superchat", "sticker", "membership", "gift", "text"]
+        );
+        assert_eq!(messages[0].content, "hello");
+        assert_eq!(messages[0].timestamp_usec, "1700000000000000");
+        assert_eq!(
+            messages[1].message_type,
+            MessageType::SuperChat {
+                amount: "¥500".to_string()
+            }
+        );
+    }
+
     #[test]
     fn test_extract_milestone_months_from_badge_english() {
         // 英語フォーマット: "Member (6 months)"
@@ -662,6 +1164,181 @@ mod tests {
         assert_eq!(extract_milestone_months_from_badge(""), None);
     }
 
+    fn badge(renderer: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "liveChatAuthorBadgeRenderer": renderer })
+    }
+
+    fn icon_badge(icon_type: &str, tooltip: &str) -> serde_json::Value {
+        badge(serde_json::json!({
+            "icon": {"iconType": icon_type},
+            "tooltip": tooltip,
+            "accessibility": {"accessibilityData": {"label": tooltip}}
+        }))
+    }
+
+    #[test]
+    fn test_author_badges_indicate_member() {
+        // customThumbnail または メンバーの tooltip を持つバッジはメンバー
+        let thumbnail_only = badge(serde_json::json!({
+            "customThumbnail": {"thumbnails": [{"url": "https://example.com/badge.png"}]},
+            "tooltip": "サポーター"
+        }));
+        assert!(author_badges_indicate_member(&[thumbnail_only]));
+        for tooltip in [
+            "Member",
+            "New member",
+            "Member (6 months)",
+            "メンバー（1 か月）",
+            "新規メンバー",
+        ] {
+            let tooltip_only = badge(serde_json::json!({ "tooltip": tooltip }));
+            assert!(
+                author_badges_indicate_member(&[tooltip_only]),
+                "{} はメンバー",
+                tooltip
+            );
+        }
+    }
+
+    #[test]
+    fn test_author_badges_moderator_verified_owner_are_not_member() {
+        // モデレーター・認証済み・オーナーのバッジはメンバーとみなさない
+        let badges = [
+            icon_badge("MODERATOR", "Moderator"),
+            icon_badge("VERIFIED", "Verified"),
+            icon_badge("OWNER", "Owner"),
+            icon_badge("MODERATOR", "モデレーター"),
+            icon_badge("VERIFIED", "確認済み"),
+        ];
+        for b in &badges {
+            assert!(!author_badges_indicate_member(std::slice::from_ref(b)));
+        }
+        assert!(!author_badges_indicate_member(&badges));
+        assert!(!author_badges_indicate_member(&[]));
+
+        // モデレーターかつメンバーの場合はメンバー
+        let member = badge(serde_json::json!({
+            "customThumbnail": {"thumbnails": [{"url": "https://example.com/badge.png"}]},
+            "tooltip": "Member (2 months)"
+        }));
+        assert!(author_badges_indicate_member(&[badges[0].clone(), member]));
+    }
+
+    #[test]
+    fn test_member_months_from_badge() {
+        // メンバーバッジの tooltip から月数を取得し、他のバッジは無視する
+        let member = badge(serde_json::json!({
+            "customThumbnail": {"thumbnails": [{"url": "https://example.com/badge.png"}]},
+            "tooltip": "Member (6 months)"
+        }));
+        let moderator = icon_badge("MODERATOR", "Moderator");
+        assert_eq!(
+            member_months_from_badge(&[moderator.clone(), member]),
+            Some(6)
+        );
+        assert_eq!(member_months_from_badge(&[moderator]), None);
+        let new_member = badge(serde_json::json!({ "tooltip": "New member" }));
+        assert_eq!(member_months_from_badge(&[new_member]), None);
+    }
+
+    #[test]
+    fn test_author_role_from_badges() {
+        // アイコン種別と tooltip からロールを判定すること
+        let member = badge(serde_json::json!({
+            "customThumbnail": {"thumbnails": [{"url": "https://example.com/badge.png"}]},
+            "tooltip": "Member (2 months)"
+        }));
+        let role = author_role_from_badges(&[icon_badge("MODERATOR", "Moderator"), member]);
+        assert_eq!(
+            role,
+            AuthorRole {
+                is_moderator: true,
+                is_member: true,
+                ..Default::default()
+            }
+        );
+
+        let role = author_role_from_badges(&[
+            icon_badge("OWNER", "Owner"),
+            icon_badge("VERIFIED", "Verified"),
+        ]);
+        assert!(role.is_owner && role.is_verified);
+        assert!(!role.is_moderator && !role.is_member);
+
+        // アイコン種別がない場合は tooltip で判定する
+        let tooltip_only = badge(serde_json::json!({ "tooltip": "モデレーター" }));
+        assert!(author_role_from_badges(&[tooltip_only]).is_moderator);
+
+        assert_eq!(author_role_from_badges(&[]), AuthorRole::default());
+    }
+
+    #[test]
+    fn test_text_message_from_owner_has_role_metadata() {
+        // テキストメッセージでもオーナー・モデレーターのロールをメタデータに設定すること
+        let text_action = |badges: serde_json::Value| {
+            serde_json::json!({
+                "addChatItemAction": {
+                    "item": {
+                        "liveChatTextMessageRenderer": {
+                            "id": "text_role",
+                            "timestampUsec": "1234567890000000",
+                            "authorName": {"simpleText": "Streamer"},
+                            "authorExternalChannelId": "UC_owner",
+                            "message": {"runs": [{"text": "Hello"}]},
+                            "authorBadges": badges
+                        }
+                    }
+                }
+            })
+        };
+
+        let msg = parse_chat_action(&text_action(serde_json::json!([icon_badge(
+            "OWNER", "Owner"
+        )])))
+        .expect("メッセージがパースされること");
+        let metadata = msg.metadata.as_ref().expect("metadata が存在すること");
+        assert!(metadata.is_owner);
+        assert!(!metadata.is_moderator);
+        assert!(msg.author_role().is_owner);
+        assert!(!msg.author_role().is_member);
+
+        // ロールのない投稿者はメタデータを持たない
+        let msg = parse_chat_action(&text_action(serde_json::json!([])))
+            .expect("メッセージがパースされること");
+        assert!(msg.metadata.is_none());
+        assert_eq!(msg.author_role(), AuthorRole::default());
+    }
+
+    #[test]
+    fn test_superchat_from_member_sets_is_member() {
+        // SuperChat でもメンバーバッジから is_member を設定すること
+        let action = serde_json::json!({
+            "addChatItemAction": {
+                "item": {
+                    "liveChatPaidMessageRenderer": {
+                        "id": "sc_member",
+                        "timestampUsec": "1234567890000000",
+                        "authorName": {"simpleText": "MemberUser"},
+                        "authorExternalChannelId": "UC_member",
+                        "purchaseAmountText": {"simpleText": "¥500"},
+                        "authorBadges": [{
+                            "liveChatAuthorBadgeRenderer": {
+                                "customThumbnail": {
+                                    "thumbnails": [{"url": "https://example.com/badge.png"}]
+                                },
+                                "tooltip": "Member (3 months)"
+                            }
+                        }]
+                    }
+                }
+            }
+        });
+
+        let msg = parse_chat_action(&action).expect("SuperChat がパースされること");
+        assert!(msg.is_member);
+        assert!(!msg.author_role().is_moderator);
+    }
+
     #[test]
     fn test_extract_gift_count_japanese() {
         // 日本語フォーマット: "5人にメンバーシップをギフトしました"
@@ -728,6 +1405,102 @@ mod tests {
         }
     }
 
+    /// 実際の YouTube フォーマットに沿ったメンバーシップ renderer
+    fn membership_renderer(primary_runs: Option<Value>, subtext: Value) -> Value {
+        let mut renderer = serde_json::json!({
+            "id": "membership_1",
+            "timestampUsec": "1234567890000000",
+            "authorName": {"simpleText": "Member"},
+            "authorExternalChannelId": "UC_member",
+            "headerSubtext": subtext,
+        });
+        if let Some(runs) = primary_runs {
+            renderer["headerPrimaryText"] = serde_json::json!({ "runs": runs });
+        }
+        renderer
+    }
+
+    #[test]
+    fn test_membership_milestone_months_english() {
+        for (months, unit) in [(1, " month"), (6, " months"), (12, " months")] {
+            let renderer = membership_renderer(
+                Some(serde_json::json!([
+                    {"text": "Member for "},
+                    {"text": months.to_string()},
+                    {"text": unit}
+                ])),
+                serde_json::json!({"simpleText": "Thanks for the support!"}),
+            );
+            assert_eq!(membership_milestone_months(&renderer), Some(months));
+        }
+    }
+
+    #[test]
+    fn test_membership_milestone_months_japanese() {
+        for months in [1, 6, 12] {
+            let renderer = membership_renderer(
+                Some(serde_json::json!([
+                    {"text": "メンバー歴 "},
+                    {"text": months.to_string()},
+                    {"text": " か月"}
+                ])),
+                serde_json::json!({"runs": []}),
+            );
+            assert_eq!(
+                parse_membership_header(&renderer),
+                Some(MembershipHeader::Milestone(months))
+            );
+        }
+    }
+
+    #[test]
+    fn test_membership_header_new_member() {
+        // 新規メンバーは月数なしだが、判定不能（None）とは区別されること
+        let en = membership_renderer(
+            None,
+            serde_json::json!({"runs": [{"text": "Welcome to "}, {"text": "Channel"}, {"text": "!"}]}),
+        );
+        let ja = membership_renderer(None, serde_json::json!({"simpleText": "新規メンバー"}));
+
+        for renderer in [en, ja] {
+            assert_eq!(
+                parse_membership_header(&renderer),
+                Some(MembershipHeader::NewMember)
+            );
+            assert_eq!(membership_milestone_months(&renderer), None);
+        }
+    }
+
+    #[test]
+    fn test_membership_header_unknown_layout() {
+        let renderer = membership_renderer(None, serde_json::json!({"simpleText": "???"}));
+        assert_eq!(parse_membership_header(&renderer), None);
+    }
+
+    #[test]
+    fn test_parse_membership_milestone_from_header() {
+        // バッジがなくてもヘッダーから月数を取得すること
+        let renderer = membership_renderer(
+            Some(serde_json::json!([
+                {"text": "Member for "},
+                {"text": "6"},
+                {"text": " months"}
+            ])),
+            serde_json::json!({"simpleText": "Hi!"}),
+        );
+        let action = serde_json::json!({
+            "addChatItemAction": { "item": { "liveChatMembershipItemRenderer": renderer } }
+        });
+
+        let msg = parse_chat_action(&action).expect("パースされること");
+        assert_eq!(
+            msg.message_type,
+            MessageType::Membership {
+                milestone_months: Some(6)
+            }
+        );
+    }
+
     #[test]
     fn test_parse_membership_gift_message() {
         // メンバーシップギフトアナウンスのパース（実際の YouTube フォーマット）
@@ -770,6 +1543,45 @@ mod tests {
         }
     }
 
+    fn gift_renderer(primary_text: Value) -> Value {
+        serde_json::json!({
+            "id": "gift_msg",
+            "timestampUsec": "1234567890000000",
+            "header": {
+                "liveChatSponsorshipsHeaderRenderer": {
+                    "authorName": {"simpleText": "GiftGiver"},
+                    "primaryText": primary_text
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_membership_gift_count_japanese_template() {
+        let renderer = gift_renderer(serde_json::json!({"runs": [
+            {"text": "10", "bold": true},
+            {"text": " 件のメンバーシップ ギフトを贈呈しました", "bold": true}
+        ]}));
+        assert_eq!(membership_gift_count(&renderer), Some(10));
+    }
+
+    #[test]
+    fn test_membership_gift_count_english_template() {
+        let renderer = gift_renderer(serde_json::json!({"runs": [
+            {"text": "Sent ", "bold": true},
+            {"text": "20", "bold": true},
+            {"text": " Channel gift memberships", "bold": true}
+        ]}));
+        assert_eq!(membership_gift_count(&renderer), Some(20));
+    }
+
+    #[test]
+    fn test_membership_gift_count_unknown_layout() {
+        let renderer = gift_renderer(serde_json::json!({"simpleText": "Thanks!"}));
+        assert_eq!(membership_gift_count(&renderer), None);
+        assert_eq!(membership_gift_count(&serde_json::json!({})), None);
+    }
+
     #[test]
     fn test_parse_new_member_no_milestone() {
         // 新規メンバーは milestone 月数なし（バッジ tooltip は "New member"）
@@ -901,6 +1713,8 @@ mod tests {
         // 数値でない文字列は空文字列を返すこと
         let result = format_timestamp("not_a_number");
         assert_eq!(result, "");
+        // 範囲外の値を 1970-01-01 にしないこと
+        assert_eq!(format_timestamp("99999999999999999999"), "");
     }
 
     #[test]
@@ -948,5 +1762,152 @@ mod tests {
         assert_eq!(colors.body_background, "#1E88E5", "body_background は青");
         assert_eq!(colors.header_text, "#FFFFFF", "header_text は白");
         assert_eq!(colors.body_text, "#FFFFFF", "body_text は白");
+
+        let sticker = metadata.sticker.expect("sticker が存在すること");
+        assert_eq!(sticker.url, "https://example.com/sticker.png");
+    }
+
+    #[test]
+    fn test_parse_supersticker_picks_best_fit_thumbnail() {
+        let action = serde_json::json!({
+            "addChatItemAction": {
+                "item": {
+                    "liveChatPaidStickerRenderer": {
+                        "id": "sticker_size_test",
+                        "timestampUsec": "1234567890000000",
+                        "authorName": {"simpleText": "StickerUser"},
+                        "purchaseAmountText": {"simpleText": "¥200"},
+                        "sticker": {
+                            "thumbnails": [
+                                {"url": "//lh3.googleusercontent.com/s40", "width": 40, "height": 40},
+                                {"url": "//lh3.googleusercontent.com/s80", "width": 80, "height": 80},
+                                {"url": "//lh3.googleusercontent.com/s160", "width": 160, "height": 160}
+                            ],
+                            "accessibility": {"accessibilityData": {"label": "Hello"}}
+                        }
+                    }
+                }
+            }
+        });
+
+        let msg = parse_chat_action(&action).unwrap();
+        let sticker = msg.metadata.unwrap().sticker.unwrap();
+        assert!(!sticker.url.is_empty());
+        assert_eq!(sticker.url, "https://lh3.googleusercontent.com/s80");
+        assert_eq!(sticker.alt_text, "Hello");
+    }
+
+    #[test]
+    fn test_best_thumbnail_url() {
+        let thumbnails = serde_json::json!([
+            {"url": "https://example.com/small", "width": 24},
+            {"url": "https://example.com/large", "width": 48}
+        ]);
+        assert_eq!(
+            best_thumbnail_url(&thumbnails, 32).as_deref(),
+            Some("https://example.com/large")
+        );
+        // 要求より大きいものがなければ最大のもの
+        assert_eq!(
+            best_thumbnail_url(&thumbnails, 96).as_deref(),
+            Some("https://example.com/large")
+        );
+        assert_eq!(
+            best_thumbnail_url(&thumbnails, 16).as_deref(),
+            Some("https://example.com/small")
+        );
+        assert_eq!(best_thumbnail_url(&serde_json::json!([]), 32), None);
+    }
+
+    #[test]
+    fn test_parse_emoji_shortcuts() {
+        // カスタム絵文字のショートコードが runs に保持されること
+        let message = serde_json::json!({
+            "runs": [
+                {"text": "草"},
+                {"emoji": {
+                    "emojiId": "UC_x/kusa",
+                    "shortcuts": [":_kusa:", ":kusa:"],
+                    "image": {
+                        "thumbnails": [{"url": "https://yt3.ggpht.com/kusa"}],
+                        "accessibility": {"accessibilityData": {"label": ":_kusa:"}}
+                    }
+                }}
+            ]
+        });
+
+        let (content, runs) = parse_message_content(&message);
+        assert_eq!(content, "草:_kusa:");
+        match &runs[1] {
+            MessageRun::Emoji { shortcuts, .. } => {
+                assert_eq!(shortcuts, &[":_kusa:", ":kusa:"]);
+            }
+            _ => panic!("Emoji run を期待"),
+        }
+    }
+
+    #[test]
+    fn test_parse_mixed_text_and_emoji_runs() {
+        // カスタム絵文字は画像の run、Unicode 絵文字は前後のテキストとつなげる
+        let message = serde_json::json!({
+            "runs": [
+                {"text": "こんにちは"},
+                {"emoji": {
+                    "emojiId": "😀",
+                    "shortcuts": [":grinning:"],
+                    "image": {
+                        "thumbnails": [{"url": "https://www.youtube.com/s/gaming/emoji/1f600.svg"}],
+                        "accessibility": {"accessibilityData": {"label": "grinning face"}}
+                    }
+                }},
+                {"text": " "},
+                {"emoji": {
+                    "emojiId": "UC_x/hi",
+                    "shortcuts": [":_hi:"],
+                    "isCustomEmoji": true,
+                    "image": {
+                        "thumbnails": [
+                            {"url": "https://yt3.ggpht.com/hi=w24-h24", "width": 24, "height": 24},
+                            {"url": "https://yt3.ggpht.com/hi=w48-h48", "width": 48, "height": 48}
+                        ],
+                        "accessibility": {"accessibilityData": {"label": ":_hi:"}}
+                    }
+                }},
+                {"text": "!"}
+            ]
+        });
+
+        let (content, runs) = parse_message_content(&message);
+        assert_eq!(content, "こんにちは😀 :_hi:!");
+        assert_eq!(runs.len(), 3);
+        assert!(matches!(&runs[0], MessageRun::Text { content } if content == "こんにちは😀 "));
+        match &runs[1] {
+            MessageRun::Emoji {
+                emoji_id,
+                image_url,
+                alt_text,
+                ..
+            } => {
+                assert_eq!(emoji_id, "UC_x/hi");
+                assert_eq!(image_url, "https://yt3.ggpht.com/hi=w48-h48");
+                assert_eq!(alt_text, ":_hi:");
+            }
+            _ => panic!("Emoji run を期待"),
+        }
+        assert!(matches!(&runs[2], MessageRun::Text { content } if content == "!"));
+    }
+
+    #[test]
+    fn test_deleted_item_ids() {
+        let response = serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": [
+                { "markChatItemAsDeletedAction": { "targetItemId": "a" } },
+                { "removeChatItemAction": { "targetItemId": "b" } },
+                { "markChatItemAsDeletedAction": { "targetItemId": "" } },
+                { "addChatItemAction": { "item": {} } }
+            ]}}
+        });
+        assert_eq!(deleted_item_ids(&response), ["a", "b"]);
+        assert!(deleted_item_ids(&serde_json::json!({})).is_empty());
     }
 }
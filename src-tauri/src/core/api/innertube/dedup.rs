@@ -0,0 +1,112 @@
+//! チャットアイテムの重複除去
+//!
+//! YouTube は同じ `addChatItemAction` を連続する continuation レスポンスで
+//! 再送することがあるため、直近に受け取ったアイテム ID を覚えておき重複を捨てる。
+
+use std::collections::{HashSet, VecDeque};
+
+/// 既定で覚えておくアイテム ID の件数
+pub const DEFAULT_DEDUP_CAPACITY: usize = 2000;
+
+/// 直近に受け取ったアイテム ID の集合（件数上限付き）
+///
+/// 上限を超えると最も古く記録した ID から忘れる。容量 0 の場合は何も記録せず、
+/// すべての ID を新規として扱う（重複除去の無効化）。
+#[derive(Debug, Clone)]
+pub struct DedupTracker {
+    capacity: usize,
+    order: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl Default for DedupTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEDUP_CAPACITY)
+    }
+}
+
+impl DedupTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::with_capacity(capacity.min(DEFAULT_DEDUP_CAPACITY)),
+            seen: HashSet::with_capacity(capacity.min(DEFAULT_DEDUP_CAPACITY)),
+        }
+    }
+
+    /// ID を記録する。初めて見る ID なら `true`、記録済みなら `false` を返す
+    pub fn insert(&mut self, id: &str) -> bool {
+        if self.capacity == 0 {
+            return true;
+        }
+        if self.seen.contains(id) {
+            return false;
+        }
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.order.push_back(id.to_string());
+        self.seen.insert(id.to_string());
+        true
+    }
+
+    /// 記録している ID の件数
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_reports_new_and_seen_ids() {
+        let mut tracker = DedupTracker::new(10);
+
+        assert!(tracker.insert("a"));
+        assert!(tracker.insert("b"));
+        assert!(!tracker.insert("a"));
+        assert_eq!(tracker.len(), 2);
+    }
+
+    #[test]
+    fn oldest_id_is_forgotten_when_full() {
+        let mut tracker = DedupTracker::new(2);
+
+        tracker.insert("a");
+        tracker.insert("b");
+        tracker.insert("c");
+
+        assert_eq!(tracker.len(), 2);
+        assert!(!tracker.insert("c"));
+        assert!(!tracker.insert("b"));
+        // "a" は押し出されているので新規扱い
+        assert!(tracker.insert("a"));
+    }
+
+    #[test]
+    fn zero_capacity_disables_dedup() {
+        let mut tracker = DedupTracker::new(0);
+
+        assert!(tracker.insert("a"));
+        assert!(tracker.insert("a"));
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn default_capacity() {
+        let mut tracker = DedupTracker::default();
+        for i in 0..DEFAULT_DEDUP_CAPACITY + 1 {
+            tracker.insert(&i.to_string());
+        }
+        assert_eq!(tracker.len(), DEFAULT_DEDUP_CAPACITY);
+        assert!(tracker.insert("0"));
+    }
+}
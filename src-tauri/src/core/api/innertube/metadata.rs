@@ -0,0 +1,323 @@
+//! 配信のメタデータ（タイトル・チャンネル・同時視聴者数・配信状態）の取得
+//!
+//! ウォッチページの `ytInitialPlayerResponse`（videoDetails / microformat）と
+//! `ytInitialData`（同時視聴者数）から組み立てる。再接続のたびに取得し直さないよう、
+//! 結果は動画 ID ごとに短時間キャッシュする。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+use super::client;
+use super::initial_data::extract_yt_initial_data;
+use crate::core::api::ProxyConfig;
+
+/// メタデータのキャッシュ保持期間
+const METADATA_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 動画 ID ごとの取得済みメタデータ
+static METADATA_CACHE: LazyLock<Mutex<HashMap<String, (Instant, VideoMetadata)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 配信状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum LiveStatus {
+    /// 配信予定（待機所）
+    Upcoming,
+    Live,
+    /// 配信終了（アーカイブ）
+    Ended,
+    /// ライブ配信ではない通常の動画
+    NotLive,
+}
+
+/// 配信のメタデータ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct VideoMetadata {
+    pub video_id: String,
+    pub title: String,
+    pub channel_name: String,
+    pub channel_id: String,
+    /// 同時視聴者数（配信中のみ）
+    pub concurrent_viewers: Option<u32>,
+    pub live_status: LiveStatus,
+}
+
+/// HTML から `ytInitialPlayerResponse` JSON を抽出する
+///
+/// 直後に `;</script>` 以外（`;var meta = ...`）が続くこともあるため、
+/// 終端を探さずに JSON 値 1 つ分だけ読み取る。
+pub fn extract_yt_initial_player_response(html: &str) -> Option<Value> {
+    let start_marker = "var ytInitialPlayerResponse = ";
+    let start = html.find(start_marker)? + start_marker.len();
+    serde_json::Deserializer::from_str(&html[start..])
+        .into_iter::<Value>()
+        .next()?
+        .ok()
+}
+
+/// `ytInitialPlayerResponse` と `ytInitialData` からメタデータを組み立てる
+///
+/// タイトル・チャンネルは player response の `videoDetails` から取り、
+/// 同時視聴者数は配信中のみ `ytInitialData` の `viewCount` から取る。
+pub fn parse_video_metadata(
+    video_id: &str,
+    player: &Value,
+    initial: Option<&Value>,
+) -> Option<VideoMetadata> {
+    let details = player.get("videoDetails")?;
+    let text = |key: &str| details.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let live_status = parse_live_status(player);
+    let concurrent_viewers = match (live_status, initial) {
+        (LiveStatus::Live, Some(initial)) => parse_concurrent_viewers(initial),
+        _ => None,
+    };
+
+    Some(VideoMetadata {
+        video_id: video_id.to_string(),
+        title: text("title")?,
+        channel_name: text("author").unwrap_or_default(),
+        channel_id: text("channelId").unwrap_or_default(),
+        concurrent_viewers,
+        live_status,
+    })
+}
+
+/// player response の `videoDetails` / `liveBroadcastDetails` から配信状態を判定する
+fn parse_live_status(player: &Value) -> LiveStatus {
+    let flag = |pointer: &str| {
+        player
+            .pointer(pointer)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    let broadcast = player.pointer("/microformat/playerMicroformatRenderer/liveBroadcastDetails");
+
+    if flag("/videoDetails/isUpcoming") {
+        LiveStatus::Upcoming
+    } else if flag("/videoDetails/isLive")
+        || flag("/microformat/playerMicroformatRenderer/liveBroadcastDetails/isLiveNow")
+    {
+        LiveStatus::Live
+    } else if flag("/videoDetails/isLiveContent") || broadcast.is_some() {
+        LiveStatus::Ended
+    } else {
+        LiveStatus::NotLive
+    }
+}
+
+/// `ytInitialData` の `viewCount` から同時視聴者数を取り出す
+fn parse_concurrent_viewers(initial: &Value) -> Option<u32> {
+    let view_count = initial.pointer(
+        "/contents/twoColumnWatchNextResults/results/results/contents/0/videoPrimaryInfoRenderer/viewCount/videoViewCountRenderer",
+    )?;
+    // originalViewCount は桁区切りなしの数値文字列
+    if let Some(count) = view_count
+        .get("originalViewCount")
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse().ok())
+    {
+        return Some(count);
+    }
+    // なければ "1,234 watching now" / "1,234 人が視聴中" の先頭の数値を使う
+    let text = view_count.pointer("/viewCount/runs/0/text")?.as_str()?;
+    let digits: String = text
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ',')
+        .filter(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// ウォッチページから配信のメタデータを取得する
+///
+/// `proxy` を通して取得する。同じ動画 ID の結果は [`METADATA_CACHE_TTL`] の間キャッシュから返す。
+pub async fn fetch_video_metadata(video_id: &str, proxy: &ProxyConfig) -> Result<VideoMetadata> {
+    if let Some(cached) = cached_metadata(video_id, Instant::now()) {
+        return Ok(cached);
+    }
+
+    let page_url = format!("{}/watch?v={}", client::get_youtube_base_url(), video_id);
+    let html = client::build_http_client_with_proxy(proxy)?
+        .get(&page_url)
+        .header(
+            "User-Agent",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+        )
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let player = extract_yt_initial_player_response(&html)
+        .ok_or_else(|| anyhow!("ytInitialPlayerResponse not found: video_id={}", video_id))?;
+    let initial = extract_yt_initial_data(&html);
+    let metadata = parse_video_metadata(video_id, &player, initial.as_ref())
+        .ok_or_else(|| anyhow!("videoDetails not found: video_id={}", video_id))?;
+
+    METADATA_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(video_id.to_string(), (Instant::now(), metadata.clone()));
+    Ok(metadata)
+}
+
+/// キャッシュが有効期間内ならメタデータを返す（期限切れのものは捨てる）
+fn cached_metadata(video_id: &str, now: Instant) -> Option<VideoMetadata> {
+    let mut cache = METADATA_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    cache.retain(|_, (fetched_at, _)| now.duration_since(*fetched_at) < METADATA_CACHE_TTL);
+    cache.get(video_id).map(|(_, metadata)| metadata.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player_response(details: Value, broadcast: Option<Value>) -> Value {
+        let mut player = serde_json::json!({ "videoDetails": details });
+        if let Some(broadcast) = broadcast {
+            player["microformat"] = serde_json::json!({
+                "playerMicroformatRenderer": { "liveBroadcastDetails": broadcast }
+            });
+        }
+        player
+    }
+
+    fn initial_with_viewers(original: Option<&str>, text: &str) -> Value {
+        let mut renderer = serde_json::json!({
+            "viewCount": { "runs": [{ "text": text }] },
+            "isLive": true
+        });
+        if let Some(original) = original {
+            renderer["originalViewCount"] = Value::from(original);
+        }
+        serde_json::json!({
+            "contents": { "twoColumnWatchNextResults": { "results": { "results": {
+                "contents": [{ "videoPrimaryInfoRenderer": {
+                    "viewCount": { "videoViewCountRenderer": renderer }
+                }}]
+            }}}}
+        })
+    }
+
+    #[test]
+    fn test_extract_player_response_with_trailing_script() {
+        // `;var meta = ...` が続いても JSON 部分だけを取り出すこと
+        let html = r#"<script>var ytInitialPlayerResponse = {"videoDetails":{"title":"a;b"}};var meta = document.createElement('meta');</script>"#;
+        let player = extract_yt_initial_player_response(html).unwrap();
+        assert_eq!(player["videoDetails"]["title"], "a;b");
+        assert!(extract_yt_initial_player_response("<html></html>").is_none());
+    }
+
+    #[test]
+    fn test_parse_live_video_metadata() {
+        let player = player_response(
+            serde_json::json!({
+                "title": "雑談配信",
+                "author": "Alice",
+                "channelId": "UC_alice",
+                "isLive": true,
+                "isLiveContent": true
+            }),
+            Some(
+                serde_json::json!({ "isLiveNow": true, "startTimestamp": "2026-01-01T00:00:00+00:00" }),
+            ),
+        );
+        let initial = initial_with_viewers(Some("1234"), "1,234 watching now");
+
+        let metadata = parse_video_metadata("vid", &player, Some(&initial)).unwrap();
+        assert_eq!(
+            metadata,
+            VideoMetadata {
+                video_id: "vid".to_string(),
+                title: "雑談配信".to_string(),
+                channel_name: "Alice".to_string(),
+                channel_id: "UC_alice".to_string(),
+                concurrent_viewers: Some(1234),
+                live_status: LiveStatus::Live,
+            }
+        );
+    }
+
+    #[test]
+    fn test_concurrent_viewers_falls_back_to_text() {
+        let initial = initial_with_viewers(None, "12,345 人が視聴中");
+        assert_eq!(parse_concurrent_viewers(&initial), Some(12345));
+    }
+
+    #[test]
+    fn test_parse_live_status_variants() {
+        let upcoming = player_response(
+            serde_json::json!({ "isUpcoming": true, "isLiveContent": true }),
+            Some(serde_json::json!({ "isLiveNow": false })),
+        );
+        assert_eq!(parse_live_status(&upcoming), LiveStatus::Upcoming);
+
+        let ended = player_response(
+            serde_json::json!({ "isLiveContent": true }),
+            Some(
+                serde_json::json!({ "isLiveNow": false, "endTimestamp": "2026-01-01T01:00:00+00:00" }),
+            ),
+        );
+        assert_eq!(parse_live_status(&ended), LiveStatus::Ended);
+
+        let vod = player_response(serde_json::json!({ "isLiveContent": false }), None);
+        assert_eq!(parse_live_status(&vod), LiveStatus::NotLive);
+    }
+
+    #[test]
+    fn test_viewers_only_reported_while_live() {
+        // 配信終了後の viewCount は総再生回数なので同時視聴者数として扱わないこと
+        let player = player_response(
+            serde_json::json!({ "title": "t", "author": "a", "channelId": "c", "isLiveContent": true }),
+            None,
+        );
+        let initial = initial_with_viewers(Some("50000"), "50,000 views");
+        let metadata = parse_video_metadata("vid", &player, Some(&initial)).unwrap();
+        assert_eq!(metadata.live_status, LiveStatus::Ended);
+        assert_eq!(metadata.concurrent_viewers, None);
+    }
+
+    #[test]
+    fn test_parse_requires_title() {
+        let player = player_response(serde_json::json!({ "author": "a" }), None);
+        assert!(parse_video_metadata("vid", &player, None).is_none());
+        assert!(parse_video_metadata("vid", &serde_json::json!({}), None).is_none());
+    }
+
+    #[test]
+    fn test_cached_metadata_expires_after_ttl() {
+        let metadata = VideoMetadata {
+            video_id: "cache_test".to_string(),
+            title: "t".to_string(),
+            channel_name: String::new(),
+            channel_id: String::new(),
+            concurrent_viewers: None,
+            live_status: LiveStatus::Live,
+        };
+        let fetched_at = Instant::now();
+        METADATA_CACHE
+            .lock()
+            .unwrap()
+            .insert("cache_test".to_string(), (fetched_at, metadata.clone()));
+
+        assert_eq!(cached_metadata("cache_test", fetched_at), Some(metadata));
+        assert_eq!(
+            cached_metadata("cache_test", fetched_at + METADATA_CACHE_TTL),
+            None
+        );
+        assert!(!METADATA_CACHE.lock().unwrap().contains_key("cache_test"));
+    }
+}
@@ -1,56 +1,251 @@
 //! InnerTube API クライアント（YouTube Live Chat）
 //!
 //! サブモジュール構成:
+//! - `access`       : メンバー限定配信のアクセス拒否の検出
 //! - `client`       : HTTP リクエスト構築・送信・cookie 管理
 //! - `initial_data` : ウォッチページ HTML パース・continuation token 解析
 //! - `chat_parser`  : チャットメッセージのパース・変換ロジック
+//! - `retry`        : 一時的な HTTP エラーのリトライ（指数バックオフ + ジッター）
+//! - `amount`       : SuperChat 金額テキストのパース（通貨・数値）
+//! - `dedup`        : 再送されたチャットアイテムの重複除去
+//! - `metadata`     : 配信のメタデータ（タイトル・チャンネル・同時視聴者数・配信状態）
 
+mod access;
+mod amount;
 mod chat_parser;
 mod client;
+mod dedup;
 mod initial_data;
+mod metadata;
+mod retry;
+mod timestamp;
 
+use crate::core::api::{ProxyConfig, ProxyError, attribute_to_proxy, check_proxy_response};
 use crate::core::models::*;
 use anyhow::{Result, anyhow};
+use futures_util::Stream;
 use reqwest::Client;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
 
-pub use chat_parser::parse_chat_actions;
-pub use client::{get_innertube_api_url, get_youtube_base_url};
+/// 取り出されるまで保持する未対応のアクションの最大件数（超えた分は数えるだけで捨てる）
+const MAX_PENDING_UNKNOWN_ACTIONS: usize = 100;
+
+/// continuation が連続して欠落した場合に配信終了とみなす回数
+const STREAM_END_MISSING_CONTINUATION_THRESHOLD: u32 = 3;
+
+/// レスポンスに `timeoutMs` がない場合のポーリング間隔
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(1500);
+/// `timeoutMs` に従う場合の最短ポーリング間隔（過剰なポーリングでのレート制限を避ける）
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1000);
+/// `timeoutMs` に従う場合の最長ポーリング間隔（チャットの表示遅延を抑える）
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(10_000);
+/// ユーザー指定のポーリング間隔の最短値
+pub const MIN_POLL_INTERVAL_OVERRIDE: Duration = Duration::from_millis(500);
+/// ユーザー指定のポーリング間隔の最長値
+pub const MAX_POLL_INTERVAL_OVERRIDE: Duration = Duration::from_millis(30_000);
+
+pub use access::{MembersOnly, is_members_only};
+pub use amount::{ParsedAmount, parse_purchase_amount};
+pub use chat_parser::{
+    MembershipHeader, author_badges_indicate_member, author_role_from_badges, chat_item,
+    chat_items, extract_milestone_months_from_badge, member_months_from_badge,
+    membership_gift_count, membership_milestone_months, parse_chat_actions, parse_chat_item,
+    parse_membership_header,
+};
+pub use chat_parser::{UnknownAction, unknown_actions};
+pub use chat_parser::{has_stream_end_notice, is_stream_end_notice};
+pub use client::{
+    ContinuationKind, ContinuationMeta, InnerTubeContext, REQUEST_TIMEOUT, build_http_client,
+    build_http_client_with_proxy, get_innertube_api_url, get_next_continuation_with_timeout,
+    get_youtube_base_url,
+};
+pub use dedup::{DEFAULT_DEDUP_CAPACITY, DedupTracker};
+pub use metadata::{LiveStatus, VideoMetadata, fetch_video_metadata};
+pub use retry::{
+    RateLimitStats, RetryConfig, RetryExhausted, is_rate_limited, send_with_retry,
+    server_retry_after,
+};
+pub use timestamp::{usec_to_datetime, usec_to_relative, usec_to_rfc3339};
+
+/// リクエストの試行ごとに呼ぶコールバック
+pub type AttemptObserver = Arc<dyn Fn() + Send + Sync>;
 
 /// InnerTube API クライアント
 pub struct InnerTubeClient {
     http_client: Client,
+    /// 使っているプロキシの表示用 URL（プロキシのエラーを区別するため）
+    proxy: Option<String>,
     video_id: String,
     api_key: String,
-    client_version: String,
+    context: InnerTubeContext,
     continuation: Option<String>,
+    /// 直近のレスポンスから採用した continuation の種類（デバッグ用）
+    continuation_kind: Option<ContinuationKind>,
     chat_mode: ChatMode,
-    auth_cookies: Option<YouTubeCookies>,
+    auth: Option<ApiCredentials>,
     pub broadcaster_channel_id: Option<String>,
     pub broadcaster_name: Option<String>,
     pub stream_title: Option<String>,
     pub is_replay: bool,
+    /// continuation が返されなかった連続レスポンス数（配信終了判定用）
+    missing_continuation_count: u32,
+    /// 配信終了を知らせるエンゲージメントメッセージを受信したか
+    stream_end_notice: bool,
+    /// 削除アクションで届いた、まだ取り出されていないメッセージ ID
+    deleted_ids: Vec<String>,
+    /// この接続で受けた未対応のアクション・アイテムの件数
+    unknown_action_count: u64,
+    /// debug ログに出した未対応の種類（同じ種類は 1 回だけ出す）
+    logged_unknown_kinds: HashSet<String>,
+    /// まだ取り出されていない未対応のアクション・アイテム
+    pending_unknown_actions: Vec<UnknownAction>,
+    /// 直近のレスポンスが指定した次回ポーリングまでの待機時間（ミリ秒）
+    poll_timeout_ms: Option<u64>,
+    /// ユーザーが指定したポーリング間隔（`timeoutMs` より優先する）
+    poll_interval_override: Option<Duration>,
+    retry_config: RetryConfig,
+    /// この接続で受けたレート制限（429）の集計
+    rate_limit_stats: RateLimitStats,
+    /// 再送されたチャットアイテムを捨てるための既読 ID
+    dedup: DedupTracker,
+    /// 保存済み continuation から再開した場合の、ウォッチページ由来の最新 continuation
+    /// （保存済みのものが無効だったときに切り替える。最初の成功で破棄する）
+    fallback_continuation: Option<String>,
+    /// チャット取得のリクエストを送るたび（リトライを含む）に呼ぶコールバック
+    attempt_observer: Option<AttemptObserver>,
 }
 
 impl InnerTubeClient {
     pub fn new(video_id: impl Into<String>) -> Self {
         Self {
-            http_client: Client::new(),
+            http_client: client::build_http_client(),
+            proxy: ProxyConfig::default().active_url(),
             video_id: video_id.into(),
             api_key: client::DEFAULT_API_KEY.to_string(),
-            client_version: "2.20240101.00.00".to_string(),
+            context: InnerTubeContext::default(),
             continuation: None,
+            continuation_kind: None,
             chat_mode: ChatMode::TopChat,
-            auth_cookies: None,
+            auth: None,
             broadcaster_channel_id: None,
             broadcaster_name: None,
             stream_title: None,
             is_replay: false,
+            missing_continuation_count: 0,
+            stream_end_notice: false,
+            deleted_ids: Vec::new(),
+            unknown_action_count: 0,
+            logged_unknown_kinds: HashSet::new(),
+            pending_unknown_actions: Vec::new(),
+            poll_timeout_ms: None,
+            poll_interval_override: None,
+            retry_config: RetryConfig::default(),
+            rate_limit_stats: RateLimitStats::default(),
+            dedup: DedupTracker::default(),
+            fallback_continuation: None,
+            attempt_observer: None,
+        }
+    }
+
+    /// HTTP リクエストのリトライポリシーを設定する
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// リクエストで名乗るクライアント（`clientVersion`・User-Agent など）を設定する
+    ///
+    /// 値は検証しない。設定から渡す場合は [`InnerTubeContext::validate`] を通したものを渡す。
+    pub fn with_context(mut self, context: InnerTubeContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// 指定したプロキシを通して接続する（未指定なら環境変数 `LISCOV_PROXY` に従う）
+    pub fn with_proxy(mut self, proxy: &ProxyConfig) -> Result<Self, ProxyError> {
+        self.http_client = client::build_http_client_with_proxy(proxy)?;
+        self.proxy = proxy.active_url();
+        Ok(self)
+    }
+
+    /// 重複除去のために覚えておくチャットアイテム ID の件数を設定する（0 で無効）
+    pub fn with_dedup_capacity(mut self, capacity: usize) -> Self {
+        self.dedup = DedupTracker::new(capacity);
+        self
+    }
+
+    /// 監視対象の動画 ID を返す
+    pub fn video_id(&self) -> &str {
+        &self.video_id
+    }
+
+    /// この接続で受けたレート制限（429）の集計を返す
+    pub fn rate_limit_stats(&self) -> RateLimitStats {
+        self.rate_limit_stats
+    }
+
+    /// 現在の continuation token を返す（取得位置の保存用）
+    pub fn continuation(&self) -> Option<&str> {
+        self.continuation.as_deref()
+    }
+
+    /// 直近のレスポンスから採用した continuation の種類を返す（デバッグ用）
+    pub fn continuation_kind(&self) -> Option<ContinuationKind> {
+        self.continuation_kind
+    }
+
+    /// 保存済みの continuation token から取得を再開する
+    ///
+    /// `initialize` の後に呼ぶ。保存済みの token が無効（期限切れ等）で API がエラーを返した場合は、
+    /// `initialize` で得たライブエッジの token に切り替えて取得を続ける。
+    /// チャットモードは保存済みの token から検出したものになる。
+    pub fn resume_from(&mut self, continuation: String) {
+        self.fallback_continuation = self.continuation.replace(continuation);
+        if let Some(mode) = self.detect_chat_mode() {
+            self.chat_mode = mode;
         }
     }
 
+    /// 配信が終了したと判定されたかを返す
+    ///
+    /// 一時的な欠落で誤検出しないよう、continuation が連続して
+    /// 返されなかった場合のみ終了とみなす。
+    pub fn is_stream_ended(&self) -> bool {
+        self.missing_continuation_count >= STREAM_END_MISSING_CONTINUATION_THRESHOLD
+    }
+
+    /// 配信終了を知らせるエンゲージメントメッセージ（「配信は終了しました」等）を受信したかを返す
+    pub fn has_stream_end_notice(&self) -> bool {
+        self.stream_end_notice
+    }
+
+    /// 削除アクションで届いたメッセージ ID を取り出す（取り出した分はクリアされる）
+    pub fn take_deleted_ids(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.deleted_ids)
+    }
+
+    /// この接続で受けた未対応のアクション・アイテムの件数を返す
+    pub fn unknown_action_count(&self) -> u64 {
+        self.unknown_action_count
+    }
+
+    /// 未対応のアクション・アイテムを取り出す（取り出した分はクリアされる）
+    ///
+    /// 取り出されないまま [`MAX_PENDING_UNKNOWN_ACTIONS`] 件を超えた分は件数だけ数える。
+    pub fn take_unknown_actions(&mut self) -> Vec<UnknownAction> {
+        std::mem::take(&mut self.pending_unknown_actions)
+    }
+
     /// 認証 cookie を設定する
     pub fn set_auth(&mut self, cookies: YouTubeCookies) {
-        self.auth_cookies = Some(cookies);
+        self.set_credentials(ApiCredentials::Cookies(cookies));
+    }
+
+    /// 認証情報（cookie または OAuth トークン）を設定する
+    pub fn set_credentials(&mut self, credentials: ApiCredentials) {
+        self.auth = Some(credentials);
     }
 
     /// チャットモードを設定し、continuation token のバイナリデータを変更する。
@@ -110,7 +305,7 @@ impl InnerTubeClient {
         tracing::info!(
             "initialize: video_id={}, has_auth={}",
             self.video_id,
-            self.auth_cookies.is_some()
+            self.auth.is_some()
         );
 
         // Step 1: ウォッチページを取得する（公開配信は cookie なしで可能）
@@ -120,18 +315,28 @@ impl InnerTubeClient {
             self.video_id
         );
 
-        let mut request = self.http_client.get(&page_url).header(
-            "User-Agent",
-            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
-        );
-
-        // ページ取得時は Cookie ヘッダーのみ送信（SAPISIDHASH Authorization は不要）
-        if let Some(cookies) = &self.auth_cookies {
-            request = request.header("Cookie", cookies.to_cookie_string());
-        }
+        let response =
+            retry::send_with_retry(&self.retry_config, &mut self.rate_limit_stats, || {
+                let request = self
+                    .http_client
+                    .get(&page_url)
+                    .header("User-Agent", &self.context.user_agent);
 
-        let response = request.send().await?;
+                // ページ取得時は Cookie ヘッダーのみ送信（SAPISIDHASH Authorization は不要）
+                match &self.auth {
+                    Some(ApiCredentials::Cookies(cookies)) => {
+                        request.header("Cookie", cookies.to_cookie_string())
+                    }
+                    _ => request,
+                }
+            })
+            .await
+            .map_err(|e| attribute_to_proxy(e, self.proxy.as_deref()))?;
+        check_proxy_response(&response, self.proxy.as_deref())?;
         let html = response.text().await?;
+        // continuation が得られなかった場合に、メンバー限定かどうかを判定するために控える
+        let members_only = metadata::extract_yt_initial_player_response(&html)
+            .and_then(|player| access::watch_page_denial(&player));
 
         if let Some(data) = initial_data::extract_yt_initial_data(&html) {
             let has_chat = data
@@ -161,7 +366,7 @@ impl InnerTubeClient {
 
         // Step 2: ウォッチページから continuation token が得られず、認証がある場合は
         // InnerTube API を試みる（メンバー限定配信でページ cookie が不十分な場合に必要）
-        if self.continuation.is_none() && self.auth_cookies.is_some() {
+        if self.continuation.is_none() && self.auth.is_some() {
             tracing::info!(
                 "Watch page did not return continuation token, trying InnerTube API fallback..."
             );
@@ -169,8 +374,8 @@ impl InnerTubeClient {
                 &self.http_client,
                 &self.video_id,
                 &self.api_key,
-                &self.client_version,
-                &self.auth_cookies,
+                &self.context,
+                &self.auth,
                 &mut self.broadcaster_channel_id,
                 &mut self.broadcaster_name,
                 &mut self.stream_title,
@@ -188,6 +393,15 @@ impl InnerTubeClient {
             }
         }
 
+        if let Some(denial) = members_only.filter(|_| self.continuation.is_none()) {
+            tracing::warn!(
+                "メンバー限定配信へのアクセスを拒否: video_id={} reason={:?}",
+                self.video_id,
+                denial.reason
+            );
+            return Err(denial.into());
+        }
+
         Ok(ConnectionStatus {
             is_connected: self.continuation.is_some(),
             stream_title: self.stream_title.clone(),
@@ -216,42 +430,165 @@ impl InnerTubeClient {
             .as_ref()
             .ok_or_else(|| anyhow!("No continuation token"))?;
 
-        let request_body =
-            client::build_request_body(&self.video_id, continuation, &self.client_version);
+        let request_body = client::build_request_body(&self.video_id, continuation, &self.context);
         let url = format!(
             "{}?key={}&prettyPrint=false",
             client::get_innertube_api_url(),
             self.api_key
         );
 
-        let mut request = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header(
-                "User-Agent",
-                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
-            );
+        let response =
+            retry::send_with_retry(&self.retry_config, &mut self.rate_limit_stats, || {
+                // リトライの試行ごとに呼ばれる
+                if let Some(observer) = &self.attempt_observer {
+                    observer();
+                }
+                let mut request = self
+                    .http_client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .header("User-Agent", &self.context.user_agent);
 
-        if let Some(cookies) = &self.auth_cookies {
-            let headers = super::auth::build_auth_headers(cookies);
-            for (key, value) in headers {
-                request = request.header(&key, &value);
-            }
-        }
+                // SAPISIDHASH はタイムスタンプを含むため送信ごとに生成する
+                if let Some(credentials) = &self.auth {
+                    let headers = super::auth::build_credential_headers(credentials);
+                    for (key, value) in headers {
+                        request = request.header(&key, &value);
+                    }
+                }
 
-        let response = request.json(&request_body).send().await?;
+                request.json(&request_body)
+            })
+            .await
+            .map_err(|e| attribute_to_proxy(e, self.proxy.as_deref()))?;
+        check_proxy_response(&response, self.proxy.as_deref())?;
         let raw_json = response.text().await?;
         let data: serde_json::Value = serde_json::from_str(&raw_json)?;
-
-        if let Some(new_continuation) = client::extract_continuation(&data) {
-            self.continuation = Some(new_continuation);
+        // 権限エラーは continuation の欠落（配信終了の判定）として扱わない
+        if let Some(denial) = access::live_chat_denial(&data) {
+            return Err(denial.into());
         }
 
-        let messages = chat_parser::parse_chat_actions(&data);
+        let messages = self.process_response(&data);
         Ok((messages, raw_json))
     }
 
+    /// レスポンスから continuation を取り込み、未受信のチャットメッセージを返す
+    ///
+    /// ID が空のメッセージは重複判定できないためそのまま返す。
+    fn process_response(&mut self, data: &serde_json::Value) -> Vec<ChatMessage> {
+        self.update_continuation(data);
+        if chat_parser::has_stream_end_notice(data) {
+            self.stream_end_notice = true;
+        }
+        self.deleted_ids.extend(chat_parser::deleted_item_ids(data));
+        self.record_unknown_actions(data);
+
+        chat_parser::parse_chat_actions(data)
+            .into_iter()
+            .filter(|msg| msg.id.is_empty() || self.dedup.insert(&msg.id))
+            .collect()
+    }
+
+    /// 未対応のアクション・アイテムを数え、初めて見た種類だけ debug ログに出す
+    fn record_unknown_actions(&mut self, data: &serde_json::Value) {
+        for unknown in chat_parser::unknown_actions(data) {
+            self.unknown_action_count += 1;
+            if self.logged_unknown_kinds.insert(unknown.kind.clone()) {
+                tracing::debug!(
+                    "未対応のアクションを無視: video_id={} kind={}",
+                    self.video_id,
+                    unknown.kind
+                );
+            }
+            if self.pending_unknown_actions.len() < MAX_PENDING_UNKNOWN_ACTIONS {
+                self.pending_unknown_actions.push(unknown);
+            }
+        }
+    }
+
+    /// チャットメッセージを非同期ストリームとして取得する
+    ///
+    /// continuation token の引き継ぎはクライアント内部で行い、各レスポンスの
+    /// `timeoutMs`（1〜10 秒に丸める。なければ 1,500ms）だけ待機してから次を取得する。
+    /// 取得エラーはストリームの要素として返し、ポーリングは継続する。
+    /// 配信終了と判定された場合（`is_stream_ended` / `has_stream_end_notice`）と continuation token がない場合は
+    /// ストリームを終了する。
+    pub fn message_stream(self) -> impl Stream<Item = Result<Vec<ChatMessage>>> {
+        futures_util::stream::unfold((self, false), |(mut client, started)| async move {
+            if started {
+                if client.continuation.is_none()
+                    || client.is_stream_ended()
+                    || client.has_stream_end_notice()
+                {
+                    return None;
+                }
+                tokio::time::sleep(client.next_poll_delay()).await;
+            }
+            let item = client.fetch_messages().await;
+            Some((item, (client, true)))
+        })
+    }
+
+    /// 次回ポーリングまでの待機時間
+    ///
+    /// ポーリング間隔の指定があれば [`MIN_POLL_INTERVAL_OVERRIDE`]〜[`MAX_POLL_INTERVAL_OVERRIDE`]
+    /// に丸めてそれを返す。なければ直近のレスポンスの `timeoutMs` を
+    /// [`MIN_POLL_INTERVAL`]〜[`MAX_POLL_INTERVAL`] に丸めて返す。
+    /// `timeoutMs` もなければ [`DEFAULT_POLL_INTERVAL`]。
+    pub fn next_poll_delay(&self) -> Duration {
+        if let Some(interval) = self.poll_interval_override {
+            return interval.clamp(MIN_POLL_INTERVAL_OVERRIDE, MAX_POLL_INTERVAL_OVERRIDE);
+        }
+        self.poll_timeout_ms
+            .map(|ms| Duration::from_millis(ms).clamp(MIN_POLL_INTERVAL, MAX_POLL_INTERVAL))
+            .unwrap_or(DEFAULT_POLL_INTERVAL)
+    }
+
+    /// チャット取得のリクエストを送るたびに呼ぶコールバックを設定する（`None` で解除）
+    ///
+    /// リトライ中も進行していることをウォッチドッグに伝えるために使う。
+    pub fn set_attempt_observer(&mut self, observer: Option<AttemptObserver>) {
+        self.attempt_observer = observer;
+    }
+
+    /// サーバーの `timeoutMs` の代わりに使うポーリング間隔を設定する（`None` で解除）
+    pub fn set_poll_interval_override(&mut self, interval: Option<Duration>) {
+        self.poll_interval_override = interval;
+    }
+
+    /// レスポンスから次の continuation を取り込み、配信終了判定を更新する
+    fn update_continuation(&mut self, data: &serde_json::Value) {
+        let next = client::get_next_continuation_with_timeout(data);
+        self.poll_timeout_ms = next.as_ref().and_then(|meta| meta.timeout_ms);
+        if data.get("error").is_some() {
+            // 保存済み continuation が受け付けられなかった場合はライブエッジから取り直す
+            if let Some(fallback) = self.fallback_continuation.take() {
+                tracing::warn!(
+                    "Saved continuation was rejected, falling back to live edge: video_id={}",
+                    self.video_id
+                );
+                self.continuation = Some(fallback);
+            }
+        } else if let Some(meta) = next {
+            if self.continuation_kind != Some(meta.kind) {
+                tracing::debug!(
+                    "Continuation kind: {:?} -> {:?} (video_id={})",
+                    self.continuation_kind,
+                    meta.kind,
+                    self.video_id
+                );
+                self.continuation_kind = Some(meta.kind);
+            }
+            self.continuation = Some(meta.token);
+            self.missing_continuation_count = 0;
+            self.fallback_continuation = None;
+        } else {
+            // エラーレスポンス以外で continuation がない場合は配信終了の兆候
+            self.missing_continuation_count += 1;
+        }
+    }
+
     /// 現在の接続状態を返す
     pub fn status(&self) -> ConnectionStatus {
         ConnectionStatus {
@@ -269,6 +606,7 @@ impl InnerTubeClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_http::{ReceivedRequest, json_response, serve};
 
     #[test]
     fn test_set_chat_mode_without_continuation() {
@@ -363,4 +701,576 @@ mod tests {
 
         assert_eq!(client.detect_chat_mode(), Some(ChatMode::AllChat));
     }
+
+    #[test]
+    fn test_stream_ended_after_consecutive_missing_continuations() {
+        // continuation が連続して欠落した場合のみ配信終了と判定すること
+        let mut client = InnerTubeClient::new("test_video");
+        let ended_response = serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": [] } }
+        });
+
+        client.update_continuation(&ended_response);
+        client.update_continuation(&ended_response);
+        assert!(!client.is_stream_ended());
+
+        client.update_continuation(&ended_response);
+        assert!(client.is_stream_ended());
+    }
+
+    #[test]
+    fn test_stream_ended_counter_resets_on_continuation() {
+        // continuation が返されたら欠落カウンターはリセットされること
+        let mut client = InnerTubeClient::new("test_video");
+        let ended_response = serde_json::json!({});
+        let live_response = serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": {
+                    "continuations": [{
+                        "timedContinuationData": { "continuation": "next_token" }
+                    }]
+                }
+            }
+        });
+
+        client.update_continuation(&ended_response);
+        client.update_continuation(&ended_response);
+        client.update_continuation(&live_response);
+        client.update_continuation(&ended_response);
+
+        assert!(!client.is_stream_ended());
+        assert_eq!(client.continuation.as_deref(), Some("next_token"));
+    }
+
+    #[test]
+    fn test_resume_from_uses_saved_continuation() {
+        // 保存済み continuation で取得を始め、成功したらライブエッジの token は破棄すること
+        let mut client = InnerTubeClient::new("test_video");
+        client.continuation = Some("live_edge".to_string());
+        client.resume_from("saved".to_string());
+        assert_eq!(client.continuation(), Some("saved"));
+
+        let live_response = serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": {
+                    "continuations": [{
+                        "timedContinuationData": { "continuation": "next_token" }
+                    }]
+                }
+            }
+        });
+        client.update_continuation(&live_response);
+        assert_eq!(client.continuation(), Some("next_token"));
+
+        client.update_continuation(&serde_json::json!({ "error": { "code": 400 } }));
+        assert_eq!(client.continuation(), Some("next_token"));
+    }
+
+    #[test]
+    fn test_resume_from_falls_back_when_saved_continuation_is_rejected() {
+        // 保存済み continuation がエラーになったらライブエッジの token に切り替えること
+        let mut client = InnerTubeClient::new("test_video");
+        client.continuation = Some("live_edge".to_string());
+        client.resume_from("expired".to_string());
+
+        client.update_continuation(&serde_json::json!({ "error": { "code": 400 } }));
+        assert_eq!(client.continuation(), Some("live_edge"));
+        assert!(!client.is_stream_ended());
+    }
+
+    #[test]
+    fn test_next_poll_delay_uses_timeout_ms() {
+        // timeoutMs があればその値、なければデフォルト間隔で待機すること
+        let mut client = InnerTubeClient::new("test_video");
+        assert_eq!(client.next_poll_delay(), DEFAULT_POLL_INTERVAL);
+
+        client.update_continuation(&serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": {
+                    "continuations": [{
+                        "invalidationContinuationData": {
+                            "continuation": "next_token",
+                            "timeoutMs": 5000
+                        }
+                    }]
+                }
+            }
+        }));
+        assert_eq!(client.next_poll_delay(), Duration::from_millis(5000));
+
+        client.update_continuation(&serde_json::json!({}));
+        assert_eq!(client.next_poll_delay(), DEFAULT_POLL_INTERVAL);
+    }
+
+    #[test]
+    fn test_stream_end_notice_is_remembered() {
+        // 配信終了のエンゲージメントメッセージを受信したら以降も終了通知ありとして扱うこと
+        let mut client = InnerTubeClient::new("test_video");
+        client.process_response(&serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": {
+                    "continuations": [{
+                        "invalidationContinuationData": { "continuation": "next" }
+                    }],
+                    "actions": [{
+                        "addChatItemAction": {
+                            "item": {
+                                "liveChatViewerEngagementMessageRenderer": {
+                                    "message": { "runs": [{ "text": "This live stream has ended." }] }
+                                }
+                            }
+                        }
+                    }]
+                }
+            }
+        }));
+        assert!(client.has_stream_end_notice());
+        assert!(!client.is_stream_ended());
+
+        client.process_response(&serde_json::json!({}));
+        assert!(client.has_stream_end_notice());
+    }
+
+    #[test]
+    fn test_deleted_ids_are_collected_until_taken() {
+        let mut client = InnerTubeClient::new("test_video");
+        let response = serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": [
+                { "markChatItemAsDeletedAction": { "targetItemId": "msg_1" } }
+            ]}}
+        });
+        client.process_response(&response);
+        client.process_response(&response);
+        assert_eq!(client.take_deleted_ids(), ["msg_1", "msg_1"]);
+        assert!(client.take_deleted_ids().is_empty());
+    }
+
+    #[test]
+    fn test_unknown_actions_are_counted_and_kept_until_taken() {
+        let mut client = InnerTubeClient::new("test_video");
+        let response = serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": [
+                { "newShinyFeatureAction": { "foo": 1 } },
+                { "addChatItemAction": { "item": { "liveChatNewRenderer": {} } } },
+                { "addChatItemAction": { "item": {
+                    "liveChatViewerEngagementMessageRenderer": { "id": "engagement" }
+                }}}
+            ]}}
+        });
+        assert!(client.process_response(&response).is_empty());
+        assert_eq!(client.unknown_action_count(), 2);
+
+        client.process_response(&response);
+        assert_eq!(client.unknown_action_count(), 4);
+        assert_eq!(client.logged_unknown_kinds.len(), 2);
+        let taken = client.take_unknown_actions();
+        assert_eq!(taken.len(), 4);
+        assert_eq!(taken[0].kind, "newShinyFeatureAction");
+        assert!(client.take_unknown_actions().is_empty());
+        assert_eq!(client.unknown_action_count(), 4);
+
+        // 取り出されなければ上限までしか保持しない
+        for _ in 0..MAX_PENDING_UNKNOWN_ACTIONS {
+            client.process_response(&response);
+        }
+        assert_eq!(
+            client.take_unknown_actions().len(),
+            MAX_PENDING_UNKNOWN_ACTIONS
+        );
+        assert_eq!(
+            client.unknown_action_count(),
+            4 + 2 * MAX_PENDING_UNKNOWN_ACTIONS as u64
+        );
+    }
+
+    #[test]
+    fn test_next_poll_delay_clamps_timeout_ms() {
+        // 極端な timeoutMs は最短・最長の範囲に丸めること
+        let mut client = InnerTubeClient::new("test_video");
+        client.poll_timeout_ms = Some(0);
+        assert_eq!(client.next_poll_delay(), MIN_POLL_INTERVAL);
+        client.poll_timeout_ms = Some(60_000);
+        assert_eq!(client.next_poll_delay(), MAX_POLL_INTERVAL);
+    }
+
+    #[test]
+    fn test_poll_interval_override_takes_precedence() {
+        // 指定した間隔は timeoutMs より優先し、指定用の範囲に丸めること
+        let mut client = InnerTubeClient::new("test_video");
+        client.poll_timeout_ms = Some(5000);
+        client.set_poll_interval_override(Some(Duration::from_millis(800)));
+        assert_eq!(client.next_poll_delay(), Duration::from_millis(800));
+
+        client.set_poll_interval_override(Some(Duration::from_millis(100)));
+        assert_eq!(client.next_poll_delay(), MIN_POLL_INTERVAL_OVERRIDE);
+        client.set_poll_interval_override(Some(Duration::from_secs(120)));
+        assert_eq!(client.next_poll_delay(), MAX_POLL_INTERVAL_OVERRIDE);
+
+        // 解除すると timeoutMs に戻る
+        client.set_poll_interval_override(None);
+        assert_eq!(client.next_poll_delay(), Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_get_next_continuation_with_timeout() {
+        // continuation の種類ごとにトークンと timeoutMs を取り出すこと
+        let response = |kind: &str, data: serde_json::Value| {
+            serde_json::json!({
+                "continuationContents": {
+                    "liveChatContinuation": { "continuations": [{ kind: data }] }
+                }
+            })
+        };
+
+        let timed = response(
+            "timedContinuationData",
+            serde_json::json!({ "continuation": "timed", "timeoutMs": 8000 }),
+        );
+        assert_eq!(
+            get_next_continuation_with_timeout(&timed),
+            Some(ContinuationMeta {
+                kind: ContinuationKind::Timed,
+                token: "timed".to_string(),
+                timeout_ms: Some(8000),
+            })
+        );
+
+        let reload = response(
+            "reloadContinuationData",
+            serde_json::json!({ "continuation": "reload" }),
+        );
+        assert_eq!(
+            get_next_continuation_with_timeout(&reload),
+            Some(ContinuationMeta {
+                kind: ContinuationKind::Reload,
+                token: "reload".to_string(),
+                timeout_ms: None,
+            })
+        );
+
+        assert_eq!(
+            get_next_continuation_with_timeout(&serde_json::json!({})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_continuation_prefers_polling_kinds_in_live_fixture() {
+        // reload と player seek が先に並んでいても invalidation を採用すること
+        let data: serde_json::Value = serde_json::from_str(include_str!(
+            "fixtures/get_live_chat_mixed_continuations.json"
+        ))
+        .unwrap();
+
+        let meta = get_next_continuation_with_timeout(&data).unwrap();
+        assert_eq!(meta.kind, ContinuationKind::Invalidation);
+        assert_eq!(meta.token, "0ofMyANqGlhDaWtxSndvWVZVTlRTak0w");
+        assert_eq!(meta.timeout_ms, Some(10000));
+
+        let mut client = InnerTubeClient::new("test_video");
+        client.update_continuation(&data);
+        assert_eq!(
+            client.continuation_kind(),
+            Some(ContinuationKind::Invalidation)
+        );
+        assert_eq!(
+            client.continuation(),
+            Some("0ofMyANqGlhDaWtxSndvWVZVTlRTak0w")
+        );
+    }
+
+    #[test]
+    fn test_next_continuation_falls_back_to_reload_after_mode_switch() {
+        // ポーリング用の continuation がなければ reload に切り替え、次のレスポンスで戻ること
+        let data: serde_json::Value =
+            serde_json::from_str(include_str!("fixtures/get_live_chat_reload_only.json")).unwrap();
+
+        let meta = get_next_continuation_with_timeout(&data).unwrap();
+        assert_eq!(meta.kind, ContinuationKind::Reload);
+        assert_eq!(meta.timeout_ms, None);
+
+        let mut client = InnerTubeClient::new("test_video");
+        client.update_continuation(&data);
+        assert_eq!(client.continuation_kind(), Some(ContinuationKind::Reload));
+        assert_eq!(client.next_poll_delay(), DEFAULT_POLL_INTERVAL);
+        assert!(!client.is_stream_ended());
+
+        client.update_continuation(
+            &serde_json::from_str(include_str!(
+                "fixtures/get_live_chat_mixed_continuations.json"
+            ))
+            .unwrap(),
+        );
+        assert_eq!(
+            client.continuation_kind(),
+            Some(ContinuationKind::Invalidation)
+        );
+    }
+
+    #[test]
+    fn test_next_continuation_ignores_unusable_kinds() {
+        // 次回ポーリングに使えない種類しかなければ continuation なしとして扱うこと
+        let data = serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": {
+                    "continuations": [{
+                        "playerSeekContinuationData": { "continuation": "seek" }
+                    }]
+                }
+            }
+        });
+        assert_eq!(get_next_continuation_with_timeout(&data), None);
+    }
+
+    #[tokio::test]
+    async fn test_message_stream_without_continuation_yields_error_and_ends() {
+        // continuation token がない場合はエラーを1件返してストリームを終了すること
+        use futures_util::StreamExt;
+
+        let client = InnerTubeClient::new("test_video");
+        let items: Vec<_> = client.message_stream().collect().await;
+
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+
+    /// 受け付けた接続に、リクエストを読み切ってから順に `(ステータス, JSON)` を返すサーバー
+    async fn serve_json(
+        responses: Vec<(&'static str, serde_json::Value)>,
+    ) -> (
+        std::net::SocketAddr,
+        tokio::sync::mpsc::UnboundedReceiver<ReceivedRequest>,
+    ) {
+        let responses = responses
+            .into_iter()
+            .map(|(status, body)| json_response(status, &body.to_string()));
+        serve(responses).await
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_message_stream_follows_continuations_until_stream_end() {
+        // continuation を引き継いでポーリングし、配信終了判定でストリームを終了すること
+        use futures_util::StreamExt;
+
+        let responses = vec![
+            serde_json::json!({
+                "continuationContents": {
+                    "liveChatContinuation": {
+                        "continuations": [{
+                            "timedContinuationData": { "continuation": "token_2", "timeoutMs": 1 }
+                        }]
+                    }
+                }
+            }),
+            serde_json::json!({ "error": { "code": 503 } }),
+            serde_json::json!({}),
+            serde_json::json!({}),
+            serde_json::json!({}),
+        ];
+        let (addr, _) =
+            serve_json(responses.into_iter().map(|body| ("200 OK", body)).collect()).await;
+
+        unsafe { std::env::set_var("LISCOV_YOUTUBE_BASE_URL", format!("http://{}", addr)) };
+        let mut client =
+            InnerTubeClient::new("test_video").with_retry_config(RetryConfig::disabled());
+        client.continuation = Some("token_1".to_string());
+        client.poll_timeout_ms = Some(1);
+
+        let items: Vec<_> = tokio::time::timeout(
+            Duration::from_secs(30),
+            client.message_stream().collect::<Vec<_>>(),
+        )
+        .await
+        .unwrap();
+        unsafe { std::env::remove_var("LISCOV_YOUTUBE_BASE_URL") };
+
+        // 継続レスポンス + エラーレスポンス + continuation 欠落3回で終了
+        assert_eq!(items.len(), 5);
+        assert!(items.iter().all(|item| item.is_ok()));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_attempt_observer_is_called_for_each_retry() {
+        let (addr, _) = serve_json(vec![
+            ("503 Service Unavailable", serde_json::json!({})),
+            ("200 OK", serde_json::json!({})),
+        ])
+        .await;
+
+        unsafe { std::env::set_var("LISCOV_YOUTUBE_BASE_URL", format!("http://{}", addr)) };
+        let mut client = InnerTubeClient::new("test_video").with_retry_config(RetryConfig {
+            max_retries: 1,
+            base_delay_ms: 1,
+            max_delay_ms: 1,
+            jitter: false,
+        });
+        client.continuation = Some("token_1".to_string());
+        let attempts = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        client.set_attempt_observer(Some(Arc::new({
+            let attempts = Arc::clone(&attempts);
+            move || {
+                attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+        })));
+        let result = client.fetch_messages().await;
+        unsafe { std::env::remove_var("LISCOV_YOUTUBE_BASE_URL") };
+
+        result.unwrap();
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_fetch_permission_denied_is_members_only() {
+        // 権限エラーは continuation の欠落ではなくメンバー限定のエラーにすること
+        let denial: serde_json::Value =
+            serde_json::from_str(include_str!("fixtures/get_live_chat_members_only.json")).unwrap();
+        let (addr, _) = serve_json(vec![("403 Forbidden", denial)]).await;
+
+        unsafe { std::env::set_var("LISCOV_YOUTUBE_BASE_URL", format!("http://{}", addr)) };
+        let mut client =
+            InnerTubeClient::new("test_video").with_retry_config(RetryConfig::disabled());
+        client.continuation = Some("token_1".to_string());
+        let result = client.fetch_messages().await;
+        unsafe { std::env::remove_var("LISCOV_YOUTUBE_BASE_URL") };
+
+        let error = result.unwrap_err();
+        assert!(is_members_only(&error), "unexpected error: {}", error);
+        assert_eq!(client.continuation(), Some("token_1"));
+        assert!(!client.is_stream_ended());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_custom_context_is_sent_in_request() {
+        // 設定したクライアント情報がリクエストボディと User-Agent に反映されること
+        let (addr, mut requests) = serve_json(vec![("200 OK", serde_json::json!({}))]).await;
+
+        unsafe { std::env::set_var("LISCOV_YOUTUBE_BASE_URL", format!("http://{}", addr)) };
+        let context = InnerTubeContext {
+            client_version: "2.20990101.01.00".to_string(),
+            user_agent: "liscov-test/1.0".to_string(),
+            hl: "ja".to_string(),
+            ..InnerTubeContext::default()
+        };
+        let mut client = InnerTubeClient::new("test_video")
+            .with_retry_config(RetryConfig::disabled())
+            .with_context(context);
+        client.continuation = Some("token_1".to_string());
+        let result = client.fetch_messages().await;
+        unsafe { std::env::remove_var("LISCOV_YOUTUBE_BASE_URL") };
+        result.unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.header("user-agent"), Some("liscov-test/1.0"));
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            body["context"]["client"],
+            serde_json::json!({
+                "clientName": "WEB",
+                "clientVersion": "2.20990101.01.00",
+                "gl": "US",
+                "hl": "ja"
+            })
+        );
+        assert_eq!(body["continuation"], "token_1");
+    }
+
+    #[test]
+    fn test_context_validation() {
+        assert!(InnerTubeContext::default().validate().is_ok());
+
+        let with = |edit: fn(&mut InnerTubeContext)| {
+            let mut context = InnerTubeContext::default();
+            edit(&mut context);
+            context.validate()
+        };
+        assert!(with(|c| c.client_version = "2.20250301.01.00".to_string()).is_ok());
+        assert!(with(|c| c.client_name = " ".to_string()).is_err());
+        assert!(with(|c| c.hl = String::new()).is_err());
+        for version in ["2", "2..1", "2.2024a", "v2.1"] {
+            let context = InnerTubeContext {
+                client_version: version.to_string(),
+                ..InnerTubeContext::default()
+            };
+            assert!(
+                context.validate().is_err(),
+                "{} should be rejected",
+                version
+            );
+        }
+        assert!(with(|c| c.user_agent = "agent\r\nX-Injected: 1".to_string()).is_err());
+    }
+
+    fn text_message_response(ids: &[&str]) -> serde_json::Value {
+        let actions: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "addChatItemAction": {
+                        "item": {
+                            "liveChatTextMessageRenderer": {
+                                "id": id,
+                                "timestampUsec": "1234567890000000",
+                                "authorName": {"simpleText": "User"},
+                                "authorExternalChannelId": "UC_user",
+                                "message": {"runs": [{"text": "Hello"}]}
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "continuationContents": { "liveChatContinuation": { "actions": actions } }
+        })
+    }
+
+    #[test]
+    fn test_process_response_drops_resent_items() {
+        // 同じレスポンスを2回受け取ってもメッセージは1回だけ返すこと
+        let mut client = InnerTubeClient::new("test_video");
+        let response = text_message_response(&["msg_1", "msg_2"]);
+
+        let first = client.process_response(&response);
+        let second = client.process_response(&response);
+
+        assert_eq!(
+            first.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            vec!["msg_1", "msg_2"]
+        );
+        assert!(second.is_empty());
+
+        // 一部だけ再送された場合は新しいアイテムのみ返すこと
+        let third = client.process_response(&text_message_response(&["msg_2", "msg_3"]));
+        assert_eq!(
+            third.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            vec!["msg_3"]
+        );
+    }
+
+    #[test]
+    fn test_process_response_without_dedup() {
+        // 容量 0 では重複除去しないこと
+        let mut client = InnerTubeClient::new("test_video").with_dedup_capacity(0);
+        let response = text_message_response(&["msg_1"]);
+
+        assert_eq!(client.process_response(&response).len(), 1);
+        assert_eq!(client.process_response(&response).len(), 1);
+    }
+
+    #[test]
+    fn test_stream_ended_ignores_error_responses() {
+        // エラーレスポンスは配信終了の判定に数えないこと
+        let mut client = InnerTubeClient::new("test_video");
+        let error_response = serde_json::json!({ "error": { "code": 503 } });
+
+        for _ in 0..5 {
+            client.update_continuation(&error_response);
+        }
+
+        assert!(!client.is_stream_ended());
+    }
 }
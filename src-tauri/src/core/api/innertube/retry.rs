@@ -0,0 +1,502 @@
+//! HTTP リクエストのリトライ（指数バックオフ + フルジッター）
+//!
+//! 配信が混雑すると InnerTube API が断続的に 429 / 503 を返すため、
+//! 一時的なエラーは待機してから再送する。Discord の Webhook への投稿でも使う。
+
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::time::Duration;
+
+use anyhow::Result;
+use reqwest::{RequestBuilder, Response, StatusCode, header::RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// リトライポリシー
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    /// 初回リクエスト後の最大リトライ回数
+    pub max_retries: u32,
+    /// バックオフの基準待機時間（ミリ秒）。リトライごとに倍になる
+    pub base_delay_ms: u64,
+    /// バックオフの上限待機時間（ミリ秒）
+    pub max_delay_ms: u64,
+    /// フルジッター（0〜計算値の一様乱数）を適用するか
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// リトライしない設定
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// `retry`（0 始まり）回目のリトライ前の待機時間を計算する
+    ///
+    /// `random` は [0, 1) の値で、ジッター有効時に上限値へ掛け合わせる。
+    pub fn backoff_delay(&self, retry: u32, random: f64) -> Duration {
+        let exp = self
+            .base_delay_ms
+            .saturating_mul(1u64.checked_shl(retry).unwrap_or(u64::MAX));
+        let capped = exp.min(self.max_delay_ms);
+        let delay_ms = if self.jitter {
+            (capped as f64 * random.clamp(0.0, 1.0)) as u64
+        } else {
+            capped
+        };
+        Duration::from_millis(delay_ms)
+    }
+
+    /// `Retry-After` の待機をリトライの中で行うか（`max_delay_ms` 以下）
+    ///
+    /// 長い値（`Retry-After: 60` など）は早めに再送しても 429 が続くだけのため、
+    /// リトライせずに呼び出し元へ返し、その間隔を空けさせる。
+    pub fn waits_for_retry_after(&self, retry_after: Duration) -> bool {
+        retry_after <= Duration::from_millis(self.max_delay_ms)
+    }
+}
+
+/// レート制限（429）の集計
+///
+/// ポーリング間隔が詰めすぎかどうかをユーザーが判断できるよう、接続ごとに累計する。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct RateLimitStats {
+    /// 429 を受け取った回数
+    pub hits: u32,
+    /// 429 の後に待機した時間の合計（ミリ秒）
+    #[ts(type = "number")]
+    pub total_backoff_ms: u64,
+    /// 1 回の待機の最長時間（ミリ秒）
+    #[ts(type = "number")]
+    pub longest_wait_ms: u64,
+}
+
+impl RateLimitStats {
+    /// 429 を 1 回記録する（リトライを使い切って待機しない場合は `wait` が None）
+    pub fn record_hit(&mut self, wait: Option<Duration>) {
+        self.hits = self.hits.saturating_add(1);
+        if let Some(wait) = wait {
+            let wait_ms = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+            self.total_backoff_ms = self.total_backoff_ms.saturating_add(wait_ms);
+            self.longest_wait_ms = self.longest_wait_ms.max(wait_ms);
+        }
+    }
+}
+
+/// リトライを使い切っても 429 / 5xx が返った場合のエラー
+///
+/// `Retry-After` が `max_delay_ms` を超える 429 は、リトライせずにこのエラーで返す。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryExhausted {
+    pub status: StatusCode,
+    pub attempts: u32,
+    /// 429 の `Retry-After`（呼び出し元は次のリクエストまでこれだけ待つ）
+    pub retry_after: Option<Duration>,
+}
+
+impl std::fmt::Display for RetryExhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HTTP request returned {} (attempts: {})",
+            self.status, self.attempts
+        )?;
+        if let Some(retry_after) = self.retry_after {
+            write!(f, " — retry after {}s", retry_after.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RetryExhausted {}
+
+/// レート制限（429）でリトライを使い切ったエラーか
+pub fn is_rate_limited(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<RetryExhausted>()
+        .is_some_and(|e| e.status == StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// サーバーが 429 の `Retry-After` で求めた待機時間（`RetryExhausted` でなければ None）
+pub fn server_retry_after(error: &anyhow::Error) -> Option<Duration> {
+    error
+        .downcast_ref::<RetryExhausted>()
+        .and_then(|e| e.retry_after)
+}
+
+/// リトライ対象のステータスか（429 Too Many Requests と 5xx）
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// リトライ対象の通信エラーか（タイムアウト・接続失敗）
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect()
+}
+
+/// `Retry-After` ヘッダー（秒数形式）を待機時間に変換する
+///
+/// HTTP-date 形式は未対応のため None を返し、計算したバックオフを使用する。
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// ジッター用の [0, 1) の乱数
+///
+/// `RandomState` はインスタンスごとに異なるキーで初期化されるため、
+/// 暗号強度は不要なバックオフのばらつきに利用する。
+fn jitter_random() -> f64 {
+    let value = RandomState::new().build_hasher().finish();
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// リトライポリシーに従ってリクエストを送信する
+///
+/// `build` はリトライごとに新しいリクエストを構築する。429 / 5xx と
+/// タイムアウト・接続失敗はリトライし、429 で `Retry-After` があればその値だけ待機する。
+/// `Retry-After` が `max_delay_ms` を超える場合は早めに再送せず、その値を持たせた
+/// `RetryExhausted` を返す（待機は呼び出し元が行う）。
+/// リトライを使い切った場合は元のエラーに試行回数を付けて返す。
+/// 429 を受け取るたびに `rate_limits` へ回数と待機時間を記録する。
+pub async fn send_with_retry<F>(
+    config: &RetryConfig,
+    rate_limits: &mut RateLimitStats,
+    build: F,
+) -> Result<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut retry = 0;
+    loop {
+        let attempts = retry + 1;
+        let (retry_after, rate_limited) = match build().send().await {
+            Ok(response) if !is_retryable_status(response.status()) => return Ok(response),
+            Ok(response) => {
+                let status = response.status();
+                let rate_limited = status == StatusCode::TOO_MANY_REQUESTS;
+                let retry_after = rate_limited
+                    .then(|| response.headers().get(RETRY_AFTER))
+                    .flatten()
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_retry_after);
+                let deferred = retry_after.is_some_and(|r| !config.waits_for_retry_after(r));
+                if retry >= config.max_retries || deferred {
+                    if rate_limited {
+                        rate_limits.record_hit(None);
+                        tracing::warn!(
+                            hits = rate_limits.hits,
+                            total_backoff_ms = rate_limits.total_backoff_ms,
+                            attempts,
+                            retry_after_secs = retry_after.map(|r| r.as_secs()),
+                            "HTTP rate limit — giving up until Retry-After"
+                        );
+                    }
+                    return Err(RetryExhausted {
+                        status,
+                        attempts,
+                        retry_after,
+                    }
+                    .into());
+                }
+                tracing::warn!(
+                    "HTTP request returned {} — retrying (attempt {}/{})",
+                    status,
+                    attempts,
+                    config.max_retries + 1
+                );
+                (retry_after, rate_limited)
+            }
+            Err(e) if is_retryable_error(&e) && retry < config.max_retries => {
+                tracing::warn!(
+                    "HTTP request failed: {} — retrying (attempt {}/{})",
+                    e,
+                    attempts,
+                    config.max_retries + 1
+                );
+                (None, false)
+            }
+            Err(e) => {
+                return Err(anyhow::Error::new(e).context(format!("attempts: {}", attempts)));
+            }
+        };
+
+        let delay = match retry_after {
+            Some(retry_after) => retry_after,
+            None => config.backoff_delay(retry, jitter_random()),
+        };
+        if rate_limited {
+            rate_limits.record_hit(Some(delay));
+            tracing::warn!(
+                hits = rate_limits.hits,
+                wait_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                total_backoff_ms = rate_limits.total_backoff_ms,
+                longest_wait_ms = rate_limits.longest_wait_ms,
+                "HTTP rate limit — backing off"
+            );
+        }
+        tokio::time::sleep(delay).await;
+        retry += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{ReceivedRequest, serve};
+    use tokio::sync::mpsc;
+
+    fn no_jitter() -> RetryConfig {
+        RetryConfig {
+            max_retries: 3,
+            base_delay_ms: 1,
+            max_delay_ms: 5,
+            jitter: false,
+        }
+    }
+
+    /// 先頭 `failures` 件に `failure_response` を返し、続けて 200 を返すモックサーバー
+    ///
+    /// 受け取ったリクエストの件数は戻り値の `len()` で確認する。
+    async fn spawn_server(
+        failures: u32,
+        failure_response: &'static str,
+    ) -> (String, mpsc::UnboundedReceiver<ReceivedRequest>) {
+        // 送信は最大 4 回なので、それを超える分の失敗は用意しない
+        let responses = std::iter::repeat_n(failure_response, failures.min(8) as usize)
+            .chain(["HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"]);
+        let (addr, requests) = serve(responses).await;
+        (format!("http://{}/", addr), requests)
+    }
+
+    const TOO_MANY_REQUESTS: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    const SERVICE_UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    #[test]
+    fn default_retries_three_times() {
+        let config = RetryConfig::default();
+        assert_eq!(config.max_retries, 3);
+        assert!(config.jitter);
+    }
+
+    #[test]
+    fn backoff_delay_doubles_and_caps() {
+        let config = RetryConfig {
+            max_retries: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 1_000,
+            jitter: false,
+        };
+        assert_eq!(config.backoff_delay(0, 0.5), Duration::from_millis(100));
+        assert_eq!(config.backoff_delay(1, 0.5), Duration::from_millis(200));
+        assert_eq!(config.backoff_delay(3, 0.5), Duration::from_millis(800));
+        assert_eq!(config.backoff_delay(4, 0.5), Duration::from_millis(1_000));
+        assert_eq!(config.backoff_delay(100, 0.5), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn backoff_delay_full_jitter_scales_capped_value() {
+        let config = RetryConfig {
+            base_delay_ms: 100,
+            max_delay_ms: 1_000,
+            ..RetryConfig::default()
+        };
+        assert_eq!(config.backoff_delay(2, 0.0), Duration::ZERO);
+        assert_eq!(config.backoff_delay(2, 0.5), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn jitter_random_is_in_unit_range() {
+        for _ in 0..100 {
+            let r = jitter_random();
+            assert!((0.0..1.0).contains(&r));
+        }
+    }
+
+    #[test]
+    fn parse_retry_after_seconds() {
+        assert_eq!(parse_retry_after("2"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_retry_after(" 10 "), Some(Duration::from_secs(10)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_recovers_after_transient_errors() {
+        let (url, requests) = spawn_server(2, TOO_MANY_REQUESTS).await;
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(&no_jitter(), &mut RateLimitStats::default(), || {
+            client.get(&url)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_reports_attempts_when_exhausted() {
+        let (url, requests) = spawn_server(u32::MAX, SERVICE_UNAVAILABLE).await;
+        let client = reqwest::Client::new();
+
+        let err = send_with_retry(&no_jitter(), &mut RateLimitStats::default(), || {
+            client.get(&url)
+        })
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("503"));
+        assert!(err.to_string().contains("attempts: 4"));
+        assert!(!is_rate_limited(&err));
+        assert_eq!(requests.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_reports_rate_limit_when_exhausted() {
+        let (url, _requests) = spawn_server(u32::MAX, TOO_MANY_REQUESTS).await;
+        let client = reqwest::Client::new();
+
+        let err = send_with_retry(&no_jitter(), &mut RateLimitStats::default(), || {
+            client.get(&url)
+        })
+        .await
+        .unwrap_err();
+
+        assert!(is_rate_limited(&err));
+        assert!(is_rate_limited(&err.context("fetch_messages_with_raw")));
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_records_rate_limit_stats() {
+        let (url, _requests) = spawn_server(
+            2,
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let mut stats = RateLimitStats::default();
+
+        send_with_retry(&no_jitter(), &mut stats, || client.get(&url))
+            .await
+            .unwrap();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.total_backoff_ms, 0);
+
+        // 503 はレート制限として数えない
+        let (url, _requests) = spawn_server(1, SERVICE_UNAVAILABLE).await;
+        send_with_retry(&no_jitter(), &mut stats, || client.get(&url))
+            .await
+            .unwrap();
+        assert_eq!(stats.hits, 2);
+
+        // リトライを使い切った 429 も数える（4 回とも 429、待機は 3 回）
+        let (url, _requests) = spawn_server(u32::MAX, TOO_MANY_REQUESTS).await;
+        send_with_retry(&no_jitter(), &mut stats, || client.get(&url))
+            .await
+            .unwrap_err();
+        assert_eq!(stats.hits, 6);
+        // バックオフは 1ms → 2ms → 4ms
+        assert_eq!(stats.total_backoff_ms, 7);
+        assert_eq!(stats.longest_wait_ms, 4);
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_defers_long_retry_after_to_caller() {
+        let (url, requests) = spawn_server(
+            u32::MAX,
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 60\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let mut stats = RateLimitStats::default();
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(5),
+            send_with_retry(&no_jitter(), &mut stats, || client.get(&url)),
+        )
+        .await
+        .expect("上限を超える Retry-After は待たずに返す")
+        .unwrap_err();
+
+        // 早めの再送はしない
+        assert_eq!(requests.len(), 1);
+        assert!(is_rate_limited(&err));
+        assert_eq!(server_retry_after(&err), Some(Duration::from_secs(60)));
+        assert_eq!(
+            server_retry_after(&err.context("fetch_messages_with_raw")),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.total_backoff_ms, 0);
+    }
+
+    #[test]
+    fn waits_for_retry_after_up_to_max_delay() {
+        let config = RetryConfig::default();
+        assert!(config.waits_for_retry_after(Duration::from_secs(2)));
+        assert!(config.waits_for_retry_after(Duration::from_secs(10)));
+        assert!(!config.waits_for_retry_after(Duration::from_secs(11)));
+    }
+
+    #[test]
+    fn server_retry_after_ignores_other_errors() {
+        assert_eq!(server_retry_after(&anyhow::anyhow!("boom")), None);
+        let exhausted = RetryExhausted {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            attempts: 4,
+            retry_after: None,
+        };
+        assert_eq!(server_retry_after(&exhausted.into()), None);
+    }
+
+    #[test]
+    fn rate_limit_stats_track_total_and_longest_wait() {
+        let mut stats = RateLimitStats::default();
+        stats.record_hit(Some(Duration::from_millis(500)));
+        stats.record_hit(Some(Duration::from_secs(2)));
+        stats.record_hit(None);
+        assert_eq!(
+            stats,
+            RateLimitStats {
+                hits: 3,
+                total_backoff_ms: 2_500,
+                longest_wait_ms: 2_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_does_not_retry_client_errors() {
+        let (url, requests) = spawn_server(
+            u32::MAX,
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        )
+        .await;
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(&no_jitter(), &mut RateLimitStats::default(), || {
+            client.get(&url)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(requests.len(), 1);
+    }
+}
@@ -0,0 +1,81 @@
+//! `timestamp_usec`（UNIX エポックからのマイクロ秒の文字列）の変換
+//!
+//! 各レンダラーの `timestampUsec` を表示用の時刻・分析の時間帯・配信開始からの経過時間に
+//! 変換する処理をここにまとめ、表示と集計で同じ時刻を使うようにする。
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// `timestamp_usec` を UTC 時刻に変換する
+///
+/// 数値でない・`i64` に収まらない・表現できる日時の範囲外の場合は `None`。
+pub fn usec_to_datetime(usec: &str) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_micros(usec.parse().ok()?)
+}
+
+/// `timestamp_usec` を RFC3339 文字列（秒単位、UTC）に変換する
+///
+/// フロントエンドがローカルタイムゾーンに変換して表示する。
+pub fn usec_to_rfc3339(usec: &str) -> Option<String> {
+    usec_to_datetime(usec).map(|datetime| datetime.to_rfc3339_opts(SecondsFormat::Secs, false))
+}
+
+/// 配信開始からの経過時間を "1:23:45" / "4:05" の形式で返す
+///
+/// アーカイブのシーク位置と同じ表記。配信開始前（待機所）のメッセージは "-0:30" のように
+/// 負の経過時間で表す。`timestamp_usec` を解釈できない場合は `None`。
+pub fn usec_to_relative(usec: &str, stream_start: DateTime<Utc>) -> Option<String> {
+    let elapsed = usec_to_datetime(usec)?.signed_duration_since(stream_start);
+    let sign = if elapsed.num_seconds() < 0 { "-" } else { "" };
+    let total = elapsed.num_seconds().unsigned_abs();
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    Some(if hours > 0 {
+        format!("{}{}:{:02}:{:02}", sign, hours, minutes, seconds)
+    } else {
+        format!("{}{}:{:02}", sign, minutes, seconds)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_usec_to_datetime() {
+        let datetime = usec_to_datetime("1700000000123456").unwrap();
+        assert_eq!(datetime.timestamp(), 1_700_000_000);
+        assert_eq!(datetime.timestamp_subsec_micros(), 123_456);
+    }
+
+    #[test]
+    fn rejects_non_numeric_and_overflow() {
+        assert_eq!(usec_to_datetime(""), None);
+        assert_eq!(usec_to_datetime("12:34"), None);
+        assert_eq!(usec_to_datetime("99999999999999999999"), None);
+        // i64 に収まっても日時として表現できない値
+        assert_eq!(usec_to_datetime(&i64::MAX.to_string()), None);
+        assert_eq!(usec_to_rfc3339("abc"), None);
+    }
+
+    #[test]
+    fn formats_rfc3339_in_seconds() {
+        assert_eq!(
+            usec_to_rfc3339("1234567890999999").as_deref(),
+            Some("2009-02-13T23:31:30+00:00")
+        );
+    }
+
+    #[test]
+    fn formats_relative_time() {
+        let start = usec_to_datetime("1700000000000000").unwrap();
+        let relative = |usec: i64| usec_to_relative(&usec.to_string(), start);
+
+        assert_eq!(relative(1_700_000_000_000_000).as_deref(), Some("0:00"));
+        assert_eq!(relative(1_700_000_245_900_000).as_deref(), Some("4:05"));
+        assert_eq!(
+            relative(1_700_000_000_000_000 + 5_025_000_000).as_deref(),
+            Some("1:23:45")
+        );
+        assert_eq!(relative(1_699_999_970_000_000).as_deref(), Some("-0:30"));
+        assert_eq!(usec_to_relative("x", start), None);
+    }
+}
@@ -0,0 +1,283 @@
+//! OBS のブラウザソース向けのローカル HTTP フィード（Server-Sent Events）
+//!
+//! `GET /events` はフィルタ適用後のメッセージ、`GET /events/raw` は全メッセージを
+//! `event: message` として 1 件ずつ送る。127.0.0.1 にのみバインドする。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+/// リクエストヘッダーの最大サイズ
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// 接続を維持するためのコメントを送る間隔
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 配信待ちのメッセージ数（遅いクライアントはこれを超えた分を取りこぼす）
+const FEED_CHANNEL_CAPACITY: usize = 1024;
+
+/// フィードに流す 1 件（JSON にシリアライズ済み）
+#[derive(Debug, Clone)]
+struct FeedItem {
+    json: Arc<str>,
+    /// フィルタで非表示になるメッセージか（`/events/raw` にだけ流す）
+    filtered_out: bool,
+}
+
+/// 購読するフィード
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Feed {
+    Filtered,
+    Raw,
+}
+
+/// ローカル SSE サーバー
+pub struct LocalFeedServer {
+    port: u16,
+    tx: broadcast::Sender<FeedItem>,
+    shutdown: CancellationToken,
+}
+
+impl LocalFeedServer {
+    /// `127.0.0.1:port` で待ち受けを始める（`port = 0` なら空いているポート）
+    pub async fn start(port: u16) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        let port = listener.local_addr()?.port();
+        let (tx, _) = broadcast::channel(FEED_CHANNEL_CAPACITY);
+        let shutdown = CancellationToken::new();
+
+        let accept_tx = tx.clone();
+        let accept_shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = accept_shutdown.cancelled() => break,
+                    result = listener.accept() => match result {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            tracing::warn!("Local feed accept failed: {}", e);
+                            continue;
+                        }
+                    },
+                };
+                let rx = accept_tx.subscribe();
+                let shutdown = accept_shutdown.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, rx, shutdown).await {
+                        tracing::debug!("Local feed client closed: {}", e);
+                    }
+                });
+            }
+        });
+
+        tracing::info!("Local feed listening on http://127.0.0.1:{}/events", port);
+        Ok(Self { port, tx, shutdown })
+    }
+
+    /// 待ち受けているポート
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// 接続中のクライアント数
+    pub fn connected_clients(&self) -> usize {
+        self.tx.receiver_count()
+    }
+
+    /// メッセージを全クライアントに送る（`filtered_out` なら `/events/raw` のみ）
+    pub fn publish(&self, json: String, filtered_out: bool) {
+        // 購読者がいない場合のエラーは無視する
+        let _ = self.tx.send(FeedItem {
+            json: json.into(),
+            filtered_out,
+        });
+    }
+
+    /// 待ち受けと全クライアントの接続を終了する
+    pub fn stop(&self) {
+        self.shutdown.cancel();
+    }
+}
+
+impl Drop for LocalFeedServer {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+/// リクエスト行からフィードを選ぶ（GET 以外・未知のパスはエラー応答を返す）
+fn route(request_line: &str) -> Result<Feed, &'static str> {
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default();
+    let feed = match path.trim_end_matches('/') {
+        "/events" => Feed::Filtered,
+        "/events/raw" => Feed::Raw,
+        _ => return Err("404 Not Found"),
+    };
+    if method != "GET" {
+        return Err("405 Method Not Allowed");
+    }
+    Ok(feed)
+}
+
+/// リクエストヘッダーを空行まで読み、リクエスト行を返す
+async fn read_request_line(stream: &mut TcpStream) -> anyhow::Result<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > MAX_REQUEST_HEAD {
+            anyhow::bail!("request head too large");
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            anyhow::bail!("connection closed before request");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let head = String::from_utf8_lossy(&buf);
+    Ok(head.lines().next().unwrap_or_default().to_string())
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    mut rx: broadcast::Receiver<FeedItem>,
+    shutdown: CancellationToken,
+) -> anyhow::Result<()> {
+    let request_line = read_request_line(&mut stream).await?;
+    let feed = match route(&request_line) {
+        Ok(feed) => feed,
+        Err(status) => {
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            stream.write_all(response.as_bytes()).await?;
+            return Ok(());
+        }
+    };
+
+    // ブラウザソースは別オリジンのページから接続するため CORS を許可する
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\n\
+              Content-Type: text/event-stream\r\n\
+              Cache-Control: no-cache\r\n\
+              Connection: keep-alive\r\n\
+              Access-Control-Allow-Origin: *\r\n\r\n\
+              : connected\n\n",
+        )
+        .await?;
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+    loop {
+        let chunk = tokio::select! {
+            _ = shutdown.cancelled() => return Ok(()),
+            _ = keepalive.tick() => ": ping\n\n".to_string(),
+            item = rx.recv() => match item {
+                Ok(item) if feed == Feed::Filtered && item.filtered_out => continue,
+                Ok(item) => format!("event: message\ndata: {}\n\n", item.json),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Local feed client lagged, skipped {} messages", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+        };
+        stream.write_all(chunk.as_bytes()).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `path` に接続してレスポンスヘッダーまで読む
+    async fn connect(port: u16, path: &str) -> (TcpStream, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let head = read_until(&mut stream, ": connected\n\n").await;
+        (stream, head)
+    }
+
+    /// `marker` を受け取るまで読む
+    async fn read_until(stream: &mut TcpStream, marker: &str) -> String {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 1024];
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !String::from_utf8_lossy(&buf).contains(marker) {
+                let n = stream.read(&mut chunk).await.unwrap();
+                assert!(n > 0, "connection closed");
+                buf.extend_from_slice(&chunk[..n]);
+            }
+        })
+        .await
+        .expect("timed out");
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+
+    #[test]
+    fn route_selects_feed() {
+        assert_eq!(route("GET /events HTTP/1.1"), Ok(Feed::Filtered));
+        assert_eq!(route("GET /events/?x=1 HTTP/1.1"), Ok(Feed::Filtered));
+        assert_eq!(route("GET /events/raw HTTP/1.1"), Ok(Feed::Raw));
+        assert_eq!(
+            route("POST /events HTTP/1.1"),
+            Err("405 Method Not Allowed")
+        );
+        assert_eq!(route("GET / HTTP/1.1"), Err("404 Not Found"));
+    }
+
+    #[tokio::test]
+    async fn streams_messages_and_hides_filtered_ones() {
+        let server = LocalFeedServer::start(0).await.unwrap();
+        let (mut filtered, head) = connect(server.port(), "/events").await;
+        assert!(head.starts_with("HTTP/1.1 200 OK"));
+        assert!(head.contains("Content-Type: text/event-stream"));
+        let (mut raw, _) = connect(server.port(), "/events/raw").await;
+        assert_eq!(server.connected_clients(), 2);
+
+        server.publish(r#"{"id":"hidden"}"#.to_string(), true);
+        server.publish(r#"{"id":"shown"}"#.to_string(), false);
+
+        let received = read_until(&mut filtered, "shown").await;
+        assert!(received.contains("event: message\ndata: {\"id\":\"shown\"}\n\n"));
+        assert!(!received.contains("hidden"));
+
+        let received = read_until(&mut raw, "shown").await;
+        let hidden = received.find("hidden").unwrap();
+        assert!(hidden < received.find("shown").unwrap());
+    }
+
+    #[tokio::test]
+    async fn unknown_path_returns_404() {
+        let server = LocalFeedServer::start(0).await.unwrap();
+        let mut stream = TcpStream::connect(("127.0.0.1", server.port()))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET /favicon.ico HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+    }
+
+    #[tokio::test]
+    async fn stop_closes_client_connections() {
+        let server = LocalFeedServer::start(0).await.unwrap();
+        let (mut stream, _) = connect(server.port(), "/events").await;
+        server.stop();
+        let mut rest = Vec::new();
+        let read = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest));
+        assert!(read.await.expect("connection stays open").is_ok());
+    }
+}
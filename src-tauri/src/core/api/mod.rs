@@ -3,9 +3,15 @@
 mod auth;
 mod continuation_builder;
 mod innertube;
+#[cfg(feature = "local_server")]
+mod local_feed;
+mod proxy;
 mod websocket;
 
 pub use auth::*;
 pub use continuation_builder::*;
 pub use innertube::*;
+#[cfg(feature = "local_server")]
+pub use local_feed::*;
+pub use proxy::*;
 pub use websocket::*;
@@ -0,0 +1,406 @@
+//! キャプチャ健全性ウォッチドッグ
+//!
+//! ポーリングループが応答しないリクエストやロック待ちで停止した場合に検出し、
+//! ポーリングタスクを再起動する。
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 停止判定のチェック間隔の上限
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 停止判定しきい値の下限
+///
+/// 監視ループはリクエストの試行ごとに進行を記録するため、試行の間隔は最長で
+/// 1 回のタイムアウト（`REQUEST_TIMEOUT` = 30 秒）とバックオフの上限（10 秒）の合計になる。
+/// これより短いしきい値では応答の遅いリトライ中に停止と判定してしまう。
+pub const MIN_STALL_THRESHOLD: Duration = Duration::from_secs(45);
+
+/// 停止判定を止めたままにできる時間（しきい値の倍数）
+///
+/// 処理が DB のロック待ちなどで止まると、監視ループはキューへの追加で待たされ続ける。
+/// 再起動しても解消しないが、気付けるよう長すぎる場合は停止として通知する。
+pub const PAUSE_LIMIT_FACTOR: u32 = 3;
+
+/// 検出した停止
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stall {
+    /// 最後の進行から経過時間がしきい値を超えた（ループを再起動する）
+    Unresponsive(Duration),
+    /// 停止判定を止めたまま上限（しきい値 × `PAUSE_LIMIT_FACTOR`）を超えた（通知のみで再起動しない）
+    PausedTooLong(Duration),
+}
+
+impl Stall {
+    /// 停止していた時間
+    pub fn elapsed(&self) -> Duration {
+        match self {
+            Self::Unresponsive(elapsed) | Self::PausedTooLong(elapsed) => *elapsed,
+        }
+    }
+
+    /// ループを再起動して回復を試みるか
+    pub fn restarts(&self) -> bool {
+        matches!(self, Self::Unresponsive(_))
+    }
+}
+
+/// 停止判定を止めている区間の状態
+#[derive(Default)]
+struct PauseState {
+    /// 保持されているガードの数
+    count: usize,
+    /// 止め始めた時刻（`count` が 0 なら None）
+    since: Option<Instant>,
+    /// この区間の `PausedTooLong` を通知済みか
+    reported: bool,
+}
+
+/// 最後に成功したポーリングからの経過時間を追跡するウォッチドッグ
+///
+/// ポーリングループ側は成功のたびに `record_success` を呼び、
+/// 監視側は `wait_for_stall` で停止を待ち受ける。
+/// 処理の遅れなど、再起動しても解消しない待ち時間は `pause` で判定から外す
+/// （上限を超えて止めたままなら `Stall::PausedTooLong` を返す）。
+#[derive(Clone)]
+pub struct CaptureWatchdog {
+    last_success: Arc<Mutex<Instant>>,
+    pause: Arc<Mutex<PauseState>>,
+    threshold: Duration,
+}
+
+/// 停止判定を止めている間保持するガード
+///
+/// 破棄すると進行を記録して判定を再開する（止めていた時間は経過時間に含めない）。
+pub struct WatchdogPause {
+    watchdog: CaptureWatchdog,
+}
+
+impl Drop for WatchdogPause {
+    fn drop(&mut self) {
+        if let Ok(mut pause) = self.watchdog.pause.lock() {
+            pause.count = pause.count.saturating_sub(1);
+            if pause.count == 0 {
+                *pause = PauseState::default();
+            }
+        }
+        self.watchdog.record_success();
+    }
+}
+
+impl CaptureWatchdog {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            last_success: Arc::new(Mutex::new(Instant::now())),
+            pause: Arc::new(Mutex::new(PauseState::default())),
+            threshold,
+        }
+    }
+
+    /// ポーリング成功（またはリクエストの試行などの進行）を記録する
+    pub fn record_success(&self) {
+        if let Ok(mut last) = self.last_success.lock() {
+            *last = Instant::now();
+        }
+    }
+
+    /// 返したガードを破棄するまで停止と判定しない
+    ///
+    /// 止めたまま上限を超えると `wait_for_stall` は `Stall::PausedTooLong` を 1 回返す。
+    pub fn pause(&self) -> WatchdogPause {
+        if let Ok(mut pause) = self.pause.lock() {
+            pause.count += 1;
+            pause.since.get_or_insert_with(Instant::now);
+        }
+        WatchdogPause {
+            watchdog: self.clone(),
+        }
+    }
+
+    /// 停止判定を止めているか
+    pub fn is_paused(&self) -> bool {
+        self.pause.lock().is_ok_and(|pause| pause.count > 0)
+    }
+
+    /// 停止判定を止めたままにできる時間
+    pub fn pause_limit(&self) -> Duration {
+        self.threshold * PAUSE_LIMIT_FACTOR
+    }
+
+    /// 進行を記録しながら `duration` だけ待つ
+    ///
+    /// ポーリング間隔や `Retry-After` による意図した待機を停止と判定しないようにする。
+    pub async fn sleep_alive(&self, duration: Duration) {
+        let deadline = tokio::time::Instant::now() + duration;
+        let step = (self.threshold / 2).max(Duration::from_millis(1));
+        loop {
+            self.record_success();
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return;
+            }
+            tokio::time::sleep((deadline - now).min(step)).await;
+        }
+    }
+
+    /// 最後のポーリング成功からの経過時間
+    pub fn elapsed_since_success(&self) -> Duration {
+        self.last_success
+            .lock()
+            .map(|last| last.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// 止めている区間が上限を超えて、まだ通知していなければその長さを返す（通知済みにする）
+    fn take_overlong_pause(&self) -> Option<Duration> {
+        let mut pause = self.pause.lock().ok()?;
+        let paused = pause.since?.elapsed();
+        if pause.reported || paused < self.pause_limit() {
+            return None;
+        }
+        pause.reported = true;
+        Some(paused)
+    }
+
+    /// 停止を検出するまで待機する
+    ///
+    /// 止めている間はしきい値で判定せず、上限を超えたときだけ `Stall::PausedTooLong` を返す。
+    pub async fn wait_for_stall(&self) -> Stall {
+        let check_interval =
+            (self.threshold / 4).clamp(Duration::from_millis(1), MAX_CHECK_INTERVAL);
+        loop {
+            if self.is_paused() {
+                if let Some(paused) = self.take_overlong_pause() {
+                    return Stall::PausedTooLong(paused);
+                }
+                tokio::time::sleep(check_interval).await;
+                continue;
+            }
+            let elapsed = self.elapsed_since_success();
+            if elapsed >= self.threshold {
+                return Stall::Unresponsive(elapsed);
+            }
+            tokio::time::sleep(check_interval.min(self.threshold - elapsed)).await;
+        }
+    }
+}
+
+/// ポーリングタスクをウォッチドッグの監視下で実行する
+///
+/// `start` が返す Future が完了すればその結果を返す。停止を検出するたびに `on_stall` を呼ぶ。
+/// `Stall::Unresponsive` なら実行中の Future を破棄して `start` から新しいタスクを起動し直し、
+/// `Stall::PausedTooLong` なら通知だけしてそのまま待つ。
+pub async fn run_supervised<F, Fut, S>(
+    watchdog: &CaptureWatchdog,
+    mut start: F,
+    mut on_stall: S,
+) -> Fut::Output
+where
+    F: FnMut() -> Fut,
+    Fut: Future,
+    S: FnMut(Stall),
+{
+    loop {
+        // 起動直後に即座に停止と判定されないよう猶予をリセット
+        watchdog.record_success();
+        let task = start();
+        tokio::pin!(task);
+        loop {
+            tokio::select! {
+                output = &mut task => return output,
+                stall = watchdog.wait_for_stall() => {
+                    on_stall(stall);
+                    if stall.restarts() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::{REQUEST_TIMEOUT, RetryConfig};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn min_stall_threshold_covers_one_retry_attempt() {
+        let max_backoff = Duration::from_millis(RetryConfig::default().max_delay_ms);
+        assert!(MIN_STALL_THRESHOLD > REQUEST_TIMEOUT + max_backoff);
+    }
+
+    #[tokio::test]
+    async fn wait_for_stall_returns_after_threshold() {
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(20));
+        let stall = watchdog.wait_for_stall().await;
+        assert!(stall.restarts());
+        assert!(stall.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn record_success_resets_elapsed() {
+        let watchdog = CaptureWatchdog::new(Duration::from_secs(60));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        watchdog.record_success();
+        assert!(watchdog.elapsed_since_success() < Duration::from_millis(10));
+    }
+
+    #[tokio::test]
+    async fn paused_watchdog_does_not_stall() {
+        // しきい値（20ms）を超えても、止めたままにできる上限（60ms）までは判定しない
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(20));
+        let pause = watchdog.pause();
+        let waited =
+            tokio::time::timeout(Duration::from_millis(40), watchdog.wait_for_stall()).await;
+        assert!(waited.is_err());
+
+        // 再開すると止めていた時間は含めずにしきい値まで待つ
+        drop(pause);
+        assert!(!watchdog.is_paused());
+        assert!(watchdog.elapsed_since_success() < Duration::from_millis(20));
+        let stall = watchdog.wait_for_stall().await;
+        assert!(
+            matches!(stall, Stall::Unresponsive(elapsed) if elapsed >= Duration::from_millis(20))
+        );
+    }
+
+    #[tokio::test]
+    async fn sleep_alive_longer_than_threshold_is_not_a_stall() {
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(20));
+        let starts = AtomicU32::new(0);
+        let mut stall_count = 0;
+
+        run_supervised(
+            &watchdog,
+            || {
+                starts.fetch_add(1, Ordering::SeqCst);
+                let watchdog = watchdog.clone();
+                async move { watchdog.sleep_alive(Duration::from_millis(80)).await }
+            },
+            |_| stall_count += 1,
+        )
+        .await;
+
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        assert_eq!(stall_count, 0);
+    }
+
+    #[tokio::test]
+    async fn run_supervised_restarts_stalled_fetch() {
+        // 最初のタスクはしきい値内に応答しないフェッチを模擬し、再起動後のタスクは完了する
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(20));
+        let starts = AtomicU32::new(0);
+        let mut stalls = Vec::new();
+
+        let output = run_supervised(
+            &watchdog,
+            || {
+                let attempt = starts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        std::future::pending::<()>().await;
+                    }
+                    attempt
+                }
+            },
+            |elapsed| stalls.push(elapsed),
+        )
+        .await;
+
+        assert_eq!(output, 1);
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+        assert_eq!(stalls.len(), 1);
+        assert!(stalls[0].restarts());
+        assert!(stalls[0].elapsed() >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn run_supervised_does_not_restart_healthy_task() {
+        // 成功を記録し続けるタスクはしきい値を超えて動作しても再起動されない
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(30));
+        let starts = AtomicU32::new(0);
+        let mut stall_count = 0;
+
+        run_supervised(
+            &watchdog,
+            || {
+                starts.fetch_add(1, Ordering::SeqCst);
+                let watchdog = watchdog.clone();
+                async move {
+                    for _ in 0..10 {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        watchdog.record_success();
+                    }
+                }
+            },
+            |_| stall_count += 1,
+        )
+        .await;
+
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        assert_eq!(stall_count, 0);
+    }
+
+    #[tokio::test]
+    async fn run_supervised_does_not_restart_while_paused() {
+        // 処理の遅れで待たされている間はしきい値（30ms）を超えても上限（90ms）までは再起動しない
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(30));
+        let starts = AtomicU32::new(0);
+        let mut stall_count = 0;
+
+        run_supervised(
+            &watchdog,
+            || {
+                starts.fetch_add(1, Ordering::SeqCst);
+                let watchdog = watchdog.clone();
+                async move {
+                    let _pause = watchdog.pause();
+                    tokio::time::sleep(Duration::from_millis(60)).await;
+                }
+            },
+            |_| stall_count += 1,
+        )
+        .await;
+
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        assert_eq!(stall_count, 0);
+    }
+
+    #[tokio::test]
+    async fn run_supervised_reports_a_processor_that_never_finishes() {
+        // 処理側が止まったまま満杯のキューへの追加で待たされ続けても、上限を超えたら
+        // 再起動せずに 1 回だけ通知する
+        let watchdog = CaptureWatchdog::new(Duration::from_millis(10));
+        let (tx, _rx) = tokio::sync::mpsc::channel::<u32>(1);
+        tx.send(0).await.unwrap();
+        let starts = AtomicU32::new(0);
+        let stalls = Mutex::new(Vec::new());
+
+        let supervised = run_supervised(
+            &watchdog,
+            || {
+                starts.fetch_add(1, Ordering::SeqCst);
+                let watchdog = watchdog.clone();
+                let tx = tx.clone();
+                async move {
+                    let _pause = watchdog.pause();
+                    let _ = tx.send(1).await;
+                }
+            },
+            |stall| stalls.lock().unwrap().push(stall),
+        );
+        let finished = tokio::time::timeout(Duration::from_millis(200), supervised).await;
+
+        assert!(finished.is_err());
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        let stalls = stalls.into_inner().unwrap();
+        assert_eq!(stalls.len(), 1);
+        assert!(matches!(
+            stalls[0],
+            Stall::PausedTooLong(paused) if paused >= watchdog.pause_limit()
+        ));
+    }
+}
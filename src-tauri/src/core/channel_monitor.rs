@@ -0,0 +1,227 @@
+//! 配信終了後のチャンネル監視（次の配信への自動接続）
+//!
+//! 連続して配信するチャンネル向けに、配信終了後もチャンネルをポーリングし、
+//! 新しいライブ配信の動画 ID が見つかった時点で呼び出し元に返す。
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use tokio_util::sync::CancellationToken;
+
+use crate::core::api::{
+    ProxyConfig, ProxyError, build_http_client_with_proxy, get_youtube_base_url,
+};
+use crate::core::models::extract_video_id;
+
+/// チャンネル ID から現在ライブ中の動画 ID を解決する
+#[async_trait]
+pub trait LiveVideoResolver: Send + Sync {
+    /// ライブ中であれば動画 ID を、配信していなければ None を返す
+    async fn resolve_live_video_id(&self, channel_id: &str) -> Result<Option<String>>;
+}
+
+/// チャンネルの `/live` ページから動画 ID を解決する
+pub struct YouTubeLiveResolver {
+    http_client: Client,
+}
+
+impl YouTubeLiveResolver {
+    pub fn new() -> Self {
+        Self {
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 指定したプロキシを通して解決する（未指定なら環境変数 `LISCOV_PROXY` に従う）
+    pub fn with_proxy(proxy: &ProxyConfig) -> Result<Self, ProxyError> {
+        Ok(Self {
+            http_client: build_http_client_with_proxy(proxy)?,
+        })
+    }
+}
+
+impl Default for YouTubeLiveResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LiveVideoResolver for YouTubeLiveResolver {
+    async fn resolve_live_video_id(&self, channel_id: &str) -> Result<Option<String>> {
+        let url = format!("{}/channel/{}/live", get_youtube_base_url(), channel_id);
+        let html = self
+            .http_client
+            .get(&url)
+            .header(
+                "User-Agent",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+            )
+            .send()
+            .await?
+            .text()
+            .await?;
+        Ok(extract_live_video_id(&html))
+    }
+}
+
+/// チャンネルの `/live` ページ HTML からライブ中の動画 ID を抽出する
+///
+/// 待機所（配信予定）や通常のチャンネルページでは None を返す。
+pub fn extract_live_video_id(html: &str) -> Option<String> {
+    if !html.contains("\"isLiveNow\":true") {
+        return None;
+    }
+
+    let canonical_start = html.find("<link rel=\"canonical\" href=\"")?;
+    let rest = &html[canonical_start + "<link rel=\"canonical\" href=\"".len()..];
+    let href = &rest[..rest.find('"')?];
+    extract_video_id(href)
+}
+
+/// 配信終了後、チャンネルに新しいライブ配信が現れるまでポーリングする
+///
+/// 終了した配信（`ended_video_id`）と同じ動画 ID は新しい配信とみなさない。
+/// キャンセルされた場合は None を返す。
+pub async fn wait_for_next_live(
+    resolver: &dyn LiveVideoResolver,
+    channel_id: &str,
+    ended_video_id: &str,
+    poll_interval: Duration,
+    cancellation_token: &CancellationToken,
+) -> Option<String> {
+    tracing::info!(
+        "次の配信を待機開始 channel_id: {} interval: {:?}",
+        channel_id,
+        poll_interval
+    );
+
+    loop {
+        if cancellation_token.is_cancelled() {
+            return None;
+        }
+
+        match resolver.resolve_live_video_id(channel_id).await {
+            Ok(Some(video_id)) if video_id != ended_video_id => {
+                tracing::info!(
+                    "次の配信を検出 channel_id: {} video_id: {}",
+                    channel_id,
+                    video_id
+                );
+                return Some(video_id);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("ライブ配信の解決に失敗 channel_id: {}: {}", channel_id, e);
+            }
+        }
+
+        tokio::select! {
+            _ = cancellation_token.cancelled() => return None,
+            _ = tokio::time::sleep(poll_interval) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// 事前に用意した応答を順に返すモックリゾルバー
+    struct MockResolver {
+        responses: Mutex<VecDeque<Option<String>>>,
+        calls: Mutex<u32>,
+    }
+
+    impl MockResolver {
+        fn new(responses: Vec<Option<&str>>) -> Self {
+            Self {
+                responses: Mutex::new(
+                    responses
+                        .into_iter()
+                        .map(|r| r.map(|s| s.to_string()))
+                        .collect(),
+                ),
+                calls: Mutex::new(0),
+            }
+        }
+
+        fn calls(&self) -> u32 {
+            *self.calls.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl LiveVideoResolver for MockResolver {
+        async fn resolve_live_video_id(&self, _channel_id: &str) -> Result<Option<String>> {
+            *self.calls.lock().unwrap() += 1;
+            Ok(self.responses.lock().unwrap().pop_front().flatten())
+        }
+    }
+
+    #[test]
+    fn extract_live_video_id_from_live_page() {
+        let html = r#"<html><head><link rel="canonical" href="https://www.youtube.com/watch?v=abc123XYZ_-"></head>
+            <script>var ytInitialPlayerResponse = {"isLiveNow":true};</script></html>"#;
+        assert_eq!(extract_live_video_id(html), Some("abc123XYZ_-".to_string()));
+    }
+
+    #[test]
+    fn extract_live_video_id_not_live_returns_none() {
+        // 待機所（配信予定）は isLiveNow=false
+        let html = r#"<link rel="canonical" href="https://www.youtube.com/watch?v=upcoming01">
+            {"isLiveNow":false}"#;
+        assert_eq!(extract_live_video_id(html), None);
+    }
+
+    #[test]
+    fn extract_live_video_id_channel_page_returns_none() {
+        let html = r#"<link rel="canonical" href="https://www.youtube.com/channel/UC_test">"#;
+        assert_eq!(extract_live_video_id(html), None);
+    }
+
+    #[tokio::test]
+    async fn wait_for_next_live_returns_new_video_after_end() {
+        // 配信終了直後は旧配信・未配信が返り、新しい動画 ID が現れた時点で返すこと
+        let resolver = MockResolver::new(vec![Some("old_video"), None, Some("new_video")]);
+        let token = CancellationToken::new();
+
+        let next = wait_for_next_live(
+            &resolver,
+            "UC_channel",
+            "old_video",
+            Duration::from_millis(1),
+            &token,
+        )
+        .await;
+
+        assert_eq!(next, Some("new_video".to_string()));
+        assert_eq!(resolver.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn wait_for_next_live_stops_on_cancel() {
+        let resolver = MockResolver::new(vec![]);
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let next = wait_for_next_live(
+            &resolver,
+            "UC_channel",
+            "old_video",
+            Duration::from_millis(1),
+            &token,
+        )
+        .await;
+
+        assert_eq!(next, None);
+        assert_eq!(resolver.calls(), 0);
+    }
+}
@@ -3,24 +3,54 @@
 //! connect_to_stream コマンドから抽出された監視ロジック。
 //! コマンド層は入出力の変換と MonitoringDeps / run_monitoring_loop への委譲のみを担う。
 
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::Arc;
 use tokio::sync::{RwLock, watch};
 use tokio_util::sync::CancellationToken;
-
-use tauri::AppHandle;
-
-use crate::core::api::{InnerTubeClient, WebSocketServer};
+use ts_rs::TS;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::config::ConfigState;
+use crate::commands::engagement::EngagementState;
+use crate::commands::notifications::DiscordState;
+use crate::core::api::{
+    InnerTubeClient, WebSocketServer, fetch_video_metadata, is_members_only, is_rate_limited,
+    server_retry_after,
+};
+use crate::core::capture_watchdog::CaptureWatchdog;
+use crate::core::connection_health::{ConnectionHealthEvent, ConnectionHealthHandle};
+use crate::core::continuation_store::ContinuationStore;
+use crate::core::discord::DiscordEvent;
+use crate::core::disk_archive::ArchiveWriter;
+use crate::core::first_chatter::{FirstTimeChatterEvent, count_in_stream_comment};
+use crate::core::milestones::MilestoneTracker;
 use crate::core::models::{ChatMessage, ChatMode};
-use crate::core::raw_response::{RawResponseSaver, SaveConfig};
+use crate::core::processing_queue::{
+    ProcessingHandler, ProcessingItem, ProcessingReceiver, processing_queue, run_processing_queue,
+};
+use crate::core::raw_response::{RawResponseSaver, SaveConfig, append_unknown_actions};
+use crate::core::revenue_goal::{GoalProgress, GoalReachedEvent, GoalTracker};
+use crate::core::stream_end::{StreamEndDetector, StreamEndedEvent};
+use crate::core::transform::MessageTransforms;
 use crate::database::{self, Database};
-use crate::state::MAX_MESSAGES;
+use crate::state;
 use crate::tts::{TtsManager, TtsPriority, TtsQueueItem};
 
+/// 1 つのトランザクションでまとめて DB に書き込むメッセージ数の上限
+///
+/// メッセージごとのコミット（WAL への同期）を避けつつ、GUI への配信が遅れすぎないようにする。
+pub const DB_WRITE_BATCH_SIZE: usize = 50;
+
+/// 配信状態（メタデータ）を確認して配信終了を検出する間隔
+const LIVE_STATUS_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(120);
+
 /// 監視タスクが必要とする共有依存をまとめた構造体
 ///
 /// 複数接続間で共有されるリソース（メッセージバッファ、DB、WebSocket、TTS）を保持する。
 /// 接続固有の情報（session_id, broadcaster_id, client）は run_monitoring_loop の引数で渡す。
+#[derive(Clone)]
 pub struct MonitoringDeps {
     /// 全接続のメッセージを統合するグローバルバッファ
     pub messages: Arc<RwLock<VecDeque<ChatMessage>>>,
@@ -30,6 +60,12 @@ pub struct MonitoringDeps {
     pub websocket_server: Arc<RwLock<Option<WebSocketServer>>>,
     /// TTS マネージャー
     pub tts_manager: Arc<TtsManager>,
+    /// 取得位置（continuation token）の保存先
+    pub continuation_store: Arc<ContinuationStore>,
+    /// 取得したメッセージに適用する変換
+    pub message_transforms: Arc<std::sync::RwLock<MessageTransforms>>,
+    /// メッセージバッファから押し出したメッセージのディスクアーカイブへの書き込み口
+    pub archive_writer: Arc<ArchiveWriter>,
 }
 
 impl MonitoringDeps {
@@ -40,14 +76,71 @@ impl MonitoringDeps {
             database: Arc::clone(&state.database),
             websocket_server: Arc::clone(&state.websocket_server),
             tts_manager: Arc::clone(&state.tts_manager),
+            continuation_store: Arc::clone(&state.continuation_store),
+            message_transforms: Arc::clone(&state.message_transforms),
+            archive_writer: Arc::clone(&state.archive_writer),
+        }
+    }
+
+    /// 登録された変換を適用する（変換で破棄されたら None）
+    pub fn transform(&self, msg: ChatMessage) -> Option<ChatMessage> {
+        match self.message_transforms.read() {
+            Ok(transforms) => transforms.apply(msg),
+            Err(poisoned) => poisoned.into_inner().apply(msg),
+        }
+    }
+}
+
+/// メッセージ削除イベント（`chat:message_deleted`）のペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct MessageDeletedEvent {
+    pub connection_id: u64,
+    /// 削除されたメッセージの ID（表示・保存されていないものも含む）
+    pub message_ids: Vec<String>,
+}
+
+/// 接続の処理タスクの置き場
+///
+/// ウォッチドッグが監視ループを再起動しても前の処理タスクは積まれた分を処理し続けるため、
+/// 次の監視ループはその終了を待ってから処理タスクを起動する（同じ接続で書き込みが重ならないように）。
+#[derive(Clone, Default)]
+pub struct ProcessorSlot(Arc<tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>>);
+
+/// 置き場に残っている処理タスクの終了を待つ
+///
+/// 待っている途中で破棄されてもハンドルは残り、次の呼び出しで改めて待つ。
+async fn join_processor(slot: &mut Option<tokio::task::JoinHandle<()>>, connection_id: u64) {
+    if let Some(processor) = slot.as_mut() {
+        if let Err(e) = processor.await {
+            tracing::warn!(
+                "処理タスクが異常終了 connection_id: {}: {}",
+                connection_id,
+                e
+            );
         }
     }
+    *slot = None;
+}
+
+/// 監視ループの終了理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitoringOutcome {
+    /// CancellationToken によるキャンセル（ユーザーによる切断等）
+    Cancelled,
+    /// 配信終了を検出した
+    StreamEnded,
+    /// InnerTube クライアントが存在しない
+    ClientUnavailable,
+    /// メンバー限定配信のためチャットの取得を拒否された（ログインし直すまで解消しない）
+    MembersOnly,
 }
 
 /// チャット監視のポーリングループ全体を実行する
 ///
 /// この関数は tokio::spawn で別タスクとして起動される。
-/// ループ終了後にセッションの終了処理（end_session / update_session_stats）を行う。
+/// ループ終了後にセッションの終了処理（end_session で終了時刻と統計を確定）を行い、
+/// 終了理由を返す。
 ///
 /// # 引数
 /// - `deps` — 監視タスクが必要とする共有依存一式
@@ -60,6 +153,9 @@ impl MonitoringDeps {
 /// - `cancellation_token` — この接続のキャンセレーショントークン
 /// - `save_config` — レスポンス保存設定
 /// - `chat_mode_rx` — チャットモード変更要求を受信する watch チャネル
+/// - `watchdog` — ポーリング成功を記録するウォッチドッグ
+/// - `health` — ポーリングごとに更新する接続の健全性（段階が変わったら `chat:connection_health` を emit）
+/// - `processor_slot` — 接続の処理タスクの置き場（再起動をまたいで共有する）
 /// - `emit_gui_message` — ChatMessage を GUI 用に変換して emit するコールバック
 #[allow(clippy::too_many_arguments)]
 pub async fn run_monitoring_loop<F>(
@@ -73,41 +169,46 @@ pub async fn run_monitoring_loop<F>(
     cancellation_token: CancellationToken,
     save_config: SaveConfig,
     mut chat_mode_rx: watch::Receiver<ChatMode>,
+    watchdog: CaptureWatchdog,
+    health: ConnectionHealthHandle,
+    processor_slot: ProcessorSlot,
     emit_gui_message: F,
-) where
+) -> MonitoringOutcome
+where
     F: Fn(&AppHandle, &ChatMessage) + Send + Sync + 'static,
 {
     tracing::info!("チャット監視タスク開始 connection_id: {}", connection_id);
-    let poll_interval = std::time::Duration::from_millis(1500);
     let raw_response_saver = RawResponseSaver::new(save_config);
     let mut poll_count = 0u64;
+    let mut outcome = MonitoringOutcome::Cancelled;
 
-    // セッション開始時点のコメント数をDBから復元してカウンターを初期化
-    // 復元失敗時に silent に空マップへフォールバックすると既存コメント者も
-    // 「初回扱い」となり first_comment_only / プレフィックス機能の挙動が崩れるため、
-    // 失敗時は warn ログで副作用を明示する (provenance: branch-owned)
-    let mut in_stream_counts: std::collections::HashMap<String, u32> = {
-        let db_guard = deps.database.read().await;
-        match db_guard.as_ref() {
-            Some(db) => {
-                let conn = db.connection().await;
-                match database::get_in_stream_comment_counts(&conn, &video_id) {
-                    Ok(counts) => counts,
-                    Err(e) => {
-                        tracing::warn!(
-                            "in_stream_comment_count の DB 復元失敗 video_id={}: {}。\
-                             空状態で続行するため、既存コメント者も「初回扱い」となり \
-                             first_comment_only / プレフィックス機能に影響する可能性あり",
-                            video_id,
-                            e
-                        );
-                        std::collections::HashMap::new()
-                    }
-                }
-            }
-            None => std::collections::HashMap::new(),
-        }
-    };
+    // 取得したメッセージは処理キュー経由で別タスクが処理する（spec: 02_chat.md 処理キュー）
+    let monitoring_config = app.state::<ConfigState>().get().monitoring;
+    let (queue, queue_rx) = processing_queue(
+        monitoring_config.processing_queue_capacity,
+        monitoring_config.processing_queue_policy,
+    );
+    {
+        let mut slot = processor_slot.0.lock().await;
+        // 再起動前の処理タスクが積まれた分を処理し終えるまでは停止と判定しない
+        let _pause = watchdog.pause();
+        join_processor(&mut slot, connection_id).await;
+        *slot = Some(tokio::spawn(run_processor(
+            MessageProcessor::new(
+                deps.clone(),
+                app.clone(),
+                video_id.clone(),
+                connection_id,
+                session_id.clone(),
+                broadcaster_id.clone(),
+                emit_gui_message,
+            ),
+            queue_rx,
+        )));
+    }
+
+    let mut end_detector = StreamEndDetector::new();
+    let mut last_status_check = std::time::Instant::now();
 
     loop {
         // CancellationToken でループ停止を確認
@@ -130,8 +231,14 @@ pub async fn run_monitoring_loop<F>(
 
         let Some(mut client) = client_opt else {
             tracing::warn!("InnerTube クライアントが存在しないため監視を停止");
+            outcome = MonitoringOutcome::ClientUnavailable;
             break;
         };
+        // 応答の遅いリトライ中も停止と判定しないよう、試行ごとに進行を記録する
+        client.set_attempt_observer(Some(Arc::new({
+            let watchdog = watchdog.clone();
+            move || watchdog.record_success()
+        })));
 
         // フェッチ前にもキャンセルを確認
         if cancellation_token.is_cancelled() {
@@ -143,13 +250,60 @@ pub async fn run_monitoring_loop<F>(
         }
 
         // メッセージをフェッチ（ロックを保持しない）
-        let (new_messages, raw_response) = match client.fetch_messages_with_raw().await {
+        let fetch_started = std::time::Instant::now();
+        let fetched = client.fetch_messages_with_raw().await;
+        // 設定変更を接続中にも反映するためポーリングごとに参照する
+        let config = app.state::<ConfigState>().get();
+        client.set_poll_interval_override(config.monitoring.poll_interval_override());
+        // 指定がなければサーバーが指定した timeoutMs に従って次回のポーリングまで待つ
+        let poll_interval = client.next_poll_delay();
+        // 429 の Retry-After がリトライの上限より長ければ、早めに再送せずその間隔を空ける
+        let retry_after = fetched.as_ref().err().and_then(server_retry_after);
+        let health_change = match &fetched {
+            Ok((msgs, _)) => health.record_success(fetch_started.elapsed(), msgs.len()),
+            Err(e) => health.record_failure(is_rate_limited(e)),
+        };
+        // ポーリング間隔やレート制限の集計が変わったら段階が同じでも接続一覧へ伝える
+        // （後に記録したものほど新しいので、後のものを優先する）
+        let health_change = health.record_poll_interval(poll_interval).or(health_change);
+        let health_change = health
+            .record_rate_limit_stats(client.rate_limit_stats())
+            .or(health_change);
+        let health_change = health
+            .record_processing_queue(queue.stats())
+            .or(health_change);
+        if let Some(info) = health_change {
+            tracing::info!(
+                "接続の健全性が変化 connection_id: {} level: {:?}",
+                connection_id,
+                info.level
+            );
+            let _ = app.emit(
+                "chat:connection_health",
+                ConnectionHealthEvent {
+                    connection_id,
+                    health: info,
+                },
+            );
+        }
+        let (new_messages, raw_response) = match fetched {
             Ok((msgs, raw)) => {
+                watchdog.record_success();
                 if !msgs.is_empty() {
                     tracing::debug!("ポーリング {}: {} 件取得", poll_count, msgs.len());
                 }
                 (msgs, Some(raw))
             }
+            Err(e) if is_members_only(&e) => {
+                // ポーリングを続けても拒否され続けるため停止する（spec: 02_chat.md メンバー限定配信のアクセス拒否）
+                tracing::warn!(
+                    "メンバー限定のためチャットを取得できない — 監視を停止 connection_id: {}: {}",
+                    connection_id,
+                    e
+                );
+                outcome = MonitoringOutcome::MembersOnly;
+                break;
+            }
             Err(e) => {
                 tracing::warn!("ポーリング {}: メッセージ取得失敗: {}", poll_count, e);
                 (vec![], None)
@@ -162,6 +316,14 @@ pub async fn run_monitoring_loop<F>(
                 "フェッチ中にキャンセル検出（クライアントを戻さず終了） connection_id: {}",
                 connection_id
             );
+            // 取得済みの分は保存してから終わる（アプリ終了時の stop_monitoring はこれを待つ）
+            let messages = new_messages
+                .into_iter()
+                .filter_map(|msg| deps.transform(msg))
+                .collect();
+            let items = ProcessingItem::poll(messages, client.take_deleted_ids(), None);
+            let _pause = watchdog.pause();
+            queue.send_all(items).await;
             break;
         }
 
@@ -183,11 +345,34 @@ pub async fn run_monitoring_loop<F>(
             }
         }
 
+        let mut end_reason =
+            end_detector.observe_poll(client.is_stream_ended(), client.has_stream_end_notice());
+        let is_replay = client.is_replay;
+        // 取得に成功したら、再起動後に再開できるよう取得位置を控えておく
+        let resume_continuation = raw_response
+            .is_some()
+            .then(|| client.continuation().map(str::to_string))
+            .flatten();
+        let deleted_ids = client.take_deleted_ids();
+        let unknown_actions = client.take_unknown_actions();
+
         {
             let mut client_guard = innertube_client.write().await;
             *client_guard = Some(client);
         }
 
+        // 未対応のアクションを保存（設定が有効な場合）
+        if let Some(path) = config
+            .monitoring
+            .unknown_actions_file
+            .as_deref()
+            .filter(|_| !unknown_actions.is_empty())
+        {
+            if let Err(e) = append_unknown_actions(path, &video_id, &unknown_actions) {
+                tracing::warn!("未対応のアクションの保存失敗: {}", e);
+            }
+        }
+
         // 生レスポンスを保存（設定が有効な場合）
         if let Some(raw_json) = raw_response {
             if let Err(e) = raw_response_saver.save_response(&raw_json).await {
@@ -195,40 +380,59 @@ pub async fn run_monitoring_loop<F>(
             }
         }
 
-        // 各メッセージを処理
-        for mut msg in new_messages {
-            process_message(
-                &mut msg,
-                &video_id,
-                &session_id,
-                &broadcaster_id,
-                &mut in_stream_counts,
-                &deps,
-            )
-            .await;
-
-            // メッセージバッファに追加
-            {
-                let mut msgs = deps.messages.write().await;
-                if msgs.len() >= MAX_MESSAGES {
-                    msgs.pop_front();
-                }
-                msgs.push_back(msg.clone());
-            }
-
-            // GUI メッセージをフロントエンドに emit（コールバック経由）
-            emit_gui_message(&app, &msg);
+        // 取得したメッセージを処理キューに積む
+        let messages = new_messages
+            .into_iter()
+            .filter_map(|msg| deps.transform(msg))
+            .collect();
+        let items = ProcessingItem::poll(messages, deleted_ids, resume_continuation);
+        // 処理が追いつかず待たされている間は停止と判定しない（再起動しても解消しないため）
+        let sent = {
+            let _pause = watchdog.pause();
+            queue.send_all(items).await
+        };
+        if !sent {
+            tracing::info!(
+                "処理タスクが終了したためループ停止 connection_id: {}",
+                connection_id
+            );
+            break;
+        }
 
-            // WebSocket クライアントへブロードキャスト
-            {
-                let ws = deps.websocket_server.read().await;
-                if let Some(server) = ws.as_ref() {
-                    server.broadcast_message(&msg).await;
+        // 配信状態を定期的に確認する（アーカイブのチャットは常に終了状態なので対象外）
+        if end_reason.is_none()
+            && !is_replay
+            && last_status_check.elapsed() >= LIVE_STATUS_CHECK_INTERVAL
+        {
+            last_status_check = std::time::Instant::now();
+            match fetch_video_metadata(&video_id, &config.proxy).await {
+                Ok(metadata) => {
+                    end_reason = end_detector.observe_live_status(metadata.live_status);
+                }
+                Err(e) => {
+                    tracing::debug!("配信状態の確認失敗 video_id={}: {}", video_id, e);
                 }
             }
+        }
 
-            // TTS キューに追加
-            enqueue_tts(&deps.tts_manager, &msg).await;
+        // 最後のメッセージを処理してから配信終了でループを抜ける
+        if let Some(reason) = end_reason {
+            tracing::info!(
+                "配信終了を検出 connection_id: {} video_id: {} reason: {:?}",
+                connection_id,
+                video_id,
+                reason
+            );
+            let _ = app.emit(
+                "chat:stream_ended",
+                StreamEndedEvent {
+                    connection_id,
+                    video_id: video_id.clone(),
+                    reason,
+                },
+            );
+            outcome = MonitoringOutcome::StreamEnded;
+            break;
         }
 
         // スリープ中もキャンセルを検知できるように select! を使用
@@ -237,22 +441,260 @@ pub async fn run_monitoring_loop<F>(
                 tracing::info!("sleep中にCancellationTokenキャンセル connection_id: {}", connection_id);
                 break;
             }
-            _ = tokio::time::sleep(poll_interval) => {}
+            _ = watchdog.sleep_alive(retry_after.map_or(poll_interval, |r| r.max(poll_interval))) => {}
         }
     }
 
+    // 積んだメッセージを処理し終えてからセッションを終了する（キャンセル時も積んだ分は処理する）
+    drop(queue);
+    {
+        let _pause = watchdog.pause();
+        join_processor(&mut *processor_slot.0.lock().await, connection_id).await;
+    }
+
     // セッション終了処理
     finish_session(&deps, connection_id, &session_id).await;
 
+    // 配信終了・ユーザーによる切断では再開の必要がないため取得位置を破棄する
+    if matches!(
+        outcome,
+        MonitoringOutcome::StreamEnded | MonitoringOutcome::Cancelled
+    ) {
+        if let Err(e) = deps.continuation_store.remove(&video_id).await {
+            tracing::warn!("取得位置の削除失敗 video_id={}: {}", video_id, e);
+        }
+    }
+
     tracing::info!(
-        "チャット監視タスク停止 connection_id: {} polls: {}",
+        "チャット監視タスク停止 connection_id: {} polls: {} outcome: {:?}",
         connection_id,
-        poll_count
+        poll_count,
+        outcome
     );
+
+    outcome
+}
+
+/// 処理キューから取り出したメッセージを処理する側の状態（spec: 02_chat.md 処理キュー）
+///
+/// in-stream カウント・マイルストーン・収益目標は処理した順に更新するため、処理タスクが持つ。
+struct MessageProcessor<F> {
+    deps: MonitoringDeps,
+    app: AppHandle,
+    video_id: String,
+    connection_id: u64,
+    session_id: Option<String>,
+    broadcaster_id: Option<String>,
+    emit_gui_message: F,
+    in_stream_counts: std::collections::HashMap<String, u32>,
+    milestones: MilestoneTracker,
+    revenue_goal: GoalTracker,
+    connected_at: std::time::Instant,
+}
+
+impl<F> MessageProcessor<F>
+where
+    F: Fn(&AppHandle, &ChatMessage) + Send + Sync + 'static,
+{
+    fn new(
+        deps: MonitoringDeps,
+        app: AppHandle,
+        video_id: String,
+        connection_id: u64,
+        session_id: Option<String>,
+        broadcaster_id: Option<String>,
+        emit_gui_message: F,
+    ) -> Self {
+        Self {
+            deps,
+            app,
+            video_id,
+            connection_id,
+            session_id,
+            broadcaster_id,
+            emit_gui_message,
+            in_stream_counts: std::collections::HashMap::new(),
+            milestones: MilestoneTracker::new(),
+            revenue_goal: GoalTracker::new(),
+            connected_at: std::time::Instant::now(),
+        }
+    }
+
+    /// セッション開始時点のコメント数を DB から復元する
+    ///
+    /// 復元失敗時に silent に空マップへフォールバックすると既存コメント者も
+    /// 「初回扱い」となり first_comment_only / プレフィックス機能の挙動が崩れるため、
+    /// 失敗時は warn ログで副作用を明示する (provenance: branch-owned)
+    async fn restore_in_stream_counts(&mut self) {
+        let db_guard = self.deps.database.read().await;
+        let Some(db) = db_guard.as_ref() else {
+            return;
+        };
+        let conn = db.connection().await;
+        match database::get_in_stream_comment_counts(&conn, &self.video_id) {
+            Ok(counts) => self.in_stream_counts = counts,
+            Err(e) => {
+                tracing::warn!(
+                    "in_stream_comment_count の DB 復元失敗 video_id={}: {}。\
+                     空状態で続行するため、既存コメント者も「初回扱い」となり \
+                     first_comment_only / プレフィックス機能に影響する可能性あり",
+                    self.video_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// メッセージを DB に保存して下流へ流す（DB への書き込みは 1 つのトランザクションにまとめる）
+    async fn process(&mut self, mut messages: Vec<ChatMessage>) {
+        if messages.is_empty() {
+            return;
+        }
+        process_messages(
+            &mut messages,
+            &self.video_id,
+            &self.session_id,
+            &self.broadcaster_id,
+            &mut self.in_stream_counts,
+            &self.deps,
+        )
+        .await;
+        let config = self.app.state::<ConfigState>().get();
+        let rates = config.currency.rate_table();
+        for msg in &messages {
+            self.milestones
+                .record(msg, &rates, &config.currency.base_currency);
+            deliver_message(&self.deps, &self.app, msg, &self.emit_gui_message).await;
+            // 配信内の初コメントを通知する（spec: 02_chat.md 配信内の初コメント）
+            if let Some(event) = FirstTimeChatterEvent::from_message(self.connection_id, msg) {
+                let _ = self.app.emit("chat:first_time_chatter", event);
+            }
+        }
+    }
+
+    /// 1 回のポーリング分を処理し終えたときの通知と取得位置の保存
+    async fn finish_poll(&mut self, continuation: Option<String>) {
+        let config = self.app.state::<ConfigState>().get();
+        let rates = config.currency.rate_table();
+
+        // しきい値を超えたマイルストーンをシステムメッセージとして流す（spec: 02_chat.md マイルストーンの通知）
+        for milestone in self.milestones.check(
+            &config.milestones,
+            self.in_stream_counts.len(),
+            self.connected_at.elapsed(),
+        ) {
+            tracing::info!(
+                "マイルストーン到達 connection_id: {} {:?}",
+                self.connection_id,
+                milestone
+            );
+            let msg =
+                milestone.to_system_message(&config.currency.base_currency, chrono::Utc::now());
+            publish_message(&self.deps, &self.app, &msg, &self.emit_gui_message).await;
+            self.app.state::<DiscordState>().0.notify(
+                &config.discord,
+                &DiscordEvent::Milestone {
+                    text: msg.content.clone(),
+                },
+            );
+            if config.milestones.tts {
+                enqueue_tts(&self.deps.tts_manager, &msg).await;
+            }
+        }
+
+        // 収益目標への到達を通知する（spec: 07_revenue.md 収益目標）
+        // 目標は設定変更で配信中に変わりうるため、ポーリングごとに参照する
+        if let Some(progress) = GoalProgress::compute(
+            self.milestones.super_chat_total(),
+            &config.currency.base_currency,
+            &config.revenue_goal,
+            &rates,
+        ) {
+            if self.revenue_goal.check(&config.revenue_goal, &progress) {
+                tracing::info!(
+                    "収益目標に到達 connection_id: {} raised: {} {}",
+                    self.connection_id,
+                    progress.raised,
+                    progress.currency
+                );
+                let _ = self.app.emit(
+                    "chat:goal_reached",
+                    GoalReachedEvent {
+                        connection_id: self.connection_id,
+                        progress,
+                    },
+                );
+            }
+        }
+
+        // 取得したメッセージを処理し終えてから保存する（途中で落ちても取りこぼさない）
+        if let Some(continuation) = continuation {
+            if let Err(e) = self
+                .deps
+                .continuation_store
+                .save(&self.video_id, &continuation)
+                .await
+            {
+                tracing::warn!("取得位置の保存失敗 video_id={}: {}", self.video_id, e);
+            }
+        }
+    }
+}
+
+impl<F> ProcessingHandler for MessageProcessor<F>
+where
+    F: Fn(&AppHandle, &ChatMessage) + Send + Sync + 'static,
+{
+    /// キューから取り出した分を積まれた順に処理する
+    async fn handle(&mut self, items: Vec<ProcessingItem>) {
+        let mut messages = Vec::new();
+        for item in items {
+            match item {
+                ProcessingItem::Message(msg) => messages.push(msg),
+                ProcessingItem::Deleted(ids) => {
+                    // 同じレスポンスで追加されたものも対象にするため、先に積まれたメッセージを処理してから行う
+                    self.process(std::mem::take(&mut messages)).await;
+                    remove_deleted_messages(
+                        &self.deps,
+                        &self.app,
+                        self.connection_id,
+                        &self.session_id,
+                        ids,
+                    )
+                    .await;
+                }
+                ProcessingItem::PollEnd { continuation } => {
+                    self.process(std::mem::take(&mut messages)).await;
+                    self.finish_poll(continuation).await;
+                }
+            }
+        }
+        self.process(messages).await;
+    }
+}
+
+/// 処理キューが閉じる（監視ループが終わる）まで積まれたものを処理する
+///
+/// キャンセルされても積まれた分は処理してから終わる（spec: 02_chat.md 処理キュー）。
+async fn run_processor<F>(mut processor: MessageProcessor<F>, mut queue: ProcessingReceiver)
+where
+    F: Fn(&AppHandle, &ChatMessage) + Send + Sync + 'static,
+{
+    processor.restore_in_stream_counts().await;
+    // マイルストーンの通知（復元したカウンターで既に超えているものは通知しない）
+    processor.milestones.mark_reached(
+        &processor.app.state::<ConfigState>().get().milestones,
+        processor.in_stream_counts.len(),
+        std::time::Duration::ZERO,
+    );
+
+    run_processing_queue(&mut processor, &mut queue, DB_WRITE_BATCH_SIZE).await;
 }
 
 /// 1 件のメッセージに対して、DB 保存・初回視聴者判定・in-stream カウント更新を行う
-async fn process_message(
+///
+/// `session_id` / `broadcaster_id` が None の場合は in-stream カウント更新のみ行う。
+pub(crate) async fn process_message(
     msg: &mut ChatMessage,
     video_id: &str,
     session_id: &Option<String>,
@@ -260,40 +702,172 @@ async fn process_message(
     in_stream_counts: &mut std::collections::HashMap<String, u32>,
     deps: &MonitoringDeps,
 ) {
-    let is_system = matches!(msg.message_type, crate::core::models::MessageType::System);
+    process_messages(
+        std::slice::from_mut(msg),
+        video_id,
+        session_id,
+        broadcaster_id,
+        in_stream_counts,
+        deps,
+    )
+    .await;
+}
 
+/// 複数のメッセージに対して、DB 保存・初回視聴者判定・in-stream カウント更新を行う
+///
+/// DB への書き込み（メッセージ・viewer_profile・viewer_stream）は 1 つのトランザクションにまとめる。
+/// 初回視聴者の判定は同じトランザクション内で行うため、直前に保存した viewer_streams が反映される。
+pub(crate) async fn process_messages(
+    msgs: &mut [ChatMessage],
+    video_id: &str,
+    session_id: &Option<String>,
+    broadcaster_id: &Option<String>,
+    in_stream_counts: &mut std::collections::HashMap<String, u32>,
+    deps: &MonitoringDeps,
+) {
     // システムメッセージ以外は in-stream コメントカウンターをインクリメント
-    if !is_system {
-        let count = in_stream_counts.entry(msg.channel_id.clone()).or_insert(0);
-        *count += 1;
-        msg.in_stream_comment_count = Some(*count);
+    for msg in msgs.iter_mut() {
+        count_in_stream_comment(msg, in_stream_counts);
     }
 
-    // DB に保存（viewer_profile + viewer_stream を生成・更新）
-    if let Some(sid) = session_id {
-        let db_guard = deps.database.read().await;
-        if let Some(db) = db_guard.as_ref() {
-            let conn = db.connection().await;
+    if session_id.is_none() && broadcaster_id.is_none() {
+        return;
+    }
+    let db_guard = deps.database.read().await;
+    let Some(db) = db_guard.as_ref() else {
+        return;
+    };
+    let conn = db.connection().await;
+    let tx = match conn.unchecked_transaction() {
+        Ok(tx) => Some(tx),
+        Err(e) => {
+            // トランザクションを開始できなくても 1 件ずつ書き込む
+            tracing::warn!("トランザクション開始失敗: {}", e);
+            None
+        }
+    };
+
+    for msg in msgs.iter_mut() {
+        // DB に保存（viewer_profile + viewer_stream を生成・更新）
+        if let Some(sid) = session_id {
             if let Err(e) =
                 database::save_message(&conn, sid, broadcaster_id.as_deref(), msg, Some(video_id))
             {
                 tracing::warn!("メッセージ保存失敗: {}", e);
             }
         }
-    }
 
-    // DB 保存後に初回視聴者かどうかを判定（viewer_streams が更新済みのため）
-    if !is_system {
-        if let Some(bid) = broadcaster_id {
-            let db_guard = deps.database.read().await;
-            if let Some(db) = db_guard.as_ref() {
-                let conn = db.connection().await;
+        // DB 保存後に初回視聴者かどうかを判定（viewer_streams が更新済みのため）
+        if !matches!(msg.message_type, crate::core::models::MessageType::System) {
+            if let Some(bid) = broadcaster_id {
                 msg.is_first_time_viewer =
                     database::is_first_time_viewer(&conn, bid, &msg.channel_id, video_id)
                         .unwrap_or(false);
             }
         }
     }
+
+    if let Some(tx) = tx {
+        if let Err(e) = tx.commit() {
+            tracing::warn!("メッセージ保存のコミット失敗: {}", e);
+        }
+    }
+}
+
+/// 処理済みのメッセージを下流（メッセージバッファ・GUI・WebSocket・TTS）へ流す
+///
+/// ライブの監視ループと保存済みレスポンスの再生（core::replay）で共通の経路。
+pub async fn deliver_message<F>(
+    deps: &MonitoringDeps,
+    app: &AppHandle,
+    msg: &ChatMessage,
+    emit_gui_message: &F,
+) where
+    F: Fn(&AppHandle, &ChatMessage),
+{
+    publish_message(deps, app, msg, emit_gui_message).await;
+
+    // エンゲージメントを集計し、間隔が経っていればスナップショットを記録する
+    // （spec: 02_chat.md エンゲージメントの推移）
+    let engagement_config = app.state::<ConfigState>().get().engagement;
+    if let Ok(mut metrics) = app.state::<EngagementState>().0.lock() {
+        let now = chrono::Utc::now();
+        metrics.record(msg, now);
+        metrics.maybe_sample(&engagement_config, now);
+    }
+
+    // TTS キューに追加
+    enqueue_tts(&deps.tts_manager, msg).await;
+}
+
+/// メッセージをメッセージバッファ・GUI・WebSocket へ流す（TTS には追加しない）
+async fn publish_message<F>(
+    deps: &MonitoringDeps,
+    app: &AppHandle,
+    msg: &ChatMessage,
+    emit_gui_message: &F,
+) where
+    F: Fn(&AppHandle, &ChatMessage),
+{
+    // メッセージバッファに追加し、押し出したメッセージはディスクアーカイブに追記する
+    // （spec: 02_chat.md メッセージのディスクアーカイブ。書き込みは専用のスレッドで行い待たない）
+    let archive_config = app.state::<ConfigState>().get().archive;
+    let evicted = {
+        let mut msgs = deps.messages.write().await;
+        state::push_message(&mut msgs, msg.clone(), archive_config.hot_size)
+    };
+    if let Some(path) = archive_config.archive_to_disk {
+        deps.archive_writer.append(path, evicted);
+    }
+
+    // GUI メッセージをフロントエンドに emit（コールバック経由）
+    emit_gui_message(app, msg);
+
+    // WebSocket クライアントへブロードキャスト
+    {
+        let ws = deps.websocket_server.read().await;
+        if let Some(server) = ws.as_ref() {
+            server.broadcast_message(msg).await;
+        }
+    }
+}
+
+/// 削除されたメッセージをメッセージバッファから取り除き、保存済みのものを削除済みにして GUI に通知する
+async fn remove_deleted_messages(
+    deps: &MonitoringDeps,
+    app: &AppHandle,
+    connection_id: u64,
+    session_id: &Option<String>,
+    message_ids: Vec<String>,
+) {
+    let removed = {
+        let mut msgs = deps.messages.write().await;
+        state::remove_messages(&mut msgs, &message_ids)
+    };
+    tracing::debug!(
+        "メッセージ削除 connection_id: {} ids: {} removed: {}",
+        connection_id,
+        message_ids.len(),
+        removed
+    );
+
+    if let Some(sid) = session_id {
+        let db_guard = deps.database.read().await;
+        if let Some(db) = db_guard.as_ref() {
+            let conn = db.connection().await;
+            if let Err(e) = database::mark_messages_deleted(&conn, sid, &message_ids) {
+                tracing::warn!("メッセージの削除済み記録失敗: {}", e);
+            }
+        }
+    }
+
+    let _ = app.emit(
+        "chat:message_deleted",
+        MessageDeletedEvent {
+            connection_id,
+            message_ids,
+        },
+    );
 }
 
 /// メッセージを TTS キューに追加する
@@ -312,10 +886,16 @@ async fn enqueue_tts(tts_manager: &TtsManager, msg: &ChatMessage) {
         _ => None,
     };
 
+    // システムメッセージは本文だけを読み上げる
+    let author_name = match &msg.message_type {
+        crate::core::models::MessageType::System => None,
+        _ => Some(msg.author.clone()),
+    };
+
     let item = TtsQueueItem {
         text: msg.content.clone(),
         priority,
-        author_name: Some(msg.author.clone()),
+        author_name,
         amount,
         in_stream_comment_count: msg.in_stream_comment_count,
         message_id: Some(msg.id.clone()),
@@ -338,12 +918,10 @@ async fn finish_session(deps: &MonitoringDeps, connection_id: u64, session_id: &
         let db_guard = deps.database.read().await;
         if let Some(db) = db_guard.as_ref() {
             let conn = db.connection().await;
-            if let Err(e) = database::end_session(&conn, sid) {
+            let ended_at = chrono::Utc::now().to_rfc3339();
+            if let Err(e) = database::end_session(&conn, sid, &ended_at) {
                 tracing::warn!("セッション終了失敗: {}", e);
             }
-            if let Err(e) = database::update_session_stats(&conn, sid) {
-                tracing::warn!("セッション統計更新失敗: {}", e);
-            }
             tracing::debug!(
                 "監視タスク終了処理: セッション終了完了 connection_id: {}",
                 connection_id
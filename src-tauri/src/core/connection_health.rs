@@ -0,0 +1,418 @@
+//! 接続の健全性（spec: 02_chat.md 接続の健全性）
+//!
+//! ポーリングがエラーにならないまま劣化している状態（応答の遅延、空の応答の連続、レート制限、
+//! 処理キューの滞留）を
+//! 検出し、接続一覧の表示を黄・赤に切り替えるための指標を集計する。
+
+use crate::core::api::RateLimitStats;
+use crate::core::processing_queue::ProcessingQueueStats;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use ts_rs::TS;
+
+/// 平均応答時間がこれ以上なら Degraded
+pub const DEGRADED_LATENCY_MS: f64 = 3000.0;
+/// 空の応答がこの回数以上連続したら Degraded（1.5秒間隔で約1分）
+pub const DEGRADED_CONSECUTIVE_EMPTY: u32 = 40;
+/// 最後のポーリング成功からこの時間が経過したら Degraded
+pub const DEGRADED_AFTER: Duration = Duration::from_secs(10);
+/// 最後のポーリング成功からこの時間が経過したら Stalled
+pub const STALLED_AFTER: Duration = Duration::from_secs(30);
+/// 平均応答時間の指数移動平均の重み
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+/// 接続の健全性の段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub enum HealthLevel {
+    Good,
+    Degraded,
+    Stalled,
+}
+
+/// ポーリング結果から集計した接続の指標
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionHealth {
+    /// 最後にポーリングが成功した時刻
+    pub last_success: Instant,
+    /// 新着メッセージのない応答の連続回数
+    pub consecutive_empty: u32,
+    /// 応答時間の指数移動平均（ミリ秒）。成功がなければ 0
+    pub avg_latency_ms: f64,
+    /// 最後の失敗がレート制限（429）だったか（次の成功で解除）
+    pub rate_limited: bool,
+    /// 接続してからのレート制限の集計（InnerTube クライアントから転記する）
+    pub rate_limit: RateLimitStats,
+    /// 実際に使っているポーリング間隔（ミリ秒）。最初のポーリングまでは 0
+    pub poll_interval_ms: u64,
+    /// 取得と処理の間のキューの状態（監視ループから転記する）
+    pub processing_queue: ProcessingQueueStats,
+}
+
+impl ConnectionHealth {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_success: now,
+            consecutive_empty: 0,
+            avg_latency_ms: 0.0,
+            rate_limited: false,
+            rate_limit: RateLimitStats::default(),
+            poll_interval_ms: 0,
+            processing_queue: ProcessingQueueStats::default(),
+        }
+    }
+
+    /// ポーリング成功を記録する
+    pub fn record_success(&mut self, now: Instant, latency: Duration, message_count: usize) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        self.avg_latency_ms = if self.avg_latency_ms == 0.0 {
+            latency_ms
+        } else {
+            self.avg_latency_ms + LATENCY_SMOOTHING * (latency_ms - self.avg_latency_ms)
+        };
+        self.last_success = now;
+        self.rate_limited = false;
+        if message_count == 0 {
+            self.consecutive_empty = self.consecutive_empty.saturating_add(1);
+        } else {
+            self.consecutive_empty = 0;
+        }
+    }
+
+    /// ポーリング失敗を記録する
+    pub fn record_failure(&mut self, rate_limited: bool) {
+        self.rate_limited = rate_limited;
+    }
+
+    /// しきい値に基づく健全性の段階
+    pub fn health_level(&self, now: Instant) -> HealthLevel {
+        let since_success = now.saturating_duration_since(self.last_success);
+        if since_success >= STALLED_AFTER {
+            HealthLevel::Stalled
+        } else if self.rate_limited
+            || since_success >= DEGRADED_AFTER
+            || self.avg_latency_ms >= DEGRADED_LATENCY_MS
+            || self.consecutive_empty >= DEGRADED_CONSECUTIVE_EMPTY
+            || self.processing_queue.is_backlogged()
+        {
+            HealthLevel::Degraded
+        } else {
+            HealthLevel::Good
+        }
+    }
+
+    /// フロントエンドに公開する形に変換する
+    pub fn info(&self, now: Instant) -> ConnectionHealthInfo {
+        ConnectionHealthInfo {
+            level: self.health_level(now),
+            secs_since_success: now.saturating_duration_since(self.last_success).as_secs(),
+            consecutive_empty: self.consecutive_empty,
+            avg_latency_ms: self.avg_latency_ms,
+            rate_limited: self.rate_limited,
+            rate_limit: self.rate_limit,
+            poll_interval_ms: self.poll_interval_ms,
+            processing_queue: self.processing_queue,
+        }
+    }
+}
+
+/// フロントエンドに公開する接続の健全性
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ConnectionHealthInfo {
+    pub level: HealthLevel,
+    /// 最後のポーリング成功からの経過秒数
+    pub secs_since_success: u64,
+    pub consecutive_empty: u32,
+    pub avg_latency_ms: f64,
+    pub rate_limited: bool,
+    pub rate_limit: RateLimitStats,
+    /// 実際に使っているポーリング間隔（ミリ秒）
+    #[ts(type = "number")]
+    pub poll_interval_ms: u64,
+    pub processing_queue: ProcessingQueueStats,
+}
+
+/// 健全性の段階変化イベント（`chat:connection_health`）のペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ConnectionHealthEvent {
+    pub connection_id: u64,
+    pub health: ConnectionHealthInfo,
+}
+
+#[derive(Debug)]
+struct HealthState {
+    health: ConnectionHealth,
+    /// 最後に通知した段階（変化したときだけイベントを送る）
+    reported: HealthLevel,
+}
+
+/// 監視タスクと接続一覧で共有する健全性のハンドル
+///
+/// 記録系のメソッドは段階が前回の通知から変わったときだけ `Some` を返す。
+#[derive(Debug, Clone)]
+pub struct ConnectionHealthHandle {
+    inner: Arc<Mutex<HealthState>>,
+}
+
+impl Default for ConnectionHealthHandle {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HealthState {
+                health: ConnectionHealth::new(Instant::now()),
+                reported: HealthLevel::Good,
+            })),
+        }
+    }
+}
+
+impl ConnectionHealthHandle {
+    /// ポーリング成功を記録する
+    pub fn record_success(
+        &self,
+        latency: Duration,
+        message_count: usize,
+    ) -> Option<ConnectionHealthInfo> {
+        self.update(|health, now| health.record_success(now, latency, message_count))
+    }
+
+    /// ポーリング失敗を記録する
+    pub fn record_failure(&self, rate_limited: bool) -> Option<ConnectionHealthInfo> {
+        self.update(|health, _| health.record_failure(rate_limited))
+    }
+
+    /// レート制限の集計を更新する
+    ///
+    /// 集計が増えたときは段階が変わらなくても `Some` を返す（接続一覧の表示を更新するため）。
+    pub fn record_rate_limit_stats(&self, stats: RateLimitStats) -> Option<ConnectionHealthInfo> {
+        let now = Instant::now();
+        let mut state = self.inner.lock().ok()?;
+        if state.health.rate_limit == stats {
+            return None;
+        }
+        state.health.rate_limit = stats;
+        let info = state.health.info(now);
+        state.reported = info.level;
+        Some(info)
+    }
+
+    /// 実際に使っているポーリング間隔を記録する
+    ///
+    /// 間隔が変わったときは段階が変わらなくても `Some` を返す（接続一覧の表示を更新するため）。
+    pub fn record_poll_interval(&self, interval: Duration) -> Option<ConnectionHealthInfo> {
+        let now = Instant::now();
+        let interval_ms = interval.as_millis() as u64;
+        let mut state = self.inner.lock().ok()?;
+        if state.health.poll_interval_ms == interval_ms {
+            return None;
+        }
+        state.health.poll_interval_ms = interval_ms;
+        let info = state.health.info(now);
+        state.reported = info.level;
+        Some(info)
+    }
+
+    /// 処理キューの状態を記録する
+    ///
+    /// 破棄した件数が増えたときは段階が変わらなくても `Some` を返す（接続一覧で警告するため）。
+    /// 処理待ちの件数だけの変化は、滞留による段階の変化があったときだけ伝える。
+    pub fn record_processing_queue(
+        &self,
+        stats: ProcessingQueueStats,
+    ) -> Option<ConnectionHealthInfo> {
+        let now = Instant::now();
+        let mut state = self.inner.lock().ok()?;
+        let dropped_more = stats.dropped != state.health.processing_queue.dropped;
+        state.health.processing_queue = stats;
+        let info = state.health.info(now);
+        if !dropped_more && info.level == state.reported {
+            return None;
+        }
+        state.reported = info.level;
+        Some(info)
+    }
+
+    /// 時間経過による段階の変化を確認する
+    pub fn check(&self) -> Option<ConnectionHealthInfo> {
+        self.update(|_, _| {})
+    }
+
+    /// 現在の健全性
+    pub fn info(&self) -> ConnectionHealthInfo {
+        let now = Instant::now();
+        match self.inner.lock() {
+            Ok(state) => state.health.info(now),
+            Err(poisoned) => poisoned.into_inner().health.info(now),
+        }
+    }
+
+    fn update(
+        &self,
+        f: impl FnOnce(&mut ConnectionHealth, Instant),
+    ) -> Option<ConnectionHealthInfo> {
+        let now = Instant::now();
+        let mut state = self.inner.lock().ok()?;
+        f(&mut state.health, now);
+        let info = state.health.info(now);
+        if info.level == state.reported {
+            return None;
+        }
+        state.reported = info.level;
+        Some(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn healthy_polling_is_good() {
+        let start = Instant::now();
+        let mut health = ConnectionHealth::new(start);
+        health.record_success(start, 200 * MS, 3);
+        assert_eq!(health.health_level(start), HealthLevel::Good);
+        assert_eq!(health.avg_latency_ms, 200.0);
+    }
+
+    #[test]
+    fn time_since_success_degrades_then_stalls() {
+        let start = Instant::now();
+        let health = ConnectionHealth::new(start);
+        assert_eq!(
+            health.health_level(start + Duration::from_secs(9)),
+            HealthLevel::Good
+        );
+        assert_eq!(
+            health.health_level(start + DEGRADED_AFTER),
+            HealthLevel::Degraded
+        );
+        assert_eq!(
+            health.health_level(start + STALLED_AFTER),
+            HealthLevel::Stalled
+        );
+    }
+
+    #[test]
+    fn consecutive_empty_responses_degrade() {
+        let start = Instant::now();
+        let mut health = ConnectionHealth::new(start);
+        for _ in 0..DEGRADED_CONSECUTIVE_EMPTY {
+            health.record_success(start, 100 * MS, 0);
+        }
+        assert_eq!(health.health_level(start), HealthLevel::Degraded);
+
+        health.record_success(start, 100 * MS, 1);
+        assert_eq!(health.consecutive_empty, 0);
+        assert_eq!(health.health_level(start), HealthLevel::Good);
+    }
+
+    #[test]
+    fn slow_responses_degrade_via_moving_average() {
+        let start = Instant::now();
+        let mut health = ConnectionHealth::new(start);
+        health.record_success(start, 500 * MS, 1);
+        health.record_success(start, 5500 * MS, 1);
+        // 500 + 0.2 * (5500 - 500)
+        assert_eq!(health.avg_latency_ms, 1500.0);
+        assert_eq!(health.health_level(start), HealthLevel::Good);
+
+        for _ in 0..10 {
+            health.record_success(start, 5500 * MS, 1);
+        }
+        assert_eq!(health.health_level(start), HealthLevel::Degraded);
+    }
+
+    #[test]
+    fn rate_limit_degrades_until_next_success() {
+        let start = Instant::now();
+        let mut health = ConnectionHealth::new(start);
+        health.record_failure(true);
+        assert!(health.rate_limited);
+        assert_eq!(health.health_level(start), HealthLevel::Degraded);
+
+        health.record_success(start, 100 * MS, 1);
+        assert!(!health.rate_limited);
+        assert_eq!(health.health_level(start), HealthLevel::Good);
+    }
+
+    #[test]
+    fn handle_reports_only_level_changes() {
+        let handle = ConnectionHealthHandle::default();
+        assert_eq!(handle.record_success(100 * MS, 1), None);
+
+        let info = handle.record_failure(true).unwrap();
+        assert_eq!(info.level, HealthLevel::Degraded);
+        assert!(info.rate_limited);
+        assert_eq!(handle.record_failure(true), None);
+
+        let info = handle.record_success(100 * MS, 1).unwrap();
+        assert_eq!(info.level, HealthLevel::Good);
+        assert_eq!(handle.check(), None);
+        assert_eq!(handle.info().level, HealthLevel::Good);
+    }
+
+    #[test]
+    fn handle_reports_rate_limit_stats_changes() {
+        let handle = ConnectionHealthHandle::default();
+        assert_eq!(
+            handle.record_rate_limit_stats(RateLimitStats::default()),
+            None
+        );
+
+        let stats = RateLimitStats {
+            hits: 2,
+            total_backoff_ms: 1_500,
+            longest_wait_ms: 1_000,
+        };
+        let info = handle.record_rate_limit_stats(stats).unwrap();
+        assert_eq!(info.level, HealthLevel::Good);
+        assert_eq!(info.rate_limit, stats);
+        assert_eq!(handle.record_rate_limit_stats(stats), None);
+        assert_eq!(handle.info().rate_limit, stats);
+    }
+
+    #[test]
+    fn processing_queue_backlog_degrades_and_drops_are_reported() {
+        let handle = ConnectionHealthHandle::default();
+        let stats = |queued, dropped| ProcessingQueueStats {
+            queued,
+            capacity: 100,
+            dropped,
+        };
+        assert_eq!(handle.record_processing_queue(stats(10, 0)), None);
+
+        let info = handle.record_processing_queue(stats(80, 0)).unwrap();
+        assert_eq!(info.level, HealthLevel::Degraded);
+        assert_eq!(info.processing_queue.queued, 80);
+        assert_eq!(handle.record_processing_queue(stats(90, 0)), None);
+
+        let info = handle.record_processing_queue(stats(100, 3)).unwrap();
+        assert_eq!(info.processing_queue.dropped, 3);
+        assert_eq!(handle.record_processing_queue(stats(100, 3)), None);
+
+        let info = handle.record_processing_queue(stats(0, 3)).unwrap();
+        assert_eq!(info.level, HealthLevel::Good);
+        assert_eq!(handle.info().processing_queue, stats(0, 3));
+    }
+
+    #[test]
+    fn handle_reports_poll_interval_changes() {
+        let handle = ConnectionHealthHandle::default();
+        let info = handle.record_poll_interval(1500 * MS).unwrap();
+        assert_eq!(info.level, HealthLevel::Good);
+        assert_eq!(info.poll_interval_ms, 1500);
+        assert_eq!(handle.record_poll_interval(1500 * MS), None);
+        assert_eq!(
+            handle
+                .record_poll_interval(Duration::from_secs(5))
+                .map(|i| i.poll_interval_ms),
+            Some(5000)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! continuation token の保存（spec: 02_chat.md 取得位置の再開）
+//!
+//! 長時間の配信中にアプリが落ちても、再起動後に前回の取得位置から再開できるよう、
+//! 動画ごとに最新の continuation token をファイルに保存する。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// 再開に使う continuation token の最大経過時間（これより古いものは使わずに破棄する）
+pub const MAX_RESUME_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// 保存した continuation token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedContinuation {
+    pub continuation: String,
+    /// 保存時刻（UNIX 秒）
+    pub saved_at: i64,
+}
+
+/// 動画 ID ごとの continuation token を JSON ファイルに保存する
+///
+/// 複数接続から同時に書き込まれるため、読み書きは内部のロックで直列化する。
+/// パスがない場合（保存先を特定できない環境）は何もしない。
+#[derive(Debug)]
+pub struct ContinuationStore {
+    path: Option<PathBuf>,
+    max_age: Duration,
+    lock: Mutex<()>,
+}
+
+impl Default for ContinuationStore {
+    fn default() -> Self {
+        Self::new(crate::paths::continuation_state_path().ok())
+    }
+}
+
+impl ContinuationStore {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            max_age: MAX_RESUME_AGE,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// 再開に使う continuation token の最大経過時間を設定する
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// 再開に使える continuation token を返す（期限切れ・未保存なら None）
+    pub async fn load(&self, video_id: &str) -> Option<String> {
+        let _guard = self.lock.lock().await;
+        let saved = self.read_all().await.remove(video_id)?;
+        self.is_fresh(&saved, now_secs())
+            .then_some(saved.continuation)
+    }
+
+    /// 最新の continuation token を保存する（期限切れのエントリは同時に捨てる）
+    pub async fn save(&self, video_id: &str, continuation: &str) -> std::io::Result<()> {
+        let _guard = self.lock.lock().await;
+        let now = now_secs();
+        let mut entries = self.read_all().await;
+        entries.retain(|_, saved| self.is_fresh(saved, now));
+        entries.insert(
+            video_id.to_string(),
+            SavedContinuation {
+                continuation: continuation.to_string(),
+                saved_at: now,
+            },
+        );
+        self.write_all(&entries).await
+    }
+
+    /// 保存した continuation token を削除する（配信終了・ユーザーによる切断時）
+    pub async fn remove(&self, video_id: &str) -> std::io::Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut entries = self.read_all().await;
+        if entries.remove(video_id).is_none() {
+            return Ok(());
+        }
+        self.write_all(&entries).await
+    }
+
+    fn is_fresh(&self, saved: &SavedContinuation, now: i64) -> bool {
+        let age = now.saturating_sub(saved.saved_at);
+        u64::try_from(age).is_ok_and(|age| age <= self.max_age.as_secs())
+    }
+
+    /// ファイルを読み込む（存在しない・壊れている場合は空）
+    async fn read_all(&self) -> HashMap<String, SavedContinuation> {
+        let Some(path) = &self.path else {
+            return HashMap::new();
+        };
+        match tokio::fs::read_to_string(path).await {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                tracing::warn!("continuation 保存ファイルの読み込みに失敗: {}", e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    /// 一時ファイルに書いてから置き換える（書き込み途中で落ちてもファイルを壊さない）
+    async fn write_all(&self, entries: &HashMap<String, SavedContinuation>) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(entries)?).await?;
+        tokio::fs::rename(&tmp, path).await
+    }
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_in(dir: &tempfile::TempDir) -> ContinuationStore {
+        ContinuationStore::new(Some(dir.path().join("continuations.json")))
+    }
+
+    #[tokio::test]
+    async fn saved_token_is_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        store_in(&dir).save("video1", "token_a").await.unwrap();
+        store_in(&dir).save("video1", "token_b").await.unwrap();
+        store_in(&dir).save("video2", "token_c").await.unwrap();
+
+        // 別インスタンス（再起動後）からも最新の token を読み込める
+        let store = store_in(&dir);
+        assert_eq!(store.load("video1").await.as_deref(), Some("token_b"));
+        assert_eq!(store.load("video2").await.as_deref(), Some("token_c"));
+        assert_eq!(store.load("unknown").await, None);
+    }
+
+    #[tokio::test]
+    async fn removed_token_is_not_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store_in(&dir);
+        store.save("video1", "token_a").await.unwrap();
+        store.remove("video1").await.unwrap();
+        store.remove("video1").await.unwrap();
+        assert_eq!(store.load("video1").await, None);
+    }
+
+    #[tokio::test]
+    async fn expired_token_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("continuations.json");
+        let stale = HashMap::from([(
+            "video1".to_string(),
+            SavedContinuation {
+                continuation: "old".to_string(),
+                saved_at: now_secs() - 7200,
+            },
+        )]);
+        std::fs::write(&path, serde_json::to_vec(&stale).unwrap()).unwrap();
+
+        let store = ContinuationStore::new(Some(path)).with_max_age(Duration::from_secs(3600));
+        assert_eq!(store.load("video1").await, None);
+
+        // 保存時に期限切れのエントリは捨てる
+        store.save("video2", "new").await.unwrap();
+        assert_eq!(store.read_all().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn corrupted_file_is_treated_as_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("continuations.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let store = ContinuationStore::new(Some(path));
+        assert_eq!(store.load("video1").await, None);
+        store.save("video1", "token").await.unwrap();
+        assert_eq!(store.load("video1").await.as_deref(), Some("token"));
+    }
+
+    #[tokio::test]
+    async fn store_without_path_is_noop() {
+        let store = ContinuationStore::new(None);
+        store.save("video1", "token").await.unwrap();
+        assert_eq!(store.load("video1").await, None);
+    }
+}
@@ -0,0 +1,207 @@
+//! 通貨換算（spec: 07_revenue.md 通貨換算）
+//!
+//! SuperChat の金額は通貨が混在するため、合計などの数値計算は基準通貨へ換算してから行う。
+//! レートはユーザーが設定した固定レート表（[`StaticRateTable`]）を使う。
+//! `exchange-rates` feature を有効にすると HTTP でレートを取得する [`CachedRateFetcher`] も使える。
+
+use std::collections::HashMap;
+
+/// 通貨の換算
+pub trait CurrencyConverter {
+    /// `from` 建ての `amount` を `to` 建てに換算する（通貨は ISO 4217 コード）。
+    /// レートがなければ None
+    fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64>;
+}
+
+/// 基準通貨に対する固定レート表
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StaticRateTable {
+    base: String,
+    /// 通貨 1 単位あたりの基準通貨建ての値
+    rates: HashMap<String, f64>,
+}
+
+impl StaticRateTable {
+    /// `rates` は通貨 1 単位あたりの `base` 建ての値（例: base = "JPY" なら `("USD", 150.0)`）。
+    /// 通貨コードは大文字に揃え、正の有限値でないレートは無視する
+    pub fn new(base: &str, rates: impl IntoIterator<Item = (String, f64)>) -> Self {
+        Self {
+            base: base.to_ascii_uppercase(),
+            rates: rates
+                .into_iter()
+                .filter(|(_, rate)| rate.is_finite() && *rate > 0.0)
+                .map(|(currency, rate)| (currency.to_ascii_uppercase(), rate))
+                .collect(),
+        }
+    }
+
+    /// 基準通貨（ISO 4217 コード）
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    fn rate(&self, currency: &str) -> Option<f64> {
+        if currency == self.base {
+            return Some(1.0);
+        }
+        self.rates.get(currency).copied()
+    }
+}
+
+impl CurrencyConverter for StaticRateTable {
+    fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        if from == to {
+            return Some(amount);
+        }
+        // 基準通貨を経由したクロスレートで換算する
+        Some(amount * self.rate(from)? / self.rate(to)?)
+    }
+}
+
+#[cfg(feature = "exchange-rates")]
+pub use fetcher::CachedRateFetcher;
+
+#[cfg(feature = "exchange-rates")]
+mod fetcher {
+    use super::{CurrencyConverter, StaticRateTable};
+    use std::sync::RwLock;
+    use std::time::{Duration, Instant};
+
+    /// HTTP で取得したレートを一定時間キャッシュする換算器
+    ///
+    /// `url` は `{"rates": {"USD": 0.0067, ...}}`（基準通貨 1 単位あたりの各通貨建ての値）を返す API。
+    /// 取得は [`CachedRateFetcher::refresh_if_stale`] で明示的に行い、`convert` は通信しない。
+    pub struct CachedRateFetcher {
+        client: reqwest::Client,
+        url: String,
+        base: String,
+        ttl: Duration,
+        cache: RwLock<Option<(Instant, StaticRateTable)>>,
+    }
+
+    impl CachedRateFetcher {
+        pub fn new(client: reqwest::Client, url: String, base: &str, ttl: Duration) -> Self {
+            Self {
+                client,
+                url,
+                base: base.to_ascii_uppercase(),
+                ttl,
+                cache: RwLock::new(None),
+            }
+        }
+
+        /// キャッシュがないか `ttl` を過ぎていればレートを取得し直す
+        ///
+        /// 取得に失敗した場合は古いキャッシュを残す。
+        pub async fn refresh_if_stale(&self) -> Result<(), reqwest::Error> {
+            let fresh = self
+                .cache
+                .read()
+                .ok()
+                .and_then(|cache| cache.as_ref().map(|(at, _)| at.elapsed() < self.ttl))
+                .unwrap_or(false);
+            if fresh {
+                return Ok(());
+            }
+
+            let body: serde_json::Value = self
+                .client
+                .get(&self.url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let table = table_from_response(&self.base, &body);
+            if let Ok(mut cache) = self.cache.write() {
+                *cache = Some((Instant::now(), table));
+            }
+            Ok(())
+        }
+    }
+
+    impl CurrencyConverter for CachedRateFetcher {
+        fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+            let cache = self.cache.read().ok()?;
+            cache.as_ref()?.1.convert(amount, from, to)
+        }
+    }
+
+    /// API のレート（基準通貨 1 単位あたりの各通貨建て）を [`StaticRateTable`] の向きに変換する
+    pub(super) fn table_from_response(base: &str, body: &serde_json::Value) -> StaticRateTable {
+        let rates = body
+            .get("rates")
+            .and_then(|v| v.as_object())
+            .into_iter()
+            .flatten()
+            .filter_map(|(currency, rate)| Some((currency.clone(), 1.0 / rate.as_f64()?)));
+        StaticRateTable::new(base, rates)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn response_rates_are_inverted() {
+            let body = serde_json::json!({ "rates": { "USD": 0.005, "JPY": 1.0, "XXX": 0.0 } });
+            let table = table_from_response("JPY", &body);
+            assert_eq!(table.convert(1.0, "USD", "JPY"), Some(200.0));
+            // 0 のレートは無限大になるため無視される
+            assert_eq!(table.convert(1.0, "XXX", "JPY"), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> StaticRateTable {
+        StaticRateTable::new(
+            "jpy",
+            [("usd".to_string(), 150.0), ("EUR".to_string(), 160.0)],
+        )
+    }
+
+    #[test]
+    fn converts_to_and_from_base() {
+        let table = table();
+        assert_eq!(table.base(), "JPY");
+        assert_eq!(table.convert(10.0, "USD", "JPY"), Some(1500.0));
+        assert_eq!(table.convert(3000.0, "JPY", "USD"), Some(20.0));
+        assert_eq!(table.convert(500.0, "JPY", "JPY"), Some(500.0));
+    }
+
+    #[test]
+    fn converts_via_cross_rate() {
+        assert_eq!(
+            table().convert(16.0, "EUR", "USD"),
+            Some(16.0 * 160.0 / 150.0)
+        );
+    }
+
+    #[test]
+    fn missing_rate_returns_none() {
+        let table = table();
+        assert_eq!(table.convert(10.0, "GBP", "JPY"), None);
+        assert_eq!(table.convert(10.0, "JPY", "GBP"), None);
+        // 同じ通貨同士はレートがなくても換算できる
+        assert_eq!(table.convert(10.0, "GBP", "GBP"), Some(10.0));
+    }
+
+    #[test]
+    fn invalid_rates_are_ignored() {
+        let table = StaticRateTable::new(
+            "JPY",
+            [
+                ("USD".to_string(), 0.0),
+                ("EUR".to_string(), -1.0),
+                ("GBP".to_string(), f64::NAN),
+            ],
+        );
+        for currency in ["USD", "EUR", "GBP"] {
+            assert_eq!(table.convert(1.0, currency, "JPY"), None);
+        }
+    }
+}
@@ -0,0 +1,521 @@
+//! Discord Webhook への通知（spec: 02_chat.md Discord への通知）
+//!
+//! 高額スーパーチャット・メンバーシップ・マイルストーンを Discord の Webhook に embed として投稿する。
+//! 投稿はキューに積んでバックグラウンドのワーカーが順に送るため、監視ループは待たされない。
+//! Discord の Webhook のレート制限（1 チャンネルあたり 30 件/分）を超えないよう投稿の間隔を空け、
+//! 429 / 5xx は `Retry-After` に従ってリトライする。失敗はログに残して破棄する。
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::core::api::{
+    ParsedAmount, RateLimitStats, RetryConfig, send_with_retry, server_retry_after,
+};
+use crate::core::models::{ChatMessage, MessageType};
+
+/// 投稿待ちのキューの長さ（溢れた通知は破棄する）
+pub const DISCORD_QUEUE_CAPACITY: usize = 64;
+
+/// 投稿の最小間隔（Webhook の上限 30 件/分に合わせる）
+pub const DISCORD_POST_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 1 回の投稿のタイムアウト
+const DISCORD_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Discord 通知の設定（config.toml の `[discord]` セクション）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscordConfig {
+    /// Discord への通知を有効にするか
+    pub enabled: bool,
+    /// Webhook の URL（`https://discord.com/api/webhooks/...`）
+    pub webhook_url: String,
+    /// 高額スーパーチャット（`donation_alert` のしきい値以上）を通知するか
+    pub big_donations: bool,
+    /// メンバーシップ（新規・継続・ギフト）を通知するか
+    pub memberships: bool,
+    /// マイルストーンを通知するか
+    pub milestones: bool,
+    /// 高額スーパーチャットの本文（`{author}` `{amount}` `{message}` を置換）
+    pub donation_template: String,
+    /// メンバーシップの本文（`{author}` `{detail}` `{message}` を置換）
+    pub membership_template: String,
+    /// マイルストーンの本文（`{message}` を置換）
+    pub milestone_template: String,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhook_url: String::new(),
+            big_donations: true,
+            memberships: true,
+            milestones: true,
+            donation_template: "**{author}** さんから {amount} のスーパーチャット\n{message}"
+                .to_string(),
+            membership_template: "**{author}** さんが{detail}\n{message}".to_string(),
+            milestone_template: "{message}".to_string(),
+        }
+    }
+}
+
+/// Webhook の URL として受け付けるか（Discord の Webhook 以外への送信を防ぐ）
+pub fn is_valid_webhook_url(url: &str) -> bool {
+    [
+        "https://discord.com/api/webhooks/",
+        "https://discordapp.com/api/webhooks/",
+    ]
+    .iter()
+    .any(|prefix| url.starts_with(prefix) && url.len() > prefix.len())
+}
+
+/// Discord に通知するイベント
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiscordEvent {
+    BigDonation {
+        author: String,
+        amount: ParsedAmount,
+        message: String,
+    },
+    Membership {
+        author: String,
+        /// 「メンバーになりました」などの内容
+        detail: String,
+        message: String,
+    },
+    Milestone {
+        text: String,
+    },
+}
+
+impl DiscordEvent {
+    /// メンバーシップのメッセージからイベントを作る（それ以外は None）
+    pub fn membership(msg: &ChatMessage) -> Option<Self> {
+        let detail = match &msg.message_type {
+            MessageType::Membership {
+                milestone_months: Some(months),
+            } => format!("メンバー継続 {}か月", months),
+            MessageType::Membership {
+                milestone_months: None,
+            } => "メンバーになりました".to_string(),
+            MessageType::MembershipGift { gift_count } => {
+                format!("メンバーシップを{}件ギフトしました", gift_count)
+            }
+            _ => return None,
+        };
+        Some(Self::Membership {
+            author: msg.author.clone(),
+            detail,
+            message: msg.content.clone(),
+        })
+    }
+
+    /// 設定で通知対象になっているか
+    fn is_enabled(&self, config: &DiscordConfig) -> bool {
+        match self {
+            DiscordEvent::BigDonation { .. } => config.big_donations,
+            DiscordEvent::Membership { .. } => config.memberships,
+            DiscordEvent::Milestone { .. } => config.milestones,
+        }
+    }
+
+    /// embed の本文
+    pub fn description(&self, config: &DiscordConfig) -> String {
+        let text = match self {
+            DiscordEvent::BigDonation {
+                author,
+                amount,
+                message,
+            } => render_template(
+                &config.donation_template,
+                &[
+                    ("author", author),
+                    ("amount", &amount.raw),
+                    ("message", message),
+                ],
+            ),
+            DiscordEvent::Membership {
+                author,
+                detail,
+                message,
+            } => render_template(
+                &config.membership_template,
+                &[("author", author), ("detail", detail), ("message", message)],
+            ),
+            DiscordEvent::Milestone { text } => {
+                render_template(&config.milestone_template, &[("message", text)])
+            }
+        };
+        text.trim().to_string()
+    }
+
+    /// Webhook に POST する JSON（embed 1 件）
+    pub fn payload(&self, config: &DiscordConfig) -> serde_json::Value {
+        let (title, color) = match self {
+            DiscordEvent::BigDonation { .. } => ("スーパーチャット", 0xE6_21_17),
+            DiscordEvent::Membership { .. } => ("メンバーシップ", 0x0F_9D_58),
+            DiscordEvent::Milestone { .. } => ("マイルストーン", 0x1E_88_E5),
+        };
+        json!({
+            "embeds": [{
+                "title": title,
+                "description": self.description(config),
+                "color": color,
+            }]
+        })
+    }
+}
+
+/// `{name}` 形式のプレースホルダーを置換する
+///
+/// 置換後の値に含まれる `{...}` は再び置換しない。未知のプレースホルダーはそのまま残す。
+pub fn render_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 投稿 1 件分
+struct DiscordPost {
+    url: String,
+    payload: serde_json::Value,
+}
+
+/// Webhook への投稿キュー
+///
+/// 最初の通知でワーカーを起動する。キューが満杯なら通知を破棄し、監視ループを止めない。
+pub struct DiscordNotifier {
+    sender: Mutex<Option<mpsc::Sender<DiscordPost>>>,
+    retry: RetryConfig,
+    interval: Duration,
+}
+
+impl Default for DiscordNotifier {
+    fn default() -> Self {
+        Self::new(RetryConfig::default(), DISCORD_POST_INTERVAL)
+    }
+}
+
+impl DiscordNotifier {
+    pub fn new(retry: RetryConfig, interval: Duration) -> Self {
+        Self {
+            sender: Mutex::new(None),
+            retry,
+            interval,
+        }
+    }
+
+    /// 設定で有効なら通知をキューに積む（送信は待たない）
+    pub fn notify(&self, config: &DiscordConfig, event: &DiscordEvent) {
+        if !config.enabled || !event.is_enabled(config) {
+            return;
+        }
+        if !is_valid_webhook_url(&config.webhook_url) {
+            tracing::warn!("Discord の Webhook URL が不正なため通知しません");
+            return;
+        }
+        self.enqueue(config.webhook_url.clone(), event.payload(config));
+    }
+
+    fn enqueue(&self, url: String, payload: serde_json::Value) {
+        let Ok(mut sender) = self.sender.lock() else {
+            return;
+        };
+        if sender.as_ref().is_none_or(|tx| tx.is_closed()) {
+            let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                tracing::warn!("Discord への通知をスキップ: 非同期ランタイムの外から呼ばれました");
+                return;
+            };
+            let (tx, rx) = mpsc::channel(DISCORD_QUEUE_CAPACITY);
+            runtime.spawn(run_worker(rx, self.retry.clone(), self.interval));
+            *sender = Some(tx);
+        }
+        let Some(tx) = sender.as_ref() else {
+            return;
+        };
+        if let Err(e) = tx.try_send(DiscordPost { url, payload }) {
+            tracing::warn!("Discord への通知を破棄: {}", e);
+        }
+    }
+}
+
+/// キューの投稿を `interval` 以上の間隔で順に送る
+///
+/// 429 の `Retry-After` がリトライの上限より長ければ、その間は何も送らずに待ってから
+/// 同じ投稿を 1 回だけ送り直す（早めの再送は不正なリクエストとして数えられるため）。
+async fn run_worker(mut rx: mpsc::Receiver<DiscordPost>, retry: RetryConfig, interval: Duration) {
+    let client = reqwest::Client::builder()
+        .timeout(DISCORD_REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to create HTTP client");
+    let mut rate_limits = RateLimitStats::default();
+    while let Some(post) = rx.recv().await {
+        let mut resent = false;
+        loop {
+            let result = send_with_retry(&retry, &mut rate_limits, || {
+                client.post(&post.url).json(&post.payload)
+            })
+            .await;
+            match result {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => {
+                    tracing::warn!("Discord への通知に失敗: HTTP {}", response.status());
+                }
+                Err(e) => match server_retry_after(&e) {
+                    Some(wait) if !resent => {
+                        tracing::warn!(
+                            "Discord のレート制限 — {}秒待ってから送り直す",
+                            wait.as_secs()
+                        );
+                        tokio::time::sleep(wait.max(interval)).await;
+                        resent = true;
+                        continue;
+                    }
+                    Some(wait) => {
+                        tracing::warn!("Discord への通知に失敗: {}", e);
+                        tokio::time::sleep(wait).await;
+                    }
+                    None => tracing::warn!("Discord への通知に失敗: {}", e),
+                },
+            }
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::serve;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    const NO_CONTENT: &str = "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n";
+    const TOO_MANY_REQUESTS: &str = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    /// `responses` を順に返し、2xx を返したリクエストの本文を送るモックサーバー
+    async fn spawn_server(
+        responses: Vec<&'static str>,
+    ) -> (String, mpsc::UnboundedReceiver<serde_json::Value>) {
+        let accepted: Vec<bool> = responses
+            .iter()
+            .map(|response| response.starts_with("HTTP/1.1 2"))
+            .collect();
+        let (addr, mut requests) = serve(responses).await;
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for accepted in accepted {
+                let Some(request) = requests.recv().await else {
+                    return;
+                };
+                if accepted {
+                    let _ = tx.send(serde_json::from_slice(&request.body).unwrap());
+                }
+            }
+        });
+        (format!("http://{}/api/webhooks/1/token", addr), rx)
+    }
+
+    fn no_jitter() -> RetryConfig {
+        RetryConfig {
+            max_retries: 2,
+            base_delay_ms: 1,
+            max_delay_ms: 5,
+            jitter: false,
+        }
+    }
+
+    fn donation() -> DiscordEvent {
+        DiscordEvent::BigDonation {
+            author: "Alice".to_string(),
+            amount: ParsedAmount {
+                currency: "JPY".to_string(),
+                amount: 10000.0,
+                raw: "¥10,000".to_string(),
+            },
+            message: "応援してます".to_string(),
+        }
+    }
+
+    fn enabled_config() -> DiscordConfig {
+        DiscordConfig {
+            enabled: true,
+            webhook_url: "https://discord.com/api/webhooks/1/token".to_string(),
+            ..DiscordConfig::default()
+        }
+    }
+
+    #[test]
+    fn render_template_replaces_known_placeholders_once() {
+        let rendered = render_template(
+            "{author}: {amount} {unknown} {",
+            &[("author", "{amount}"), ("amount", "¥500")],
+        );
+        assert_eq!(rendered, "{amount}: ¥500 {unknown} {");
+    }
+
+    #[test]
+    fn default_templates_render_each_event() {
+        let config = DiscordConfig::default();
+        assert_eq!(
+            donation().description(&config),
+            "**Alice** さんから ¥10,000 のスーパーチャット\n応援してます"
+        );
+
+        let msg = ChatMessage {
+            author: "Bob".to_string(),
+            message_type: MessageType::Membership {
+                milestone_months: Some(12),
+            },
+            ..Default::default()
+        };
+        let membership = DiscordEvent::membership(&msg).unwrap();
+        assert_eq!(
+            membership.description(&config),
+            "**Bob** さんがメンバー継続 12か月"
+        );
+
+        let milestone = DiscordEvent::Milestone {
+            text: "ユニーク投稿者が100人に到達しました".to_string(),
+        };
+        let payload = milestone.payload(&config);
+        assert_eq!(payload["embeds"][0]["title"], "マイルストーン");
+        assert_eq!(
+            payload["embeds"][0]["description"],
+            "ユニーク投稿者が100人に到達しました"
+        );
+    }
+
+    #[test]
+    fn membership_ignores_other_message_types() {
+        assert!(DiscordEvent::membership(&ChatMessage::default()).is_none());
+    }
+
+    #[test]
+    fn webhook_url_must_be_discord() {
+        assert!(is_valid_webhook_url(
+            "https://discord.com/api/webhooks/123/abc"
+        ));
+        assert!(is_valid_webhook_url(
+            "https://discordapp.com/api/webhooks/123/abc"
+        ));
+        assert!(!is_valid_webhook_url("https://discord.com/api/webhooks/"));
+        assert!(!is_valid_webhook_url("http://discord.com/api/webhooks/1/a"));
+        assert!(!is_valid_webhook_url(
+            "https://example.com/api/webhooks/1/a"
+        ));
+    }
+
+    #[tokio::test]
+    async fn notify_skips_disabled_events_and_invalid_url() {
+        let notifier = DiscordNotifier::default();
+        notifier.notify(&DiscordConfig::default(), &donation());
+        let config = DiscordConfig {
+            big_donations: false,
+            ..enabled_config()
+        };
+        notifier.notify(&config, &donation());
+        let config = DiscordConfig {
+            webhook_url: "https://example.com/hook".to_string(),
+            ..enabled_config()
+        };
+        notifier.notify(&config, &donation());
+        // どれもキューに積まれないのでワーカーも起動しない
+        assert!(notifier.sender.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn worker_posts_embeds_in_order_and_retries_rate_limits() {
+        let (url, mut received) =
+            spawn_server(vec![TOO_MANY_REQUESTS, NO_CONTENT, NO_CONTENT]).await;
+        let notifier = Arc::new(DiscordNotifier::new(no_jitter(), Duration::ZERO));
+        let config = enabled_config();
+
+        // テストではモックサーバーの URL に直接投稿する
+        notifier.enqueue(url.clone(), donation().payload(&config));
+        let milestone = DiscordEvent::Milestone {
+            text: "接続から1時間が経過しました".to_string(),
+        };
+        notifier.enqueue(url, milestone.payload(&config));
+
+        let first = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first["embeds"][0]["title"], "スーパーチャット");
+        assert_eq!(
+            first["embeds"][0]["description"],
+            "**Alice** さんから ¥10,000 のスーパーチャット\n応援してます"
+        );
+        let second = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            second["embeds"][0]["description"],
+            "接続から1時間が経過しました"
+        );
+    }
+
+    #[tokio::test]
+    async fn worker_waits_for_long_retry_after_before_resending() {
+        let (url, mut received) = spawn_server(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            NO_CONTENT,
+        ])
+        .await;
+        // 上限（5ms）を超える Retry-After はリトライの中で待たない
+        let notifier = DiscordNotifier::new(no_jitter(), Duration::ZERO);
+        let started = std::time::Instant::now();
+        notifier.enqueue(url, donation().payload(&enabled_config()));
+
+        let posted = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(posted["embeds"][0]["title"], "スーパーチャット");
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn full_queue_drops_without_blocking() {
+        // 応答しないサーバーでワーカーを止めたまま、キューの上限を超えて積む
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let notifier = DiscordNotifier::new(RetryConfig::disabled(), Duration::ZERO);
+        for _ in 0..DISCORD_QUEUE_CAPACITY + 10 {
+            notifier.enqueue(url.clone(), json!({}));
+        }
+        let capacity = notifier.sender.lock().unwrap().as_ref().unwrap().capacity();
+        assert_eq!(capacity, 0);
+        drop(listener);
+    }
+}
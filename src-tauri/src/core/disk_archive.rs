@@ -0,0 +1,403 @@
+//! メモリから外れたメッセージのディスクアーカイブ（spec: 02_chat.md メッセージのディスクアーカイブ）
+//!
+//! 1 行 1 メッセージの NDJSON に追記する。検索はファイルを先頭から 1 行ずつ読み、
+//! 全体をメモリに載せない。監視中の追記は [`ArchiveWriter`] が専用のスレッドでまとめて行う。
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use crate::core::models::ChatMessage;
+
+/// 検索結果の 1 ページ（新しい順の `offset` 件目から最大 `limit` 件）
+#[derive(Debug, Clone, Default)]
+pub struct SearchPage {
+    pub messages: Vec<ChatMessage>,
+    /// 一致した件数の合計（ページに含まれないものも数える）
+    pub total: usize,
+}
+
+/// NDJSON のメッセージアーカイブ
+#[derive(Debug, Clone)]
+pub struct DiskArchive {
+    path: PathBuf,
+}
+
+impl DiskArchive {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// アーカイブファイルのパス
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// メッセージを末尾に追記する（ファイル・親ディレクトリがなければ作る）
+    pub fn append(&self, messages: &[ChatMessage]) -> std::io::Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let mut writer = self.open_append()?;
+        write_messages(&mut writer, messages)?;
+        writer.flush()
+    }
+
+    /// 追記用に開く（ファイル・親ディレクトリがなければ作る）
+    fn open_append(&self) -> std::io::Result<BufWriter<File>> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        Ok(BufWriter::new(file))
+    }
+
+    /// 本文に `query` を含むメッセージを新しい順に `offset` 件目から最大 `limit` 件返す
+    pub fn search_by_content(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> std::io::Result<SearchPage> {
+        let query = query.to_lowercase();
+        self.search(|msg| content_matches(msg, &query), offset, limit)
+    }
+
+    /// 投稿者名に `query` を含むメッセージを新しい順に `offset` 件目から最大 `limit` 件返す
+    pub fn search_by_author(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> std::io::Result<SearchPage> {
+        let query = query.to_lowercase();
+        self.search(|msg| author_matches(msg, &query), offset, limit)
+    }
+
+    /// 条件に一致するメッセージを新しい順に `offset` 件目から最大 `limit` 件返す
+    ///
+    /// 1 回目の走査で一致件数を数え、2 回目で該当範囲だけを読み込む（保持するのは `limit` 件まで）。
+    /// 走査の間に追記された行は数えない。ファイルがなければ空を返す。
+    fn search(
+        &self,
+        matches: impl Fn(&ChatMessage) -> bool,
+        offset: usize,
+        limit: usize,
+    ) -> std::io::Result<SearchPage> {
+        let mut total = 0;
+        self.scan(&matches, |_| {
+            total += 1;
+            true
+        })?;
+        if limit == 0 || offset >= total {
+            return Ok(SearchPage {
+                messages: Vec::new(),
+                total,
+            });
+        }
+
+        // 新しい順の [offset, offset + limit) は、古い順の [start, end)
+        let end = total - offset;
+        let start = end.saturating_sub(limit);
+        let mut index = 0;
+        let mut messages = Vec::with_capacity(end - start);
+        self.scan(&matches, |message| {
+            if index >= start {
+                messages.push(message);
+            }
+            index += 1;
+            index < end
+        })?;
+        messages.reverse();
+        Ok(SearchPage { messages, total })
+    }
+
+    /// 条件に一致するメッセージを古い順に `visit` へ渡す（`visit` が false を返したら止める）
+    ///
+    /// 読めない行（書き込み途中で終了した行など）は読み飛ばす。
+    fn scan(
+        &self,
+        matches: &impl Fn(&ChatMessage) -> bool,
+        mut visit: impl FnMut(ChatMessage) -> bool,
+    ) -> std::io::Result<()> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let Ok(message) = serde_json::from_str::<ChatMessage>(&line) else {
+                continue;
+            };
+            if matches(&message) && !visit(message) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// メッセージを 1 行ずつ書き込む（フラッシュはしない）
+fn write_messages(writer: &mut impl Write, messages: &[ChatMessage]) -> std::io::Result<()> {
+    for message in messages {
+        serde_json::to_writer(&mut *writer, message)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// 書き込み用のスレッドへの依頼
+enum WriteRequest {
+    Append {
+        path: PathBuf,
+        messages: Vec<ChatMessage>,
+    },
+    Flush(tokio::sync::oneshot::Sender<()>),
+}
+
+/// アーカイブへの追記を専用のスレッドで行う書き込み口
+///
+/// 監視タスクは `append` で依頼するだけで、tokio のワーカーでファイル I/O を行わない。
+/// スレッドは追記用のハンドルを開いたまま持ち、溜まった依頼をまとめて書き込んでからフラッシュする。
+/// すべての `ArchiveWriter` が破棄されるとスレッドも終わる。
+#[derive(Debug, Clone)]
+pub struct ArchiveWriter {
+    tx: mpsc::Sender<WriteRequest>,
+}
+
+impl Default for ArchiveWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchiveWriter {
+    /// 書き込み用のスレッドを起動する
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("disk-archive".to_string())
+            .spawn(move || run_writer(rx))
+            .expect("failed to spawn the disk archive writer");
+        Self { tx }
+    }
+
+    /// `path` のアーカイブへの追記を依頼する（書き込みは待たない）
+    pub fn append(&self, path: PathBuf, messages: Vec<ChatMessage>) {
+        if messages.is_empty() {
+            return;
+        }
+        if self
+            .tx
+            .send(WriteRequest::Append { path, messages })
+            .is_err()
+        {
+            tracing::warn!("ディスクアーカイブの書き込みスレッドが終了している");
+        }
+    }
+
+    /// それまでに依頼した追記を書き込み終えるまで待つ
+    pub async fn flush(&self) {
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        if self.tx.send(WriteRequest::Flush(done_tx)).is_ok() {
+            let _ = done_rx.await;
+        }
+    }
+}
+
+/// 依頼が届くたびに、溜まっている分をまとめて書き込んでフラッシュする
+fn run_writer(rx: mpsc::Receiver<WriteRequest>) {
+    // 最後に書き込んだアーカイブ（設定でパスが変わったら開き直す）
+    let mut current: Option<(PathBuf, BufWriter<File>)> = None;
+    while let Ok(first) = rx.recv() {
+        let mut waiting = Vec::new();
+        for request in std::iter::once(first).chain(rx.try_iter()) {
+            match request {
+                WriteRequest::Append { path, messages } => {
+                    if let Err(e) = append_to(&mut current, path, &messages) {
+                        tracing::warn!("ディスクアーカイブへの書き込みに失敗: {}", e);
+                        current = None;
+                    }
+                }
+                WriteRequest::Flush(done) => waiting.push(done),
+            }
+        }
+        let flushed = current
+            .as_mut()
+            .map_or(Ok(()), |(_, writer)| writer.flush());
+        if let Err(e) = flushed {
+            tracing::warn!("ディスクアーカイブへの書き込みに失敗: {}", e);
+            current = None;
+        }
+        for done in waiting {
+            let _ = done.send(());
+        }
+    }
+}
+
+/// 開いているハンドルに追記する（別のパスなら書き込み済みの分をフラッシュして開き直す）
+fn append_to(
+    current: &mut Option<(PathBuf, BufWriter<File>)>,
+    path: PathBuf,
+    messages: &[ChatMessage],
+) -> std::io::Result<()> {
+    if current.as_ref().is_none_or(|(open, _)| *open != path) {
+        if let Some((_, mut previous)) = current.take() {
+            previous.flush()?;
+        }
+        let writer = DiskArchive::new(path.clone()).open_append()?;
+        *current = Some((path, writer));
+    }
+    match current.as_mut() {
+        Some((_, writer)) => write_messages(writer, messages),
+        None => Ok(()),
+    }
+}
+
+/// 本文に小文字化済みの `query` を含むか（大文字小文字を区別しない）
+pub fn content_matches(message: &ChatMessage, query: &str) -> bool {
+    message.content.to_lowercase().contains(query)
+}
+
+/// 投稿者名に小文字化済みの `query` を含むか（大文字小文字を区別しない）
+pub fn author_matches(message: &ChatMessage, query: &str) -> bool {
+    message.author.to_lowercase().contains(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, author: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            id: id.to_string(),
+            author: author.to_string(),
+            content: content.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn search_returns_newest_matches_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = DiskArchive::new(dir.path().join("archive/chat.ndjson"));
+        archive
+            .append(&[
+                message("1", "Alice", "Hello world"),
+                message("2", "Bob", "こんにちは"),
+            ])
+            .unwrap();
+        archive
+            .append(&[message("3", "alice2", "HELLO again")])
+            .unwrap();
+
+        let ids = |page: SearchPage| page.messages.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(archive.search_by_content("hello", 0, 10).unwrap()),
+            ["3", "1"]
+        );
+        assert_eq!(
+            ids(archive.search_by_content("hello", 0, 1).unwrap()),
+            ["3"]
+        );
+        assert_eq!(
+            ids(archive.search_by_author("ALICE", 0, 10).unwrap()),
+            ["3", "1"]
+        );
+        assert_eq!(
+            ids(archive.search_by_content("こんにちは", 0, 10).unwrap()),
+            ["2"]
+        );
+    }
+
+    #[test]
+    fn search_pages_through_matches_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = DiskArchive::new(dir.path().join("chat.ndjson"));
+        let messages: Vec<ChatMessage> = (0..10)
+            .map(|i| {
+                let content = if i % 3 == 0 { "skip" } else { "hit" };
+                message(&i.to_string(), "A", content)
+            })
+            .collect();
+        archive.append(&messages).unwrap();
+
+        // 一致するのは 1, 2, 4, 5, 7, 8（新しい順に 8, 7, 5, 4, 2, 1）
+        let page = |offset, limit| {
+            let page = archive.search_by_content("hit", offset, limit).unwrap();
+            let ids: Vec<String> = page.messages.into_iter().map(|m| m.id).collect();
+            (ids, page.total)
+        };
+        assert_eq!(page(0, 2), (vec!["8".into(), "7".into()], 6));
+        assert_eq!(page(2, 2), (vec!["5".into(), "4".into()], 6));
+        assert_eq!(page(4, 10), (vec!["2".into(), "1".into()], 6));
+        assert_eq!(page(6, 2), (vec![], 6));
+        assert_eq!(page(1, 0), (vec![], 6));
+    }
+
+    #[tokio::test]
+    async fn writer_appends_in_order_and_follows_path_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = DiskArchive::new(dir.path().join("archive/first.ndjson"));
+        let second = DiskArchive::new(dir.path().join("second.ndjson"));
+        let writer = ArchiveWriter::new();
+
+        writer.append(first.path().to_path_buf(), vec![message("1", "A", "x")]);
+        writer.append(
+            first.path().to_path_buf(),
+            vec![message("2", "A", "x"), message("3", "A", "x")],
+        );
+        writer.append(second.path().to_path_buf(), vec![message("4", "A", "x")]);
+        writer.append(first.path().to_path_buf(), vec![message("5", "A", "x")]);
+        writer.flush().await;
+
+        let ids = |archive: &DiskArchive| {
+            let page = archive.search_by_content("x", 0, 10).unwrap();
+            page.messages.into_iter().map(|m| m.id).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&first), ["5", "3", "2", "1"]);
+        assert_eq!(ids(&second), ["4"]);
+    }
+
+    #[tokio::test]
+    async fn writer_keeps_going_after_a_failed_write() {
+        let dir = tempfile::tempdir().unwrap();
+        // ディレクトリには追記できない
+        let broken = dir.path().join("dir");
+        std::fs::create_dir(&broken).unwrap();
+        let archive = DiskArchive::new(dir.path().join("chat.ndjson"));
+        let writer = ArchiveWriter::new();
+
+        writer.append(broken, vec![message("1", "A", "x")]);
+        writer.append(archive.path().to_path_buf(), vec![message("2", "A", "x")]);
+        writer.flush().await;
+
+        let page = archive.search_by_content("x", 0, 10).unwrap();
+        assert_eq!(page.total, 1);
+    }
+
+    #[test]
+    fn search_skips_broken_lines_and_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = DiskArchive::new(dir.path().join("chat.ndjson"));
+        let empty = archive.search_by_content("x", 0, 10).unwrap();
+        assert!(empty.messages.is_empty());
+        assert_eq!(empty.total, 0);
+
+        archive.append(&[message("1", "A", "x")]).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(archive.path())
+            .unwrap()
+            .write_all(b"{\"id\":\"trunc")
+            .unwrap();
+
+        let page = archive.search_by_content("x", 0, 10).unwrap();
+        assert_eq!((page.messages.len(), page.total), (1, 1));
+    }
+}
@@ -0,0 +1,181 @@
+//! テキスト中の絵文字の数え方（spec: 02_chat.md エンゲージメントの推移）
+//!
+//! ZWJ で結合した家族の絵文字や肌の色の修飾子付きの絵文字は複数のコードポイントからなるため、
+//! 書記素クラスタ単位で区切り、見た目の 1 文字を 1 個として数える。
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Extended_Pictographic（Unicode emoji-data.txt）のコードポイント範囲
+///
+/// 国旗（Regional Indicator）とキーキャップは [`is_emoji_cluster`] で別に判定する。
+const EXTENDED_PICTOGRAPHIC: &[(u32, u32)] = &[
+    (0x00A9, 0x00A9),
+    (0x00AE, 0x00AE),
+    (0x203C, 0x203C),
+    (0x2049, 0x2049),
+    (0x2122, 0x2122),
+    (0x2139, 0x2139),
+    (0x2194, 0x2199),
+    (0x21A9, 0x21AA),
+    (0x231A, 0x231B),
+    (0x2328, 0x2328),
+    (0x2388, 0x2388),
+    (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3),
+    (0x23F8, 0x23FA),
+    (0x24C2, 0x24C2),
+    (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6),
+    (0x25C0, 0x25C0),
+    (0x25FB, 0x25FE),
+    (0x2600, 0x2605),
+    (0x2607, 0x2612),
+    (0x2614, 0x2685),
+    (0x2690, 0x2705),
+    (0x2708, 0x2712),
+    (0x2714, 0x2714),
+    (0x2716, 0x2716),
+    (0x271D, 0x271D),
+    (0x2721, 0x2721),
+    (0x2728, 0x2728),
+    (0x2733, 0x2734),
+    (0x2744, 0x2744),
+    (0x2747, 0x2747),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2763, 0x2767),
+    (0x2795, 0x2797),
+    (0x27A1, 0x27A1),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2934, 0x2935),
+    (0x2B05, 0x2B07),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x3030, 0x3030),
+    (0x303D, 0x303D),
+    (0x3297, 0x3297),
+    (0x3299, 0x3299),
+    (0x1F000, 0x1F0FF),
+    (0x1F10D, 0x1F10F),
+    (0x1F12F, 0x1F12F),
+    (0x1F16C, 0x1F171),
+    (0x1F17E, 0x1F17F),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1AD, 0x1F1E5),
+    (0x1F201, 0x1F20F),
+    (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F23A),
+    (0x1F23C, 0x1F23F),
+    (0x1F249, 0x1F3FA),
+    (0x1F400, 0x1F53D),
+    (0x1F546, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F774, 0x1F77F),
+    (0x1F7D5, 0x1F7FF),
+    (0x1F80C, 0x1F80F),
+    (0x1F848, 0x1F84F),
+    (0x1F85A, 0x1F85F),
+    (0x1F888, 0x1F88F),
+    (0x1F8AE, 0x1F8FF),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1FAFF),
+    (0x1FC00, 0x1FFFD),
+];
+
+/// 国旗を構成する Regional Indicator の範囲
+const REGIONAL_INDICATORS: (u32, u32) = (0x1F1E6, 0x1F1FF);
+
+/// キーキャップの結合文字（`1️⃣` など）
+const COMBINING_ENCLOSING_KEYCAP: char = '\u{20E3}';
+
+fn is_extended_pictographic(c: char) -> bool {
+    let cp = u32::from(c);
+    EXTENDED_PICTOGRAPHIC
+        .binary_search_by(|&(start, end)| {
+            if end < cp {
+                std::cmp::Ordering::Less
+            } else if start > cp {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// 書記素クラスタが絵文字か
+fn is_emoji_cluster(cluster: &str) -> bool {
+    cluster.chars().any(|c| {
+        let cp = u32::from(c);
+        is_extended_pictographic(c)
+            || (REGIONAL_INDICATORS.0..=REGIONAL_INDICATORS.1).contains(&cp)
+            || c == COMBINING_ENCLOSING_KEYCAP
+    })
+}
+
+/// テキスト中の絵文字を見た目の 1 文字ずつ数える
+///
+/// ZWJ シーケンス・肌の色の修飾子・国旗・キーキャップはそれぞれ 1 個と数える。
+pub fn count_emojis(text: &str) -> usize {
+    // ASCII だけのテキストには絵文字がない（大半のメッセージはここで終わる）
+    if text.is_ascii() {
+        return 0;
+    }
+    text.graphemes(true)
+        .filter(|cluster| is_emoji_cluster(cluster))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted_and_disjoint() {
+        assert!(
+            EXTENDED_PICTOGRAPHIC
+                .windows(2)
+                .all(|w| w[0].0 <= w[0].1 && w[0].1 < w[1].0)
+        );
+    }
+
+    #[test]
+    fn counts_zwj_family_as_one() {
+        assert_eq!(count_emojis("👨\u{200D}👩\u{200D}👧\u{200D}👦"), 1);
+        assert_eq!(count_emojis("家族👨‍👩‍👧‍👦と👨‍👩‍👧‍👦"), 2);
+    }
+
+    #[test]
+    fn counts_skin_tone_variant_as_one() {
+        assert_eq!(count_emojis("👍🏽"), 1);
+        assert_eq!(count_emojis("👍👍🏿"), 2);
+    }
+
+    #[test]
+    fn counts_flags_as_one() {
+        assert_eq!(count_emojis("🇯🇵"), 1);
+        assert_eq!(count_emojis("🇯🇵🇺🇸 がんばれ"), 2);
+    }
+
+    #[test]
+    fn counts_keycaps_but_not_plain_digits() {
+        assert_eq!(count_emojis("1\u{FE0F}\u{20E3}"), 1);
+        assert_eq!(count_emojis("#️⃣ 1 2 3"), 1);
+        assert_eq!(count_emojis("123"), 0);
+    }
+
+    #[test]
+    fn ignores_plain_text() {
+        assert_eq!(count_emojis(""), 0);
+        assert_eq!(count_emojis("hello?"), 0);
+        assert_eq!(count_emojis("こんにちは、初見です！"), 0);
+        assert_eq!(count_emojis("草www ✨🎉"), 2);
+    }
+}
@@ -0,0 +1,484 @@
+//! エンゲージメントの推移（spec: 02_chat.md エンゲージメントの推移）
+//!
+//! 受信したメッセージを集計し、一定間隔ごとにスナップショットを記録する。
+//! メッセージバッファは上限を超えると古いものから捨てるため、推移のグラフは
+//! 過去のメッセージから計算し直さずにこのスナップショットから描く。
+//! ヒートマップ用のメッセージの密度も、投稿時刻の秒ごとの件数から求める。
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::core::api::usec_to_datetime;
+use crate::core::emoji;
+use crate::core::models::{ChatMessage, MessageRun, MessageType};
+
+/// エンゲージメントの推移の設定（config.toml の `[engagement]` セクション）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngagementConfig {
+    /// スナップショットを記録する間隔（秒）
+    pub snapshot_interval_secs: u64,
+    /// 保持するスナップショットの最大件数（超えたら古いものから捨てる）
+    pub max_snapshots: usize,
+}
+
+impl Default for EngagementConfig {
+    fn default() -> Self {
+        Self {
+            snapshot_interval_secs: 60,
+            // 1 分間隔で 6 時間分
+            max_snapshots: 360,
+        }
+    }
+}
+
+/// ある時点のエンゲージメント
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct EngagementSnapshot {
+    /// 記録した時刻（RFC3339、UTC）
+    pub at: String,
+    /// 集計を始めてからのユニーク投稿者数
+    pub unique_viewers: u32,
+    /// 前回のスナップショット以降のメッセージ数
+    pub messages: u32,
+    /// 前回のスナップショット以降の 1 分あたりのメッセージ数
+    pub engagement_rate: f64,
+    /// 前回のスナップショット以降のメッセージのうち絵文字を含むものの割合（0.0〜1.0）
+    pub emoji_rate: f64,
+    /// 前回のスナップショット以降の絵文字の数（カスタム絵文字を含む）
+    pub emojis: u32,
+    /// 前回のスナップショット以降の質問（「?」「？」を含むメッセージ）の数
+    pub questions: u32,
+}
+
+/// 密度の重み付けで、スーパーチャット・スーパーステッカー 1 件を何件分とみなすか
+pub const PAID_MESSAGE_WEIGHT: usize = 5;
+/// 密度の重み付けで、メンバーシップ（新規・継続・ギフト）1 件を何件分とみなすか
+pub const MEMBERSHIP_WEIGHT: usize = 3;
+/// 密度の集計に残す期間（秒、最も新しい投稿時刻から数える）
+const DENSITY_RETENTION_SECS: i64 = 24 * 60 * 60;
+
+/// 投稿時刻の 1 秒あたりの件数
+#[derive(Debug, Clone, Copy, Default)]
+struct SecondCounts {
+    messages: usize,
+    /// [`event_weight`] で重み付けした件数
+    weighted: usize,
+}
+
+/// メッセージを集計してスナップショットを記録する（全接続で共有する）
+#[derive(Debug, Default)]
+pub struct EngagementMetrics {
+    /// 投稿したことのあるチャンネル ID
+    viewers: HashSet<String>,
+    /// 前回のスナップショット以降のメッセージ数
+    messages: u32,
+    /// 前回のスナップショット以降の絵文字を含むメッセージ数
+    emoji_messages: u32,
+    /// 前回のスナップショット以降の絵文字の数
+    emojis: u32,
+    /// 前回のスナップショット以降の質問の数
+    questions: u32,
+    /// 集計区間の開始時刻（前回のスナップショット、なければ最初のメッセージの受信時刻）
+    interval_started_at: Option<DateTime<Utc>>,
+    /// 記録したスナップショット（古い順）
+    snapshots: Vec<EngagementSnapshot>,
+    /// 投稿時刻（UTC の秒）ごとの件数（`DENSITY_RETENTION_SECS` の間だけ残す）
+    density: BTreeMap<i64, SecondCounts>,
+}
+
+impl EngagementMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 受信したメッセージを集計する（システムメッセージは数えない）
+    pub fn record(&mut self, msg: &ChatMessage, now: DateTime<Utc>) {
+        if msg.message_type == MessageType::System {
+            return;
+        }
+        self.interval_started_at.get_or_insert(now);
+        let viewer = if msg.channel_id.is_empty() {
+            &msg.author
+        } else {
+            &msg.channel_id
+        };
+        if !self.viewers.contains(viewer) {
+            self.viewers.insert(viewer.clone());
+        }
+        self.messages += 1;
+        let emojis = count_emojis(msg);
+        if emojis > 0 {
+            self.emoji_messages += 1;
+            self.emojis = self
+                .emojis
+                .saturating_add(u32::try_from(emojis).unwrap_or(u32::MAX));
+        }
+        if msg.content.contains(['?', '？']) {
+            self.questions += 1;
+        }
+
+        // 投稿時刻がなければ受信時刻で数える
+        let posted_at = usec_to_datetime(&msg.timestamp_usec)
+            .unwrap_or(now)
+            .timestamp();
+        let counts = self.density.entry(posted_at).or_default();
+        counts.messages += 1;
+        counts.weighted += event_weight(msg);
+        if let Some(&newest) = self.density.keys().next_back() {
+            while self
+                .density
+                .first_key_value()
+                .is_some_and(|(&oldest, _)| newest - oldest > DENSITY_RETENTION_SECS)
+            {
+                self.density.pop_first();
+            }
+        }
+    }
+
+    /// 現時点のスナップショットを作って記録し、集計区間を `at` から始め直す
+    ///
+    /// 記録したスナップショットが `max_snapshots` を超えたら古いものから捨てる。
+    pub fn take_snapshot(&mut self, at: DateTime<Utc>, max_snapshots: usize) -> EngagementSnapshot {
+        let minutes = self
+            .interval_started_at
+            .and_then(|started| (at - started).to_std().ok())
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64() / 60.0);
+        let rate = |count: u32, total: f64| {
+            if total > 0.0 {
+                f64::from(count) / total
+            } else {
+                0.0
+            }
+        };
+        let snapshot = EngagementSnapshot {
+            at: at.to_rfc3339(),
+            unique_viewers: u32::try_from(self.viewers.len()).unwrap_or(u32::MAX),
+            messages: self.messages,
+            engagement_rate: rate(self.messages, minutes),
+            emoji_rate: rate(self.emoji_messages, f64::from(self.messages)),
+            emojis: self.emojis,
+            questions: self.questions,
+        };
+
+        self.messages = 0;
+        self.emoji_messages = 0;
+        self.emojis = 0;
+        self.questions = 0;
+        self.interval_started_at = Some(at);
+        self.snapshots.push(snapshot.clone());
+        let excess = self.snapshots.len().saturating_sub(max_snapshots);
+        self.snapshots.drain(..excess);
+        snapshot
+    }
+
+    /// 前回のスナップショットから設定の間隔が経っていればスナップショットを記録する
+    ///
+    /// メッセージを 1 件も受信していないうちは記録しない。
+    pub fn maybe_sample(
+        &mut self,
+        config: &EngagementConfig,
+        now: DateTime<Utc>,
+    ) -> Option<EngagementSnapshot> {
+        let started = self.interval_started_at?;
+        let interval = Duration::from_secs(config.snapshot_interval_secs.max(1));
+        let due = (now - started)
+            .to_std()
+            .is_ok_and(|elapsed| elapsed >= interval);
+        due.then(|| self.take_snapshot(now, config.max_snapshots))
+    }
+
+    /// 記録したスナップショット（古い順）
+    pub fn snapshots(&self) -> &[EngagementSnapshot] {
+        &self.snapshots
+    }
+
+    /// 投稿時刻を `bucket` ごとに区切ったメッセージ数（古い順）
+    ///
+    /// 区間は UNIX 時刻を `bucket` の倍数で区切り、開始時刻と件数を返す。最初と最後のメッセージの
+    /// 間でメッセージのない区間も 0 件として含める。`bucket` は秒単位に切り捨てる（最小 1 秒）。
+    pub fn density_buckets(&self, bucket: Duration) -> Vec<(DateTime<Utc>, usize)> {
+        self.buckets(bucket, |counts| counts.messages)
+    }
+
+    /// [`density_buckets`](Self::density_buckets) の件数を [`event_weight`] で重み付けしたもの
+    pub fn weighted_density_buckets(&self, bucket: Duration) -> Vec<(DateTime<Utc>, usize)> {
+        self.buckets(bucket, |counts| counts.weighted)
+    }
+
+    fn buckets(
+        &self,
+        bucket: Duration,
+        value: impl Fn(&SecondCounts) -> usize,
+    ) -> Vec<(DateTime<Utc>, usize)> {
+        let size = i64::try_from(bucket.as_secs().max(1)).unwrap_or(i64::MAX);
+        let (Some(&first), Some(&last)) =
+            (self.density.keys().next(), self.density.keys().next_back())
+        else {
+            return Vec::new();
+        };
+        let first_bucket = first.div_euclid(size);
+        let len = usize::try_from(last.div_euclid(size) - first_bucket + 1).unwrap_or(0);
+        let mut buckets: Vec<(DateTime<Utc>, usize)> = (0..len)
+            .map(|i| {
+                let start = (first_bucket + i as i64) * size;
+                (DateTime::from_timestamp(start, 0).unwrap_or_default(), 0)
+            })
+            .collect();
+        for (&second, counts) in &self.density {
+            let index = (second.div_euclid(size) - first_bucket) as usize;
+            buckets[index].1 += value(counts);
+        }
+        buckets
+    }
+}
+
+/// 密度の重み付けでメッセージ 1 件を何件分とみなすか
+///
+/// スーパーチャット・スーパーステッカーは [`PAID_MESSAGE_WEIGHT`]、メンバーシップは
+/// [`MEMBERSHIP_WEIGHT`]、それ以外は 1。
+pub fn event_weight(msg: &ChatMessage) -> usize {
+    match msg.message_type {
+        MessageType::SuperChat { .. } | MessageType::SuperSticker { .. } => PAID_MESSAGE_WEIGHT,
+        MessageType::Membership { .. } | MessageType::MembershipGift { .. } => MEMBERSHIP_WEIGHT,
+        MessageType::Text | MessageType::System => 1,
+    }
+}
+
+/// メッセージ中の絵文字の数
+///
+/// カスタム絵文字（`MessageRun::Emoji`）は 1 個ずつ、テキスト中の Unicode 絵文字は
+/// 書記素クラスタ単位で数える（[`emoji::count_emojis`]）。
+pub fn count_emojis(msg: &ChatMessage) -> usize {
+    msg.runs
+        .iter()
+        .map(|run| match run {
+            MessageRun::Emoji { .. } => 1,
+            MessageRun::Text { content } => emoji::count_emojis(content),
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    fn message(channel_id: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            channel_id: channel_id.to_string(),
+            content: content.to_string(),
+            runs: vec![MessageRun::Text {
+                content: content.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn emoji_message(channel_id: &str) -> ChatMessage {
+        ChatMessage {
+            runs: vec![MessageRun::Emoji {
+                emoji_id: "kusa".to_string(),
+                image_url: String::new(),
+                alt_text: ":_kusa:".to_string(),
+                shortcuts: Vec::new(),
+            }],
+            ..message(channel_id, ":_kusa:")
+        }
+    }
+
+    #[test]
+    fn snapshot_summarizes_interval() {
+        let mut metrics = EngagementMetrics::new();
+        metrics.record(&message("UC_a", "こんにちは"), at(0));
+        metrics.record(&message("UC_b", "初見です？"), at(10));
+        metrics.record(&emoji_message("UC_a"), at(20));
+        metrics.record(&message("UC_c", "what game is this?"), at(30));
+
+        let snapshot = metrics.take_snapshot(at(120), 10);
+        assert_eq!(
+            snapshot,
+            EngagementSnapshot {
+                at: "2023-11-14T22:15:20+00:00".to_string(),
+                unique_viewers: 3,
+                messages: 4,
+                engagement_rate: 2.0,
+                emoji_rate: 0.25,
+                emojis: 1,
+                questions: 2,
+            }
+        );
+        assert_eq!(metrics.snapshots(), [snapshot]);
+    }
+
+    #[test]
+    fn counts_custom_and_unicode_emojis() {
+        let mut mixed = emoji_message("UC_a");
+        mixed.runs.push(MessageRun::Text {
+            content: " 👨\u{200D}👩\u{200D}👧 👍🏽🇯🇵".to_string(),
+        });
+        assert_eq!(count_emojis(&mixed), 4);
+        assert_eq!(count_emojis(&message("UC_a", "1 2 3?")), 0);
+
+        let mut metrics = EngagementMetrics::new();
+        metrics.record(&mixed, at(0));
+        metrics.record(&message("UC_b", "👏👏"), at(10));
+        metrics.record(&message("UC_c", "hi"), at(20));
+        let snapshot = metrics.take_snapshot(at(60), 10);
+        assert_eq!(snapshot.emojis, 6);
+        assert_eq!(snapshot.emoji_rate, 2.0 / 3.0);
+    }
+
+    #[test]
+    fn counters_reset_but_viewers_accumulate() {
+        let mut metrics = EngagementMetrics::new();
+        metrics.record(&message("UC_a", "a"), at(0));
+        metrics.take_snapshot(at(60), 10);
+
+        metrics.record(&message("UC_b", "b"), at(70));
+        let snapshot = metrics.take_snapshot(at(120), 10);
+        assert_eq!(snapshot.unique_viewers, 2);
+        assert_eq!(snapshot.messages, 1);
+        assert_eq!(snapshot.engagement_rate, 1.0);
+
+        // メッセージのない区間
+        let quiet = metrics.take_snapshot(at(180), 10);
+        assert_eq!(quiet.messages, 0);
+        assert_eq!(quiet.engagement_rate, 0.0);
+        assert_eq!(quiet.emoji_rate, 0.0);
+    }
+
+    #[test]
+    fn system_messages_are_ignored() {
+        let mut metrics = EngagementMetrics::new();
+        let system = ChatMessage {
+            message_type: MessageType::System,
+            ..message("", "ユニーク投稿者が100人に到達しました？")
+        };
+        metrics.record(&system, at(0));
+        assert_eq!(
+            metrics.maybe_sample(&EngagementConfig::default(), at(600)),
+            None
+        );
+    }
+
+    #[test]
+    fn samples_at_configured_interval() {
+        let config = EngagementConfig {
+            snapshot_interval_secs: 30,
+            max_snapshots: 10,
+        };
+        let mut metrics = EngagementMetrics::new();
+        assert_eq!(metrics.maybe_sample(&config, at(100)), None);
+
+        metrics.record(&message("UC_a", "a"), at(0));
+        assert_eq!(metrics.maybe_sample(&config, at(29)), None);
+        assert!(metrics.maybe_sample(&config, at(30)).is_some());
+        assert_eq!(metrics.maybe_sample(&config, at(45)), None);
+        assert!(metrics.maybe_sample(&config, at(61)).is_some());
+        assert_eq!(metrics.snapshots().len(), 2);
+    }
+
+    fn posted(secs: i64, message_type: MessageType) -> ChatMessage {
+        ChatMessage {
+            timestamp_usec: (at(secs).timestamp() * 1_000_000).to_string(),
+            message_type,
+            ..message("UC_a", "a")
+        }
+    }
+
+    #[test]
+    fn density_fills_empty_buckets_with_zero() {
+        let mut metrics = EngagementMetrics::new();
+        // 受信時刻ではなく投稿時刻で数える
+        for secs in [0, 5, 59, 60, 200] {
+            metrics.record(&posted(secs, MessageType::Text), at(1000));
+        }
+
+        // 区間は UNIX 時刻で区切る（at(0) は 1 分の区切りから 20 秒後）
+        let minute = metrics.density_buckets(Duration::from_secs(60));
+        assert_eq!(
+            minute,
+            [(at(-20), 2), (at(40), 2), (at(100), 0), (at(160), 1)]
+        );
+
+        let half_minute = metrics.density_buckets(Duration::from_secs(30));
+        let counts: Vec<usize> = half_minute.iter().map(|(_, n)| *n).collect();
+        assert_eq!(counts, [2, 0, 2, 0, 0, 0, 0, 1]);
+        assert_eq!(half_minute[0].0, at(-20));
+        assert_eq!(half_minute[7].0, at(190));
+
+        assert!(
+            EngagementMetrics::new()
+                .density_buckets(Duration::from_secs(60))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn weighted_density_counts_paid_messages_more() {
+        let mut metrics = EngagementMetrics::new();
+        metrics.record(&posted(0, MessageType::Text), at(0));
+        metrics.record(
+            &posted(
+                10,
+                MessageType::SuperChat {
+                    amount: "¥500".to_string(),
+                },
+            ),
+            at(10),
+        );
+        metrics.record(
+            &posted(
+                70,
+                MessageType::Membership {
+                    milestone_months: None,
+                },
+            ),
+            at(70),
+        );
+
+        let bucket = Duration::from_secs(60);
+        assert_eq!(metrics.density_buckets(bucket), [(at(-20), 2), (at(40), 1)]);
+        assert_eq!(
+            metrics.weighted_density_buckets(bucket),
+            [
+                (at(-20), 1 + PAID_MESSAGE_WEIGHT),
+                (at(40), MEMBERSHIP_WEIGHT),
+            ]
+        );
+    }
+
+    #[test]
+    fn density_forgets_seconds_older_than_retention() {
+        let mut metrics = EngagementMetrics::new();
+        metrics.record(&posted(0, MessageType::Text), at(0));
+        metrics.record(
+            &posted(DENSITY_RETENTION_SECS + 1, MessageType::Text),
+            at(0),
+        );
+        assert_eq!(
+            metrics.density_buckets(Duration::from_secs(1)),
+            [(at(DENSITY_RETENTION_SECS + 1), 1)]
+        );
+    }
+
+    #[test]
+    fn snapshot_buffer_is_bounded() {
+        let mut metrics = EngagementMetrics::new();
+        for i in 0..5 {
+            metrics.record(&message("UC_a", "a"), at(i * 60));
+            metrics.take_snapshot(at(i * 60 + 30), 3);
+        }
+        let times: Vec<_> = metrics.snapshots().iter().map(|s| s.at.clone()).collect();
+        assert_eq!(times, [at(150), at(210), at(270)].map(|t| t.to_rfc3339()));
+    }
+}
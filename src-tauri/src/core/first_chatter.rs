@@ -0,0 +1,135 @@
+//! 配信内で初めて発言した視聴者の検出（spec: 02_chat.md 配信内の初コメント）
+//!
+//! 配信内コメント数（`in_stream_comment_count`）を数え、チャンネル ID ごとに最初の 1 件だけ
+//! `chat:first_time_chatter` を発行するための判定を行う。カウンターは監視タスクが配信ごとに持ち、
+//! 再接続時は DB から復元するため、同じ配信で同じ視聴者について 2 回発行しない。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::core::models::{ChatMessage, MessageType};
+
+/// 配信内の初コメントイベント（`chat:first_time_chatter`）のペイロード
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct FirstTimeChatterEvent {
+    pub connection_id: u64,
+    pub message_id: String,
+    pub channel_id: String,
+    pub author: String,
+    /// 配信者チャンネルで初めてのコメントか（初見さん判定の結果）
+    pub is_first_time_viewer: bool,
+}
+
+impl FirstTimeChatterEvent {
+    /// 配信内で最初のコメントならイベントを作る
+    ///
+    /// 初見さん判定を含めるため、`process_messages` の後のメッセージを渡す。
+    pub fn from_message(connection_id: u64, message: &ChatMessage) -> Option<Self> {
+        if !is_first_in_stream(message) {
+            return None;
+        }
+        Some(Self {
+            connection_id,
+            message_id: message.id.clone(),
+            channel_id: message.channel_id.clone(),
+            author: message.author.clone(),
+            is_first_time_viewer: message.is_first_time_viewer,
+        })
+    }
+}
+
+/// 配信内コメント数を数え、`in_stream_comment_count` に設定する
+///
+/// システムメッセージは数えない。
+pub fn count_in_stream_comment(message: &mut ChatMessage, counts: &mut HashMap<String, u32>) {
+    if matches!(message.message_type, MessageType::System) {
+        return;
+    }
+    let count = counts.entry(message.channel_id.clone()).or_insert(0);
+    *count += 1;
+    message.in_stream_comment_count = Some(*count);
+}
+
+/// 配信内で最初のコメントか
+pub fn is_first_in_stream(message: &ChatMessage) -> bool {
+    message.in_stream_comment_count == Some(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, channel_id: &str) -> ChatMessage {
+        ChatMessage {
+            id: id.to_string(),
+            author: format!("{}_name", channel_id),
+            channel_id: channel_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn events(messages: &mut [ChatMessage], counts: &mut HashMap<String, u32>) -> Vec<String> {
+        messages
+            .iter_mut()
+            .filter_map(|msg| {
+                count_in_stream_comment(msg, counts);
+                FirstTimeChatterEvent::from_message(1, msg)
+            })
+            .map(|event| event.message_id)
+            .collect()
+    }
+
+    #[test]
+    fn fires_once_per_channel_id_per_stream() {
+        let mut counts = HashMap::new();
+        let mut first_poll = vec![
+            message("1", "UC_a"),
+            message("2", "UC_b"),
+            message("3", "UC_a"),
+        ];
+        assert_eq!(events(&mut first_poll, &mut counts), ["1", "2"]);
+
+        let mut second_poll = vec![message("4", "UC_b"), message("5", "UC_c")];
+        assert_eq!(events(&mut second_poll, &mut counts), ["5"]);
+        assert_eq!(second_poll[0].in_stream_comment_count, Some(2));
+    }
+
+    #[test]
+    fn restored_counts_do_not_fire_again() {
+        // 再接続時は DB から復元したカウンターで続きを数える
+        let mut counts = HashMap::from([("UC_a".to_string(), 3)]);
+        let mut messages = vec![message("1", "UC_a"), message("2", "UC_b")];
+        assert_eq!(events(&mut messages, &mut counts), ["2"]);
+        assert_eq!(messages[0].in_stream_comment_count, Some(4));
+    }
+
+    #[test]
+    fn system_messages_are_not_counted() {
+        let mut counts = HashMap::new();
+        let mut system = ChatMessage {
+            message_type: MessageType::System,
+            ..message("1", "")
+        };
+        count_in_stream_comment(&mut system, &mut counts);
+        assert_eq!(system.in_stream_comment_count, None);
+        assert!(FirstTimeChatterEvent::from_message(1, &system).is_none());
+        assert!(counts.is_empty());
+    }
+
+    #[test]
+    fn event_carries_first_time_viewer_flag() {
+        let mut msg = ChatMessage {
+            is_first_time_viewer: true,
+            ..message("1", "UC_a")
+        };
+        count_in_stream_comment(&mut msg, &mut HashMap::new());
+        let event = FirstTimeChatterEvent::from_message(7, &msg).unwrap();
+        assert_eq!(event.connection_id, 7);
+        assert_eq!(event.channel_id, "UC_a");
+        assert_eq!(event.author, "UC_a_name");
+        assert!(event.is_first_time_viewer);
+    }
+}
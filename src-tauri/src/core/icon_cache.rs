@@ -0,0 +1,388 @@
+//! 投稿者アイコンのキャッシュ（spec: 02_chat.md 投稿者アイコンのキャッシュ）
+//!
+//! 同じ常連のアイコンをポーリングのたびに CDN から取り直さないよう、
+//! URL をキーに data URL を保持する。件数と経過時間で上限を設け、超えたら最も古く使われたものから捨てる。
+//! 取得は [`IconFetcher`] が同じ URL ごとに 1 回にまとめ、大きすぎる応答は受け付けない。
+
+use base64::{Engine as _, engine::general_purpose};
+use futures_util::FutureExt;
+use futures_util::future::{BoxFuture, Shared};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 既定の最大件数
+pub const DEFAULT_MAX_ENTRIES: usize = 500;
+/// 既定の保持期間（取得からの経過時間）
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// 1 件のアイコンとして受け付ける最大サイズ（アバターは数 KB〜数十 KB）
+pub const MAX_ICON_BYTES: usize = 256 * 1024;
+
+/// 取得を許可するアイコン画像のホスト（YouTube のアバター配信元）
+const ALLOWED_HOST_SUFFIXES: &[&str] = &["ggpht.com", "googleusercontent.com", "ytimg.com"];
+
+/// アイコン URL として取得してよいか（https かつ YouTube のアバター配信元のみ）
+pub fn is_allowed_icon_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    if parsed.scheme() != "https" {
+        return false;
+    }
+    let Some(host) = parsed.host_str() else {
+        return false;
+    };
+    ALLOWED_HOST_SUFFIXES
+        .iter()
+        .any(|suffix| host == *suffix || host.ends_with(&format!(".{}", suffix)))
+}
+
+/// 画像のバイト列を data URL にする（Content-Type が画像でなければ `image/jpeg` とみなす）
+pub fn to_data_url(content_type: Option<&str>, bytes: &[u8]) -> String {
+    let mime = content_type
+        .map(|ct| ct.split(';').next().unwrap_or("").trim())
+        .filter(|mime| mime.starts_with("image/"))
+        .unwrap_or("image/jpeg");
+    format!(
+        "data:{};base64,{}",
+        mime,
+        general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+#[derive(Debug)]
+struct CachedIcon {
+    data_url: String,
+    fetched_at: Instant,
+    /// 最後に使われた順序（大きいほど新しい）
+    last_used: u64,
+}
+
+/// URL をキーにしたアイコンの LRU キャッシュ
+#[derive(Debug)]
+pub struct IconCache {
+    max_entries: usize,
+    max_age: Duration,
+    entries: HashMap<String, CachedIcon>,
+    tick: u64,
+}
+
+impl Default for IconCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENTRIES, DEFAULT_MAX_AGE)
+    }
+}
+
+impl IconCache {
+    pub fn new(max_entries: usize, max_age: Duration) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            max_age,
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// キャッシュ済みの data URL を返す（保持期間を過ぎたものは捨てて None）
+    pub fn get(&mut self, url: &str, now: Instant) -> Option<String> {
+        let expired =
+            now.saturating_duration_since(self.entries.get(url)?.fetched_at) > self.max_age;
+        if expired {
+            self.entries.remove(url);
+            return None;
+        }
+        self.tick += 1;
+        let entry = self.entries.get_mut(url)?;
+        entry.last_used = self.tick;
+        Some(entry.data_url.clone())
+    }
+
+    /// data URL を保存する（上限を超える場合は最も古く使われたものを捨てる）
+    pub fn insert(&mut self, url: String, data_url: String, now: Instant) {
+        if !self.entries.contains_key(&url) && self.entries.len() >= self.max_entries {
+            self.evict_expired(now);
+            if self.entries.len() >= self.max_entries {
+                self.evict_least_recently_used();
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(
+            url,
+            CachedIcon {
+                data_url,
+                fetched_at: now,
+                last_used: self.tick,
+            },
+        );
+    }
+
+    /// 全件削除する
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn evict_expired(&mut self, now: Instant) {
+        let max_age = self.max_age;
+        self.entries
+            .retain(|_, icon| now.saturating_duration_since(icon.fetched_at) <= max_age);
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, icon)| icon.last_used)
+            .map(|(url, _)| url.clone());
+        if let Some(url) = oldest {
+            self.entries.remove(&url);
+        }
+    }
+}
+
+/// アイコンの取得のエラー（同じ URL を待っていた呼び出し元すべてに返すため複製できる形で持つ）
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IconFetchError {
+    /// 接続できない・エラーのステータスが返った
+    #[error("Failed to fetch author icon: {0}")]
+    Request(String),
+    /// 応答が [`MAX_ICON_BYTES`] を超えた
+    #[error("Author icon is larger than {MAX_ICON_BYTES} bytes")]
+    TooLarge,
+}
+
+type PendingFetch = Shared<BoxFuture<'static, Result<String, IconFetchError>>>;
+
+/// アイコンの取得を URL ごとに 1 回にまとめる
+///
+/// 同じ常連のメッセージが続けて届くと、キャッシュに入る前に同じ URL の問い合わせが重なる。
+/// 取得中の URL には新しくリクエストを送らず、先に始まった取得の結果を待つ。
+#[derive(Default)]
+pub struct IconFetcher {
+    pending: Mutex<HashMap<String, PendingFetch>>,
+}
+
+impl IconFetcher {
+    /// `url` のアイコンを data URL で取得する（同じ URL を取得中ならその結果を待つ）
+    pub async fn fetch(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<String, IconFetchError> {
+        let fetch = self
+            .lock()
+            .entry(url.to_string())
+            .or_insert_with(|| {
+                download_icon(client.clone(), url.to_string())
+                    .boxed()
+                    .shared()
+            })
+            .clone();
+        let result = fetch.clone().await;
+        // 終わった取得を片付ける（その後に始まった同じ URL の取得は残す）
+        let mut pending = self.lock();
+        if pending
+            .get(url)
+            .is_some_and(|current| current.ptr_eq(&fetch))
+        {
+            pending.remove(url);
+        }
+        result
+    }
+
+    /// 取得中の URL の数
+    pub fn in_flight(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, PendingFetch>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// アイコンをダウンロードして data URL にする（[`MAX_ICON_BYTES`] を超えたら読むのをやめる）
+async fn download_icon(client: reqwest::Client, url: String) -> Result<String, IconFetchError> {
+    let request_error = |e: reqwest::Error| IconFetchError::Request(e.to_string());
+    let mut response = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(request_error)?;
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_ICON_BYTES as u64)
+    {
+        return Err(IconFetchError::TooLarge);
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    // Content-Length がない応答もあるため、読みながら上限を確かめる
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(request_error)? {
+        if bytes.len() + chunk.len() > MAX_ICON_BYTES {
+            return Err(IconFetchError::TooLarge);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(to_data_url(content_type.as_deref(), &bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::serve;
+
+    #[test]
+    fn returns_cached_data_url() {
+        let mut cache = IconCache::default();
+        let now = Instant::now();
+        assert_eq!(cache.get("https://yt3.ggpht.com/a", now), None);
+
+        cache.insert(
+            "https://yt3.ggpht.com/a".to_string(),
+            "data:image/png;base64,AA==".to_string(),
+            now,
+        );
+        assert_eq!(
+            cache.get("https://yt3.ggpht.com/a", now).as_deref(),
+            Some("data:image/png;base64,AA==")
+        );
+    }
+
+    #[test]
+    fn evicts_least_recently_used_when_full() {
+        let mut cache = IconCache::new(2, DEFAULT_MAX_AGE);
+        let now = Instant::now();
+        cache.insert("a".to_string(), "A".to_string(), now);
+        cache.insert("b".to_string(), "B".to_string(), now);
+        // a を使ったので、次に捨てられるのは b
+        assert!(cache.get("a", now).is_some());
+        cache.insert("c".to_string(), "C".to_string(), now);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a", now).is_some());
+        assert!(cache.get("b", now).is_none());
+        assert!(cache.get("c", now).is_some());
+    }
+
+    #[test]
+    fn expires_entries_after_max_age() {
+        let mut cache = IconCache::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+        cache.insert("a".to_string(), "A".to_string(), now);
+
+        assert!(cache.get("a", now + Duration::from_secs(60)).is_some());
+        assert!(cache.get("a", now + Duration::from_secs(61)).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn clear_removes_all_entries() {
+        let mut cache = IconCache::default();
+        cache.insert("a".to_string(), "A".to_string(), Instant::now());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn only_youtube_avatar_hosts_are_allowed() {
+        assert!(is_allowed_icon_url("https://yt3.ggpht.com/abc=s64"));
+        assert!(is_allowed_icon_url("https://lh3.googleusercontent.com/abc"));
+        assert!(!is_allowed_icon_url("http://yt3.ggpht.com/abc"));
+        assert!(!is_allowed_icon_url("https://evilggpht.com/abc"));
+        assert!(!is_allowed_icon_url("https://127.0.0.1/abc"));
+        assert!(!is_allowed_icon_url("not a url"));
+    }
+
+    #[test]
+    fn data_url_uses_image_content_type() {
+        assert_eq!(
+            to_data_url(Some("image/png; charset=binary"), &[0, 1]),
+            "data:image/png;base64,AAE="
+        );
+        assert_eq!(
+            to_data_url(Some("text/html"), &[0, 1]),
+            "data:image/jpeg;base64,AAE="
+        );
+        assert_eq!(to_data_url(None, &[]), "data:image/jpeg;base64,");
+    }
+
+    fn icon_response(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    }
+
+    #[tokio::test]
+    async fn concurrent_fetches_of_the_same_url_share_one_request() {
+        // 応答は 1 件だけ用意する（2 件目のリクエストは接続を拒否される）
+        let (addr, requests) = serve([icon_response("icon")]).await;
+        let url = format!("http://{}/a", addr);
+        let fetcher = IconFetcher::default();
+        let client = reqwest::Client::new();
+
+        let (first, second) =
+            tokio::join!(fetcher.fetch(&client, &url), fetcher.fetch(&client, &url));
+
+        assert_eq!(first.as_deref(), Ok("data:image/png;base64,aWNvbg=="));
+        assert_eq!(first, second);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(fetcher.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_is_not_kept_in_flight() {
+        let (addr, _requests) =
+            serve(["HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"])
+                .await;
+        let url = format!("http://{}/a", addr);
+        let fetcher = IconFetcher::default();
+
+        let result = fetcher.fetch(&reqwest::Client::new(), &url).await;
+
+        assert!(matches!(result, Err(IconFetchError::Request(_))));
+        assert_eq!(fetcher.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn rejects_icons_larger_than_the_limit() {
+        let client = reqwest::Client::new();
+        let fetcher = IconFetcher::default();
+        let too_large = "x".repeat(MAX_ICON_BYTES + 1);
+
+        // Content-Length で判定できる応答
+        let (addr, _requests) = serve([icon_response(&too_large)]).await;
+        let result = fetcher.fetch(&client, &format!("http://{}/a", addr)).await;
+        assert_eq!(result, Err(IconFetchError::TooLarge));
+
+        // Content-Length がなく、接続が閉じるまで続く応答
+        let (addr, _requests) = serve([format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nConnection: close\r\n\r\n{}",
+            too_large
+        )])
+        .await;
+        let result = fetcher.fetch(&client, &format!("http://{}/b", addr)).await;
+        assert_eq!(result, Err(IconFetchError::TooLarge));
+
+        // 上限ちょうどは受け付ける
+        let (addr, _requests) = serve([icon_response(&too_large[1..])]).await;
+        let result = fetcher.fetch(&client, &format!("http://{}/c", addr)).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,367 @@
+//! 配信のマイルストーン通知（spec: 02_chat.md マイルストーンの通知）
+//!
+//! ユニーク投稿者数・スーパーチャットの合計金額・経過時間がしきい値を超えたら、
+//! システムメッセージを生成してチャット欄に流す。各マイルストーンはセッション中に 1 回だけ通知する。
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::api::parse_purchase_amount;
+use crate::core::currency::CurrencyConverter;
+use crate::core::models::{ChatMessage, MessageRun, MessageType};
+
+/// システムメッセージの投稿者名
+const SYSTEM_AUTHOR: &str = "liscov";
+
+/// マイルストーン通知の設定（config.toml の `[milestones]` セクション）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MilestoneConfig {
+    /// マイルストーンのシステムメッセージを生成するか
+    pub enabled: bool,
+    /// ユニーク投稿者数のしきい値（人）
+    pub unique_chatters: Vec<u32>,
+    /// スーパーチャット合計金額のしきい値（`currency.base_currency` 建て）
+    pub super_chat_total: Vec<u64>,
+    /// 接続してからの経過時間のしきい値（分）
+    pub stream_duration_minutes: Vec<u32>,
+    /// マイルストーンのメッセージを読み上げるか
+    pub tts: bool,
+}
+
+impl Default for MilestoneConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            unique_chatters: vec![100],
+            super_chat_total: vec![50000],
+            stream_duration_minutes: vec![60],
+            tts: false,
+        }
+    }
+}
+
+/// 到達したマイルストーン（値はしきい値）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Milestone {
+    UniqueChatters(u32),
+    SuperChatTotal(u64),
+    StreamDuration(u32),
+}
+
+impl Milestone {
+    /// 通知メッセージの本文
+    pub fn text(&self, base_currency: &str) -> String {
+        match self {
+            Milestone::UniqueChatters(count) => {
+                format!("ユニーク投稿者が{}人に到達しました", count)
+            }
+            Milestone::SuperChatTotal(amount) => format!(
+                "スーパーチャットの合計が{} {}に到達しました",
+                group_digits(*amount),
+                base_currency
+            ),
+            Milestone::StreamDuration(minutes) => {
+                format!("接続から{}が経過しました", format_minutes(*minutes))
+            }
+        }
+    }
+
+    /// チャット欄に流すシステムメッセージを作る
+    pub fn to_system_message(&self, base_currency: &str, now: DateTime<Utc>) -> ChatMessage {
+        let content = self.text(base_currency);
+        let key = match self {
+            Milestone::UniqueChatters(count) => format!("unique_chatters_{}", count),
+            Milestone::SuperChatTotal(amount) => format!("super_chat_total_{}", amount),
+            Milestone::StreamDuration(minutes) => format!("stream_duration_{}", minutes),
+        };
+        ChatMessage {
+            id: format!("milestone_{}_{}", key, now.timestamp_micros()),
+            timestamp: now.to_rfc3339(),
+            timestamp_usec: now.timestamp_micros().to_string(),
+            message_type: MessageType::System,
+            author: SYSTEM_AUTHOR.to_string(),
+            runs: vec![MessageRun::Text {
+                content: content.clone(),
+            }],
+            content,
+            ..Default::default()
+        }
+    }
+}
+
+/// セッション中の指標を集計し、しきい値を超えたマイルストーンを 1 回ずつ返す
+#[derive(Debug, Default)]
+pub struct MilestoneTracker {
+    /// スーパーチャット（SuperSticker を含む）の合計金額（基準通貨建て）
+    super_chat_total: f64,
+    /// 通知済みのマイルストーン
+    fired: HashSet<Milestone>,
+}
+
+impl MilestoneTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// メッセージの金額を合計に加える
+    ///
+    /// 金額をパースできない・基準通貨への換算レートがないものは加算しない。
+    pub fn record(
+        &mut self,
+        msg: &ChatMessage,
+        converter: &dyn CurrencyConverter,
+        base_currency: &str,
+    ) {
+        let amount = match &msg.message_type {
+            MessageType::SuperChat { amount } | MessageType::SuperSticker { amount } => amount,
+            _ => return,
+        };
+        if let Some(converted) = parse_purchase_amount(amount)
+            .and_then(|parsed| converter.convert(parsed.amount, &parsed.currency, base_currency))
+        {
+            self.super_chat_total += converted;
+        }
+    }
+
+    /// これまでに加算したスーパーチャットの合計金額（基準通貨建て）
+    pub fn super_chat_total(&self) -> f64 {
+        self.super_chat_total
+    }
+
+    /// 新たにしきい値を超えたマイルストーンを返す（通知済みのものは返さない）
+    ///
+    /// - `unique_chatters` — このセッションで投稿したユニーク投稿者数
+    /// - `elapsed` — 接続してからの経過時間
+    pub fn check(
+        &mut self,
+        config: &MilestoneConfig,
+        unique_chatters: usize,
+        elapsed: Duration,
+    ) -> Vec<Milestone> {
+        if !config.enabled {
+            return Vec::new();
+        }
+        let elapsed_minutes = elapsed.as_secs() / 60;
+        let super_chat_total = self.super_chat_total;
+        config
+            .unique_chatters
+            .iter()
+            .filter(|&&t| unique_chatters >= t as usize)
+            .map(|&t| Milestone::UniqueChatters(t))
+            .chain(
+                config
+                    .super_chat_total
+                    .iter()
+                    .filter(|&&t| super_chat_total >= t as f64)
+                    .map(|&t| Milestone::SuperChatTotal(t)),
+            )
+            .chain(
+                config
+                    .stream_duration_minutes
+                    .iter()
+                    .filter(|&&t| elapsed_minutes >= u64::from(t))
+                    .map(|&t| Milestone::StreamDuration(t)),
+            )
+            .filter(|milestone| self.fired.insert(*milestone))
+            .collect()
+    }
+
+    /// 到達済みのマイルストーンを通知せずに通知済みとして記録する
+    ///
+    /// 再接続時に、DB から復元した指標で既に超えているマイルストーンを再通知しないために使う。
+    pub fn mark_reached(
+        &mut self,
+        config: &MilestoneConfig,
+        unique_chatters: usize,
+        elapsed: Duration,
+    ) {
+        let enabled = MilestoneConfig {
+            enabled: true,
+            ..config.clone()
+        };
+        self.check(&enabled, unique_chatters, elapsed);
+    }
+}
+
+/// 3 桁ごとにカンマで区切る（例: 50000 → "50,000"）
+fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 分を「1時間」「1時間30分」「45分」の形式にする
+fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}分", m),
+        (h, 0) => format!("{}時間", h),
+        (h, m) => format!("{}時間{}分", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::currency::StaticRateTable;
+
+    fn enabled_config() -> MilestoneConfig {
+        MilestoneConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    fn super_chat(amount: &str) -> ChatMessage {
+        ChatMessage {
+            message_type: MessageType::SuperChat {
+                amount: amount.to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fires_unique_chatters_on_first_cross() {
+        let mut tracker = MilestoneTracker::new();
+        let config = enabled_config();
+        assert!(tracker.check(&config, 99, Duration::ZERO).is_empty());
+        assert_eq!(
+            tracker.check(&config, 100, Duration::ZERO),
+            vec![Milestone::UniqueChatters(100)]
+        );
+    }
+
+    #[test]
+    fn does_not_refire_after_cross() {
+        let mut tracker = MilestoneTracker::new();
+        let config = enabled_config();
+        assert_eq!(tracker.check(&config, 150, Duration::ZERO).len(), 1);
+        assert!(tracker.check(&config, 150, Duration::ZERO).is_empty());
+        assert!(tracker.check(&config, 300, Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn fires_super_chat_total_with_conversion() {
+        let mut tracker = MilestoneTracker::new();
+        let config = enabled_config();
+        let rates = StaticRateTable::new("JPY", [("USD".to_string(), 150.0)]);
+
+        tracker.record(&super_chat("¥30,000"), &rates, "JPY");
+        assert!(tracker.check(&config, 0, Duration::ZERO).is_empty());
+
+        // $200 = ¥30,000 で合計 ¥60,000
+        tracker.record(&super_chat("$200.00"), &rates, "JPY");
+        assert_eq!(
+            tracker.check(&config, 0, Duration::ZERO),
+            vec![Milestone::SuperChatTotal(50000)]
+        );
+
+        tracker.record(&super_chat("¥10,000"), &rates, "JPY");
+        assert!(tracker.check(&config, 0, Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn skips_amounts_without_rate() {
+        let mut tracker = MilestoneTracker::new();
+        let rates = StaticRateTable::new("JPY", []);
+        tracker.record(&super_chat("€1,000.00"), &rates, "JPY");
+        tracker.record(&ChatMessage::default(), &rates, "JPY");
+        assert_eq!(tracker.super_chat_total, 0.0);
+    }
+
+    #[test]
+    fn fires_each_threshold_once() {
+        let mut tracker = MilestoneTracker::new();
+        let config = MilestoneConfig {
+            enabled: true,
+            unique_chatters: vec![],
+            super_chat_total: vec![],
+            stream_duration_minutes: vec![60, 120],
+            tts: false,
+        };
+        assert!(
+            tracker
+                .check(&config, 0, Duration::from_secs(59 * 60))
+                .is_empty()
+        );
+        assert_eq!(
+            tracker.check(&config, 0, Duration::from_secs(60 * 60)),
+            vec![Milestone::StreamDuration(60)]
+        );
+        assert_eq!(
+            tracker.check(&config, 0, Duration::from_secs(121 * 60)),
+            vec![Milestone::StreamDuration(120)]
+        );
+        assert!(
+            tracker
+                .check(&config, 0, Duration::from_secs(180 * 60))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn disabled_config_fires_nothing() {
+        let mut tracker = MilestoneTracker::new();
+        let config = MilestoneConfig::default();
+        assert!(
+            tracker
+                .check(&config, 1000, Duration::from_secs(7200))
+                .is_empty()
+        );
+        // 無効中に超えたものも記録しないため、有効にすれば通知される
+        assert_eq!(
+            tracker.check(&enabled_config(), 1000, Duration::ZERO),
+            vec![Milestone::UniqueChatters(100)]
+        );
+    }
+
+    #[test]
+    fn mark_reached_suppresses_restored_milestones() {
+        let mut tracker = MilestoneTracker::new();
+        tracker.mark_reached(&MilestoneConfig::default(), 120, Duration::ZERO);
+        assert!(
+            tracker
+                .check(&enabled_config(), 130, Duration::ZERO)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn builds_system_message() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let msg = Milestone::SuperChatTotal(50000).to_system_message("JPY", now);
+        assert_eq!(msg.message_type, MessageType::System);
+        assert_eq!(
+            msg.content,
+            "スーパーチャットの合計が50,000 JPYに到達しました"
+        );
+        assert_eq!(msg.timestamp_usec, "1700000000000000");
+        assert!(msg.id.starts_with("milestone_super_chat_total_50000_"));
+
+        assert_eq!(
+            Milestone::StreamDuration(90).text("JPY"),
+            "接続から1時間30分が経過しました"
+        );
+        assert_eq!(
+            Milestone::UniqueChatters(100).text("JPY"),
+            "ユニーク投稿者が100人に到達しました"
+        );
+    }
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits(0), "0");
+        assert_eq!(group_digits(999), "999");
+        assert_eq!(group_digits(1000), "1,000");
+        assert_eq!(group_digits(1234567), "1,234,567");
+    }
+}
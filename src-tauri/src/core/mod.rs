@@ -3,9 +3,30 @@
 //! This module contains the business logic that is independent of the UI framework.
 
 pub mod api;
+pub mod capture_watchdog;
+pub mod channel_monitor;
 pub mod chat_runtime;
+pub mod connection_health;
+pub mod continuation_store;
+pub mod currency;
+pub mod disk_archive;
+pub mod discord;
+pub mod emoji;
+pub mod engagement;
+pub mod first_chatter;
+pub mod icon_cache;
+pub mod milestones;
 pub mod models;
+pub mod moderation;
+pub mod notifications;
+pub mod processing_queue;
 pub mod raw_response;
+pub mod replay;
+pub mod revenue_goal;
+pub mod shutdown;
+pub mod stream_end;
+pub mod transform;
+pub mod trend;
 
 pub use models::*;
 pub use raw_response::*;
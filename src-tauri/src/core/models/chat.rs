@@ -32,6 +32,9 @@ pub enum MessageRun {
         emoji_id: String,
         image_url: String,
         alt_text: String,
+        /// 入力用のショートコード（`:_kusa:` など）
+        #[serde(default)]
+        shortcuts: Vec<String>,
     },
 }
 
@@ -53,8 +56,16 @@ pub struct SuperChatColors {
     pub body_text: String,
 }
 
-/// Message metadata
+/// SuperSticker の画像（spec: 02_chat.md）
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StickerImage {
+    pub url: String,
+    /// アクセシビリティラベル（なければ空文字列）
+    pub alt_text: String,
+}
+
+/// Message metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MessageMetadata {
     pub amount: Option<String>,
     pub badges: Vec<String>,
@@ -62,7 +73,31 @@ pub struct MessageMetadata {
     pub color: Option<String>,
     pub is_moderator: bool,
     pub is_verified: bool,
+    /// 配信者本人（チャンネルオーナー）のメッセージか
+    #[serde(default)]
+    pub is_owner: bool,
     pub superchat_colors: Option<SuperChatColors>,
+    /// SuperSticker の画像
+    #[serde(default)]
+    pub sticker: Option<StickerImage>,
+}
+
+/// 投稿者のロール（spec: 02_chat.md 投稿者ロール）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorRole {
+    /// 配信者本人（チャンネルオーナー）
+    pub is_owner: bool,
+    pub is_moderator: bool,
+    /// 認証済みチャンネル
+    pub is_verified: bool,
+    pub is_member: bool,
+}
+
+impl AuthorRole {
+    /// オーナー・モデレーター・認証済みのいずれかか（メンバーは含まない）
+    pub fn is_privileged(&self) -> bool {
+        self.is_owner || self.is_moderator || self.is_verified
+    }
 }
 
 /// Chat message
@@ -83,6 +118,19 @@ pub struct ChatMessage {
     pub in_stream_comment_count: Option<u32>,
 }
 
+impl ChatMessage {
+    /// メタデータと `is_member` から投稿者のロールを返す
+    pub fn author_role(&self) -> AuthorRole {
+        let metadata = self.metadata.as_ref();
+        AuthorRole {
+            is_owner: metadata.is_some_and(|m| m.is_owner),
+            is_moderator: metadata.is_some_and(|m| m.is_moderator),
+            is_verified: metadata.is_some_and(|m| m.is_verified),
+            is_member: self.is_member,
+        }
+    }
+}
+
 /// Chat statistics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ChatStats {
@@ -123,11 +171,7 @@ impl ChatStats {
 
 /// Parse amount string (e.g., "¥1,000", "$10.00") to f64
 fn parse_amount(amount: &str) -> Option<f64> {
-    let cleaned: String = amount
-        .chars()
-        .filter(|c| c.is_ascii_digit() || *c == '.')
-        .collect();
-    cleaned.parse().ok()
+    crate::core::api::parse_purchase_amount(amount).map(|parsed| parsed.amount)
 }
 
 #[cfg(test)]
@@ -80,9 +80,30 @@ pub struct YouTubeCookies {
     /// Full cookie string from browser (includes __Secure-* cookies needed for member-only streams)
     #[serde(default)]
     pub raw_cookie_string: Option<String>,
+    /// 必須 Cookie のうち最も早い有効期限（UNIX 秒。手入力やセッション Cookie では不明なので None）
+    #[serde(default)]
+    pub expires_at: Option<i64>,
 }
 
 impl YouTubeCookies {
+    /// SAPISIDHASH 認証に必要な 5 つの Cookie がすべて空でないか
+    pub fn has_required_cookies(&self) -> bool {
+        [
+            &self.sid,
+            &self.hsid,
+            &self.ssid,
+            &self.apisid,
+            &self.sapisid,
+        ]
+        .iter()
+        .all(|value| !value.is_empty())
+    }
+
+    /// 有効期限を過ぎている可能性が高いか（有効期限が不明なら false）
+    pub fn is_likely_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
     /// Build cookie header string
     /// Returns full browser cookies if available, otherwise the 5 API cookies
     pub fn to_cookie_string(&self) -> String {
@@ -97,6 +118,59 @@ impl YouTubeCookies {
     }
 }
 
+/// OAuth 2.0 のトークン（デバイス認可フローで取得）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    /// リフレッシュトークン（更新時に返されなければ以前のものを使い続ける）
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// アクセストークンの有効期限（UNIX 秒）
+    pub expires_at: i64,
+}
+
+impl OAuthTokens {
+    /// 有効期限まで `margin_secs` 秒未満か（送信中に失効しないよう早めに更新する）
+    pub fn is_expired(&self, now: i64, margin_secs: i64) -> bool {
+        now.saturating_add(margin_secs) >= self.expires_at
+    }
+
+    /// Authorization ヘッダーの値
+    pub fn authorization_header(&self) -> String {
+        format!("Bearer {}", self.access_token)
+    }
+
+    /// 再ログインが必要になる時刻（リフレッシュトークンがあれば自動更新できるので None）
+    pub fn lapses_at(&self) -> Option<i64> {
+        match self.refresh_token {
+            Some(_) => None,
+            None => Some(self.expires_at),
+        }
+    }
+}
+
+/// InnerTube API の認証情報（WebView の cookie または OAuth トークン）
+#[derive(Debug, Clone)]
+pub enum ApiCredentials {
+    Cookies(YouTubeCookies),
+    OAuth(OAuthTokens),
+}
+
+impl ApiCredentials {
+    /// 再ログインが必要になる時刻（不明・自動更新できる場合は None）
+    pub fn lapses_at(&self) -> Option<i64> {
+        match self {
+            ApiCredentials::Cookies(cookies) => cookies.expires_at,
+            ApiCredentials::OAuth(tokens) => tokens.lapses_at(),
+        }
+    }
+
+    /// 有効期限を過ぎている可能性が高いか
+    pub fn is_likely_expired(&self, now: i64) -> bool {
+        self.lapses_at().is_some_and(|lapses_at| now >= lapses_at)
+    }
+}
+
 /// Connection status
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConnectionStatus {
@@ -188,6 +262,7 @@ mod tests {
             apisid: "a".to_string(),
             sapisid: "sa".to_string(),
             raw_cookie_string: Some("SID=s; HSID=h; __Secure-1PSID=sec1; YSC=ysc".to_string()),
+            expires_at: None,
         };
         assert_eq!(
             cookies.to_cookie_string(),
@@ -204,6 +279,7 @@ mod tests {
             apisid: "apisid_val".to_string(),
             sapisid: "sapisid_val".to_string(),
             raw_cookie_string: None,
+            expires_at: None,
         };
         let result = cookies.to_cookie_string();
         assert!(result.contains("SID=sid_val"));
@@ -224,6 +300,7 @@ mod tests {
             apisid: "a".to_string(),
             sapisid: "sa".to_string(),
             raw_cookie_string: Some("SID=s; __Secure-1PSID=sec1".to_string()),
+            expires_at: None,
         };
         let json = serde_json::to_string(&cookies).unwrap();
         let deserialized: YouTubeCookies = serde_json::from_str(&json).unwrap();
@@ -237,9 +314,40 @@ mod tests {
         let json = r#"{"sid":"s","hsid":"h","ssid":"ss","apisid":"a","sapisid":"sa"}"#;
         let cookies: YouTubeCookies = serde_json::from_str(json).unwrap();
         assert!(cookies.raw_cookie_string.is_none());
+        assert!(cookies.expires_at.is_none());
         assert!(cookies.to_cookie_string().contains("SID=s"));
     }
 
+    #[test]
+    fn youtube_cookies_validation_and_expiry() {
+        let json =
+            r#"{"sid":"s","hsid":"h","ssid":"ss","apisid":"a","sapisid":"sa","expires_at":1000}"#;
+        let mut cookies: YouTubeCookies = serde_json::from_str(json).unwrap();
+        assert!(cookies.has_required_cookies());
+        assert!(!cookies.is_likely_expired(999));
+        assert!(cookies.is_likely_expired(1000));
+
+        cookies.hsid.clear();
+        assert!(!cookies.has_required_cookies());
+        cookies.expires_at = None;
+        assert!(!cookies.is_likely_expired(i64::MAX));
+    }
+
+    #[test]
+    fn oauth_credentials_lapse_only_without_refresh_token() {
+        let mut tokens = OAuthTokens {
+            access_token: "at".to_string(),
+            refresh_token: Some("rt".to_string()),
+            expires_at: 1000,
+        };
+        assert_eq!(tokens.lapses_at(), None);
+        assert!(!ApiCredentials::OAuth(tokens.clone()).is_likely_expired(2000));
+
+        tokens.refresh_token = None;
+        assert_eq!(tokens.lapses_at(), Some(1000));
+        assert!(ApiCredentials::OAuth(tokens).is_likely_expired(2000));
+    }
+
     // G4: Cookie Usage Consistency — raw_cookie_stringが5基本Cookieより優先されることを保証
     #[test]
     fn to_cookie_string_returns_raw_not_five_basic() {
@@ -255,6 +363,7 @@ mod tests {
                 "SID=s; HSID=h; SSID=ss; APISID=a; SAPISID=sa; __Secure-1PSID=sec1; YSC=ysc"
                     .to_string(),
             ),
+            expires_at: None,
         };
 
         let result = cookies.to_cookie_string();
@@ -0,0 +1,430 @@
+//! ローカルモデレーション（spec: 02_chat.md ローカルモデレーション）
+//!
+//! ユーザー定義のルールでメッセージ本文を判定し、GUI での非表示・フラグ・強調表示を決める。
+//! YouTube 側のモデレーション操作は行わず、表示にのみ影響する。
+
+use crate::core::models::ChatMessage;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use ts_rs::TS;
+
+/// ルールの一致条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MatchSpec {
+    /// 正規表現（regex クレートの構文）
+    Regex {
+        pattern: String,
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+    /// いずれかのキーワードを含む（大文字小文字を区別しない部分一致）
+    Keyword { keywords: Vec<String> },
+    /// 同じ文字が `threshold` 回以上連続する
+    RepeatedChar { threshold: usize },
+    /// 大文字小文字の区別がある文字が `min_letters` 文字以上あり、そのうち大文字の割合が `min_ratio` 以上
+    CapsRatio { min_ratio: f64, min_letters: usize },
+}
+
+/// ルールに一致したときの表示上の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// 表示しない
+    Hide,
+    /// 要確認として印を付ける
+    Flag,
+    /// 強調表示する
+    Highlight,
+}
+
+/// モデレーションルール（`pattern` に一致したら `action` を適用）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ModerationRule {
+    pub pattern: MatchSpec,
+    pub action: ModerationAction,
+}
+
+/// ルールの検証・読み書きのエラー
+#[derive(Debug, thiserror::Error)]
+pub enum ModerationError {
+    /// ルールの内容が不正（`index` は 0 始まりのルール番号）
+    #[error("Invalid moderation rule {index}: {message}")]
+    InvalidRule { index: usize, message: String },
+    #[error("Failed to access moderation rules file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse moderation rules: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// 大文字小文字を区別しないキーワードの部分一致
+///
+/// ローカルモデレーションの `Keyword` ルールとキーワード通知（core::notifications）で共通の判定。
+#[derive(Debug, Clone, Default)]
+pub struct KeywordSet(Vec<String>);
+
+impl KeywordSet {
+    /// 前後の空白を除いて小文字にする（空のキーワードは捨てる）
+    pub fn new<S: AsRef<str>>(keywords: &[S]) -> Self {
+        Self(
+            keywords
+                .iter()
+                .map(|k| k.as_ref().trim().to_lowercase())
+                .filter(|k| !k.is_empty())
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// いずれかのキーワードを含むか
+    pub fn is_match(&self, content: &str) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+        let content = content.to_lowercase();
+        self.0.iter().any(|k| content.contains(k.as_str()))
+    }
+}
+
+/// コンパイル済みの一致条件（正規表現はルール設定時に一度だけコンパイルする）
+#[derive(Debug)]
+enum Matcher {
+    Regex(Regex),
+    Keyword(KeywordSet),
+    RepeatedChar(usize),
+    CapsRatio { min_ratio: f64, min_letters: usize },
+}
+
+impl Matcher {
+    fn compile(spec: &MatchSpec, index: usize) -> Result<Self, ModerationError> {
+        let invalid = |message: String| ModerationError::InvalidRule { index, message };
+        match spec {
+            MatchSpec::Regex {
+                pattern,
+                case_insensitive,
+            } => RegexBuilder::new(pattern)
+                .case_insensitive(*case_insensitive)
+                .build()
+                .map(Matcher::Regex)
+                .map_err(|e| invalid(e.to_string())),
+            MatchSpec::Keyword { keywords } => {
+                let keywords = KeywordSet::new(keywords);
+                if keywords.is_empty() {
+                    return Err(invalid("keywords must not be empty".to_string()));
+                }
+                Ok(Matcher::Keyword(keywords))
+            }
+            MatchSpec::RepeatedChar { threshold } => {
+                if *threshold < 2 {
+                    return Err(invalid("threshold must be at least 2".to_string()));
+                }
+                Ok(Matcher::RepeatedChar(*threshold))
+            }
+            MatchSpec::CapsRatio {
+                min_ratio,
+                min_letters,
+            } => {
+                if !(0.0..=1.0).contains(min_ratio) {
+                    return Err(invalid("min_ratio must be between 0 and 1".to_string()));
+                }
+                Ok(Matcher::CapsRatio {
+                    min_ratio: *min_ratio,
+                    min_letters: *min_letters,
+                })
+            }
+        }
+    }
+
+    fn is_match(&self, content: &str) -> bool {
+        match self {
+            Matcher::Regex(regex) => regex.is_match(content),
+            Matcher::Keyword(keywords) => keywords.is_match(content),
+            Matcher::RepeatedChar(threshold) => longest_char_run(content) >= *threshold,
+            Matcher::CapsRatio {
+                min_ratio,
+                min_letters,
+            } => {
+                let upper = content.chars().filter(|c| c.is_uppercase()).count();
+                let lower = content.chars().filter(|c| c.is_lowercase()).count();
+                let letters = upper + lower;
+                letters > 0
+                    && letters >= *min_letters
+                    && upper as f64 / letters as f64 >= *min_ratio
+            }
+        }
+    }
+}
+
+/// 同じ文字が連続する最大の長さ
+fn longest_char_run(content: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous = None;
+    for c in content.chars() {
+        current = if previous == Some(c) { current + 1 } else { 1 };
+        longest = longest.max(current);
+        previous = Some(c);
+    }
+    longest
+}
+
+/// ルール列でメッセージを判定するモデレーター
+#[derive(Debug, Default)]
+pub struct LocalModerator {
+    rules: Vec<ModerationRule>,
+    matchers: Vec<Matcher>,
+}
+
+impl LocalModerator {
+    /// ルールを検証・コンパイルする。1件でも不正なルールがあればエラー
+    pub fn new(rules: Vec<ModerationRule>) -> Result<Self, ModerationError> {
+        let matchers = rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| Matcher::compile(&rule.pattern, index))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules, matchers })
+    }
+
+    /// 設定済みのルール（定義順）
+    pub fn rules(&self) -> &[ModerationRule] {
+        &self.rules
+    }
+
+    /// 一致したルールのアクションを定義順に返す（重複は除く）
+    ///
+    /// `Hide` に一致した時点で以降のルールは評価しない。
+    pub fn evaluate(&self, message: &ChatMessage) -> Vec<ModerationAction> {
+        let mut actions = Vec::new();
+        for (rule, matcher) in self.rules.iter().zip(&self.matchers) {
+            if !matcher.is_match(&message.content) {
+                continue;
+            }
+            if !actions.contains(&rule.action) {
+                actions.push(rule.action);
+            }
+            if rule.action == ModerationAction::Hide {
+                break;
+            }
+        }
+        actions
+    }
+
+    /// JSON ファイルからルールを読み込む。ファイルがなければルールなし
+    pub fn load(path: &Path) -> Result<Self, ModerationError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let rules: Vec<ModerationRule> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Self::new(rules)
+    }
+
+    /// ルールを JSON ファイルに保存する
+    pub fn save(&self, path: &Path) -> Result<(), ModerationError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.rules)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> ChatMessage {
+        ChatMessage {
+            content: content.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn rule(pattern: MatchSpec, action: ModerationAction) -> ModerationRule {
+        ModerationRule { pattern, action }
+    }
+
+    fn caps_rule(action: ModerationAction) -> ModerationRule {
+        rule(
+            MatchSpec::CapsRatio {
+                min_ratio: 0.8,
+                min_letters: 8,
+            },
+            action,
+        )
+    }
+
+    #[test]
+    fn caps_spam_is_flagged() {
+        let moderator = LocalModerator::new(vec![caps_rule(ModerationAction::Flag)]).unwrap();
+
+        assert_eq!(
+            moderator.evaluate(&message("BUY FOLLOWERS NOW!!!")),
+            vec![ModerationAction::Flag]
+        );
+        assert!(moderator.evaluate(&message("Hello everyone")).is_empty());
+        // 短い叫び（"GG" 等）は min_letters 未満なので対象外
+        assert!(moderator.evaluate(&message("GG")).is_empty());
+        // 大文字小文字のない文字（日本語）は割合の計算に含めない
+        assert!(moderator.evaluate(&message("こんにちは OK")).is_empty());
+    }
+
+    #[test]
+    fn emoji_flood_is_hidden() {
+        let moderator = LocalModerator::new(vec![
+            rule(
+                MatchSpec::Regex {
+                    pattern: r"(?:\p{Extended_Pictographic}\x{FE0F}?){8,}".to_string(),
+                    case_insensitive: false,
+                },
+                ModerationAction::Hide,
+            ),
+            rule(
+                MatchSpec::RepeatedChar { threshold: 5 },
+                ModerationAction::Flag,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            moderator.evaluate(&message("🎉😂❤️🔥🎉😂❤️🔥👏")),
+            vec![ModerationAction::Hide]
+        );
+        assert_eq!(
+            moderator.evaluate(&message("おめでとう🎉🎉🎉🎉🎉")),
+            vec![ModerationAction::Flag]
+        );
+        assert!(moderator.evaluate(&message("おめでとう🎉")).is_empty());
+    }
+
+    #[test]
+    fn evaluation_stops_at_first_hide() {
+        let moderator = LocalModerator::new(vec![
+            rule(
+                MatchSpec::Keyword {
+                    keywords: vec!["sale".to_string()],
+                },
+                ModerationAction::Highlight,
+            ),
+            caps_rule(ModerationAction::Hide),
+            caps_rule(ModerationAction::Flag),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            moderator.evaluate(&message("BIG SALE TODAY")),
+            vec![ModerationAction::Highlight, ModerationAction::Hide]
+        );
+    }
+
+    #[test]
+    fn keyword_match_ignores_case() {
+        let moderator = LocalModerator::new(vec![rule(
+            MatchSpec::Keyword {
+                keywords: vec!["Spam".to_string(), " ".to_string()],
+            },
+            ModerationAction::Hide,
+        )])
+        .unwrap();
+
+        assert_eq!(
+            moderator.evaluate(&message("this is SPAM")),
+            vec![ModerationAction::Hide]
+        );
+        assert!(moderator.evaluate(&message("hello world")).is_empty());
+    }
+
+    #[test]
+    fn regex_case_insensitive_flag() {
+        let spec = |case_insensitive| MatchSpec::Regex {
+            pattern: "free.*gift".to_string(),
+            case_insensitive,
+        };
+        let sensitive =
+            LocalModerator::new(vec![rule(spec(false), ModerationAction::Flag)]).unwrap();
+        let insensitive =
+            LocalModerator::new(vec![rule(spec(true), ModerationAction::Flag)]).unwrap();
+
+        assert!(sensitive.evaluate(&message("FREE GIFT")).is_empty());
+        assert_eq!(
+            insensitive.evaluate(&message("FREE GIFT")),
+            vec![ModerationAction::Flag]
+        );
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected_with_index() {
+        let cases = vec![
+            MatchSpec::Regex {
+                pattern: "(unclosed".to_string(),
+                case_insensitive: false,
+            },
+            MatchSpec::Keyword { keywords: vec![] },
+            MatchSpec::RepeatedChar { threshold: 1 },
+            MatchSpec::CapsRatio {
+                min_ratio: 1.5,
+                min_letters: 1,
+            },
+        ];
+        for spec in cases {
+            let rules = vec![
+                caps_rule(ModerationAction::Flag),
+                rule(spec.clone(), ModerationAction::Flag),
+            ];
+
+            let err = LocalModerator::new(rules).unwrap_err();
+
+            assert!(
+                matches!(err, ModerationError::InvalidRule { index: 1, .. }),
+                "{:?} は不正なルールとして扱うべき",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn rules_roundtrip_through_json_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("moderation_rules.json");
+        let rules = vec![
+            caps_rule(ModerationAction::Flag),
+            rule(
+                MatchSpec::Regex {
+                    pattern: "https?://".to_string(),
+                    case_insensitive: true,
+                },
+                ModerationAction::Hide,
+            ),
+        ];
+
+        LocalModerator::new(rules.clone())
+            .unwrap()
+            .save(&path)
+            .unwrap();
+        let loaded = LocalModerator::load(&path).unwrap();
+
+        assert_eq!(loaded.rules(), rules.as_slice());
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json[0]["pattern"]["type"], "caps_ratio");
+        assert_eq!(json[1]["action"], "hide");
+    }
+
+    #[test]
+    fn load_missing_file_returns_no_rules() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let moderator = LocalModerator::load(&dir.path().join("none.json")).unwrap();
+
+        assert!(moderator.rules().is_empty());
+        assert!(moderator.evaluate(&message("ANYTHING GOES")).is_empty());
+    }
+}
@@ -0,0 +1,325 @@
+//! キーワード通知（spec: 02_chat.md キーワード通知）
+//!
+//! 自分の名前などのキーワードを含むメッセージや、スーパーチャット・メンバーシップを受信したら
+//! `chat:notification` を発行し、フロントエンドがデスクトップ通知と通知音を出す。
+//! キーワードの連投で通知が溢れないよう、キーワード通知にはクールダウンを設ける。
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::core::models::{ChatMessage, MessageType};
+use crate::core::moderation::KeywordSet;
+
+/// キーワード通知の設定（config.toml の `[notifications]` セクション）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    /// 通知を有効にするか
+    pub enabled: bool,
+    /// 通知するキーワード（大文字小文字を区別しない部分一致）
+    pub keywords: Vec<String>,
+    /// スーパーチャット・スーパーステッカーを通知するか
+    pub on_superchat: bool,
+    /// 新規メンバー・メンバーシップギフトを通知するか
+    pub on_membership: bool,
+    /// 通知音のファイル（wav / mp3 / ogg、未設定なら鳴らさない）
+    pub sound: Option<PathBuf>,
+    /// キーワード通知の最短間隔（秒）
+    pub cooldown_secs: u64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keywords: Vec::new(),
+            on_superchat: false,
+            on_membership: false,
+            sound: None,
+            cooldown_secs: 10,
+        }
+    }
+}
+
+/// 通知した理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationReason {
+    Keyword,
+    SuperChat,
+    Membership,
+}
+
+/// 通知イベント（`chat:notification`）のペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ChatNotificationEvent {
+    pub connection_id: u64,
+    pub message_id: String,
+    pub author: String,
+    pub content: String,
+    pub reason: NotificationReason,
+    /// 直前の通知以降にクールダウンで抑制したキーワード通知の件数
+    pub suppressed: u32,
+}
+
+/// 通知の判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Notification {
+    pub reason: NotificationReason,
+    pub suppressed: u32,
+}
+
+/// 受信したメッセージを通知するか判定する（全接続で共有し、クールダウンも全体で数える）
+#[derive(Debug, Default)]
+pub struct MentionNotifier {
+    /// 最後にキーワード通知した時刻
+    last_keyword_at: Option<Instant>,
+    /// クールダウン中に抑制したキーワード通知の件数
+    suppressed: u32,
+}
+
+impl MentionNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// メッセージを通知するなら理由を返す
+    ///
+    /// スーパーチャット・メンバーシップはクールダウンの対象外。
+    /// システムメッセージは通知しない。
+    pub fn evaluate(
+        &mut self,
+        config: &NotificationConfig,
+        msg: &ChatMessage,
+        now: Instant,
+    ) -> Option<Notification> {
+        if !config.enabled {
+            return None;
+        }
+        let reason = match &msg.message_type {
+            MessageType::System => return None,
+            MessageType::SuperChat { .. } | MessageType::SuperSticker { .. }
+                if config.on_superchat =>
+            {
+                NotificationReason::SuperChat
+            }
+            MessageType::Membership { .. } | MessageType::MembershipGift { .. }
+                if config.on_membership =>
+            {
+                NotificationReason::Membership
+            }
+            _ if KeywordSet::new(&config.keywords).is_match(&msg.content) => {
+                NotificationReason::Keyword
+            }
+            _ => return None,
+        };
+        if reason != NotificationReason::Keyword {
+            return Some(Notification {
+                reason,
+                suppressed: 0,
+            });
+        }
+
+        let cooldown = Duration::from_secs(config.cooldown_secs);
+        if self
+            .last_keyword_at
+            .is_some_and(|last| now.duration_since(last) < cooldown)
+        {
+            self.suppressed += 1;
+            return None;
+        }
+        self.last_keyword_at = Some(now);
+        Some(Notification {
+            reason,
+            suppressed: std::mem::take(&mut self.suppressed),
+        })
+    }
+}
+
+/// 通知音のファイルを `<audio>` で再生できる data URL にする
+pub fn sound_data_url(path: &Path) -> std::io::Result<String> {
+    let mime = match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("mp3") => "audio/mpeg",
+        Some("ogg") => "audio/ogg",
+        _ => "audio/wav",
+    };
+    let bytes = std::fs::read(path)?;
+    Ok(format!(
+        "data:{};base64,{}",
+        mime,
+        general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> NotificationConfig {
+        NotificationConfig {
+            enabled: true,
+            keywords: vec!["Liscov".to_string(), "りすこ".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn message(content: &str) -> ChatMessage {
+        ChatMessage {
+            content: content.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn super_chat(content: &str) -> ChatMessage {
+        ChatMessage {
+            message_type: MessageType::SuperChat {
+                amount: "¥500".to_string(),
+            },
+            ..message(content)
+        }
+    }
+
+    #[test]
+    fn keyword_match_ignores_case() {
+        let mut notifier = MentionNotifier::new();
+        let now = Instant::now();
+        assert_eq!(
+            notifier.evaluate(&config(), &message("hi LISCOV!"), now),
+            Some(Notification {
+                reason: NotificationReason::Keyword,
+                suppressed: 0
+            })
+        );
+        assert_eq!(
+            notifier.evaluate(&config(), &message("hello"), now + Duration::from_secs(60)),
+            None
+        );
+    }
+
+    #[test]
+    fn keyword_spam_is_debounced() {
+        let mut notifier = MentionNotifier::new();
+        let start = Instant::now();
+        assert!(
+            notifier
+                .evaluate(&config(), &message("りすこさん"), start)
+                .is_some()
+        );
+        for secs in 1..=3 {
+            assert!(
+                notifier
+                    .evaluate(
+                        &config(),
+                        &message("りすこさん"),
+                        start + Duration::from_secs(secs)
+                    )
+                    .is_none()
+            );
+        }
+        // クールダウン明けの通知で抑制した件数を伝える
+        assert_eq!(
+            notifier.evaluate(
+                &config(),
+                &message("りすこさん"),
+                start + Duration::from_secs(10)
+            ),
+            Some(Notification {
+                reason: NotificationReason::Keyword,
+                suppressed: 3
+            })
+        );
+    }
+
+    #[test]
+    fn paid_messages_bypass_cooldown() {
+        let mut notifier = MentionNotifier::new();
+        let config = NotificationConfig {
+            on_superchat: true,
+            ..config()
+        };
+        let now = Instant::now();
+        assert!(
+            notifier
+                .evaluate(&config, &message("liscov"), now)
+                .is_some()
+        );
+        assert_eq!(
+            notifier
+                .evaluate(&config, &super_chat("liscov"), now)
+                .map(|n| n.reason),
+            Some(NotificationReason::SuperChat)
+        );
+        // キーワードのクールダウンは続いている
+        assert!(
+            notifier
+                .evaluate(&config, &message("liscov"), now)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn superchat_without_option_falls_back_to_keyword() {
+        let mut notifier = MentionNotifier::new();
+        let now = Instant::now();
+        assert_eq!(
+            notifier
+                .evaluate(&config(), &super_chat("liscov"), now)
+                .map(|n| n.reason),
+            Some(NotificationReason::Keyword)
+        );
+        assert!(
+            notifier
+                .evaluate(
+                    &config(),
+                    &super_chat("thanks"),
+                    now + Duration::from_secs(60)
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn disabled_or_system_messages_are_not_notified() {
+        let mut notifier = MentionNotifier::new();
+        let now = Instant::now();
+        let disabled = NotificationConfig {
+            enabled: false,
+            ..config()
+        };
+        assert!(
+            notifier
+                .evaluate(&disabled, &message("liscov"), now)
+                .is_none()
+        );
+
+        let system = ChatMessage {
+            message_type: MessageType::System,
+            ..message("liscov")
+        };
+        assert!(notifier.evaluate(&config(), &system, now).is_none());
+    }
+
+    #[test]
+    fn sound_file_becomes_data_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ding.MP3");
+        std::fs::write(&path, [0u8, 1]).unwrap();
+
+        assert_eq!(
+            sound_data_url(&path).unwrap(),
+            "data:audio/mpeg;base64,AAE="
+        );
+        assert!(sound_data_url(&dir.path().join("missing.wav")).is_err());
+    }
+}
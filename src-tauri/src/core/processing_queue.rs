@@ -0,0 +1,532 @@
+//! 取得と処理の間の有界キュー（spec: 02_chat.md 処理キュー）
+//!
+//! 監視ループは取得したメッセージをこのキューに積み、処理タスク（DB 保存・分析・TTS・配信）が
+//! 順に取り出す。処理が追いつかないときにメモリが際限なく増えないよう容量を設け、満杯のときは
+//! 設定に応じて取得を待たせるか、最も古い通常のチャットを破棄する。削除・スーパーチャットなどの
+//! 重要なイベントとポーリングの区切りは破棄しない。
+//!
+//! 古いものから破棄するため `mpsc` ではなく、`VecDeque` を `Mutex` で守り `Notify` で待ち合わせる。
+
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use ts_rs::TS;
+
+use crate::core::models::{ChatMessage, MessageType};
+
+/// キューの既定の容量（メッセージ数）
+pub const DEFAULT_PROCESSING_QUEUE_CAPACITY: usize = 1000;
+/// 設定できるキューの容量
+pub const PROCESSING_QUEUE_CAPACITY_RANGE: RangeInclusive<usize> = 10..=100_000;
+
+/// キューが満杯のときの動作（config.toml の `monitoring.processing_queue_policy`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueFullPolicy {
+    /// 空きができるまで取得を待たせる（メッセージを失わない）
+    #[default]
+    Block,
+    /// キューで最も古い通常のチャットを破棄して新しいものを積む（取得は止めない）
+    ///
+    /// ライブの表示では古い未処理のチャットより新しいチャットに価値があるため。
+    DropOldest,
+}
+
+/// 監視ループから処理タスクへ渡すもの
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum ProcessingItem {
+    /// 取得したメッセージ（変換済み）
+    Message(ChatMessage),
+    /// 削除されたメッセージの ID
+    Deleted(Vec<String>),
+    /// 1 回のポーリングの区切り。ここまで処理したら取得位置（continuation）を保存する
+    PollEnd { continuation: Option<String> },
+}
+
+impl ProcessingItem {
+    /// 1 回のポーリングで取得したものを積む順に並べる
+    ///
+    /// 削除は同じレスポンスで追加されたものも対象にするため、メッセージの後に積む。
+    pub fn poll(
+        messages: Vec<ChatMessage>,
+        deleted_ids: Vec<String>,
+        continuation: Option<String>,
+    ) -> Vec<Self> {
+        let mut items: Vec<Self> = messages.into_iter().map(Self::Message).collect();
+        if !deleted_ids.is_empty() {
+            items.push(Self::Deleted(deleted_ids));
+        }
+        items.push(Self::PollEnd { continuation });
+        items
+    }
+
+    /// 満杯のときに破棄してよいか（通常のチャットだけ）
+    pub fn is_droppable(&self) -> bool {
+        matches!(self, Self::Message(msg) if matches!(msg.message_type, MessageType::Text))
+    }
+}
+
+/// キューの状態（`chat:connection_health` で公開する）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ProcessingQueueStats {
+    /// 処理待ちの件数
+    pub queued: usize,
+    /// キューの容量
+    pub capacity: usize,
+    /// 満杯のため破棄したメッセージ数（接続してからの累計）
+    #[ts(type = "number")]
+    pub dropped: u64,
+}
+
+impl ProcessingQueueStats {
+    /// 処理が追いついていないか（容量の 8 割以上が埋まっている）
+    pub fn is_backlogged(&self) -> bool {
+        self.capacity > 0 && self.queued * 5 >= self.capacity * 4
+    }
+}
+
+/// 送信口と受信口が共有するキューの状態
+#[derive(Debug)]
+struct QueueState {
+    items: VecDeque<ProcessingItem>,
+    /// 生きている送信口の数（0 になったら閉じる）
+    senders: usize,
+    /// 受信口が破棄された
+    receiver_closed: bool,
+    /// 満杯のため破棄したメッセージ数（接続してからの累計）
+    dropped: u64,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<QueueState>,
+    capacity: usize,
+    policy: QueueFullPolicy,
+    /// 積まれた・送信口がすべて破棄された
+    item_ready: Notify,
+    /// 空きができた・受信口が破棄された
+    space_ready: Notify,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 監視ループ側の送信口
+#[derive(Debug)]
+pub struct ProcessingSender {
+    shared: Arc<Shared>,
+}
+
+/// 処理タスク側の受信口
+#[derive(Debug)]
+pub struct ProcessingReceiver {
+    shared: Arc<Shared>,
+}
+
+/// 容量 `capacity` の処理キューを作る
+pub fn processing_queue(
+    capacity: usize,
+    policy: QueueFullPolicy,
+) -> (ProcessingSender, ProcessingReceiver) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(QueueState {
+            items: VecDeque::new(),
+            senders: 1,
+            receiver_closed: false,
+            dropped: 0,
+        }),
+        capacity: capacity.max(1),
+        policy,
+        item_ready: Notify::new(),
+        space_ready: Notify::new(),
+    });
+    let sender = ProcessingSender {
+        shared: Arc::clone(&shared),
+    };
+    (sender, ProcessingReceiver { shared })
+}
+
+impl Clone for ProcessingSender {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for ProcessingSender {
+    fn drop(&mut self) {
+        let closed = {
+            let mut state = self.shared.lock();
+            state.senders -= 1;
+            state.senders == 0
+        };
+        if closed {
+            self.shared.item_ready.notify_waiters();
+        }
+    }
+}
+
+impl Drop for ProcessingReceiver {
+    fn drop(&mut self) {
+        self.shared.lock().receiver_closed = true;
+        self.shared.space_ready.notify_waiters();
+    }
+}
+
+impl ProcessingSender {
+    /// キューに積む
+    ///
+    /// 満杯のとき、`DropOldest` で通常のチャットならキューで最も古い通常のチャットを破棄して数える
+    /// （キューに通常のチャットがなければ積もうとしたものを破棄する）。それ以外は空きができるまで待つ。
+    /// 処理タスクが終了していれば false を返す。
+    pub async fn send(&self, item: ProcessingItem) -> bool {
+        let shared = &*self.shared;
+        loop {
+            // 確認と待機の間の通知を取りこぼさないよう、先に待機を登録する
+            let space_ready = shared.space_ready.notified();
+            tokio::pin!(space_ready);
+            space_ready.as_mut().enable();
+            {
+                let mut state = shared.lock();
+                if state.receiver_closed {
+                    return false;
+                }
+                if state.items.len() < shared.capacity {
+                    state.items.push_back(item);
+                    drop(state);
+                    shared.item_ready.notify_waiters();
+                    return true;
+                }
+                if shared.policy == QueueFullPolicy::DropOldest && item.is_droppable() {
+                    state.dropped += 1;
+                    if let Some(oldest) = state.items.iter().position(ProcessingItem::is_droppable)
+                    {
+                        state.items.remove(oldest);
+                        state.items.push_back(item);
+                        drop(state);
+                        shared.item_ready.notify_waiters();
+                    }
+                    return true;
+                }
+            }
+            space_ready.await;
+        }
+    }
+
+    /// 順に積む（処理タスクが終了していれば残りは積まずに false を返す）
+    pub async fn send_all(&self, items: impl IntoIterator<Item = ProcessingItem>) -> bool {
+        for item in items {
+            if !self.send(item).await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// 現在のキューの状態
+    pub fn stats(&self) -> ProcessingQueueStats {
+        let state = self.shared.lock();
+        ProcessingQueueStats {
+            queued: state.items.len(),
+            capacity: self.shared.capacity,
+            dropped: state.dropped,
+        }
+    }
+}
+
+impl ProcessingReceiver {
+    /// 先頭の 1 件を待ち、続けて取り出せるものを合わせて最大 `limit` 件返す
+    ///
+    /// 空で送信口がすべて破棄されていれば None を返す。
+    pub async fn recv_batch(&mut self, limit: usize) -> Option<Vec<ProcessingItem>> {
+        let shared = &*self.shared;
+        loop {
+            let item_ready = shared.item_ready.notified();
+            tokio::pin!(item_ready);
+            item_ready.as_mut().enable();
+            {
+                let mut state = shared.lock();
+                if !state.items.is_empty() {
+                    let count = state.items.len().min(limit.max(1));
+                    let batch: Vec<_> = state.items.drain(..count).collect();
+                    drop(state);
+                    shared.space_ready.notify_waiters();
+                    return Some(batch);
+                }
+                if state.senders == 0 {
+                    return None;
+                }
+            }
+            item_ready.await;
+        }
+    }
+}
+
+/// 処理キューから取り出したものを処理する側
+pub trait ProcessingHandler {
+    /// 取り出した分を積まれた順に処理する
+    fn handle(&mut self, items: Vec<ProcessingItem>) -> impl Future<Output = ()> + Send;
+}
+
+/// 処理キューが閉じる（監視ループが終わる）まで、積まれたものを最大 `limit` 件ずつ `handler` に渡す
+///
+/// キャンセルされても積まれた分は捨てない。監視ループはキャンセルを検知すると送信口を手放すため、
+/// 残りを処理し終えたところで終わる（待機中の送信も取り出すことで解除される）。
+pub async fn run_processing_queue<H: ProcessingHandler>(
+    handler: &mut H,
+    rx: &mut ProcessingReceiver,
+    limit: usize,
+) {
+    while let Some(items) = rx.recv_batch(limit).await {
+        handler.handle(items).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn text(id: &str) -> ProcessingItem {
+        ProcessingItem::Message(ChatMessage {
+            id: id.to_string(),
+            message_type: MessageType::Text,
+            ..Default::default()
+        })
+    }
+
+    fn super_chat(id: &str) -> ProcessingItem {
+        ProcessingItem::Message(ChatMessage {
+            id: id.to_string(),
+            message_type: MessageType::SuperChat {
+                amount: "¥1,000".to_string(),
+            },
+            ..Default::default()
+        })
+    }
+
+    fn ids(batch: &[ProcessingItem]) -> Vec<String> {
+        batch
+            .iter()
+            .map(|item| match item {
+                ProcessingItem::Message(msg) => msg.id.clone(),
+                ProcessingItem::Deleted(ids) => format!("deleted:{}", ids.join(",")),
+                ProcessingItem::PollEnd { .. } => "poll_end".to_string(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_discards_old_chat_but_keeps_important_items() {
+        let (tx, mut rx) = processing_queue(3, QueueFullPolicy::DropOldest);
+        assert!(tx.send(super_chat("1")).await);
+        assert!(tx.send(text("2")).await);
+        assert!(tx.send(text("3")).await);
+        // 満杯なので最も古い通常のチャット "2" を破棄して積む（スーパーチャットは残す）
+        assert!(tx.send(text("4")).await);
+        assert_eq!(
+            tx.stats(),
+            ProcessingQueueStats {
+                queued: 3,
+                capacity: 3,
+                dropped: 1,
+            }
+        );
+        assert!(tx.stats().is_backlogged());
+
+        // 満杯でもスーパーチャット・削除・区切りは空きを待って積む
+        let important = tokio::spawn({
+            let tx = tx.clone();
+            async move {
+                tx.send(super_chat("5")).await
+                    && tx
+                        .send(ProcessingItem::Deleted(vec!["1".to_string()]))
+                        .await
+                    && tx
+                        .send(ProcessingItem::PollEnd { continuation: None })
+                        .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!important.is_finished());
+
+        let mut received = Vec::new();
+        while received.len() < 6 {
+            received.extend(rx.recv_batch(10).await.unwrap());
+        }
+        assert!(important.await.unwrap());
+        assert_eq!(
+            ids(&received),
+            ["1", "3", "4", "5", "deleted:1", "poll_end"]
+        );
+        assert_eq!(tx.stats().dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_discards_incoming_chat_when_nothing_queued_is_droppable() {
+        let (tx, mut rx) = processing_queue(2, QueueFullPolicy::DropOldest);
+        assert!(tx.send(super_chat("1")).await);
+        assert!(
+            tx.send(ProcessingItem::PollEnd { continuation: None })
+                .await
+        );
+        assert!(tx.send(text("2")).await);
+        assert_eq!(tx.stats().dropped, 1);
+        drop(tx);
+
+        assert_eq!(ids(&rx.recv_batch(10).await.unwrap()), ["1", "poll_end"]);
+        assert!(rx.recv_batch(10).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn block_waits_for_room_without_dropping() {
+        let (tx, mut rx) = processing_queue(2, QueueFullPolicy::Block);
+        assert!(tx.send(text("1")).await);
+        assert!(tx.send(text("2")).await);
+
+        let blocked = tokio::spawn({
+            let tx = tx.clone();
+            async move { tx.send(text("3")).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!blocked.is_finished());
+
+        assert_eq!(ids(&rx.recv_batch(1).await.unwrap()), ["1"]);
+        assert!(blocked.await.unwrap());
+        assert_eq!(ids(&rx.recv_batch(10).await.unwrap()), ["2", "3"]);
+        assert_eq!(tx.stats().dropped, 0);
+        assert_eq!(tx.stats().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn send_fails_after_the_processor_stops() {
+        let (tx, rx) = processing_queue(2, QueueFullPolicy::DropOldest);
+        drop(rx);
+        assert!(!tx.send(text("1")).await);
+        assert!(!tx.send(super_chat("2")).await);
+        assert_eq!(tx.stats().dropped, 0);
+    }
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        handled: Vec<String>,
+    }
+
+    impl ProcessingHandler for RecordingHandler {
+        async fn handle(&mut self, items: Vec<ProcessingItem>) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            self.handled.extend(ids(&items));
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_connection_processes_queued_items() {
+        let (tx, mut rx) = processing_queue(2, QueueFullPolicy::Block);
+        let cancellation_token = tokio_util::sync::CancellationToken::new();
+
+        // 監視ループ: 満杯で待たされている間にキャンセルされ、積み終えたら送信口を手放す
+        let monitor = tokio::spawn({
+            let cancellation_token = cancellation_token.clone();
+            async move {
+                for item in [
+                    text("1"),
+                    text("2"),
+                    text("3"),
+                    ProcessingItem::PollEnd { continuation: None },
+                ] {
+                    assert!(tx.send(item).await);
+                }
+                cancellation_token.cancelled().await;
+                drop(tx);
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        cancellation_token.cancel();
+
+        let mut handler = RecordingHandler::default();
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            run_processing_queue(&mut handler, &mut rx, 10),
+        )
+        .await
+        .expect("キューが閉じたら終わる");
+        monitor.await.unwrap();
+        assert_eq!(handler.handled, ["1", "2", "3", "poll_end"]);
+    }
+
+    #[test]
+    fn poll_orders_deletions_after_messages() {
+        let message = |id: &str| ChatMessage {
+            id: id.to_string(),
+            ..Default::default()
+        };
+        let items = ProcessingItem::poll(
+            vec![message("1"), message("2")],
+            vec!["1".to_string()],
+            Some("next".to_string()),
+        );
+        assert_eq!(ids(&items), ["1", "2", "deleted:1", "poll_end"]);
+        assert!(matches!(
+            items.last(),
+            Some(ProcessingItem::PollEnd { continuation: Some(c) }) if c == "next"
+        ));
+
+        let items = ProcessingItem::poll(vec![], vec![], None);
+        assert_eq!(ids(&items), ["poll_end"]);
+    }
+
+    #[tokio::test]
+    async fn batch_fetched_before_cancel_is_processed() {
+        let (tx, mut rx) = processing_queue(10, QueueFullPolicy::Block);
+        let cancellation_token = tokio_util::sync::CancellationToken::new();
+        let processor = tokio::spawn(async move {
+            let mut handler = RecordingHandler::default();
+            run_processing_queue(&mut handler, &mut rx, 10).await;
+            handler.handled
+        });
+
+        // 監視ループ: フェッチ中にキャンセルされても、取得した分を積んでから抜ける
+        cancellation_token.cancel();
+        let message = ChatMessage {
+            id: "1".to_string(),
+            ..Default::default()
+        };
+        if cancellation_token.is_cancelled() {
+            assert!(
+                tx.send_all(ProcessingItem::poll(
+                    vec![message],
+                    vec!["0".to_string()],
+                    None
+                ))
+                .await
+            );
+        }
+        drop(tx);
+
+        assert_eq!(processor.await.unwrap(), ["1", "deleted:0", "poll_end"]);
+    }
+
+    #[test]
+    fn backlog_threshold() {
+        let stats = |queued| ProcessingQueueStats {
+            queued,
+            capacity: 10,
+            dropped: 0,
+        };
+        assert!(!stats(7).is_backlogged());
+        assert!(stats(8).is_backlogged());
+        assert!(!ProcessingQueueStats::default().is_backlogged());
+    }
+}
@@ -6,9 +6,13 @@ use serde::{Deserialize, Serialize};
 use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::fs::metadata;
 use tracing::{info, warn};
 
+use crate::core::api::UnknownAction;
+
 /// 保存設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveConfig {
@@ -22,6 +26,49 @@ pub struct SaveConfig {
     pub enable_rotation: bool,
     /// 最大保持ファイル数
     pub max_backup_files: u32,
+    /// ローテーション条件（指定時は `enable_rotation` / `max_file_size_mb` より優先）
+    #[serde(default)]
+    pub rotation: Option<RotationPolicy>,
+}
+
+/// ローテーション条件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationPolicy {
+    /// ファイルサイズ（バイト）が閾値以上になったら
+    BySize(u64),
+    /// 現在のファイルへの書き込み開始から指定時間が経過したら（秒数で保存）
+    ByDuration(#[serde(with = "duration_secs")] Duration),
+    /// 現在のファイルへの書き込み件数が閾値に達したら
+    ByMessageCount(usize),
+}
+
+/// `Duration` を秒数としてシリアライズする
+mod duration_secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+/// 現在のファイルに関するローテーション判定用の状態
+///
+/// 件数と経過時間はメモリ上で数えるため、判定のたびにファイルを読み直さない。
+/// 起動前から存在するファイルへの書き込みは、このセッションでの件数・時間から数え始める。
+#[derive(Debug, Default)]
+struct RotationState {
+    /// 現在のファイルに書き込んだ件数
+    message_count: usize,
+    /// 現在のファイルへの最初の書き込み時刻
+    started_at: Option<Instant>,
+    /// 次のローテーションで付ける連番
+    next_sequence: u32,
 }
 
 impl Default for SaveConfig {
@@ -32,20 +79,384 @@ impl Default for SaveConfig {
             max_file_size_mb: 100,
             enable_rotation: true,
             max_backup_files: 5,
+            rotation: None,
+        }
+    }
+}
+
+/// 保存ファイルの1行分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseEntry {
+    /// 保存時刻（Unixタイムスタンプ、秒）
+    pub timestamp: i64,
+    /// 生レスポンス（JSONとして解釈できなかった場合は文字列）
+    pub response: serde_json::Value,
+}
+
+/// 読み込み時にスキップした行
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// 行番号（1始まり）
+    pub line: usize,
+    /// 行の内容（先頭 `WARNING_CONTENT_MAX_CHARS` 文字まで）
+    pub content: String,
+    /// パースエラーの内容
+    pub message: String,
+}
+
+/// `ParseWarning::content` に残す最大文字数
+const WARNING_CONTENT_MAX_CHARS: usize = 200;
+
+/// 保存ファイルを1行ずつ読む際のエラー
+#[derive(Debug, thiserror::Error)]
+pub enum NdjsonReadError {
+    /// ファイルの読み込みに失敗した（以降の行は読まない）
+    #[error("Failed to read raw response file: {0}")]
+    Io(#[from] std::io::Error),
+    /// 行をパースできなかった（次の行から読み込みを続けられる）
+    #[error("Malformed line {}: {}", .0.line, .0.message)]
+    Parse(ParseWarning),
+}
+
+/// 保存ファイルを1行ずつパースするイテレータ（`ndjson_entries` が返す）
+struct NdjsonEntries<R> {
+    reader: R,
+    buf: Vec<u8>,
+    line_number: usize,
+    finished: bool,
+}
+
+impl<R: BufRead> Iterator for NdjsonEntries<R> {
+    type Item = std::result::Result<ResponseEntry, NdjsonReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            self.buf.clear();
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => self.finished = true,
+                Ok(_) => {
+                    self.line_number += 1;
+
+                    // 途中で切れたマルチバイト文字も行単位のパースエラーで済ませる
+                    let line = String::from_utf8_lossy(&self.buf);
+                    let line = line.trim_end_matches(['\r', '\n']);
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+
+                    return Some(serde_json::from_str::<ResponseEntry>(line).map_err(|e| {
+                        NdjsonReadError::Parse(ParseWarning {
+                            line: self.line_number,
+                            content: line.chars().take(WARNING_CONTENT_MAX_CHARS).collect(),
+                            message: e.to_string(),
+                        })
+                    }));
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(NdjsonReadError::Io(e)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 保存ファイルを1行ずつ遅延パースする
+///
+/// ファイル全体をメモリに載せないため、長時間配信の巨大なファイルも畳み込みで集計できる。
+/// 空行は読み飛ばす。パースエラーの後も次の行から読み続けるが、
+/// 読み込みエラー（`NdjsonReadError::Io`）の後は何も返さない。
+pub fn ndjson_entries(
+    path: impl AsRef<Path>,
+) -> Result<impl Iterator<Item = std::result::Result<ResponseEntry, NdjsonReadError>>> {
+    let path = path.as_ref();
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open raw response file: {}", path.display()))?;
+    Ok(NdjsonEntries {
+        reader: BufReader::new(file),
+        buf: Vec::new(),
+        line_number: 0,
+        finished: false,
+    })
+}
+
+/// 保存ファイルを読み込み、パースできない行はスキップする
+///
+/// 書き込み中の異常終了で末尾が途切れたファイルでも、読めた行はすべて返す。
+/// スキップした行は `ParseWarning` として返し、空行は無視する。
+/// エラーになるのはファイル自体を読めない場合のみ。
+pub fn parse_ndjson_file_lenient(
+    path: impl AsRef<Path>,
+) -> Result<(Vec<ResponseEntry>, Vec<ParseWarning>)> {
+    let path = path.as_ref();
+
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+    for item in ndjson_entries(path)? {
+        match item {
+            Ok(entry) => entries.push(entry),
+            Err(NdjsonReadError::Parse(warning)) => warnings.push(warning),
+            Err(e @ NdjsonReadError::Io(_)) => return Err(e.into()),
+        }
+    }
+
+    if !warnings.is_empty() {
+        warn!(
+            "Skipped {} malformed line(s) in {}",
+            warnings.len(),
+            path.display()
+        );
+    }
+
+    Ok((entries, warnings))
+}
+
+/// レスポンスとパーサーが期待する構造との不一致
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaMismatch {
+    /// 不一致の位置（JSON Pointer 形式、例: `/continuationContents/liveChatContinuation/actions/3`）
+    pub path: String,
+    /// 期待した型（`object` / `array` / `string` など）
+    pub expected: String,
+    /// 実際の型（値がなければ `missing`、JSON として読めなければエラー内容）
+    pub found: String,
+}
+
+/// パーサーがメッセージとして読むレンダラーと、その必須フィールド
+const REQUIRED_RENDERER_FIELDS: &[(&str, &[(&str, JsonKind)])] = &[
+    (
+        "liveChatTextMessageRenderer",
+        &[
+            ("/id", JsonKind::String),
+            ("/timestampUsec", JsonKind::String),
+            ("/message", JsonKind::Object),
+        ],
+    ),
+    (
+        "liveChatPaidMessageRenderer",
+        &[
+            ("/id", JsonKind::String),
+            ("/timestampUsec", JsonKind::String),
+        ],
+    ),
+    (
+        "liveChatPaidStickerRenderer",
+        &[
+            ("/id", JsonKind::String),
+            ("/timestampUsec", JsonKind::String),
+        ],
+    ),
+    (
+        "liveChatMembershipItemRenderer",
+        &[
+            ("/id", JsonKind::String),
+            ("/timestampUsec", JsonKind::String),
+        ],
+    ),
+    (
+        "liveChatSponsorshipsGiftPurchaseAnnouncementRenderer",
+        &[
+            ("/id", JsonKind::String),
+            ("/timestampUsec", JsonKind::String),
+            (
+                "/header/liveChatSponsorshipsHeaderRenderer",
+                JsonKind::Object,
+            ),
+        ],
+    ),
+];
+
+/// 検証で扱う JSON の型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonKind {
+    Object,
+    Array,
+    String,
+}
+
+impl JsonKind {
+    fn name(self) -> &'static str {
+        match self {
+            JsonKind::Object => "object",
+            JsonKind::Array => "array",
+            JsonKind::String => "string",
+        }
+    }
+
+    fn matches(self, value: &serde_json::Value) -> bool {
+        match self {
+            JsonKind::Object => value.is_object(),
+            JsonKind::Array => value.is_array(),
+            JsonKind::String => value.is_string(),
+        }
+    }
+}
+
+/// JSON の値の型名
+fn kind_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+/// `path` の値が `kind` でなければ不一致を記録する（一致すれば値を返す）
+fn expect_kind<'a>(
+    root: &'a serde_json::Value,
+    path: &str,
+    kind: JsonKind,
+    mismatches: &mut Vec<SchemaMismatch>,
+) -> Option<&'a serde_json::Value> {
+    match root.pointer(path) {
+        Some(value) if kind.matches(value) => Some(value),
+        found => {
+            mismatches.push(SchemaMismatch {
+                path: path.to_string(),
+                expected: kind.name().to_string(),
+                found: found.map_or("missing", kind_name).to_string(),
+            });
+            None
         }
     }
 }
 
+/// 保存したレスポンスがチャットパーサーの期待する構造か検証する
+///
+/// `get_live_chat` のレスポンス（`ResponseEntry::response`）を受け取り、
+/// `continuationContents.liveChatContinuation` と、メッセージとして読むアクションの
+/// 必須フィールドを確認する。パーサーが読み飛ばす未知のアクション・レンダラーは検証しない。
+/// JSON として読めない場合は行・列を含むエラー内容を 1 件だけ返す。
+pub fn validate_response(bytes: &[u8]) -> std::result::Result<(), Vec<SchemaMismatch>> {
+    let root: serde_json::Value = serde_json::from_slice(bytes).map_err(|e| {
+        vec![SchemaMismatch {
+            path: String::new(),
+            expected: "JSON".to_string(),
+            found: e.to_string(),
+        }]
+    })?;
+
+    let mut mismatches = Vec::new();
+    let continuation = "/continuationContents/liveChatContinuation";
+    if expect_kind(&root, continuation, JsonKind::Object, &mut mismatches).is_some() {
+        // メッセージがないレスポンスでは actions 自体が省略される
+        let actions_path = format!("{}/actions", continuation);
+        let actions = match root.pointer(&actions_path) {
+            Some(_) => expect_kind(&root, &actions_path, JsonKind::Array, &mut mismatches),
+            None => None,
+        };
+        for (index, action) in actions
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            validate_action(
+                action,
+                &format!("{}/{}", actions_path, index),
+                &mut mismatches,
+            );
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+/// アクション 1 件のうち、パーサーがメッセージとして読むレンダラーの必須フィールドを確認する
+///
+/// パーサーと同じく、アクションごとに最初に見つかったレンダラーだけを確認する。
+fn validate_action(action: &serde_json::Value, path: &str, mismatches: &mut Vec<SchemaMismatch>) {
+    let Some(item_path) = [
+        "/replayChatItemAction/actions/0/addChatItemAction/item",
+        "/addChatItemAction/item",
+    ]
+    .into_iter()
+    .find(|pointer| action.pointer(pointer).is_some()) else {
+        return;
+    };
+    let Some((renderer, fields)) = REQUIRED_RENDERER_FIELDS.iter().find(|(renderer, _)| {
+        action
+            .pointer(&format!("{}/{}", item_path, renderer))
+            .is_some()
+    }) else {
+        return;
+    };
+
+    let renderer_path = format!("{}/{}", item_path, renderer);
+    let mut found = Vec::new();
+    for (field, kind) in *fields {
+        expect_kind(
+            action,
+            &format!("{}{}", renderer_path, field),
+            *kind,
+            &mut found,
+        );
+    }
+    // アクション内のパスをレスポンス全体のパスにする
+    mismatches.extend(found.into_iter().map(|mismatch| SchemaMismatch {
+        path: format!("{}{}", path, mismatch.path),
+        ..mismatch
+    }));
+}
+
+/// JSON を整形して返す（保存ファイルの 1 行をバグ報告に貼る用途）
+pub fn pretty_print(bytes: &[u8]) -> serde_json::Result<String> {
+    let value: serde_json::Value = serde_json::from_slice(bytes)?;
+    serde_json::to_string_pretty(&value)
+}
+
+/// 未対応のアクションを 1 行 1 件の NDJSON で追記する（ファイル・親ディレクトリがなければ作る）
+///
+/// 各行は `{"timestamp": <Unix 秒>, "video_id": ..., "kind": ..., "raw": <生の JSON>}`。
+pub fn append_unknown_actions(
+    path: &Path,
+    video_id: &str,
+    actions: &[UnknownAction],
+) -> std::io::Result<()> {
+    if actions.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let timestamp = Utc::now().timestamp();
+    let mut lines = String::new();
+    for action in actions {
+        let entry = serde_json::json!({
+            "timestamp": timestamp,
+            "video_id": video_id,
+            "kind": action.kind,
+            "raw": action.raw,
+        });
+        lines.push_str(&entry.to_string());
+        lines.push('\n');
+    }
+    file.write_all(lines.as_bytes())
+}
+
 /// YouTubeレスポンス保存管理
 #[derive(Debug)]
 pub struct RawResponseSaver {
     config: SaveConfig,
+    rotation_state: Mutex<RotationState>,
 }
 
 impl RawResponseSaver {
     /// 新しいインスタンスを作成
     pub fn new(config: SaveConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            rotation_state: Mutex::new(RotationState::default()),
+        }
     }
 
     /// レスポンスを保存 (JSON文字列として受け取る)
@@ -62,22 +473,29 @@ impl RawResponseSaver {
         );
 
         // ファイルサイズチェックとローテーション
-        if self.config.enable_rotation {
-            self.check_and_rotate_file().await?;
+        match &self.config.rotation {
+            Some(policy) => self.rotate_by_policy(policy).await?,
+            None if self.config.enable_rotation => self.check_and_rotate_file().await?,
+            None => {}
         }
 
         // タイムスタンプを追加してJSON行を作成
-        let entry = serde_json::json!({
-            "timestamp": Utc::now().timestamp(),
-            "response": serde_json::from_str::<serde_json::Value>(response_json)
-                .unwrap_or_else(|_| serde_json::Value::String(response_json.to_string()))
-        });
+        let entry = ResponseEntry {
+            timestamp: Utc::now().timestamp(),
+            response: serde_json::from_str::<serde_json::Value>(response_json)
+                .unwrap_or_else(|_| serde_json::Value::String(response_json.to_string())),
+        };
 
         let json_line =
             serde_json::to_string(&entry).context("Failed to serialize response to JSON")?;
 
         // ファイルに追記
         self.append_to_file(&json_line).await?;
+        {
+            let mut state = self.lock_rotation_state();
+            state.message_count += 1;
+            state.started_at.get_or_insert_with(Instant::now);
+        }
 
         tracing::info!(
             "💾 Raw response saved successfully to: {}",
@@ -100,6 +518,46 @@ impl RawResponseSaver {
         Ok(())
     }
 
+    fn lock_rotation_state(&self) -> std::sync::MutexGuard<'_, RotationState> {
+        self.rotation_state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// `RotationPolicy` の条件を満たしていればローテーション
+    ///
+    /// 存在しないファイル・空のファイルはローテーションしない。
+    async fn rotate_by_policy(&self, policy: &RotationPolicy) -> Result<()> {
+        let file_path = Path::new(&self.config.file_path);
+        if !file_path.exists() {
+            return Ok(());
+        }
+        let file_size = metadata(&self.config.file_path).await?.len();
+        if file_size == 0 {
+            return Ok(());
+        }
+
+        let should_rotate = match policy {
+            RotationPolicy::BySize(max_bytes) => file_size >= *max_bytes,
+            RotationPolicy::ByDuration(max_duration) => {
+                let state = self.lock_rotation_state();
+                state
+                    .started_at
+                    .is_some_and(|started_at| started_at.elapsed() >= *max_duration)
+            }
+            RotationPolicy::ByMessageCount(max_count) => {
+                self.lock_rotation_state().message_count >= *max_count
+            }
+        };
+
+        if should_rotate {
+            info!("Rotation policy {:?} triggered, rotating file", policy);
+            self.rotate_file().await?;
+        }
+
+        Ok(())
+    }
+
     /// ファイルサイズをチェックしてローテーション
     async fn check_and_rotate_file(&self) -> Result<()> {
         let file_path = Path::new(&self.config.file_path);
@@ -145,9 +603,18 @@ impl RawResponseSaver {
 
         let now = Utc::now();
         let timestamp = now.format("%Y%m%d_%H%M%S");
-
-        // 新しいファイル名を生成
-        let rotated_name = format!("{}_{}.{}", file_stem, timestamp, file_ext);
+        let sequence = {
+            let mut state = self.lock_rotation_state();
+            let sequence = state.next_sequence;
+            *state = RotationState {
+                next_sequence: sequence + 1,
+                ..RotationState::default()
+            };
+            sequence
+        };
+
+        // 新しいファイル名を生成（同一秒内のローテーションでも重複しないよう連番を付ける）
+        let rotated_name = format!("{}_{}_{:03}.{}", file_stem, timestamp, sequence, file_ext);
         let rotated_path = file_path
             .parent()
             .unwrap_or_else(|| Path::new("."))
@@ -259,6 +726,33 @@ mod tests {
         dir
     }
 
+    #[test]
+    fn append_unknown_actions_writes_one_line_per_action() {
+        let dir = temp_dir_for_test("unknown_actions");
+        let path = dir.join("sub").join("unknown.ndjson");
+        let action = |kind: &str| UnknownAction {
+            kind: kind.to_string(),
+            raw: serde_json::json!({ kind: { "foo": 1 } }),
+        };
+
+        append_unknown_actions(&path, "video_1", &[]).unwrap();
+        assert!(!path.exists());
+        append_unknown_actions(&path, "video_1", &[action("newAction")]).unwrap();
+        append_unknown_actions(&path, "video_1", &[action("liveChatNewRenderer")]).unwrap();
+
+        let lines: Vec<serde_json::Value> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["video_id"], "video_1");
+        assert_eq!(lines[0]["kind"], "newAction");
+        assert_eq!(lines[0]["raw"]["newAction"]["foo"], 1);
+        assert_eq!(lines[1]["kind"], "liveChatNewRenderer");
+        assert!(lines[1]["timestamp"].is_i64());
+    }
+
     // ========================================================================
     // SaveConfig defaults (05_raw_response.md: デフォルト値)
     // ========================================================================
@@ -398,6 +892,7 @@ mod tests {
             max_file_size_mb: 1, // 1 MB limit
             enable_rotation: true,
             max_backup_files: 5,
+            rotation: None,
         });
 
         saver.save_response(r#"{"new": true}"#).await.unwrap();
@@ -447,6 +942,7 @@ mod tests {
             max_file_size_mb: 1,
             enable_rotation: true,
             max_backup_files: 5,
+            rotation: None,
         });
 
         saver
@@ -494,6 +990,7 @@ mod tests {
             max_file_size_mb: 1,
             enable_rotation: true,
             max_backup_files: 3, // ちょうど既存件数と同じ
+            rotation: None,
         });
 
         saver.save_response(r#"{"test": true}"#).await.unwrap();
@@ -543,6 +1040,7 @@ mod tests {
             max_file_size_mb: 1,
             enable_rotation: true,
             max_backup_files: 3, // Only keep 3 backups
+            rotation: None,
         });
 
         saver.save_response(r#"{"test": true}"#).await.unwrap();
@@ -563,4 +1061,420 @@ mod tests {
             backup_count
         );
     }
+
+    // ========================================================================
+    // RotationPolicy (05_raw_response.md: ローテーション条件)
+    // ========================================================================
+
+    fn backup_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("responses_") && name.ends_with(".ndjson"))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn policy_saver(file_path: &Path, policy: RotationPolicy) -> RawResponseSaver {
+        RawResponseSaver::new(SaveConfig {
+            enabled: true,
+            file_path: file_path.to_string_lossy().to_string(),
+            enable_rotation: false,
+            max_backup_files: 10,
+            rotation: Some(policy),
+            ..SaveConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn rotation_by_message_count() {
+        let dir = temp_dir_for_test("policy_count");
+        let file_path = dir.join("responses.ndjson");
+        let saver = policy_saver(&file_path, RotationPolicy::ByMessageCount(2));
+
+        for i in 0..5 {
+            saver
+                .save_response(&format!(r#"{{"msg": {}}}"#, i))
+                .await
+                .unwrap();
+        }
+
+        // 2件 + 2件でローテーションし、現在のファイルに1件残る
+        let backups = backup_names(&dir);
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].ends_with("_000.ndjson"), "{:?}", backups);
+        assert!(backups[1].ends_with("_001.ndjson"), "{:?}", backups);
+        assert_eq!(saver.get_saved_response_count().unwrap(), 1);
+        for backup in &backups {
+            let (entries, _) = parse_ndjson_file_lenient(dir.join(backup)).unwrap();
+            assert_eq!(entries.len(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn rotation_by_size_in_bytes() {
+        let dir = temp_dir_for_test("policy_size");
+        let file_path = dir.join("responses.ndjson");
+        let saver = policy_saver(&file_path, RotationPolicy::BySize(10));
+
+        saver.save_response(r#"{"msg": 1}"#).await.unwrap();
+        assert!(backup_names(&dir).is_empty());
+
+        saver.save_response(r#"{"msg": 2}"#).await.unwrap();
+        assert_eq!(backup_names(&dir).len(), 1);
+        assert_eq!(saver.get_saved_response_count().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn rotation_by_duration() {
+        let dir = temp_dir_for_test("policy_duration");
+        let file_path = dir.join("responses.ndjson");
+        let saver = policy_saver(
+            &file_path,
+            RotationPolicy::ByDuration(Duration::from_millis(50)),
+        );
+
+        saver.save_response(r#"{"msg": 1}"#).await.unwrap();
+        saver.save_response(r#"{"msg": 2}"#).await.unwrap();
+        assert!(backup_names(&dir).is_empty());
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        saver.save_response(r#"{"msg": 3}"#).await.unwrap();
+
+        assert_eq!(backup_names(&dir).len(), 1);
+        assert_eq!(saver.get_saved_response_count().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn rotation_policy_does_not_rotate_missing_file() {
+        let dir = temp_dir_for_test("policy_missing");
+        let file_path = dir.join("responses.ndjson");
+        let saver = policy_saver(&file_path, RotationPolicy::ByMessageCount(0));
+
+        saver.save_response(r#"{"msg": 1}"#).await.unwrap();
+
+        assert!(backup_names(&dir).is_empty());
+    }
+
+    #[test]
+    fn rotation_policy_serde_format() {
+        let config = SaveConfig {
+            rotation: Some(RotationPolicy::ByDuration(Duration::from_secs(3600))),
+            ..SaveConfig::default()
+        };
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["rotation"], serde_json::json!({ "by_duration": 3600 }));
+
+        let loaded: SaveConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.rotation, config.rotation);
+
+        // rotation のない既存設定も読める
+        let legacy: SaveConfig = serde_json::from_str(
+            r#"{"enabled":true,"file_path":"a.ndjson","max_file_size_mb":1,"enable_rotation":true,"max_backup_files":1}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.rotation, None);
+    }
+
+    // ========================================================================
+    // parse_ndjson_file_lenient (05_raw_response.md: 保存ファイルの読み込み)
+    // ========================================================================
+
+    #[tokio::test]
+    async fn lenient_parse_reads_saved_responses() {
+        let dir = temp_dir_for_test("lenient_roundtrip");
+        let file_path = dir.join("test.ndjson");
+
+        let saver = RawResponseSaver::new(SaveConfig {
+            enabled: true,
+            file_path: file_path.to_string_lossy().to_string(),
+            enable_rotation: false,
+            ..SaveConfig::default()
+        });
+        saver.save_response(r#"{"msg": 1}"#).await.unwrap();
+        saver.save_response("not json").await.unwrap();
+
+        let (entries, warnings) = parse_ndjson_file_lenient(&file_path).unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].response, serde_json::json!({"msg": 1}));
+        assert_eq!(entries[1].response, serde_json::json!("not json"));
+    }
+
+    #[test]
+    fn lenient_parse_skips_malformed_lines() {
+        let dir = temp_dir_for_test("lenient_malformed");
+        let file_path = dir.join("test.ndjson");
+        fs::write(
+            &file_path,
+            concat!(
+                "{\"timestamp\":1,\"response\":{\"a\":1}}\n",
+                "{\"timestamp\":2,\"resp\n",
+                "\n",
+                "{\"timestamp\":3,\"response\":{\"a\":3}}\r\n",
+                "{\"timestamp\":4,\"respo",
+            ),
+        )
+        .unwrap();
+
+        let (entries, warnings) = parse_ndjson_file_lenient(&file_path).unwrap();
+
+        assert_eq!(
+            entries.iter().map(|e| e.timestamp).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(
+            warnings.iter().map(|w| w.line).collect::<Vec<_>>(),
+            vec![2, 5]
+        );
+        assert_eq!(warnings[0].content, "{\"timestamp\":2,\"resp");
+        assert!(!warnings[0].message.is_empty());
+    }
+
+    #[test]
+    fn lenient_parse_truncates_warning_content() {
+        let dir = temp_dir_for_test("lenient_truncate");
+        let file_path = dir.join("test.ndjson");
+        fs::write(&file_path, "あ".repeat(WARNING_CONTENT_MAX_CHARS + 50)).unwrap();
+
+        let (entries, warnings) = parse_ndjson_file_lenient(&file_path).unwrap();
+
+        assert!(entries.is_empty());
+        assert_eq!(
+            warnings[0].content.chars().count(),
+            WARNING_CONTENT_MAX_CHARS
+        );
+    }
+
+    #[test]
+    fn lenient_parse_tolerates_truncated_multibyte_line() {
+        let dir = temp_dir_for_test("lenient_utf8");
+        let file_path = dir.join("test.ndjson");
+        let mut bytes = b"{\"timestamp\":1,\"response\":\"ok\"}\n".to_vec();
+        // 「あ」(E3 81 82) の途中で途切れた行
+        bytes.extend_from_slice(b"{\"timestamp\":2,\"response\":\"\xE3\x81");
+        fs::write(&file_path, bytes).unwrap();
+
+        let (entries, warnings) = parse_ndjson_file_lenient(&file_path).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 2);
+    }
+
+    #[test]
+    fn lenient_parse_missing_file_is_error() {
+        assert!(parse_ndjson_file_lenient("/nonexistent/path/test.ndjson").is_err());
+    }
+
+    // ========================================================================
+    // ndjson_entries (05_raw_response.md: 保存ファイルの読み込み)
+    // ========================================================================
+
+    #[test]
+    fn ndjson_entries_yields_entries_and_parse_errors_in_order() {
+        let dir = temp_dir_for_test("entries_order");
+        let file_path = dir.join("test.ndjson");
+        fs::write(
+            &file_path,
+            concat!(
+                "{\"timestamp\":1,\"response\":{}}\n",
+                "\n",
+                "broken\n",
+                "{\"timestamp\":4,\"response\":{}}\n",
+            ),
+        )
+        .unwrap();
+
+        let items: Vec<_> = ndjson_entries(&file_path).unwrap().collect();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap().timestamp, 1);
+        assert!(matches!(
+            &items[1],
+            Err(NdjsonReadError::Parse(warning)) if warning.line == 3 && warning.content == "broken"
+        ));
+        assert_eq!(items[2].as_ref().unwrap().timestamp, 4);
+    }
+
+    #[test]
+    fn ndjson_entries_is_lazy() {
+        let dir = temp_dir_for_test("entries_lazy");
+        let file_path = dir.join("test.ndjson");
+        let mut file = fs::File::create(&file_path).unwrap();
+        for i in 0..1000 {
+            writeln!(file, "{{\"timestamp\":{},\"response\":{{}}}}", i).unwrap();
+        }
+        drop(file);
+
+        let total: i64 = ndjson_entries(&file_path)
+            .unwrap()
+            .map(|item| item.unwrap().timestamp)
+            .sum();
+        let first_two: Vec<_> = ndjson_entries(&file_path)
+            .unwrap()
+            .take(2)
+            .map(|item| item.unwrap().timestamp)
+            .collect();
+
+        assert_eq!(total, (0..1000).sum::<i64>());
+        assert_eq!(first_two, vec![0, 1]);
+    }
+
+    #[test]
+    fn ndjson_entries_stops_after_io_error() {
+        struct FailingReader;
+        impl std::io::Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk error"))
+            }
+        }
+
+        let mut entries = NdjsonEntries {
+            reader: BufReader::new(FailingReader),
+            buf: Vec::new(),
+            line_number: 0,
+            finished: false,
+        };
+
+        assert!(matches!(entries.next(), Some(Err(NdjsonReadError::Io(_)))));
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn ndjson_entries_missing_file_is_error() {
+        assert!(ndjson_entries("/nonexistent/path/test.ndjson").is_err());
+    }
+
+    // ========================================================================
+    // validate_response / pretty_print (05_raw_response.md: 保存レスポンスの検証)
+    // ========================================================================
+
+    fn live_chat_response(actions: serde_json::Value) -> Vec<u8> {
+        serde_json::json!({
+            "continuationContents": {
+                "liveChatContinuation": { "actions": actions }
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    #[test]
+    fn validate_accepts_well_formed_response() {
+        let response = live_chat_response(serde_json::json!([
+            { "addChatItemAction": { "item": { "liveChatTextMessageRenderer": {
+                "id": "m1",
+                "timestampUsec": "1700000000000000",
+                "message": { "runs": [{ "text": "hi" }] }
+            }}}},
+            // パーサーが読まないアクションは検証しない
+            { "markChatItemAsDeletedAction": { "targetItemId": "m0" } }
+        ]));
+        assert_eq!(validate_response(&response), Ok(()));
+
+        // メッセージがなく actions が省略されたレスポンスも正常
+        let empty = br#"{"continuationContents":{"liveChatContinuation":{"continuations":[]}}}"#;
+        assert_eq!(validate_response(empty), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_path_and_expected_type() {
+        let response = live_chat_response(serde_json::json!([
+            { "addChatItemAction": { "item": { "liveChatPaidMessageRenderer": {
+                "id": "p1",
+                "timestampUsec": 1700000000000000u64
+            }}}},
+            { "addChatItemAction": { "item": { "liveChatTextMessageRenderer": {
+                "timestampUsec": "1700000000000000",
+                "message": "hi"
+            }}}}
+        ]));
+        let prefix = "/continuationContents/liveChatContinuation/actions";
+        assert_eq!(
+            validate_response(&response),
+            Err(vec![
+                SchemaMismatch {
+                    path: format!(
+                        "{}/0/addChatItemAction/item/liveChatPaidMessageRenderer/timestampUsec",
+                        prefix
+                    ),
+                    expected: "string".to_string(),
+                    found: "number".to_string(),
+                },
+                SchemaMismatch {
+                    path: format!(
+                        "{}/1/addChatItemAction/item/liveChatTextMessageRenderer/id",
+                        prefix
+                    ),
+                    expected: "string".to_string(),
+                    found: "missing".to_string(),
+                },
+                SchemaMismatch {
+                    path: format!(
+                        "{}/1/addChatItemAction/item/liveChatTextMessageRenderer/message",
+                        prefix
+                    ),
+                    expected: "object".to_string(),
+                    found: "string".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_checks_replay_actions_and_container() {
+        let replay = live_chat_response(serde_json::json!([
+            { "replayChatItemAction": { "actions": [
+                { "addChatItemAction": { "item": { "liveChatMembershipItemRenderer": {
+                    "id": "s1"
+                }}}}
+            ]}}
+        ]));
+        let errors = validate_response(&replay).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].path.ends_with(
+            "/0/replayChatItemAction/actions/0/addChatItemAction/item/liveChatMembershipItemRenderer/timestampUsec"
+        ));
+
+        let not_array = br#"{"continuationContents":{"liveChatContinuation":{"actions":{}}}}"#;
+        assert_eq!(
+            validate_response(not_array),
+            Err(vec![SchemaMismatch {
+                path: "/continuationContents/liveChatContinuation/actions".to_string(),
+                expected: "array".to_string(),
+                found: "object".to_string(),
+            }])
+        );
+
+        assert_eq!(
+            validate_response(br#"{"responseContext":{}}"#),
+            Err(vec![SchemaMismatch {
+                path: "/continuationContents/liveChatContinuation".to_string(),
+                expected: "object".to_string(),
+                found: "missing".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn validate_reports_syntax_error_position() {
+        let errors = validate_response(b"{\"continuationContents\": ").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "");
+        assert_eq!(errors[0].expected, "JSON");
+        assert!(errors[0].found.contains("line 1"), "{}", errors[0].found);
+    }
+
+    #[test]
+    fn pretty_print_formats_json() {
+        assert_eq!(
+            pretty_print(br#"{"a":[1,2]}"#).unwrap(),
+            "{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+        assert!(pretty_print(b"{").is_err());
+    }
 }
@@ -0,0 +1,489 @@
+//! 保存済みレスポンスの再生（spec: 05_raw_response.md 再生モード）
+//!
+//! ライブ配信なしでオーバーレイや TTS を確認したり、ユーザーから受け取った記録で
+//! 不具合を再現したりするため、NDJSON の保存ファイルを元の投稿間隔でライブと同じ経路に流す。
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use ts_rs::TS;
+
+use crate::core::api::{DedupTracker, parse_chat_actions};
+use crate::core::models::ChatMessage;
+use crate::core::raw_response::{ParseWarning, ResponseEntry, parse_ndjson_file_lenient};
+
+/// 再生時に待機する投稿間隔の上限（記録の中断などで空いた時間を詰める）
+pub const MAX_REPLAY_GAP: Duration = Duration::from_secs(10);
+
+/// 再生する 1 件のメッセージ
+#[derive(Debug, Clone)]
+pub struct ReplayItem {
+    /// 元の投稿時刻（マイクロ秒）
+    pub at_usec: u64,
+    pub message: ChatMessage,
+}
+
+/// 投稿時刻順に並べた再生メッセージ列
+///
+/// 時刻での範囲検索とシーク位置の算出を二分探索で行う（タイムラインのスクラブ用）。
+/// 投稿時刻が同じメッセージは追加した順に並ぶ。
+#[derive(Debug, Clone, Default)]
+pub struct ReplayTimeline {
+    items: Vec<ReplayItem>,
+}
+
+impl ReplayTimeline {
+    pub fn new(mut items: Vec<ReplayItem>) -> Self {
+        items.sort_by_key(|item| item.at_usec);
+        Self { items }
+    }
+
+    /// 投稿時刻順を保ったままメッセージを追加する
+    pub fn push(&mut self, item: ReplayItem) {
+        let index = self.items.partition_point(|i| i.at_usec <= item.at_usec);
+        self.items.insert(index, item);
+    }
+
+    pub fn items(&self) -> &[ReplayItem] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// `at_usec` 以降で最初のメッセージの位置（すべてそれより前なら `len()`）
+    pub fn position_at(&self, at_usec: u64) -> usize {
+        self.items.partition_point(|item| item.at_usec < at_usec)
+    }
+
+    /// 投稿時刻が `start_usec` 以上 `end_usec` 未満のメッセージ
+    pub fn messages_in_range(&self, start_usec: u64, end_usec: u64) -> &[ReplayItem] {
+        let start = self.position_at(start_usec);
+        let end = self.position_at(end_usec).max(start);
+        &self.items[start..end]
+    }
+}
+
+/// 保存済みレスポンスを再生するメッセージ列に変換する
+///
+/// 連続するレスポンスで再送されたアイテムは ID で除外する。
+/// 投稿時刻は `timestamp_usec`、なければレスポンスの保存時刻を使う。
+pub fn replay_items(entries: &[ResponseEntry]) -> Vec<ReplayItem> {
+    let mut dedup = DedupTracker::default();
+    let mut items = Vec::new();
+    for entry in entries {
+        let saved_at_usec = u64::try_from(entry.timestamp)
+            .unwrap_or(0)
+            .saturating_mul(1_000_000);
+        for message in parse_chat_actions(&entry.response) {
+            if !message.id.is_empty() && !dedup.insert(&message.id) {
+                continue;
+            }
+            let at_usec = message.timestamp_usec.parse().unwrap_or(saved_at_usec);
+            items.push(ReplayItem { at_usec, message });
+        }
+    }
+    items
+}
+
+/// 保存ファイルを読み込んで投稿時刻順のメッセージ列を返す（パースできない行は警告として返す）
+pub fn load_replay_items(path: impl AsRef<Path>) -> Result<(ReplayTimeline, Vec<ParseWarning>)> {
+    let (entries, warnings) = parse_ndjson_file_lenient(path)?;
+    Ok((ReplayTimeline::new(replay_items(&entries)), warnings))
+}
+
+/// 前のメッセージから次のメッセージまでの待機時間
+///
+/// `speed` 倍速で再生する（0 以下は待機しない）。間隔は `MAX_REPLAY_GAP` で打ち切る。
+pub fn replay_delay(prev_usec: u64, next_usec: u64, speed: f64) -> Duration {
+    if !speed.is_finite() || speed <= 0.0 {
+        return Duration::ZERO;
+    }
+    let gap = Duration::from_micros(next_usec.saturating_sub(prev_usec)).min(MAX_REPLAY_GAP);
+    gap.div_f64(speed)
+}
+
+/// 再生状態
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct ReplayStatus {
+    /// 再生するメッセージの総数
+    pub total: usize,
+    /// 次に再生するメッセージの位置（0 始まり）
+    pub position: usize,
+    pub paused: bool,
+    /// 最後まで再生したか、停止された
+    pub finished: bool,
+    /// 再生速度の倍率（0 は待機なし）
+    pub speed: f64,
+}
+
+#[derive(Debug)]
+struct ControlState {
+    position: usize,
+    paused: bool,
+    seek_to: Option<usize>,
+    finished: bool,
+}
+
+/// 再生タスクを操作するハンドル（一時停止・再開・シーク・停止）
+#[derive(Debug, Clone)]
+pub struct ReplayController {
+    state: Arc<Mutex<ControlState>>,
+    notify: Arc<Notify>,
+    cancel: CancellationToken,
+    timeline: Arc<ReplayTimeline>,
+    total: usize,
+    speed: f64,
+}
+
+impl ReplayController {
+    pub fn new(timeline: ReplayTimeline, speed: f64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ControlState {
+                position: 0,
+                paused: false,
+                seek_to: None,
+                finished: false,
+            })),
+            notify: Arc::new(Notify::new()),
+            cancel: CancellationToken::new(),
+            total: timeline.len(),
+            timeline: Arc::new(timeline),
+            speed: speed.max(0.0),
+        }
+    }
+
+    pub fn pause(&self) {
+        self.lock().paused = true;
+        self.notify.notify_one();
+    }
+
+    pub fn resume(&self) {
+        self.lock().paused = false;
+        self.notify.notify_one();
+    }
+
+    /// 指定位置（メッセージの順番、0 始まり）から再生し直す
+    pub fn seek(&self, position: usize) {
+        let position = position.min(self.total);
+        let mut state = self.lock();
+        state.seek_to = Some(position);
+        state.position = position;
+        drop(state);
+        self.notify.notify_one();
+    }
+
+    /// 指定時刻（マイクロ秒）以降で最初のメッセージから再生し直す
+    pub fn seek_time(&self, at_usec: u64) {
+        self.seek(self.timeline.position_at(at_usec));
+    }
+
+    /// 再生しているメッセージ列
+    pub fn timeline(&self) -> &ReplayTimeline {
+        &self.timeline
+    }
+
+    /// 再生を終了する
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn status(&self) -> ReplayStatus {
+        let state = self.lock();
+        ReplayStatus {
+            total: self.total,
+            position: state.position,
+            paused: state.paused,
+            finished: state.finished,
+            speed: self.speed,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ControlState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// メッセージ列を元の投稿間隔で取り出すプレイヤー
+///
+/// 一時停止中は待機し、シークされたら次のメッセージを待たずにその位置から再開する。
+pub struct ReplayPlayer {
+    controller: ReplayController,
+    /// 直前に返したメッセージの投稿時刻（シーク直後は None で待機しない）
+    prev_at: Option<u64>,
+}
+
+impl ReplayPlayer {
+    pub fn new(controller: ReplayController) -> Self {
+        Self {
+            controller,
+            prev_at: None,
+        }
+    }
+
+    /// 次のメッセージを再生時刻まで待って返す（最後まで再生したか停止されたら None）
+    pub async fn next_message(&mut self) -> Option<ChatMessage> {
+        let message = self.wait_next().await;
+        if message.is_none() {
+            self.controller.lock().finished = true;
+        }
+        message
+    }
+
+    async fn wait_next(&mut self) -> Option<ChatMessage> {
+        loop {
+            let (position, paused) = {
+                let mut state = self.controller.lock();
+                if state.seek_to.take().is_some() {
+                    self.prev_at = None;
+                }
+                (state.position, state.paused)
+            };
+            let timeline = Arc::clone(&self.controller.timeline);
+            let item = timeline.items().get(position)?;
+
+            let delay = (!paused).then(|| {
+                self.prev_at.map_or(Duration::ZERO, |prev| {
+                    replay_delay(prev, item.at_usec, self.controller.speed)
+                })
+            });
+            tokio::select! {
+                _ = self.controller.cancel.cancelled() => return None,
+                // 操作があったら状態を読み直す
+                _ = self.controller.notify.notified() => continue,
+                _ = async {
+                    match delay {
+                        Some(delay) => tokio::time::sleep(delay).await,
+                        None => std::future::pending().await,
+                    }
+                } => {}
+            }
+
+            let seeked = {
+                let mut state = self.controller.lock();
+                let seeked = state.seek_to.is_some();
+                if !seeked {
+                    state.position = position + 1;
+                }
+                seeked
+            };
+            if seeked {
+                continue;
+            }
+            self.prev_at = Some(item.at_usec);
+            return Some(item.message.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::MessageType;
+
+    fn text_action(id: &str, usec: u64) -> serde_json::Value {
+        serde_json::json!({
+            "addChatItemAction": {
+                "item": {
+                    "liveChatTextMessageRenderer": {
+                        "id": id,
+                        "timestampUsec": usec.to_string(),
+                        "authorName": { "simpleText": "viewer" },
+                        "authorExternalChannelId": "UC_viewer",
+                        "message": { "runs": [{ "text": id }] }
+                    }
+                }
+            }
+        })
+    }
+
+    fn entry(timestamp: i64, actions: Vec<serde_json::Value>) -> ResponseEntry {
+        ResponseEntry {
+            timestamp,
+            response: serde_json::json!({
+                "continuationContents": { "liveChatContinuation": { "actions": actions } }
+            }),
+        }
+    }
+
+    fn item(id: &str) -> ReplayItem {
+        item_at(id, 0)
+    }
+
+    fn item_at(id: &str, at_usec: u64) -> ReplayItem {
+        ReplayItem {
+            at_usec,
+            message: ChatMessage {
+                id: id.to_string(),
+                message_type: MessageType::Text,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn timeline(ids: &[&str]) -> ReplayTimeline {
+        ReplayTimeline::new(ids.iter().map(|id| item(id)).collect())
+    }
+
+    fn ids(items: &[ReplayItem]) -> Vec<&str> {
+        items.iter().map(|i| i.message.id.as_str()).collect()
+    }
+
+    #[test]
+    fn timeline_orders_by_time_and_keeps_ties_in_insertion_order() {
+        let mut timeline = ReplayTimeline::new(vec![
+            item_at("late", 3_000),
+            item_at("tie1", 2_000),
+            item_at("early", 1_000),
+            item_at("tie2", 2_000),
+        ]);
+        timeline.push(item_at("tie3", 2_000));
+        timeline.push(item_at("first", 0));
+        assert_eq!(
+            ids(timeline.items()),
+            ["first", "early", "tie1", "tie2", "tie3", "late"]
+        );
+    }
+
+    #[test]
+    fn messages_in_range_matches_linear_scan() {
+        // 0.25 秒間隔で 5000 件（約 20 分）、同時刻のメッセージも含める
+        let mut timeline = ReplayTimeline::default();
+        for i in 0..5000u64 {
+            timeline.push(item_at(&i.to_string(), (i / 2) * 500_000));
+        }
+        assert_eq!(timeline.len(), 5000);
+
+        let ranges = [
+            (0, 1),
+            (60_000_000, 65_000_000),
+            (250_000, 750_000),
+            (1_249_500_000, u64::MAX),
+            (5_000_000_000, 6_000_000_000),
+            (10_000_000, 5_000_000),
+        ];
+        for (start, end) in ranges {
+            let expected: Vec<_> = timeline
+                .items()
+                .iter()
+                .filter(|i| i.at_usec >= start && i.at_usec < end)
+                .map(|i| i.message.id.as_str())
+                .collect();
+            assert_eq!(
+                ids(timeline.messages_in_range(start, end)),
+                expected,
+                "range {}..{}",
+                start,
+                end
+            );
+        }
+        // 1:00 から 1:05 は 20 件 × 5 秒 × 2 件/0.5 秒
+        assert_eq!(timeline.messages_in_range(60_000_000, 65_000_000).len(), 20);
+        assert_eq!(timeline.position_at(0), 0);
+        assert_eq!(timeline.position_at(500_000), 2);
+        assert_eq!(timeline.position_at(u64::MAX), 5000);
+    }
+
+    #[test]
+    fn replay_items_skips_resent_items() {
+        let entries = vec![
+            entry(
+                1,
+                vec![text_action("a", 1_000_000), text_action("b", 2_000_000)],
+            ),
+            entry(
+                2,
+                vec![text_action("b", 2_000_000), text_action("c", 3_500_000)],
+            ),
+        ];
+        let items = replay_items(&entries);
+        let ids: Vec<_> = items.iter().map(|i| i.message.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(items[2].at_usec, 3_500_000);
+    }
+
+    #[test]
+    fn replay_delay_scales_with_speed() {
+        assert_eq!(replay_delay(0, 2_000_000, 1.0), Duration::from_secs(2));
+        assert_eq!(replay_delay(0, 2_000_000, 2.0), Duration::from_secs(1));
+        assert_eq!(replay_delay(0, 2_000_000, 0.0), Duration::ZERO);
+        // 時刻が戻っても待機しない
+        assert_eq!(replay_delay(2_000_000, 0, 1.0), Duration::ZERO);
+        // 長い中断は打ち切る
+        assert_eq!(replay_delay(0, 600_000_000, 1.0), MAX_REPLAY_GAP);
+    }
+
+    async fn collect(mut player: ReplayPlayer) -> Vec<String> {
+        let mut seen = Vec::new();
+        while let Some(msg) = player.next_message().await {
+            seen.push(msg.id);
+        }
+        seen
+    }
+
+    #[tokio::test]
+    async fn player_returns_all_items_in_order() {
+        let controller = ReplayController::new(timeline(&["a", "b", "c"]), 0.0);
+        let player = ReplayPlayer::new(controller.clone());
+        assert_eq!(collect(player).await, ["a", "b", "c"]);
+        let status = controller.status();
+        assert_eq!(status.position, 3);
+        assert!(status.finished);
+    }
+
+    #[tokio::test]
+    async fn paused_replay_resumes_from_seek_position() {
+        let controller = ReplayController::new(timeline(&["a", "b", "c"]), 0.0);
+        controller.pause();
+        let player = ReplayPlayer::new(controller.clone());
+        let task = tokio::spawn(collect(player));
+
+        tokio::task::yield_now().await;
+        assert_eq!(controller.status().position, 0);
+
+        controller.seek(1);
+        controller.resume();
+        assert_eq!(task.await.unwrap(), ["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn seek_time_starts_from_first_message_at_or_after() {
+        let items = vec![
+            item_at("a", 1_000),
+            item_at("b", 2_000),
+            item_at("c", 3_000),
+        ];
+        let controller = ReplayController::new(ReplayTimeline::new(items), 0.0);
+        controller.pause();
+        let task = tokio::spawn(collect(ReplayPlayer::new(controller.clone())));
+
+        controller.seek_time(1_500);
+        assert_eq!(controller.status().position, 1);
+        controller.resume();
+        assert_eq!(task.await.unwrap(), ["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn stop_ends_paused_replay() {
+        let controller = ReplayController::new(timeline(&["a"]), 1.0);
+        controller.pause();
+        let task = tokio::spawn(collect(ReplayPlayer::new(controller.clone())));
+        controller.stop();
+        assert!(task.await.unwrap().is_empty());
+        let status = controller.status();
+        assert!(status.finished);
+        assert_eq!(status.position, 0);
+    }
+}
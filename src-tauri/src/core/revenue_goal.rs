@@ -0,0 +1,192 @@
+//! 収益目標の進捗（spec: 07_revenue.md 収益目標）
+//!
+//! スーパーチャット（SuperSticker を含む）の合計を目標の通貨に換算して進捗を求め、
+//! 目標に到達したら `chat:goal_reached` を発行する。到達の通知は目標ごとに 1 回だけで、
+//! 配信中に目標を変えた場合は新しい目標について改めて判定する。
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::core::currency::CurrencyConverter;
+
+/// 収益目標の設定（config.toml の `[revenue_goal]` セクション）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RevenueGoal {
+    /// 目標金額（`currency` 建て、0 なら目標なし）
+    pub target: f64,
+    /// 目標の通貨（ISO 4217 コード）
+    pub currency: String,
+}
+
+impl Default for RevenueGoal {
+    fn default() -> Self {
+        Self {
+            target: 0.0,
+            currency: "JPY".to_string(),
+        }
+    }
+}
+
+impl RevenueGoal {
+    /// 目標が設定されているか
+    pub fn is_set(&self) -> bool {
+        self.target > 0.0
+    }
+}
+
+/// 収益目標の進捗 (07_revenue.md)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct GoalProgress {
+    /// 集まった金額（`currency` 建て）
+    pub raised: f64,
+    /// 目標金額（`currency` 建て）
+    pub target: f64,
+    /// 目標の通貨（ISO 4217 コード）
+    pub currency: String,
+    /// 達成率（%、目標を超えると 100 より大きくなる）
+    pub percent: f64,
+    /// 目標までの残り（到達後は 0）
+    pub remaining: f64,
+}
+
+impl GoalProgress {
+    /// `from` 建ての `raised` から目標に対する進捗を求める
+    ///
+    /// 目標が設定されていない・目標の通貨への換算レートがなければ None。
+    pub fn compute(
+        raised: f64,
+        from: &str,
+        goal: &RevenueGoal,
+        converter: &dyn CurrencyConverter,
+    ) -> Option<Self> {
+        if !goal.is_set() {
+            return None;
+        }
+        let raised = converter.convert(raised, from, &goal.currency)?;
+        Some(Self {
+            raised,
+            target: goal.target,
+            currency: goal.currency.clone(),
+            percent: raised / goal.target * 100.0,
+            remaining: (goal.target - raised).max(0.0),
+        })
+    }
+
+    /// 目標に到達したか
+    pub fn is_reached(&self) -> bool {
+        self.raised >= self.target
+    }
+}
+
+/// 目標到達イベント（`chat:goal_reached`）のペイロード
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct GoalReachedEvent {
+    pub connection_id: u64,
+    pub progress: GoalProgress,
+}
+
+/// 目標への到達を目標ごとに 1 回だけ知らせる
+#[derive(Debug, Default)]
+pub struct GoalTracker {
+    /// 最後に到達を通知した目標
+    reached: Option<RevenueGoal>,
+}
+
+impl GoalTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 新たに目標に到達したら true を返す
+    ///
+    /// 通知済みの目標では true を返さない。目標が変わった場合は、変更後の目標に
+    /// 到達していれば（変更前に超えていた場合も含めて）1 回だけ true を返す。
+    pub fn check(&mut self, goal: &RevenueGoal, progress: &GoalProgress) -> bool {
+        if !progress.is_reached() || self.reached.as_ref() == Some(goal) {
+            return false;
+        }
+        self.reached = Some(goal.clone());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::currency::StaticRateTable;
+
+    fn rates() -> StaticRateTable {
+        StaticRateTable::new("JPY", [("USD".to_string(), 150.0)])
+    }
+
+    fn goal(target: f64, currency: &str) -> RevenueGoal {
+        RevenueGoal {
+            target,
+            currency: currency.to_string(),
+        }
+    }
+
+    #[test]
+    fn partial_progress_in_goal_currency() {
+        // 15,000 円は 100 USD
+        let progress =
+            GoalProgress::compute(15000.0, "JPY", &goal(400.0, "USD"), &rates()).unwrap();
+        assert_eq!(progress.raised, 100.0);
+        assert_eq!(progress.target, 400.0);
+        assert_eq!(progress.currency, "USD");
+        assert_eq!(progress.percent, 25.0);
+        assert_eq!(progress.remaining, 300.0);
+        assert!(!progress.is_reached());
+    }
+
+    #[test]
+    fn remaining_is_zero_past_the_goal() {
+        let progress =
+            GoalProgress::compute(12000.0, "JPY", &goal(10000.0, "JPY"), &rates()).unwrap();
+        assert_eq!(progress.percent, 120.0);
+        assert_eq!(progress.remaining, 0.0);
+        assert!(progress.is_reached());
+    }
+
+    #[test]
+    fn no_progress_without_goal_or_rate() {
+        assert!(GoalProgress::compute(100.0, "JPY", &RevenueGoal::default(), &rates()).is_none());
+        assert!(GoalProgress::compute(100.0, "JPY", &goal(10.0, "EUR"), &rates()).is_none());
+    }
+
+    #[test]
+    fn goal_reached_fires_once() {
+        let mut tracker = GoalTracker::new();
+        let goal = goal(10000.0, "JPY");
+        let progress = |raised| GoalProgress::compute(raised, "JPY", &goal, &rates()).unwrap();
+
+        assert!(!tracker.check(&goal, &progress(9999.0)));
+        assert!(tracker.check(&goal, &progress(10000.0)));
+        assert!(!tracker.check(&goal, &progress(10000.0)));
+        assert!(!tracker.check(&goal, &progress(20000.0)));
+    }
+
+    #[test]
+    fn changing_the_goal_mid_session_rearms_it() {
+        let mut tracker = GoalTracker::new();
+        let first = goal(10000.0, "JPY");
+        let raised = |goal: &RevenueGoal, amount| {
+            GoalProgress::compute(amount, "JPY", goal, &rates()).unwrap()
+        };
+        assert!(tracker.check(&first, &raised(&first, 12000.0)));
+
+        // 目標を引き上げると、新しい目標に届いたときにもう一度通知する
+        let raised_goal = goal(200.0, "USD");
+        assert!(!tracker.check(&raised_goal, &raised(&raised_goal, 12000.0)));
+        assert!(tracker.check(&raised_goal, &raised(&raised_goal, 30000.0)));
+        assert!(!tracker.check(&raised_goal, &raised(&raised_goal, 31000.0)));
+
+        // 既に超えている金額に下げた場合は、変更後すぐに 1 回だけ通知する
+        let lowered = goal(100.0, "USD");
+        assert!(tracker.check(&lowered, &raised(&lowered, 31000.0)));
+        assert!(!tracker.check(&lowered, &raised(&lowered, 31000.0)));
+    }
+}
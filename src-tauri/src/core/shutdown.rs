@@ -0,0 +1,210 @@
+//! アプリ終了時の後始末（spec: 08_database.md アプリ終了時の処理）
+//!
+//! ウィンドウを閉じた直後にプロセスが終わると、取得中のメッセージの書き込みや
+//! セッションの終了処理が行われず、配信の最後の数十秒が欠ける。
+//! 終了前に各段階をタイムアウト付きで順に実行し、完了・タイムアウト・失敗を報告する。
+
+use std::future::Future;
+use std::time::Duration;
+
+/// 各段階のデフォルトのタイムアウト（切断時にタスクを待つ時間と同じ）
+pub const DEFAULT_STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 終了処理の段階（実行順）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownStep {
+    /// 監視タスクを止め、取得済みメッセージの書き込みとセッション終了を待つ
+    MonitoringTasks,
+    /// 監視タスクが終了処理をできなかったセッションを終了する
+    OpenSessions,
+    /// データベースの WAL を DB ファイルに書き戻す
+    Database,
+    /// 読み上げキューを止める（設定に応じて読み上げ終えるか破棄する）
+    TtsQueue,
+}
+
+impl ShutdownStep {
+    pub const ALL: [ShutdownStep; 4] = [
+        ShutdownStep::MonitoringTasks,
+        ShutdownStep::OpenSessions,
+        ShutdownStep::Database,
+        ShutdownStep::TtsQueue,
+    ];
+}
+
+/// 終了処理の結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    pub completed: Vec<ShutdownStep>,
+    pub timed_out: Vec<ShutdownStep>,
+    pub failed: Vec<(ShutdownStep, String)>,
+}
+
+impl ShutdownReport {
+    /// すべての段階が完了したか
+    pub fn is_clean(&self) -> bool {
+        self.timed_out.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// 終了処理の各段階の実装
+///
+/// アプリでは `AppState` が実装する。テストではモックに差し替える。
+pub trait ShutdownHooks: Sync {
+    /// 全接続の監視タスクをキャンセルし、終了を待つ
+    fn stop_monitoring(&self) -> impl Future<Output = Result<(), String>> + Send;
+    /// 終了時刻が記録されていないセッションを終了する
+    fn end_open_sessions(&self) -> impl Future<Output = Result<(), String>> + Send;
+    /// データベースへの書き込みを確定する
+    fn flush_database(&self) -> impl Future<Output = Result<(), String>> + Send;
+    /// 読み上げキューを止める
+    fn stop_tts(&self) -> impl Future<Output = Result<(), String>> + Send;
+}
+
+/// 終了処理の調整役
+#[derive(Debug, Clone, Copy)]
+pub struct AppShutdown {
+    step_timeout: Duration,
+}
+
+impl Default for AppShutdown {
+    fn default() -> Self {
+        Self::new(DEFAULT_STEP_TIMEOUT)
+    }
+}
+
+impl AppShutdown {
+    pub fn new(step_timeout: Duration) -> Self {
+        Self { step_timeout }
+    }
+
+    /// 各段階を順に実行する
+    ///
+    /// ある段階がタイムアウト・失敗しても残りの段階は実行する
+    /// （監視タスクが止まらなくてもセッションの終了と WAL の書き戻しは行う）。
+    pub async fn run<H: ShutdownHooks>(&self, hooks: &H) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        for step in ShutdownStep::ALL {
+            let result = match step {
+                ShutdownStep::MonitoringTasks => {
+                    tokio::time::timeout(self.step_timeout, hooks.stop_monitoring()).await
+                }
+                ShutdownStep::OpenSessions => {
+                    tokio::time::timeout(self.step_timeout, hooks.end_open_sessions()).await
+                }
+                ShutdownStep::Database => {
+                    tokio::time::timeout(self.step_timeout, hooks.flush_database()).await
+                }
+                ShutdownStep::TtsQueue => {
+                    tokio::time::timeout(self.step_timeout, hooks.stop_tts()).await
+                }
+            };
+            match result {
+                Ok(Ok(())) => report.completed.push(step),
+                Ok(Err(e)) => {
+                    tracing::warn!("終了処理に失敗: {:?}: {}", step, e);
+                    report.failed.push((step, e));
+                }
+                Err(_) => {
+                    tracing::warn!("終了処理がタイムアウト: {:?}", step);
+                    report.timed_out.push(step);
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 呼ばれた段階を記録するモック（DB の書き戻しも記録する）
+    #[derive(Default)]
+    struct MockHooks {
+        calls: Mutex<Vec<ShutdownStep>>,
+        hang_on: Option<ShutdownStep>,
+        fail_on: Option<ShutdownStep>,
+    }
+
+    impl MockHooks {
+        async fn call(&self, step: ShutdownStep) -> Result<(), String> {
+            self.calls.lock().unwrap().push(step);
+            if self.hang_on == Some(step) {
+                std::future::pending::<()>().await;
+            }
+            if self.fail_on == Some(step) {
+                return Err("disk full".to_string());
+            }
+            Ok(())
+        }
+
+        fn calls(&self) -> Vec<ShutdownStep> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl ShutdownHooks for MockHooks {
+        fn stop_monitoring(&self) -> impl Future<Output = Result<(), String>> + Send {
+            self.call(ShutdownStep::MonitoringTasks)
+        }
+        fn end_open_sessions(&self) -> impl Future<Output = Result<(), String>> + Send {
+            self.call(ShutdownStep::OpenSessions)
+        }
+        fn flush_database(&self) -> impl Future<Output = Result<(), String>> + Send {
+            self.call(ShutdownStep::Database)
+        }
+        fn stop_tts(&self) -> impl Future<Output = Result<(), String>> + Send {
+            self.call(ShutdownStep::TtsQueue)
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_every_step_in_order_and_flushes_database() {
+        let hooks = MockHooks::default();
+        let report = AppShutdown::default().run(&hooks).await;
+
+        assert_eq!(hooks.calls(), ShutdownStep::ALL.to_vec());
+        assert_eq!(report.completed, ShutdownStep::ALL.to_vec());
+        assert!(report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn flushes_database_even_if_monitoring_tasks_hang() {
+        let hooks = MockHooks {
+            hang_on: Some(ShutdownStep::MonitoringTasks),
+            ..Default::default()
+        };
+        let report = AppShutdown::new(Duration::from_millis(20))
+            .run(&hooks)
+            .await;
+
+        assert!(hooks.calls().contains(&ShutdownStep::Database));
+        assert_eq!(report.timed_out, vec![ShutdownStep::MonitoringTasks]);
+        assert_eq!(
+            report.completed,
+            vec![
+                ShutdownStep::OpenSessions,
+                ShutdownStep::Database,
+                ShutdownStep::TtsQueue
+            ]
+        );
+        assert!(!report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn reports_failed_step_and_continues() {
+        let hooks = MockHooks {
+            fail_on: Some(ShutdownStep::Database),
+            ..Default::default()
+        };
+        let report = AppShutdown::default().run(&hooks).await;
+
+        assert_eq!(
+            report.failed,
+            vec![(ShutdownStep::Database, "disk full".to_string())]
+        );
+        assert!(report.completed.contains(&ShutdownStep::TtsQueue));
+    }
+}
@@ -0,0 +1,141 @@
+//! 配信終了の検出（spec: 02_chat.md 配信終了の検出）
+//!
+//! continuation の連続欠落・配信終了のエンゲージメントメッセージ・メタデータの配信状態の
+//! いずれかで配信終了とみなし、`chat:stream_ended` を 1 回だけ発行するための判定を行う。
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::core::api::LiveStatus;
+
+/// 配信終了と判定した根拠
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub enum StreamEndReason {
+    /// continuation が連続して返されなかった
+    ContinuationMissing,
+    /// 「配信は終了しました」等のエンゲージメントメッセージを受信した
+    EndNotice,
+    /// メタデータの配信状態が終了に変わった
+    LiveStatusEnded,
+}
+
+/// 配信終了イベント（`chat:stream_ended`）のペイロード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/lib/types/generated/")]
+pub struct StreamEndedEvent {
+    pub connection_id: u64,
+    pub video_id: String,
+    pub reason: StreamEndReason,
+}
+
+/// 配信終了の検出器
+///
+/// どの根拠で検出しても一度だけ `Some` を返す（以降は常に `None`）。
+#[derive(Debug, Default)]
+pub struct StreamEndDetector {
+    fired: Option<StreamEndReason>,
+}
+
+impl StreamEndDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ポーリング結果を確認する
+    ///
+    /// - `continuation_missing` — continuation が連続して欠落した（`InnerTubeClient::is_stream_ended`）
+    /// - `end_notice` — 配信終了のエンゲージメントメッセージを受信した
+    pub fn observe_poll(
+        &mut self,
+        continuation_missing: bool,
+        end_notice: bool,
+    ) -> Option<StreamEndReason> {
+        if end_notice {
+            self.fire(StreamEndReason::EndNotice)
+        } else if continuation_missing {
+            self.fire(StreamEndReason::ContinuationMissing)
+        } else {
+            None
+        }
+    }
+
+    /// メタデータから取得した配信状態を確認する
+    ///
+    /// 配信予定・配信中・通常の動画では終了とみなさない。
+    pub fn observe_live_status(&mut self, status: LiveStatus) -> Option<StreamEndReason> {
+        if status == LiveStatus::Ended {
+            self.fire(StreamEndReason::LiveStatusEnded)
+        } else {
+            None
+        }
+    }
+
+    /// 配信終了を検出済みならその根拠を返す
+    pub fn fired(&self) -> Option<StreamEndReason> {
+        self.fired
+    }
+
+    fn fire(&mut self, reason: StreamEndReason) -> Option<StreamEndReason> {
+        if self.fired.is_some() {
+            return None;
+        }
+        self.fired = Some(reason);
+        Some(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_on_missing_continuation() {
+        let mut detector = StreamEndDetector::new();
+        assert_eq!(detector.observe_poll(false, false), None);
+        assert_eq!(
+            detector.observe_poll(true, false),
+            Some(StreamEndReason::ContinuationMissing)
+        );
+        assert_eq!(detector.fired(), Some(StreamEndReason::ContinuationMissing));
+    }
+
+    #[test]
+    fn fires_on_end_notice() {
+        // 終了通知と continuation 欠落が同時なら、より直接的な終了通知を根拠にする
+        let mut detector = StreamEndDetector::new();
+        assert_eq!(
+            detector.observe_poll(true, true),
+            Some(StreamEndReason::EndNotice)
+        );
+    }
+
+    #[test]
+    fn fires_on_live_status_ended_only() {
+        let mut detector = StreamEndDetector::new();
+        assert_eq!(detector.observe_live_status(LiveStatus::Upcoming), None);
+        assert_eq!(detector.observe_live_status(LiveStatus::Live), None);
+        assert_eq!(detector.observe_live_status(LiveStatus::NotLive), None);
+        assert_eq!(
+            detector.observe_live_status(LiveStatus::Ended),
+            Some(StreamEndReason::LiveStatusEnded)
+        );
+    }
+
+    #[test]
+    fn fires_only_once_across_triggers() {
+        let mut detector = StreamEndDetector::new();
+        assert!(detector.observe_poll(false, true).is_some());
+        assert_eq!(detector.observe_poll(false, true), None);
+        assert_eq!(detector.observe_poll(true, false), None);
+        assert_eq!(detector.observe_live_status(LiveStatus::Ended), None);
+        assert_eq!(detector.fired(), Some(StreamEndReason::EndNotice));
+    }
+
+    #[test]
+    fn reason_serializes_to_snake_case() {
+        let json = serde_json::to_string(&StreamEndReason::LiveStatusEnded).unwrap();
+        assert_eq!(json, r#""live_status_ended""#);
+    }
+}
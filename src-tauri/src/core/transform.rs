@@ -0,0 +1,281 @@
+//! メッセージ変換フック（spec: 02_chat.md メッセージ変換）
+//!
+//! 取得したメッセージを DB 保存・GUI・WebSocket・TTS に流す前に書き換える
+//! （カスタム絵文字のショートコード展開、伏せ字など）。登録順に適用し、
+//! 変換が None を返したメッセージは以降の処理に流さない。
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::core::models::{ChatMessage, MessageRun};
+
+/// メッセージを書き換える変換
+pub trait MessageTransform: Send + Sync {
+    /// 変換したメッセージを返す（None なら破棄する）
+    fn transform(&self, msg: ChatMessage) -> Option<ChatMessage>;
+}
+
+/// 登録順に適用する変換の一覧
+#[derive(Default)]
+pub struct MessageTransforms {
+    transforms: Vec<Box<dyn MessageTransform>>,
+}
+
+impl MessageTransforms {
+    /// 組み込みの変換（絵文字ショートコード展開）を登録した一覧
+    pub fn with_builtins() -> Self {
+        let mut transforms = Self::default();
+        transforms.push(EmojiShortcodeExpander::default());
+        transforms
+    }
+
+    /// 変換を末尾に追加する
+    pub fn push(&mut self, transform: impl MessageTransform + 'static) {
+        self.transforms.push(Box::new(transform));
+    }
+
+    pub fn len(&self) -> usize {
+        self.transforms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    /// 登録順にすべての変換を適用する（途中で破棄されたら None）
+    pub fn apply(&self, msg: ChatMessage) -> Option<ChatMessage> {
+        self.transforms
+            .iter()
+            .try_fold(msg, |msg, transform| transform.transform(msg))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KnownEmoji {
+    emoji_id: String,
+    image_url: String,
+    alt_text: String,
+}
+
+/// テキストとして届いたカスタム絵文字のショートコード（`:_kusa:` など）を絵文字に展開する
+///
+/// メンバー以外が入力したメンバー限定絵文字などはテキストのまま届くため、
+/// 他のメッセージの絵文字の `shortcuts` から覚えた画像に置き換える。
+#[derive(Debug, Default)]
+pub struct EmojiShortcodeExpander {
+    known: RwLock<HashMap<String, KnownEmoji>>,
+}
+
+impl EmojiShortcodeExpander {
+    /// 覚えている絵文字の数
+    pub fn known_count(&self) -> usize {
+        self.known.read().map(|known| known.len()).unwrap_or(0)
+    }
+
+    fn learn(&self, runs: &[MessageRun]) {
+        let new_emojis: Vec<_> = runs
+            .iter()
+            .filter_map(|run| match run {
+                MessageRun::Emoji {
+                    emoji_id,
+                    image_url,
+                    alt_text,
+                    shortcuts,
+                } if !shortcuts.is_empty() && !image_url.is_empty() => Some((
+                    shortcuts,
+                    KnownEmoji {
+                        emoji_id: emoji_id.clone(),
+                        image_url: image_url.clone(),
+                        alt_text: alt_text.clone(),
+                    },
+                )),
+                _ => None,
+            })
+            .collect();
+        if new_emojis.is_empty() {
+            return;
+        }
+        let Ok(mut known) = self.known.write() else {
+            return;
+        };
+        for (shortcuts, emoji) in new_emojis {
+            for shortcut in shortcuts {
+                known.insert(shortcut.clone(), emoji.clone());
+            }
+        }
+    }
+
+    /// テキストを絵文字に置き換えた runs を返す（置き換えがなければ None）
+    fn expand_text(known: &HashMap<String, KnownEmoji>, text: &str) -> Option<Vec<MessageRun>> {
+        let mut runs = Vec::new();
+        let mut rest = text;
+        let mut plain = String::new();
+        let mut expanded = false;
+        while let Some(start) = rest.find(':') {
+            let after = &rest[start + 1..];
+            let Some(len) = after.find(':') else {
+                break;
+            };
+            let shortcut = &rest[start..start + len + 2];
+            match known.get(shortcut) {
+                Some(emoji) => {
+                    plain.push_str(&rest[..start]);
+                    if !plain.is_empty() {
+                        runs.push(MessageRun::Text {
+                            content: std::mem::take(&mut plain),
+                        });
+                    }
+                    runs.push(MessageRun::Emoji {
+                        emoji_id: emoji.emoji_id.clone(),
+                        image_url: emoji.image_url.clone(),
+                        alt_text: emoji.alt_text.clone(),
+                        shortcuts: vec![shortcut.to_string()],
+                    });
+                    rest = &rest[start + len + 2..];
+                    expanded = true;
+                }
+                None => {
+                    // 閉じ側の ':' は次のショートコードの開始になりうる
+                    plain.push_str(&rest[..=start + len]);
+                    rest = &rest[start + len + 1..];
+                }
+            }
+        }
+        if !expanded {
+            return None;
+        }
+        plain.push_str(rest);
+        if !plain.is_empty() {
+            runs.push(MessageRun::Text { content: plain });
+        }
+        Some(runs)
+    }
+}
+
+impl MessageTransform for EmojiShortcodeExpander {
+    fn transform(&self, mut msg: ChatMessage) -> Option<ChatMessage> {
+        self.learn(&msg.runs);
+
+        let Ok(known) = self.known.read() else {
+            return Some(msg);
+        };
+        if known.is_empty() {
+            return Some(msg);
+        }
+        let mut changed = false;
+        let mut runs = Vec::with_capacity(msg.runs.len());
+        for run in std::mem::take(&mut msg.runs) {
+            match &run {
+                MessageRun::Text { content } => match Self::expand_text(&known, content) {
+                    Some(expanded) => {
+                        runs.extend(expanded);
+                        changed = true;
+                    }
+                    None => runs.push(run),
+                },
+                MessageRun::Emoji { .. } => runs.push(run),
+            }
+        }
+        msg.runs = runs;
+        if changed {
+            // content は runs と同じく絵文字を代替テキストで表す
+            msg.content = msg
+                .runs
+                .iter()
+                .map(|run| match run {
+                    MessageRun::Text { content } => content.as_str(),
+                    MessageRun::Emoji { alt_text, .. } => alt_text.as_str(),
+                })
+                .collect();
+        }
+        Some(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_message(text: &str) -> ChatMessage {
+        ChatMessage {
+            content: text.to_string(),
+            runs: vec![MessageRun::Text {
+                content: text.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn kusa_emoji() -> MessageRun {
+        MessageRun::Emoji {
+            emoji_id: "UC_x/kusa".to_string(),
+            image_url: "https://yt3.ggpht.com/kusa".to_string(),
+            alt_text: ":_kusa:".to_string(),
+            shortcuts: vec![":_kusa:".to_string(), ":kusa:".to_string()],
+        }
+    }
+
+    struct Append(&'static str);
+
+    impl MessageTransform for Append {
+        fn transform(&self, mut msg: ChatMessage) -> Option<ChatMessage> {
+            msg.content.push_str(self.0);
+            Some(msg)
+        }
+    }
+
+    struct DropEmpty;
+
+    impl MessageTransform for DropEmpty {
+        fn transform(&self, msg: ChatMessage) -> Option<ChatMessage> {
+            (!msg.content.is_empty()).then_some(msg)
+        }
+    }
+
+    #[test]
+    fn transforms_run_in_registration_order() {
+        let mut transforms = MessageTransforms::default();
+        transforms.push(Append("a"));
+        transforms.push(Append("b"));
+        transforms.push(Append("c"));
+        let msg = transforms.apply(text_message(">")).unwrap();
+        assert_eq!(msg.content, ">abc");
+    }
+
+    #[test]
+    fn dropped_message_skips_later_transforms() {
+        let mut transforms = MessageTransforms::default();
+        transforms.push(DropEmpty);
+        transforms.push(Append("x"));
+        assert!(transforms.apply(text_message("")).is_none());
+        assert_eq!(transforms.apply(text_message("hi")).unwrap().content, "hix");
+    }
+
+    #[test]
+    fn expander_replaces_known_shortcodes_with_emoji() {
+        let expander = EmojiShortcodeExpander::default();
+        let mut member = text_message("");
+        member.runs = vec![kusa_emoji()];
+        expander.transform(member).unwrap();
+        assert_eq!(expander.known_count(), 2);
+
+        let msg = expander
+            .transform(text_message("それは:kusa::unknown:w"))
+            .unwrap();
+        assert_eq!(msg.runs.len(), 3);
+        assert!(matches!(&msg.runs[0], MessageRun::Text { content } if content == "それは"));
+        assert!(
+            matches!(&msg.runs[1], MessageRun::Emoji { emoji_id, .. } if emoji_id == "UC_x/kusa")
+        );
+        assert!(matches!(&msg.runs[2], MessageRun::Text { content } if content == ":unknown:w"));
+        assert_eq!(msg.content, "それは:_kusa::unknown:w");
+    }
+
+    #[test]
+    fn expander_keeps_message_without_known_shortcodes() {
+        let expander = EmojiShortcodeExpander::default();
+        let msg = expander.transform(text_message("12:30 :kusa:")).unwrap();
+        assert_eq!(msg.runs.len(), 1);
+        assert_eq!(msg.content, "12:30 :kusa:");
+    }
+}
@@ -0,0 +1,294 @@
+//! トレンド語の集計（spec: 07_revenue.md トレンドワード）
+//!
+//! 直近のメッセージ本文を語に分割し、時間窓内での出現数上位を返す。
+//! 形態素解析は行わず、文字種（漢字・ひらがな・カタカナ・英数字）の切り替わりで区切る。
+
+use crate::core::models::{ChatMessage, MessageRun};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// 既定のストップワード（日本語の助詞・助動詞など。空白区切り）
+pub const DEFAULT_STOPWORDS_JA: &str = "の に は を が で と も へ や か ね よ な て た だ です ます でした ました \
+     から まで けど って この その あの これ それ あれ ここ そこ する した して いる ある ない なる \
+     さん ちゃん";
+
+/// 既定のストップワード（英語の機能語。空白区切り）
+pub const DEFAULT_STOPWORDS_EN: &str = "the a an and or but is are was were be to of in on at for with \
+     it this that i you he she we they my your so do not";
+
+/// 文字種（語の区切りの判定に使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Kanji,
+    Hiragana,
+    Katakana,
+    Alphanumeric,
+    /// 空白・記号など（区切り文字）
+    Separator,
+}
+
+fn char_class(c: char) -> CharClass {
+    match c {
+        '々'
+        | '〆'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}' => CharClass::Kanji,
+        '\u{3041}'..='\u{309F}' => CharClass::Hiragana,
+        '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+            CharClass::Katakana
+        }
+        c if c.is_alphanumeric() => CharClass::Alphanumeric,
+        _ => CharClass::Separator,
+    }
+}
+
+/// 全角英数字を半角にし、小文字に揃える
+fn normalize_char(c: char) -> char {
+    let c = match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    };
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// テキストを文字種の切り替わりで語に分割する
+///
+/// 1文字の語は漢字（「草」など）のみ残す。
+fn tokenize_text(text: &str, tokens: &mut Vec<String>) {
+    let mut current = String::new();
+    let mut current_class = CharClass::Separator;
+    let mut flush = |current: &mut String, class: CharClass| {
+        if current.chars().count() > 1 || (class == CharClass::Kanji && !current.is_empty()) {
+            tokens.push(std::mem::take(current));
+        } else {
+            current.clear();
+        }
+    };
+    for c in text.chars().map(normalize_char) {
+        let class = char_class(c);
+        if class != current_class {
+            flush(&mut current, current_class);
+            current_class = class;
+        }
+        if class != CharClass::Separator {
+            current.push(c);
+        }
+    }
+    flush(&mut current, current_class);
+}
+
+/// メッセージを語に分割する
+///
+/// カスタム絵文字は代替テキスト（ショートカット名）を1語として数える。
+/// `runs` がない場合は `content` を分割する。
+fn tokenize_message(message: &ChatMessage) -> Vec<String> {
+    let mut tokens = Vec::new();
+    if message.runs.is_empty() {
+        tokenize_text(&message.content, &mut tokens);
+        return tokens;
+    }
+    for run in &message.runs {
+        match run {
+            MessageRun::Text { content } => tokenize_text(content, &mut tokens),
+            MessageRun::Emoji {
+                emoji_id, alt_text, ..
+            } => {
+                let key = if alt_text.is_empty() {
+                    emoji_id
+                } else {
+                    alt_text
+                };
+                if !key.is_empty() {
+                    tokens.push(key.clone());
+                }
+            }
+        }
+    }
+    tokens
+}
+
+/// 時間窓内のトレンド語を集計する
+#[derive(Debug)]
+pub struct TrendAnalyzer {
+    stopwords: HashSet<String>,
+    /// (投稿時刻のマイクロ秒, メッセージ内の重複を除いた語) を投稿順に保持
+    entries: VecDeque<(i64, Vec<String>)>,
+}
+
+impl Default for TrendAnalyzer {
+    fn default() -> Self {
+        Self::with_stopwords(
+            DEFAULT_STOPWORDS_JA
+                .split_whitespace()
+                .chain(DEFAULT_STOPWORDS_EN.split_whitespace())
+                .map(str::to_string),
+        )
+    }
+}
+
+impl TrendAnalyzer {
+    /// ストップワードを指定して作成する（既定のストップワードは含まない）
+    pub fn with_stopwords(stopwords: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            stopwords: stopwords
+                .into_iter()
+                .map(|w| w.chars().map(normalize_char).collect())
+                .collect(),
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// ストップワードを追加する
+    pub fn add_stopwords(&mut self, stopwords: impl IntoIterator<Item = String>) {
+        self.stopwords.extend(
+            stopwords
+                .into_iter()
+                .map(|w| w.chars().map(normalize_char).collect()),
+        );
+    }
+
+    /// メッセージを追加する（`timestamp_usec` を解釈できないメッセージは無視）
+    pub fn add_message(&mut self, message: &ChatMessage) {
+        let Ok(timestamp) = message.timestamp_usec.parse::<i64>() else {
+            return;
+        };
+        let mut seen = HashSet::new();
+        let terms: Vec<String> = tokenize_message(message)
+            .into_iter()
+            .filter(|t| !self.stopwords.contains(t) && seen.insert(t.clone()))
+            .collect();
+        self.entries.push_back((timestamp, terms));
+    }
+
+    /// 直近 `window` 内で多く使われた語を上位 `top_n` 件返す
+    ///
+    /// 窓の終端は最後に追加したメッセージの投稿時刻（アーカイブ再生でも同じ結果になる）。
+    /// 1メッセージ内の同じ語は1回と数え、同数の場合は語の辞書順。
+    pub fn trending_terms(&self, window: Duration, top_n: usize) -> Vec<(String, usize)> {
+        let Some(latest) = self.entries.iter().map(|(t, _)| *t).max() else {
+            return Vec::new();
+        };
+        let window_usec = i64::try_from(window.as_micros()).unwrap_or(i64::MAX);
+        let since = latest.saturating_sub(window_usec);
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (_, terms) in self.entries.iter().filter(|(t, _)| *t >= since) {
+            for term in terms {
+                *counts.entry(term.as_str()).or_default() += 1;
+            }
+        }
+
+        let mut ranked: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(term, count)| (term.to_string(), count))
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(top_n);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEC: i64 = 1_000_000;
+
+    fn message(timestamp_sec: i64, content: &str) -> ChatMessage {
+        ChatMessage {
+            timestamp_usec: (timestamp_sec * SEC).to_string(),
+            content: content.to_string(),
+            runs: vec![MessageRun::Text {
+                content: content.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn tokens(text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        tokenize_text(text, &mut tokens);
+        tokens
+    }
+
+    #[test]
+    fn tokenize_splits_on_script_changes() {
+        assert_eq!(tokens("今日の配信最高！"), vec!["今日", "配信最高"]);
+        assert_eq!(tokens("スパチャありがとう"), vec!["スパチャ", "ありがとう"]);
+        assert_eq!(tokens("ＧＧ wp"), vec!["gg", "wp"]);
+        assert_eq!(tokens("草"), vec!["草"]);
+    }
+
+    #[test]
+    fn stopwords_are_excluded() {
+        let mut analyzer = TrendAnalyzer::default();
+        analyzer.add_message(&message(0, "神です"));
+        assert_eq!(
+            analyzer.trending_terms(Duration::from_secs(60), 10),
+            vec![("神".to_string(), 1)]
+        );
+
+        analyzer.add_stopwords(["神".to_string()]);
+        analyzer.add_message(&message(1, "神"));
+        assert_eq!(
+            analyzer.trending_terms(Duration::from_secs(60), 10),
+            vec![("神".to_string(), 1)],
+            "追加前に集計済みの語は残り、追加後のメッセージからは除外される"
+        );
+    }
+
+    #[test]
+    fn counts_only_messages_within_window() {
+        let mut analyzer = TrendAnalyzer::default();
+        analyzer.add_message(&message(0, "おはよう"));
+        analyzer.add_message(&message(100, "草"));
+        analyzer.add_message(&message(110, "草 草"));
+        analyzer.add_message(&message(120, "かわいい 草"));
+
+        assert_eq!(
+            analyzer.trending_terms(Duration::from_secs(60), 10),
+            vec![("草".to_string(), 3), ("かわいい".to_string(), 1)]
+        );
+        assert_eq!(analyzer.trending_terms(Duration::from_secs(60), 1).len(), 1);
+        assert_eq!(
+            analyzer.trending_terms(Duration::from_secs(600), 10).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn custom_emoji_counted_by_shortcut() {
+        let mut analyzer = TrendAnalyzer::default();
+        for t in 0..2 {
+            let mut msg = message(t, "");
+            msg.runs = vec![
+                MessageRun::Emoji {
+                    emoji_id: "UC_x/abc".to_string(),
+                    image_url: String::new(),
+                    alt_text: ":_kusa:".to_string(),
+                    shortcuts: vec![":_kusa:".to_string()],
+                },
+                MessageRun::Text {
+                    content: "ナイス".to_string(),
+                },
+            ];
+            analyzer.add_message(&msg);
+        }
+
+        assert_eq!(
+            analyzer.trending_terms(Duration::from_secs(60), 10),
+            vec![(":_kusa:".to_string(), 2), ("ナイス".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn empty_analyzer_returns_nothing() {
+        let analyzer = TrendAnalyzer::default();
+        assert!(
+            analyzer
+                .trending_terms(Duration::from_secs(60), 10)
+                .is_empty()
+        );
+    }
+}
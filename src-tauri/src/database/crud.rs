@@ -1,7 +1,8 @@
 //! CRUD operations for the database
 
 use super::models::*;
-use crate::core::models::ChatMessage;
+use crate::core::api::parse_purchase_amount;
+use crate::core::models::{ChatMessage, extract_video_id};
 use anyhow::Result;
 use rusqlite::{Connection, OptionalExtension, params};
 
@@ -16,7 +17,7 @@ pub fn create_session(
     stream_title: Option<&str>,
     broadcaster_channel_id: Option<&str>,
     broadcaster_name: Option<&str>,
-) -> Result<String> {
+) -> Result<SessionId> {
     // Debug: Log session creation details
     tracing::info!(
         "Creating session: stream_url={:?}, stream_title={:?}, broadcaster_channel_id={:?}, broadcaster_name={:?}",
@@ -26,13 +27,14 @@ pub fn create_session(
         broadcaster_name
     );
 
-    let id = uuid::Uuid::new_v4().to_string();
-    let start_time = chrono::Utc::now().to_rfc3339();
-
-    conn.execute(
-        "INSERT INTO sessions (id, start_time, stream_url, stream_title, broadcaster_channel_id, broadcaster_name)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![id, start_time, stream_url, stream_title, broadcaster_channel_id, broadcaster_name],
+    let video_id = stream_url.and_then(extract_video_id);
+    let id = insert_session(
+        conn,
+        video_id.as_deref(),
+        stream_url,
+        stream_title,
+        broadcaster_channel_id,
+        broadcaster_name,
     )?;
 
     // Also save broadcaster profile if we have broadcaster info
@@ -51,21 +53,35 @@ pub fn create_session(
     Ok(id)
 }
 
-/// End a session
-pub fn end_session(conn: &Connection, session_id: &str) -> Result<()> {
-    let end_time = chrono::Utc::now().to_rfc3339();
+/// 動画のセッションを開始する（配信者情報を持たない保存・再生用）
+pub fn begin_session(conn: &Connection, video_id: &str, title: Option<&str>) -> Result<SessionId> {
+    insert_session(conn, Some(video_id), None, title, None, None)
+}
+
+fn insert_session(
+    conn: &Connection,
+    video_id: Option<&str>,
+    stream_url: Option<&str>,
+    stream_title: Option<&str>,
+    broadcaster_channel_id: Option<&str>,
+    broadcaster_name: Option<&str>,
+) -> Result<SessionId> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let start_time = chrono::Utc::now().to_rfc3339();
+
     conn.execute(
-        "UPDATE sessions SET end_time = ?1 WHERE id = ?2",
-        params![end_time, session_id],
+        "INSERT INTO sessions (id, start_time, video_id, stream_url, stream_title, broadcaster_channel_id, broadcaster_name)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![id, start_time, video_id, stream_url, stream_title, broadcaster_channel_id, broadcaster_name],
     )?;
-    Ok(())
+
+    Ok(id)
 }
 
-/// Update session statistics
-pub fn update_session_stats(conn: &Connection, session_id: &str) -> Result<()> {
-    conn.execute(
-        "UPDATE sessions SET
-            total_messages = (SELECT COUNT(*) FROM messages WHERE session_id = ?1),
+/// 保存済みメッセージからセッションの合計メッセージ数・収益を集計する SET 句（?1 = session_id）
+///
+/// 削除されたメッセージは数えない。
+const SESSION_TOTALS_SET: &str = "total_messages = (SELECT COUNT(*) FROM messages WHERE session_id = ?1 AND is_deleted = 0),
             total_revenue = (SELECT COALESCE(SUM(
                 CASE
                     WHEN amount IS NOT NULL THEN CAST(
@@ -74,39 +90,61 @@ pub fn update_session_stats(conn: &Connection, session_id: &str) -> Result<()> {
                     )
                     ELSE 0
                 END
-            ), 0) FROM messages WHERE session_id = ?1 AND message_type IN ('superchat', 'supersticker'))
-         WHERE id = ?1",
-        params![session_id],
-    )?;
+            ), 0) FROM messages
+            WHERE session_id = ?1 AND is_deleted = 0 AND message_type IN ('superchat', 'supersticker'))";
+
+/// End a session
+///
+/// 終了時刻の記録と合計メッセージ数・収益の最終集計を 1 文で行う。
+/// 終了したセッションにはメッセージを追加できない（読み取り専用）。
+/// 存在しない・終了済みのセッションは変更せず false を返す。
+pub fn end_session(conn: &Connection, session_id: &str, ended_at: &str) -> Result<bool> {
+    let sql = format!(
+        "UPDATE sessions SET end_time = ?2, {} WHERE id = ?1 AND end_time IS NULL",
+        SESSION_TOTALS_SET
+    );
+    let updated = conn.execute(&sql, params![session_id, ended_at])?;
+    Ok(updated > 0)
+}
+
+/// Update session statistics
+pub fn update_session_stats(conn: &Connection, session_id: &str) -> Result<()> {
+    let sql = format!("UPDATE sessions SET {} WHERE id = ?1", SESSION_TOTALS_SET);
+    conn.execute(&sql, params![session_id])?;
     Ok(())
 }
 
+/// Build Session from a row with standard column order
+fn row_to_session(row: &rusqlite::Row) -> rusqlite::Result<Session> {
+    Ok(Session {
+        id: row.get(0)?,
+        start_time: row.get(1)?,
+        end_time: row.get(2)?,
+        video_id: row.get(3)?,
+        stream_url: row.get(4)?,
+        stream_title: row.get(5)?,
+        broadcaster_channel_id: row.get(6)?,
+        broadcaster_name: row.get(7)?,
+        total_messages: row.get(8)?,
+        total_revenue: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
+const SESSION_COLUMNS: &str = "id, start_time, end_time, video_id, stream_url, stream_title, \
+     broadcaster_channel_id, broadcaster_name, total_messages, total_revenue, created_at, updated_at";
+
 /// Get sessions list
 pub fn get_sessions(conn: &Connection, limit: usize) -> Result<Vec<Session>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, start_time, end_time, stream_url, stream_title, broadcaster_channel_id,
-                broadcaster_name, total_messages, total_revenue, created_at, updated_at
-         FROM sessions
-         ORDER BY start_time DESC
-         LIMIT ?1",
-    )?;
+    let sql = format!(
+        "SELECT {} FROM sessions ORDER BY start_time DESC LIMIT ?1",
+        SESSION_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
 
     let sessions = stmt
-        .query_map(params![limit], |row| {
-            Ok(Session {
-                id: row.get(0)?,
-                start_time: row.get(1)?,
-                end_time: row.get(2)?,
-                stream_url: row.get(3)?,
-                stream_title: row.get(4)?,
-                broadcaster_channel_id: row.get(5)?,
-                broadcaster_name: row.get(6)?,
-                total_messages: row.get(7)?,
-                total_revenue: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        })?
+        .query_map(params![limit], row_to_session)?
         .collect::<Result<Vec<_>, _>>()?;
 
     Ok(sessions)
@@ -114,28 +152,9 @@ pub fn get_sessions(conn: &Connection, limit: usize) -> Result<Vec<Session>> {
 
 /// Get a single session by ID
 pub fn get_session(conn: &Connection, session_id: &str) -> Result<Option<Session>> {
+    let sql = format!("SELECT {} FROM sessions WHERE id = ?1", SESSION_COLUMNS);
     let session = conn
-        .query_row(
-            "SELECT id, start_time, end_time, stream_url, stream_title, broadcaster_channel_id,
-                    broadcaster_name, total_messages, total_revenue, created_at, updated_at
-             FROM sessions WHERE id = ?1",
-            params![session_id],
-            |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    start_time: row.get(1)?,
-                    end_time: row.get(2)?,
-                    stream_url: row.get(3)?,
-                    stream_title: row.get(4)?,
-                    broadcaster_channel_id: row.get(5)?,
-                    broadcaster_name: row.get(6)?,
-                    total_messages: row.get(7)?,
-                    total_revenue: row.get(8)?,
-                    created_at: row.get(9)?,
-                    updated_at: row.get(10)?,
-                })
-            },
-        )
+        .query_row(&sql, params![session_id], row_to_session)
         .optional()?;
 
     Ok(session)
@@ -168,12 +187,18 @@ pub fn save_message(
         _ => None,
     };
 
+    // ロール・バッジ情報はエクスポート用に JSON で保存
+    let metadata = message
+        .metadata
+        .as_ref()
+        .and_then(|m| serde_json::to_string(m).ok());
+
     // Insert message (ignore duplicates)
     conn.execute(
         "INSERT OR IGNORE INTO messages
          (session_id, message_id, timestamp, timestamp_usec, author, author_icon_url,
-          channel_id, content, message_type, amount, is_member)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+          channel_id, content, message_type, amount, is_member, metadata)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         params![
             session_id,
             message.id,
@@ -186,6 +211,7 @@ pub fn save_message(
             message_type,
             amount,
             message.is_member,
+            metadata,
         ],
     )?;
 
@@ -208,7 +234,28 @@ pub fn save_message(
     Ok(conn.last_insert_rowid())
 }
 
-/// Get messages for a session
+/// 配信中に削除されたメッセージを削除済みにする（行は残す）
+///
+/// 削除済みのメッセージは一覧・検索・集計から除外される。更新した件数を返す。
+pub fn mark_messages_deleted(
+    conn: &Connection,
+    session_id: &str,
+    message_ids: &[String],
+) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "UPDATE messages SET is_deleted = 1
+         WHERE session_id = ?1 AND message_id = ?2 AND is_deleted = 0",
+    )?;
+    let mut updated = 0;
+    for message_id in message_ids {
+        updated += stmt.execute(params![session_id, message_id])?;
+    }
+    Ok(updated)
+}
+
+/// Get messages for a session (excluding deleted ones), newest first
+///
+/// 同じ秒のメッセージも並びが揺れないよう、`timestamp_usec` → `message_id` の順で全順序を付ける。
 pub fn get_session_messages(
     conn: &Connection,
     session_id: &str,
@@ -218,8 +265,8 @@ pub fn get_session_messages(
         "SELECT id, session_id, message_id, timestamp, timestamp_usec, author, author_icon_url,
                 channel_id, content, message_type, amount, is_member, metadata, created_at
          FROM messages
-         WHERE session_id = ?1
-         ORDER BY timestamp DESC
+         WHERE session_id = ?1 AND is_deleted = 0
+         ORDER BY CAST(timestamp_usec AS INTEGER) DESC, message_id DESC
          LIMIT ?2",
     )?;
 
@@ -247,6 +294,236 @@ pub fn get_session_messages(
     Ok(messages)
 }
 
+/// Get every message of a session in the order it was received
+///
+/// 集計の再計算（spec: 07_revenue.md）用。件数の上限は設けない。削除されたメッセージは除く。
+pub fn get_all_session_messages(conn: &Connection, session_id: &str) -> Result<Vec<StoredMessage>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, session_id, message_id, timestamp, timestamp_usec, author, author_icon_url,
+                channel_id, content, message_type, amount, is_member, metadata, created_at
+         FROM messages
+         WHERE session_id = ?1 AND is_deleted = 0
+         ORDER BY id",
+    )?;
+
+    let messages = stmt
+        .query_map(params![session_id], |row| {
+            Ok(StoredMessage {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                message_id: row.get(2)?,
+                timestamp: row.get(3)?,
+                timestamp_usec: row.get(4)?,
+                author: row.get(5)?,
+                author_icon_url: row.get(6)?,
+                channel_id: row.get(7)?,
+                content: row.get(8)?,
+                message_type: row.get(9)?,
+                amount: row.get(10)?,
+                is_member: row.get::<_, i64>(11)? != 0,
+                metadata: row.get(12)?,
+                created_at: row.get(13)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(messages)
+}
+
+/// trigram tokenizer が一致を判定できる最短の語の長さ（文字数）
+const FTS_MIN_TERM_CHARS: usize = 3;
+
+/// 保存済みメッセージの検索方法
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageSearchQuery {
+    /// FTS5 の MATCH 式
+    Match(String),
+    /// trigram で扱えない短い語を含むため、各語を本文・投稿者名の LIKE で照合する（AND）
+    Like(Vec<String>),
+}
+
+/// 入力された検索語を FTS5 の MATCH 式に変換する
+///
+/// `"..."` はフレーズ、末尾 `*` の語は前方一致として扱い、それ以外の語は
+/// FTS5 の演算子として解釈されないよう引用符で囲む。語はすべて AND で結合する。
+/// trigram tokenizer は 3 文字未満の語に一致しないため、そのような語を含む場合は
+/// LIKE での照合に切り替える。検索語がない場合は None を返す。
+pub fn build_fts_query(input: &str) -> Option<MessageSearchQuery> {
+    // (語, 前方一致か)
+    let mut terms: Vec<(String, bool)> = Vec::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        if let Some(after_quote) = rest.strip_prefix('"') {
+            // フレーズ: 閉じ引用符まで（なければ末尾まで）
+            let end = after_quote.find('"').unwrap_or(after_quote.len());
+            let phrase = after_quote[..end].trim();
+            if !phrase.is_empty() {
+                terms.push((phrase.replace('"', ""), false));
+            }
+            rest = after_quote.get(end + 1..).unwrap_or("").trim_start();
+            continue;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '"')
+            .unwrap_or(rest.len());
+        let word = &rest[..end];
+        rest = rest[end..].trim_start();
+
+        let (word, prefix) = match word.strip_suffix('*') {
+            Some(stem) => (stem.trim_end_matches('*'), true),
+            None => (word, false),
+        };
+        if word.is_empty() {
+            continue;
+        }
+        terms.push((word.to_string(), prefix));
+    }
+
+    if terms.is_empty() {
+        return None;
+    }
+    if terms
+        .iter()
+        .any(|(term, _)| term.chars().count() < FTS_MIN_TERM_CHARS)
+    {
+        return Some(MessageSearchQuery::Like(
+            terms.into_iter().map(|(term, _)| term).collect(),
+        ));
+    }
+    let expr = terms
+        .iter()
+        .map(|(term, prefix)| format!("\"{}\"{}", term, if *prefix { "*" } else { "" }))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(MessageSearchQuery::Match(expr))
+}
+
+/// LIKE のパターンとして扱われないよう `%` `_` `\` をエスケープする（`ESCAPE '\'` と併用）
+fn escape_like(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 本文で最初に見つかった語を `<mark>` で囲む（LIKE 検索の抜粋。見つからなければ本文のまま）
+fn mark_first_match(content: &str, terms: &[String]) -> String {
+    // LIKE と同じく ASCII の大文字小文字を区別しない（バイト位置は変わらない）
+    let lower = content.to_ascii_lowercase();
+    let found = terms
+        .iter()
+        .filter_map(|term| {
+            lower
+                .find(&term.to_ascii_lowercase())
+                .map(|start| (start, start + term.len()))
+        })
+        .min();
+    match found {
+        Some((start, end)) => format!(
+            "{}<mark>{}</mark>{}",
+            &content[..start],
+            &content[start..end],
+            &content[end..]
+        ),
+        None => content.to_string(),
+    }
+}
+
+const SEARCH_MESSAGE_COLUMNS: &str =
+    "m.id, m.session_id, m.message_id, m.timestamp, m.timestamp_usec, m.author,
+     m.author_icon_url, m.channel_id, m.content, m.message_type, m.amount,
+     m.is_member, m.metadata, m.created_at";
+
+fn stored_message_from_search_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<StoredMessage> {
+    Ok(StoredMessage {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        message_id: row.get(2)?,
+        timestamp: row.get(3)?,
+        timestamp_usec: row.get(4)?,
+        author: row.get(5)?,
+        author_icon_url: row.get(6)?,
+        channel_id: row.get(7)?,
+        content: row.get(8)?,
+        message_type: row.get(9)?,
+        amount: row.get(10)?,
+        is_member: row.get::<_, i64>(11)? != 0,
+        metadata: row.get(12)?,
+        created_at: row.get(13)?,
+    })
+}
+
+/// 保存済みメッセージを本文・投稿者名で全文検索する
+///
+/// FTS5 で検索した場合は関連度順、短い語を含み LIKE で照合した場合は新しい順（`rank` は 0）。
+pub fn search_messages(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<MessageSearchResult>> {
+    match build_fts_query(query) {
+        None => Ok(Vec::new()),
+        Some(MessageSearchQuery::Match(fts_query)) => {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {SEARCH_MESSAGE_COLUMNS},
+                        snippet(messages_fts, 0, '<mark>', '</mark>', '…', 16),
+                        bm25(messages_fts)
+                 FROM messages_fts
+                 JOIN messages m ON m.id = messages_fts.rowid
+                 WHERE messages_fts MATCH ?1 AND m.is_deleted = 0
+                 ORDER BY bm25(messages_fts)
+                 LIMIT ?2"
+            ))?;
+            let results = stmt
+                .query_map(params![fts_query, limit], |row| {
+                    Ok(MessageSearchResult {
+                        message: stored_message_from_search_row(row)?,
+                        snippet: row.get(14)?,
+                        rank: row.get(15)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(results)
+        }
+        Some(MessageSearchQuery::Like(terms)) => {
+            let conditions = (1..=terms.len())
+                .map(|i| {
+                    format!("(m.content LIKE ?{i} ESCAPE '\\' OR m.author LIKE ?{i} ESCAPE '\\')")
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {SEARCH_MESSAGE_COLUMNS}
+                 FROM messages m
+                 WHERE {conditions} AND m.is_deleted = 0
+                 ORDER BY m.id DESC
+                 LIMIT {limit}"
+            ))?;
+            let patterns: Vec<String> = terms
+                .iter()
+                .map(|term| format!("%{}%", escape_like(term)))
+                .collect();
+            let results = stmt
+                .query_map(rusqlite::params_from_iter(&patterns), |row| {
+                    let message = stored_message_from_search_row(row)?;
+                    Ok(MessageSearchResult {
+                        snippet: mark_first_match(&message.content, &terms),
+                        message,
+                        rank: 0.0,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(results)
+        }
+    }
+}
+
 // ============================================================================
 // Viewer Profile Operations
 // ============================================================================
@@ -319,7 +596,7 @@ pub fn get_in_stream_comment_counts(
     Ok(counts)
 }
 
-/// Upsert viewer profile (returns the profile id)
+/// Upsert viewer profile for one message (returns the profile id)
 pub fn upsert_viewer_profile(
     conn: &Connection,
     broadcaster_channel_id: &str,
@@ -327,24 +604,44 @@ pub fn upsert_viewer_profile(
     display_name: &str,
     amount: Option<&str>,
 ) -> Result<i64> {
-    let now = chrono::Utc::now().to_rfc3339();
     let contribution = parse_amount(amount).unwrap_or(0.0);
+    upsert_viewer_activity(
+        conn,
+        broadcaster_channel_id,
+        channel_id,
+        display_name,
+        &ActivityDelta::message(contribution),
+    )
+}
 
-    conn.execute(
+/// Add activity to a viewer profile in a single statement (returns the profile id)
+///
+/// 新規の視聴者は `seen_at` を初見日時として作成し、既存の視聴者は
+/// メッセージ数・貢献額を加算して表示名と最終確認日時を更新する（初見日時は変えない）。
+pub fn upsert_viewer_activity(
+    conn: &Connection,
+    broadcaster_channel_id: &str,
+    channel_id: &str,
+    display_name: &str,
+    delta: &ActivityDelta,
+) -> Result<i64> {
+    let id = conn.query_row(
         "INSERT INTO viewer_profiles (broadcaster_channel_id, channel_id, display_name, first_seen, last_seen, message_count, total_contribution)
-         VALUES (?1, ?2, ?3, ?4, ?4, 1, ?5)
+         VALUES (?1, ?2, ?3, ?4, ?4, ?5, ?6)
          ON CONFLICT(broadcaster_channel_id, channel_id) DO UPDATE SET
             display_name = excluded.display_name,
             last_seen = excluded.last_seen,
-            message_count = message_count + 1,
-            total_contribution = total_contribution + excluded.total_contribution",
-        params![broadcaster_channel_id, channel_id, display_name, now, contribution],
-    )?;
-
-    // Get the id of the upserted row
-    let id: i64 = conn.query_row(
-        "SELECT id FROM viewer_profiles WHERE broadcaster_channel_id = ?1 AND channel_id = ?2",
-        params![broadcaster_channel_id, channel_id],
+            message_count = message_count + excluded.message_count,
+            total_contribution = total_contribution + excluded.total_contribution
+         RETURNING id",
+        params![
+            broadcaster_channel_id,
+            channel_id,
+            display_name,
+            delta.seen_at,
+            delta.message_count,
+            delta.contribution
+        ],
         |row| row.get(0),
     )?;
 
@@ -409,6 +706,42 @@ pub fn get_viewer_profile_by_id(conn: &Connection, id: i64) -> Result<Option<Vie
     Ok(profile)
 }
 
+/// Get a page of viewer profiles for a broadcaster (returns the page and the total count)
+///
+/// 同じ値の行は id 順に並べ、ページをまたいで重複・欠落しないようにする。
+/// いずれの並び順も `(broadcaster_channel_id, <ソート列> DESC)` のインデックスを使う。
+pub fn list_viewer_profiles(
+    conn: &Connection,
+    broadcaster_channel_id: &str,
+    offset: usize,
+    limit: usize,
+    sort: ProfileSort,
+) -> Result<(Vec<ViewerProfile>, i64)> {
+    let order_by = match sort {
+        ProfileSort::TotalContribution => "total_contribution DESC, id",
+        ProfileSort::MessageCount => "message_count DESC, id",
+        ProfileSort::LastSeen => "last_seen DESC, id",
+    };
+    let sql = format!(
+        "SELECT {} FROM viewer_profiles
+         WHERE broadcaster_channel_id = ?1
+         ORDER BY {}
+         LIMIT ?2 OFFSET ?3",
+        VIEWER_PROFILE_COLUMNS, order_by
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let profiles = stmt
+        .query_map(
+            params![broadcaster_channel_id, limit, offset],
+            row_to_viewer_profile,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    let total = get_viewer_count_for_broadcaster(conn, broadcaster_channel_id)?;
+
+    Ok((profiles, total))
+}
+
 /// Get top contributors for a session
 pub fn get_top_contributors(
     conn: &Connection,
@@ -421,7 +754,7 @@ pub fn get_top_contributors(
                     CAST(REPLACE(REPLACE(REPLACE(REPLACE(m.amount, '$', ''), '¥', ''), '€', ''), ',', '') AS REAL)
                 ELSE 0 END), 0) as contribution
          FROM messages m
-         WHERE m.session_id = ?1
+         WHERE m.session_id = ?1 AND m.is_deleted = 0
          GROUP BY m.channel_id
          ORDER BY contribution DESC, msg_count DESC
          LIMIT ?2",
@@ -717,37 +1050,7 @@ pub fn get_distinct_broadcaster_channels(conn: &Connection) -> Result<Vec<Broadc
 
 /// Parse amount string to f64
 fn parse_amount(amount: Option<&str>) -> Option<f64> {
-    let amount = amount?;
-    let cleaned: String = amount
-        .chars()
-        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
-        .collect();
-
-    // Handle European decimal format (1.234,56 -> 1234.56)
-    let normalized = if cleaned.contains(',') && cleaned.contains('.') {
-        // Assume comma is decimal separator if it comes after the last dot
-        let last_comma = cleaned.rfind(',').unwrap_or(0);
-        let last_dot = cleaned.rfind('.').unwrap_or(0);
-        if last_comma > last_dot {
-            cleaned.replace('.', "").replace(',', ".")
-        } else {
-            cleaned.replace(',', "")
-        }
-    } else if cleaned.contains(',') {
-        // Comma could be decimal or thousands separator
-        let parts: Vec<&str> = cleaned.split(',').collect();
-        if parts.len() == 2 && parts[1].len() <= 2 {
-            // Likely decimal separator
-            cleaned.replace(',', ".")
-        } else {
-            // Likely thousands separator
-            cleaned.replace(',', "")
-        }
-    } else {
-        cleaned
-    };
-
-    normalized.parse().ok()
+    parse_purchase_amount(amount?).map(|parsed| parsed.amount)
 }
 
 #[cfg(test)]
@@ -862,10 +1165,74 @@ mod tests {
         let conn = db.connection().await;
         let id = create_session(&conn, None, None, None, None).unwrap();
 
-        end_session(&conn, &id).unwrap();
+        assert!(end_session(&conn, &id, "2026-01-01T01:00:00+00:00").unwrap());
+
+        let session = get_session(&conn, &id).unwrap().unwrap();
+        assert_eq!(
+            session.end_time.as_deref(),
+            Some("2026-01-01T01:00:00+00:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn session_create_extracts_video_id() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let id = create_session(
+            &conn,
+            Some("https://www.youtube.com/watch?v=abc123XYZ_-&t=10"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
 
         let session = get_session(&conn, &id).unwrap().unwrap();
-        assert!(session.end_time.is_some());
+        assert_eq!(session.video_id.as_deref(), Some("abc123XYZ_-"));
+    }
+
+    #[tokio::test]
+    async fn session_lifecycle_finalizes_counts_and_becomes_read_only() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let id = begin_session(&conn, "video1", Some("Stream")).unwrap();
+
+        save_message(
+            &conn,
+            &id,
+            None,
+            &make_text_message("m1", "U", "UC_u", "hi"),
+            None,
+        )
+        .unwrap();
+        save_message(
+            &conn,
+            &id,
+            None,
+            &make_superchat_message("sc1", "U", "UC_u", "$10.00"),
+            None,
+        )
+        .unwrap();
+
+        assert!(end_session(&conn, &id, "2026-01-01T01:00:00+00:00").unwrap());
+        // 終了済みのセッションは再度終了しない
+        assert!(!end_session(&conn, &id, "2026-01-01T02:00:00+00:00").unwrap());
+
+        let session = get_session(&conn, &id).unwrap().unwrap();
+        assert_eq!(session.video_id.as_deref(), Some("video1"));
+        assert_eq!(session.stream_title.as_deref(), Some("Stream"));
+        assert_eq!(
+            session.end_time.as_deref(),
+            Some("2026-01-01T01:00:00+00:00")
+        );
+        assert_eq!(session.total_messages, 2);
+        assert!((session.total_revenue - 10.0).abs() < f64::EPSILON);
+
+        // 終了後のメッセージは保存できず、保存済みのメッセージは読める
+        let late = make_text_message("m2", "U", "UC_u", "late");
+        assert!(save_message(&conn, &id, None, &late, None).is_err());
+        assert_eq!(get_session_messages(&conn, &id, 10).unwrap().len(), 2);
+        assert_eq!(get_session(&conn, &id).unwrap().unwrap().total_messages, 2);
     }
 
     #[tokio::test]
@@ -967,6 +1334,61 @@ mod tests {
         assert_eq!(messages.len(), 3);
     }
 
+    #[tokio::test]
+    async fn session_messages_in_the_same_second_have_a_stable_order() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, None, None).unwrap();
+
+        // 表示上は同じ秒（12:00:00）で、受信順と ID 順・マイクロ秒順がばらばら
+        for (id, usec) in [
+            ("b", "1000500"),
+            ("c", "1000100"),
+            ("a", "1000500"),
+            ("d", "999999"),
+        ] {
+            let msg = ChatMessage {
+                timestamp_usec: usec.to_string(),
+                ..make_text_message(id, "User", "UC_u", id)
+            };
+            save_message(&conn, &session_id, None, &msg, None).unwrap();
+        }
+
+        for _ in 0..3 {
+            let messages = get_session_messages(&conn, &session_id, 10).unwrap();
+            let ids: Vec<&str> = messages.iter().map(|m| m.message_id.as_str()).collect();
+            assert_eq!(ids, vec!["b", "a", "c", "d"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn all_session_messages_in_received_order_convert_back() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, None, None).unwrap();
+
+        for i in 0..5 {
+            let msg = make_text_message(&format!("m{}", i), "User", "UC_u", &format!("msg{}", i));
+            save_message(&conn, &session_id, None, &msg, None).unwrap();
+        }
+        let sc = make_superchat_message("sc", "Fan", "UC_fan", "¥1,000");
+        save_message(&conn, &session_id, None, &sc, None).unwrap();
+
+        let messages = get_all_session_messages(&conn, &session_id).unwrap();
+        let ids: Vec<&str> = messages.iter().map(|m| m.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["m0", "m1", "m2", "m3", "m4", "sc"]);
+
+        let restored = messages.into_iter().last().unwrap().into_chat_message();
+        assert_eq!(
+            restored.message_type,
+            MessageType::SuperChat {
+                amount: "¥1,000".to_string()
+            }
+        );
+        assert_eq!(restored.channel_id, "UC_fan");
+        assert_eq!(restored.timestamp_usec, "1000000");
+    }
+
     // ========================================================================
     // Viewer Profile (06_viewer.md + 08_database.md: 視聴者プロフィール)
     // ========================================================================
@@ -1430,6 +1852,241 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[tokio::test]
+    async fn save_message_stores_metadata_json() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, Some("UC_bc"), Some("BC")).unwrap();
+
+        let mut message = make_text_message("m1", "Mod", "UC_mod", "hi");
+        message.metadata = Some(crate::core::models::MessageMetadata {
+            is_moderator: true,
+            ..Default::default()
+        });
+        save_message(&conn, &session_id, Some("UC_bc"), &message, None).unwrap();
+
+        let metadata_json: String = conn
+            .query_row(
+                "SELECT metadata FROM messages WHERE message_id = 'm1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let metadata: crate::core::models::MessageMetadata =
+            serde_json::from_str(&metadata_json).unwrap();
+        assert!(metadata.is_moderator);
+    }
+
+    // ========================================================================
+    // mark_messages_deleted (08_database.md: 削除されたメッセージ)
+    // ========================================================================
+
+    #[tokio::test]
+    async fn deleted_messages_are_excluded_from_lists_and_totals() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, Some("UC_bc"), Some("BC")).unwrap();
+        save_message(
+            &conn,
+            &session_id,
+            None,
+            &make_text_message("m1", "A", "UC_a", "keep"),
+            None,
+        )
+        .unwrap();
+        save_message(
+            &conn,
+            &session_id,
+            None,
+            &make_superchat_message("m2", "B", "UC_b", "¥1,000"),
+            None,
+        )
+        .unwrap();
+
+        let ids = vec!["m2".to_string(), "unknown".to_string()];
+        assert_eq!(mark_messages_deleted(&conn, &session_id, &ids).unwrap(), 1);
+        // 削除済みのものは数え直さない
+        assert_eq!(mark_messages_deleted(&conn, &session_id, &ids).unwrap(), 0);
+
+        let messages = get_all_session_messages(&conn, &session_id).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].message_id, "m1");
+        assert_eq!(
+            get_session_messages(&conn, &session_id, 10).unwrap().len(),
+            1
+        );
+        assert_eq!(
+            get_top_contributors(&conn, &session_id, 10).unwrap().len(),
+            1
+        );
+
+        // セッション終了時の集計からも外れる
+        assert!(end_session(&conn, &session_id, "2025-01-01T01:00:00Z").unwrap());
+        let session = get_session(&conn, &session_id).unwrap().unwrap();
+        assert_eq!(session.total_messages, 1);
+        assert_eq!(session.total_revenue, 0.0);
+    }
+
+    // ========================================================================
+    // search_messages (08_database.md: 全文検索)
+    // ========================================================================
+
+    #[test]
+    fn build_fts_query_quotes_terms() {
+        let fts = |expr: &str| Some(MessageSearchQuery::Match(expr.to_string()));
+        assert_eq!(build_fts_query("hello world"), fts("\"hello\" \"world\""));
+        assert_eq!(build_fts_query("stre*"), fts("\"stre\"*"));
+        assert_eq!(
+            build_fts_query("\"good morning\" chat"),
+            fts("\"good morning\" \"chat\"")
+        );
+        // FTS5 の演算子・記号はリテラルとして扱う
+        assert_eq!(build_fts_query("a:b -ccc"), fts("\"a:b\" \"-ccc\""));
+        assert_eq!(build_fts_query("   "), None);
+        assert_eq!(build_fts_query("*"), None);
+    }
+
+    #[test]
+    fn build_fts_query_falls_back_to_like_for_short_terms() {
+        let like = |terms: &[&str]| {
+            Some(MessageSearchQuery::Like(
+                terms.iter().map(|t| t.to_string()).collect(),
+            ))
+        };
+        assert_eq!(build_fts_query("世界"), like(&["世界"]));
+        assert_eq!(build_fts_query("草 morning*"), like(&["草", "morning"]));
+        assert_eq!(build_fts_query("\"ab\""), like(&["ab"]));
+        assert_eq!(
+            build_fts_query("世界が"),
+            Some(MessageSearchQuery::Match("\"世界が\"".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn search_messages_finds_japanese_substrings() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, Some("UC_bc"), Some("BC")).unwrap();
+        for (id, author, content) in [
+            ("m1", "たろう", "今日は世界が平和ですね"),
+            ("m2", "Bob", "今日もお疲れさまでした"),
+            ("m3", "Carol", "100%の力で歌います"),
+            ("m4", "Dave", "こんばんは"),
+        ] {
+            save_message(
+                &conn,
+                &session_id,
+                Some("UC_bc"),
+                &make_text_message(id, author, &format!("UC_{}", id), content),
+                None,
+            )
+            .unwrap();
+        }
+        let ids = |query: &str| -> Vec<String> {
+            let mut ids: Vec<_> = search_messages(&conn, query, 10)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.message.message_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        // 3 文字以上は trigram の FTS5 で文中の部分一致
+        assert_eq!(ids("世界が平和"), vec!["m1"]);
+        assert_eq!(ids("お疲れさま"), vec!["m2"]);
+        let results = search_messages(&conn, "世界が", 10).unwrap();
+        assert_eq!(results[0].snippet, "今日は<mark>世界が</mark>平和ですね");
+
+        // 2 文字以下は LIKE で照合する
+        assert_eq!(ids("世界"), vec!["m1"]);
+        assert_eq!(ids("今日"), vec!["m1", "m2"]);
+        assert_eq!(ids("今日 平和"), vec!["m1"]);
+        assert_eq!(ids("たろ"), vec!["m1"]);
+        let results = search_messages(&conn, "世界", 10).unwrap();
+        assert_eq!(results[0].snippet, "今日は<mark>世界</mark>が平和ですね");
+        assert_eq!(results[0].rank, 0.0);
+
+        // LIKE のワイルドカードは文字として扱う
+        assert_eq!(ids("%"), vec!["m3"]);
+        assert!(ids("_").is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_messages_matches_content_and_author() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, Some("UC_bc"), Some("BC")).unwrap();
+        for (id, author, content) in [
+            ("m1", "Alice", "good morning everyone"),
+            ("m2", "Bob", "morning stream starts now"),
+            ("m3", "Carol", "streaming is fun"),
+            ("m4", "morningbird", "hello"),
+        ] {
+            save_message(
+                &conn,
+                &session_id,
+                Some("UC_bc"),
+                &make_text_message(id, author, &format!("UC_{}", id), content),
+                None,
+            )
+            .unwrap();
+        }
+
+        // フレーズ一致
+        let results = search_messages(&conn, "\"good morning\"", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message.message_id, "m1");
+        assert!(results[0].snippet.contains("<mark>"));
+        assert!(results[0].snippet.contains("morning"));
+
+        // 前方一致は本文・投稿者名の両方が対象
+        let mut ids: Vec<_> = search_messages(&conn, "morning*", 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.message.message_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["m1", "m2", "m4"]);
+
+        let ids: Vec<_> = search_messages(&conn, "stream*", 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.message.message_id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+
+        assert_eq!(search_messages(&conn, "morning*", 1).unwrap().len(), 1);
+        assert!(search_messages(&conn, "", 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_messages_index_follows_update_and_delete() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        let session_id = create_session(&conn, None, None, Some("UC_bc"), Some("BC")).unwrap();
+        save_message(
+            &conn,
+            &session_id,
+            Some("UC_bc"),
+            &make_text_message("m1", "Alice", "UC_alice", "original text"),
+            None,
+        )
+        .unwrap();
+
+        conn.execute(
+            "UPDATE messages SET content = 'edited text' WHERE message_id = 'm1'",
+            [],
+        )
+        .unwrap();
+        assert!(search_messages(&conn, "original", 10).unwrap().is_empty());
+        assert_eq!(search_messages(&conn, "edited", 10).unwrap().len(), 1);
+
+        conn.execute("DELETE FROM messages WHERE message_id = 'm1'", [])
+            .unwrap();
+        assert!(search_messages(&conn, "edited", 10).unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn save_message_without_video_id_does_not_create_viewer_stream() {
         let db = setup_db();
@@ -1852,6 +2509,212 @@ mod tests {
         assert_eq!(by_id.display_name, "Viewer1");
     }
 
+    // ========================================================================
+    // upsert_viewer_activity (06_viewer.md: 視聴者プロフィールの更新)
+    // ========================================================================
+
+    fn delta(message_count: i64, contribution: f64, seen_at: &str) -> ActivityDelta {
+        ActivityDelta {
+            message_count,
+            contribution,
+            seen_at: seen_at.to_string(),
+        }
+    }
+
+    /// spec: 複数回の upsert で初見日時は変わらず、最終確認日時・件数・貢献額が更新される
+    #[tokio::test]
+    async fn upsert_viewer_activity_preserves_first_seen() {
+        let db = setup_db();
+        let conn = db.connection().await;
+
+        let id = upsert_viewer_activity(
+            &conn,
+            "UC_bc",
+            "UC_v1",
+            "Viewer",
+            &delta(1, 0.0, "2024-01-01T00:00:00+00:00"),
+        )
+        .unwrap();
+        let same_id = upsert_viewer_activity(
+            &conn,
+            "UC_bc",
+            "UC_v1",
+            "Renamed",
+            &delta(3, 500.0, "2024-01-02T00:00:00+00:00"),
+        )
+        .unwrap();
+        upsert_viewer_activity(
+            &conn,
+            "UC_bc",
+            "UC_v1",
+            "Renamed",
+            &delta(1, 100.0, "2024-01-03T00:00:00+00:00"),
+        )
+        .unwrap();
+        assert_eq!(id, same_id);
+
+        let profile = get_viewer_profile(&conn, "UC_bc", "UC_v1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(profile.id, id);
+        assert_eq!(profile.first_seen, "2024-01-01T00:00:00+00:00");
+        assert_eq!(profile.last_seen, "2024-01-03T00:00:00+00:00");
+        assert_eq!(profile.display_name, "Renamed");
+        assert_eq!(profile.message_count, 5);
+        assert!((profile.total_contribution - 600.0).abs() < f64::EPSILON);
+    }
+
+    /// spec: トランザクション内でまとめた upsert もコミット後に反映され、ロールバックでは反映されない
+    #[tokio::test]
+    async fn upsert_viewer_activity_in_transaction() {
+        let db = setup_db();
+        let conn = db.connection().await;
+
+        let tx = conn.unchecked_transaction().unwrap();
+        for _ in 0..3 {
+            upsert_viewer_activity(
+                &tx,
+                "UC_bc",
+                "UC_v1",
+                "Viewer",
+                &ActivityDelta::message(0.0),
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = conn.unchecked_transaction().unwrap();
+        upsert_viewer_activity(
+            &tx,
+            "UC_bc",
+            "UC_v1",
+            "Viewer",
+            &ActivityDelta::message(0.0),
+        )
+        .unwrap();
+        tx.rollback().unwrap();
+
+        let profile = get_viewer_profile(&conn, "UC_bc", "UC_v1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(profile.message_count, 3);
+    }
+
+    // ========================================================================
+    // list_viewer_profiles (08_database.md: 視聴者プロフィールのページ取得)
+    // ========================================================================
+
+    /// 3人の視聴者（貢献額・メッセージ数・最終確認日時がそれぞれ異なる）を登録する
+    fn insert_ranked_viewers(conn: &Connection) {
+        for (channel_id, last_seen, message_count, contribution) in [
+            ("UC_a", "2024-01-03T00:00:00+00:00", 5, 100.0),
+            ("UC_b", "2024-01-01T00:00:00+00:00", 20, 0.0),
+            ("UC_c", "2024-01-02T00:00:00+00:00", 1, 500.0),
+        ] {
+            conn.execute(
+                "INSERT INTO viewer_profiles (broadcaster_channel_id, channel_id, display_name, first_seen, last_seen, message_count, total_contribution)
+                 VALUES ('UC_bc', ?1, ?1, ?2, ?2, ?3, ?4)",
+                params![channel_id, last_seen, message_count, contribution],
+            )
+            .unwrap();
+        }
+        upsert_viewer_profile(conn, "UC_other", "UC_x", "Other", None).unwrap();
+    }
+
+    fn channel_ids(profiles: &[ViewerProfile]) -> Vec<&str> {
+        profiles.iter().map(|p| p.channel_id.as_str()).collect()
+    }
+
+    /// spec: list_viewer_profiles は指定した並び順で配信者の視聴者を返す
+    #[tokio::test]
+    async fn list_viewer_profiles_sorts_by_requested_column() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        insert_ranked_viewers(&conn);
+
+        let (by_contribution, total) =
+            list_viewer_profiles(&conn, "UC_bc", 0, 10, ProfileSort::TotalContribution).unwrap();
+        assert_eq!(channel_ids(&by_contribution), ["UC_c", "UC_a", "UC_b"]);
+        assert_eq!(total, 3);
+
+        let (by_count, _) =
+            list_viewer_profiles(&conn, "UC_bc", 0, 10, ProfileSort::MessageCount).unwrap();
+        assert_eq!(channel_ids(&by_count), ["UC_b", "UC_a", "UC_c"]);
+
+        let (by_last_seen, _) =
+            list_viewer_profiles(&conn, "UC_bc", 0, 10, ProfileSort::LastSeen).unwrap();
+        assert_eq!(channel_ids(&by_last_seen), ["UC_a", "UC_c", "UC_b"]);
+    }
+
+    /// spec: list_viewer_profiles はページ外の件数も含めた総数を返す
+    #[tokio::test]
+    async fn list_viewer_profiles_paginates_with_total_count() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        insert_ranked_viewers(&conn);
+
+        let (first, total) =
+            list_viewer_profiles(&conn, "UC_bc", 0, 2, ProfileSort::MessageCount).unwrap();
+        assert_eq!(channel_ids(&first), ["UC_b", "UC_a"]);
+        assert_eq!(total, 3);
+
+        let (second, total) =
+            list_viewer_profiles(&conn, "UC_bc", 2, 2, ProfileSort::MessageCount).unwrap();
+        assert_eq!(channel_ids(&second), ["UC_c"]);
+        assert_eq!(total, 3);
+
+        let (empty, total) =
+            list_viewer_profiles(&conn, "UC_none", 0, 2, ProfileSort::MessageCount).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(total, 0);
+    }
+
+    /// spec: 同じ値の行は id 順に並び、ページをまたいで重複しない
+    #[tokio::test]
+    async fn list_viewer_profiles_orders_ties_by_id() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        for i in 0..5 {
+            upsert_viewer_profile(&conn, "UC_bc", &format!("UC_v{}", i), "Viewer", None).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        for offset in [0, 2, 4] {
+            let (page, _) =
+                list_viewer_profiles(&conn, "UC_bc", offset, 2, ProfileSort::TotalContribution)
+                    .unwrap();
+            seen.extend(page.into_iter().map(|p| p.channel_id));
+        }
+        assert_eq!(seen, ["UC_v0", "UC_v1", "UC_v2", "UC_v3", "UC_v4"]);
+    }
+
+    /// spec: いずれの並び順もインデックスで並べ替える（一時 B-tree を使わない）
+    #[tokio::test]
+    async fn list_viewer_profiles_sorts_using_indexes() {
+        let db = setup_db();
+        let conn = db.connection().await;
+        for column in ["total_contribution", "message_count", "last_seen"] {
+            let sql = format!(
+                "EXPLAIN QUERY PLAN SELECT id FROM viewer_profiles
+                 WHERE broadcaster_channel_id = 'UC_bc'
+                 ORDER BY {} DESC, id LIMIT 10",
+                column
+            );
+            let mut stmt = conn.prepare(&sql).unwrap();
+            let plan: Vec<String> = stmt
+                .query_map([], |row| row.get(3))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert!(
+                plan.iter().all(|detail| !detail.contains("TEMP B-TREE")),
+                "{}: {:?}",
+                column,
+                plan
+            );
+        }
+    }
+
     // ========================================================================
     // get_top_contributors (08_database.md: トップ貢献者取得)
     // ========================================================================
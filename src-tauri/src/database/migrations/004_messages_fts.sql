@@ -0,0 +1,37 @@
+-- Migration 004: Full-text search over stored messages
+-- messages.content / messages.author を対象とする FTS5 インデックス（external content）。
+-- トリガーで messages の INSERT / UPDATE / DELETE と同期する。
+-- 空白を含まない日本語の文中でも部分一致できるよう trigram tokenizer を使う
+-- （3 文字以上の部分一致。2 文字以下は LIKE で検索する）。
+
+CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
+    content,
+    author,
+    content='messages',
+    content_rowid='id',
+    tokenize='trigram'
+);
+
+CREATE TRIGGER IF NOT EXISTS messages_fts_insert
+    AFTER INSERT ON messages
+    BEGIN
+        INSERT INTO messages_fts(rowid, content, author) VALUES (NEW.id, NEW.content, NEW.author);
+    END;
+
+CREATE TRIGGER IF NOT EXISTS messages_fts_delete
+    AFTER DELETE ON messages
+    BEGIN
+        INSERT INTO messages_fts(messages_fts, rowid, content, author)
+        VALUES ('delete', OLD.id, OLD.content, OLD.author);
+    END;
+
+CREATE TRIGGER IF NOT EXISTS messages_fts_update
+    AFTER UPDATE OF content, author ON messages
+    BEGIN
+        INSERT INTO messages_fts(messages_fts, rowid, content, author)
+        VALUES ('delete', OLD.id, OLD.content, OLD.author);
+        INSERT INTO messages_fts(rowid, content, author) VALUES (NEW.id, NEW.content, NEW.author);
+    END;
+
+-- 既存メッセージをインデックスに取り込む
+INSERT INTO messages_fts(messages_fts) VALUES ('rebuild');
@@ -0,0 +1,4 @@
+-- Migration 005: Index for listing viewer profiles by last seen
+-- 貢献額順・メッセージ数順は 001 のインデックスを使う。
+
+CREATE INDEX IF NOT EXISTS idx_viewer_profiles_last_seen ON viewer_profiles(broadcaster_channel_id, last_seen DESC);
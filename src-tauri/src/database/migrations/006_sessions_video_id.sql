@@ -0,0 +1,26 @@
+-- Migration 006: Session lifecycle (video_id + read-only finished sessions)
+-- Adds sessions.video_id so past streams can be listed and queried per video.
+-- Existing rows are backfilled from sessions.stream_url (format: https://...watch?v=VIDEO_ID...)
+
+ALTER TABLE sessions ADD COLUMN video_id TEXT;
+
+UPDATE sessions
+SET video_id = CASE
+        WHEN INSTR(SUBSTR(stream_url, INSTR(stream_url, 'watch?v=') + 8), '&') > 0 THEN
+            SUBSTR(stream_url,
+                   INSTR(stream_url, 'watch?v=') + 8,
+                   INSTR(SUBSTR(stream_url, INSTR(stream_url, 'watch?v=') + 8), '&') - 1)
+        ELSE
+            SUBSTR(stream_url, INSTR(stream_url, 'watch?v=') + 8)
+    END
+WHERE stream_url LIKE '%watch?v=%';
+
+CREATE INDEX IF NOT EXISTS idx_sessions_video_id ON sessions(video_id);
+
+-- 終了したセッションは読み取り専用（最終集計した件数・収益と保存済みメッセージを一致させる）
+CREATE TRIGGER IF NOT EXISTS reject_messages_for_ended_session
+    BEFORE INSERT ON messages
+    WHEN (SELECT end_time FROM sessions WHERE id = NEW.session_id) IS NOT NULL
+BEGIN
+    SELECT RAISE(ABORT, 'session has ended');
+END;
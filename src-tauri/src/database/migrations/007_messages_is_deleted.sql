@@ -0,0 +1,5 @@
+-- Migration 007: Deleted chat messages
+-- 配信中に削除（markChatItemAsDeletedAction / removeChatItemAction）されたメッセージを記録する。
+-- 行は残し、集計・一覧・検索から除外する。終了したセッションでも UPDATE は許可されている。
+
+ALTER TABLE messages ADD COLUMN is_deleted INTEGER NOT NULL DEFAULT 0;
@@ -2,6 +2,9 @@
 //!
 //! Handles schema versioning and migrations to ensure the database
 //! schema is always up-to-date with the application code.
+//!
+//! 適用済みのマイグレーションは `schema_versions` テーブルに記録し、
+//! 最新の適用済みバージョンを `PRAGMA user_version` にも保持する。
 
 use anyhow::{Context, Result};
 use rusqlite::Connection;
@@ -9,6 +12,8 @@ use std::collections::HashSet;
 
 /// Migration definition
 struct Migration {
+    /// スキーマバージョン（1 始まりの連番。`PRAGMA user_version` に記録する）
+    version: u32,
     /// Unique name (used as identifier in schema_versions table)
     name: &'static str,
     /// SQL to execute for this migration
@@ -19,21 +24,57 @@ struct Migration {
 /// New migrations should be added to the end of this list
 const MIGRATIONS: &[Migration] = &[
     Migration {
+        version: 1,
         name: "001_initial",
         sql: include_str!("001_initial.sql"),
     },
     Migration {
+        version: 2,
         name: "002_viewer_streams",
         sql: include_str!("002_viewer_streams.sql"),
     },
     Migration {
+        version: 3,
         name: "003_backfill_viewer_streams",
         sql: include_str!("003_backfill_viewer_streams.sql"),
     },
+    Migration {
+        version: 4,
+        name: "004_messages_fts",
+        sql: include_str!("004_messages_fts.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "005_viewer_profiles_last_seen",
+        sql: include_str!("005_viewer_profiles_last_seen.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "006_sessions_video_id",
+        sql: include_str!("006_sessions_video_id.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "007_messages_is_deleted",
+        sql: include_str!("007_messages_is_deleted.sql"),
+    },
 ];
 
 /// Run all pending migrations
 pub fn run_migrations(conn: &Connection) -> Result<()> {
+    apply_migrations(conn, MIGRATIONS)
+}
+
+/// 現在のスキーマバージョン（`PRAGMA user_version`）を返す
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
+/// 指定したマイグレーション列のうち未適用のものを順に適用する
+///
+/// 各マイグレーションは SQL の実行・`schema_versions` への記録・`user_version` の更新を
+/// 1 つのトランザクションで行い、失敗時はロールバックして以降を適用しない。
+fn apply_migrations(conn: &Connection, migrations: &[Migration]) -> Result<()> {
     // Check for legacy database (old schema without version tracking)
     if is_legacy_database(conn)? {
         handle_legacy_database(conn)?;
@@ -46,19 +87,54 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
     let applied = get_applied_migrations(conn)?;
 
     // Run pending migrations
-    for migration in MIGRATIONS {
+    for migration in migrations {
         if !applied.contains(migration.name) {
-            tracing::info!("Applying migration: {}", migration.name);
-
-            conn.execute_batch(migration.sql)
-                .with_context(|| format!("Failed to apply migration: {}", migration.name))?;
-
-            record_migration(conn, migration.name)?;
+            tracing::info!(
+                "Applying migration: {} (version {})",
+                migration.name,
+                migration.version
+            );
+
+            conn.execute_batch("BEGIN TRANSACTION;")?;
+            let result = apply_migration(conn, migration);
+            match result {
+                Ok(()) => conn.execute_batch("COMMIT;")?,
+                Err(e) => {
+                    conn.execute_batch("ROLLBACK;")?;
+                    return Err(e);
+                }
+            }
 
             tracing::info!("Migration applied successfully: {}", migration.name);
         }
     }
 
+    // バージョン管理導入前に適用済みだった DB の user_version を同期する
+    let latest_applied = migrations
+        .iter()
+        .filter(|m| applied.contains(m.name))
+        .map(|m| m.version)
+        .max()
+        .unwrap_or(0);
+    if schema_version(conn)? < latest_applied {
+        set_schema_version(conn, latest_applied)?;
+    }
+
+    Ok(())
+}
+
+/// 1 件のマイグレーションを適用して記録する（トランザクションは呼び出し元が管理）
+fn apply_migration(conn: &Connection, migration: &Migration) -> Result<()> {
+    conn.execute_batch(migration.sql)
+        .with_context(|| format!("Failed to apply migration: {}", migration.name))?;
+    record_migration(conn, migration.name)?;
+    set_schema_version(conn, migration.version)?;
+    Ok(())
+}
+
+/// `PRAGMA user_version` を更新する
+fn set_schema_version(conn: &Connection, version: u32) -> Result<()> {
+    conn.pragma_update(None, "user_version", version)?;
     Ok(())
 }
 
@@ -338,6 +414,80 @@ mod tests {
         assert_eq!(count, MIGRATIONS.len() as i64);
     }
 
+    #[test]
+    fn test_migrates_v0_database_forward() {
+        // user_version = 0 の新規 DB から最新バージョンまで適用されること
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("v0.db");
+        {
+            let conn = Connection::open(&path).unwrap();
+            assert_eq!(schema_version(&conn).unwrap(), 0);
+            run_migrations(&conn).unwrap();
+        }
+
+        let conn = Connection::open(&path).unwrap();
+        let latest = MIGRATIONS.last().unwrap().version;
+        assert_eq!(schema_version(&conn).unwrap(), latest);
+
+        let has_viewer_streams: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='viewer_streams'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(has_viewer_streams);
+    }
+
+    #[test]
+    fn test_syncs_user_version_for_previously_migrated_database() {
+        // schema_versions はあるが user_version が未設定の既存 DB
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        set_schema_version(&conn, 0).unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        assert_eq!(
+            schema_version(&conn).unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let conn = Connection::open_in_memory().unwrap();
+        let migrations = [
+            Migration {
+                version: 1,
+                name: "001_ok",
+                sql: "CREATE TABLE ok_table (id INTEGER);",
+            },
+            Migration {
+                version: 2,
+                name: "002_broken",
+                sql: "CREATE TABLE partial (id INTEGER); INSERT INTO missing_table VALUES (1);",
+            },
+        ];
+
+        assert!(apply_migrations(&conn, &migrations).is_err());
+
+        // 失敗したマイグレーションの変更と記録は残らない
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        let has_partial: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='partial'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!has_partial);
+        let recorded: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_versions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(recorded, 1);
+    }
+
     #[test]
     fn test_legacy_database_detection() {
         let conn = Connection::open_in_memory().unwrap();
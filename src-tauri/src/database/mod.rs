@@ -9,13 +9,29 @@ pub use models::*;
 
 use anyhow::Result;
 use rusqlite::Connection;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// ファイル DB のデフォルトのコネクションプールサイズ
+pub const DEFAULT_POOL_SIZE: usize = 4;
+
+/// 書き込みロック競合時に待機する最大時間
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// プールから貸し出されたコネクション（drop でプールに返却される）
+pub type PooledConnection = OwnedMutexGuard<Connection>;
 
 /// Database wrapper for thread-safe access
+///
+/// 複数のコネクションをプールし、分析クエリとチャットの書き込みが
+/// 単一のコネクションで直列化されないようにする。
 pub struct Database {
-    conn: Arc<Mutex<Connection>>,
+    pool: Vec<Arc<Mutex<Connection>>>,
+    /// 空きがない場合に待機するコネクションを分散させるためのカウンタ
+    next: AtomicUsize,
 }
 
 impl Database {
@@ -28,36 +44,100 @@ impl Database {
             std::fs::create_dir_all(parent)?;
         }
 
-        let conn = Connection::open(&path)?;
+        let db = Self::with_pool_size(&path, DEFAULT_POOL_SIZE)?;
 
-        // Enable foreign keys
-        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        tracing::info!(
+            "Database initialized at {:?} (pool size: {})",
+            path,
+            DEFAULT_POOL_SIZE
+        );
 
-        // Run migrations
-        migrations::run_migrations(&conn)?;
+        Ok(db)
+    }
 
-        tracing::info!("Database initialized at {:?}", path);
+    /// 指定したサイズのコネクションプールでデータベースを開く
+    ///
+    /// 各コネクションで WAL モードを有効にし、読み取りが書き込みをブロックしないようにする。
+    /// マイグレーションは最初のコネクションでのみ実行する。
+    pub fn with_pool_size(path: &Path, size: usize) -> Result<Self> {
+        let size = size.max(1);
+        let mut pool = Vec::with_capacity(size);
+        for i in 0..size {
+            let conn = Connection::open(path)?;
+            configure_connection(&conn)?;
+            let mode: String =
+                conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+            if !mode.eq_ignore_ascii_case("wal") {
+                tracing::warn!(
+                    "WAL モードを有効化できませんでした (journal_mode: {})",
+                    mode
+                );
+            }
+            if i == 0 {
+                // Run migrations
+                migrations::run_migrations(&conn)?;
+            }
+            pool.push(Arc::new(Mutex::new(conn)));
+        }
 
         Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+            pool,
+            next: AtomicUsize::new(0),
         })
     }
 
     /// Create an in-memory database (for testing)
+    ///
+    /// インメモリ DB はコネクションごとに別の DB になるため、プールサイズは 1。
     #[cfg(test)]
     pub fn new_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
-        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        configure_connection(&conn)?;
         migrations::run_migrations(&conn)?;
         Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+            pool: vec![Arc::new(Mutex::new(conn))],
+            next: AtomicUsize::new(0),
         })
     }
 
+    /// プールのコネクション数
+    pub fn pool_size(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// プールからコネクションを取得する
+    ///
+    /// 空いているコネクションがあれば即座に返し、すべて使用中の場合は
+    /// ラウンドロビンで選んだコネクションの返却を待つ。
+    pub async fn get_conn(&self) -> PooledConnection {
+        for conn in &self.pool {
+            if let Ok(guard) = Arc::clone(conn).try_lock_owned() {
+                return guard;
+            }
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.pool.len();
+        Arc::clone(&self.pool[index]).lock_owned().await
+    }
+
     /// Get the connection for operations
-    pub async fn connection(&self) -> tokio::sync::MutexGuard<'_, Connection> {
-        self.conn.lock().await
+    pub async fn connection(&self) -> PooledConnection {
+        self.get_conn().await
     }
+
+    /// WAL の内容を DB ファイルに書き戻し、WAL ファイルを切り詰める（アプリ終了時に呼ぶ）
+    pub async fn checkpoint(&self) -> Result<()> {
+        let conn = self.get_conn().await;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+}
+
+/// 全コネクション共通の設定
+fn configure_connection(conn: &Connection) -> Result<()> {
+    // Enable foreign keys
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(())
 }
 
 /// データベースファイルのパスを返す
@@ -69,3 +149,73 @@ fn get_database_path() -> Result<PathBuf> {
 pub fn get_backup_dir() -> Result<PathBuf> {
     crate::paths::backup_dir().map_err(|e| anyhow::anyhow!(e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn with_pool_size_hands_out_distinct_connections() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::with_pool_size(&dir.path().join("pool.db"), 2).unwrap();
+        assert_eq!(db.pool_size(), 2);
+
+        // 1つ目を保持したままでも2つ目を取得でき、同じ DB を参照する
+        let writer = db.get_conn().await;
+        writer
+            .execute(
+                "INSERT INTO sessions (id, start_time) VALUES ('s1', '2024-01-01T00:00:00Z')",
+                [],
+            )
+            .unwrap();
+        let reader = db.get_conn().await;
+        let count: i64 = reader
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let mode: String = reader
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode.to_lowercase(), "wal");
+    }
+
+    #[tokio::test]
+    async fn checkpoint_truncates_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.db");
+        let db = Database::with_pool_size(&path, 2).unwrap();
+        db.get_conn()
+            .await
+            .execute(
+                "INSERT INTO sessions (id, start_time) VALUES ('s1', '2024-01-01T00:00:00Z')",
+                [],
+            )
+            .unwrap();
+        let wal = dir.path().join("checkpoint.db-wal");
+        assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+
+        db.checkpoint().await.unwrap();
+        assert_eq!(std::fs::metadata(&wal).unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn get_conn_waits_when_pool_exhausted() {
+        let db = Arc::new(Database::new_in_memory().unwrap());
+        assert_eq!(db.pool_size(), 1);
+
+        let held = db.get_conn().await;
+        let db_for_task = Arc::clone(&db);
+        let waiter = tokio::spawn(async move {
+            let conn = db_for_task.get_conn().await;
+            conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))
+                .unwrap()
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(held);
+        assert_eq!(waiter.await.unwrap(), 1);
+    }
+}
@@ -2,12 +2,18 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::models::{ChatMessage, MessageMetadata, MessageRun, MessageType};
+
+/// セッション ID（UUID v4）
+pub type SessionId = String;
+
 /// Session record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
-    pub id: String,
+    pub id: SessionId,
     pub start_time: String,
     pub end_time: Option<String>,
+    pub video_id: Option<String>,
     pub stream_url: Option<String>,
     pub stream_title: Option<String>,
     pub broadcaster_channel_id: Option<String>,
@@ -37,6 +43,54 @@ pub struct StoredMessage {
     pub created_at: Option<String>,
 }
 
+impl StoredMessage {
+    /// 保存したメッセージを集計に渡せる `ChatMessage` に戻す（spec: 07_revenue.md 集計の再計算）
+    ///
+    /// 本文は1つのテキストランとして扱う。メンバー継続月数・ギフト数は保存していないため持たない。
+    pub fn into_chat_message(self) -> ChatMessage {
+        let amount = self.amount.unwrap_or_default();
+        let message_type = match self.message_type.as_str() {
+            "superchat" => MessageType::SuperChat { amount },
+            "supersticker" => MessageType::SuperSticker { amount },
+            "membership" => MessageType::Membership {
+                milestone_months: None,
+            },
+            "membership_gift" => MessageType::MembershipGift { gift_count: 0 },
+            "system" => MessageType::System,
+            _ => MessageType::Text,
+        };
+        let metadata: Option<MessageMetadata> = self
+            .metadata
+            .and_then(|json| serde_json::from_str(&json).ok());
+        ChatMessage {
+            id: self.message_id,
+            timestamp: self.timestamp,
+            timestamp_usec: self.timestamp_usec,
+            message_type,
+            author: self.author,
+            author_icon_url: self.author_icon_url,
+            channel_id: self.channel_id,
+            runs: vec![MessageRun::Text {
+                content: self.content.clone(),
+            }],
+            content: self.content,
+            metadata,
+            is_member: self.is_member,
+            ..Default::default()
+        }
+    }
+}
+
+/// Full-text search result for stored messages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchResult {
+    pub message: StoredMessage,
+    /// 一致箇所を `<mark>` で囲んだ本文の抜粋
+    pub snippet: String,
+    /// FTS5 の bm25 スコア（小さいほど関連度が高い）
+    pub rank: f64,
+}
+
 /// Viewer profile record (broadcaster-scoped)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViewerProfile {
@@ -54,6 +108,41 @@ pub struct ViewerProfile {
     pub updated_at: Option<String>,
 }
 
+/// Activity added to a viewer profile by `upsert_viewer_activity`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityDelta {
+    /// 加算するメッセージ数
+    pub message_count: i64,
+    /// 加算する貢献額（SuperChat 等）
+    pub contribution: f64,
+    /// 最終確認日時（RFC3339）。新規の視聴者では初見日時にもなる
+    pub seen_at: String,
+}
+
+impl ActivityDelta {
+    /// 現在時刻に受信した 1 件のメッセージ分
+    pub fn message(contribution: f64) -> Self {
+        Self {
+            message_count: 1,
+            contribution,
+            seen_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Sort order for the paginated viewer profile list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileSort {
+    /// 総貢献額（SuperChat 等）の多い順
+    #[default]
+    TotalContribution,
+    /// メッセージ数の多い順
+    MessageCount,
+    /// 最終確認日時の新しい順
+    LastSeen,
+}
+
 /// Viewer custom info record (extension of viewer_profiles)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ViewerCustomInfo {
@@ -14,6 +14,9 @@ pub enum CommandError {
     /// 認証失敗（無効なクレデンシャル）
     #[error("{0}")]
     AuthFailed(String),
+    /// 指定したアカウントプロファイルが存在しない
+    #[error("Profile not found: {0}")]
+    ProfileNotFound(String),
     /// 認証情報ストレージ操作失敗
     #[error("{0}")]
     StorageError(String),
@@ -32,6 +35,12 @@ pub enum CommandError {
     /// HTTP/API呼び出しエラー
     #[error("{0}")]
     ApiError(String),
+    /// メンバー限定配信で、メンバーのアカウントでログインしていない
+    #[error("{0}")]
+    MembersOnly(String),
+    /// プロキシに接続できない・プロキシの設定が不正
+    #[error("{0}")]
+    ProxyError(String),
     /// TTS操作エラー
     #[error("{0}")]
     TtsError(String),
@@ -70,6 +79,29 @@ impl From<std::io::Error> for CommandError {
     }
 }
 
+impl From<crate::core::moderation::ModerationError> for CommandError {
+    fn from(e: crate::core::moderation::ModerationError) -> Self {
+        use crate::core::moderation::ModerationError;
+        match e {
+            ModerationError::InvalidRule { .. } => CommandError::InvalidInput(e.to_string()),
+            ModerationError::Io(_) => CommandError::IoError(e.to_string()),
+            ModerationError::Json(_) => CommandError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<crate::core::api::ProxyError> for CommandError {
+    fn from(e: crate::core::api::ProxyError) -> Self {
+        CommandError::ProxyError(e.to_string())
+    }
+}
+
+impl From<crate::core::icon_cache::IconFetchError> for CommandError {
+    fn from(e: crate::core::icon_cache::IconFetchError) -> Self {
+        CommandError::ApiError(e.to_string())
+    }
+}
+
 impl From<reqwest::Error> for CommandError {
     fn from(e: reqwest::Error) -> Self {
         CommandError::ApiError(e.to_string())
@@ -8,6 +8,8 @@ pub mod database;
 pub mod errors;
 pub mod paths;
 pub mod state;
+#[cfg(test)]
+mod test_http;
 pub mod tts;
 
 pub use database::Database;
@@ -18,21 +20,37 @@ use tauri_plugin_window_state::StateFlags;
 
 // Re-export command functions for registration
 use commands::{
+    AnalyticsRecomputeState,
     ConfigState,
+    DiscordState,
+    EngagementState,
+    IconCacheState,
+    ModerationState,
+    NotificationState,
+    ReplayState,
     SaveConfigState,
     auth_check_session_validity,
     auth_clear_webview_cookies,
     auth_delete_credentials,
+    auth_delete_profile,
     // Auth (spec: 01_auth.md)
     auth_get_status,
+    auth_list_profiles,
     auth_load_credentials,
+    auth_oauth_logout,
+    auth_oauth_poll,
+    auth_oauth_start,
     auth_open_window,
     auth_save_credentials,
+    auth_save_profile,
     auth_save_raw_cookies,
+    auth_switch_profile,
     auth_use_fallback_storage,
     auth_validate_credentials,
     broadcaster_delete,
     broadcaster_get_list,
+    cancel_analytics_recompute,
+    clear_icon_cache,
     config_get_value,
     // Config (spec: 09_config.md)
     config_load,
@@ -42,9 +60,16 @@ use commands::{
     connect_to_stream,
     disconnect_all_streams,
     disconnect_stream,
+    estimate_current_export,
+    export_contributor_leaderboard,
     export_current_messages,
     export_session_data,
+    // Icon cache (spec: 02_chat.md)
+    get_author_icon,
     get_connections,
+    // Engagement (spec: 02_chat.md)
+    get_engagement_snapshots,
+    get_message_density,
     // Analytics (spec: 07_revenue.md)
     get_revenue_analytics,
     get_session_analytics,
@@ -52,16 +77,39 @@ use commands::{
     // Database (spec: 08_database.md)
     get_sessions,
     get_top_contributors,
+    get_trending_terms,
+    get_video_metadata,
+    import_session_file,
+    // Moderation (spec: 02_chat.md)
+    moderation_load_rules,
+    moderation_save_rules,
+    // Notifications (spec: 02_chat.md)
+    notification_sound,
     // Raw Response (spec: 05_raw_response.md)
     raw_response_get_config,
     raw_response_resolve_path,
     raw_response_update_config,
+    raw_response_validate,
+    recompute_session_analytics,
+    // Replay (spec: 05_raw_response.md)
+    replay_get_status,
+    replay_messages_in_range,
+    replay_pause,
+    replay_resume,
+    replay_seek,
+    replay_seek_time,
+    replay_start,
+    replay_stop,
+    search_archive_by_author,
+    search_archive_by_content,
+    search_stored_messages,
     set_chat_mode,
     tts_clear_queue,
     tts_discover_exe,
     tts_get_config,
     tts_get_launch_status,
     tts_get_status,
+    tts_get_voicevox_speakers,
     tts_kill_backend,
     tts_launch_backend,
     tts_select_exe,
@@ -77,6 +125,7 @@ use commands::{
     viewer_get_list,
     // Viewer (spec: 06_viewer.md)
     viewer_get_profile,
+    viewer_list_profiles,
     viewer_search,
     viewer_update_info,
     viewer_upsert_custom_info,
@@ -97,6 +146,13 @@ pub fn run() {
         .manage(AppState::new())
         .manage(ConfigState::default())
         .manage(SaveConfigState::default())
+        .manage(ModerationState::default())
+        .manage(NotificationState::default())
+        .manage(DiscordState::default())
+        .manage(EngagementState::default())
+        .manage(IconCacheState::default())
+        .manage(ReplayState::default())
+        .manage(AnalyticsRecomputeState::default())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(
@@ -127,6 +183,18 @@ pub fn run() {
                 start_websocket_server_auto(app_handle, ws_server).await;
             });
 
+            // Start the local feed for browser sources if enabled
+            #[cfg(feature = "local_server")]
+            {
+                app.manage(commands::websocket::LocalFeedState::default());
+                tauri::async_runtime::spawn(commands::websocket::start_local_feed_auto(
+                    app.handle().clone(),
+                ));
+            }
+
+            // Watch authentication expiry in the background
+            commands::auth_window::spawn_expiry_watcher(app.handle().clone());
+
             // Auto-start TTS processing if enabled
             let tts_manager = state.tts_manager.clone();
             let tts_process_manager = state.tts_process_manager.clone();
@@ -173,12 +241,22 @@ pub fn run() {
             auth_open_window,
             auth_check_session_validity,
             auth_use_fallback_storage,
+            auth_oauth_start,
+            auth_oauth_poll,
+            auth_oauth_logout,
+            auth_list_profiles,
+            auth_save_profile,
+            auth_switch_profile,
+            auth_delete_profile,
             // Chat (spec: 02_chat.md)
             connect_to_stream,
             disconnect_stream,
             disconnect_all_streams,
             get_connections,
             set_chat_mode,
+            get_video_metadata,
+            search_archive_by_content,
+            search_archive_by_author,
             // Config (spec: 09_config.md)
             config_load,
             config_save,
@@ -189,18 +267,37 @@ pub fn run() {
             // Database (spec: 08_database.md)
             get_sessions,
             get_session_messages,
+            search_stored_messages,
             viewer_update_info,
             // Analytics (spec: 07_revenue.md)
             get_revenue_analytics,
             get_session_analytics,
+            get_trending_terms,
             export_session_data,
             export_current_messages,
+            estimate_current_export,
+            export_contributor_leaderboard,
+            import_session_file,
+            recompute_session_analytics,
+            cancel_analytics_recompute,
+            // Moderation (spec: 02_chat.md)
+            moderation_load_rules,
+            moderation_save_rules,
+            // Notifications (spec: 02_chat.md)
+            notification_sound,
+            // Engagement (spec: 02_chat.md)
+            get_engagement_snapshots,
+            get_message_density,
+            // Icon cache (spec: 02_chat.md)
+            get_author_icon,
+            clear_icon_cache,
             // TTS (spec: 04_tts.md)
             tts_speak,
             tts_speak_direct,
             tts_update_config,
             tts_get_config,
             tts_test_connection,
+            tts_get_voicevox_speakers,
             tts_start,
             tts_stop,
             tts_clear_queue,
@@ -213,6 +310,7 @@ pub fn run() {
             // Viewer (spec: 06_viewer.md)
             viewer_get_profile,
             viewer_get_list,
+            viewer_list_profiles,
             viewer_search,
             viewer_get_custom_info,
             viewer_upsert_custom_info,
@@ -224,13 +322,38 @@ pub fn run() {
             raw_response_get_config,
             raw_response_update_config,
             raw_response_resolve_path,
+            raw_response_validate,
+            // Replay (spec: 05_raw_response.md)
+            replay_start,
+            replay_pause,
+            replay_resume,
+            replay_seek,
+            replay_seek_time,
+            replay_stop,
+            replay_get_status,
+            replay_messages_in_range,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app_handle, event| {
             if let tauri::RunEvent::ExitRequested { .. } = event {
-                // Kill auto-launched TTS processes on exit
                 let state = app_handle.state::<AppState>();
+
+                // Flush chat captures, sessions and the TTS queue before exiting
+                let report = tauri::async_runtime::block_on(
+                    core::shutdown::AppShutdown::default().run(state.inner()),
+                );
+                if report.is_clean() {
+                    log::info!("Shutdown completed: {:?}", report.completed);
+                } else {
+                    log::warn!(
+                        "Shutdown incomplete: timed out {:?}, failed {:?}",
+                        report.timed_out,
+                        report.failed
+                    );
+                }
+
+                // Kill auto-launched TTS processes on exit
                 let tts_manager = state.tts_manager.clone();
                 let tts_process_manager = state.tts_process_manager.clone();
 
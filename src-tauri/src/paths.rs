@@ -34,11 +34,26 @@ pub fn credentials_path() -> Result<PathBuf, String> {
     Ok(config_dir()?.join("credentials.toml"))
 }
 
+/// 名前付きプロファイルの認証情報ファイルのパスを返す（config_dir + "credentials-{name}.toml"）
+pub fn profile_credentials_path(name: &str) -> Result<PathBuf, String> {
+    Ok(config_dir()?.join(format!("credentials-{}.toml", name)))
+}
+
+/// OAuth トークンの保存ファイルのパスを返す（config_dir + "oauth_tokens.json"、フォールバックモード用）
+pub fn oauth_tokens_path() -> Result<PathBuf, String> {
+    Ok(config_dir()?.join("oauth_tokens.json"))
+}
+
 /// 設定ファイルのパスを返す（config_dir + "config.toml"）
 pub fn config_path() -> Result<PathBuf, String> {
     Ok(config_dir()?.join("config.toml"))
 }
 
+/// ローカルモデレーションのルールファイルのパスを返す（config_dir + "moderation_rules.json"）
+pub fn moderation_rules_path() -> Result<PathBuf, String> {
+    Ok(config_dir()?.join("moderation_rules.json"))
+}
+
 /// データベースファイルのパスを返す（data_dir + "liscov.db"）
 pub fn database_path() -> Result<PathBuf, String> {
     Ok(data_dir()?.join("liscov.db"))
@@ -49,6 +64,11 @@ pub fn backup_dir() -> Result<PathBuf, String> {
     Ok(data_dir()?.join("backups"))
 }
 
+/// 取得位置（continuation token）の保存ファイルのパスを返す（data_dir + "continuations.json"）
+pub fn continuation_state_path() -> Result<PathBuf, String> {
+    Ok(data_dir()?.join("continuations.json"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,4 +187,13 @@ mod tests {
         let path = backup_dir().expect("backup_dir should succeed");
         assert!(path.ends_with("backups"));
     }
+
+    #[test]
+    #[serial(liscov_env)]
+    fn continuation_state_path_ends_with_continuations_json() {
+        // SAFETY: テスト環境でのみ実行。#[serial] で直列化済み
+        unsafe { std::env::remove_var("LISCOV_APP_NAME") };
+        let path = continuation_state_path().expect("continuation_state_path should succeed");
+        assert!(path.ends_with("continuations.json"));
+    }
 }
@@ -2,8 +2,12 @@
 
 use crate::connection::StreamConnection;
 use crate::core::api::WebSocketServer;
+use crate::core::continuation_store::ContinuationStore;
+use crate::core::disk_archive::ArchiveWriter;
 use crate::core::models::ChatMessage;
-use crate::database::Database;
+use crate::core::shutdown::ShutdownHooks;
+use crate::core::transform::MessageTransforms;
+use crate::database::{self, Database};
 use crate::tts::{TtsManager, TtsProcessManager};
 use std::collections::HashMap;
 use std::collections::VecDeque;
@@ -11,9 +15,27 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 use tokio::sync::RwLock;
 
-/// メモリに保持するメッセージの最大数
+/// メモリに保持するメッセージの最大数（`archive.hot_size` の既定値）
 pub const MAX_MESSAGES: usize = 1000;
 
+/// メッセージバッファの末尾にメッセージを追加し、`capacity` を超えて押し出したメッセージを古い順に返す
+pub fn push_message(
+    messages: &mut VecDeque<ChatMessage>,
+    message: ChatMessage,
+    capacity: usize,
+) -> Vec<ChatMessage> {
+    messages.push_back(message);
+    let excess = messages.len().saturating_sub(capacity.max(1));
+    messages.drain(..excess).collect()
+}
+
+/// メッセージバッファから指定した ID のメッセージを取り除き、取り除いた件数を返す
+pub fn remove_messages(messages: &mut VecDeque<ChatMessage>, ids: &[String]) -> usize {
+    let before = messages.len();
+    messages.retain(|msg| !ids.contains(&msg.id));
+    before - messages.len()
+}
+
 /// Application state shared across commands
 pub struct AppState {
     /// WebSocket server for external app integration
@@ -30,6 +52,12 @@ pub struct AppState {
     pub next_connection_id: Arc<AtomicU64>,
     /// アクティブな接続のマップ（connection_id -> StreamConnection）
     pub connections: Arc<RwLock<HashMap<u64, StreamConnection>>>,
+    /// 動画ごとの取得位置（continuation token）の保存先
+    pub continuation_store: Arc<ContinuationStore>,
+    /// 取得したメッセージに登録順で適用する変換
+    pub message_transforms: Arc<std::sync::RwLock<MessageTransforms>>,
+    /// メッセージバッファから押し出したメッセージのディスクアーカイブへの書き込み口
+    pub archive_writer: Arc<ArchiveWriter>,
 }
 
 impl AppState {
@@ -57,16 +85,18 @@ impl AppState {
             tts_process_manager: Arc::new(tts_process_manager),
             next_connection_id: Arc::new(AtomicU64::new(0)),
             connections: Arc::new(RwLock::new(HashMap::new())),
+            continuation_store: Arc::new(ContinuationStore::default()),
+            message_transforms: Arc::new(
+                std::sync::RwLock::new(MessageTransforms::with_builtins()),
+            ),
+            archive_writer: Arc::new(ArchiveWriter::new()),
         }
     }
 
     /// メッセージバッファにメッセージを追加する
     pub async fn add_message(&self, message: ChatMessage) {
         let mut messages = self.messages.write().await;
-        if messages.len() >= MAX_MESSAGES {
-            messages.pop_front();
-        }
-        messages.push_back(message);
+        push_message(&mut messages, message, MAX_MESSAGES);
     }
 
     /// 最近のメッセージを取得する
@@ -82,8 +112,136 @@ impl AppState {
     }
 }
 
+/// アプリ終了時の後始末（spec: 08_database.md アプリ終了時の処理）
+impl ShutdownHooks for AppState {
+    async fn stop_monitoring(&self) -> Result<(), String> {
+        let handles: Vec<_> = {
+            let mut connections = self.connections.write().await;
+            connections
+                .values_mut()
+                .filter_map(|conn| {
+                    conn.cancellation_token.cancel();
+                    conn.task_handle.take()
+                })
+                .collect()
+        };
+        // 各タスクは取得済みメッセージを書き込み、セッションを終了してから終わる
+        for result in futures_util::future::join_all(handles).await {
+            if let Err(e) = result {
+                tracing::warn!("監視タスクが異常終了: {}", e);
+            }
+        }
+        // ディスクアーカイブへの追記も書き込み終えるまで待つ
+        self.archive_writer.flush().await;
+        Ok(())
+    }
+
+    async fn end_open_sessions(&self) -> Result<(), String> {
+        let session_ids: Vec<String> = {
+            let connections = self.connections.read().await;
+            connections
+                .values()
+                .filter_map(|conn| conn.session_id.clone())
+                .collect()
+        };
+        let db_guard = self.database.read().await;
+        let Some(db) = db_guard.as_ref() else {
+            return Ok(());
+        };
+        let conn = db.connection().await;
+        let ended_at = chrono::Utc::now().to_rfc3339();
+        for session_id in &session_ids {
+            // 監視タスクが終了済みにしたセッションは変更されない
+            database::end_session(&conn, session_id, &ended_at).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    async fn flush_database(&self) -> Result<(), String> {
+        let db_guard = self.database.read().await;
+        match db_guard.as_ref() {
+            Some(db) => db.checkpoint().await.map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    async fn stop_tts(&self) -> Result<(), String> {
+        let config = self.tts_manager.get_config().await;
+        self.tts_manager.shutdown(config.drain_queue_on_exit).await;
+        Ok(())
+    }
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str) -> ChatMessage {
+        ChatMessage {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // spec: 02_chat.md メッセージのディスクアーカイブ
+    #[test]
+    fn evicted_messages_land_in_the_disk_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = crate::core::disk_archive::DiskArchive::new(dir.path().join("chat.ndjson"));
+        let mut messages = VecDeque::new();
+        for id in ["a", "b", "c"] {
+            let evicted = push_message(&mut messages, message(id), 2);
+            archive.append(&evicted).unwrap();
+        }
+
+        let ids: Vec<_> = messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["b", "c"]);
+        let written = std::fs::read_to_string(archive.path()).unwrap();
+        let archived: Vec<ChatMessage> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, "a");
+    }
+
+    #[test]
+    fn push_message_evicts_down_to_a_reduced_capacity() {
+        let mut messages: VecDeque<_> = ["a", "b", "c"].into_iter().map(message).collect();
+
+        let evicted = push_message(&mut messages, message("d"), 2);
+
+        let evicted: Vec<_> = evicted.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(evicted, ["a", "b"]);
+        assert_eq!(messages.len(), 2);
+    }
+
+    // spec: 02_chat.md 削除されたメッセージ
+    #[test]
+    fn removing_an_added_message_leaves_nothing_visible() {
+        let mut messages = VecDeque::new();
+        messages.push_back(message("m1"));
+
+        assert_eq!(remove_messages(&mut messages, &["m1".to_string()]), 1);
+        assert!(messages.is_empty());
+        // 既に取り除いた ID は何もしない
+        assert_eq!(remove_messages(&mut messages, &["m1".to_string()]), 0);
+    }
+
+    #[test]
+    fn remove_messages_keeps_other_messages_in_order() {
+        let mut messages: VecDeque<_> = ["a", "b", "c"].into_iter().map(message).collect();
+
+        let removed = remove_messages(&mut messages, &["b".to_string(), "x".to_string()]);
+
+        assert_eq!(removed, 1);
+        let ids: Vec<_> = messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+    }
+}
@@ -0,0 +1,122 @@
+//! テスト用の最小限の HTTP サーバー
+//!
+//! 接続ごとにリクエスト（ヘッダーと Content-Length 分の本文）を読み切ってから応答を返して閉じる。
+//! 読み切る前に閉じると送信中のクライアントがリセットを受けることがあり、テストが不安定になる。
+
+use std::net::SocketAddr;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// サーバーが受け取ったリクエスト
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReceivedRequest {
+    /// リクエスト行とヘッダー（末尾の空行を含まない）
+    pub head: String,
+    pub body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    /// ヘッダーの値（名前は大文字小文字を区別しない）
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+}
+
+/// `(ステータス, 本文)` の JSON 応答
+pub(crate) fn json_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// 受け付けた接続に `responses` を順に返すサーバーを起動する
+///
+/// 受け取ったリクエストは応答を書く前に送る（応答を受け取った時点で記録済み）。
+/// すべて返し終えたら待ち受けをやめ、以降の接続は拒否される。
+pub(crate) async fn serve<S: Into<String>>(
+    responses: impl IntoIterator<Item = S>,
+) -> (SocketAddr, mpsc::UnboundedReceiver<ReceivedRequest>) {
+    let responses: Vec<String> = responses.into_iter().map(Into::into).collect();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        for response in responses {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let request = read_request(&mut stream).await;
+            let _ = tx.send(request);
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+        }
+    });
+    (addr, rx)
+}
+
+/// ヘッダーと Content-Length 分の本文を読む（途中で閉じられたら読めた分を返す）
+async fn read_request(stream: &mut TcpStream) -> ReceivedRequest {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(header_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+            let mut request = ReceivedRequest {
+                head,
+                body: Vec::new(),
+            };
+            let length = request
+                .header("content-length")
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0);
+            let body_start = header_end + 4;
+            if buf.len() >= body_start + length {
+                request.body = buf[body_start..body_start + length].to_vec();
+                return request;
+            }
+        }
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => {
+                return ReceivedRequest {
+                    head: String::from_utf8_lossy(&buf).into_owned(),
+                    body: Vec::new(),
+                };
+            }
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_a_body_split_across_writes_before_responding() {
+        let (addr, mut requests) = serve([json_response("200 OK", "{}")]).await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /x HTTP/1.1\r\nContent-Length: 11\r\n\r\nhello")
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        stream.write_all(b" world").await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+
+        let request = requests.recv().await.unwrap();
+        assert!(request.head.starts_with("POST /x HTTP/1.1"));
+        assert_eq!(request.header("CONTENT-LENGTH"), Some("11"));
+        assert_eq!(request.body, b"hello world");
+    }
+}
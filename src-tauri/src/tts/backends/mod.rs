@@ -2,17 +2,21 @@
 //!
 //! 新しいバックエンドを追加する場合:
 //! 1. `TtsBackend` トレイトを実装した構造体を作成
-//! 2. `create_backend` ファクトリ関数にマッチアームを追加
-//! 3. `TtsBackendType` に新しいバリアントを追加
+//! 2. `register_backend` でバックエンドIDとファクトリを登録
+//! 3. `TtsConfig::backend` に `TtsBackendType::Custom(<ID>)` を設定
+//!
+//! 棒読みちゃん・VOICEVOX は組み込みバックエンドとして登録済み。
 
 pub mod bouyomichan;
 pub mod voicevox;
 
 pub use bouyomichan::BouyomichanBackend;
-pub use voicevox::VoicevoxBackend;
+pub use voicevox::{Speaker, SpeakerStyle, VoicevoxBackend};
 
-use crate::tts::config::{BouyomichanConfig, TtsBackendType, VoicevoxConfig};
+use crate::tts::config::{TtsBackendType, TtsConfig};
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{LazyLock, PoisonError, RwLock};
 
 /// TTS backend error
 #[derive(Debug, thiserror::Error)]
@@ -28,12 +32,16 @@ pub enum TtsError {
 
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
+
+    /// `TtsBackend::stop` により読み上げが中断された
+    #[error("Speech interrupted")]
+    Interrupted,
 }
 
 /// TTS バックエンドの共通インターフェース
 ///
 /// 新しいバックエンドを追加する際はこのトレイトを実装し、
-/// `create_backend` ファクトリ関数に登録する。
+/// `register_backend` でファクトリを登録する。
 #[async_trait]
 pub trait TtsBackend: Send + Sync {
     /// バックエンドへの接続テスト
@@ -42,55 +50,160 @@ pub trait TtsBackend: Send + Sync {
     async fn speak(&self, text: &str) -> Result<(), TtsError>;
     /// バックエンド名を返す
     fn name(&self) -> &'static str;
+    /// 読み上げ中の発話を中断する
+    ///
+    /// 中断された `speak` は `TtsError::Interrupted` を返す。
+    /// 中断に対応しないバックエンドは何もしない。
+    async fn stop(&self) -> Result<(), TtsError> {
+        Ok(())
+    }
+}
+
+/// 設定からバックエンドインスタンスを生成するファクトリ
+pub type TtsBackendFactory = Box<dyn Fn(&TtsConfig) -> Box<dyn TtsBackend> + Send + Sync>;
+
+/// バックエンドIDとファクトリの登録表（組み込みバックエンドを登録済みの状態で初期化する）
+static BACKEND_REGISTRY: LazyLock<RwLock<HashMap<String, TtsBackendFactory>>> =
+    LazyLock::new(|| {
+        let mut factories: HashMap<String, TtsBackendFactory> = HashMap::new();
+        factories.insert(
+            TtsBackendType::Bouyomichan.id().to_string(),
+            Box::new(|config| Box::new(BouyomichanBackend::new(config.bouyomichan.clone()))),
+        );
+        factories.insert(
+            TtsBackendType::Voicevox.id().to_string(),
+            Box::new(|config| Box::new(VoicevoxBackend::new(config.voicevox.clone()))),
+        );
+        RwLock::new(factories)
+    });
+
+/// バックエンドを登録する
+///
+/// 同じIDが登録済みの場合は置き換える。"none" は「バックエンドなし」を表すため登録できない。
+pub fn register_backend(id: impl Into<String>, factory: TtsBackendFactory) -> Result<(), String> {
+    let id = id.into();
+    if matches!(TtsBackendType::from_id(&id), TtsBackendType::None) {
+        return Err(format!("Reserved TTS backend id: {:?}", id));
+    }
+    BACKEND_REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(id, factory);
+    Ok(())
+}
+
+/// バックエンドIDが登録済みか
+pub fn is_backend_registered(id: &str) -> bool {
+    BACKEND_REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .contains_key(id)
+}
+
+/// 登録済みのバックエンドID一覧（昇順）
+pub fn registered_backend_ids() -> Vec<String> {
+    let mut ids: Vec<String> = BACKEND_REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .keys()
+        .cloned()
+        .collect();
+    ids.sort();
+    ids
 }
 
 /// 設定からバックエンドインスタンスを生成する
+///
+/// `TtsBackendType::None` または未登録のIDの場合は None を返す。
 pub fn create_backend(
     backend_type: &TtsBackendType,
-    bouyomichan: &BouyomichanConfig,
-    voicevox: &VoicevoxConfig,
+    config: &TtsConfig,
 ) -> Option<Box<dyn TtsBackend>> {
-    match backend_type {
-        TtsBackendType::None => None,
-        TtsBackendType::Bouyomichan => Some(Box::new(BouyomichanBackend::new(bouyomichan.clone()))),
-        TtsBackendType::Voicevox => Some(Box::new(VoicevoxBackend::new(voicevox.clone()))),
+    if matches!(backend_type, TtsBackendType::None) {
+        return None;
+    }
+    let registry = BACKEND_REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    match registry.get(backend_type.id()) {
+        Some(factory) => Some(factory(config)),
+        None => {
+            log::warn!("TTS backend is not registered: {}", backend_type.id());
+            None
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tts::config::{BouyomichanConfig, VoicevoxConfig};
+
+    struct DummyBackend;
+
+    #[async_trait]
+    impl TtsBackend for DummyBackend {
+        async fn test_connection(&self) -> Result<bool, TtsError> {
+            Ok(true)
+        }
+        async fn speak(&self, _text: &str) -> Result<(), TtsError> {
+            Ok(())
+        }
+        fn name(&self) -> &'static str {
+            "Dummy"
+        }
+    }
 
     #[test]
     fn create_backend_none_returns_none() {
-        let result = create_backend(
-            &TtsBackendType::None,
-            &BouyomichanConfig::default(),
-            &VoicevoxConfig::default(),
-        );
+        let result = create_backend(&TtsBackendType::None, &TtsConfig::default());
         assert!(result.is_none());
     }
 
     #[test]
     fn create_backend_bouyomichan_returns_some() {
-        let result = create_backend(
-            &TtsBackendType::Bouyomichan,
-            &BouyomichanConfig::default(),
-            &VoicevoxConfig::default(),
-        );
+        let result = create_backend(&TtsBackendType::Bouyomichan, &TtsConfig::default());
         assert!(result.is_some());
         assert_eq!(result.unwrap().name(), "Bouyomichan");
     }
 
     #[test]
     fn create_backend_voicevox_returns_some() {
-        let result = create_backend(
-            &TtsBackendType::Voicevox,
-            &BouyomichanConfig::default(),
-            &VoicevoxConfig::default(),
-        );
+        let result = create_backend(&TtsBackendType::Voicevox, &TtsConfig::default());
         assert!(result.is_some());
         assert_eq!(result.unwrap().name(), "VOICEVOX");
     }
+
+    #[test]
+    fn builtin_backends_are_registered_by_default() {
+        let ids = registered_backend_ids();
+        assert!(ids.contains(&"bouyomichan".to_string()));
+        assert!(ids.contains(&"voicevox".to_string()));
+    }
+
+    #[test]
+    fn create_backend_custom_uses_registered_factory() {
+        register_backend("test-dummy", Box::new(|_| Box::new(DummyBackend))).unwrap();
+
+        assert!(is_backend_registered("test-dummy"));
+        let result = create_backend(
+            &TtsBackendType::Custom("test-dummy".to_string()),
+            &TtsConfig::default(),
+        );
+        assert_eq!(result.unwrap().name(), "Dummy");
+    }
+
+    #[test]
+    fn create_backend_unregistered_custom_returns_none() {
+        let result = create_backend(
+            &TtsBackendType::Custom("test-unregistered".to_string()),
+            &TtsConfig::default(),
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn register_backend_rejects_none_id() {
+        assert!(register_backend("none", Box::new(|_| Box::new(DummyBackend))).is_err());
+        assert!(register_backend("", Box::new(|_| Box::new(DummyBackend))).is_err());
+    }
 }
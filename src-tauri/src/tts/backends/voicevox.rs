@@ -1,15 +1,55 @@
 //! VOICEVOX TTS backend
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use super::{TtsBackend, TtsError};
 use crate::tts::config::VoicevoxConfig;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// VOICEVOX の話者（`/speakers` の要素）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Speaker {
+    pub name: String,
+    pub speaker_uuid: String,
+    pub styles: Vec<SpeakerStyle>,
+}
+
+/// 話者のスタイル。`id` を `VoicevoxConfig::speaker_id` に指定する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerStyle {
+    pub name: String,
+    pub id: i32,
+}
+
+/// 話速・音高・抑揚・音量を audio_query に設定する
+///
+/// 数値として表現できない値（NaN 等）は VOICEVOX の既定値を使う。
+fn apply_audio_parameters(audio_query: &mut serde_json::Value, config: &VoicevoxConfig) {
+    let Some(obj) = audio_query.as_object_mut() else {
+        return;
+    };
+    let params = [
+        ("volumeScale", config.volume_scale, 1.0),
+        ("speedScale", config.speed_scale, 1.0),
+        ("pitchScale", config.pitch_scale, 0.0),
+        ("intonationScale", config.intonation_scale, 1.0),
+    ];
+    for (key, value, default) in params {
+        let number = serde_json::Number::from_f64(value as f64)
+            .unwrap_or_else(|| serde_json::Number::from_f64(default).unwrap());
+        obj.insert(key.to_string(), serde_json::Value::Number(number));
+    }
+}
 
 /// VOICEVOX backend
 pub struct VoicevoxBackend {
     config: VoicevoxConfig,
     client: reqwest::Client,
+    /// `stop` で立て、再生中のループが検知して中断する
+    stop_requested: Arc<AtomicBool>,
 }
 
 impl VoicevoxBackend {
@@ -20,7 +60,28 @@ impl VoicevoxBackend {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { config, client }
+        Self {
+            config,
+            client,
+            stop_requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 話者一覧を取得する（`GET /speakers`）
+    pub async fn list_speakers(&self) -> Result<Vec<Speaker>, TtsError> {
+        let url = format!("http://{}:{}/speakers", self.config.host, self.config.port);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(TtsError::Connection(format!(
+                "speakers failed: status {}",
+                response.status()
+            )));
+        }
+
+        let speakers: Vec<Speaker> = response.json().await?;
+        Ok(speakers)
     }
 
     /// Get audio query
@@ -73,7 +134,9 @@ impl VoicevoxBackend {
     }
 
     /// Play WAV data (blocking)
-    fn play_wav_blocking(wav_bytes: Vec<u8>) -> Result<(), TtsError> {
+    ///
+    /// `stop_requested` が立った時点で再生を止め、`TtsError::Interrupted` を返す。
+    fn play_wav_blocking(wav_bytes: Vec<u8>, stop_requested: &AtomicBool) -> Result<(), TtsError> {
         use rodio::{Decoder, OutputStreamBuilder, Sink};
         use std::io::Cursor;
 
@@ -88,7 +151,13 @@ impl VoicevoxBackend {
             .map_err(|e| TtsError::AudioDecode(format!("Failed to decode WAV: {}", e)))?;
 
         sink.append(source);
-        sink.sleep_until_end();
+        while !sink.empty() {
+            if stop_requested.load(Ordering::SeqCst) {
+                sink.stop();
+                return Err(TtsError::Interrupted);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
 
         Ok(())
     }
@@ -132,47 +201,20 @@ impl TtsBackend for VoicevoxBackend {
         }
 
         log::debug!("Sending to VOICEVOX: {}", text);
+        self.stop_requested.store(false, Ordering::SeqCst);
 
         // 1. Get audio query
         let mut audio_query = self.get_audio_query(text).await?;
 
         // 2. Apply audio parameters
-        if let Some(obj) = audio_query.as_object_mut() {
-            obj.insert(
-                "volumeScale".to_string(),
-                serde_json::Value::Number(
-                    serde_json::Number::from_f64(self.config.volume_scale as f64)
-                        .unwrap_or_else(|| serde_json::Number::from_f64(1.0).unwrap()),
-                ),
-            );
-            obj.insert(
-                "speedScale".to_string(),
-                serde_json::Value::Number(
-                    serde_json::Number::from_f64(self.config.speed_scale as f64)
-                        .unwrap_or_else(|| serde_json::Number::from_f64(1.0).unwrap()),
-                ),
-            );
-            obj.insert(
-                "pitchScale".to_string(),
-                serde_json::Value::Number(
-                    serde_json::Number::from_f64(self.config.pitch_scale as f64)
-                        .unwrap_or_else(|| serde_json::Number::from_f64(0.0).unwrap()),
-                ),
-            );
-            obj.insert(
-                "intonationScale".to_string(),
-                serde_json::Value::Number(
-                    serde_json::Number::from_f64(self.config.intonation_scale as f64)
-                        .unwrap_or_else(|| serde_json::Number::from_f64(1.0).unwrap()),
-                ),
-            );
-        }
+        apply_audio_parameters(&mut audio_query, &self.config);
 
         // 3. Synthesize
         let wav_bytes = self.synthesize(&audio_query).await?;
 
         // 4. Play (spawn_blocking for blocking task)
-        tokio::task::spawn_blocking(move || Self::play_wav_blocking(wav_bytes))
+        let stop_requested = Arc::clone(&self.stop_requested);
+        tokio::task::spawn_blocking(move || Self::play_wav_blocking(wav_bytes, &stop_requested))
             .await
             .map_err(|e| TtsError::AudioOutput(format!("Playback task error: {}", e)))??;
 
@@ -183,4 +225,110 @@ impl TtsBackend for VoicevoxBackend {
     fn name(&self) -> &'static str {
         "VOICEVOX"
     }
+
+    async fn stop(&self) -> Result<(), TtsError> {
+        self.stop_requested.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{json_response, serve};
+
+    #[test]
+    fn apply_audio_parameters_sets_config_values() {
+        let config = VoicevoxConfig {
+            speed_scale: 1.5,
+            pitch_scale: 0.1,
+            intonation_scale: 0.5,
+            volume_scale: 2.0,
+            ..VoicevoxConfig::default()
+        };
+        let mut query = serde_json::json!({ "accent_phrases": [], "speedScale": 1.0 });
+
+        apply_audio_parameters(&mut query, &config);
+
+        assert_eq!(query["speedScale"], 1.5);
+        assert!((query["pitchScale"].as_f64().unwrap() - 0.1).abs() < 1e-6);
+        assert_eq!(query["intonationScale"], 0.5);
+        assert_eq!(query["volumeScale"], 2.0);
+        assert!(query["accent_phrases"].is_array());
+    }
+
+    #[test]
+    fn apply_audio_parameters_defaults_match_voicevox() {
+        let mut query = serde_json::json!({});
+
+        apply_audio_parameters(&mut query, &VoicevoxConfig::default());
+
+        assert_eq!(query["speedScale"], 1.0);
+        assert_eq!(query["pitchScale"], 0.0);
+        assert_eq!(query["intonationScale"], 1.0);
+        assert_eq!(query["volumeScale"], 1.0);
+    }
+
+    #[test]
+    fn apply_audio_parameters_falls_back_for_nan() {
+        let config = VoicevoxConfig {
+            speed_scale: f32::NAN,
+            ..VoicevoxConfig::default()
+        };
+        let mut query = serde_json::json!({});
+
+        apply_audio_parameters(&mut query, &config);
+
+        assert_eq!(query["speedScale"], 1.0);
+    }
+
+    /// 1 リクエストに `body` を JSON で返すモックサーバー
+    async fn spawn_server(status: &'static str, body: &'static str) -> u16 {
+        let (addr, _requests) = serve([json_response(status, body)]).await;
+        addr.port()
+    }
+
+    fn backend_for(port: u16) -> VoicevoxBackend {
+        VoicevoxBackend::new(VoicevoxConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            ..VoicevoxConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn list_speakers_parses_styles() {
+        let port = spawn_server(
+            "200 OK",
+            r#"[{"name":"四国めたん","speaker_uuid":"7ffcb7ce","styles":[{"name":"ノーマル","id":2,"type":"talk"},{"name":"あまあま","id":0}],"version":"0.14.0"}]"#,
+        )
+        .await;
+
+        let speakers = backend_for(port).list_speakers().await.unwrap();
+
+        assert_eq!(speakers.len(), 1);
+        assert_eq!(speakers[0].name, "四国めたん");
+        assert_eq!(
+            speakers[0].styles,
+            vec![
+                SpeakerStyle {
+                    name: "ノーマル".to_string(),
+                    id: 2
+                },
+                SpeakerStyle {
+                    name: "あまあま".to_string(),
+                    id: 0
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_speakers_reports_error_status() {
+        let port = spawn_server("500 Internal Server Error", "{}").await;
+
+        let err = backend_for(port).list_speakers().await.unwrap_err();
+
+        assert!(matches!(err, TtsError::Connection(msg) if msg.contains("500")));
+    }
 }
@@ -12,6 +12,30 @@ pub enum TtsBackendType {
     None,
     Bouyomichan,
     Voicevox,
+    /// `backends::register_backend` で登録したバックエンド（値はバックエンドID）
+    Custom(String),
+}
+
+impl TtsBackendType {
+    /// バックエンドID（"none" / "bouyomichan" / "voicevox" / カスタムバックエンドのID）
+    pub fn id(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Bouyomichan => "bouyomichan",
+            Self::Voicevox => "voicevox",
+            Self::Custom(id) => id,
+        }
+    }
+
+    /// バックエンドIDから変換する（組み込み以外のIDは `Custom`）
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "" | "none" => Self::None,
+            "bouyomichan" => Self::Bouyomichan,
+            "voicevox" => Self::Voicevox,
+            other => Self::Custom(other.to_string()),
+        }
+    }
 }
 
 /// Bouyomichan configuration
@@ -106,6 +130,26 @@ pub struct TtsConfig {
     pub first_comment_prefix: String,
     #[serde(default)]
     pub first_comment_only: bool,
+    /// SuperChat / メンバーシップの受信時に読み上げ中の通常メッセージを中断する
+    #[serde(default)]
+    pub preempt_normal_on_priority: bool,
+    /// アプリ終了時にキューに残ったメッセージを読み上げ終えてから止める（false なら破棄する）
+    #[serde(default)]
+    pub drain_queue_on_exit: bool,
+    /// 記号・絵文字ばかりのメッセージを読み上げない閾値
+    /// （空白以外の文字に占める文字・数字の割合がこれ未満なら読まない。0.0 で無効）
+    #[serde(default = "default_min_alnum_ratio")]
+    pub min_alnum_ratio: f32,
+    /// 読み替え辞書（表記 → 読み）。読み上げ前に語全体が一致する箇所を置き換える
+    #[serde(default)]
+    pub reading_overrides: Vec<(String, String)>,
+    /// 読み替え辞書を投稿者名にも適用する（本文には常に適用する）
+    #[serde(default = "default_true")]
+    pub apply_reading_overrides_to_author: bool,
+}
+
+fn default_min_alnum_ratio() -> f32 {
+    0.3
 }
 
 impl Default for TtsConfig {
@@ -125,6 +169,11 @@ impl Default for TtsConfig {
             first_comment_prefix_enabled: false,
             first_comment_prefix: String::new(),
             first_comment_only: false,
+            preempt_normal_on_priority: false,
+            drain_queue_on_exit: false,
+            min_alnum_ratio: default_min_alnum_ratio(),
+            reading_overrides: Vec::new(),
+            apply_reading_overrides_to_author: true,
         }
     }
 }
@@ -275,4 +324,28 @@ mod tests {
         assert_eq!(config.max_text_length, 200);
         assert!(!config.enabled);
     }
+
+    #[test]
+    fn backend_type_id_roundtrip() {
+        for backend in [
+            TtsBackendType::None,
+            TtsBackendType::Bouyomichan,
+            TtsBackendType::Voicevox,
+            TtsBackendType::Custom("coqui".to_string()),
+        ] {
+            assert_eq!(TtsBackendType::from_id(backend.id()), backend);
+        }
+        assert_eq!(TtsBackendType::from_id(""), TtsBackendType::None);
+    }
+
+    #[test]
+    fn custom_backend_survives_toml_roundtrip() {
+        let config = TtsConfig {
+            backend: TtsBackendType::Custom("coqui".to_string()),
+            ..TtsConfig::default()
+        };
+        let toml_str = toml::to_string_pretty(&config).expect("serialize failed");
+        let loaded: TtsConfig = toml::from_str(&toml_str).expect("deserialize failed");
+        assert_eq!(loaded.backend, TtsBackendType::Custom("coqui".to_string()));
+    }
 }
@@ -12,7 +12,10 @@ use std::collections::VecDeque;
 use std::sync::{Arc, LazyLock};
 use tokio::sync::{Mutex, RwLock, mpsc};
 
-pub use backends::{BouyomichanBackend, TtsBackend, TtsError, VoicevoxBackend};
+pub use backends::{
+    BouyomichanBackend, TtsBackend, TtsBackendFactory, TtsError, VoicevoxBackend,
+    register_backend, registered_backend_ids,
+};
 pub use config::{BouyomichanConfig, TtsBackendType, TtsConfig, VoicevoxConfig};
 pub use process::TtsProcessManager;
 
@@ -49,13 +52,14 @@ pub struct TtsManager {
     queue: Arc<Mutex<VecDeque<TtsQueueItem>>>,
     is_processing: Arc<RwLock<bool>>,
     shutdown_tx: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+    /// 読み上げ中のアイテムの優先度（読み上げていない間は None）
+    speaking_priority: Arc<Mutex<Option<TtsPriority>>>,
 }
 
 impl TtsManager {
     /// Create a new TTS manager
     pub fn new(config: TtsConfig) -> Self {
-        let backend =
-            backends::create_backend(&config.backend, &config.bouyomichan, &config.voicevox);
+        let backend = backends::create_backend(&config.backend, &config);
         Self::with_backend(config, backend)
     }
 
@@ -67,6 +71,7 @@ impl TtsManager {
             queue: Arc::new(Mutex::new(VecDeque::new())),
             is_processing: Arc::new(RwLock::new(false)),
             shutdown_tx: Arc::new(Mutex::new(None)),
+            speaking_priority: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -77,8 +82,7 @@ impl TtsManager {
             log::error!("Failed to save TTS config: {}", e);
         }
 
-        let backend =
-            backends::create_backend(&config.backend, &config.bouyomichan, &config.voicevox);
+        let backend = backends::create_backend(&config.backend, &config);
         *self.config.write().await = config;
         *self.backend.write().await = backend;
     }
@@ -103,8 +107,7 @@ impl TtsManager {
         backend_type: TtsBackendType,
     ) -> Result<bool, TtsError> {
         let config = self.config.read().await;
-        let test_backend =
-            backends::create_backend(&backend_type, &config.bouyomichan, &config.voicevox);
+        let test_backend = backends::create_backend(&backend_type, &config);
 
         match test_backend {
             Some(b) => b.test_connection().await,
@@ -115,17 +118,7 @@ impl TtsManager {
     /// Format text for TTS reading
     pub async fn format_text(&self, item: &TtsQueueItem) -> String {
         let config = self.config.read().await;
-        build_tts_text(
-            item.author_name.as_deref(),
-            item.amount.as_deref(),
-            &item.text,
-            config.read_author_name,
-            config.strip_at_prefix,
-            config.strip_handle_suffix,
-            config.add_honorific,
-            config.read_superchat_amount,
-            config.max_text_length,
-        )
+        build_item_text(&config, item)
     }
 
     /// Add item to queue
@@ -146,6 +139,19 @@ impl TtsManager {
             return;
         }
 
+        // 記号・絵文字ばかりの本文: 通常メッセージはスキップし、SuperChat 等は本文を読まない
+        let mut item = item;
+        if is_mostly_symbols(&sanitize_message(&item.text), config.min_alnum_ratio) {
+            if item.priority == TtsPriority::Normal {
+                log::debug!(
+                    "TTS skipped: mostly symbols (message_id={:?})",
+                    item.message_id
+                );
+                return;
+            }
+            item.text.clear();
+        }
+
         let mut queue = self.queue.lock().await;
 
         // Check queue size limit
@@ -155,6 +161,7 @@ impl TtsManager {
         }
 
         // Insert based on priority (higher priority items go to front)
+        let priority = item.priority;
         let insert_pos = queue
             .iter()
             .position(|q| q.priority < item.priority)
@@ -162,6 +169,27 @@ impl TtsManager {
 
         queue.insert(insert_pos, item);
         log::debug!("TTS queue size: {}", queue.len());
+        drop(queue);
+
+        if config.preempt_normal_on_priority && priority > TtsPriority::Normal {
+            self.preempt_normal().await;
+        }
+    }
+
+    /// 読み上げ中の通常メッセージを中断する
+    ///
+    /// 中断されたアイテムは処理ループで再キューされる（破棄しない）。
+    async fn preempt_normal(&self) {
+        if *self.speaking_priority.lock().await != Some(TtsPriority::Normal) {
+            return;
+        }
+        let backend = self.backend.read().await;
+        if let Some(b) = backend.as_ref() {
+            log::debug!("TTS preempting normal message for priority message");
+            if let Err(e) = b.stop().await {
+                log::warn!("TTS stop error: {}", e);
+            }
+        }
     }
 
     /// Speak text directly (bypasses queue)
@@ -190,6 +218,7 @@ impl TtsManager {
         let backend = Arc::clone(&self.backend);
         let config = Arc::clone(&self.config);
         let is_processing = Arc::clone(&self.is_processing);
+        let speaking_priority = Arc::clone(&self.speaking_priority);
 
         tokio::spawn(async move {
             log::info!("TTS queue processing started");
@@ -202,26 +231,21 @@ impl TtsManager {
                     }
                     _ = async {
                         // Get next item from queue
+                        // 読み上げ中の印はキューのロック内で立てる（終了時の待機が取り出し直後を見逃さないように）
                         let item = {
                             let mut q = queue.lock().await;
-                            q.pop_front()
+                            let item = q.pop_front();
+                            if let Some(item) = &item {
+                                *speaking_priority.lock().await = Some(item.priority);
+                            }
+                            item
                         };
 
                         if let Some(item) = item {
                             // Format text using shared helper
                             let text = {
                                 let cfg = config.read().await;
-                                let base = build_tts_text(
-                                    item.author_name.as_deref(),
-                                    item.amount.as_deref(),
-                                    &item.text,
-                                    cfg.read_author_name,
-                                    cfg.strip_at_prefix,
-                                    cfg.strip_handle_suffix,
-                                    cfg.add_honorific,
-                                    cfg.read_superchat_amount,
-                                    cfg.max_text_length,
-                                );
+                                let base = build_item_text(&cfg, &item);
                                 // 初回コメントプレフィックス
                                 match build_first_comment_prefix(
                                     cfg.first_comment_prefix_enabled,
@@ -236,13 +260,28 @@ impl TtsManager {
                             // Speak
                             let b = backend.read().await;
                             if let Some(ref backend) = *b {
-                                if let Err(e) = backend.speak(&text).await {
-                                    log::error!(
-                                        "TTS speak error (message_id={:?}): {}",
-                                        item.message_id,
-                                        e
-                                    );
+                                let result = backend.speak(&text).await;
+                                *speaking_priority.lock().await = None;
+
+                                match result {
+                                    Ok(()) => {}
+                                    Err(TtsError::Interrupted) => {
+                                        log::debug!(
+                                            "TTS interrupted, requeueing (message_id={:?})",
+                                            item.message_id
+                                        );
+                                        requeue_interrupted(&mut queue.lock().await, item);
+                                    }
+                                    Err(e) => {
+                                        log::error!(
+                                            "TTS speak error (message_id={:?}): {}",
+                                            item.message_id,
+                                            e
+                                        );
+                                    }
                                 }
+                            } else {
+                                *speaking_priority.lock().await = None;
                             }
                         } else {
                             // No items, wait a bit
@@ -264,6 +303,33 @@ impl TtsManager {
         }
     }
 
+    /// アプリ終了時に読み上げキューを止める
+    ///
+    /// `drain` が true なら残りのアイテムを読み上げ終えるまで待つ（処理が止まっている場合は待たない）。
+    /// false なら残りを破棄し、読み上げ中のものも中断する。
+    pub async fn shutdown(&self, drain: bool) {
+        if drain && self.is_processing().await {
+            while !self.is_idle().await {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            }
+        } else {
+            self.clear_queue().await;
+            let backend = self.backend.read().await;
+            if let Some(b) = backend.as_ref() {
+                if let Err(e) = b.stop().await {
+                    log::warn!("TTS stop error: {}", e);
+                }
+            }
+        }
+        self.stop_processing().await;
+    }
+
+    /// キューが空で読み上げ中のアイテムもないか
+    async fn is_idle(&self) -> bool {
+        let queue = self.queue.lock().await;
+        queue.is_empty() && self.speaking_priority.lock().await.is_none()
+    }
+
     /// Clear the queue
     pub async fn clear_queue(&self) {
         self.queue.lock().await.clear();
@@ -294,6 +360,15 @@ impl Default for TtsManager {
     }
 }
 
+/// 中断されたアイテムを同じ優先度のアイテムより前に戻す
+pub(crate) fn requeue_interrupted(queue: &mut VecDeque<TtsQueueItem>, item: TtsQueueItem) {
+    let insert_pos = queue
+        .iter()
+        .position(|q| q.priority <= item.priority)
+        .unwrap_or(queue.len());
+    queue.insert(insert_pos, item);
+}
+
 // ============================================================================
 // Pure helper functions for TTS text generation (04_tts.md)
 // ============================================================================
@@ -384,15 +459,146 @@ pub(crate) fn sanitize_message(text: &str) -> String {
     result.trim().to_string()
 }
 
-/// Truncate text to max_length (by chars), appending "、以下省略" if truncated
+/// 切り詰めたときに末尾に付けて読み上げる文言
+const TRUNCATION_SUFFIX: &str = "…以下略";
+
+/// 文の終わり（この文字の直後で切る）
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '。' | '．' | '.' | '！' | '!' | '？' | '?')
+}
+
+/// 語の区切り（この文字の直前で切る）
+fn is_word_break(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '、' | '，' | ',')
+}
+
+/// Truncate text to max_length (by chars), appending "…以下略" if truncated
+///
+/// 仕様 (04_tts.md: テキスト切り詰め):
+/// 先頭 `max_length` 文字のうち、後半にある最後の文の終わり → 語の区切りの順に切る位置を探す。
+/// どちらもなければ（区切りのない日本語の長文など）`max_length` 文字で切る。
 pub(crate) fn truncate_text(text: &str, max_length: usize) -> String {
-    if text.chars().count() > max_length {
-        let mut truncated: String = text.chars().take(max_length).collect();
-        truncated.push_str("、以下省略");
-        truncated
-    } else {
-        text.to_string()
+    let Some((end, next)) = text.char_indices().nth(max_length) else {
+        return text.to_string();
+    };
+    let head = &text[..end];
+    // 区切りが前半にしかなければ読み上げが短くなりすぎるため使わない
+    let long_enough = |cut: usize| head[..cut].chars().count() >= max_length / 2;
+
+    let sentence_cut = head
+        .char_indices()
+        .filter(|(_, c)| is_sentence_end(*c))
+        .map(|(i, c)| i + c.len_utf8())
+        .next_back()
+        .filter(|&cut| long_enough(cut));
+    let word_cut = || {
+        if is_word_break(next) {
+            return Some(end);
+        }
+        head.char_indices()
+            .filter(|(_, c)| is_word_break(*c))
+            .map(|(i, _)| i)
+            .next_back()
+            .filter(|&cut| long_enough(cut))
+    };
+    let cut = sentence_cut.or_else(word_cut).unwrap_or(end);
+
+    format!(
+        "{}{}",
+        head[..cut].trim_end_matches(is_word_break),
+        TRUNCATION_SUFFIX
+    )
+}
+
+/// 記号・絵文字ばかりのメッセージか（アスキーアート・絵文字の連投など）
+///
+/// 空白以外の文字に占める文字・数字（かな・漢字を含む）の割合が `min_alnum_ratio` 未満なら true。
+/// `min_alnum_ratio` が 0 以下、または空白以外の文字がない場合は false。
+pub(crate) fn is_mostly_symbols(text: &str, min_alnum_ratio: f32) -> bool {
+    if min_alnum_ratio <= 0.0 {
+        return false;
     }
+    let (alnum, total) = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .fold((0usize, 0usize), |(alnum, total), c| {
+            (alnum + usize::from(c.is_alphanumeric()), total + 1)
+        });
+    total > 0 && (alnum as f32) < min_alnum_ratio * total as f32
+}
+
+/// 読み替え辞書（表記 → 読み）を適用する
+///
+/// 仕様 (04_tts.md: 読み替え辞書):
+/// - 英数字は大文字・小文字を区別せず、前後が英数字・`_` でない位置（語全体）だけを置き換える
+///   （`liscov` は `liscovery` の中では置き換えない）
+/// - かな・漢字など英数字以外で始まる・終わる表記は、その側の境界を問わない
+/// - 同じ位置で複数の表記が一致する場合は長い方を優先する（置き換えた読みは再び置き換えない）
+pub(crate) fn apply_reading_overrides(text: &str, overrides: &[(String, String)]) -> String {
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    // ASCII の大文字・小文字の変換はバイト位置を変えない
+    let mut ordered: Vec<(String, &str, bool, bool)> = overrides
+        .iter()
+        .filter(|(from, _)| !from.is_empty())
+        .map(|(from, reading)| {
+            (
+                from.to_ascii_lowercase(),
+                reading.as_str(),
+                from.starts_with(is_word_char),
+                from.ends_with(is_word_char),
+            )
+        })
+        .collect();
+    ordered.sort_by_key(|(from, ..)| std::cmp::Reverse(from.chars().count()));
+
+    // 置き換えた読みを再び置き換えないよう、先頭から1回だけ走査する
+    let haystack = text.to_ascii_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut pos = 0;
+    while let Some(c) = text[pos..].chars().next() {
+        let matched = ordered.iter().find(|(from, _, check_start, check_end)| {
+            let end = pos + from.len();
+            haystack[pos..].starts_with(from.as_str())
+                && (!check_start || !text[..pos].ends_with(is_word_char))
+                && (!check_end || !text[end..].starts_with(is_word_char))
+        });
+        match matched {
+            Some((from, reading, ..)) => {
+                result.push_str(reading);
+                pos += from.len();
+            }
+            None => {
+                result.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+    result
+}
+
+/// 設定に従ってキューアイテムの読み上げテキストを作る
+///
+/// 読み替え辞書は本文に適用し、投稿者名には `apply_reading_overrides_to_author` の場合のみ適用する。
+pub(crate) fn build_item_text(config: &TtsConfig, item: &TtsQueueItem) -> String {
+    let author_name = item.author_name.as_deref().map(|name| {
+        if config.apply_reading_overrides_to_author {
+            apply_reading_overrides(name, &config.reading_overrides)
+        } else {
+            name.to_string()
+        }
+    });
+    let text = apply_reading_overrides(&item.text, &config.reading_overrides);
+    build_tts_text(
+        author_name.as_deref(),
+        item.amount.as_deref(),
+        &text,
+        config.read_author_name,
+        config.strip_at_prefix,
+        config.strip_handle_suffix,
+        config.add_honorific,
+        config.read_superchat_amount,
+        config.max_text_length,
+    )
 }
 
 /// Build complete TTS text from parts
@@ -428,7 +634,9 @@ pub(crate) fn build_tts_text(
     }
 
     let sanitized = sanitize_message(message);
-    parts.push(truncate_text(&sanitized, max_text_length));
+    if !sanitized.is_empty() {
+        parts.push(truncate_text(&sanitized, max_text_length));
+    }
 
     parts.join("、")
 }
@@ -557,11 +765,126 @@ mod tests {
 
     #[test]
     fn truncate_exceeding_limit() {
+        // 区切りがなければ max_length 文字で切る
         let text: String = "あ".repeat(201);
-        let expected: String = "あ".repeat(200) + "、以下省略";
+        let expected: String = "あ".repeat(200) + "…以下略";
         assert_eq!(truncate_text(&text, 200), expected);
     }
 
+    #[test]
+    fn truncate_long_paragraph_at_sentence_boundary() {
+        let text = "今日の配信はここまでです。来てくれてありがとう。\
+                    次回は土曜日の夜九時から新しいゲームをやる予定なので楽しみにしていてください";
+        assert_eq!(
+            truncate_text(text, 30),
+            "今日の配信はここまでです。来てくれてありがとう。…以下略"
+        );
+    }
+
+    #[test]
+    fn truncate_at_word_boundary_without_sentence_end() {
+        let text = "this stream has been really fun and I hope you come back tomorrow";
+        assert_eq!(
+            truncate_text(text, 30),
+            "this stream has been really…以下略"
+        );
+    }
+
+    #[test]
+    fn truncate_ignores_boundary_in_first_half() {
+        // 区切りが前半にしかない場合は max_length 文字で切る
+        let text = format!("はい。{}", "あ".repeat(30));
+        assert_eq!(
+            truncate_text(&text, 20),
+            format!("はい。{}…以下略", "あ".repeat(17))
+        );
+    }
+
+    // ========================================================================
+    // apply_reading_overrides (04_tts.md: 読み替え辞書)
+    // ========================================================================
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reading_override_replaces_whole_token_only() {
+        let dict = overrides(&[("liscov", "リスコフ")]);
+        assert_eq!(
+            apply_reading_overrides("liscov is not liscovery or myliscov", &dict),
+            "リスコフ is not liscovery or myliscov"
+        );
+    }
+
+    #[test]
+    fn reading_override_ignores_ascii_case_and_japanese_neighbors() {
+        let dict = overrides(&[("liscov", "リスコフ")]);
+        assert_eq!(
+            apply_reading_overrides("Liscovで見てる、LISCOV最高", &dict),
+            "リスコフで見てる、リスコフ最高"
+        );
+    }
+
+    #[test]
+    fn reading_override_prefers_longer_written_form() {
+        let dict = overrides(&[("w", "わら"), ("www", "大草原"), ("草", "くさ")]);
+        assert_eq!(
+            apply_reading_overrides("www 草生える w", &dict),
+            "大草原 くさ生える わら"
+        );
+    }
+
+    #[test]
+    fn reading_override_applies_to_author_only_when_enabled() {
+        let item = TtsQueueItem {
+            text: "liscov使ってます".to_string(),
+            priority: TtsPriority::Normal,
+            author_name: Some("@liscov-abc".to_string()),
+            amount: None,
+            in_stream_comment_count: None,
+            message_id: None,
+        };
+        let mut config = TtsConfig {
+            reading_overrides: overrides(&[("liscov", "リスコフ")]),
+            apply_reading_overrides_to_author: true,
+            ..TtsConfig::default()
+        };
+        assert_eq!(
+            build_item_text(&config, &item),
+            "リスコフさん、リスコフ使ってます"
+        );
+
+        config.apply_reading_overrides_to_author = false;
+        assert_eq!(
+            build_item_text(&config, &item),
+            "liscovさん、リスコフ使ってます"
+        );
+    }
+
+    // ========================================================================
+    // is_mostly_symbols (04_tts.md: 記号ばかりのメッセージ)
+    // ========================================================================
+
+    #[test]
+    fn mostly_symbols_detects_emoji_flood_and_ascii_art() {
+        assert!(is_mostly_symbols("😂😂😂😂😂😂😂😂😂😂", 0.3));
+        assert!(is_mostly_symbols("草😂😂😂😂😂😂😂😂😂", 0.3));
+        assert!(is_mostly_symbols("(´・ω・`) ＿|￣|○ ///", 0.3));
+    }
+
+    #[test]
+    fn mostly_symbols_keeps_normal_text() {
+        assert!(!is_mostly_symbols("こんにちは！😊", 0.3));
+        assert!(!is_mostly_symbols("ｗｗｗｗｗ", 0.3));
+        assert!(!is_mostly_symbols("", 0.3));
+        // 0 で無効
+        assert!(!is_mostly_symbols("😂😂😂", 0.0));
+    }
+
     #[test]
     fn truncate_empty() {
         assert_eq!(truncate_text("", 200), "");
@@ -750,7 +1073,7 @@ mod tests {
         // spec: サニタイズ後のテキストに対してmax_text_lengthが適用される
         let long_msg = format!("https://example.com/long {}", "あ".repeat(201));
         let result = build_tts_text(None, None, &long_msg, true, true, true, true, true, 200);
-        let expected = format!("{}、以下省略", "あ".repeat(200));
+        let expected = format!("{}…以下略", "あ".repeat(200));
         assert_eq!(result, expected);
     }
 
@@ -1010,6 +1333,39 @@ mod tests {
         assert_eq!(manager.queue_size().await, 1);
     }
 
+    #[tokio::test]
+    async fn enqueue_skips_emoji_flood() {
+        let manager = TtsManager::new(test_config_with_first_comment(false));
+        let item = TtsQueueItem {
+            text: "😂".repeat(40),
+            priority: TtsPriority::Normal,
+            author_name: Some("テスター".to_string()),
+            amount: None,
+            in_stream_comment_count: Some(3),
+            message_id: None,
+        };
+        manager.enqueue(item).await;
+        assert_eq!(manager.queue_size().await, 0);
+    }
+
+    #[tokio::test]
+    async fn enqueue_keeps_superchat_with_emoji_flood_without_body() {
+        // SuperChat は本文だけ読まない（投稿者名と金額は読み上げる）
+        let manager = TtsManager::new(test_config_with_first_comment(false));
+        let item = TtsQueueItem {
+            text: "😂".repeat(40),
+            priority: TtsPriority::SuperChat,
+            author_name: Some("テスター".to_string()),
+            amount: Some("¥500".to_string()),
+            in_stream_comment_count: Some(3),
+            message_id: None,
+        };
+        manager.enqueue(item).await;
+        assert_eq!(manager.queue_size().await, 1);
+        let queued = manager.queue.lock().await.front().cloned().unwrap();
+        assert_eq!(manager.format_text(&queued).await, "テスターさん、¥500の");
+    }
+
     #[tokio::test]
     async fn enqueue_does_nothing_when_disabled() {
         // spec: enabled=false の場合、メッセージはキューに入らない
@@ -1522,6 +1878,49 @@ mod tests {
         assert!(!manager.is_processing().await);
     }
 
+    #[tokio::test]
+    async fn shutdown_without_drain_discards_queue() {
+        // spec: drain_queue_on_exit=false なら残りのアイテムを読み上げずに止める
+        let manager = TtsManager::with_backend(
+            TtsConfig {
+                enabled: true,
+                ..TtsConfig::default()
+            },
+            Some(Box::new(MockTtsBackend::connected())),
+        );
+        manager
+            .enqueue(test_item("残り", TtsPriority::Normal, None))
+            .await;
+        assert_eq!(manager.queue_size().await, 1);
+        manager.shutdown(false).await;
+        assert_eq!(manager.queue_size().await, 0);
+    }
+
+    #[tokio::test]
+    async fn shutdown_with_drain_speaks_remaining_items() {
+        // spec: drain_queue_on_exit=true なら残りのアイテムを読み上げ終えてから止める
+        let mock = MockTtsBackend::connected();
+        let calls = Arc::clone(&mock.speak_calls);
+        let manager = TtsManager::with_backend(
+            TtsConfig {
+                enabled: true,
+                read_author_name: false,
+                ..TtsConfig::default()
+            },
+            Some(Box::new(mock)),
+        );
+        manager
+            .enqueue(test_item("一つ目", TtsPriority::Normal, None))
+            .await;
+        manager
+            .enqueue(test_item("二つ目", TtsPriority::Normal, None))
+            .await;
+        manager.start_processing().await;
+        manager.shutdown(true).await;
+        assert_eq!(manager.queue_size().await, 0);
+        assert_eq!(calls.lock().await.len(), 2);
+    }
+
     // ========================================================================
     // 初回コメント機能 end-to-end テスト (AC-1〜AC-6)
     //
@@ -1769,4 +2168,112 @@ mod tests {
             spoken[2]
         );
     }
+
+    // ========================================================================
+    // 優先メッセージによる割り込み (04_tts.md: preempt_normal_on_priority)
+    // ========================================================================
+
+    #[test]
+    fn requeue_interrupted_goes_before_same_priority() {
+        let mut queue = VecDeque::from(vec![
+            test_item("sc", TtsPriority::SuperChat, None),
+            test_item("n1", TtsPriority::Normal, None),
+            test_item("n2", TtsPriority::Normal, None),
+        ]);
+        requeue_interrupted(&mut queue, test_item("interrupted", TtsPriority::Normal, None));
+        let texts: Vec<&str> = queue.iter().map(|q| q.text.as_str()).collect();
+        assert_eq!(texts, vec!["sc", "interrupted", "n1", "n2"]);
+    }
+
+    /// "長文" を含むテキストは stop() されるまで（最大 300ms）読み上げ続けるモック
+    struct InterruptibleMockBackend {
+        speak_calls: Arc<Mutex<Vec<String>>>,
+        stop_notify: Arc<tokio::sync::Notify>,
+        interrupted: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl TtsBackend for InterruptibleMockBackend {
+        async fn test_connection(&self) -> Result<bool, backends::TtsError> {
+            Ok(true)
+        }
+        async fn speak(&self, text: &str) -> Result<(), backends::TtsError> {
+            use std::sync::atomic::Ordering;
+            self.speak_calls.lock().await.push(text.to_string());
+            if !text.contains("長文") || self.interrupted.load(Ordering::SeqCst) {
+                return Ok(());
+            }
+            tokio::select! {
+                _ = self.stop_notify.notified() => {
+                    self.interrupted.store(true, Ordering::SeqCst);
+                    Err(backends::TtsError::Interrupted)
+                }
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(300)) => Ok(()),
+            }
+        }
+        fn name(&self) -> &'static str {
+            "InterruptibleMock"
+        }
+        async fn stop(&self) -> Result<(), backends::TtsError> {
+            self.stop_notify.notify_one();
+            Ok(())
+        }
+    }
+
+    /// 通常メッセージの読み上げ中に SuperChat を enqueue し、speak_calls を返す
+    async fn run_preemption_scenario(preempt: bool) -> Vec<String> {
+        let mock = InterruptibleMockBackend {
+            speak_calls: Arc::new(Mutex::new(Vec::new())),
+            stop_notify: Arc::new(tokio::sync::Notify::new()),
+            interrupted: std::sync::atomic::AtomicBool::new(false),
+        };
+        let calls = Arc::clone(&mock.speak_calls);
+        let manager = TtsManager::with_backend(
+            TtsConfig {
+                enabled: true,
+                read_author_name: false,
+                preempt_normal_on_priority: preempt,
+                ..TtsConfig::default()
+            },
+            Some(Box::new(mock)),
+        );
+
+        manager
+            .enqueue(test_item("長文", TtsPriority::Normal, None))
+            .await;
+        manager.start_processing().await;
+        // 通常メッセージの読み上げ開始を待つ
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while calls.lock().await.is_empty() && std::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        manager
+            .enqueue(test_item("スパチャ", TtsPriority::SuperChat, None))
+            .await;
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while (manager.queue_size().await > 0 || calls.lock().await.len() < 2)
+            && std::time::Instant::now() < deadline
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        // 読み上げ中のアイテム（最大 300ms）の完了を待つ
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        manager.stop_processing().await;
+        calls.lock().await.clone()
+    }
+
+    #[tokio::test]
+    async fn preempt_interrupts_normal_and_requeues_it() {
+        // spec: 割り込み有効 → 通常メッセージを中断し、SuperChat の後に読み直す
+        let spoken = run_preemption_scenario(true).await;
+        assert_eq!(spoken, vec!["長文", "スパチャ", "長文"]);
+    }
+
+    #[tokio::test]
+    async fn no_preempt_waits_for_normal_to_finish() {
+        // spec: 割り込み無効 → 通常メッセージの読み上げ完了後に SuperChat を読み上げる
+        let spoken = run_preemption_scenario(false).await;
+        assert_eq!(spoken, vec!["長文", "スパチャ"]);
+    }
 }
@@ -33,7 +33,7 @@ impl TtsProcessManager {
         let paths = match backend {
             TtsBackendType::Bouyomichan => Self::bouyomichan_search_paths(),
             TtsBackendType::Voicevox => Self::voicevox_search_paths(),
-            TtsBackendType::None => return None,
+            TtsBackendType::None | TtsBackendType::Custom(_) => return None,
         };
 
         for path in paths {
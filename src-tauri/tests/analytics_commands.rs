@@ -6,6 +6,9 @@
 mod common;
 
 use app_lib::commands::analytics::RevenueAnalytics;
+use app_lib::commands::config::{Config, ConfigState};
+use app_lib::core::continuation_store::ContinuationStore;
+use app_lib::core::transform::MessageTransforms;
 use app_lib::core::{ChatMessage, MessageType};
 use app_lib::state::AppState;
 use common::{invoke_no_args, invoke_with_args};
@@ -45,13 +48,26 @@ fn build_app_state(messages: Vec<ChatMessage>) -> AppState {
         tts_process_manager: Arc::new(app_lib::tts::TtsProcessManager::new()),
         next_connection_id: Arc::new(AtomicU64::new(0)),
         connections: Arc::new(RwLock::new(HashMap::new())),
+        continuation_store: Arc::new(ContinuationStore::new(None)),
+        message_transforms: Arc::new(std::sync::RwLock::new(MessageTransforms::default())),
     }
 }
 
 /// Tauri テストアプリを構築するヘルパー
 fn build_test_app(app_state: AppState) -> tauri::App<tauri::test::MockRuntime> {
+    build_test_app_with_config(app_state, Config::default())
+}
+
+/// 設定を指定して Tauri テストアプリを構築するヘルパー
+fn build_test_app_with_config(
+    app_state: AppState,
+    config: Config,
+) -> tauri::App<tauri::test::MockRuntime> {
+    let config_state = ConfigState::new();
+    config_state.set(config);
     mock_builder()
         .manage(app_state)
+        .manage(config_state)
         .invoke_handler(tauri::generate_handler![
             app_lib::commands::analytics::get_revenue_analytics,
             app_lib::commands::analytics::export_current_messages,
@@ -138,6 +154,54 @@ async fn get_revenue_analytics_with_superchat_messages() {
 
     // 仕様: 2人の送信者が top_contributors に含まれる
     assert_eq!(analytics.top_contributors.len(), 2);
+
+    // 仕様: レート未設定のため USD は基準通貨（JPY）に換算されない
+    let total = analytics.total.expect("total は常に返る");
+    assert_eq!(total.currency, "JPY");
+    assert_eq!(total.amount, 0.0);
+    assert_eq!(total.unconverted_currencies, vec!["USD".to_string()]);
+}
+
+#[tokio::test]
+async fn get_revenue_analytics_total_uses_configured_rates() {
+    // 仕様: 設定の currency.rates で基準通貨に換算した合計を返す
+    let messages = vec![
+        make_chat_message(
+            "sc1",
+            "UserA",
+            "UC_a",
+            MessageType::SuperChat {
+                amount: "$10.00".to_string(),
+            },
+        ),
+        make_chat_message(
+            "sc2",
+            "UserB",
+            "UC_b",
+            MessageType::SuperChat {
+                amount: "¥500".to_string(),
+            },
+        ),
+    ];
+    let mut config = Config::default();
+    config.currency.rates.insert("USD".to_string(), 150.0);
+
+    let app = build_test_app_with_config(build_app_state(messages), config);
+    let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+        .build()
+        .unwrap();
+
+    let response = get_ipc_response(&webview, invoke_no_args("get_revenue_analytics"));
+    let analytics: RevenueAnalytics = response
+        .expect("get_revenue_analytics は成功するべき")
+        .deserialize()
+        .expect("デシリアライズ失敗");
+
+    let total = analytics.total.expect("total は常に返る");
+    assert_eq!(total.currency, "JPY");
+    assert_eq!(total.amount, 2000.0);
+    assert_eq!(total.converted_count, 2);
+    assert!(total.unconverted_currencies.is_empty());
 }
 
 #[tokio::test]
@@ -24,6 +24,7 @@ fn build_test_app() -> tauri::App<tauri::test::MockRuntime> {
         let config = Config {
             storage: StorageConfig {
                 mode: StorageMode::Fallback,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -41,11 +42,33 @@ fn build_test_app() -> tauri::App<tauri::test::MockRuntime> {
             app_lib::commands::auth::auth_delete_credentials,
             app_lib::commands::auth::auth_validate_credentials,
             app_lib::commands::auth::auth_get_status,
+            app_lib::commands::auth::auth_list_profiles,
+            app_lib::commands::auth::auth_save_profile,
+            app_lib::commands::auth::auth_switch_profile,
+            app_lib::commands::auth::auth_delete_profile,
         ])
         .build(mock_context(noop_assets()))
         .expect("テスト用アプリのビルドに失敗")
 }
 
+/// テストで作成する名前付きプロファイル
+const TEST_PROFILES: [&str; 2] = ["main", "sub"];
+
+/// テストで作成したファイル（credentials・プロファイル・config）を削除する
+fn remove_test_files() {
+    if let Ok(cred_path) = app_lib::paths::credentials_path() {
+        let _ = fs::remove_file(&cred_path);
+    }
+    for name in TEST_PROFILES {
+        if let Ok(path) = app_lib::paths::profile_credentials_path(name) {
+            let _ = fs::remove_file(&path);
+        }
+    }
+    if let Ok(config_path) = app_lib::paths::config_path() {
+        let _ = fs::remove_file(&config_path);
+    }
+}
+
 /// テスト用の app_name を設定し、テスト後にクリーンアップするガード。
 /// #[serial] と組み合わせて環境変数競合と CREDENTIALS_CACHE 汚染を防ぐ。
 struct AppNameGuard;
@@ -56,9 +79,7 @@ impl AppNameGuard {
         unsafe { std::env::set_var("LISCOV_APP_NAME", "liscov-test-auth-cmd") };
         // テスト開始前にインメモリキャッシュと credentials ファイルを削除してクリーンな状態にする
         app_lib::commands::auth::clear_credentials_cache_for_test();
-        if let Ok(cred_path) = app_lib::paths::credentials_path() {
-            let _ = fs::remove_file(&cred_path);
-        }
+        remove_test_files();
         Self
     }
 }
@@ -66,9 +87,7 @@ impl AppNameGuard {
 impl Drop for AppNameGuard {
     fn drop(&mut self) {
         // テスト後に credentials ファイルをクリーンアップ
-        if let Ok(cred_path) = app_lib::paths::credentials_path() {
-            let _ = fs::remove_file(&cred_path);
-        }
+        remove_test_files();
         // SAFETY: テスト環境でのみ実行
         unsafe { std::env::remove_var("LISCOV_APP_NAME") };
     }
@@ -355,3 +374,106 @@ async fn auth_validate_credentials_with_valid_credentials_returns_true() {
     let is_valid: bool = validate_resp.unwrap().deserialize().unwrap();
     assert!(is_valid, "保存後は validate が true を返すべき");
 }
+
+// ============================================================================
+// アカウントプロファイル テスト
+// ============================================================================
+
+#[tokio::test]
+#[serial]
+async fn auth_profiles_save_two_accounts_and_switch_between_them() {
+    // 仕様: ログイン中の認証情報を名前付きプロファイルに保存し、使用するプロファイルを切り替えられる
+    let _guard = AppNameGuard::new();
+
+    let app = build_test_app();
+    let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+        .build()
+        .unwrap();
+
+    // 2つのアカウントでログインし、それぞれプロファイルに保存
+    for (name, raw) in [
+        ("main", "SID=s1; HSID=h1; SSID=ss1; APISID=a1; SAPISID=main"),
+        ("sub", "SID=s2; HSID=h2; SSID=ss2; APISID=a2; SAPISID=sub"),
+    ] {
+        let save_resp = get_ipc_response(
+            &webview,
+            invoke_with_args(
+                "auth_save_raw_cookies",
+                serde_json::json!({ "rawCookies": raw }),
+            ),
+        );
+        assert!(save_resp.is_ok(), "auth_save_raw_cookies should succeed");
+        let profile_resp = get_ipc_response(
+            &webview,
+            invoke_with_args("auth_save_profile", serde_json::json!({ "name": name })),
+        );
+        assert!(
+            profile_resp.is_ok(),
+            "auth_save_profile should succeed: {:?}",
+            profile_resp.err()
+        );
+        let path = app_lib::paths::profile_credentials_path(name).unwrap();
+        assert!(path.exists(), "profile file should be created for {}", name);
+    }
+
+    let list: serde_json::Value = get_ipc_response(&webview, invoke_no_args("auth_list_profiles"))
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(list["profiles"], serde_json::json!(["main", "sub"]));
+    assert_eq!(list["active_profile"], serde_json::Value::Null);
+
+    // main に切り替えて削除すると、main のファイルだけが消える
+    let switch_resp = get_ipc_response(
+        &webview,
+        invoke_with_args("auth_switch_profile", serde_json::json!({ "name": "main" })),
+    );
+    assert!(switch_resp.is_ok(), "auth_switch_profile should succeed");
+    let load_resp = get_ipc_response(&webview, invoke_no_args("auth_load_credentials"));
+    assert!(load_resp.is_ok(), "main profile should be loadable");
+
+    let delete_resp = get_ipc_response(&webview, invoke_no_args("auth_delete_credentials"));
+    assert!(delete_resp.is_ok());
+    assert!(
+        !app_lib::paths::profile_credentials_path("main")
+            .unwrap()
+            .exists()
+    );
+    assert!(
+        app_lib::paths::profile_credentials_path("sub")
+            .unwrap()
+            .exists()
+    );
+    assert!(app_lib::paths::credentials_path().unwrap().exists());
+    let load_resp = get_ipc_response(&webview, invoke_no_args("auth_load_credentials"));
+    assert!(
+        load_resp.is_err(),
+        "main profile has no credentials after delete"
+    );
+
+    // sub に切り替えると sub の認証情報が使われる
+    let switch_resp = get_ipc_response(
+        &webview,
+        invoke_with_args("auth_switch_profile", serde_json::json!({ "name": "sub" })),
+    );
+    assert!(switch_resp.is_ok());
+    let load_resp = get_ipc_response(&webview, invoke_no_args("auth_load_credentials"));
+    assert!(load_resp.is_ok(), "sub profile should be loadable");
+
+    let list: serde_json::Value = get_ipc_response(&webview, invoke_no_args("auth_list_profiles"))
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(list["active_profile"], "sub");
+
+    // 未登録のプロファイルには切り替えられない
+    let err = get_ipc_response(
+        &webview,
+        invoke_with_args(
+            "auth_switch_profile",
+            serde_json::json!({ "name": "other" }),
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(err["kind"], "ProfileNotFound");
+}
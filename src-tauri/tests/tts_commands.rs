@@ -6,6 +6,8 @@
 mod common;
 
 use app_lib::commands::tts::{TtsConfigDto, TtsStatus};
+use app_lib::core::continuation_store::ContinuationStore;
+use app_lib::core::transform::MessageTransforms;
 use app_lib::state::AppState;
 use app_lib::tts::backends::TtsError;
 use app_lib::tts::{TtsBackend, TtsConfig, TtsManager, TtsProcessManager};
@@ -84,6 +86,8 @@ fn build_app_state(tts_manager: TtsManager) -> AppState {
         tts_process_manager: Arc::new(TtsProcessManager::new()),
         next_connection_id: Arc::new(AtomicU64::new(0)),
         connections: Arc::new(RwLock::new(HashMap::new())),
+        continuation_store: Arc::new(ContinuationStore::new(None)),
+        message_transforms: Arc::new(std::sync::RwLock::new(MessageTransforms::default())),
     }
 }
 
@@ -608,6 +608,7 @@ async fn test_chatmessage_json_format_matches_spec() {
             color: None,
             is_moderator: false,
             is_verified: true,
+            is_owner: false,
             superchat_colors: None,
         }),
         is_member: true,
@@ -0,0 +1,109 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+import { render, fireEvent } from '@testing-library/svelte';
+import ArchiveSearch from './ArchiveSearch.svelte';
+import type { ChatMessage } from '$lib/types';
+import { searchArchiveByAuthor, searchArchiveByContent } from '$lib/tauri/chat';
+
+vi.mock('$lib/tauri/chat', () => ({
+	searchArchiveByContent: vi.fn(),
+	searchArchiveByAuthor: vi.fn(),
+}));
+
+function createMessage(id: string): ChatMessage {
+	return {
+		id,
+		timestamp: '2026-01-27T11:36:06+09:00',
+		timestamp_usec: '0',
+		author: 'TestUser',
+		author_icon_url: null,
+		channel_id: 'UC_test',
+		content: `メッセージ ${id}`,
+		runs: [],
+		message_type: 'text',
+		amount: null,
+		is_member: false,
+		author_role: { is_owner: false, is_moderator: false, is_verified: false, is_member: false },
+		is_first_time_viewer: false,
+		in_stream_comment_count: null,
+		is_first_in_stream: false,
+		metadata: null,
+		connection_id: BigInt(0),
+		platform: 'youtube',
+		broadcaster_name: '',
+		moderation: [],
+	};
+}
+
+// offset から limit 件（全 total 件）を返すモック
+function pages(total: number) {
+	return async (_query: string, offset: number, limit: number) => ({
+		messages: Array.from({ length: Math.max(0, Math.min(limit, total - offset)) }, (_, i) =>
+			createMessage(String(offset + i))
+		),
+		total,
+	});
+}
+
+describe('ArchiveSearch', () => {
+	beforeEach(() => {
+		vi.useFakeTimers();
+		vi.mocked(searchArchiveByContent).mockReset();
+		vi.mocked(searchArchiveByAuthor).mockReset();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+	});
+
+	it('入力が止まるまで検索しない', async () => {
+		vi.mocked(searchArchiveByContent).mockImplementation(pages(3));
+		const { getByTestId } = render(ArchiveSearch);
+		const input = getByTestId('archive-search-query');
+
+		await fireEvent.input(input, { target: { value: 'he' } });
+		await vi.advanceTimersByTimeAsync(100);
+		await fireEvent.input(input, { target: { value: 'hel' } });
+		await vi.advanceTimersByTimeAsync(100);
+		await fireEvent.input(input, { target: { value: 'hello' } });
+		expect(searchArchiveByContent).not.toHaveBeenCalled();
+
+		await vi.advanceTimersByTimeAsync(300);
+		expect(searchArchiveByContent).toHaveBeenCalledTimes(1);
+		expect(searchArchiveByContent).toHaveBeenCalledWith('hello', 0, 50);
+	});
+
+	it('1文字では検索しない', async () => {
+		const { getByTestId } = render(ArchiveSearch);
+		await fireEvent.input(getByTestId('archive-search-query'), { target: { value: 'a' } });
+		await vi.advanceTimersByTimeAsync(500);
+		expect(searchArchiveByContent).not.toHaveBeenCalled();
+	});
+
+	it('次のページを offset を進めて読み込み、末尾で止まる', async () => {
+		vi.mocked(searchArchiveByContent).mockImplementation(pages(120));
+		const { getByTestId, queryByTestId } = render(ArchiveSearch);
+		await fireEvent.input(getByTestId('archive-search-query'), { target: { value: 'hello' } });
+		await vi.advanceTimersByTimeAsync(300);
+		expect(getByTestId('archive-search-count').textContent).toContain('120件中 50件');
+
+		await fireEvent.click(getByTestId('archive-search-more'));
+		await vi.advanceTimersByTimeAsync(0);
+		expect(searchArchiveByContent).toHaveBeenLastCalledWith('hello', 50, 50);
+
+		await fireEvent.click(getByTestId('archive-search-more'));
+		await vi.advanceTimersByTimeAsync(0);
+		expect(searchArchiveByContent).toHaveBeenLastCalledWith('hello', 100, 50);
+		expect(getByTestId('archive-search-count').textContent).toContain('120件中 120件');
+		expect(queryByTestId('archive-search-more')).toBeNull();
+	});
+
+	it('投稿者を選ぶと投稿者名で検索する', async () => {
+		vi.mocked(searchArchiveByAuthor).mockImplementation(pages(1));
+		const { getByTestId } = render(ArchiveSearch);
+		await fireEvent.change(getByTestId('archive-search-field'), { target: { value: 'author' } });
+		await fireEvent.input(getByTestId('archive-search-query'), { target: { value: 'alice' } });
+		await vi.advanceTimersByTimeAsync(300);
+		expect(searchArchiveByAuthor).toHaveBeenCalledWith('alice', 0, 50);
+		expect(searchArchiveByContent).not.toHaveBeenCalled();
+	});
+});
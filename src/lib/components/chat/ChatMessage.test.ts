@@ -1,8 +1,15 @@
-import { describe, it, expect } from 'vitest';
-import { render } from '@testing-library/svelte';
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { render, fireEvent } from '@testing-library/svelte';
 import ChatMessage from './ChatMessage.svelte';
 import type { ChatMessage as ChatMessageType } from '$lib/types';
 
+vi.mock('$lib/tauri/icon', () => ({
+	getAuthorIcon: vi.fn((url: string) =>
+		url.includes('broken') ? Promise.reject(new Error('blocked')) : Promise.resolve(`data:image/png;base64,${btoa(url)}`)
+	),
+	clearIconCache: vi.fn(),
+}));
+
 function createMessage(overrides: Partial<ChatMessageType> = {}): ChatMessageType {
 	return {
 		id: 'test_msg_1',
@@ -16,13 +23,16 @@ function createMessage(overrides: Partial<ChatMessageType> = {}): ChatMessageTyp
 		message_type: 'text',
 		amount: null,
 		is_member: false,
+		author_role: { is_owner: false, is_moderator: false, is_verified: false, is_member: false },
 		is_first_time_viewer: false,
 		in_stream_comment_count: null,
+		is_first_in_stream: false,
 		metadata: null,
 		// 多接続対応で追加されたフィールド
 		connection_id: BigInt(1),
 		platform: 'youtube',
 		broadcaster_name: 'TestBroadcaster',
+		moderation: [],
 		...overrides,
 	};
 }
@@ -47,6 +57,7 @@ describe('ChatMessage', () => {
 					milestone_months: null,
 					gift_count: null,
 					badges: [],
+					sticker: null,
 				},
 			});
 
@@ -75,6 +86,7 @@ describe('ChatMessage', () => {
 					milestone_months: null,
 					gift_count: null,
 					badges: [],
+					sticker: null,
 				},
 			});
 
@@ -112,6 +124,7 @@ describe('ChatMessage', () => {
 					milestone_months: null,
 					gift_count: null,
 					badges: [],
+					sticker: null,
 				},
 			});
 
@@ -143,6 +156,7 @@ describe('ChatMessage', () => {
 					milestone_months: null,
 					gift_count: null,
 					badges: [],
+					sticker: null,
 				},
 			});
 
@@ -153,6 +167,110 @@ describe('ChatMessage', () => {
 		});
 	});
 
+	describe('カスタム絵文字', () => {
+		it('カスタム絵文字を代替テキスト付きの画像としてテキストの間に表示する', () => {
+			const message = createMessage({
+				content: 'こんにちは😀 :_hi:!',
+				runs: [
+					{ type: 'Text', content: 'こんにちは😀 ' },
+					{ type: 'Emoji', emoji_id: 'UC_x/hi', image_url: 'https://yt3.ggpht.com/hi', alt_text: ':_hi:' },
+					{ type: 'Text', content: '!' },
+				],
+			});
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			const body = container.querySelector('.mt-1.ml-8 p') as HTMLElement;
+			const nodes = Array.from(body.children).map((el) => el.tagName);
+			expect(nodes).toEqual(['SPAN', 'IMG', 'SPAN']);
+			const img = body.querySelector('img') as HTMLImageElement;
+			expect(img.getAttribute('src')).toBe('https://yt3.ggpht.com/hi');
+			expect(img.alt).toBe(':_hi:');
+			expect(body.textContent).toContain('😀');
+		});
+
+		it('画像のない絵文字はラベルを表示する', () => {
+			const message = createMessage({
+				runs: [{ type: 'Emoji', emoji_id: 'UC_x/hi', image_url: '', alt_text: ':_hi:' }],
+			});
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			const body = container.querySelector('.mt-1.ml-8 p') as HTMLElement;
+			expect(body.querySelector('img')).toBeNull();
+			expect(body.textContent).toContain(':_hi:');
+		});
+	});
+
+	describe('スーパーステッカーの画像', () => {
+		function createSticker(url: string): ChatMessageType {
+			return createMessage({
+				message_type: 'supersticker',
+				amount: '¥200',
+				content: '[Sticker]',
+				runs: [],
+				metadata: {
+					amount: '¥200',
+					superchat_colors: null,
+					badge_info: [],
+					is_moderator: false,
+					is_verified: false,
+					milestone_months: null,
+					gift_count: null,
+					badges: [],
+					sticker: { url, alt_text: 'こんにちは' },
+				},
+			});
+		}
+
+		it('キャッシュ経由の画像を金額の横に表示する', async () => {
+			const message = createSticker('https://lh3.googleusercontent.com/sticker-ok');
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			const sticker = container.querySelector('[data-testid="sticker"]') as HTMLElement;
+			expect(sticker.textContent).toContain('¥200');
+			await vi.waitFor(() => {
+				const img = sticker.querySelector('img') as HTMLImageElement;
+				expect(img.getAttribute('src')).toMatch(/^data:image\/png/);
+				expect(img.alt).toBe('こんにちは');
+			});
+			expect(container.textContent).not.toContain('[Sticker]');
+		});
+
+		it('画像を読み込めなければプレースホルダーを表示する', async () => {
+			const message = createSticker('https://lh3.googleusercontent.com/broken');
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			// キャッシュに失敗すると元の URL を使う
+			const img = await vi.waitFor(() => {
+				const img = container.querySelector('[data-testid="sticker"] img') as HTMLImageElement;
+				expect(img.getAttribute('src')).toBe('https://lh3.googleusercontent.com/broken');
+				return img;
+			});
+			await fireEvent.error(img);
+			expect(container.querySelector('[data-testid="sticker"] img')).toBeNull();
+			expect(container.querySelector('[data-testid="sticker-placeholder"]')).not.toBeNull();
+		});
+	});
+
+	describe('注目ワードの強調表示', () => {
+		it('watched=true のとき watched クラスと強調色を付ける', () => {
+			const message = createMessage();
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false, watched: true, watchColor: '#ffaa00' } });
+			const root = container.querySelector('.chat-message') as HTMLElement;
+			expect(root.classList.contains('watched')).toBe(true);
+			expect(root.getAttribute('style')).toContain('#ffaa00');
+		});
+
+		it('一時的な強調（highlighted）と重ねて表示できる', () => {
+			const message = createMessage();
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false, watched: true, highlighted: true } });
+			const root = container.querySelector('.chat-message') as HTMLElement;
+			expect(root.classList.contains('watched')).toBe(true);
+			expect(root.getAttribute('style')).toContain('border: 2px solid var(--accent)');
+		});
+
+		it('watched=false のとき watched クラスを付けない', () => {
+			const message = createMessage();
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.querySelector('.chat-message')?.classList.contains('watched')).toBe(false);
+		});
+	});
+
 	describe('初見さんバッジ', () => {
 		it('is_first_time_viewer=true + count=1 のとき 🎉初見さん バッジを表示する', () => {
 			const message = createMessage({ is_first_time_viewer: true, in_stream_comment_count: 1 });
@@ -172,6 +290,20 @@ describe('ChatMessage', () => {
 			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
 			expect(container.textContent).not.toContain('初見さん');
 		});
+
+		it('初見さんでない視聴者の配信内の初コメントには 👋 を表示する', () => {
+			const message = createMessage({ in_stream_comment_count: 1, is_first_in_stream: true });
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.querySelector('[data-testid="first-in-stream"]')?.textContent).toContain('👋');
+			expect(container.textContent).not.toContain('初見さん');
+		});
+
+		it('初見さんの初コメントには 👋 を重ねて表示しない', () => {
+			const message = createMessage({ is_first_time_viewer: true, in_stream_comment_count: 1, is_first_in_stream: true });
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.textContent).toContain('🎉初見さん');
+			expect(container.querySelector('[data-testid="first-in-stream"]')).toBeNull();
+		});
 	});
 
 	describe('配信内コメント回数', () => {
@@ -204,4 +336,133 @@ describe('ChatMessage', () => {
 			expect(container.textContent).toContain('#1');
 		});
 	});
+
+	describe('投稿者ロール', () => {
+		const role = { is_owner: false, is_moderator: false, is_verified: false, is_member: false };
+
+		it('オーナーのメッセージに配信者バッジを表示する', () => {
+			const message = createMessage({ author_role: { ...role, is_owner: true } });
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.querySelector('[data-testid="role-owner"]')).not.toBeNull();
+			expect(container.querySelector('[data-testid="role-moderator"]')).toBeNull();
+			const name = container.querySelector('.font-medium.truncate') as HTMLElement;
+			expect(name.getAttribute('style')).toContain('var(--author-owner)');
+		});
+
+		it('モデレーターのメッセージはメンバーでもモデレーターの色で表示する', () => {
+			const message = createMessage({
+				is_member: true,
+				author_role: { ...role, is_moderator: true, is_member: true },
+			});
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.querySelector('[data-testid="role-moderator"]')).not.toBeNull();
+			const name = container.querySelector('.font-medium.truncate') as HTMLElement;
+			expect(name.getAttribute('style')).toContain('var(--author-moderator)');
+			expect(container.textContent).toContain('メンバー');
+		});
+
+		it('ロールのない投稿者にはロールバッジを表示しない', () => {
+			const message = createMessage();
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.querySelector('[data-testid^="role-"]')).toBeNull();
+			const name = container.querySelector('.font-medium.truncate') as HTMLElement;
+			expect(name.getAttribute('style')).toContain('var(--author-default)');
+		});
+	});
+
+	describe('テーマの配色', () => {
+		it('YouTube の色がないスーパーチャットはテーマの色で表示する', () => {
+			const message = createMessage({ message_type: 'superchat', amount: '¥500' });
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			const root = container.querySelector('[data-message-id]') as HTMLElement;
+			expect(root.getAttribute('style')).toContain('border-left-color: var(--chat-superchat)');
+			const label = container.querySelector('.rounded-full') as HTMLElement;
+			expect(label.getAttribute('style')).toContain('var(--chat-label-text)');
+		});
+
+		it('配色の固定値を使わない', () => {
+			for (const message_type of ['superchat', 'supersticker', 'membership', 'membership_gift', 'system'] as const) {
+				const message = createMessage({ message_type });
+				const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+				const root = container.querySelector('[data-message-id]') as HTMLElement;
+				expect(root.getAttribute('style')).not.toMatch(/#[0-9a-f]{6}/i);
+			}
+		});
+	});
+
+	describe('ローカルモデレーション', () => {
+		it('flag に一致したメッセージに要確認バッジを表示する', () => {
+			const message = createMessage({ moderation: ['flag'] });
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(container.querySelector('[data-testid="moderation-flag"]')).not.toBeNull();
+		});
+
+		it('highlight に一致したメッセージは枠線で強調する', () => {
+			const message = createMessage({ moderation: ['highlight'] });
+			const { container } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			const root = container.querySelector('[data-message-id]') as HTMLElement;
+			expect(root.getAttribute('style')).toContain('outline: 2px solid var(--warning)');
+			expect(container.querySelector('[data-testid="moderation-flag"]')).toBeNull();
+		});
+	});
+
+	describe('メッセージのアクション', () => {
+		afterEach(() => {
+			vi.unstubAllGlobals();
+		});
+
+		it('本文とチャンネルのリンクをコピーし、メッセージのクリックは発火しない', async () => {
+			const writeText = vi.fn().mockResolvedValue(undefined);
+			vi.stubGlobal('navigator', { clipboard: { writeText } });
+			const onClick = vi.fn();
+			const message = createMessage();
+			const { getByLabelText } = render(ChatMessage, {
+				props: { message, fontSize: 13, showTimestamps: false, onClick },
+			});
+
+			await fireEvent.click(getByLabelText('本文をコピー'));
+			await fireEvent.click(getByLabelText('チャンネルのリンクをコピー'));
+
+			expect(writeText).toHaveBeenNthCalledWith(1, 'テストメッセージ');
+			expect(writeText).toHaveBeenNthCalledWith(2, 'https://www.youtube.com/channel/UC_test');
+			expect(onClick).not.toHaveBeenCalled();
+		});
+
+		it('チャンネルIDのないメッセージにはリンクのコピーを表示しない', () => {
+			const message = createMessage({ channel_id: '', message_type: 'system' });
+			const { queryByLabelText } = render(ChatMessage, { props: { message, fontSize: 13, showTimestamps: false } });
+			expect(queryByLabelText('本文をコピー')).not.toBeNull();
+			expect(queryByLabelText('チャンネルのリンクをコピー')).toBeNull();
+		});
+
+		it('対応済みのメッセージは薄く表示し、ボタンで切り替えを要求する', async () => {
+			const onToggleAnswered = vi.fn();
+			const message = createMessage();
+			const { container, getByLabelText } = render(ChatMessage, {
+				props: { message, fontSize: 13, showTimestamps: false, answered: true, onToggleAnswered },
+			});
+
+			const root = container.querySelector('[data-message-id]') as HTMLElement;
+			expect(root.classList.contains('answered')).toBe(true);
+
+			const button = getByLabelText('対応済みを解除');
+			expect(button.getAttribute('aria-pressed')).toBe('true');
+			await fireEvent.click(button);
+			expect(onToggleAnswered).toHaveBeenCalledOnce();
+		});
+
+		it('アクションボタン上の Enter ではメッセージのクリックを発火しない', async () => {
+			const onClick = vi.fn();
+			const message = createMessage();
+			const { container, getByLabelText } = render(ChatMessage, {
+				props: { message, fontSize: 13, showTimestamps: false, onClick },
+			});
+
+			await fireEvent.keyDown(getByLabelText('本文をコピー'), { key: 'Enter' });
+			expect(onClick).not.toHaveBeenCalled();
+
+			await fireEvent.keyDown(container.querySelector('[data-message-id]') as HTMLElement, { key: 'Enter' });
+			expect(onClick).toHaveBeenCalledOnce();
+		});
+	});
 });
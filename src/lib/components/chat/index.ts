@@ -1,4 +1,5 @@
 // Chat components
+export { default as ArchiveSearch } from './ArchiveSearch.svelte';
 export { default as ChatDisplay } from './ChatDisplay.svelte';
 export { default as ChatMessage } from './ChatMessage.svelte';
 export { default as ConnectionList } from './ConnectionList.svelte';
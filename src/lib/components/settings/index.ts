@@ -2,3 +2,5 @@
 export { default as AuthSettings } from './AuthSettings.svelte';
 export { default as TtsSettings } from './TtsSettings.svelte';
 export { default as RawResponseSettings } from './RawResponseSettings.svelte';
+export { default as PollingSettings } from './PollingSettings.svelte';
+export { default as DiscordSettings } from './DiscordSettings.svelte';
@@ -1,5 +1,13 @@
 // Analytics state management using Svelte 5 runes
-import type { RevenueAnalytics, ExportConfig } from '$lib/types';
+import type {
+  RevenueAnalytics,
+  ExportConfig,
+  LeaderboardExportConfig,
+  ImportedSessionAnalytics,
+  RecomputeProgress,
+  GoalReachedEvent
+} from '$lib/types';
+import { listen } from '@tauri-apps/api/event';
 import * as analyticsApi from '$lib/tauri/analytics';
 
 // ファクトリ関数：テスト時に独立したストアインスタンスを生成できる
@@ -9,6 +17,15 @@ function createAnalyticsStore() {
   let isLoading = $state(false);
   let error = $state<string | null>(null);
   let lastUpdate = $state<Date | null>(null);
+  let trendingTerms = $state<[string, number][]>([]);
+  // エクスポートファイルから読み込んだセッション（表示中は現在のセッションで上書きしない）
+  let importedSession = $state<Omit<ImportedSessionAnalytics, 'analytics'> | null>(null);
+  // 保存したセッションの再計算の進捗（再計算中のみ）
+  let recomputeProgress = $state<RecomputeProgress | null>(null);
+
+  // トレンドワードの集計範囲 (spec: 07_revenue.md)
+  const TRENDING_WINDOW_SECS = 300;
+  const TRENDING_TOP_N = 10;
 
   // アクション
   async function loadAnalytics(): Promise<void> {
@@ -25,6 +42,14 @@ function createAnalyticsStore() {
     }
   }
 
+  async function loadTrendingTerms(): Promise<void> {
+    try {
+      trendingTerms = await analyticsApi.getTrendingTerms(TRENDING_WINDOW_SECS, TRENDING_TOP_N);
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
   async function loadSessionAnalytics(sessionId: string): Promise<void> {
     isLoading = true;
     error = null;
@@ -39,6 +64,78 @@ function createAnalyticsStore() {
     }
   }
 
+  async function importSession(filePath: string): Promise<void> {
+    isLoading = true;
+    error = null;
+
+    try {
+      const { analytics: imported, ...session } = await analyticsApi.importSessionFile(filePath);
+      analytics = imported;
+      importedSession = session;
+      lastUpdate = new Date();
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    } finally {
+      isLoading = false;
+    }
+  }
+
+  // 保存したセッションの集計を作り直し、読み込んだセッションとして表示する
+  async function recomputeSession(sessionId: string, streamTitle: string | null): Promise<void> {
+    isLoading = true;
+    error = null;
+    recomputeProgress = { processed: 0, total: 0 };
+    const unlisten = await listen<RecomputeProgress>('analytics:recompute_progress', (event) => {
+      recomputeProgress = event.payload;
+    });
+
+    try {
+      const result = await analyticsApi.recomputeSessionAnalytics(sessionId);
+      // キャンセルされた場合は表示を変えない
+      if (result) {
+        analytics = result.revenue;
+        importedSession = {
+          session_id: sessionId,
+          stream_title: streamTitle,
+          message_count: result.message_count,
+          unique_viewers: result.engagement.at(-1)?.unique_viewers ?? 0,
+          warnings: []
+        };
+        lastUpdate = new Date();
+      }
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    } finally {
+      unlisten();
+      recomputeProgress = null;
+      isLoading = false;
+    }
+  }
+
+  // 画面を離れたときなどに実行中の再計算を打ち切る
+  async function cancelRecompute(): Promise<void> {
+    if (!recomputeProgress) return;
+    try {
+      await analyticsApi.cancelAnalyticsRecompute();
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
+  // 収益目標に到達したら、自動更新を待たずに現在のセッションの進捗を取り直す (spec: 07_revenue.md 収益目標)
+  // 戻り値の関数で購読をやめる
+  async function watchGoal(): Promise<() => void> {
+    return listen<GoalReachedEvent>('chat:goal_reached', () => {
+      if (!importedSession) loadAnalytics();
+    });
+  }
+
+  // 読み込んだセッションの表示をやめ、現在のセッションに戻す
+  async function clearImportedSession(): Promise<void> {
+    importedSession = null;
+    await loadAnalytics();
+  }
+
   async function exportSession(
     sessionId: string,
     filePath: string,
@@ -61,6 +158,20 @@ function createAnalyticsStore() {
     }
   }
 
+  // 見積もりの失敗はエクスポートのエラー表示に出さず、呼び出し側で扱う
+  async function estimateCurrentExport(config: ExportConfig): Promise<number> {
+    return analyticsApi.estimateCurrentExport(config);
+  }
+
+  async function exportLeaderboard(filePath: string, config: LeaderboardExportConfig): Promise<void> {
+    try {
+      await analyticsApi.exportContributorLeaderboard(filePath, config);
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+      throw e;
+    }
+  }
+
   function clearError(): void {
     error = null;
   }
@@ -79,6 +190,15 @@ function createAnalyticsStore() {
     get lastUpdate() {
       return lastUpdate;
     },
+    get trendingTerms() {
+      return trendingTerms;
+    },
+    get importedSession() {
+      return importedSession;
+    },
+    get recomputeProgress() {
+      return recomputeProgress;
+    },
 
     // 算出値
     get totalPaidCount() {
@@ -94,8 +214,16 @@ function createAnalyticsStore() {
     // アクション
     loadAnalytics,
     loadSessionAnalytics,
+    loadTrendingTerms,
+    importSession,
+    recomputeSession,
+    cancelRecompute,
+    watchGoal,
+    clearImportedSession,
     exportSession,
     exportCurrent,
+    estimateCurrentExport,
+    exportLeaderboard,
     clearError
   };
 }
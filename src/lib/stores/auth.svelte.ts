@@ -1,19 +1,23 @@
 // Auth store (01_auth.md)
 
-import type { AuthStatus, SessionValidity, AuthIndicatorState } from '$lib/types';
+import type { AuthStatus, AuthExpiry, SessionValidity, AuthIndicatorState } from '$lib/types';
 import * as authApi from '$lib/tauri/auth';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 
 function createAuthStore() {
   let status = $state<AuthStatus>({
     is_authenticated: false,
     has_saved_credentials: false,
     storage_type: 'secure',
-    storage_error: null
+    storage_error: null,
+    auth_source: null,
+    expiry: { state: 'unknown' }
   });
   let sessionValidity = $state<SessionValidity | null>(null);
   let isLoading = $state(false);
   let isCheckingSession = $state(false);
   let error = $state<string | null>(null);
+  let unlistenExpiry: UnlistenFn | null = null;
 
   // Derived: indicator state based on all conditions
   let indicatorState = $derived.by((): AuthIndicatorState => {
@@ -32,9 +36,16 @@ function createAuthStore() {
       return 'authenticated_checking';
     }
 
+    // 認証情報の有効期限が切れている場合はセッション確認結果より優先
+    if (status.expiry.state === 'expired') {
+      return 'authenticated_invalid';
+    }
+
     if (sessionValidity) {
       if (sessionValidity.is_valid) {
-        return 'authenticated_valid';
+        return status.expiry.state === 'expiring_soon'
+          ? 'authenticated_expiring'
+          : 'authenticated_valid';
       }
       // Check if it was a network error
       if (sessionValidity.error?.includes('Network')) {
@@ -80,6 +91,16 @@ function createAuthStore() {
       return error;
     },
 
+    // バックエンドの定期確認による有効期限の警告を購読
+    async setupEventListeners() {
+      if (unlistenExpiry) {
+        unlistenExpiry();
+      }
+      unlistenExpiry = await listen<AuthExpiry>('auth-expiry-warning', (event) => {
+        status = { ...status, expiry: event.payload };
+      });
+    },
+
     async refreshStatus() {
       isLoading = true;
       error = null;
@@ -147,6 +168,8 @@ function createAuthStore() {
       try {
         // Delete saved credentials from secure storage
         await authApi.authDeleteCredentials();
+        // OAuth でログインしている場合はトークンも削除
+        await authApi.authOAuthLogout();
         // Clear WebView cookies to logout from YouTube
         await authApi.authClearWebviewCookies();
         sessionValidity = null;
@@ -3,7 +3,7 @@
 import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
 import { listen } from '@tauri-apps/api/event';
 import * as chatApi from '$lib/tauri/chat';
-import type { ConnectionResult, ConnectionInfo } from '$lib/types';
+import type { BigDonationEvent, ConnectionHealthEvent, ConnectionResult, ConnectionInfo, MessageDeletedEvent } from '$lib/types';
 
 // chatApiをモック（setupファイルより前に宣言することでホイスティングを確保）
 vi.mock('$lib/tauri/chat', () => ({
@@ -50,6 +50,16 @@ function makeConnectionInfo(overrides: Partial<ConnectionInfo> = {}): Connection
 		broadcaster_channel_id: 'UC_alice',
 		is_monitoring: true,
 		is_cancelling: false,
+		health: {
+			level: 'good',
+			secs_since_success: BigInt(0),
+			consecutive_empty: 0,
+			avg_latency_ms: 0,
+			rate_limited: false,
+			rate_limit: { hits: 0, total_backoff_ms: 0, longest_wait_ms: 0 },
+			poll_interval_ms: 0,
+			processing_queue: { queued: 0, capacity: 1000, dropped: 0 },
+		},
 		...overrides,
 	};
 }
@@ -474,6 +484,28 @@ describe('chatStore 接続管理', () => {
 			expect(chatStore.error).toBe('watch task died');
 		});
 
+		// spec: 監視中にメンバー限定で拒否されたら接続時と同じログインの案内を表示する
+		it('success=false + MembersOnly で接続が削除されログインの案内がセットされる', async () => {
+			const { connectToStream } = await import('$lib/tauri/chat');
+			const { MEMBERS_ONLY_MESSAGE } = await import('$lib/tauri/errors');
+			vi.mocked(connectToStream).mockResolvedValue(makeSuccessResult());
+			await chatStore.connect('https://example.com');
+
+			emitConnection!({
+				success: false,
+				connection_id: BigInt(1),
+				stream_title: null,
+				broadcaster_name: null,
+				broadcaster_channel_id: null,
+				is_replay: false,
+				error: JSON.stringify({ kind: 'MembersOnly', message: 'members-only' }),
+				session_id: null,
+			});
+
+			expect(chatStore.connections.has(1)).toBe(false);
+			expect(chatStore.error).toBe(MEMBERS_ONLY_MESSAGE);
+		});
+
 		// spec: 存在しない connection_id のイベントは無視される
 		it('存在しない connection_id のイベントは無視される', async () => {
 			emitConnection!({
@@ -1416,6 +1448,8 @@ describe('chatStore initDisplaySettings', () => {
 				messageFontSize: 20,
 				showTimestamps: false,
 				autoScrollEnabled: false,
+				blockedChannelIds: ['UC_spam'],
+				allowedChannelIds: ['UC_vip'],
 				setMessageFontSize: vi.fn(),
 			},
 		}));
@@ -1431,9 +1465,155 @@ describe('chatStore initDisplaySettings', () => {
 		expect(store.showTimestamps).toBe(false);
 		// spec: autoScroll が configStore.autoScrollEnabled になる
 		expect(store.autoScroll).toBe(false);
+		// spec: 保存済みのブロック/許可リストがフィルタに反映される
+		expect(store.filter.blockedChannelIds).toEqual(['UC_spam']);
+		expect(store.filter.allowedChannelIds).toEqual(['UC_vip']);
 
 		store.cleanup();
 		vi.doUnmock('$lib/tauri/chat');
 		vi.doUnmock('./config.svelte');
 	});
 });
+
+// =====================================================================
+// 高額スーパーチャットイベント
+// spec: chat:big_donation を購読し、直近のイベントを bigDonation に保持する
+// =====================================================================
+describe('chatStore 高額スーパーチャット通知', () => {
+	it('chat:big_donation ハンドラを登録し、受信したイベントを保持・破棄できる', async () => {
+		let bigDonationHandler: ((e: { payload: BigDonationEvent }) => void) | undefined;
+		vi.mocked(listen).mockReset();
+		vi.mocked(listen).mockImplementation(async (event: string, handler: unknown) => {
+			if (event === 'chat:big_donation') {
+				bigDonationHandler = handler as (e: { payload: BigDonationEvent }) => void;
+			}
+			return () => {};
+		});
+
+		vi.resetModules();
+		const mod = await import('./chat.svelte');
+		const store = mod.chatStore;
+		await store.setupEventListeners();
+
+		expect(listen).toHaveBeenCalledWith('chat:big_donation', expect.any(Function));
+		expect(store.bigDonation).toBeNull();
+
+		const payload: BigDonationEvent = {
+			connection_id: BigInt(1),
+			message_id: 'msg_1',
+			author: 'Alice',
+			channel_id: 'UC_alice',
+			amount: { currency: 'JPY', amount: 10000, raw: '¥10,000' },
+		};
+		bigDonationHandler!({ payload });
+		expect(store.bigDonation).toEqual(payload);
+
+		store.dismissBigDonation();
+		expect(store.bigDonation).toBeNull();
+
+		store.cleanup();
+	});
+});
+
+describe('chatStore 接続の健全性', () => {
+	it('chat:connection_health で対象接続の health を更新する', async () => {
+		let healthHandler: ((e: { payload: ConnectionHealthEvent }) => void) | undefined;
+		vi.mocked(listen).mockReset();
+		vi.mocked(listen).mockImplementation(async (event: string, handler: unknown) => {
+			if (event === 'chat:connection_health') {
+				healthHandler = handler as (e: { payload: ConnectionHealthEvent }) => void;
+			}
+			return () => {};
+		});
+
+		vi.resetModules();
+		const api = await import('$lib/tauri/chat');
+		vi.mocked(api.getConnections).mockResolvedValue([makeConnectionInfo({ id: BigInt(1) })]);
+		const mod = await import('./chat.svelte');
+		const store = mod.chatStore;
+		await store.setupEventListeners();
+		await store.restoreConnections();
+
+		expect(listen).toHaveBeenCalledWith('chat:connection_health', expect.any(Function));
+		expect(store.connections.get(1)?.health).toBe('good');
+
+		const health = {
+			level: 'degraded' as const,
+			secs_since_success: BigInt(12),
+			consecutive_empty: 0,
+			avg_latency_ms: 250,
+			rate_limited: true,
+			rate_limit: { hits: 3, total_backoff_ms: 4500, longest_wait_ms: 2000 },
+			poll_interval_ms: 3000,
+			processing_queue: { queued: 900, capacity: 1000, dropped: 12 },
+		};
+		healthHandler!({ payload: { connection_id: BigInt(1), health } });
+		expect(store.connections.get(1)?.health).toBe('degraded');
+		expect(store.connections.get(1)?.rateLimit).toEqual(health.rate_limit);
+		expect(store.connections.get(1)?.pollIntervalMs).toBe(3000);
+		expect(store.connections.get(1)?.processingQueue).toEqual(health.processing_queue);
+
+		// 存在しない接続のイベントは無視する
+		healthHandler!({ payload: { connection_id: BigInt(99), health } });
+		expect(store.connections.has(99)).toBe(false);
+
+		store.cleanup();
+	});
+});
+
+// =====================================================================
+// 削除されたメッセージ
+// spec: chat:message_deleted を購読し、対象メッセージを表示とインデックスから取り除く
+// =====================================================================
+describe('chatStore 削除されたメッセージ', () => {
+	it('追加したメッセージを削除すると表示されるメッセージが0件になる', async () => {
+		vi.useFakeTimers();
+		let messageHandler: ((e: { payload: unknown }) => void) | undefined;
+		let deletedHandler: ((e: { payload: MessageDeletedEvent }) => void) | undefined;
+		vi.mocked(listen).mockReset();
+		vi.mocked(listen).mockImplementation(async (event: string, handler: unknown) => {
+			if (event === 'chat:message') {
+				messageHandler = handler as (e: { payload: unknown }) => void;
+			}
+			if (event === 'chat:message_deleted') {
+				deletedHandler = handler as (e: { payload: MessageDeletedEvent }) => void;
+			}
+			return () => {};
+		});
+
+		vi.resetModules();
+		const mod = await import('./chat.svelte');
+		const store = mod.chatStore;
+		await store.setupEventListeners();
+		expect(listen).toHaveBeenCalledWith('chat:message_deleted', expect.any(Function));
+
+		messageHandler!({ payload: {
+			id: 'msg_1', connection_id: 1, channel_id: 'ch_1',
+			author: 'User', content: 'spam', message_type: 'text',
+			timestamp: '2024-01-01T00:00:00Z', author_photo: null, amount: null, currency: null,
+		}});
+		vi.advanceTimersByTime(50);
+		expect(store.messages).toHaveLength(1);
+
+		// 別の接続の同じ ID は対象外
+		deletedHandler!({ payload: { connection_id: BigInt(2), message_ids: ['msg_1'] } });
+		expect(store.messages).toHaveLength(1);
+
+		deletedHandler!({ payload: { connection_id: BigInt(1), message_ids: ['msg_1'] } });
+		expect(store.messages).toHaveLength(0);
+		expect(store.displayedMessages).toHaveLength(0);
+		expect(store.getMessagesForChannel('ch_1')).toHaveLength(0);
+
+		// 同じメッセージが再送されても表示しない
+		messageHandler!({ payload: {
+			id: 'msg_1', connection_id: 1, channel_id: 'ch_1',
+			author: 'User', content: 'spam', message_type: 'text',
+			timestamp: '2024-01-01T00:00:00Z', author_photo: null, amount: null, currency: null,
+		}});
+		vi.advanceTimersByTime(50);
+		expect(store.messages).toHaveLength(0);
+
+		store.cleanup();
+		vi.useRealTimers();
+	});
+});
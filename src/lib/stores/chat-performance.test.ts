@@ -19,14 +19,21 @@ vi.mock('./config.svelte', () => ({
 		showTimestamps: true,
 		autoScrollEnabled: true,
 		setMessageFontSize: vi.fn(),
+		setBlockedChannelIds: vi.fn(),
+		setAllowedChannelIds: vi.fn(),
+		setHighlightRules: vi.fn(),
+		hotArchiveLimit: null as number | null,
 	},
 }));
 
+// 受信順どおりに並ぶよう、既定の timestamp_usec は作成順に増やす
+let nextTimestampUsec = 1769481366000000;
+
 function createMessage(id: string, overrides: Partial<ChatMessage> = {}): ChatMessage {
 	return {
 		id,
 		timestamp: '2026-01-27T11:36:06+09:00',
-		timestamp_usec: '0',
+		timestamp_usec: String(nextTimestampUsec++),
 		author: 'TestUser',
 		author_icon_url: null,
 		channel_id: 'UC_test',
@@ -35,13 +42,16 @@ function createMessage(id: string, overrides: Partial<ChatMessage> = {}): ChatMe
 		message_type: 'text',
 		amount: null,
 		is_member: false,
+		author_role: { is_owner: false, is_moderator: false, is_verified: false, is_member: false },
 		is_first_time_viewer: false,
 		in_stream_comment_count: null,
+		is_first_in_stream: false,
 		metadata: null,
 		// 多接続対応で追加されたフィールド
 		connection_id: BigInt(1),
 		platform: 'youtube',
 		broadcaster_name: 'TestBroadcaster',
+		moderation: [],
 		...overrides,
 	};
 }
@@ -209,6 +219,26 @@ describe('chatStore パフォーマンス最適化', () => {
 			addAndFlush(newMessages);
 			expect(chatStore.messages).toHaveLength(50);
 		});
+
+		it('ディスクアーカイブ有効時は hot_size 件を超えた古いメッセージを手放す', async () => {
+			const { configStore } = await import('./config.svelte');
+			Object.assign(configStore, { hotArchiveLimit: 2 });
+			try {
+				addAndFlush([
+					createMessage('hot_1', { channel_id: 'UC_old' }),
+					createMessage('hot_2'),
+					createMessage('hot_3'),
+				]);
+
+				expect(chatStore.messages.map((m) => m.id)).toEqual(['hot_2', 'hot_3']);
+				expect(chatStore.getMessagesForChannel('UC_old')).toEqual([]);
+				// 手放したメッセージが再送されても表示しない
+				addAndFlush([createMessage('hot_1')]);
+				expect(chatStore.messages).toHaveLength(2);
+			} finally {
+				Object.assign(configStore, { hotArchiveLimit: null });
+			}
+		});
 	});
 
 	describe('デフォルトフィルタ最適化 (Phase 2)', () => {
@@ -306,6 +336,24 @@ describe('chatStore パフォーマンス最適化', () => {
 			expect(chatStore.messages[1].id).toBe('flush_2');
 		});
 
+		it('同じ秒のメッセージは timestamp_usec → ID の順で並ぶ', () => {
+			addAndFlush([
+				createMessage('b', { timestamp_usec: '1769481366500000' }),
+				createMessage('c', { timestamp_usec: '1769481366100000' }),
+				createMessage('a', { timestamp_usec: '1769481366500000' }),
+				createMessage('d', { timestamp_usec: '999999999999999' }),
+			]);
+
+			expect(chatStore.messages.map((m) => m.id)).toEqual(['d', 'c', 'a', 'b']);
+		});
+
+		it('表示済みのメッセージは後から来た古いメッセージで並べ替えない', () => {
+			addAndFlush([createMessage('late', { timestamp_usec: '1769481366500000' })]);
+			addAndFlush([createMessage('early', { timestamp_usec: '1769481366100000' })]);
+
+			expect(chatStore.messages.map((m) => m.id)).toEqual(['late', 'early']);
+		});
+
 		it('複数バッチが順序を保って追加される', () => {
 			addAndFlush([createMessage('batch1_1')]);
 			addAndFlush([createMessage('batch2_1')]);
@@ -490,6 +538,173 @@ describe('chatStore パフォーマンス最適化', () => {
 		});
 	});
 
+	// spec: 検索は全角/半角を区別しない（normalizeWidth）
+	describe('検索クエリ全角半角正規化', () => {
+		it('全角クエリで半角コンテンツがヒットする', () => {
+			chatStore.setFilter({ searchQuery: 'ＡＢＣ' });
+			addAndFlush([
+				createMessage('1', { content: 'ABC text' }),
+				createMessage('2', { content: 'xyz' }),
+			]);
+			expect(chatStore.filteredMessages).toHaveLength(1);
+			expect(chatStore.filteredMessages[0].id).toBe('1');
+		});
+
+		it('半角クエリで全角コンテンツがヒットする', () => {
+			chatStore.setFilter({ searchQuery: 'www' });
+			addAndFlush([
+				createMessage('1', { content: 'ｗｗｗ' }),
+				createMessage('2', { content: '草' }),
+			]);
+			expect(chatStore.filteredMessages).toHaveLength(1);
+			expect(chatStore.filteredMessages[0].id).toBe('1');
+		});
+
+		it('正規化しても表示用のコンテンツは変更されない', () => {
+			chatStore.setFilter({ searchQuery: 'abc' });
+			addAndFlush([createMessage('1', { content: 'ＡＢＣ' })]);
+			expect(chatStore.filteredMessages[0].content).toBe('ＡＢＣ');
+		});
+
+		it('normalizeWidth=false のとき全角クエリは半角コンテンツにヒットしない', () => {
+			chatStore.setFilter({ searchQuery: 'ＡＢＣ', normalizeWidth: false });
+			addAndFlush([createMessage('1', { content: 'ABC text' })]);
+			expect(chatStore.filteredMessages).toHaveLength(0);
+		});
+	});
+
+	// spec: 02_chat.md フィルタ機能（本文の正規表現）
+	describe('本文の正規表現フィルタ', () => {
+		it('パターンに一致する本文のみ表示される', () => {
+			chatStore.setFilter({ contentRegex: 'fr[e3]{2}' });
+			addAndFlush([
+				createMessage('1', { content: 'fr33 gift' }),
+				createMessage('2', { content: 'hello' }),
+			]);
+			expect(chatStore.filteredMessages.map((m) => m.id)).toEqual(['1']);
+			expect(chatStore.contentRegexError).toBeNull();
+		});
+
+		it('caseInsensitive で大文字小文字を区別しない', () => {
+			chatStore.setFilter({ contentRegex: 'spam' });
+			addAndFlush([createMessage('1', { content: 'SPAM' })]);
+			expect(chatStore.filteredMessages).toHaveLength(0);
+
+			chatStore.setFilter({ caseInsensitive: true });
+			expect(chatStore.filteredMessages).toHaveLength(1);
+		});
+
+		it('不正なパターンはエラーを公開し、絞り込みには使わない', () => {
+			chatStore.setFilter({ contentRegex: '(unclosed' });
+			addAndFlush([createMessage('1', { content: 'hello' })]);
+			expect(chatStore.contentRegexError).not.toBeNull();
+			expect(chatStore.filteredMessages).toHaveLength(1);
+		});
+	});
+
+	// spec: 02_chat.md フィルタ機能（チャンネルのブロック/許可リスト）
+	describe('チャンネルのブロック/許可リスト', () => {
+		it('ブロックしたチャンネルの投稿は表示されない', () => {
+			chatStore.setBlockedChannelIds(['UC_spam']);
+			addAndFlush([
+				createMessage('1', { channel_id: 'UC_spam' }),
+				createMessage('2', { channel_id: 'UC_other' }),
+			]);
+			expect(chatStore.filteredMessages.map((m) => m.id)).toEqual(['2']);
+		});
+
+		it('許可リスト指定時はそのチャンネルの投稿のみ表示される', () => {
+			chatStore.setAllowedChannelIds(['UC_vip']);
+			addAndFlush([
+				createMessage('1', { channel_id: 'UC_vip' }),
+				createMessage('2', { channel_id: 'UC_other' }),
+			]);
+			expect(chatStore.filteredMessages.map((m) => m.id)).toEqual(['1']);
+		});
+
+		it('許可リストに含まれていてもブロックが優先される', () => {
+			chatStore.setAllowedChannelIds(['UC_vip', 'UC_spam']);
+			chatStore.setBlockedChannelIds(['UC_spam']);
+			addAndFlush([
+				createMessage('1', { channel_id: 'UC_vip' }),
+				createMessage('2', { channel_id: 'UC_spam' }),
+			]);
+			expect(chatStore.filteredMessages.map((m) => m.id)).toEqual(['1']);
+		});
+
+		it('システムメッセージは許可リストの対象外', () => {
+			chatStore.setAllowedChannelIds(['UC_vip']);
+			addAndFlush([createMessage('1', { channel_id: '', message_type: 'system' })]);
+			expect(chatStore.filteredMessages).toHaveLength(1);
+		});
+
+		it('許可リストを解除すると全チャンネルが表示される', () => {
+			chatStore.setAllowedChannelIds(['UC_vip']);
+			addAndFlush([createMessage('1', { channel_id: 'UC_other' })]);
+			expect(chatStore.filteredMessages).toHaveLength(0);
+
+			chatStore.setAllowedChannelIds(null);
+			expect(chatStore.filteredMessages).toHaveLength(1);
+		});
+
+		it('変更は設定に保存される', async () => {
+			const { configStore } = await import('./config.svelte');
+			chatStore.setBlockedChannelIds(['UC_spam', 'UC_spam']);
+			chatStore.setAllowedChannelIds(['UC_vip']);
+			expect(configStore.setBlockedChannelIds).toHaveBeenCalledWith(['UC_spam']);
+			expect(configStore.setAllowedChannelIds).toHaveBeenCalledWith(['UC_vip']);
+		});
+	});
+
+	// spec: 02_chat.md ローカルモデレーション
+	describe('注目ワードの強調表示', () => {
+		it('キーワード（全角/半角・大文字小文字を区別しない）またはチャンネルIDに一致したメッセージを強調する', () => {
+			chatStore.setHighlightRules({ keywords: [' ABC ', ''], channel_ids: ['UC_watch'], color: '#ffaa00' });
+			addAndFlush([
+				createMessage('1', { content: 'ｈｅｌｌｏ ａｂｃ' }),
+				createMessage('2', { content: 'hello' }),
+				createMessage('3', { content: 'hello', channel_id: 'UC_watch' }),
+				createMessage('4', { content: 'abc', message_type: 'system' }),
+			]);
+
+			expect(chatStore.messages.map((m) => chatStore.isWatched(m))).toEqual([true, false, true, false]);
+			expect(chatStore.highlightRules.keywords).toEqual(['ABC']);
+			expect(chatStore.highlightColor).toBe('#ffaa00');
+		});
+
+		it('強調表示はフィルタ結果を変えない', () => {
+			chatStore.setHighlightRules({ keywords: ['abc'], channel_ids: [], color: '' });
+			addAndFlush([createMessage('1', { content: 'abc' }), createMessage('2', { content: 'xyz' })]);
+
+			expect(chatStore.filteredMessages).toHaveLength(2);
+			expect(chatStore.highlightColor).toBe('var(--info)');
+		});
+	});
+
+	describe('ローカルモデレーション', () => {
+		it('hide に一致したメッセージはフィルタ済みメッセージに含まれない', () => {
+			addAndFlush([
+				createMessage('1', { moderation: ['hide'] }),
+				createMessage('2', { moderation: ['flag'] }),
+				createMessage('3'),
+			]);
+			expect(chatStore.filteredMessages.map((m) => m.id)).toEqual(['2', '3']);
+			expect(chatStore.messages).toHaveLength(3);
+			expect(chatStore.hiddenMessageCount).toBe(1);
+		});
+
+		it('非表示メッセージがなければ元の配列をそのまま返す', () => {
+			addAndFlush([createMessage('1', { moderation: ['highlight'] })]);
+			expect(chatStore.filteredMessages).toBe(chatStore.messages);
+		});
+
+		it('clearMessages で非表示カウントがリセットされる', () => {
+			addAndFlush([createMessage('1', { moderation: ['hide'] })]);
+			chatStore.clearMessages();
+			expect(chatStore.hiddenMessageCount).toBe(0);
+		});
+	});
+
 	// spec: 多接続モードの初期状態確認
 	describe('多接続モード初期値', () => {
 		// isPaused は多接続では常に false（グローバルpauseなし）
@@ -548,4 +763,105 @@ describe('chatStore パフォーマンス最適化', () => {
 			expect(chatStore.displayLimit).toBeNull();
 		});
 	});
+
+	// spec: 02_chat.md 表示件数制限（超過分はアーカイブに移動し、削除しない）
+	describe('archivedCount', () => {
+		it('displayLimit を 500 から 100 に縮めるとアーカイブが 400 件増える', () => {
+			addAndFlush(Array.from({ length: 600 }, (_, i) => createMessage(String(i))));
+			chatStore.setDisplayLimit(500);
+			expect(chatStore.archivedCount).toBe(100);
+
+			chatStore.setDisplayLimit(100);
+			expect(chatStore.archivedCount).toBe(500);
+			expect(chatStore.displayedMessages).toHaveLength(100);
+			// アーカイブ分も messages に順序どおり残る
+			expect(chatStore.messages).toHaveLength(600);
+			expect(chatStore.messages[0].id).toBe('0');
+			expect(chatStore.displayedMessages[0].id).toBe('500');
+		});
+
+		it('displayLimit=null ではアーカイブは 0 件', () => {
+			addAndFlush([createMessage('1'), createMessage('2')]);
+			expect(chatStore.archivedCount).toBe(0);
+		});
+
+		it('一時停止中の未表示分はアーカイブに含めない', () => {
+			chatStore.setDisplayLimit(1);
+			addAndFlush([createMessage('1'), createMessage('2')]);
+			chatStore.pauseDisplay();
+			addAndFlush([createMessage('3')]);
+			expect(chatStore.archivedCount).toBe(1);
+			expect(chatStore.pausedBacklog).toBe(1);
+		});
+	});
+
+	// spec: 02_chat.md 表示の一時停止
+	describe('pauseDisplay / resumeDisplay', () => {
+		it('一時停止中は表示を固定したまま受信を続け、再開で displayLimit に従って表示する', () => {
+			chatStore.setDisplayLimit(3);
+			addAndFlush([createMessage('1'), createMessage('2')]);
+
+			chatStore.pauseDisplay();
+			expect(chatStore.isDisplayPaused).toBe(true);
+			addAndFlush([createMessage('3'), createMessage('4'), createMessage('5')]);
+
+			// 受信したメッセージは蓄積されるが表示には出ない
+			expect(chatStore.messages).toHaveLength(5);
+			expect(chatStore.filteredMessages).toHaveLength(5);
+			expect(chatStore.displayedMessages.map((m) => m.id)).toEqual(['1', '2']);
+			expect(chatStore.pausedBacklog).toBe(3);
+
+			chatStore.resumeDisplay();
+			expect(chatStore.isDisplayPaused).toBe(false);
+			expect(chatStore.pausedBacklog).toBe(0);
+			expect(chatStore.displayedMessages.map((m) => m.id)).toEqual(['3', '4', '5']);
+		});
+
+		it('一時停止中もフィルタ変更は表示中のメッセージに反映される', () => {
+			addAndFlush([createMessage('1'), createMessage('2', { message_type: 'superchat' })]);
+			chatStore.pauseDisplay();
+			addAndFlush([createMessage('3', { message_type: 'superchat' })]);
+
+			chatStore.setFilter({ showText: false });
+			expect(chatStore.displayedMessages.map((m) => m.id)).toEqual(['2']);
+			expect(chatStore.filteredMessages.map((m) => m.id)).toEqual(['2', '3']);
+		});
+
+		it('一時停止中の clearMessages で未表示件数もリセットされる', () => {
+			chatStore.pauseDisplay();
+			addAndFlush([createMessage('1')]);
+			chatStore.clearMessages();
+
+			expect(chatStore.isDisplayPaused).toBe(true);
+			expect(chatStore.pausedBacklog).toBe(0);
+			addAndFlush([createMessage('2')]);
+			expect(chatStore.displayedMessages).toHaveLength(0);
+			expect(chatStore.pausedBacklog).toBe(1);
+		});
+	});
+
+	// spec: 02_chat.md メッセージのアクション
+	describe('toggleAnswered', () => {
+		it('接続IDとメッセージIDの組で対応済みを切り替える', () => {
+			const first = createMessage('1', { connection_id: BigInt(1) });
+			const sameIdOtherConnection = createMessage('1', { connection_id: BigInt(2) });
+			addAndFlush([first, sameIdOtherConnection]);
+
+			chatStore.toggleAnswered(first);
+			expect(chatStore.isAnswered(first)).toBe(true);
+			expect(chatStore.isAnswered(sameIdOtherConnection)).toBe(false);
+
+			chatStore.toggleAnswered(first);
+			expect(chatStore.isAnswered(first)).toBe(false);
+		});
+
+		it('clearMessages で対応済みもリセットされる', () => {
+			const msg = createMessage('1');
+			addAndFlush([msg]);
+			chatStore.toggleAnswered(msg);
+
+			chatStore.clearMessages();
+			expect(chatStore.isAnswered(msg)).toBe(false);
+		});
+	});
 });
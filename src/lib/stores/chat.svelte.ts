@@ -1,9 +1,16 @@
 // Chat state management using Svelte 5 runes
 import { listen } from '@tauri-apps/api/event';
-import type { ChatMessage, ConnectionResult, ChatMode, ChatFilter, FrontendConnectionState } from '$lib/types';
+import type { BigDonationEvent, ChatMessage, ChatNotificationEvent, ConnectionHealthEvent, ConnectionResult, ChatMode, ChatFilter, FrontendConnectionState, HighlightRules, MessageDeletedEvent } from '$lib/types';
 import { SvelteMap, SvelteSet } from 'svelte/reactivity';
 import * as chatApi from '$lib/tauri/chat';
+import { errorMessage } from '$lib/tauri/errors';
 import { getConnectionColor } from '$lib/utils/connection-colors';
+import { normalizeForSearch } from '$lib/utils/text-normalize';
+import { compileContentRegex } from '$lib/utils/content-regex';
+import { matchesMentions, presetMentions } from '$lib/utils/mentions-filter';
+import { compileRules, matchesRules } from '$lib/utils/message-rules';
+import { compareMessageOrder, messageKey } from '$lib/utils/message-key';
+import { showChatNotification } from '$lib/utils/desktop-notification';
 import { configStore } from './config.svelte';
 
 // ファクトリ関数：テスト時に独立したストアインスタンスを生成できる
@@ -15,6 +22,8 @@ function createChatStore() {
   let connections = $state<SvelteMap<number, FrontendConnectionState>>(new SvelteMap());
   let chatMode = $state<ChatMode>('top');
   let error = $state<string | null>(null);
+  // 直近の高額スーパーチャット（お祝い表示用、閉じると null）
+  let bigDonation = $state<BigDonationEvent | null>(null);
 
   // 多接続ベースの派生状態
   let isConnected = $derived(connections.size > 0);
@@ -25,8 +34,18 @@ function createChatStore() {
     showText: true,
     showSuperchat: true,
     showMembership: true,
-    searchQuery: ''
+    searchQuery: '',
+    normalizeWidth: true,
+    blockedChannelIds: [],
+    allowedChannelIds: null,
+    contentRegex: '',
+    caseInsensitive: false,
+    mentionsOnly: false
   });
+  // 常に強調表示するキーワード・投稿者 (spec: 02_chat.md 注目ワードの強調表示)
+  let highlightRules = $state<HighlightRules>({ keywords: [], channel_ids: [], color: '' });
+  // 「自分宛て」フィルタで照合する配信者のハンドル・呼び名 (spec: 02_chat.md 自分宛てのメッセージ)
+  let mentionKeywords = $state<string[]>([]);
 
   // チャット表示設定
   const MIN_FONT_SIZE = 10;
@@ -36,58 +55,161 @@ function createChatStore() {
   let showTimestamps = $state(true);
   let autoScroll = $state(true);
   let displayLimit = $state<number | null>(null);
+  // 表示の一時停止中は停止時点のメッセージ数（null は停止していない）
+  // 停止中も受信したメッセージは messages に蓄積する (spec: 02_chat.md 表示の一時停止)
+  let displayPausedAt = $state<number | null>(null);
   let scrollToLatestTrigger = $state(0); // インクリメントでスクロールをトリガー
 
+  // ローカルモデレーションで非表示になったメッセージ数（0 の間はフィルタの高速パスを維持する）
+  let hiddenMessageCount = $state(0);
+
   // O(1)検索のための重複チェック用セット（複合キー: connection_id:message_id）
   let messageIds = new SvelteSet<string>();
 
+  // 対応済みにしたメッセージ（複合キー: connection_id:message_id、セッション中のみ保持）
+  let answeredKeys = new SvelteSet<string>();
+
   // O(1)ビューワーメッセージ検索のためのチャンネルIDインデックス
   let messagesByChannel = new SvelteMap<string, ChatMessage[]>();
 
-  // フィルターがデフォルト状態かどうか（全タイプ表示かつ検索クエリなし）
+  // 検索用の正規化済みテキストインデックス（複合キー: connection_id:message_id）
+  // 表示用の元テキストは変更せず、検索時の比較にのみ使用する
+  const normalizedIndex = new Map<string, { content: string; author: string }>();
+
+  function getNormalizedText(msg: ChatMessage): { content: string; author: string } {
+    const key = messageKey(msg);
+    let entry = normalizedIndex.get(key);
+    if (!entry) {
+      entry = { content: normalizeForSearch(msg.content), author: normalizeForSearch(msg.author) };
+      normalizedIndex.set(key, entry);
+    }
+    return entry;
+  }
+
+  // 正規化済みの本文・投稿者名が、正規化済みの query を含むか（検索フィルタ用）
+  function normalizedTextIncludes(msg: ChatMessage, query: string, field: 'content' | 'author'): boolean {
+    return getNormalizedText(msg)[field].includes(query);
+  }
+
+  // フィルターがデフォルト状態かどうか（全タイプ表示・検索クエリなし・チャンネル指定なし・非表示メッセージなし）
   let isDefaultFilter = $derived(
-    filter.showText && filter.showSuperchat && filter.showMembership && !filter.searchQuery
+    hiddenMessageCount === 0 &&
+      filter.showText &&
+      filter.showSuperchat &&
+      filter.showMembership &&
+      !filter.searchQuery &&
+      !filter.contentRegex &&
+      filter.blockedChannelIds.length === 0 &&
+      filter.allowedChannelIds === null &&
+      !filter.mentionsOnly
+  );
+
+  // チャンネルIDの照合用セット（メッセージごとに配列を走査しない）
+  let blockedChannelSet = $derived(new Set(filter.blockedChannelIds));
+  let allowedChannelSet = $derived(
+    filter.allowedChannelIds === null ? null : new Set(filter.allowedChannelIds)
   );
 
-  // 派生状態：フィルタ済みメッセージ（カウント表示用）
+  // 本文の正規表現（フィルタ変更時のみ再コンパイル）
+  let contentRegexResult = $derived(
+    compileContentRegex(filter.contentRegex, filter.caseInsensitive)
+  );
+  // 不正なパターンはフィルタに使わず、エラーとして GUI に表示する
+  let contentRegex = $derived(contentRegexResult.ok ? contentRegexResult.regex : null);
+  let contentRegexError = $derived(contentRegexResult.ok ? null : contentRegexResult.error);
+
+  // 「自分宛て」のプリセット（接続先の配信者は接続時のメタデータから取る）
+  let mentionsPreset = $derived(
+    presetMentions(
+      [...connections.values()].map((c) => ({ channelId: c.broadcasterChannelId, name: c.broadcasterName })),
+      mentionKeywords
+    )
+  );
+
+  // メッセージが現在のフィルタに一致するか（$derived.by の中から呼ぶ）
+  function matchesFilter(msg: ChatMessage): boolean {
+    // ローカルモデレーションの非表示ルールに一致したメッセージ (spec: 02_chat.md)
+    if (msg.moderation?.includes('hide')) return false;
+
+    // 投稿者のチャンネルでフィルタ（システムメッセージは対象外）
+    if (msg.message_type !== 'system') {
+      if (blockedChannelSet.has(msg.channel_id)) return false;
+      if (allowedChannelSet !== null && !allowedChannelSet.has(msg.channel_id)) return false;
+    }
+
+    // メッセージタイプでフィルタ
+    if (!filter.showText && msg.message_type === 'text') return false;
+    if (
+      !filter.showSuperchat &&
+      (msg.message_type === 'superchat' || msg.message_type === 'supersticker')
+    )
+      return false;
+    if (
+      !filter.showMembership &&
+      (msg.message_type === 'membership' || msg.message_type === 'membership_gift')
+    )
+      return false;
+
+    // 本文の正規表現でフィルタ
+    if (contentRegex !== null && !contentRegex.test(msg.content)) return false;
+
+    // 「自分宛て」のメッセージでフィルタ
+    if (filter.mentionsOnly && !matchesMentions(mentionsPreset, msg, getNormalizedText(msg).content)) return false;
+
+    // 検索クエリでフィルタ
+    if (filter.searchQuery) {
+      if (filter.normalizeWidth) {
+        const query = normalizeForSearch(filter.searchQuery);
+        return normalizedTextIncludes(msg, query, 'content') || normalizedTextIncludes(msg, query, 'author');
+      }
+      const query = filter.searchQuery.toLowerCase();
+      return msg.content.toLowerCase().includes(query) || msg.author.toLowerCase().includes(query);
+    }
+
+    return true;
+  }
+
+  // 注目ワード・投稿者の照合用（ルール変更時のみ作り直す）
+  let watchRules = $derived(compileRules(highlightRules.channel_ids, highlightRules.keywords));
+
+  // メッセージが注目ワード・投稿者のルールに一致するか（システムメッセージは対象外）
+  function isWatched(msg: ChatMessage): boolean {
+    return matchesRules(watchRules, msg, getNormalizedText(msg).content);
+  }
+
+  // 派生状態：フィルタ済みメッセージ（カウント表示用、一時停止中に受信した分も含む）
   let filteredMessages = $derived.by(() => {
     if (isDefaultFilter) {
       return messages; // O(1)：参照をそのまま返す
     }
-    return messages.filter((msg) => {
-      // メッセージタイプでフィルタ
-      if (!filter.showText && msg.message_type === 'text') return false;
-      if (
-        !filter.showSuperchat &&
-        (msg.message_type === 'superchat' || msg.message_type === 'supersticker')
-      )
-        return false;
-      if (
-        !filter.showMembership &&
-        (msg.message_type === 'membership' || msg.message_type === 'membership_gift')
-      )
-        return false;
-
-      // 検索クエリでフィルタ
-      if (filter.searchQuery) {
-        const query = filter.searchQuery.toLowerCase();
-        return (
-          msg.content.toLowerCase().includes(query) || msg.author.toLowerCase().includes(query)
-        );
-      }
+    return messages.filter(matchesFilter);
+  });
 
-      return true;
-    });
+  // 派生状態：表示対象のメッセージ（displayLimit適用前）
+  // 一時停止中は停止時点までのメッセージだけを対象にする
+  let displaySource = $derived.by(() => {
+    if (displayPausedAt === null) {
+      return filteredMessages;
+    }
+    const frozen = messages.slice(0, displayPausedAt);
+    return isDefaultFilter ? frozen : frozen.filter(matchesFilter);
   });
 
   // 派生状態：表示メッセージ（displayLimit適用済み、レンダリング用）
   let displayedMessages = $derived.by(() => {
     if (displayLimit !== null) {
-      return filteredMessages.slice(-displayLimit);
+      return displaySource.slice(-displayLimit);
     }
-    return filteredMessages;
+    return displaySource;
   });
 
+  // アーカイブ件数：displayLimit で表示から外れたメッセージ数
+  // displayLimit を縮めても messages からは削除しないため、外れた分はアーカイブに残る
+  let archivedCount = $derived(displaySource.length - displayedMessages.length);
+
+  // 一時停止中に受信して未表示のメッセージ数
+  let pausedBacklog = $derived(displayPausedAt === null ? 0 : messages.length - displayPausedAt);
+
   // メッセージバッチング（高ボリームストリーム用）
   let pendingMessages: ChatMessage[] = [];
   let batchTimeout: ReturnType<typeof setTimeout> | null = null;
@@ -96,24 +218,29 @@ function createChatStore() {
   function flushPendingMessages(): void {
     if (pendingMessages.length === 0) return;
 
+    // バッチ内は timestamp_usec → メッセージID の順に揃える（表示済みの行は並べ替えない）
+    pendingMessages.sort(compareMessageOrder);
     for (const msg of pendingMessages) {
       // 複合キー（connection_id:message_id）で重複排除
-      const key = `${msg.connection_id}:${msg.id}`;
-      messageIds.add(key);
+      messageIds.add(messageKey(msg));
       // チャンネルインデックスを更新
       const arr = messagesByChannel.get(msg.channel_id);
       if (arr) arr.push(msg);
       else messagesByChannel.set(msg.channel_id, [msg]);
+      // 検索用の正規化インデックスを事前構築
+      getNormalizedText(msg);
+      if (msg.moderation?.includes('hide')) hiddenMessageCount++;
     }
     messages.push(...pendingMessages);
     pendingMessages = [];
     batchTimeout = null;
+    trimToHotArchive();
   }
 
   function addMessage(message: ChatMessage): void {
     // 複合キー（connection_id:message_id）でO(1)重複チェック
-    const key = `${message.connection_id}:${message.id}`;
-    if (messageIds.has(key) || pendingMessages.some((m) => `${m.connection_id}:${m.id}` === key)) {
+    const key = messageKey(message);
+    if (messageIds.has(key) || pendingMessages.some((m) => messageKey(m) === key)) {
       return;
     }
 
@@ -125,6 +252,52 @@ function createChatStore() {
     }
   }
 
+  // 配信中に削除されたメッセージを取り除く (spec: 02_chat.md 削除されたメッセージ)
+  // 重複排除キー（messageIds）は残し、同じメッセージが再送されても表示しない
+  function removeMessages(connectionId: bigint, ids: string[]): void {
+    const keys = new Set(ids.map((id) => messageKey({ connection_id: connectionId, id })));
+    pendingMessages = pendingMessages.filter((m) => !keys.has(messageKey(m)));
+
+    const removed = messages.filter((m) => keys.has(messageKey(m)));
+    if (removed.length === 0) return;
+
+    // 一時停止中は停止時点までに表示していた分だけ停止位置を戻す
+    if (displayPausedAt !== null) {
+      displayPausedAt -= messages.slice(0, displayPausedAt).filter((m) => keys.has(messageKey(m))).length;
+    }
+    messages = messages.filter((m) => !keys.has(messageKey(m)));
+    forgetMessages(removed);
+  }
+
+  // ディスクアーカイブ有効時はメモリに残すメッセージを hot_size 件までにする
+  // (spec: 02_chat.md メッセージのディスクアーカイブ)
+  // 押し出したメッセージはバックエンドがアーカイブに書き出しており、searchArchiveByContent / searchArchiveByAuthor で検索できる
+  function trimToHotArchive(): void {
+    const limit = configStore.hotArchiveLimit;
+    if (!limit || messages.length <= limit) return;
+
+    const excess = messages.length - limit;
+    const evicted = messages.slice(0, excess);
+    messages = messages.slice(excess);
+    if (displayPausedAt !== null) {
+      displayPausedAt = Math.max(0, displayPausedAt - excess);
+    }
+    forgetMessages(evicted);
+  }
+
+  // messages から外したメッセージの索引を消す（重複排除キーは残す）
+  function forgetMessages(removed: ChatMessage[]): void {
+    for (const msg of removed) {
+      const key = messageKey(msg);
+      normalizedIndex.delete(key);
+      answeredKeys.delete(key);
+      if (msg.moderation?.includes('hide')) hiddenMessageCount--;
+      const rest = (messagesByChannel.get(msg.channel_id) ?? []).filter((m) => messageKey(m) !== key);
+      if (rest.length > 0) messagesByChannel.set(msg.channel_id, rest);
+      else messagesByChannel.delete(msg.channel_id);
+    }
+  }
+
   // アクション
   // 接続中エントリの仮IDカウンタ（API応答前に一意なキーが必要）
   let nextTempConnId = -1;
@@ -179,7 +352,7 @@ function createChatStore() {
       next.delete(tempId);
       connections = next;
 
-      error = e instanceof Error ? e.message : String(e);
+      error = errorMessage(e);
       return {
         success: false,
         stream_title: null,
@@ -253,7 +426,11 @@ function createChatStore() {
       messages = [];
       messageIds.clear();
       messagesByChannel.clear();
+      normalizedIndex.clear();
+      answeredKeys.clear();
       pendingMessages = [];
+      hiddenMessageCount = 0;
+      displayPausedAt = null;
       error = null;
     }
   }
@@ -274,11 +451,70 @@ function createChatStore() {
     filter = { ...filter, ...newFilter };
   }
 
+  // ブロック/許可リストは再起動後も維持するため設定ファイルにも保存する (spec: 09_config.md)
+  function setBlockedChannelIds(ids: string[]): void {
+    const unique = [...new Set(ids)];
+    filter = { ...filter, blockedChannelIds: unique };
+    configStore.setBlockedChannelIds(unique);
+  }
+
+  function setAllowedChannelIds(ids: string[] | null): void {
+    const unique = ids === null ? null : [...new Set(ids)];
+    filter = { ...filter, allowedChannelIds: unique };
+    configStore.setAllowedChannelIds(unique);
+  }
+
+  // 注目ワード・投稿者のルールも設定ファイルに保存する (spec: 09_config.md)
+  function setHighlightRules(rules: HighlightRules): void {
+    const clean = (items: string[]) => [...new Set(items.map((item) => item.trim()).filter((item) => item))];
+    const next = { keywords: clean(rules.keywords), channel_ids: clean(rules.channel_ids), color: rules.color };
+    highlightRules = next;
+    configStore.setHighlightRules(next);
+  }
+
+  function setMentionKeywords(keywords: string[]): void {
+    const next = [...new Set(keywords.map((k) => k.trim()).filter((k) => k))];
+    mentionKeywords = next;
+    configStore.setMentionKeywords(next);
+  }
+
   function clearMessages(): void {
     messages = [];
     messageIds.clear();
     messagesByChannel.clear();
+    normalizedIndex.clear();
+    answeredKeys.clear();
     pendingMessages = [];
+    hiddenMessageCount = 0;
+    // 一時停止中なら空の状態から停止を続ける
+    if (displayPausedAt !== null) displayPausedAt = 0;
+  }
+
+  // メッセージの対応済みを切り替える（対応済みのメッセージは薄く表示する）
+  function toggleAnswered(message: ChatMessage): void {
+    const key = messageKey(message);
+    if (answeredKeys.has(key)) {
+      answeredKeys.delete(key);
+    } else {
+      answeredKeys.add(key);
+    }
+  }
+
+  function isAnswered(message: ChatMessage): boolean {
+    return answeredKeys.has(messageKey(message));
+  }
+
+  // 表示の一時停止（接続は維持し、受信したメッセージは蓄積を続ける）
+  function pauseDisplay(): void {
+    if (displayPausedAt !== null) return;
+    displayPausedAt = messages.length;
+  }
+
+  // 表示の再開（蓄積したメッセージを displayLimit に従って表示する）
+  function resumeDisplay(): void {
+    if (displayPausedAt === null) return;
+    displayPausedAt = null;
+    scrollToLatestTrigger++;
   }
 
   function setFontSize(size: number): void {
@@ -312,6 +548,10 @@ function createChatStore() {
     displayLimit = limit;
   }
 
+  function dismissBigDonation(): void {
+    bigDonation = null;
+  }
+
   function getMessagesForChannel(channelId: string): ChatMessage[] {
     return messagesByChannel.get(channelId) || [];
   }
@@ -351,16 +591,53 @@ function createChatStore() {
         // 意図的切断 — disconnect() の finally で処理されるため何もしない
       } else {
         // 監視タスクの異常終了等 — 接続を削除してエラーを表示
+        // （メンバー限定の拒否は CommandError の JSON で届くため、接続時と同じ案内にする）
         const next = new SvelteMap(connections);
         next.delete(connId);
         connections = next;
-        error = result.error;
+        error = result.error === null ? null : errorMessage(result.error);
       }
     });
 
+    // 高額スーパーチャットイベントを購読 (spec: 02_chat.md)
+    const unlistenBigDonation = await listen<BigDonationEvent>('chat:big_donation', (event) => {
+      bigDonation = event.payload;
+    });
+
+    // キーワード通知を購読 (spec: 02_chat.md)
+    const unlistenNotification = await listen<ChatNotificationEvent>('chat:notification', (event) => {
+      void showChatNotification(event.payload, configStore.config.notifications.sound);
+    });
+
+    // 接続の健全性の変化を購読 (spec: 02_chat.md)
+    const unlistenHealth = await listen<ConnectionHealthEvent>('chat:connection_health', (event) => {
+      const connId = Number(event.payload.connection_id);
+      const conn = connections.get(connId);
+      if (!conn) return;
+      const next = new SvelteMap(connections);
+      const { level, rate_limit, poll_interval_ms, processing_queue } = event.payload.health;
+      next.set(connId, {
+        ...conn,
+        health: level,
+        rateLimit: rate_limit,
+        pollIntervalMs: poll_interval_ms,
+        processingQueue: processing_queue
+      });
+      connections = next;
+    });
+
+    // 削除されたメッセージを購読 (spec: 02_chat.md)
+    const unlistenDeleted = await listen<MessageDeletedEvent>('chat:message_deleted', (event) => {
+      removeMessages(event.payload.connection_id, event.payload.message_ids);
+    });
+
     unlisten = () => {
       unlistenMessage();
       unlistenConnection();
+      unlistenBigDonation();
+      unlistenNotification();
+      unlistenHealth();
+      unlistenDeleted();
     };
   }
 
@@ -391,7 +668,11 @@ function createChatStore() {
           broadcasterName: info.broadcaster_name,
           broadcasterChannelId: info.broadcaster_channel_id,
           connectionState: info.is_monitoring ? 'connected' : 'disconnecting',
-          color: getConnectionColor(info.broadcaster_channel_id || String(connId))
+          color: getConnectionColor(info.broadcaster_channel_id || String(connId)),
+          health: info.health.level,
+          rateLimit: info.health.rate_limit,
+          pollIntervalMs: info.health.poll_interval_ms,
+          processingQueue: info.health.processing_queue
         });
       }
       connections = next;
@@ -406,6 +687,14 @@ function createChatStore() {
       messageFontSize = configStore.messageFontSize;
       showTimestamps = configStore.showTimestamps;
       autoScroll = configStore.autoScrollEnabled;
+      filter = {
+        ...filter,
+        blockedChannelIds: [...configStore.blockedChannelIds],
+        allowedChannelIds: configStore.allowedChannelIds && [...configStore.allowedChannelIds]
+      };
+      const rules = configStore.highlightRules;
+      highlightRules = { keywords: [...rules.keywords], channel_ids: [...rules.channel_ids], color: rules.color };
+      mentionKeywords = [...configStore.mentionKeywords];
     }
   }
 
@@ -457,6 +746,26 @@ function createChatStore() {
     get filter() {
       return filter;
     },
+    /** contentRegex のコンパイルエラー（有効なパターン・未指定なら null） */
+    get bigDonation() {
+      return bigDonation;
+    },
+    get hiddenMessageCount() {
+      return hiddenMessageCount;
+    },
+    get contentRegexError() {
+      return contentRegexError;
+    },
+    get highlightRules() {
+      return highlightRules;
+    },
+    get mentionKeywords() {
+      return mentionKeywords;
+    },
+    /** 注目ワードの強調色（未指定ならテーマの色） */
+    get highlightColor() {
+      return highlightRules.color || 'var(--info)';
+    },
     get messageFontSize() {
       return messageFontSize;
     },
@@ -466,6 +775,15 @@ function createChatStore() {
     get isPaused() {
       return isPaused;
     },
+    get isDisplayPaused() {
+      return displayPausedAt !== null;
+    },
+    get pausedBacklog() {
+      return pausedBacklog;
+    },
+    get archivedCount() {
+      return archivedCount;
+    },
     // 後方互換のため残す（多接続では常に'idle'か'connected'相当）
     get connectionState() {
       if (connections.size === 0) return 'idle' as const;
@@ -490,9 +808,16 @@ function createChatStore() {
     disconnectAll,
     pause,
     resume,
+    pauseDisplay,
+    resumeDisplay,
     initialize,
     setChatMode: setChatModeAction,
     setFilter,
+    setBlockedChannelIds,
+    setAllowedChannelIds,
+    setHighlightRules,
+    setMentionKeywords,
+    isWatched,
     clearMessages,
     setFontSize,
     increaseFontSize,
@@ -502,6 +827,9 @@ function createChatStore() {
     scrollToLatest,
     setDisplayLimit,
     getMessagesForChannel,
+    toggleAnswered,
+    isAnswered,
+    dismissBigDonation,
     setupEventListeners,
     cleanup,
     initDisplaySettings,
@@ -1,19 +1,100 @@
 // Config state management using Svelte 5 runes
-import type { Config, StorageMode, Theme } from '$lib/types';
+import type {
+  Config,
+  DiscordConfig,
+  HighlightRules,
+  RevenueGoal,
+  StorageMode,
+  Theme
+} from '$lib/types';
 import * as configApi from '$lib/tauri/config';
 
 // ファクトリ関数：テスト時に独立したストアインスタンスを生成できる
 function createConfigStore() {
   // リアクティブ状態
   let config = $state<Config>({
-    storage: { mode: 'secure' },
+    storage: { mode: 'secure', active_profile: null, profiles: [] },
     chat_display: {
       message_font_size: 13,
       show_timestamps: true,
-      auto_scroll_enabled: true
+      auto_scroll_enabled: true,
+      blocked_channel_ids: [],
+      allowed_channel_ids: null,
+      highlight: { keywords: [], channel_ids: [], color: '' },
+      mention_keywords: []
     },
     ui: {
       theme: 'dark'
+    },
+    monitoring: {
+      follow_next_live: false,
+      next_live_poll_interval_secs: 60,
+      stall_threshold_secs: 60,
+      poll_interval_override_ms: null,
+      unknown_actions_file: null,
+      processing_queue_capacity: 1000,
+      processing_queue_policy: 'block'
+    },
+    donation_alert: {
+      enabled: true,
+      threshold: 10000,
+      base_currency: 'JPY'
+    },
+    currency: {
+      base_currency: 'JPY',
+      rates: {}
+    },
+    milestones: {
+      enabled: false,
+      unique_chatters: [100],
+      super_chat_total: [50000],
+      stream_duration_minutes: [60],
+      tts: false
+    },
+    revenue_goal: {
+      target: 0,
+      currency: 'JPY'
+    },
+    notifications: {
+      enabled: false,
+      keywords: [],
+      on_superchat: false,
+      on_membership: false,
+      sound: null,
+      cooldown_secs: 10
+    },
+    engagement: {
+      snapshot_interval_secs: 60,
+      max_snapshots: 360
+    },
+    discord: {
+      enabled: false,
+      webhook_url: '',
+      big_donations: true,
+      memberships: true,
+      milestones: true,
+      donation_template: '**{author}** さんから {amount} のスーパーチャット\n{message}',
+      membership_template: '**{author}** さんが{detail}\n{message}',
+      milestone_template: '{message}'
+    },
+    local_server: {
+      enabled: false,
+      port: 8780
+    },
+    archive: {
+      archive_to_disk: null,
+      hot_size: 1000
+    },
+    innertube: {
+      client_name: 'WEB',
+      client_version: '2.20240101.00.00',
+      user_agent: 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36',
+      gl: 'US',
+      hl: 'en'
+    },
+    proxy: {
+      url: null,
+      auth: null
     }
   });
   let isLoaded = $state(false);
@@ -88,6 +169,46 @@ function createConfigStore() {
     }
   }
 
+  async function setBlockedChannelIds(ids: string[]): Promise<void> {
+    config.chat_display.blocked_channel_ids = ids;
+    try {
+      await configApi.configSetValue('chat_display', 'blocked_channel_ids', ids);
+      error = null;
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
+  async function setAllowedChannelIds(ids: string[] | null): Promise<void> {
+    config.chat_display.allowed_channel_ids = ids;
+    try {
+      await configApi.configSetValue('chat_display', 'allowed_channel_ids', ids);
+      error = null;
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
+  async function setHighlightRules(rules: HighlightRules): Promise<void> {
+    config.chat_display.highlight = rules;
+    try {
+      await configApi.configSetValue('chat_display', 'highlight', rules);
+      error = null;
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
+  async function setMentionKeywords(keywords: string[]): Promise<void> {
+    config.chat_display.mention_keywords = keywords;
+    try {
+      await configApi.configSetValue('chat_display', 'mention_keywords', keywords);
+      error = null;
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
   async function setTheme(theme: Theme): Promise<void> {
     config.ui.theme = theme;
     applyTheme(theme);
@@ -99,6 +220,50 @@ function createConfigStore() {
     }
   }
 
+  async function setPollIntervalOverride(ms: number | null): Promise<void> {
+    // 範囲バリデーション (500-30000ms)、null はサーバーの timeoutMs に従う
+    const clamped = ms === null ? null : Math.max(500, Math.min(30000, Math.round(ms)));
+    config.monitoring.poll_interval_override_ms = clamped;
+    try {
+      await configApi.configSetValue('monitoring', 'poll_interval_override_ms', clamped);
+      error = null;
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
+  // 通貨コードはバックエンドで検証して大文字に揃える（spec: 07_revenue.md 収益目標）
+  async function setRevenueGoal(goal: RevenueGoal): Promise<void> {
+    const target = Math.max(0, goal.target);
+    const currency = goal.currency.trim().toUpperCase();
+    config.revenue_goal = { target, currency };
+    try {
+      await configApi.configSetValue('revenue_goal', 'currency', currency);
+      await configApi.configSetValue('revenue_goal', 'target', target);
+      error = null;
+    } catch (e) {
+      error = e instanceof Error ? e.message : String(e);
+    }
+  }
+
+  // Webhook URL の形式はバックエンドで検証し、拒否されたら元の値に戻す
+  async function setDiscordValue<K extends keyof DiscordConfig>(
+    key: K,
+    value: DiscordConfig[K]
+  ): Promise<boolean> {
+    const previous = config.discord[key];
+    config.discord[key] = value;
+    try {
+      await configApi.configSetValue('discord', key, value);
+      error = null;
+      return true;
+    } catch (e) {
+      config.discord[key] = previous;
+      error = e instanceof Error ? e.message : String(e);
+      return false;
+    }
+  }
+
   function applyTheme(theme: Theme): void {
     if (typeof document !== 'undefined') {
       document.documentElement.setAttribute('data-theme', theme);
@@ -122,9 +287,34 @@ function createConfigStore() {
     get autoScrollEnabled() {
       return config.chat_display.auto_scroll_enabled;
     },
+    get blockedChannelIds() {
+      return config.chat_display.blocked_channel_ids ?? [];
+    },
+    get allowedChannelIds() {
+      return config.chat_display.allowed_channel_ids ?? null;
+    },
+    get highlightRules() {
+      return config.chat_display.highlight ?? { keywords: [], channel_ids: [], color: '' };
+    },
+    get mentionKeywords() {
+      return config.chat_display.mention_keywords ?? [];
+    },
     get theme() {
       return config.ui.theme;
     },
+    get pollIntervalOverrideMs() {
+      return config.monitoring.poll_interval_override_ms ?? null;
+    },
+    // ディスクアーカイブ有効時にメモリに残すメッセージ数（無効なら null）
+    get hotArchiveLimit() {
+      return config.archive?.archive_to_disk ? config.archive.hot_size : null;
+    },
+    get revenueGoal() {
+      return config.revenue_goal ?? { target: 0, currency: 'JPY' };
+    },
+    get discord() {
+      return config.discord;
+    },
     get isLoaded() {
       return isLoaded;
     },
@@ -139,7 +329,14 @@ function createConfigStore() {
     setMessageFontSize,
     setShowTimestamps,
     setAutoScrollEnabled,
-    setTheme
+    setBlockedChannelIds,
+    setAllowedChannelIds,
+    setHighlightRules,
+    setMentionKeywords,
+    setTheme,
+    setPollIntervalOverride,
+    setRevenueGoal,
+    setDiscordValue
   };
 }
 
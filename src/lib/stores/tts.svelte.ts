@@ -1,6 +1,6 @@
 // TTS store
 
-import type { TtsConfig, TtsStatus, TtsPriority, TtsLaunchStatus } from '$lib/types';
+import type { TtsConfig, TtsStatus, TtsPriority, TtsLaunchStatus, VoicevoxSpeaker } from '$lib/types';
 import { defaultTtsConfig } from '$lib/types';
 import * as ttsApi from '$lib/tauri/tts';
 
@@ -188,6 +188,16 @@ function createTtsStore() {
       }
     },
 
+    async getVoicevoxSpeakers(): Promise<VoicevoxSpeaker[] | null> {
+      error = null;
+      try {
+        return await ttsApi.ttsGetVoicevoxSpeakers();
+      } catch (e) {
+        error = toErrorMessage(e);
+        return null;
+      }
+    },
+
     async selectExe(): Promise<string | null> {
       try {
         return await ttsApi.ttsSelectExe();
@@ -1,14 +1,40 @@
 // アナリティクス関連の Tauri コマンドラッパー
 import { invoke } from '@tauri-apps/api/core';
-import type { RevenueAnalytics, ExportConfig } from '$lib/types';
+import type {
+  RevenueAnalytics,
+  ExportConfig,
+  LeaderboardExportConfig,
+  ImportedSessionAnalytics,
+  RecomputedAnalytics
+} from '$lib/types';
 import { normalizeError } from './errors';
 
 /**
  * 現在のセッションの収益アナリティクスを取得する
+ * @param timezone 時間別統計を区切る IANA タイムゾーン名（省略時はシステムのローカルタイムゾーン）
  */
-export async function getRevenueAnalytics(): Promise<RevenueAnalytics> {
+export async function getRevenueAnalytics(timezone?: string): Promise<RevenueAnalytics> {
   try {
-    return await invoke('get_revenue_analytics');
+    return await invoke('get_revenue_analytics', { timezone });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 現在のセッションのトレンドワードを取得する
+ * @param windowSecs 最新メッセージの投稿時刻から遡る秒数
+ * @param topN 返す語の最大件数
+ * @param extraStopwords 既定のストップワードに追加で除外する語
+ * @returns [語, 出現メッセージ数] の配列（多い順）
+ */
+export async function getTrendingTerms(
+  windowSecs: number,
+  topN: number,
+  extraStopwords?: string[]
+): Promise<[string, number][]> {
+  try {
+    return await invoke('get_trending_terms', { windowSecs, topN, extraStopwords });
   } catch (e) {
     throw normalizeError(e);
   }
@@ -25,6 +51,36 @@ export async function getSessionAnalytics(sessionId: string): Promise<RevenueAna
   }
 }
 
+/**
+ * データベースに保存したセッションの収益アナリティクスとエンゲージメントの推移を再計算する
+ * 進捗は analytics:recompute_progress イベントで届く。キャンセルされた場合は null
+ * @param timezone 時間別統計を区切る IANA タイムゾーン名（省略時はシステムのローカルタイムゾーン）
+ */
+export async function recomputeSessionAnalytics(
+  sessionId: string,
+  timezone?: string
+): Promise<RecomputedAnalytics | null> {
+  try {
+    return await invoke<RecomputedAnalytics | null>('recompute_session_analytics', {
+      sessionId,
+      timezone
+    });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 実行中の再計算をキャンセルする
+ */
+export async function cancelAnalyticsRecompute(): Promise<void> {
+  try {
+    return await invoke('cancel_analytics_recompute');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
 /**
  * セッションデータをファイルにエクスポートする
  */
@@ -53,3 +109,40 @@ export async function exportCurrentMessages(
     throw normalizeError(e);
   }
 }
+
+/**
+ * 現在のセッションメッセージのエクスポート件数を見積もる（絞り込み・max_records 適用後）
+ */
+export async function estimateCurrentExport(config: ExportConfig): Promise<number> {
+  try {
+    return await invoke<number>('estimate_current_export', { config });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 現在のセッションの貢献者ランキングをファイルにエクスポートする
+ */
+export async function exportContributorLeaderboard(
+  filePath: string,
+  config: LeaderboardExportConfig
+): Promise<void> {
+  try {
+    return await invoke('export_contributor_leaderboard', { filePath, config });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 以前エクスポートした CSV / JSON ファイルからアナリティクスを作り直す
+ * 読み込めなかったレコードは warnings に入る
+ */
+export async function importSessionFile(filePath: string): Promise<ImportedSessionAnalytics> {
+  try {
+    return await invoke<ImportedSessionAnalytics>('import_session_file', { filePath });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
@@ -1,7 +1,13 @@
 // 認証関連の Tauri コマンドラッパー
 
 import { invoke } from '@tauri-apps/api/core';
-import type { AuthStatus, SessionValidity } from '$lib/types';
+import type {
+  AuthProfiles,
+  AuthStatus,
+  DeviceCode,
+  OAuthPollStatus,
+  SessionValidity
+} from '$lib/types';
 import { normalizeError } from './errors';
 
 export async function authGetStatus(): Promise<AuthStatus> {
@@ -89,3 +95,59 @@ export async function authOpenWindow(): Promise<void> {
     throw normalizeError(e);
   }
 }
+
+export async function authOAuthStart(): Promise<DeviceCode> {
+  try {
+    return await invoke('auth_oauth_start');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function authOAuthPoll(deviceCode: string): Promise<OAuthPollStatus> {
+  try {
+    return await invoke('auth_oauth_poll', { deviceCode });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function authOAuthLogout(): Promise<void> {
+  try {
+    await invoke('auth_oauth_logout');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function authListProfiles(): Promise<AuthProfiles> {
+  try {
+    return await invoke('auth_list_profiles');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function authSaveProfile(name: string): Promise<void> {
+  try {
+    await invoke('auth_save_profile', { name });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function authSwitchProfile(name: string | null): Promise<void> {
+  try {
+    await invoke('auth_switch_profile', { name });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function authDeleteProfile(name: string): Promise<void> {
+  try {
+    await invoke('auth_delete_profile', { name });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
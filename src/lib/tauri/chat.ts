@@ -1,6 +1,6 @@
 // チャット関連の Tauri コマンドラッパー
 import { invoke } from '@tauri-apps/api/core';
-import type { ConnectionResult, ConnectionInfo, ChatMode } from '$lib/types';
+import type { ArchiveSearchPage, ConnectionResult, ConnectionInfo, ChatMode, VideoMetadata } from '$lib/types';
 import { normalizeError } from './errors';
 
 /**
@@ -64,3 +64,38 @@ export async function setChatMode(connectionId: number, mode: ChatMode): Promise
     throw normalizeError(e);
   }
 }
+
+/**
+ * 配信のメタデータ（タイトル・チャンネル・同時視聴者数・配信状態）を取得する
+ */
+export async function getVideoMetadata(url: string): Promise<VideoMetadata> {
+  try {
+    return await invoke('get_video_metadata', { url });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 本文でメッセージを検索する（メモリ上の直近のメッセージ → ディスクアーカイブの順、新しい順）
+ * offset 件目から最大 limit 件と、一致した件数の合計を返す
+ */
+export async function searchArchiveByContent(query: string, offset: number, limit: number): Promise<ArchiveSearchPage> {
+  try {
+    return await invoke('search_archive_by_content', { query, offset, limit });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 投稿者名でメッセージを検索する（メモリ上の直近のメッセージ → ディスクアーカイブの順、新しい順）
+ * offset 件目から最大 limit 件と、一致した件数の合計を返す
+ */
+export async function searchArchiveByAuthor(query: string, offset: number, limit: number): Promise<ArchiveSearchPage> {
+  try {
+    return await invoke('search_archive_by_author', { query, offset, limit });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
@@ -0,0 +1,29 @@
+// エンゲージメントの推移関連の Tauri コマンドラッパー (spec: 02_chat.md)
+
+import { invoke } from '@tauri-apps/api/core';
+import type { EngagementSnapshot } from '$lib/types';
+import { normalizeError } from './errors';
+
+/** 記録したエンゲージメントのスナップショットを古い順に取得する */
+export async function getEngagementSnapshots(): Promise<EngagementSnapshot[]> {
+  try {
+    return await invoke<EngagementSnapshot[]>('get_engagement_snapshots');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 投稿時刻を bucketSecs 秒ごとに区切ったメッセージ数を古い順に取得する（ヒートマップ用）
+ * weighted ならスーパーチャットなどを重み付けした件数。要素は [区間の開始時刻（RFC3339）, 件数]
+ */
+export async function getMessageDensity(
+  bucketSecs: number,
+  weighted = false
+): Promise<[string, number][]> {
+  try {
+    return await invoke<[string, number][]>('get_message_density', { bucketSecs, weighted });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { normalizeError } from './errors';
+import { MEMBERS_ONLY_MESSAGE, errorMessage, normalizeError } from './errors';
 
 describe('normalizeError', () => {
   it('CommandError JSON をパースする', () => {
@@ -163,4 +163,31 @@ describe('normalizeError', () => {
     expect(result.code).toBe('Internal');
     expect(result.message).toBe('[object Object]');
   });
+
+  it('ProxyError はプロキシを直せば再試行できる', () => {
+    const result = normalizeError({
+      kind: 'ProxyError',
+      message: 'Could not connect through proxy http://proxy.example:8080/',
+    });
+    expect(result.code).toBe('ProxyError');
+    expect(result.recoverable).toBe(true);
+  });
+
+  it('正規化済みの AppError はそのまま返す', () => {
+    const appError = { code: 'MembersOnly', message: 'members-only', recoverable: true };
+    expect(normalizeError(appError)).toEqual(appError);
+  });
+});
+
+describe('errorMessage', () => {
+  it('MembersOnly はログインを促す案内にする', () => {
+    const error = { kind: 'MembersOnly', message: 'This stream is members-only' };
+    expect(errorMessage(JSON.stringify(error))).toBe(MEMBERS_ONLY_MESSAGE);
+    expect(errorMessage(normalizeError(error))).toBe(MEMBERS_ONLY_MESSAGE);
+  });
+
+  it('それ以外はメッセージをそのまま返す', () => {
+    const error = { kind: 'ConnectionFailed', message: '接続に失敗' };
+    expect(errorMessage(error)).toBe('接続に失敗');
+  });
 });
@@ -5,12 +5,15 @@
 export type ErrorCode =
   | 'AuthRequired'
   | 'AuthFailed'
+  | 'ProfileNotFound'
   | 'StorageError'
   | 'ConnectionFailed'
   | 'NotConnected'
   | 'DatabaseError'
   | 'NotFound'
   | 'ApiError'
+  | 'MembersOnly'
+  | 'ProxyError'
   | 'TtsError'
   | 'InvalidInput'
   | 'IoError'
@@ -35,8 +38,14 @@ const RECOVERABLE_CODES: Set<ErrorCode> = new Set([
   'NotConnected',
   'TtsError',
   'ApiError',
+  'MembersOnly',
+  'ProxyError',
 ]);
 
+/** メンバー限定配信に接続できなかったときの案内 */
+export const MEMBERS_ONLY_MESSAGE =
+  'メンバー限定の配信です。メンバーのアカウントでログインしてから接続してください';
+
 /**
  * Tauri コマンドの catch ブロックで受け取った任意の値を AppError に正規化する
  *
@@ -45,6 +54,10 @@ const RECOVERABLE_CODES: Set<ErrorCode> = new Set([
  * - null/undefined/その他: Internal として扱う
  */
 export function normalizeError(error: unknown): AppError {
+  if (isAppError(error)) {
+    return error;
+  }
+
   if (typeof error === 'string') {
     try {
       const parsed = JSON.parse(error);
@@ -86,3 +99,24 @@ export function normalizeError(error: unknown): AppError {
   // null / undefined / その他の型
   return { code: 'Internal', message: String(error), recoverable: false };
 }
+
+/** 正規化済みの AppError か（API ラッパーが投げ直したものを二重に正規化しない） */
+function isAppError(error: unknown): error is AppError {
+  if (!error || typeof error !== 'object') return false;
+  const record = error as Record<string, unknown>;
+  return (
+    typeof record.code === 'string' &&
+    typeof record.message === 'string' &&
+    typeof record.recoverable === 'boolean'
+  );
+}
+
+/**
+ * ユーザーに表示するエラーメッセージ
+ *
+ * メンバー限定配信の拒否は「見つからない」と誤解されないよう、ログインを促す案内にする。
+ */
+export function errorMessage(error: unknown): string {
+  const appError = normalizeError(error);
+  return appError.code === 'MembersOnly' ? MEMBERS_ONLY_MESSAGE : appError.message;
+}
@@ -0,0 +1,20 @@
+// 投稿者アイコンキャッシュ関連の Tauri コマンドラッパー (spec: 02_chat.md)
+
+import { invoke } from '@tauri-apps/api/core';
+import { normalizeError } from './errors';
+
+export async function getAuthorIcon(url: string): Promise<string> {
+  try {
+    return await invoke('get_author_icon', { url });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function clearIconCache(): Promise<void> {
+  try {
+    await invoke('clear_icon_cache');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
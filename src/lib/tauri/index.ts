@@ -6,3 +6,8 @@ export * from './websocket';
 export * from './viewer';
 export * from './analytics';
 export * from './tts';
+export * from './moderation';
+export * from './notifications';
+export * from './engagement';
+export * from './icon';
+export * from './replay';
@@ -0,0 +1,21 @@
+// ローカルモデレーション関連の Tauri コマンドラッパー (spec: 02_chat.md)
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ModerationRule } from '$lib/types';
+import { normalizeError } from './errors';
+
+export async function moderationLoadRules(): Promise<ModerationRule[]> {
+  try {
+    return await invoke('moderation_load_rules');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function moderationSaveRules(rules: ModerationRule[]): Promise<void> {
+  try {
+    await invoke('moderation_save_rules', { rules });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
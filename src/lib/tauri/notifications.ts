@@ -0,0 +1,13 @@
+// キーワード通知関連の Tauri コマンドラッパー (spec: 02_chat.md)
+
+import { invoke } from '@tauri-apps/api/core';
+import { normalizeError } from './errors';
+
+/** 設定した通知音を data URL で取得する（未設定なら null） */
+export async function notificationSound(): Promise<string | null> {
+  try {
+    return await invoke('notification_sound');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
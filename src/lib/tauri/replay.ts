@@ -0,0 +1,84 @@
+// 保存済みレスポンスの再生関連の Tauri コマンドラッパー (spec: 05_raw_response.md)
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ChatMessage, ReplayStatus } from '$lib/types';
+import { normalizeError } from './errors';
+
+/**
+ * 保存ファイルの再生を開始する（speed: 再生速度の倍率、0 は待機なし）
+ */
+export async function replayStart(filePath: string, speed?: number): Promise<ReplayStatus> {
+  try {
+    return await invoke<ReplayStatus>('replay_start', { filePath, speed });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function replayPause(): Promise<ReplayStatus> {
+  try {
+    return await invoke<ReplayStatus>('replay_pause');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function replayResume(): Promise<ReplayStatus> {
+  try {
+    return await invoke<ReplayStatus>('replay_resume');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 指定位置（メッセージの順番、0 始まり）から再生し直す
+ */
+export async function replaySeek(position: number): Promise<ReplayStatus> {
+  try {
+    return await invoke<ReplayStatus>('replay_seek', { position });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 指定時刻（UNIX エポックからのマイクロ秒）以降で最初のメッセージから再生し直す
+ */
+export async function replaySeekTime(atUsec: number): Promise<ReplayStatus> {
+  try {
+    return await invoke<ReplayStatus>('replay_seek_time', { atUsec });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+/**
+ * 投稿時刻が [startUsec, endUsec) の再生メッセージを取得する（タイムラインのスクラブ用）
+ */
+export async function replayMessagesInRange(
+  startUsec: number,
+  endUsec: number
+): Promise<ChatMessage[]> {
+  try {
+    return await invoke<ChatMessage[]>('replay_messages_in_range', { startUsec, endUsec });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function replayStop(): Promise<void> {
+  try {
+    await invoke('replay_stop');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
+export async function replayGetStatus(): Promise<ReplayStatus | null> {
+  try {
+    return await invoke<ReplayStatus | null>('replay_get_status');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
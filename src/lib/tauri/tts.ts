@@ -1,7 +1,7 @@
 // TTS 関連の Tauri コマンドラッパー
 
 import { invoke } from '@tauri-apps/api/core';
-import type { TtsConfig, TtsPriority, TtsStatus, TtsLaunchStatus } from '$lib/types';
+import type { TtsConfig, TtsPriority, TtsStatus, TtsLaunchStatus, VoicevoxSpeaker } from '$lib/types';
 import { normalizeError } from './errors';
 
 export interface SpeakOptions {
@@ -55,6 +55,14 @@ export async function ttsTestConnection(backend?: string): Promise<boolean> {
   }
 }
 
+export async function ttsGetVoicevoxSpeakers(): Promise<VoicevoxSpeaker[]> {
+  try {
+    return await invoke('tts_get_voicevox_speakers');
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
 export async function ttsStart(): Promise<void> {
   try {
     await invoke('tts_start');
@@ -2,6 +2,8 @@
 import { invoke } from '@tauri-apps/api/core';
 import type {
   ViewerProfile,
+  ViewerProfilePage,
+  ProfileSort,
   ViewerWithCustomInfo,
   Session,
   ContributorStats,
@@ -44,6 +46,22 @@ export async function viewerGetList(
   }
 }
 
+/**
+ * ブロードキャスターのビューワープロファイルを並び順を指定してページ単位で取得する（総数付き）
+ */
+export async function viewerListProfiles(
+  broadcasterId: string,
+  offset?: number,
+  limit?: number,
+  sort?: ProfileSort
+): Promise<ViewerProfilePage> {
+  try {
+    return await invoke('viewer_list_profiles', { broadcasterId, offset, limit, sort });
+  } catch (e) {
+    throw normalizeError(e);
+  }
+}
+
 /**
  * ビューワーを検索する
  */
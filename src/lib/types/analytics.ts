@@ -4,6 +4,15 @@
 export type { SuperChatTier } from './generated/SuperChatTier';
 export type { SuperChatTierStats } from './generated/SuperChatTierStats';
 export type { RevenueAnalytics } from './generated/RevenueAnalytics';
+export type { RevenueTotal } from './generated/RevenueTotal';
+export type { GoalProgress } from './generated/GoalProgress';
 export type { ContributorInfo } from './generated/ContributorInfo';
 export type { HourlyStats } from './generated/HourlyStats';
 export type { ExportConfig } from './generated/ExportConfig';
+export type { LeaderboardSortOrder } from './generated/LeaderboardSortOrder';
+export type { LeaderboardExportConfig } from './generated/LeaderboardExportConfig';
+export type { LeaderboardEntry } from './generated/LeaderboardEntry';
+export type { ImportedSessionAnalytics } from './generated/ImportedSessionAnalytics';
+export type { ImportWarning } from './generated/ImportWarning';
+export type { RecomputeProgress } from './generated/RecomputeProgress';
+export type { RecomputedAnalytics } from './generated/RecomputedAnalytics';
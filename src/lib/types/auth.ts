@@ -1,7 +1,12 @@
 // 認証関連の型定義
 // Rust型は generated/ から re-export、フロントエンド固有型はここで定義
 
+export type { AuthExpiry } from './generated/AuthExpiry';
+export type { AuthProfiles } from './generated/AuthProfiles';
+export type { AuthSource } from './generated/AuthSource';
 export type { AuthStatus } from './generated/AuthStatus';
+export type { DeviceCode } from './generated/DeviceCode';
+export type { OAuthPollStatus } from './generated/OAuthPollStatus';
 export type { SessionValidity } from './generated/SessionValidity';
 export type { StorageType } from './generated/StorageType';
 
@@ -10,6 +15,7 @@ export type AuthIndicatorState =
   | 'unauthenticated'         // グレー / 鍵（閉）
   | 'authenticated_valid'     // 緑 / 鍵（開）
   | 'authenticated_checking'  // 黄 / スピナー
+  | 'authenticated_expiring'  // 黄 / 警告
   | 'authenticated_invalid'   // 赤 / 警告
   | 'authenticated_error'     // オレンジ / ?
   | 'storage_error';          // 赤 / !
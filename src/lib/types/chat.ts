@@ -1,16 +1,55 @@
 // チャット関連の型定義
 // Rust型は generated/ から re-export、フロントエンド固有型はここで定義
 
+import type { HealthLevel } from './generated/HealthLevel';
+import type { RateLimitStats } from './generated/RateLimitStats';
+import type { ProcessingQueueStats } from './generated/ProcessingQueueStats';
+
 export type { ConnectionResult } from './generated/ConnectionResult';
 export type { ConnectionInfo } from './generated/ConnectionInfo';
 export type { Platform } from './generated/Platform';
 export type { MessageRun } from './generated/MessageRun';
 export type { BadgeInfo } from './generated/BadgeInfo';
+export type { AuthorRole } from './generated/AuthorRole';
 export type { SuperChatColors } from './generated/SuperChatColors';
+export type { StickerImage } from './generated/StickerImage';
 // GuiMessageMetadata を MessageMetadata として re-export（フロントエンドの命名慣習に合わせる）
 export type { GuiMessageMetadata as MessageMetadata } from './generated/GuiMessageMetadata';
 // GuiChatMessage を ChatMessage として re-export
 export type { GuiChatMessage as ChatMessage } from './generated/GuiChatMessage';
+export type { ParsedAmount } from './generated/ParsedAmount';
+export type { BigDonationEvent } from './generated/BigDonationEvent';
+// 接続の健全性 (spec: 02_chat.md)
+export type { HealthLevel } from './generated/HealthLevel';
+export type { ConnectionHealthInfo } from './generated/ConnectionHealthInfo';
+export type { ConnectionHealthEvent } from './generated/ConnectionHealthEvent';
+export type { RateLimitStats } from './generated/RateLimitStats';
+export type { ProcessingQueueStats } from './generated/ProcessingQueueStats';
+// ローカルモデレーション (spec: 02_chat.md)
+export type { MatchSpec } from './generated/MatchSpec';
+export type { ModerationAction } from './generated/ModerationAction';
+export type { ModerationRule } from './generated/ModerationRule';
+// 保存済みレスポンスの再生 (spec: 05_raw_response.md)
+export type { ReplayStatus } from './generated/ReplayStatus';
+// 配信のメタデータ (spec: 02_chat.md)
+export type { LiveStatus } from './generated/LiveStatus';
+export type { VideoMetadata } from './generated/VideoMetadata';
+// 配信終了の検出 (spec: 02_chat.md)
+export type { StreamEndReason } from './generated/StreamEndReason';
+export type { StreamEndedEvent } from './generated/StreamEndedEvent';
+// キーワード通知 (spec: 02_chat.md)
+export type { NotificationReason } from './generated/NotificationReason';
+export type { ChatNotificationEvent } from './generated/ChatNotificationEvent';
+// エンゲージメントの推移 (spec: 02_chat.md)
+export type { EngagementSnapshot } from './generated/EngagementSnapshot';
+// 削除されたメッセージ (spec: 02_chat.md)
+export type { MessageDeletedEvent } from './generated/MessageDeletedEvent';
+// アーカイブ検索の結果 (spec: 02_chat.md メッセージのディスクアーカイブ)
+export type { ArchiveSearchPage } from './generated/ArchiveSearchPage';
+// 配信内の初コメント (spec: 02_chat.md)
+export type { FirstTimeChatterEvent } from './generated/FirstTimeChatterEvent';
+// 収益目標への到達 (spec: 07_revenue.md)
+export type { GoalReachedEvent } from './generated/GoalReachedEvent';
 
 // メッセージタイプ（フロントエンド固有 - Rust側はstringとして送信）
 export type MessageType =
@@ -30,6 +69,18 @@ export interface ChatFilter {
   showSuperchat: boolean;
   showMembership: boolean;
   searchQuery: string;
+  /** 検索時に全角/半角の違いを無視する（NFKC正規化） */
+  normalizeWidth: boolean;
+  /** 常に非表示にする投稿者のチャンネルID */
+  blockedChannelIds: string[];
+  /** 指定時はこのチャンネルIDの投稿者のみ表示する（ブロックも併せて適用） */
+  allowedChannelIds: string[] | null;
+  /** 本文に対する正規表現（空文字で無効） */
+  contentRegex: string;
+  /** contentRegex で大文字小文字を区別しない */
+  caseInsensitive: boolean;
+  /** 「自分宛て」のメッセージのみ表示する（他の条件と併せて適用） */
+  mentionsOnly: boolean;
 }
 
 /** フロントエンド側の接続状態（色情報等を含む） */
@@ -42,4 +93,12 @@ export interface FrontendConnectionState {
   broadcasterChannelId: string;
   connectionState: 'connecting' | 'connected' | 'paused' | 'disconnecting' | 'error';
   color: string;
+  /** ポーリングの健全性（`chat:connection_health` で更新。未受信なら good とみなす） */
+  health?: HealthLevel;
+  /** レート制限（429）の集計（`chat:connection_health` で更新） */
+  rateLimit?: RateLimitStats;
+  /** 実際に使っているポーリング間隔（ミリ秒。`chat:connection_health` で更新。最初のポーリングまでは 0） */
+  pollIntervalMs?: number;
+  /** 取得と処理の間のキューの状態（`chat:connection_health` で更新） */
+  processingQueue?: ProcessingQueueStats;
 }
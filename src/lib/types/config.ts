@@ -6,35 +6,274 @@ export type Theme = 'dark' | 'light';
 
 export interface StorageConfig {
   mode: StorageMode;
+  /** 使用中のアカウントプロファイル（null はデフォルトプロファイル） */
+  active_profile: string | null;
+  /** 保存済みの名前付きプロファイル */
+  profiles: string[];
 }
 
 export interface ChatDisplayConfig {
   message_font_size: number;
   show_timestamps: boolean;
   auto_scroll_enabled: boolean;
+  /** 常に非表示にする投稿者のチャンネルID */
+  blocked_channel_ids: string[];
+  /** 指定時はこのチャンネルIDの投稿者のみ表示する */
+  allowed_channel_ids: string[] | null;
+  /** 常に強調表示するキーワード・投稿者 */
+  highlight: HighlightRules;
+  /** 「自分宛て」フィルタで本文と照合する配信者のハンドル・呼び名 */
+  mention_keywords: string[];
+}
+
+/** 常に強調表示するメッセージの条件（spec: 02_chat.md 注目ワードの強調表示） */
+export interface HighlightRules {
+  /** 本文にいずれかを含むメッセージを強調する（全角/半角・大文字小文字を区別しない） */
+  keywords: string[];
+  /** このチャンネルIDの投稿者のメッセージを強調する */
+  channel_ids: string[];
+  /** 強調色（#rrggbb。空ならテーマの色） */
+  color: string;
 }
 
 export interface UiConfig {
   theme: Theme;
 }
 
+export interface MonitoringConfig {
+  follow_next_live: boolean;
+  next_live_poll_interval_secs: number;
+  stall_threshold_secs: number;
+  /** チャットのポーリング間隔（ミリ秒）。指定するとサーバーの timeoutMs の代わりに使う。null ならサーバーに従う */
+  poll_interval_override_ms: number | null;
+  /** 未対応のアクションの生の JSON を追記する NDJSON ファイル（絶対パス）。null なら保存しない */
+  unknown_actions_file: string | null;
+  /** 取得と処理の間のキューの容量（メッセージ数、10〜100000） */
+  processing_queue_capacity: number;
+  /** キューが満杯のときの動作（block: 取得を待たせる / drop_oldest: 最も古い通常のチャットを破棄する） */
+  processing_queue_policy: 'block' | 'drop_oldest';
+}
+
+export interface DonationAlertConfig {
+  /** 高額スーパーチャットの通知（chat:big_donation）を有効にするか */
+  enabled: boolean;
+  /** 通知する金額のしきい値（base_currency 建て、この金額以上で通知） */
+  threshold: number;
+  /** しきい値の通貨（ISO 4217 コード） */
+  base_currency: string;
+}
+
+export interface CurrencyConfig {
+  /** 金額の合計などを換算する基準通貨（ISO 4217 コード） */
+  base_currency: string;
+  /** 通貨 1 単位あたりの base_currency 建ての値（例: { USD: 150 }） */
+  rates: Record<string, number>;
+}
+
+export interface MilestoneConfig {
+  /** マイルストーンのシステムメッセージを生成するか */
+  enabled: boolean;
+  /** ユニーク投稿者数のしきい値（人） */
+  unique_chatters: number[];
+  /** スーパーチャット合計金額のしきい値（currency.base_currency 建て） */
+  super_chat_total: number[];
+  /** 接続してからの経過時間のしきい値（分） */
+  stream_duration_minutes: number[];
+  /** マイルストーンのメッセージを読み上げるか */
+  tts: boolean;
+}
+
+export interface RevenueGoal {
+  /** 目標金額（currency 建て、0 なら目標なし） */
+  target: number;
+  /** 目標の通貨（ISO 4217 コード） */
+  currency: string;
+}
+
+export interface NotificationConfig {
+  /** 通知を有効にするか */
+  enabled: boolean;
+  /** 通知するキーワード（大文字小文字を区別しない部分一致） */
+  keywords: string[];
+  /** スーパーチャット・スーパーステッカーを通知するか */
+  on_superchat: boolean;
+  /** 新規メンバー・メンバーシップギフトを通知するか */
+  on_membership: boolean;
+  /** 通知音のファイル（wav / mp3 / ogg、null なら鳴らさない） */
+  sound: string | null;
+  /** キーワード通知の最短間隔（秒） */
+  cooldown_secs: number;
+}
+
+export interface EngagementConfig {
+  /** スナップショットを記録する間隔（秒） */
+  snapshot_interval_secs: number;
+  /** 保持するスナップショットの最大件数 */
+  max_snapshots: number;
+}
+
+export interface DiscordConfig {
+  /** Discord への通知を有効にするか */
+  enabled: boolean;
+  /** Webhook の URL（https://discord.com/api/webhooks/...、空文字は未設定） */
+  webhook_url: string;
+  /** 高額スーパーチャット（donation_alert のしきい値以上）を通知するか */
+  big_donations: boolean;
+  /** メンバーシップ（新規・継続・ギフト）を通知するか */
+  memberships: boolean;
+  /** マイルストーンを通知するか */
+  milestones: boolean;
+  /** 高額スーパーチャットの本文（{author} {amount} {message} を置換） */
+  donation_template: string;
+  /** メンバーシップの本文（{author} {detail} {message} を置換） */
+  membership_template: string;
+  /** マイルストーンの本文（{message} を置換） */
+  milestone_template: string;
+}
+
+export interface LocalServerConfig {
+  /** 起動時にローカル SSE フィードを開始するか（local_server feature 有効時のみ） */
+  enabled: boolean;
+  /** 待ち受けるポート（127.0.0.1 のみ、次回起動時に反映） */
+  port: number;
+}
+
+export interface ArchiveConfig {
+  /** 指定するとメモリから外れたメッセージをこのファイル（NDJSON）に追記する（絶対パス） */
+  archive_to_disk: string | null;
+  /** メモリに保持する直近のメッセージ数（ディスクアーカイブ有効時はフロントエンドもこの件数まで） */
+  hot_size: number;
+}
+
+/**
+ * InnerTube リクエストで名乗るクライアント。
+ * YouTube が受け付けない値にするとチャットを取得できなくなるため、通常は変更しない
+ */
+export interface InnerTubeContext {
+  client_name: string;
+  /** 数字をドットで区切った形（例: 2.20240101.00.00） */
+  client_version: string;
+  user_agent: string;
+  gl: string;
+  hl: string;
+}
+
+export interface ProxyAuth {
+  username: string;
+  password: string;
+}
+
+export interface ProxyConfig {
+  /** http / https / socks4 / socks4a / socks5 / socks5h の URL（null なら環境変数 LISCOV_PROXY に従う） */
+  url: string | null;
+  auth: ProxyAuth | null;
+}
+
 export interface Config {
   storage: StorageConfig;
   chat_display: ChatDisplayConfig;
   ui: UiConfig;
+  monitoring: MonitoringConfig;
+  donation_alert: DonationAlertConfig;
+  currency: CurrencyConfig;
+  milestones: MilestoneConfig;
+  revenue_goal: RevenueGoal;
+  notifications: NotificationConfig;
+  engagement: EngagementConfig;
+  discord: DiscordConfig;
+  local_server: LocalServerConfig;
+  archive: ArchiveConfig;
+  innertube: InnerTubeContext;
+  proxy: ProxyConfig;
 }
 
 // Default values
 export const DEFAULT_CONFIG: Config = {
   storage: {
-    mode: 'secure'
+    mode: 'secure',
+    active_profile: null,
+    profiles: []
   },
   chat_display: {
     message_font_size: 13,
     show_timestamps: true,
-    auto_scroll_enabled: true
+    auto_scroll_enabled: true,
+    blocked_channel_ids: [],
+    allowed_channel_ids: null,
+    highlight: { keywords: [], channel_ids: [], color: '' },
+    mention_keywords: []
   },
   ui: {
     theme: 'dark'
+  },
+  monitoring: {
+    follow_next_live: false,
+    next_live_poll_interval_secs: 60,
+    stall_threshold_secs: 60,
+    poll_interval_override_ms: null,
+    unknown_actions_file: null,
+    processing_queue_capacity: 1000,
+    processing_queue_policy: 'block'
+  },
+  donation_alert: {
+    enabled: true,
+    threshold: 10000,
+    base_currency: 'JPY'
+  },
+  currency: {
+    base_currency: 'JPY',
+    rates: {}
+  },
+  milestones: {
+    enabled: false,
+    unique_chatters: [100],
+    super_chat_total: [50000],
+    stream_duration_minutes: [60],
+    tts: false
+  },
+  revenue_goal: {
+    target: 0,
+    currency: 'JPY'
+  },
+  notifications: {
+    enabled: false,
+    keywords: [],
+    on_superchat: false,
+    on_membership: false,
+    sound: null,
+    cooldown_secs: 10
+  },
+  engagement: {
+    snapshot_interval_secs: 60,
+    max_snapshots: 360
+  },
+  discord: {
+    enabled: false,
+    webhook_url: '',
+    big_donations: true,
+    memberships: true,
+    milestones: true,
+    donation_template: '**{author}** さんから {amount} のスーパーチャット\n{message}',
+    membership_template: '**{author}** さんが{detail}\n{message}',
+    milestone_template: '{message}'
+  },
+  local_server: {
+    enabled: false,
+    port: 8780
+  },
+  archive: {
+    archive_to_disk: null,
+    hot_size: 1000
+  },
+  innertube: {
+    client_name: 'WEB',
+    client_version: '2.20240101.00.00',
+    user_agent: 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36',
+    gl: 'US',
+    hl: 'en'
+  },
+  proxy: {
+    url: null,
+    auth: null
   }
 };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GuiChatMessage } from "./GuiChatMessage";
+
+/**
+ * One page of archive search results
+ */
+export type ArchiveSearchPage = { 
+/**
+ * 新しい順の `offset` 件目から最大 `limit` 件
+ */
+messages: Array<GuiChatMessage>, 
+/**
+ * 一致した件数の合計（メッセージバッファとディスクアーカイブの合計）
+ */
+total: number, };
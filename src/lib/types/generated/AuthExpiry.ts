@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 認証の有効期限の状態
+ */
+export type AuthExpiry = { "state": "unknown" } | { "state": "valid" } | { "state": "expiring_soon", in_secs: number, } | { "state": "expired" };
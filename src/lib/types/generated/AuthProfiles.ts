@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 保存済みアカウントプロファイルの一覧
+ */
+export type AuthProfiles = { 
+/**
+ * 使用中のプロファイル（null はデフォルトプロファイル）
+ */
+active_profile: string | null, 
+/**
+ * 名前付きプロファイル（デフォルトプロファイルは含まない）
+ */
+profiles: Array<string>, };
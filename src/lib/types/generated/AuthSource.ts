@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Source of the credentials used for API requests
+ */
+export type AuthSource = "cookies" | "oauth";
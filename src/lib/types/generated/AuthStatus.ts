@@ -1,7 +1,17 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuthExpiry } from "./AuthExpiry";
+import type { AuthSource } from "./AuthSource";
 import type { StorageType } from "./StorageType";
 
 /**
  * Authentication status
  */
-export type AuthStatus = { is_authenticated: boolean, has_saved_credentials: boolean, storage_type: StorageType, storage_error: string | null, };
+export type AuthStatus = { is_authenticated: boolean, has_saved_credentials: boolean, storage_type: StorageType, storage_error: string | null, 
+/**
+ * API リクエストに使う認証情報の種類（cookie を優先する）
+ */
+auth_source: AuthSource | null, 
+/**
+ * 使用中の認証情報の有効期限
+ */
+expiry: AuthExpiry, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Author role derived from author badges (spec: 02_chat.md 投稿者ロール)
+ */
+export type AuthorRole = { is_owner: boolean, is_moderator: boolean, is_verified: boolean, is_member: boolean, };
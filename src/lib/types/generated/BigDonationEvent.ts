@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ParsedAmount } from "./ParsedAmount";
+
+/**
+ * 高額スーパーチャット検出イベント（`chat:big_donation`）のペイロード
+ */
+export type BigDonationEvent = { connection_id: bigint, message_id: string, author: string, channel_id: string, amount: ParsedAmount, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ポーリング停止検出イベント（`chat:capture_stalled`）のペイロード
+ */
+export type CaptureStalled = { connection_id: bigint, 
+/**
+ * 最後のポーリング成功からの経過秒数
+ */
+stalled_secs: bigint, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NotificationReason } from "./NotificationReason";
+
+/**
+ * 通知イベント（`chat:notification`）のペイロード
+ */
+export type ChatNotificationEvent = { connection_id: bigint, message_id: string, author: string, content: string, reason: NotificationReason, 
+/**
+ * 直前の通知以降にクールダウンで抑制したキーワード通知の件数
+ */
+suppressed: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConnectionHealthInfo } from "./ConnectionHealthInfo";
+
+/**
+ * 健全性の段階変化イベント（`chat:connection_health`）のペイロード
+ */
+export type ConnectionHealthEvent = { connection_id: bigint, health: ConnectionHealthInfo, };
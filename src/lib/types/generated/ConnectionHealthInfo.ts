@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HealthLevel } from "./HealthLevel";
+import type { ProcessingQueueStats } from "./ProcessingQueueStats";
+import type { RateLimitStats } from "./RateLimitStats";
+
+/**
+ * フロントエンドに公開する接続の健全性
+ */
+export type ConnectionHealthInfo = { level: HealthLevel, 
+/**
+ * 最後のポーリング成功からの経過秒数
+ */
+secs_since_success: bigint, consecutive_empty: number, avg_latency_ms: number, rate_limited: boolean, rate_limit: RateLimitStats, 
+/**
+ * 実際に使っているポーリング間隔（ミリ秒）
+ */
+poll_interval_ms: number, processing_queue: ProcessingQueueStats, };
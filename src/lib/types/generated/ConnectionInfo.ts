@@ -1,7 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConnectionHealthInfo } from "./ConnectionHealthInfo";
 import type { Platform } from "./Platform";
 
 /**
  * フロントエンドに公開する接続情報（シリアライズ可能）
  */
-export type ConnectionInfo = { id: bigint, platform: Platform, stream_url: string, stream_title: string, broadcaster_name: string, broadcaster_channel_id: string, is_monitoring: boolean, is_cancelling: boolean, };
+export type ConnectionInfo = { id: bigint, platform: Platform, stream_url: string, stream_title: string, broadcaster_name: string, broadcaster_channel_id: string, is_monitoring: boolean, is_cancelling: boolean, health: ConnectionHealthInfo, };
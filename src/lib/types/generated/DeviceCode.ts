@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 発行されたデバイスコード
+ */
+export type DeviceCode = { 
+/**
+ * トークン取得のポーリングに使うコード（ユーザーには表示しない）
+ */
+device_code: string, 
+/**
+ * ユーザーが入力するコード
+ */
+user_code: string, 
+/**
+ * コードを入力するページ
+ */
+verification_url: string, 
+/**
+ * コードの有効期間（秒）
+ */
+expires_in: number, 
+/**
+ * ポーリング間隔（秒）
+ */
+interval: number, };
@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ある時点のエンゲージメント
+ */
+export type EngagementSnapshot = { 
+/**
+ * 記録した時刻（RFC3339、UTC）
+ */
+at: string, 
+/**
+ * 集計を始めてからのユニーク投稿者数
+ */
+unique_viewers: number, 
+/**
+ * 前回のスナップショット以降のメッセージ数
+ */
+messages: number, 
+/**
+ * 前回のスナップショット以降の 1 分あたりのメッセージ数
+ */
+engagement_rate: number, 
+/**
+ * 前回のスナップショット以降のメッセージのうち絵文字を含むものの割合（0.0〜1.0）
+ */
+emoji_rate: number, 
+/**
+ * 前回のスナップショット以降の絵文字の数（カスタム絵文字を含む）
+ */
+emojis: number, 
+/**
+ * 前回のスナップショット以降の質問（「?」「？」を含むメッセージ）の数
+ */
+questions: number, };
@@ -3,4 +3,20 @@
 /**
  * Export configuration
  */
-export type ExportConfig = { format: string, include_metadata: boolean, include_system_messages: boolean, max_records: number | null, sort_order: string | null, };
+export type ExportConfig = { format: string, include_metadata: boolean, include_system_messages: boolean, max_records: number | null, sort_order: string | null, 
+/**
+ * ロール列（is_owner, membership_months）を出力に含めるか
+ */
+include_role_columns: boolean, 
+/**
+ * 出力対象の投稿者（チャンネルID）。None は絞り込みなし
+ */
+author_filter: Array<string> | null, 
+/**
+ * 出力対象のメッセージ種別（"text", "superchat" など）。None は絞り込みなし
+ */
+message_type_filter: Array<string> | null, 
+/**
+ * 配信中に削除されたメッセージも出力するか（出力には `is_deleted` 列が加わる）
+ */
+include_deleted: boolean, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 配信内の初コメントイベント（`chat:first_time_chatter`）のペイロード
+ */
+export type FirstTimeChatterEvent = { connection_id: bigint, message_id: string, channel_id: string, author: string, 
+/**
+ * 配信者チャンネルで初めてのコメントか（初見さん判定の結果）
+ */
+is_first_time_viewer: boolean, };
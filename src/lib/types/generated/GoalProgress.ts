@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 収益目標の進捗 (07_revenue.md)
+ */
+export type GoalProgress = { 
+/**
+ * 集まった金額（`currency` 建て）
+ */
+raised: number, 
+/**
+ * 目標金額（`currency` 建て）
+ */
+target: number, 
+/**
+ * 目標の通貨（ISO 4217 コード）
+ */
+currency: string, 
+/**
+ * 達成率（%、目標を超えると 100 より大きくなる）
+ */
+percent: number, 
+/**
+ * 目標までの残り（到達後は 0）
+ */
+remaining: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GoalProgress } from "./GoalProgress";
+
+/**
+ * 目標到達イベント（`chat:goal_reached`）のペイロード
+ */
+export type GoalReachedEvent = { connection_id: bigint, progress: GoalProgress, };
@@ -1,11 +1,25 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuthorRole } from "./AuthorRole";
 import type { GuiMessageMetadata } from "./GuiMessageMetadata";
 import type { MessageRun } from "./MessageRun";
+import type { ModerationAction } from "./ModerationAction";
 
 /**
  * GUI-friendly chat message
  */
-export type GuiChatMessage = { id: string, timestamp: string, timestamp_usec: string, author: string, author_icon_url: string | null, channel_id: string, content: string, runs: Array<MessageRun>, message_type: string, amount: string | null, is_member: boolean, is_first_time_viewer: boolean, in_stream_comment_count: number | null, metadata: GuiMessageMetadata | null, 
+export type GuiChatMessage = { id: string, timestamp: string, timestamp_usec: string, author: string, author_icon_url: string | null, channel_id: string, content: string, runs: Array<MessageRun>, message_type: string, amount: string | null, 
+/**
+ * `author_role.is_member` と同じ（既存の表示・フィルタ用）
+ */
+is_member: boolean, 
+/**
+ * 投稿者のロール（オーナー・モデレーター・認証済み・メンバー）
+ */
+author_role: AuthorRole, is_first_time_viewer: boolean, in_stream_comment_count: number | null, 
+/**
+ * 配信内で最初のコメントか（DB には保存しない。spec: 02_chat.md 配信内の初コメント）
+ */
+is_first_in_stream: boolean, metadata: GuiMessageMetadata | null, 
 /**
  * この接続に割り当てられた接続ID
  */
@@ -17,4 +31,8 @@ platform: string,
 /**
  * 配信者名
  */
-broadcaster_name: string, };
+broadcaster_name: string, 
+/**
+ * ローカルモデレーションで一致したルールのアクション（spec: 02_chat.md）
+ */
+moderation: Array<ModerationAction>, };
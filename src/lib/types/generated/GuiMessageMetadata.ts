@@ -1,8 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { BadgeInfo } from "./BadgeInfo";
+import type { StickerImage } from "./StickerImage";
 import type { SuperChatColors } from "./SuperChatColors";
 
 /**
  * Message metadata
  */
-export type GuiMessageMetadata = { amount: string | null, milestone_months: number | null, gift_count: number | null, badges: Array<string>, badge_info: Array<BadgeInfo>, is_moderator: boolean, is_verified: boolean, superchat_colors: SuperChatColors | null, };
+export type GuiMessageMetadata = { amount: string | null, milestone_months: number | null, gift_count: number | null, badges: Array<string>, badge_info: Array<BadgeInfo>, is_moderator: boolean, is_verified: boolean, superchat_colors: SuperChatColors | null, 
+/**
+ * SuperSticker image, if any
+ */
+sticker: StickerImage | null, };
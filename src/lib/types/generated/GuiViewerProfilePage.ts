@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GuiViewerProfile } from "./GuiViewerProfile";
+
+/**
+ * A page of viewer profiles with the total count for the broadcaster
+ */
+export type GuiViewerProfilePage = { profiles: Array<GuiViewerProfile>, 
+/**
+ * 配信者の視聴者の総数（JS number の安全整数範囲内）
+ */
+total: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 接続の健全性の段階
+ */
+export type HealthLevel = "good" | "degraded" | "stalled";
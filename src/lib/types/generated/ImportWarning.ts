@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 読み込み時にスキップしたレコード
+ */
+export type ImportWarning = { 
+/**
+ * CSV はレコードの開始行（1始まり）、JSON はメッセージの番号（1始まり）
+ */
+record: number, 
+/**
+ * スキップした理由
+ */
+message: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportWarning } from "./ImportWarning";
+import type { RevenueAnalytics } from "./RevenueAnalytics";
+
+/**
+ * エクスポートファイルから読み込んだセッションのアナリティクス
+ */
+export type ImportedSessionAnalytics = { session_id: string, stream_title: string | null, message_count: number, unique_viewers: number, analytics: RevenueAnalytics, 
+/**
+ * 読み込めずにスキップしたレコード
+ */
+warnings: Array<ImportWarning>, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 貢献者ランキングの1行（チャンネル×通貨ごとに集計）
+ */
+export type LeaderboardEntry = { rank: number, author: string, channel_id: string, total_amount: number, 
+/**
+ * ISO 4217 通貨コード。金額を解釈できなかった場合は空文字
+ */
+currency: string, message_count: number, first_contribution: string, last_contribution: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LeaderboardSortOrder } from "./LeaderboardSortOrder";
+
+/**
+ * 貢献者ランキングのエクスポート設定
+ */
+export type LeaderboardExportConfig = { format: string, sort_order: LeaderboardSortOrder, };
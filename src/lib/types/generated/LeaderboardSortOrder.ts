@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 貢献者ランキングの並び順 (07_revenue.md)
+ */
+export type LeaderboardSortOrder = "by_amount" | "by_message_count";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 配信状態
+ */
+export type LiveStatus = "upcoming" | "live" | "ended" | "not_live";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ルールの一致条件
+ */
+export type MatchSpec = { "type": "regex", pattern: string, case_insensitive: boolean, } | { "type": "keyword", keywords: Array<string>, } | { "type": "repeated_char", threshold: number, } | { "type": "caps_ratio", min_ratio: number, min_letters: number, };
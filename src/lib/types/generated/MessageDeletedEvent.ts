@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * メッセージ削除イベント（`chat:message_deleted`）のペイロード
+ */
+export type MessageDeletedEvent = { connection_id: bigint, 
+/**
+ * 削除されたメッセージの ID（表示・保存されていないものも含む）
+ */
+message_ids: Array<string>, };
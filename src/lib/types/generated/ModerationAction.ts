@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ルールに一致したときの表示上の扱い
+ */
+export type ModerationAction = "hide" | "flag" | "highlight";
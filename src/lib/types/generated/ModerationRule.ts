@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MatchSpec } from "./MatchSpec";
+import type { ModerationAction } from "./ModerationAction";
+
+/**
+ * モデレーションルール（`pattern` に一致したら `action` を適用）
+ */
+export type ModerationRule = { pattern: MatchSpec, action: ModerationAction, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 通知した理由
+ */
+export type NotificationReason = "keyword" | "super_chat" | "membership";
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * パース済みの金額
+ */
+export type ParsedAmount = { 
+/**
+ * ISO 4217 通貨コード（例: "JPY", "USD"）
+ */
+currency: string, amount: number, 
+/**
+ * 元の金額テキスト
+ */
+raw: string, };
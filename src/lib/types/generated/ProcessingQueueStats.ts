@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * キューの状態（`chat:connection_health` で公開する）
+ */
+export type ProcessingQueueStats = { 
+/**
+ * 処理待ちの件数
+ */
+queued: number, 
+/**
+ * キューの容量
+ */
+capacity: number, 
+/**
+ * 満杯のため破棄したメッセージ数（接続してからの累計）
+ */
+dropped: number, };
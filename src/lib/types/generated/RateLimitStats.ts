@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * レート制限（429）の集計
+ *
+ * ポーリング間隔が詰めすぎかどうかをユーザーが判断できるよう、接続ごとに累計する。
+ */
+export type RateLimitStats = { 
+/**
+ * 429 を受け取った回数
+ */
+hits: number, 
+/**
+ * 429 の後に待機した時間の合計（ミリ秒）
+ */
+total_backoff_ms: number, 
+/**
+ * 1 回の待機の最長時間（ミリ秒）
+ */
+longest_wait_ms: number, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 再計算の進捗
+ */
+export type RecomputeProgress = { 
+/**
+ * 集計を終えたメッセージ数
+ */
+processed: number, 
+/**
+ * メッセージの総数
+ */
+total: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EngagementSnapshot } from "./EngagementSnapshot";
+import type { RevenueAnalytics } from "./RevenueAnalytics";
+
+/**
+ * 再計算の結果
+ */
+export type RecomputedAnalytics = { revenue: RevenueAnalytics, 
+/**
+ * メッセージの投稿時刻で区切ったエンゲージメントの推移（古い順）
+ */
+engagement: Array<EngagementSnapshot>, message_count: number, };
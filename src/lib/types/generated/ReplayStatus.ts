@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 再生状態
+ */
+export type ReplayStatus = { 
+/**
+ * 再生するメッセージの総数
+ */
+total: number, 
+/**
+ * 次に再生するメッセージの位置（0 始まり）
+ */
+position: number, paused: boolean, 
+/**
+ * 最後まで再生したか、停止された
+ */
+finished: boolean, 
+/**
+ * 再生速度の倍率（0 は待機なし）
+ */
+speed: number, };
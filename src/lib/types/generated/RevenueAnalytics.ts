@@ -1,9 +1,23 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ContributorInfo } from "./ContributorInfo";
+import type { GoalProgress } from "./GoalProgress";
 import type { HourlyStats } from "./HourlyStats";
+import type { RevenueTotal } from "./RevenueTotal";
 import type { SuperChatTierStats } from "./SuperChatTierStats";
 
 /**
  * Revenue analytics data (07_revenue.md)
  */
-export type RevenueAnalytics = { super_chat_count: number, super_chat_by_tier: SuperChatTierStats, super_sticker_count: number, membership_gains: number, hourly_stats: Array<HourlyStats>, top_contributors: Array<ContributorInfo>, };
+export type RevenueAnalytics = { super_chat_count: number, super_chat_by_tier: SuperChatTierStats, super_sticker_count: number, membership_gains: number, hourly_stats: Array<HourlyStats>, 
+/**
+ * SuperChat + SuperSticker が最も多かった時間帯（`HourlyStats::hour`）
+ */
+peak_hour: string | null, top_contributors: Array<ContributorInfo>, 
+/**
+ * 基準通貨に換算した金額の合計（換算器を渡した場合のみ）
+ */
+total: RevenueTotal | null, 
+/**
+ * 収益目標の進捗（現在のセッションで目標を設定している場合のみ）
+ */
+goal: GoalProgress | null, };
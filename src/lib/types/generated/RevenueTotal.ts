@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 基準通貨に換算した SuperChat + SuperSticker の合計 (07_revenue.md)
+ */
+export type RevenueTotal = { 
+/**
+ * 合計の通貨（ISO 4217 コード）
+ */
+currency: string, amount: number, 
+/**
+ * 合計に含めた件数
+ */
+converted_count: number, 
+/**
+ * レートがなく合計に含めなかった通貨（昇順）
+ */
+unconverted_currencies: Array<string>, 
+/**
+ * 金額をパースできず合計に含めなかった件数
+ */
+unparsed_count: number, };
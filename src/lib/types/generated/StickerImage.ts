@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * SuperSticker image (spec: 02_chat.md)
+ */
+export type StickerImage = { url: string, alt_text: string, };
\ No newline at end of file
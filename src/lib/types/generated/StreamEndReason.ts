@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 配信終了と判定した根拠
+ */
+export type StreamEndReason = "continuation_missing" | "end_notice" | "live_status_ended";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StreamEndReason } from "./StreamEndReason";
+
+/**
+ * 配信終了イベント（`chat:stream_ended`）のペイロード
+ */
+export type StreamEndedEvent = { connection_id: bigint, video_id: string, reason: StreamEndReason, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LiveStatus } from "./LiveStatus";
+
+/**
+ * 配信のメタデータ
+ */
+export type VideoMetadata = { video_id: string, title: string, channel_name: string, channel_id: string, 
+/**
+ * 同時視聴者数（配信中のみ）
+ */
+concurrent_viewers: number | null, live_status: LiveStatus, };
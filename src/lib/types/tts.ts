@@ -16,6 +16,13 @@ export interface TtsConfig {
   first_comment_prefix_enabled: boolean;
   first_comment_prefix: string;
   first_comment_only: boolean;
+  preempt_normal_on_priority: boolean;
+  drain_queue_on_exit: boolean;
+  // 記号・絵文字ばかりとみなす文字・数字の割合（0 で無効）
+  min_alnum_ratio: number;
+  // 読み替え辞書 [表記, 読み]
+  reading_overrides: [string, string][];
+  apply_reading_overrides_to_author: boolean;
   // Bouyomichan settings
   bouyomichan_host: string;
   bouyomichan_port: number;
@@ -45,6 +52,17 @@ export interface TtsStatus {
   backend_name: string | null;
 }
 
+export interface VoicevoxSpeakerStyle {
+  name: string;
+  id: number;
+}
+
+export interface VoicevoxSpeaker {
+  name: string;
+  speaker_uuid: string;
+  styles: VoicevoxSpeakerStyle[];
+}
+
 export interface TtsLaunchStatus {
   bouyomichan_launched: boolean;
   voicevox_launched: boolean;
@@ -63,6 +81,11 @@ export const defaultTtsConfig: TtsConfig = {
   first_comment_prefix_enabled: false,
   first_comment_prefix: '',
   first_comment_only: false,
+  preempt_normal_on_priority: false,
+  drain_queue_on_exit: false,
+  min_alnum_ratio: 0.3,
+  reading_overrides: [],
+  apply_reading_overrides_to_author: true,
   bouyomichan_host: 'localhost',
   bouyomichan_port: 50080,
   bouyomichan_voice: 0,
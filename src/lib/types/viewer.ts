@@ -3,6 +3,8 @@
 
 // GuiViewerProfile を ViewerProfile として re-export（フロントエンドの命名慣習に合わせる）
 export type { GuiViewerProfile as ViewerProfile } from './generated/GuiViewerProfile';
+// GuiViewerProfilePage を ViewerProfilePage として re-export
+export type { GuiViewerProfilePage as ViewerProfilePage } from './generated/GuiViewerProfilePage';
 // GuiViewerWithInfo を ViewerWithCustomInfo として re-export
 export type { GuiViewerWithInfo as ViewerWithCustomInfo } from './generated/GuiViewerWithInfo';
 // GuiContributorStats を ContributorStats として re-export
@@ -10,11 +12,15 @@ export type { GuiContributorStats as ContributorStats } from './generated/GuiCon
 // GuiBroadcasterChannel を BroadcasterChannel として re-export
 export type { GuiBroadcasterChannel as BroadcasterChannel } from './generated/GuiBroadcasterChannel';
 
+// 視聴者プロフィール一覧の並び順（Rust 側の database::ProfileSort）
+export type ProfileSort = 'total_contribution' | 'message_count' | 'last_seen';
+
 // Session（DBから取得、フロントエンド固有定義）
 export interface Session {
   id: string;
   start_time: string;
   end_time: string | null;
+  video_id: string | null;
   stream_url: string | null;
   stream_title: string | null;
   broadcaster_name: string | null;
@@ -0,0 +1,48 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { invoke } from '@tauri-apps/api/core';
+import { clearAuthorIconCache, peekAuthorIcon, resolveAuthorIcon } from './author-icon';
+
+const mockInvoke = vi.mocked(invoke);
+
+describe('resolveAuthorIcon', () => {
+  beforeEach(async () => {
+    mockInvoke.mockReset();
+    mockInvoke.mockResolvedValue(undefined);
+    await clearAuthorIconCache();
+    mockInvoke.mockReset();
+  });
+
+  it('同じURLはバックエンドに1回だけ問い合わせる', async () => {
+    mockInvoke.mockResolvedValue('data:image/png;base64,AA==');
+    const url = 'https://yt3.ggpht.com/a';
+
+    const [first, second] = await Promise.all([resolveAuthorIcon(url), resolveAuthorIcon(url)]);
+    const third = await resolveAuthorIcon(url);
+
+    expect(first).toBe('data:image/png;base64,AA==');
+    expect(second).toBe(first);
+    expect(third).toBe(first);
+    expect(peekAuthorIcon(url)).toBe(first);
+    expect(mockInvoke).toHaveBeenCalledTimes(1);
+    expect(mockInvoke).toHaveBeenCalledWith('get_author_icon', { url });
+  });
+
+  it('取得に失敗した場合は元のURLを使う', async () => {
+    mockInvoke.mockRejectedValue({ type: 'ApiError', message: 'HTTP 404' });
+    const url = 'https://yt3.ggpht.com/missing';
+
+    expect(await resolveAuthorIcon(url)).toBe(url);
+    expect(peekAuthorIcon(url)).toBe(url);
+  });
+
+  it('キャッシュ破棄でバックエンドのキャッシュも破棄する', async () => {
+    mockInvoke.mockResolvedValue('data:image/png;base64,AA==');
+    await resolveAuthorIcon('https://yt3.ggpht.com/a');
+
+    mockInvoke.mockResolvedValue(undefined);
+    await clearAuthorIconCache();
+
+    expect(peekAuthorIcon('https://yt3.ggpht.com/a')).toBeUndefined();
+    expect(mockInvoke).toHaveBeenLastCalledWith('clear_icon_cache');
+  });
+});
@@ -0,0 +1,50 @@
+/**
+ * 投稿者アイコンの解決（spec: 02_chat.md 投稿者アイコンのキャッシュ）
+ * バックエンドのキャッシュから data URL を取得し、同じ URL の再取得を避ける
+ */
+
+import { clearIconCache, getAuthorIcon } from '$lib/tauri/icon';
+
+/** フロントエンド側で保持する最大件数（超えたら古いものから捨てる） */
+const MAX_RESOLVED_ICONS = 500;
+
+const resolved = new Map<string, string>();
+const pending = new Map<string, Promise<string>>();
+
+/** 解決済みならアイコンの src を返す */
+export function peekAuthorIcon(url: string): string | undefined {
+  return resolved.get(url);
+}
+
+/**
+ * アイコンの src を解決する
+ * 取得に失敗した場合は元の URL をそのまま使う（再試行はしない）
+ */
+export function resolveAuthorIcon(url: string): Promise<string> {
+  const cached = resolved.get(url);
+  if (cached !== undefined) {
+    return Promise.resolve(cached);
+  }
+  let request = pending.get(url);
+  if (!request) {
+    request = getAuthorIcon(url)
+      .catch(() => url)
+      .then((src) => {
+        pending.delete(url);
+        if (resolved.size >= MAX_RESOLVED_ICONS) {
+          const oldest = resolved.keys().next().value;
+          if (oldest !== undefined) resolved.delete(oldest);
+        }
+        resolved.set(url, src);
+        return src;
+      });
+    pending.set(url, request);
+  }
+  return request;
+}
+
+/** フロントエンドとバックエンドのアイコンキャッシュを破棄する */
+export async function clearAuthorIconCache(): Promise<void> {
+  resolved.clear();
+  await clearIconCache();
+}
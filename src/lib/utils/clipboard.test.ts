@@ -0,0 +1,35 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { channelUrl, copyText } from './clipboard';
+
+describe('channelUrl', () => {
+  it('チャンネルIDからチャンネルのURLを作る', () => {
+    expect(channelUrl('UC_test')).toBe('https://www.youtube.com/channel/UC_test');
+  });
+});
+
+describe('copyText', () => {
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  it('Clipboard API に書き込む', async () => {
+    const writeText = vi.fn().mockResolvedValue(undefined);
+    vi.stubGlobal('navigator', { clipboard: { writeText } });
+
+    await expect(copyText('こんにちは')).resolves.toBe(true);
+    expect(writeText).toHaveBeenCalledWith('こんにちは');
+  });
+
+  it('書き込みが拒否されたら false を返す', async () => {
+    const writeText = vi.fn().mockRejectedValue(new Error('denied'));
+    vi.stubGlobal('navigator', { clipboard: { writeText } });
+
+    await expect(copyText('x')).resolves.toBe(false);
+  });
+
+  it('Clipboard API がなければ false を返す', async () => {
+    vi.stubGlobal('navigator', {});
+
+    await expect(copyText('x')).resolves.toBe(false);
+  });
+});
@@ -0,0 +1,20 @@
+/**
+ * クリップボードへのコピー
+ * WebView の Clipboard API を使うため、追加のプラグインや権限は不要
+ */
+
+/** 投稿者のチャンネル URL */
+export function channelUrl(channelId: string): string {
+  return `https://www.youtube.com/channel/${channelId}`;
+}
+
+/** テキストをクリップボードにコピーする（失敗したら false） */
+export async function copyText(text: string): Promise<boolean> {
+  if (!navigator.clipboard) return false;
+  try {
+    await navigator.clipboard.writeText(text);
+    return true;
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,30 @@
+import { describe, it, expect } from 'vitest';
+import { compileContentRegex } from './content-regex';
+
+describe('compileContentRegex', () => {
+  it('空文字はフィルタなしとして扱う', () => {
+    expect(compileContentRegex('', false)).toEqual({ ok: true, regex: null });
+  });
+
+  it('有効なパターンをコンパイルする', () => {
+    const result = compileContentRegex('fr[e3]{2}\\s*v[i1]d', false);
+    expect(result.ok).toBe(true);
+    if (!result.ok) return;
+    expect(result.regex?.test('fr33 v1d here')).toBe(true);
+    expect(result.regex?.test('hello')).toBe(false);
+  });
+
+  it('不正なパターンはエラーを返す', () => {
+    const result = compileContentRegex('(unclosed', false);
+    expect(result.ok).toBe(false);
+    if (result.ok) return;
+    expect(result.error).not.toBe('');
+  });
+
+  it('caseInsensitive で大文字小文字を区別しない', () => {
+    const sensitive = compileContentRegex('spam', false);
+    const insensitive = compileContentRegex('spam', true);
+    expect(sensitive.ok && sensitive.regex?.test('SPAM')).toBe(false);
+    expect(insensitive.ok && insensitive.regex?.test('SPAM')).toBe(true);
+  });
+});
@@ -0,0 +1,23 @@
+/**
+ * メッセージ本文の正規表現フィルタ
+ * パターンはフィルタ変更時に一度だけコンパイルし、メッセージごとに再コンパイルしない
+ */
+
+export type ContentRegexResult =
+  | { ok: true; regex: RegExp | null }
+  | { ok: false; error: string };
+
+/**
+ * パターンをコンパイルする。空文字は「フィルタなし」として `regex: null` を返す
+ * 不正なパターンは例外にせず `ok: false` とエラーメッセージを返す（GUI で入力欄を赤枠表示する）
+ */
+export function compileContentRegex(pattern: string, caseInsensitive: boolean): ContentRegexResult {
+  if (pattern === '') {
+    return { ok: true, regex: null };
+  }
+  try {
+    return { ok: true, regex: new RegExp(pattern, caseInsensitive ? 'iu' : 'u') };
+  } catch (e) {
+    return { ok: false, error: e instanceof Error ? e.message : String(e) };
+  }
+}
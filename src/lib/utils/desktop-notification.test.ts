@@ -0,0 +1,27 @@
+import { describe, expect, it } from 'vitest';
+import type { ChatNotificationEvent } from '$lib/types';
+import { notificationTitle } from './desktop-notification';
+
+function event(overrides: Partial<ChatNotificationEvent> = {}): ChatNotificationEvent {
+  return {
+    connection_id: BigInt(1),
+    message_id: 'msg_1',
+    author: 'Alice',
+    content: 'liscov さんこんにちは',
+    reason: 'keyword',
+    suppressed: 0,
+    ...overrides
+  };
+}
+
+describe('notificationTitle', () => {
+  it('理由ごとにタイトルを変える', () => {
+    expect(notificationTitle(event())).toBe('Alice さんがキーワードに言及');
+    expect(notificationTitle(event({ reason: 'super_chat' }))).toBe('Alice さんのスーパーチャット');
+    expect(notificationTitle(event({ reason: 'membership' }))).toBe('Alice さんのメンバーシップ');
+  });
+
+  it('クールダウンで抑制した件数を添える', () => {
+    expect(notificationTitle(event({ suppressed: 3 }))).toBe('Alice さんがキーワードに言及（他3件）');
+  });
+});
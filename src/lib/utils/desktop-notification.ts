@@ -0,0 +1,56 @@
+/**
+ * キーワード通知の表示（spec: 02_chat.md キーワード通知）
+ * WebView の Notification API でデスクトップ通知を出し、設定した通知音を鳴らす
+ */
+
+import type { ChatNotificationEvent } from '$lib/types';
+import { notificationSound } from '$lib/tauri/notifications';
+
+/** 通知のタイトル */
+export function notificationTitle(event: ChatNotificationEvent): string {
+  const suffix = event.suppressed > 0 ? `（他${event.suppressed}件）` : '';
+  switch (event.reason) {
+    case 'super_chat':
+      return `${event.author} さんのスーパーチャット`;
+    case 'membership':
+      return `${event.author} さんのメンバーシップ`;
+    default:
+      return `${event.author} さんがキーワードに言及${suffix}`;
+  }
+}
+
+// 通知音は設定したパスが変わるまで再取得しない
+let cachedSound: { path: string; url: string | null } | null = null;
+
+async function resolveSound(path: string): Promise<string | null> {
+  if (cachedSound?.path !== path) {
+    cachedSound = { path, url: await notificationSound() };
+  }
+  return cachedSound.url;
+}
+
+/**
+ * 通知を表示する
+ * 通知の許可がなければ最初の通知時に要求し、拒否された場合は通知音だけ鳴らす
+ */
+export async function showChatNotification(
+  event: ChatNotificationEvent,
+  soundPath: string | null
+): Promise<void> {
+  if (typeof Notification !== 'undefined') {
+    if (Notification.permission === 'default') {
+      await Notification.requestPermission();
+    }
+    if (Notification.permission === 'granted') {
+      new Notification(notificationTitle(event), { body: event.content, tag: event.message_id });
+    }
+  }
+
+  if (!soundPath) return;
+  try {
+    const url = await resolveSound(soundPath);
+    if (url) await new Audio(url).play();
+  } catch (e) {
+    console.warn('通知音の再生に失敗しました:', e);
+  }
+}
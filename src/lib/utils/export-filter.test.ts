@@ -0,0 +1,70 @@
+import { describe, it, expect } from 'vitest';
+import type { ChatFilter } from '$lib/types';
+import { exportFiltersFromChatFilter, isEmptyExportFilter } from './export-filter';
+
+const defaultFilter: ChatFilter = {
+  showText: true,
+  showSuperchat: true,
+  showMembership: true,
+  searchQuery: '',
+  normalizeWidth: true,
+  blockedChannelIds: [],
+  allowedChannelIds: null,
+  contentRegex: '',
+  caseInsensitive: false,
+  mentionsOnly: false
+};
+
+describe('exportFiltersFromChatFilter', () => {
+  it('デフォルトのフィルタでは絞り込みなし', () => {
+    expect(exportFiltersFromChatFilter(defaultFilter, ['UC_a'])).toEqual({
+      author_filter: null,
+      message_type_filter: null
+    });
+  });
+
+  it('非表示の種別を除き、システムメッセージは残す', () => {
+    const filters = exportFiltersFromChatFilter(
+      { ...defaultFilter, showText: false, showMembership: false },
+      []
+    );
+    expect(filters.message_type_filter).toEqual(['superchat', 'supersticker', 'system']);
+  });
+
+  it('許可リストからブロック中の投稿者を除く', () => {
+    const filters = exportFiltersFromChatFilter(
+      { ...defaultFilter, allowedChannelIds: ['UC_a', 'UC_b'], blockedChannelIds: ['UC_b'] },
+      ['UC_c']
+    );
+    expect(filters.author_filter).toEqual(['UC_a']);
+  });
+
+  it('ブロックのみ指定時は現在のメッセージの投稿者からブロック中を除く', () => {
+    const filters = exportFiltersFromChatFilter(
+      { ...defaultFilter, blockedChannelIds: ['UC_b'] },
+      ['UC_a', 'UC_b', 'UC_a', 'UC_c']
+    );
+    expect(filters.author_filter).toEqual(['UC_a', 'UC_c']);
+  });
+
+  it('一致する投稿者がいなければ空配列を返す', () => {
+    const filters = exportFiltersFromChatFilter(
+      { ...defaultFilter, blockedChannelIds: ['UC_a'] },
+      ['UC_a']
+    );
+    expect(filters.author_filter).toEqual([]);
+    expect(isEmptyExportFilter(filters)).toBe(true);
+  });
+});
+
+describe('isEmptyExportFilter', () => {
+  it('null（絞り込みなし）は空ではない', () => {
+    expect(isEmptyExportFilter({ author_filter: null, message_type_filter: null })).toBe(false);
+  });
+
+  it('空でない配列は空ではない', () => {
+    expect(isEmptyExportFilter({ author_filter: ['UC_a'], message_type_filter: ['text'] })).toBe(
+      false
+    );
+  });
+});
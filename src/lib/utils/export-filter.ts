@@ -0,0 +1,54 @@
+/**
+ * チャット欄のフィルタをエクスポートの絞り込み（author_filter / message_type_filter）に変換する
+ * 検索クエリ・本文の正規表現・ローカルモデレーションの非表示は変換できないため対象外
+ */
+
+import type { ChatFilter, ExportConfig } from '$lib/types';
+
+export type ExportFilters = Pick<ExportConfig, 'author_filter' | 'message_type_filter'>;
+
+/** エクスポートで指定できる全メッセージ種別 */
+const ALL_MESSAGE_TYPES = [
+  'text',
+  'superchat',
+  'supersticker',
+  'membership',
+  'membership_gift',
+  'system'
+] as const;
+
+/**
+ * チャット欄のフィルタに対応するエクスポートの絞り込みを返す
+ *
+ * @param channelIds ブロックのみ指定されているときに、許可する投稿者の候補とする現在のメッセージのチャンネルID
+ * @returns 絞り込みなしは null。一致する投稿者がいなければ author_filter は空配列
+ */
+export function exportFiltersFromChatFilter(
+  filter: ChatFilter,
+  channelIds: Iterable<string>
+): ExportFilters {
+  const types = ALL_MESSAGE_TYPES.filter((type) => {
+    if (type === 'text') return filter.showText;
+    if (type === 'superchat' || type === 'supersticker') return filter.showSuperchat;
+    if (type === 'membership' || type === 'membership_gift') return filter.showMembership;
+    return true;
+  });
+
+  const blocked = new Set(filter.blockedChannelIds);
+  let authors: string[] | null = null;
+  if (filter.allowedChannelIds !== null) {
+    authors = filter.allowedChannelIds.filter((id) => !blocked.has(id));
+  } else if (blocked.size > 0) {
+    authors = [...new Set(channelIds)].filter((id) => !blocked.has(id));
+  }
+
+  return {
+    author_filter: authors,
+    message_type_filter: types.length === ALL_MESSAGE_TYPES.length ? null : [...types]
+  };
+}
+
+/** 絞り込みに一致するメッセージが存在し得ないか（空配列はバックエンドでエラーになるため送らない） */
+export function isEmptyExportFilter(filters: ExportFilters): boolean {
+  return filters.author_filter?.length === 0 || filters.message_type_filter?.length === 0;
+}
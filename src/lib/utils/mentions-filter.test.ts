@@ -0,0 +1,69 @@
+import { describe, expect, it } from 'vitest';
+import type { ChatMessage } from '$lib/types';
+import { matchesMentions, presetMentions } from './mentions-filter';
+
+function message(id: string, overrides: Partial<ChatMessage> = {}): ChatMessage {
+  return {
+    id,
+    timestamp: '2026-01-27T11:36:06+09:00',
+    timestamp_usec: '1769481366000000',
+    author: 'Viewer',
+    author_icon_url: null,
+    channel_id: 'UC_viewer',
+    content: '',
+    runs: [],
+    message_type: 'text',
+    amount: null,
+    is_member: false,
+    author_role: { is_owner: false, is_moderator: false, is_verified: false, is_member: false },
+    is_first_time_viewer: false,
+    in_stream_comment_count: null,
+    is_first_in_stream: false,
+    metadata: null,
+    connection_id: BigInt(1),
+    platform: 'youtube',
+    broadcaster_name: 'りすこ',
+    moderation: [],
+    ...overrides
+  };
+}
+
+describe('presetMentions', () => {
+  const preset = presetMentions([{ channelId: 'UC_owner', name: 'りすこ' }], ['@liscov_ch', 'リスコフ', ' ']);
+
+  it('キーワードと配信者へのメンションを正規化して照合語にする', () => {
+    expect(preset.terms).toEqual(['@liscov_ch', 'リスコフ', '@りすこ']);
+    expect([...preset.channelIds]).toEqual(['UC_owner']);
+  });
+
+  it('ハンドル・呼び名・メンション・配信者の投稿に一致する', () => {
+    const messages = [
+      message('handle', { content: 'こんばんは ＠LISCOV_CH さん' }),
+      message('keyword', { content: 'ﾘｽｺﾌさん今日も元気？' }),
+      message('mention', { content: '@りすこ おつかれさま' }),
+      message('owner', { channel_id: 'UC_owner', content: '次は歌枠です' }),
+      message('owner_badge', {
+        channel_id: 'UC_other_owner',
+        content: '固定コメント',
+        author_role: { is_owner: true, is_moderator: false, is_verified: false, is_member: false }
+      }),
+      message('name_only', { content: 'りすこかわいい' }),
+      message('unrelated', { content: '草' }),
+      message('system', { channel_id: 'UC_owner', content: '@りすこ', message_type: 'system' })
+    ];
+    expect(messages.filter((m) => matchesMentions(preset, m)).map((m) => m.id)).toEqual([
+      'handle',
+      'keyword',
+      'mention',
+      'owner',
+      'owner_badge'
+    ]);
+  });
+
+  it('条件がなければ配信者の投稿以外は一致しない', () => {
+    const empty = presetMentions([{ channelId: '', name: '' }], []);
+    expect(empty.terms).toEqual([]);
+    expect(empty.channelIds.size).toBe(0);
+    expect(matchesMentions(empty, message('text', { content: '@' }))).toBe(false);
+  });
+});
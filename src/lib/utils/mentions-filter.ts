@@ -0,0 +1,41 @@
+/**
+ * 「自分宛て」フィルタのプリセット (spec: 02_chat.md 自分宛てのメッセージ)
+ * 配信者のハンドル・呼び名を含むメッセージ、配信者へのメンション、配信者自身の投稿を一度に絞り込む
+ */
+
+import type { ChatMessage } from '$lib/types';
+import { compileRules, matchesRules, type MessageRules } from './message-rules';
+
+/** 接続先の配信者（接続時に取得したメタデータのチャンネルID・チャンネル名） */
+export interface MentionOwner {
+  channelId: string;
+  name: string;
+}
+
+/** 照合用に組み立てたプリセット（`channelIds` は配信者のチャンネルID） */
+export type MentionsPreset = MessageRules;
+
+/**
+ * 配信者とキーワードから「自分宛て」のプリセットを作る
+ * キーワードに加え、各配信者の `@チャンネル名`（チャット欄でのメンション）を照合する
+ */
+export function presetMentions(owners: MentionOwner[], keywords: string[]): MentionsPreset {
+  const mentions = owners.filter((o) => o.name.trim()).map((o) => `@${o.name.trim()}`);
+  return compileRules(
+    owners.map((o) => o.channelId),
+    [...keywords, ...mentions]
+  );
+}
+
+/**
+ * メッセージがプリセットに一致するか（システムメッセージは対象外）
+ * 配信者のバッジ（`author_role.is_owner`）が付いた投稿も一致する
+ */
+export function matchesMentions(
+  preset: MentionsPreset,
+  msg: ChatMessage,
+  normalizedContent?: string
+): boolean {
+  if (msg.message_type !== 'system' && msg.author_role.is_owner) return true;
+  return matchesRules(preset, msg, normalizedContent);
+}
@@ -0,0 +1,36 @@
+import { describe, it, expect } from 'vitest';
+import { compareMessageOrder, messageKey } from './message-key';
+
+describe('messageKey', () => {
+  it('接続IDとメッセージIDを組み合わせる', () => {
+    expect(messageKey({ connection_id: BigInt(3), id: 'abc' })).toBe('3:abc');
+  });
+
+  it('同じメッセージIDでも接続が異なれば別のキーになる', () => {
+    const first = messageKey({ connection_id: BigInt(1), id: 'same' });
+    const second = messageKey({ connection_id: BigInt(2), id: 'same' });
+    expect(first).not.toBe(second);
+  });
+
+  it('同じメッセージからは常に同じキーを返す', () => {
+    const msg = { connection_id: BigInt(1), id: 'stable' };
+    expect(messageKey(msg)).toBe(messageKey({ ...msg }));
+  });
+});
+
+describe('compareMessageOrder', () => {
+  it('timestamp_usec を数値として比較する', () => {
+    const earlier = { timestamp_usec: '999999', id: 'z' };
+    const later = { timestamp_usec: '1000000', id: 'a' };
+    expect(compareMessageOrder(earlier, later)).toBeLessThan(0);
+    expect(compareMessageOrder(later, earlier)).toBeGreaterThan(0);
+  });
+
+  it('同じ timestamp_usec ならメッセージIDで順序を決める', () => {
+    const a = { timestamp_usec: '1000000', id: 'a' };
+    const b = { timestamp_usec: '1000000', id: 'b' };
+    expect(compareMessageOrder(a, b)).toBeLessThan(0);
+    expect(compareMessageOrder(b, a)).toBeGreaterThan(0);
+    expect(compareMessageOrder(a, { ...a })).toBe(0);
+  });
+});
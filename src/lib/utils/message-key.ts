@@ -0,0 +1,27 @@
+/**
+ * チャットメッセージの識別キー
+ * 同じ配信に複数接続するとメッセージIDが重複するため、接続IDと組み合わせる
+ */
+
+import type { ChatMessage } from '$lib/types';
+
+/** 接続ID とメッセージID の複合キー（重複排除・仮想スクロールのキーに使う） */
+export function messageKey(msg: Pick<ChatMessage, 'connection_id' | 'id'>): string {
+  return `${msg.connection_id}:${msg.id}`;
+}
+
+/**
+ * メッセージの表示順（timestamp_usec → メッセージID の全順序）
+ * timestamp_usec は非負の整数文字列なので、桁数 → 文字列の順で比較すれば数値順になる
+ */
+export function compareMessageOrder(
+  a: Pick<ChatMessage, 'timestamp_usec' | 'id'>,
+  b: Pick<ChatMessage, 'timestamp_usec' | 'id'>,
+): number {
+  if (a.timestamp_usec.length !== b.timestamp_usec.length) {
+    return a.timestamp_usec.length - b.timestamp_usec.length;
+  }
+  if (a.timestamp_usec !== b.timestamp_usec) return a.timestamp_usec < b.timestamp_usec ? -1 : 1;
+  if (a.id !== b.id) return a.id < b.id ? -1 : 1;
+  return 0;
+}
@@ -0,0 +1,61 @@
+import { describe, expect, it } from 'vitest';
+import type { ChatMessage } from '$lib/types';
+import { compileRules, matchesRules } from './message-rules';
+
+function message(id: string, overrides: Partial<ChatMessage> = {}): ChatMessage {
+  return {
+    id,
+    timestamp: '2026-01-27T11:36:06+09:00',
+    timestamp_usec: '1769481366000000',
+    author: 'Viewer',
+    author_icon_url: null,
+    channel_id: 'UC_viewer',
+    content: '',
+    runs: [],
+    message_type: 'text',
+    amount: null,
+    is_member: false,
+    author_role: { is_owner: false, is_moderator: false, is_verified: false, is_member: false },
+    is_first_time_viewer: false,
+    in_stream_comment_count: null,
+    is_first_in_stream: false,
+    metadata: null,
+    connection_id: BigInt(1),
+    platform: 'youtube',
+    broadcaster_name: 'りすこ',
+    moderation: [],
+    ...overrides
+  };
+}
+
+describe('compileRules', () => {
+  it('語を正規化し、空のIDと空白だけの語・重複を除く', () => {
+    const rules = compileRules(['UC_a', '', 'UC_a'], ['ＡＢＣ', 'abc', ' ', 'ﾘｽｺﾌ']);
+    expect([...rules.channelIds]).toEqual(['UC_a']);
+    expect(rules.terms).toEqual(['abc', 'リスコフ']);
+  });
+});
+
+describe('matchesRules', () => {
+  const rules = compileRules(['UC_watched'], ['abc']);
+
+  it('投稿者か本文の語に一致し、システムメッセージは対象外', () => {
+    const messages = [
+      message('channel', { channel_id: 'UC_watched', content: '草' }),
+      message('term', { content: 'ＡＢＣ だ' }),
+      message('unrelated', { content: '草' }),
+      message('system', { channel_id: 'UC_watched', content: 'abc', message_type: 'system' })
+    ];
+    expect(messages.filter((m) => matchesRules(rules, m)).map((m) => m.id)).toEqual(['channel', 'term']);
+  });
+
+  it('正規化済みの本文を渡すとそれと照合する', () => {
+    const msg = message('cached', { content: 'ＡＢＣ' });
+    expect(matchesRules(rules, msg, 'abc')).toBe(true);
+    expect(matchesRules(rules, msg, 'xyz')).toBe(false);
+  });
+
+  it('空のルールは何にも一致しない', () => {
+    expect(matchesRules(compileRules([], []), message('text', { content: 'abc' }))).toBe(false);
+  });
+});
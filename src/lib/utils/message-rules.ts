@@ -0,0 +1,38 @@
+/**
+ * 投稿者・キーワードのルールとメッセージの照合
+ * 注目ワードの強調表示と「自分宛て」フィルタで共通に使う (spec: 02_chat.md)
+ */
+
+import type { ChatMessage } from '$lib/types';
+import { normalizeForSearch } from './text-normalize';
+
+/** 照合用に組み立てたルール（条件の変更時のみ作り直す） */
+export interface MessageRules {
+  /** この投稿者（チャンネルID）のメッセージは常に一致する */
+  channelIds: Set<string>;
+  /** 本文と照合する語（NFKC 正規化 + 小文字化済み） */
+  terms: string[];
+}
+
+/** チャンネルIDと語からルールを作る（空のIDと空白だけの語は除く） */
+export function compileRules(channelIds: Iterable<string>, terms: Iterable<string>): MessageRules {
+  const normalized = [...terms].map(normalizeForSearch).filter((t) => t.trim().length > 0);
+  return {
+    channelIds: new Set([...channelIds].filter((id) => id)),
+    terms: [...new Set(normalized)]
+  };
+}
+
+/**
+ * メッセージがルールに一致するか（システムメッセージは対象外）
+ * `normalizedContent` は検索フィルタのインデックスにある正規化済みの本文を渡す
+ */
+export function matchesRules(
+  rules: MessageRules,
+  msg: ChatMessage,
+  normalizedContent: string = normalizeForSearch(msg.content)
+): boolean {
+  if (msg.message_type === 'system') return false;
+  if (rules.channelIds.has(msg.channel_id)) return true;
+  return rules.terms.some((t) => normalizedContent.includes(t));
+}
@@ -0,0 +1,20 @@
+import { describe, it, expect } from 'vitest';
+import { normalizeForSearch } from './text-normalize';
+
+describe('normalizeForSearch', () => {
+  it('全角英数字を半角に変換する', () => {
+    expect(normalizeForSearch('ＡＢＣ１２３')).toBe('abc123');
+  });
+
+  it('全角の草（ｗｗｗ）を半角と同一視する', () => {
+    expect(normalizeForSearch('ｗｗｗ')).toBe(normalizeForSearch('www'));
+  });
+
+  it('半角カタカナを全角カタカナに変換する', () => {
+    expect(normalizeForSearch('ｶﾀｶﾅ')).toBe('カタカナ');
+  });
+
+  it('ひらがな・漢字はそのまま保持する', () => {
+    expect(normalizeForSearch('こんにちは世界')).toBe('こんにちは世界');
+  });
+});
@@ -0,0 +1,9 @@
+/**
+ * 検索・フィルタ用のテキスト正規化
+ * 全角/半角の英数字・記号・カタカナ（ＡＢＣ / ABC、ｗｗｗ / www、ｶﾀｶﾅ / カタカナ）を同一視する
+ */
+
+/** 全角/半角の違いを吸収し、大文字小文字を区別しない比較用文字列を返す（NFKC + 小文字化） */
+export function normalizeForSearch(text: string): string {
+  return text.normalize('NFKC').toLowerCase();
+}